@@ -0,0 +1,214 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use clap::Parser;
+use fastcrypto::class_group::QuadraticForm;
+use fastcrypto::vdf::{generate_discriminant, VdfCalibration, VdfParameters, VdfProof};
+use rand::thread_rng;
+use std::io::{Error, ErrorKind};
+use std::time::Duration;
+
+#[derive(Parser)]
+#[command(name = "vdf-cli")]
+#[command(about = "Verifiable delay function (VDF) over class groups with Wesolowski proofs.", long_about = None)]
+enum Command {
+    /// Measure the squaring speed of this host and create parameters for a target delay.
+    Calibrate(CalibrateArguments),
+
+    /// Evaluate the VDF on the default input and create a proof.
+    Evaluate(EvaluateArguments),
+
+    /// Verify an output and a proof.
+    Verify(VerifyArguments),
+}
+
+#[derive(Parser, Clone)]
+struct CalibrateArguments {
+    /// The size of the random discriminant in bits.
+    #[clap(short, long, default_value_t = 1024)]
+    discriminant_bits: u64,
+
+    /// The target delay in seconds.
+    #[clap(short, long)]
+    target_delay: f64,
+
+    /// How long to measure the squaring speed in seconds.
+    #[clap(short, long, default_value_t = 1.0)]
+    measurement_time: f64,
+}
+
+#[derive(Parser, Clone)]
+struct EvaluateArguments {
+    /// Hex encoding of the parameters as output by the calibrate command.
+    #[clap(short, long)]
+    parameters: String,
+}
+
+#[derive(Parser, Clone)]
+struct VerifyArguments {
+    /// Hex encoding of the parameters as output by the calibrate command.
+    #[clap(short, long)]
+    parameters: String,
+
+    /// Hex encoding of the output.
+    #[clap(short, long)]
+    output: String,
+
+    /// Hex encoding of the proof.
+    #[clap(short = 'r', long)]
+    proof: String,
+}
+
+fn main() {
+    match execute(Command::parse()) {
+        Ok(res) => {
+            println!("{}", res);
+            std::process::exit(exitcode::OK);
+        }
+        Err(e) => {
+            println!("Error: {}", e);
+            std::process::exit(exitcode::DATAERR);
+        }
+    }
+}
+
+fn duration_from_seconds(seconds: f64) -> Result<Duration, Error> {
+    if !seconds.is_finite() || seconds < 0.0 {
+        return Err(Error::new(ErrorKind::InvalidInput, "Invalid duration."));
+    }
+    Ok(Duration::from_secs_f64(seconds))
+}
+
+fn parse_parameters(parameters: &str) -> Result<VdfParameters, Error> {
+    let bytes = hex::decode(parameters)
+        .map_err(|_| Error::new(ErrorKind::InvalidInput, "Invalid parameters."))?;
+    bincode::deserialize::<VdfParameters>(&bytes)
+        .map_err(|_| Error::new(ErrorKind::InvalidInput, "Failed to parse parameters."))
+}
+
+fn execute(cmd: Command) -> Result<String, Error> {
+    match cmd {
+        Command::Calibrate(arguments) => {
+            let target_delay = duration_from_seconds(arguments.target_delay)?;
+            let measurement_time = duration_from_seconds(arguments.measurement_time)?;
+            let discriminant =
+                generate_discriminant(arguments.discriminant_bits, &mut thread_rng()).map_err(
+                    |_| Error::new(ErrorKind::InvalidInput, "Invalid discriminant size."),
+                )?;
+            let calibration = VdfCalibration::measure(&discriminant, measurement_time)
+                .map_err(|_| Error::new(ErrorKind::Other, "Failed to measure squaring speed."))?;
+            let parameters = calibration
+                .parameters(&discriminant, target_delay)
+                .map_err(|_| Error::new(ErrorKind::Other, "Failed to create parameters."))?;
+            let parameters_string =
+                hex::encode(bincode::serialize(&parameters).map_err(|_| {
+                    Error::new(ErrorKind::Other, "Failed to serialize parameters.")
+                })?);
+
+            let mut result = "Squarings per second: ".to_string();
+            result.push_str(&format!("{:.0}", calibration.squarings_per_second()));
+            result.push_str("\nIterations: ");
+            result.push_str(&parameters.iterations().to_string());
+            result.push_str("\nParameters: ");
+            result.push_str(&parameters_string);
+            Ok(result)
+        }
+
+        Command::Evaluate(arguments) => {
+            let parameters = parse_parameters(&arguments.parameters)?;
+            let (output, proof) = parameters
+                .evaluate(&parameters.default_input())
+                .map_err(|_| Error::new(ErrorKind::Other, "Failed to evaluate VDF."))?;
+
+            let mut result = "Output: ".to_string();
+            result.push_str(&hex::encode(bincode::serialize(&output).unwrap()));
+            result.push_str("\nProof:  ");
+            result.push_str(&hex::encode(bincode::serialize(&proof).unwrap()));
+            Ok(result)
+        }
+
+        Command::Verify(arguments) => {
+            let parameters = parse_parameters(&arguments.parameters)?;
+            let output_bytes = hex::decode(arguments.output)
+                .map_err(|_| Error::new(ErrorKind::InvalidInput, "Invalid output string."))?;
+            let proof_bytes = hex::decode(arguments.proof)
+                .map_err(|_| Error::new(ErrorKind::InvalidInput, "Invalid proof string."))?;
+            let output = bincode::deserialize::<QuadraticForm>(&output_bytes)
+                .map_err(|_| Error::new(ErrorKind::InvalidInput, "Unable to parse output."))?;
+            let proof = bincode::deserialize::<VdfProof>(&proof_bytes)
+                .map_err(|_| Error::new(ErrorKind::InvalidInput, "Unable to parse proof."))?;
+
+            if parameters
+                .verify(&parameters.default_input(), &output, &proof)
+                .is_ok()
+            {
+                return Ok("Proof verified correctly!".to_string());
+            }
+            Err(Error::new(ErrorKind::Other, "Proof is not correct."))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{execute, CalibrateArguments, Command, EvaluateArguments, VerifyArguments};
+    use regex::Regex;
+
+    #[test]
+    fn test_calibrate_evaluate_and_verify() {
+        let result = execute(Command::Calibrate(CalibrateArguments {
+            discriminant_bits: 256,
+            target_delay: 0.01,
+            measurement_time: 0.01,
+        }))
+        .unwrap();
+        let expected =
+            Regex::new(r"Squarings per second: \d+\nIterations: \d+\nParameters: ([0-9a-f]+)")
+                .unwrap();
+        let parameters = expected.captures(&result).unwrap()[1].to_string();
+
+        let result = execute(Command::Evaluate(EvaluateArguments {
+            parameters: parameters.clone(),
+        }))
+        .unwrap();
+        let expected = Regex::new(r"Output: ([0-9a-f]+)\nProof:  ([0-9a-f]+)").unwrap();
+        let captures = expected.captures(&result).unwrap();
+        let (output, proof) = (captures[1].to_string(), captures[2].to_string());
+
+        let result = execute(Command::Verify(VerifyArguments {
+            parameters: parameters.clone(),
+            output: output.clone(),
+            proof: proof.clone(),
+        }))
+        .unwrap();
+        assert_eq!("Proof verified correctly!", result);
+
+        assert!(execute(Command::Verify(VerifyArguments {
+            parameters,
+            output: proof.clone(),
+            proof,
+        }))
+        .is_err());
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        assert!(execute(Command::Calibrate(CalibrateArguments {
+            discriminant_bits: 2,
+            target_delay: 1.0,
+            measurement_time: 0.01,
+        }))
+        .is_err());
+        assert!(execute(Command::Calibrate(CalibrateArguments {
+            discriminant_bits: 256,
+            target_delay: -1.0,
+            measurement_time: 0.01,
+        }))
+        .is_err());
+        assert!(execute(Command::Evaluate(EvaluateArguments {
+            parameters: "InvalidParameters".to_string(),
+        }))
+        .is_err());
+    }
+}
@@ -0,0 +1,144 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use clap::Parser;
+use fastcrypto_zkp::inspect::{inspect_proof, inspect_verifying_key, Curve};
+use std::io::{Error, ErrorKind};
+
+#[derive(Parser)]
+#[command(name = "zkp-cli")]
+#[command(about = "Tools for Groth16 verifying keys and proofs.", long_about = None)]
+enum Command {
+    /// Print the curve, the number of public inputs, the validity of the points and the digest of a
+    /// verifying key or a proof.
+    Inspect(InspectArguments),
+}
+
+#[derive(Parser, Clone)]
+struct InspectArguments {
+    /// The curve, either bn254 or bls12381.
+    #[clap(short, long)]
+    curve: String,
+
+    /// Hex encoding of a verifying key in compressed arkworks format.
+    #[clap(short, long)]
+    vk: Option<String>,
+
+    /// Hex encoding of a proof in compressed arkworks format.
+    #[clap(short, long)]
+    proof: Option<String>,
+}
+
+fn main() {
+    match execute(Command::parse()) {
+        Ok(res) => {
+            println!("{}", res);
+            std::process::exit(exitcode::OK);
+        }
+        Err(e) => {
+            println!("Error: {}", e);
+            std::process::exit(exitcode::DATAERR);
+        }
+    }
+}
+
+fn execute(cmd: Command) -> Result<String, Error> {
+    match cmd {
+        Command::Inspect(arguments) => {
+            let curve = arguments
+                .curve
+                .parse::<Curve>()
+                .map_err(|_| Error::new(ErrorKind::InvalidInput, "Unknown curve."))?;
+            let inspection = match (arguments.vk, arguments.proof) {
+                (Some(vk), None) => {
+                    let bytes = hex::decode(vk)
+                        .map_err(|_| Error::new(ErrorKind::InvalidInput, "Invalid vk string."))?;
+                    inspect_verifying_key(curve, &bytes).map_err(|_| {
+                        Error::new(ErrorKind::InvalidInput, "Unable to parse verifying key.")
+                    })?
+                }
+                (None, Some(proof)) => {
+                    let bytes = hex::decode(proof).map_err(|_| {
+                        Error::new(ErrorKind::InvalidInput, "Invalid proof string.")
+                    })?;
+                    inspect_proof(curve, &bytes).map_err(|_| {
+                        Error::new(ErrorKind::InvalidInput, "Unable to parse proof.")
+                    })?
+                }
+                _ => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "Exactly one of vk and proof must be given.",
+                    ))
+                }
+            };
+            Ok(inspection.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{execute, Command, InspectArguments};
+    use regex::Regex;
+
+    /// A BN254 proof whose points are all the identity, which is a valid point.
+    fn identity_proof() -> String {
+        let g1 = format!("{}40", "00".repeat(31));
+        let g2 = format!("{}40", "00".repeat(63));
+        format!("{}{}{}", g1, g2, g1)
+    }
+
+    #[test]
+    fn test_inspect_proof() {
+        let result = execute(Command::Inspect(InspectArguments {
+            curve: "bn254".to_string(),
+            vk: None,
+            proof: Some(identity_proof()),
+        }))
+        .unwrap();
+        let expected =
+            Regex::new(r"^Curve: bn254\nKind: proof\nPoints valid: true\nDigest: [0-9a-f]{64}$")
+                .unwrap();
+        assert!(expected.is_match(&result));
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        // Unknown curve.
+        assert!(execute(Command::Inspect(InspectArguments {
+            curve: "secp256k1".to_string(),
+            vk: None,
+            proof: Some(identity_proof()),
+        }))
+        .is_err());
+
+        // Both or none of vk and proof.
+        assert!(execute(Command::Inspect(InspectArguments {
+            curve: "bn254".to_string(),
+            vk: Some(identity_proof()),
+            proof: Some(identity_proof()),
+        }))
+        .is_err());
+        assert!(execute(Command::Inspect(InspectArguments {
+            curve: "bn254".to_string(),
+            vk: None,
+            proof: None,
+        }))
+        .is_err());
+
+        // Invalid hex and truncated proof.
+        assert!(execute(Command::Inspect(InspectArguments {
+            curve: "bn254".to_string(),
+            vk: None,
+            proof: Some("zz".to_string()),
+        }))
+        .is_err());
+        assert!(execute(Command::Inspect(InspectArguments {
+            curve: "bn254".to_string(),
+            vk: None,
+            proof: Some(identity_proof()[2..].to_string()),
+        }))
+        .is_err());
+    }
+}
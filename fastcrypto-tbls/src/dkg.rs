@@ -14,6 +14,7 @@ use crate::types::ShareIndex;
 use fastcrypto::error::FastCryptoError;
 use fastcrypto::groups::{GroupElement, HashToGroupElement};
 use fastcrypto::traits::AllowedRng;
+use rand::seq::SliceRandom;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
@@ -23,7 +24,7 @@ use std::collections::{HashMap, HashSet};
 // TODO: Add weights to PkiNode, and change the DKG accordingly.
 
 /// PKI node, with a unique id and its encryption public key.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PkiNode<EG: GroupElement> {
     pub id: ShareIndex,
     pub pk: ecies::PublicKey<EG>,
@@ -31,8 +32,48 @@ pub struct PkiNode<EG: GroupElement> {
 
 pub type Nodes<EG> = Vec<PkiNode<EG>>;
 
-/// Party in the DKG protocol.
-#[derive(Clone, PartialEq, Eq)]
+/// How dealers encrypt the shares in their [FirstMessage]s. All parties must use the same mode, and
+/// first messages created with another mode are ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ShareEncryption {
+    /// Each encrypted share names its receiver.
+    #[default]
+    Addressed,
+    /// The encrypted shares are shuffled, padded to the same length and do not name their
+    /// receivers, so observers cannot tell which encryption is addressed to which party. Receivers
+    /// try to decrypt all of them, which costs one ECIES decryption per party.
+    Anonymous,
+}
+
+/// Identifier of a DKG session. It is derived from the random oracle of the parties, which must be
+/// unique for each session.
+pub type SessionId = [u8; 32];
+
+/// Binds a message to a session and to a round of the protocol. Parties ignore messages whose
+/// header differs from the one they expect, e.g. messages replayed from another session.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MessageHeader {
+    pub session_id: SessionId,
+    pub round: u8,
+}
+
+/// The round of [FirstMessage]s.
+pub const FIRST_ROUND: u8 = 1;
+
+/// The round of [SecondMessage]s.
+pub const SECOND_ROUND: u8 = 2;
+
+/// Plaintexts of anonymous encrypted shares are padded to a multiple of this length.
+const ANONYMOUS_SHARE_PADDING: usize = 64;
+
+/// Party in the DKG protocol. It can be serialized to persist the state of the party between the
+/// rounds, e.g. using [crate::share_storage].
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "G: Serialize, G::ScalarType: Serialize, EG: Serialize, EG::ScalarType: Serialize",
+    deserialize = "G: DeserializeOwned, G::ScalarType: DeserializeOwned, EG: DeserializeOwned, \
+                   EG::ScalarType: DeserializeOwned"
+))]
 pub struct Party<G: GroupElement, EG: GroupElement> {
     id: ShareIndex,
     nodes: Nodes<EG>,
@@ -42,6 +83,8 @@ pub struct Party<G: GroupElement, EG: GroupElement> {
     vss_pk: PublicPoly<G>,
     threshold: u32,
     random_oracle: RandomOracle,
+    session_id: SessionId,
+    share_encryption: ShareEncryption,
 }
 
 /// [EncryptedShare] holds the ECIES encryption of a share destined to the receiver.
@@ -57,9 +100,13 @@ pub struct EncryptedShare<EG: GroupElement> {
 /// protocol.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct FirstMessage<G: GroupElement, EG: GroupElement> {
+    pub header: MessageHeader,
     pub sender: ShareIndex,
     /// The encrypted shares created by the sender.
     pub encrypted_shares: Vec<EncryptedShare<EG>>,
+    /// The shuffled encryptions of the receivers' indices and shares if the sender uses
+    /// [ShareEncryption::Anonymous], in which case `encrypted_shares` is empty.
+    pub anonymous_shares: Vec<ecies::Encryption<EG>>,
     /// The commitment of the secret polynomial created by the sender.
     // TODO: add a proof of possession/knowledge?
     pub vss_pk: PublicPoly<G>,
@@ -67,7 +114,7 @@ pub struct FirstMessage<G: GroupElement, EG: GroupElement> {
 
 /// A complaint/fraud claim against a dealer that created invalid encrypted share.
 // TODO: add Serialize & Deserialize.
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Complaint<EG: GroupElement> {
     /// The identity of the sender.
     NoShare(ShareIndex),
@@ -77,13 +124,20 @@ pub enum Complaint<EG: GroupElement> {
     // This saves the ZKPoK with the price of publishing one ECIES public key & PoP for each party,
     // resulting in larger communication in the happy-path.
     InvalidEncryptedShare(ShareIndex, RecoveryPackage<EG>),
+    /// The identity of the sender and a recovery package for each of its anonymous shares, showing
+    /// that none of them is a valid share for the accuser.
+    NoValidAnonymousShare(ShareIndex, Vec<RecoveryPackage<EG>>),
 }
 
 /// A [DkgSecondMessage] is sent during the second phase of the protocol. It includes complaints
 /// created by receiver of invalid encrypted shares.
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SecondMessage<EG: GroupElement> {
+    pub header: MessageHeader,
     pub sender: ShareIndex,
+    /// A digest of the first messages processed by the sender. Second messages with another
+    /// transcript are ignored since their complaints refer to other first messages.
+    pub transcript: [u8; 32],
     /// List of complaints against other parties. Empty if there are none.
     pub complaints: Vec<Complaint<EG>>,
 }
@@ -93,7 +147,12 @@ pub type SharesMap<G> = HashMap<ShareIndex, <G as GroupElement>::ScalarType>;
 
 /// [DkgOutput] is the final output of the DKG protocol in case it runs
 /// successfully. It can be used later with [ThresholdBls], see examples in tests.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "G: Serialize, G::ScalarType: Serialize, EG: Serialize, EG::ScalarType: Serialize",
+    deserialize = "G: DeserializeOwned, G::ScalarType: DeserializeOwned, EG: DeserializeOwned, \
+                   EG::ScalarType: DeserializeOwned"
+))]
 pub struct DkgOutput<G: GroupElement, EG: GroupElement> {
     pub nodes: Nodes<EG>,
     pub vss_pk: Poly<G>,
@@ -105,6 +164,7 @@ pub struct DkgOutput<G: GroupElement, EG: GroupElement> {
 /// Can be instantiated with G1Curve or G2Curve.
 impl<G: GroupElement, EG: GroupElement> Party<G, EG>
 where
+    G: Serialize,
     <G as GroupElement>::ScalarType: Serialize + DeserializeOwned,
     EG: Serialize,
     <EG as GroupElement>::ScalarType: HashToGroupElement,
@@ -133,6 +193,7 @@ where
         }
         let vss_sk = PrivatePoly::<G>::rand(threshold - 1, rng);
         let vss_pk = vss_sk.commit::<G>();
+        let session_id = Self::truncate(random_oracle.extend("session").evaluate(&()));
 
         Ok(Self {
             id: curr_node.id,
@@ -143,34 +204,86 @@ where
             vss_pk,
             threshold,
             random_oracle,
+            session_id,
+            share_encryption: ShareEncryption::default(),
         })
     }
 
+    /// Set how shares are encrypted, see [ShareEncryption].
+    pub fn with_share_encryption(mut self, share_encryption: ShareEncryption) -> Self {
+        self.share_encryption = share_encryption;
+        self
+    }
+
     pub fn threshold(&self) -> u32 {
         self.threshold
     }
 
+    pub fn share_encryption(&self) -> ShareEncryption {
+        self.share_encryption
+    }
+
+    pub fn session_id(&self) -> SessionId {
+        self.session_id
+    }
+
+    /// The header of messages of the given round in this session.
+    pub fn header(&self, round: u8) -> MessageHeader {
+        MessageHeader {
+            session_id: self.session_id,
+            round,
+        }
+    }
+
+    /// The digest of the first messages (in any order) which is included in second messages.
+    pub fn transcript(&self, first_messages: &[FirstMessage<G, EG>]) -> [u8; 32] {
+        let mut sorted = first_messages.iter().collect::<Vec<_>>();
+        sorted.sort_by_key(|m| m.sender);
+        Self::truncate(self.random_oracle.extend("transcript").evaluate(&sorted))
+    }
+
     /// 4. Create the first message to be broadcasted.
     pub fn create_first_message<R: AllowedRng>(&self, rng: &mut R) -> FirstMessage<G, EG> {
-        let encrypted_shares = self
-            .nodes
-            .iter()
-            .filter(|n| n.id != self.id)
-            .map(|n| {
-                let share = self.vss_sk.eval(n.id);
-                let buff = bincode::serialize(&share.value)
-                    .expect("serialize of a share should never fail");
-                let encryption = n.pk.encrypt(&buff, rng);
-                EncryptedShare {
-                    receiver: n.id,
-                    encryption,
-                }
-            })
-            .collect();
+        let receivers = self.nodes.iter().filter(|n| n.id != self.id);
+        let (encrypted_shares, anonymous_shares) = match self.share_encryption {
+            ShareEncryption::Addressed => {
+                let encrypted_shares = receivers
+                    .map(|n| {
+                        let share = self.vss_sk.eval(n.id);
+                        let buff = bincode::serialize(&share.value)
+                            .expect("serialize of a share should never fail");
+                        let encryption = n.pk.encrypt(&buff, rng);
+                        EncryptedShare {
+                            receiver: n.id,
+                            encryption,
+                        }
+                    })
+                    .collect();
+                (encrypted_shares, Vec::new())
+            }
+            ShareEncryption::Anonymous => {
+                let mut anonymous_shares = receivers
+                    .map(|n| {
+                        let share = self.vss_sk.eval(n.id);
+                        let mut buff = bincode::serialize(&(share.index, share.value))
+                            .expect("serialize of a share should never fail");
+                        let padded_length = (buff.len() + ANONYMOUS_SHARE_PADDING - 1)
+                            / ANONYMOUS_SHARE_PADDING
+                            * ANONYMOUS_SHARE_PADDING;
+                        buff.resize(padded_length, 0);
+                        n.pk.encrypt(&buff, rng)
+                    })
+                    .collect::<Vec<_>>();
+                anonymous_shares.shuffle(rng);
+                (Vec::new(), anonymous_shares)
+            }
+        };
 
         FirstMessage {
+            header: self.header(FIRST_ROUND),
             sender: self.id,
             encrypted_shares,
+            anonymous_shares,
             vss_pk: self.vss_pk.clone(),
         }
     }
@@ -202,11 +315,18 @@ where
         let my_id = self.id;
         let mut shares = HashMap::new(); // Will include only valid shares.
         let mut next_message = SecondMessage {
+            header: self.header(SECOND_ROUND),
             sender: my_id,
+            transcript: self.transcript(messages),
             complaints: Vec::new(),
         };
 
         for message in messages {
+            // Ignore messages from other sessions or rounds (and other honest parties will ignore
+            // as well).
+            if message.header != self.header(FIRST_ROUND) {
+                continue;
+            }
             // Ignore if the threshold is different (and other honest parties will ignore as well).
             if message.vss_pk.degree() != self.threshold - 1 {
                 continue;
@@ -215,6 +335,49 @@ where
                 shares.insert(message.sender, self.vss_sk.eval(my_id).value);
                 continue;
             }
+            // Ignore if the share encryption is different (and other honest parties will ignore as
+            // well).
+            match self.share_encryption {
+                ShareEncryption::Addressed if !message.anonymous_shares.is_empty() => continue,
+                ShareEncryption::Anonymous if !message.encrypted_shares.is_empty() => continue,
+                ShareEncryption::Anonymous => {
+                    // Try all encryptions since we don't know which one is ours.
+                    let share = message.anonymous_shares.iter().find_map(|encryption| {
+                        Self::deserialize_and_check_anonymous_share(
+                            &self.ecies_sk.decrypt(encryption),
+                            my_id,
+                            &message.vss_pk,
+                        )
+                        .ok()
+                    });
+                    match share {
+                        Some(sh) => {
+                            shares.insert(message.sender, sh);
+                        }
+                        None => {
+                            let recovery_packages = message
+                                .anonymous_shares
+                                .iter()
+                                .map(|encryption| {
+                                    self.ecies_sk.create_recovery_package(
+                                        encryption,
+                                        &self.random_oracle.extend("ecies"),
+                                        rng,
+                                    )
+                                })
+                                .collect();
+                            next_message
+                                .complaints
+                                .push(Complaint::NoValidAnonymousShare(
+                                    message.sender,
+                                    recovery_packages,
+                                ));
+                        }
+                    }
+                    continue;
+                }
+                ShareEncryption::Addressed => (),
+            }
             // TODO: check that current dealer is in the list of pki nodes.
             // Get the relevant encrypted share (or skip message).
             let encrypted_share = message
@@ -275,6 +438,14 @@ where
         shares: SharesMap<G>,
         minimal_threshold: usize,
     ) -> Result<SharesMap<G>, FastCryptoError> {
+        // Ignore second messages from other sessions or rounds, or which processed other first
+        // messages.
+        let header = self.header(SECOND_ROUND);
+        let transcript = self.transcript(first_messages);
+        let second_messages = second_messages
+            .iter()
+            .filter(|m| m.header == header && m.transcript == transcript)
+            .collect::<Vec<_>>();
         if first_messages.len() != self.threshold as usize
             || second_messages.len() < minimal_threshold
         {
@@ -283,8 +454,7 @@ where
         // Two hash maps for faster access in the main loop below.
         let id_to_pk: HashMap<ShareIndex, &ecies::PublicKey<EG>> =
             self.nodes.iter().map(|n| (n.id, &n.pk)).collect();
-        let id_to_m1: HashMap<ShareIndex, &FirstMessage<G, EG>> =
-            first_messages.iter().map(|m| (m.sender, m)).collect();
+        let id_to_m1 = self.first_messages_by_sender(first_messages);
 
         let mut shares = shares;
         'outer: for m2 in second_messages {
@@ -292,6 +462,7 @@ where
                 let accused = match complaint {
                     Complaint::NoShare(l) => *l,
                     Complaint::InvalidEncryptedShare(l, _) => *l,
+                    Complaint::NoValidAnonymousShare(l, _) => *l,
                 };
                 // Ignore senders that are already not relevant, or invalid complaints.
                 if !shares.contains_key(&accused) {
@@ -311,10 +482,13 @@ where
                     match complaint {
                         Complaint::NoShare(_) => {
                             // Check if there is a share.
-                            encrypted_share.is_none()
+                            self.share_encryption == ShareEncryption::Addressed
+                                && encrypted_share.is_none()
                         }
                         Complaint::InvalidEncryptedShare(_accused, recovery_pkg) => {
-                            if let Some(sh) = encrypted_share {
+                            if self.share_encryption != ShareEncryption::Addressed {
+                                false
+                            } else if let Some(sh) = encrypted_share {
                                 Self::check_delegated_key_and_share(
                                     recovery_pkg,
                                     accuser_pk,
@@ -328,6 +502,27 @@ where
                                 false // Strange case indeed, but still an invalid claim.
                             }
                         }
+                        Complaint::NoValidAnonymousShare(_accused, recovery_pkgs) => {
+                            let anonymous_shares = &related_m1
+                                .expect("checked above that is not None")
+                                .anonymous_shares;
+                            self.share_encryption == ShareEncryption::Anonymous
+                                && recovery_pkgs.len() == anonymous_shares.len()
+                                && anonymous_shares.iter().zip(recovery_pkgs).all(
+                                    |(encryption, recovery_pkg)| {
+                                        Self::check_delegated_key_and_anonymous_share(
+                                            recovery_pkg,
+                                            accuser_pk,
+                                            accuser,
+                                            &related_m1
+                                                .expect("checked above that is not None")
+                                                .vss_pk,
+                                            encryption,
+                                            &self.random_oracle.extend("ecies"),
+                                        )
+                                    },
+                                )
+                        }
                     }
                 };
                 match valid_complaint {
@@ -356,7 +551,7 @@ where
         first_messages: &[FirstMessage<G, EG>],
         shares: SharesMap<G>,
     ) -> DkgOutput<G, EG> {
-        let id_to_m1: HashMap<_, _> = first_messages.iter().map(|m| (m.sender, m)).collect();
+        let id_to_m1 = self.first_messages_by_sender(first_messages);
         let mut vss_pk = PublicPoly::<G>::zero();
         let mut sk = G::ScalarType::zero();
         for (from_sender, share) in shares {
@@ -374,6 +569,25 @@ where
         }
     }
 
+    /// Map from sender to the first messages of this session.
+    fn first_messages_by_sender<'a>(
+        &self,
+        first_messages: &'a [FirstMessage<G, EG>],
+    ) -> HashMap<ShareIndex, &'a FirstMessage<G, EG>> {
+        let header = self.header(FIRST_ROUND);
+        first_messages
+            .iter()
+            .filter(|m| m.header == header)
+            .map(|m| (m.sender, m))
+            .collect()
+    }
+
+    fn truncate(digest: [u8; 64]) -> [u8; 32] {
+        digest[..32]
+            .try_into()
+            .expect("a 64 byte digest has at least 32 bytes")
+    }
+
     fn decrypt_and_check_share(
         sk: &ecies::PrivateKey<EG>,
         id: ShareIndex,
@@ -412,4 +626,36 @@ where
         )?;
         Self::deserialize_and_check_share(buffer.as_slice(), id, vss_pk)
     }
+
+    fn deserialize_and_check_anonymous_share(
+        buffer: &[u8],
+        id: ShareIndex,
+        vss_pk: &PublicPoly<G>,
+    ) -> Result<G::ScalarType, FastCryptoError> {
+        // The padding is ignored as trailing bytes.
+        let (receiver, share): (ShareIndex, G::ScalarType) =
+            bincode::deserialize(buffer).map_err(|_| FastCryptoError::InvalidInput)?;
+        if receiver != id || !vss_pk.is_valid_share(id, &share) {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        Ok(share)
+    }
+
+    /// Returns true if the recovery package is valid and the encryption is not a valid share for
+    /// the given id.
+    fn check_delegated_key_and_anonymous_share(
+        recovery_pkg: &RecoveryPackage<EG>,
+        ecies_pk: &ecies::PublicKey<EG>,
+        id: ShareIndex,
+        vss_pk: &PublicPoly<G>,
+        encryption: &ecies::Encryption<EG>,
+        random_oracle: &RandomOracle,
+    ) -> bool {
+        match ecies_pk.decrypt_with_recovery_package(recovery_pkg, random_oracle, encryption) {
+            Ok(buffer) => {
+                Self::deserialize_and_check_anonymous_share(buffer.as_slice(), id, vss_pk).is_err()
+            }
+            Err(_) => false,
+        }
+    }
 }
@@ -0,0 +1,230 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A driver which runs the DKG protocol of [Party] over channels, so applications only need to
+//! connect the channels to their transport.
+//!
+//! The transport must deliver all broadcast messages, including the party's own messages, to all
+//! parties in the same order (e.g. using a consensus protocol). The driver then uses the first
+//! `threshold` first messages and the first `minimal_threshold` second messages from distinct
+//! senders in this order, so all honest parties derive the same output. Each phase must complete
+//! within its timeout, otherwise the driver returns a [DkgFailure] describing what was received.
+
+use crate::dkg::{DkgOutput, FirstMessage, Party, SecondMessage, FIRST_ROUND, SECOND_ROUND};
+use crate::types::ShareIndex;
+use fastcrypto::error::FastCryptoError;
+use fastcrypto::groups::{GroupElement, HashToGroupElement};
+use fastcrypto::traits::AllowedRng;
+use serde::{de::DeserializeOwned, Serialize};
+use std::time::Duration;
+use tokio::sync::mpsc::{Receiver, Sender};
+use tokio::time::{timeout_at, Instant};
+
+/// A message of the DKG protocol, as sent and received by a [DkgDriver].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DkgMessage<G: GroupElement, EG: GroupElement> {
+    First(FirstMessage<G, EG>),
+    Second(SecondMessage<EG>),
+}
+
+/// The phases of the DKG protocol in which a party waits for messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DkgPhase {
+    FirstRound,
+    SecondRound,
+}
+
+/// The maximal durations of the phases, measured from the start of the phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhaseTimeouts {
+    pub first_round: Duration,
+    pub second_round: Duration,
+}
+
+/// The reason the DKG failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DkgFailureReason {
+    /// Not enough messages were received before the timeout of the phase.
+    Timeout,
+    /// The channel of incoming messages was closed.
+    IncomingClosed,
+    /// The channel of outgoing messages was closed.
+    OutgoingClosed,
+    /// The protocol returned an error.
+    Protocol(FastCryptoError),
+}
+
+/// A report of a failed DKG.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DkgFailure {
+    /// The phase in which the DKG failed.
+    pub phase: DkgPhase,
+    pub reason: DkgFailureReason,
+    /// The senders of the messages of this phase which were accepted before the failure.
+    pub received_from: Vec<ShareIndex>,
+}
+
+/// Runs the DKG protocol for a single party, see the module documentation.
+pub struct DkgDriver<G: GroupElement, EG: GroupElement> {
+    party: Party<G, EG>,
+    timeouts: PhaseTimeouts,
+    minimal_threshold: usize,
+}
+
+impl<G: GroupElement, EG: GroupElement> DkgDriver<G, EG>
+where
+    G: Serialize,
+    <G as GroupElement>::ScalarType: Serialize + DeserializeOwned,
+    EG: Serialize,
+    <EG as GroupElement>::ScalarType: HashToGroupElement,
+{
+    /// Create a driver for the given party. `minimal_threshold` is the number of second messages
+    /// to wait for, see [Party::process_responses].
+    pub fn new(party: Party<G, EG>, timeouts: PhaseTimeouts, minimal_threshold: usize) -> Self {
+        Self {
+            party,
+            timeouts,
+            minimal_threshold,
+        }
+    }
+
+    /// Run the protocol: broadcast messages on `outgoing` and process the messages received on
+    /// `incoming` until the key share is derived or the protocol fails. Messages from other
+    /// sessions and duplicate messages from the same sender are ignored.
+    pub async fn run<R: AllowedRng>(
+        self,
+        mut incoming: Receiver<DkgMessage<G, EG>>,
+        outgoing: Sender<DkgMessage<G, EG>>,
+        rng: &mut R,
+    ) -> Result<DkgOutput<G, EG>, DkgFailure> {
+        let party = &self.party;
+        let fail = |phase, reason, received_from| DkgFailure {
+            phase,
+            reason,
+            received_from,
+        };
+
+        // Second messages may arrive before we have received enough first messages.
+        let mut early_second_messages = Vec::new();
+
+        // First round.
+        outgoing
+            .send(DkgMessage::First(party.create_first_message(rng)))
+            .await
+            .map_err(|_| {
+                fail(
+                    DkgPhase::FirstRound,
+                    DkgFailureReason::OutgoingClosed,
+                    vec![],
+                )
+            })?;
+        let deadline = Instant::now() + self.timeouts.first_round;
+        let mut first_messages: Vec<FirstMessage<G, EG>> = Vec::new();
+        while first_messages.len() < party.threshold() as usize {
+            let senders = || first_messages.iter().map(|m| m.sender).collect();
+            match timeout_at(deadline, incoming.recv()).await {
+                Err(_) => {
+                    return Err(fail(
+                        DkgPhase::FirstRound,
+                        DkgFailureReason::Timeout,
+                        senders(),
+                    ))
+                }
+                Ok(None) => {
+                    return Err(fail(
+                        DkgPhase::FirstRound,
+                        DkgFailureReason::IncomingClosed,
+                        senders(),
+                    ))
+                }
+                Ok(Some(DkgMessage::First(m))) => {
+                    if m.header == party.header(FIRST_ROUND)
+                        && first_messages.iter().all(|n| n.sender != m.sender)
+                    {
+                        first_messages.push(m);
+                    }
+                }
+                Ok(Some(DkgMessage::Second(m))) => early_second_messages.push(m),
+            }
+        }
+        let (shares, second_message) =
+            party
+                .create_second_message(&first_messages, rng)
+                .map_err(|e| {
+                    fail(
+                        DkgPhase::FirstRound,
+                        DkgFailureReason::Protocol(e),
+                        first_messages.iter().map(|m| m.sender).collect(),
+                    )
+                })?;
+
+        // Second round.
+        outgoing
+            .send(DkgMessage::Second(second_message))
+            .await
+            .map_err(|_| {
+                fail(
+                    DkgPhase::SecondRound,
+                    DkgFailureReason::OutgoingClosed,
+                    vec![],
+                )
+            })?;
+        let deadline = Instant::now() + self.timeouts.second_round;
+        let header = party.header(SECOND_ROUND);
+        let transcript = party.transcript(&first_messages);
+        let is_relevant = |m: &SecondMessage<EG>, accepted: &[SecondMessage<EG>]| {
+            m.header == header
+                && m.transcript == transcript
+                && accepted.iter().all(|n| n.sender != m.sender)
+        };
+        let mut second_messages = Vec::new();
+        for m in early_second_messages {
+            if is_relevant(&m, &second_messages) {
+                second_messages.push(m);
+            }
+        }
+        while second_messages.len() < self.minimal_threshold {
+            let senders = || second_messages.iter().map(|m| m.sender).collect();
+            match timeout_at(deadline, incoming.recv()).await {
+                Err(_) => {
+                    return Err(fail(
+                        DkgPhase::SecondRound,
+                        DkgFailureReason::Timeout,
+                        senders(),
+                    ))
+                }
+                Ok(None) => {
+                    return Err(fail(
+                        DkgPhase::SecondRound,
+                        DkgFailureReason::IncomingClosed,
+                        senders(),
+                    ))
+                }
+                Ok(Some(DkgMessage::Second(m))) => {
+                    if is_relevant(&m, &second_messages) {
+                        second_messages.push(m);
+                    }
+                }
+                // Late first messages are not used.
+                Ok(Some(DkgMessage::First(_))) => (),
+            }
+        }
+        second_messages.truncate(self.minimal_threshold);
+
+        let shares = party
+            .process_responses(
+                &first_messages,
+                &second_messages,
+                shares,
+                self.minimal_threshold,
+            )
+            .map_err(|e| {
+                fail(
+                    DkgPhase::SecondRound,
+                    DkgFailureReason::Protocol(e),
+                    second_messages.iter().map(|m| m.sender).collect(),
+                )
+            })?;
+        Ok(party.aggregate(&first_messages, shares))
+    }
+}
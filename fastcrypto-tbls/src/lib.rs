@@ -16,11 +16,17 @@
 #[path = ""]
 mod tbls_modules {
     pub mod dkg;
+    pub mod dkg_driver;
     pub mod ecies;
     pub mod mocked_dkg;
+    pub mod nizk;
     pub mod polynomial;
     pub mod random_oracle;
+    pub mod shard_export;
+    pub mod share_storage;
     pub mod tbls;
+    pub mod threshold_ecdsa;
+    pub mod threshold_vrf;
     pub mod types;
 }
 
@@ -46,3 +52,27 @@ pub mod random_oracle_tests;
 #[cfg(test)]
 #[path = "tests/dkg_tests.rs"]
 pub mod dkg_tests;
+
+#[cfg(test)]
+#[path = "tests/dkg_driver_tests.rs"]
+pub mod dkg_driver_tests;
+
+#[cfg(test)]
+#[path = "tests/shard_export_tests.rs"]
+pub mod shard_export_tests;
+
+#[cfg(test)]
+#[path = "tests/share_storage_tests.rs"]
+pub mod share_storage_tests;
+
+#[cfg(test)]
+#[path = "tests/threshold_ecdsa_tests.rs"]
+pub mod threshold_ecdsa_tests;
+
+#[cfg(test)]
+#[path = "tests/threshold_vrf_tests.rs"]
+pub mod threshold_vrf_tests;
+
+#[cfg(test)]
+#[path = "tests/nizk_tests.rs"]
+pub mod nizk_tests;
@@ -0,0 +1,86 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::random_oracle::RandomOracle;
+use fastcrypto::error::FastCryptoError;
+use fastcrypto::groups::{GroupElement, HashToGroupElement, Scalar};
+use fastcrypto::traits::AllowedRng;
+use serde::{Deserialize, Serialize};
+
+/// NIZKPoK for the DDH tuple [G, H, xG, xH], i.e. that xG and xH have the same discrete log with
+/// respect to the generator G and the base H.
+/// - Prover selects a random r and sends A=rG, B=rH.
+/// - Prover computes challenge c and sends z=r+c*x.
+/// - Verifier checks that zG=A+c*xG and zH=B+c*xH.
+/// The NIZK is (A, B, z) where c is implicitly computed using a random oracle.
+///
+/// The random oracle must be unique for the protocol using the proof.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DdhTupleNizk<G: GroupElement>(G, G, G::ScalarType);
+
+impl<G> DdhTupleNizk<G>
+where
+    G: GroupElement + Serialize,
+    <G as GroupElement>::ScalarType: HashToGroupElement,
+{
+    pub fn create<R: AllowedRng>(
+        x: &G::ScalarType,
+        h: &G,
+        x_g: &G,
+        x_h: &G,
+        random_oracle: &RandomOracle,
+        rng: &mut R,
+    ) -> Self {
+        let r = G::ScalarType::rand(rng);
+        let a = G::generator() * r;
+        let b = *h * r;
+        let challenge = Self::fiat_shamir_challenge(h, x_g, x_h, &a, &b, random_oracle);
+        let z = challenge * x + r;
+        DdhTupleNizk(a, b, z)
+    }
+
+    pub fn verify(
+        &self,
+        h: &G,
+        x_g: &G,
+        x_h: &G,
+        random_oracle: &RandomOracle,
+    ) -> Result<(), FastCryptoError> {
+        let challenge = Self::fiat_shamir_challenge(h, x_g, x_h, &self.0, &self.1, random_oracle);
+        if !Self::is_valid_relation(
+            &self.0, // A
+            x_g,
+            &G::generator(),
+            &self.2, // z
+            &challenge,
+        ) || !Self::is_valid_relation(
+            &self.1, // B
+            x_h, h, &self.2, // z
+            &challenge,
+        ) {
+            Err(FastCryptoError::InvalidProof)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns the challenge for Fiat-Shamir.
+    fn fiat_shamir_challenge(
+        h: &G,
+        x_g: &G,
+        x_h: &G,
+        a: &G,
+        b: &G,
+        random_oracle: &RandomOracle,
+    ) -> G::ScalarType {
+        let output = random_oracle.evaluate(&(G::generator(), h, x_g, x_h, a, b));
+        G::ScalarType::hash_to_group_element(&output)
+    }
+
+    /// Checks if e1 + e2*c = z e3
+    fn is_valid_relation(e1: &G, e2: &G, e3: &G, z: &G::ScalarType, c: &G::ScalarType) -> bool {
+        let left = *e1 + *e2 * c;
+        let right = *e3 * z;
+        left == right
+    }
+}
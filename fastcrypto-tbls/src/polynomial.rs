@@ -8,6 +8,7 @@
 use crate::types::{IndexedValue, ShareIndex};
 use fastcrypto::error::FastCryptoError;
 use fastcrypto::groups::{GroupElement, Scalar};
+use fastcrypto::polynomial;
 use fastcrypto::traits::AllowedRng;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
@@ -71,14 +72,9 @@ impl<C: GroupElement> Poly<C> {
     /// Evaluates the polynomial at the specified value.
     pub fn eval(&self, i: ShareIndex) -> Eval<C> {
         let xi = C::ScalarType::from(i.get().into());
-        let res = self.0.iter().rev().fold(C::zero(), |mut sum, coeff| {
-            sum = sum * xi + coeff;
-            sum
-        });
-
         Eval {
             index: i,
-            value: res,
+            value: polynomial::evaluate(&self.0, &xi),
         }
     }
 
@@ -98,33 +94,7 @@ impl<C: GroupElement> Poly<C> {
             return Err(FastCryptoError::InvalidInput);
         }
 
-        // Iterate over all indices and for each multiply the lagrange basis
-        // with the value of the share.
-        let mut acc = C::zero();
-        for IndexedValue {
-            index: i,
-            value: share_i,
-        } in shares
-        {
-            let mut num = C::ScalarType::generator();
-            let mut den = C::ScalarType::generator();
-
-            for IndexedValue { index: j, value: _ } in shares {
-                if i == j {
-                    continue;
-                };
-                // j - 0
-                num = num * C::ScalarType::from(j.get() as u64);
-                // 1 / (j - i)
-                den = den
-                    * (C::ScalarType::from(j.get() as u64) - C::ScalarType::from(i.get() as u64));
-            }
-            // Next line is safe since i != j.
-            let inv = (C::ScalarType::generator() / den).unwrap();
-            acc += *share_i * num * inv;
-        }
-
-        Ok(acc)
+        interpolate_at_zero(shares)
     }
 
     // TODO: Create a batch version of is_valid_share that checks a vector in O(n) instead of O(n^2).
@@ -169,3 +139,54 @@ impl<C: Scalar> Poly<C> {
         Poly::<P>::from(commits)
     }
 }
+
+//// Lagrange interpolation.
+
+/// Computes the Lagrange coefficients `l_i(x) = prod_{j != i} (x - j) / (i - j)` for the given
+/// distinct indices, such that `p(x) = sum_i l_i(x) p(i)` for any polynomial `p` of degree less
+/// than `indices.len()`.
+///
+/// Returns an error if `indices` is empty or contains duplicates. The coefficients depend only on
+/// the (public) indices and `x`, see [polynomial::lagrange_coefficients].
+pub fn lagrange_coefficients<S: Scalar>(
+    x: &S,
+    indices: &[ShareIndex],
+) -> Result<Vec<S>, FastCryptoError> {
+    if indices.is_empty() || indices.iter().collect::<HashSet<_>>().len() != indices.len() {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    let points = indices
+        .iter()
+        .map(|i| S::from(i.get().into()))
+        .collect::<Vec<_>>();
+    polynomial::lagrange_coefficients(x, &points)
+}
+
+/// Computes the Lagrange coefficients `l_i(0)` for the given distinct indices. See
+/// [lagrange_coefficients].
+pub fn lagrange_coefficients_at_zero<S: Scalar>(
+    indices: &[ShareIndex],
+) -> Result<Vec<S>, FastCryptoError> {
+    lagrange_coefficients(&S::zero(), indices)
+}
+
+/// Given evaluations `p(i)` of a polynomial `p` of degree less than `evaluations.len()` at distinct
+/// indices, computes `p(x)`. This works for both scalars and group elements, in which case the
+/// polynomial is interpolated in the exponent.
+pub fn interpolate_at<C: GroupElement>(
+    x: &C::ScalarType,
+    evaluations: &[Eval<C>],
+) -> Result<C, FastCryptoError> {
+    let indices = evaluations.iter().map(|e| e.index).collect::<Vec<_>>();
+    let coefficients = lagrange_coefficients(x, &indices)?;
+    Ok(evaluations
+        .iter()
+        .zip(coefficients)
+        .fold(C::zero(), |acc, (e, l)| acc + e.value * l))
+}
+
+/// Given evaluations `p(i)` of a polynomial `p` of degree less than `evaluations.len()` at distinct
+/// indices, computes `p(0)`.
+pub fn interpolate_at_zero<C: GroupElement>(evaluations: &[Eval<C>]) -> Result<C, FastCryptoError> {
+    interpolate_at(&C::ScalarType::zero(), evaluations)
+}
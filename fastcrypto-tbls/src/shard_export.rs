@@ -0,0 +1,252 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Backup of a signing key as `n` password-protected shards, any `t` of which recover the key.
+//!
+//! - A random scalar k is split using a degree t-1 polynomial p with p(0) = k, and the key is
+//!   encrypted with AES-256-GCM using hkdf(k).
+//! - Shard i holds p(i) encrypted with AES-256-GCM using a key derived from its own password with
+//!   PBKDF2-HMAC-SHA3-256, together with the encrypted signing key and the commitment to p.
+//! - The commitment to p is the integrity tag of the export: every decrypted share is checked
+//!   against it before recovery, and it is bound to the encryption of each share.
+
+use crate::polynomial::{Eval, Poly, PrivatePoly, PublicPoly};
+use crate::types::ShareIndex;
+use fastcrypto::aes::{Aes256Gcm, AesKey, AuthenticatedCipher, InitializationVector};
+use fastcrypto::error::FastCryptoError;
+use fastcrypto::groups::ristretto255::{RistrettoPoint, RistrettoScalar};
+use fastcrypto::hmac::{hkdf_sha3_256, hmac_sha3_256, HkdfIkm, HmacKey};
+use fastcrypto::serde_helpers::ToFromByteArray;
+use fastcrypto::traits::{AllowedRng, Generate, ToFromBytes};
+use serde::{Deserialize, Serialize};
+use typenum::consts::U12;
+use zeroize::Zeroize;
+
+/// Current version of the shard format.
+pub const SHARD_VERSION: u8 = 1;
+
+/// Recommended number of PBKDF2 iterations.
+pub const DEFAULT_PBKDF2_ITERATIONS: u32 = 600_000;
+
+pub(crate) const AES_KEY_LENGTH: usize = 32;
+pub(crate) const SALT_LENGTH: usize = 32;
+const HKDF_INFO: &[u8] = b"fastcrypto-tbls-shard-export-v1";
+
+/// A single password-protected shard of an exported key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyShard {
+    version: u8,
+    index: ShareIndex,
+    threshold: u32,
+    commitment: PublicPoly<RistrettoPoint>,
+    encrypted_key: EncryptedBlob,
+    pbkdf2_iterations: u32,
+    salt: Vec<u8>,
+    encrypted_share: EncryptedBlob,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct EncryptedBlob {
+    pub(crate) iv: Vec<u8>,
+    pub(crate) ciphertext: Vec<u8>,
+}
+
+/// The public part of a shard which is authenticated when the share is encrypted.
+#[derive(Serialize)]
+struct ShardHeader<'a> {
+    version: u8,
+    index: ShareIndex,
+    threshold: u32,
+    commitment: &'a PublicPoly<RistrettoPoint>,
+    encrypted_key: &'a EncryptedBlob,
+}
+
+/// A share which was decrypted using the password of its holder.
+pub struct DecryptedShard<'a> {
+    shard: &'a KeyShard,
+    share: RistrettoScalar,
+}
+
+impl EncryptedBlob {
+    pub(crate) fn encrypt<R: AllowedRng>(
+        key: &[u8],
+        aad: &[u8],
+        plaintext: &[u8],
+        rng: &mut R,
+    ) -> Self {
+        let cipher = Aes256Gcm::<U12>::new(AesKey::from_bytes(key).expect("Key length is valid"));
+        let iv = InitializationVector::<U12>::generate(rng);
+        Self {
+            ciphertext: cipher.encrypt_authenticated(&iv, aad, plaintext),
+            iv: iv.as_bytes().to_vec(),
+        }
+    }
+
+    pub(crate) fn decrypt(&self, key: &[u8], aad: &[u8]) -> Result<Vec<u8>, FastCryptoError> {
+        let cipher = Aes256Gcm::<U12>::new(AesKey::from_bytes(key)?);
+        let iv = InitializationVector::<U12>::from_bytes(&self.iv)?;
+        cipher.decrypt_authenticated(&iv, aad, &self.ciphertext)
+    }
+}
+
+/// PBKDF2 (RFC 8018) with HMAC-SHA3-256 and a single output block.
+pub(crate) fn pbkdf2(password: &[u8], salt: &[u8], iterations: u32) -> [u8; AES_KEY_LENGTH] {
+    let key = HmacKey::from_bytes(password).expect("HMAC keys can have any length");
+    let mut input = salt.to_vec();
+    input.extend_from_slice(&1u32.to_be_bytes());
+    let mut u = hmac_sha3_256(&key, &input).digest;
+    let mut output = u;
+    for _ in 1..iterations {
+        u = hmac_sha3_256(&key, &u).digest;
+        output.iter_mut().zip(u.iter()).for_each(|(o, u)| *o ^= u);
+    }
+    output
+}
+
+/// Derive the key used to encrypt the exported key from the shared secret.
+fn key_encryption_key(secret: &RistrettoScalar) -> Vec<u8> {
+    let ikm = HkdfIkm::from_bytes(&secret.to_byte_array()).expect("Any length is valid");
+    hkdf_sha3_256(&ikm, &[], HKDF_INFO, AES_KEY_LENGTH).expect("Output length is valid")
+}
+
+impl KeyShard {
+    /// Split `key` into one shard for each of the given passwords, such that any `threshold` of the
+    /// shards together with their passwords can recover the key. The index of the shard which is
+    /// protected by `passwords[i]` is `i + 1`.
+    pub fn export<R: AllowedRng>(
+        key: &[u8],
+        threshold: u32,
+        passwords: &[&[u8]],
+        pbkdf2_iterations: u32,
+        rng: &mut R,
+    ) -> Result<Vec<Self>, FastCryptoError> {
+        if threshold == 0 || threshold as usize > passwords.len() || pbkdf2_iterations == 0 {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let polynomial = PrivatePoly::<RistrettoPoint>::rand(threshold - 1, rng);
+        let commitment: PublicPoly<RistrettoPoint> = polynomial.commit();
+
+        let mut kek = key_encryption_key(polynomial.c0());
+        let encrypted_key = EncryptedBlob::encrypt(&kek, &commitment_bytes(&commitment), key, rng);
+        kek.zeroize();
+
+        Ok(passwords
+            .iter()
+            .enumerate()
+            .map(|(i, password)| {
+                let index = ShareIndex::new(i as u32 + 1).expect("Index is non-zero");
+                let mut salt = vec![0u8; SALT_LENGTH];
+                rng.fill_bytes(&mut salt);
+
+                let mut shard = Self {
+                    version: SHARD_VERSION,
+                    index,
+                    threshold,
+                    commitment: commitment.clone(),
+                    encrypted_key: encrypted_key.clone(),
+                    pbkdf2_iterations,
+                    salt,
+                    encrypted_share: EncryptedBlob {
+                        iv: vec![],
+                        ciphertext: vec![],
+                    },
+                };
+                let mut password_key = pbkdf2(password, &shard.salt, pbkdf2_iterations);
+                shard.encrypted_share = EncryptedBlob::encrypt(
+                    &password_key,
+                    &shard.header(),
+                    &polynomial.eval(index).value.to_byte_array(),
+                    rng,
+                );
+                password_key.zeroize();
+                shard
+            })
+            .collect())
+    }
+
+    /// The index of this shard.
+    pub fn index(&self) -> ShareIndex {
+        self.index
+    }
+
+    /// The number of shards needed to recover the key.
+    pub fn threshold(&self) -> u32 {
+        self.threshold
+    }
+
+    fn header(&self) -> Vec<u8> {
+        bincode::serialize(&ShardHeader {
+            version: self.version,
+            index: self.index,
+            threshold: self.threshold,
+            commitment: &self.commitment,
+            encrypted_key: &self.encrypted_key,
+        })
+        .expect("Serialization should succeed")
+    }
+
+    /// Decrypt the share in this shard with the password of its holder and check it against the
+    /// integrity tag of the export.
+    pub fn decrypt(&self, password: &[u8]) -> Result<DecryptedShard<'_>, FastCryptoError> {
+        if self.version != SHARD_VERSION
+            || self.commitment == PublicPoly::from(vec![])
+            || self.commitment.degree() + 1 != self.threshold
+        {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let mut password_key = pbkdf2(password, &self.salt, self.pbkdf2_iterations);
+        let plaintext = self.encrypted_share.decrypt(&password_key, &self.header());
+        password_key.zeroize();
+        let mut plaintext = plaintext?;
+
+        let share = plaintext
+            .as_slice()
+            .try_into()
+            .map_err(|_| FastCryptoError::InvalidInput)
+            .and_then(RistrettoScalar::from_byte_array);
+        plaintext.zeroize();
+        let share = share?;
+
+        if !self.commitment.is_valid_share(self.index, &share) {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        Ok(DecryptedShard { shard: self, share })
+    }
+}
+
+impl DecryptedShard<'_> {
+    /// Recover the exported key from at least `threshold` decrypted shards of the same export.
+    pub fn recover(shards: &[DecryptedShard<'_>]) -> Result<Vec<u8>, FastCryptoError> {
+        let first = shards.first().ok_or(FastCryptoError::InvalidInput)?.shard;
+        if shards.iter().any(|s| {
+            s.shard.threshold != first.threshold
+                || s.shard.commitment != first.commitment
+                || s.shard.encrypted_key != first.encrypted_key
+        }) {
+            return Err(FastCryptoError::InvalidInput);
+        }
+
+        let mut evaluations: Vec<Eval<RistrettoScalar>> = shards
+            .iter()
+            .map(|s| Eval {
+                index: s.shard.index,
+                value: s.share,
+            })
+            .collect();
+        evaluations.sort_by_key(|e| e.index);
+        evaluations.dedup_by_key(|e| e.index);
+        evaluations.truncate(first.threshold as usize);
+        let secret = Poly::<RistrettoScalar>::recover_c0(first.threshold, &evaluations)?;
+
+        let mut kek = key_encryption_key(&secret);
+        let key = first
+            .encrypted_key
+            .decrypt(&kek, &commitment_bytes(&first.commitment));
+        kek.zeroize();
+        key
+    }
+}
+
+fn commitment_bytes(commitment: &PublicPoly<RistrettoPoint>) -> Vec<u8> {
+    bincode::serialize(commitment).expect("Serialization should succeed")
+}
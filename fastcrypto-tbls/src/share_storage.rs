@@ -0,0 +1,206 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Persistence of threshold key shares and DKG state, encrypted with a passphrase of the operator.
+//!
+//! - The value is serialized with bincode and encrypted with AES-256-GCM using a key derived from
+//!   the passphrase with PBKDF2-HMAC-SHA3-256 and a random salt.
+//! - The format version, the kind of the stored value and the KDF parameters are authenticated as
+//!   associated data, so a file cannot be opened as another kind of value.
+//! - [SealedState::write_to_file] writes to a temporary file in the same directory, syncs it and
+//!   renames it, so a crash never leaves a partially written file at the destination.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto_tbls::share_storage::*;
+//! # use rand::thread_rng;
+//! let sealed = SealedState::seal(StateKind::KeyShare, &42u64, b"passphrase", 10, &mut thread_rng()).unwrap();
+//! let bytes = sealed.to_bytes();
+//! let opened: u64 = SealedState::from_bytes(&bytes).unwrap().open(StateKind::KeyShare, b"passphrase").unwrap();
+//! assert_eq!(opened, 42);
+//! ```
+
+use crate::shard_export::{pbkdf2, EncryptedBlob, SALT_LENGTH};
+use fastcrypto::error::FastCryptoError;
+use fastcrypto::traits::AllowedRng;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use zeroize::Zeroize;
+
+pub use crate::shard_export::DEFAULT_PBKDF2_ITERATIONS;
+
+/// The maximal number of PBKDF2 iterations, so opening a modified file cannot take arbitrarily long.
+pub const MAX_PBKDF2_ITERATIONS: u32 = 10 * DEFAULT_PBKDF2_ITERATIONS;
+
+/// Current version of the storage format.
+pub const STORAGE_VERSION: u8 = 1;
+
+const MAGIC: [u8; 8] = *b"FCTBLS\0\0";
+
+/// The kind of a stored value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StateKind {
+    /// The output of a DKG, e.g. a [crate::dkg::DkgOutput], or another threshold key share.
+    KeyShare,
+    /// The state of a DKG which has not finished yet, e.g. a [crate::dkg::Party] and the shares
+    /// received so far.
+    DkgState,
+}
+
+/// An encrypted value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SealedState {
+    magic: [u8; 8],
+    version: u8,
+    kind: StateKind,
+    pbkdf2_iterations: u32,
+    salt: Vec<u8>,
+    encrypted_value: EncryptedBlob,
+}
+
+/// The authenticated header of a [SealedState].
+#[derive(Serialize)]
+struct Header<'a> {
+    magic: [u8; 8],
+    version: u8,
+    kind: StateKind,
+    pbkdf2_iterations: u32,
+    salt: &'a [u8],
+}
+
+impl SealedState {
+    /// Encrypt a value with the given passphrase. The number of PBKDF2 iterations must be positive
+    /// and at most [MAX_PBKDF2_ITERATIONS].
+    pub fn seal<T: Serialize, R: AllowedRng>(
+        kind: StateKind,
+        value: &T,
+        passphrase: &[u8],
+        pbkdf2_iterations: u32,
+        rng: &mut R,
+    ) -> Result<Self, FastCryptoError> {
+        if pbkdf2_iterations == 0 || pbkdf2_iterations > MAX_PBKDF2_ITERATIONS {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let mut salt = vec![0u8; SALT_LENGTH];
+        rng.fill_bytes(&mut salt);
+        let mut sealed = Self {
+            magic: MAGIC,
+            version: STORAGE_VERSION,
+            kind,
+            pbkdf2_iterations,
+            salt,
+            encrypted_value: EncryptedBlob {
+                iv: vec![],
+                ciphertext: vec![],
+            },
+        };
+
+        let mut plaintext = bincode::serialize(value).map_err(|_| FastCryptoError::InvalidInput)?;
+        let mut key = pbkdf2(passphrase, &sealed.salt, pbkdf2_iterations);
+        sealed.encrypted_value = EncryptedBlob::encrypt(&key, &sealed.header(), &plaintext, rng);
+        key.zeroize();
+        plaintext.zeroize();
+        Ok(sealed)
+    }
+
+    /// Decrypt a value of the given kind with the given passphrase. Fails if the passphrase is
+    /// wrong, if the value was modified or if it is of another kind. The number of PBKDF2
+    /// iterations is checked before deriving the key.
+    pub fn open<T: DeserializeOwned>(
+        &self,
+        kind: StateKind,
+        passphrase: &[u8],
+    ) -> Result<T, FastCryptoError> {
+        if self.magic != MAGIC
+            || self.version != STORAGE_VERSION
+            || self.kind != kind
+            || self.pbkdf2_iterations == 0
+            || self.pbkdf2_iterations > MAX_PBKDF2_ITERATIONS
+        {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let mut key = pbkdf2(passphrase, &self.salt, self.pbkdf2_iterations);
+        let plaintext = self.encrypted_value.decrypt(&key, &self.header());
+        key.zeroize();
+        let mut plaintext = plaintext?;
+        let value = bincode::deserialize(&plaintext).map_err(|_| FastCryptoError::InvalidInput);
+        plaintext.zeroize();
+        value
+    }
+
+    /// The kind of the stored value.
+    pub fn kind(&self) -> StateKind {
+        self.kind
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bincode::serialize(self).expect("Serialization should succeed")
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        let sealed: Self =
+            bincode::deserialize(bytes).map_err(|_| FastCryptoError::InvalidInput)?;
+        if sealed.magic != MAGIC {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(sealed)
+    }
+
+    /// Atomically replace the file at `path` with this value.
+    pub fn write_to_file(&self, path: &Path) -> Result<(), FastCryptoError> {
+        write_atomic(path, &self.to_bytes()).map_err(io_error)
+    }
+
+    pub fn read_from_file(path: &Path) -> Result<Self, FastCryptoError> {
+        Self::from_bytes(&fs::read(path).map_err(io_error)?)
+    }
+
+    fn header(&self) -> Vec<u8> {
+        bincode::serialize(&Header {
+            magic: self.magic,
+            version: self.version,
+            kind: self.kind,
+            pbkdf2_iterations: self.pbkdf2_iterations,
+            salt: &self.salt,
+        })
+        .expect("Serialization should succeed")
+    }
+}
+
+/// Write `contents` to a temporary file next to `path`, sync it and rename it to `path`.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "Not a file"))?;
+    let mut tmp_name = file_name.to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+
+    let result = (|| {
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, path)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+        return result;
+    }
+
+    // Sync the directory so the rename is durable.
+    #[cfg(unix)]
+    {
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        fs::File::open(dir)?.sync_all()?;
+    }
+    Ok(())
+}
+
+fn io_error(e: std::io::Error) -> FastCryptoError {
+    FastCryptoError::GeneralError(e.to_string())
+}
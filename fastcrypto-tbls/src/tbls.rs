@@ -5,9 +5,10 @@
 // modified for our needs.
 
 use crate::polynomial::Poly;
-use crate::types::IndexedValue;
+use crate::types::{IndexedValue, ShareIndex};
 use fastcrypto::error::FastCryptoError;
 use fastcrypto::groups::{GroupElement, HashToGroupElement, Scalar};
+use fastcrypto::traits::AllowedRng;
 
 pub type Share<S> = IndexedValue<S>;
 pub type PartialSignature<S> = IndexedValue<S>;
@@ -70,4 +71,59 @@ pub trait ThresholdBls {
         // IndexedValue<S>.
         Poly::<Self::Signature>::recover_c0(threshold, partials)
     }
+
+    /// Identify the invalid partial signatures, e.g. after [ThresholdBls::aggregate] returned an
+    /// invalid signature. Random linear combinations of the partial signatures are verified in
+    /// batches and invalid batches are bisected, so only a few pairings are needed if only a few
+    /// partial signatures are invalid. Returns the indices of the invalid partial signatures.
+    fn find_invalid_partial_signatures<R: AllowedRng>(
+        vss_pk: &Poly<Self::Public>,
+        msg: &[u8],
+        partials: &[PartialSignature<Self::Signature>],
+        rng: &mut R,
+    ) -> Vec<ShareIndex> {
+        // The random coefficients prevent invalid partial signatures from cancelling out.
+        let weighted = partials
+            .iter()
+            .map(|partial| {
+                let r = Self::Private::rand(rng);
+                (
+                    partial.index,
+                    vss_pk.eval(partial.index).value * r,
+                    partial.value * r,
+                )
+            })
+            .collect::<Vec<_>>();
+        let mut invalid = Vec::new();
+        bisect::<Self>(msg, &weighted, &mut invalid);
+        invalid
+    }
+}
+
+/// Add the indices of the invalid partial signatures in `batch` to `invalid`. If the sum of a batch
+/// is invalid, at least one of its halves is invalid too.
+fn bisect<T: ThresholdBls + ?Sized>(
+    msg: &[u8],
+    batch: &[(ShareIndex, T::Public, T::Signature)],
+    invalid: &mut Vec<ShareIndex>,
+) {
+    if batch.is_empty() {
+        return;
+    }
+    let pk = batch
+        .iter()
+        .fold(T::Public::zero(), |sum, (_, pk, _)| sum + pk);
+    let sig = batch
+        .iter()
+        .fold(T::Signature::zero(), |sum, (_, _, sig)| sum + sig);
+    if T::verify(&pk, msg, &sig).is_ok() {
+        return;
+    }
+    if batch.len() == 1 {
+        invalid.push(batch[0].0);
+        return;
+    }
+    let (left, right) = batch.split_at(batch.len() / 2);
+    bisect::<T>(msg, left, invalid);
+    bisect::<T>(msg, right, invalid);
 }
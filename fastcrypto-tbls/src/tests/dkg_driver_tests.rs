@@ -0,0 +1,155 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::dkg::{Party, PkiNode};
+use crate::dkg_driver::{
+    DkgDriver, DkgFailure, DkgFailureReason, DkgMessage, DkgPhase, PhaseTimeouts,
+};
+use crate::ecies;
+use crate::random_oracle::RandomOracle;
+use crate::tbls::ThresholdBls;
+use crate::types::{ShareIndex, ThresholdBls12381MinSig};
+use fastcrypto::groups::bls12381::G2Element;
+use fastcrypto::groups::ristretto255::RistrettoPoint;
+use rand::thread_rng;
+use std::time::Duration;
+use tokio::sync::mpsc::{channel, Receiver, Sender};
+
+type G = G2Element;
+type EG = RistrettoPoint;
+type Message = DkgMessage<G, EG>;
+
+const TIMEOUTS: PhaseTimeouts = PhaseTimeouts {
+    first_round: Duration::from_secs(10),
+    second_round: Duration::from_secs(10),
+};
+
+fn setup_parties(n: usize, threshold: u32) -> Vec<Party<G, EG>> {
+    let keys = (1..=n)
+        .map(|id| {
+            let sk = ecies::PrivateKey::<EG>::new(&mut thread_rng());
+            let pk = ecies::PublicKey::<EG>::from_private_key(&sk);
+            (ShareIndex::new(id as u32).unwrap(), sk, pk)
+        })
+        .collect::<Vec<_>>();
+    let nodes = keys
+        .iter()
+        .map(|(id, _, pk)| PkiNode::<EG> {
+            id: *id,
+            pk: pk.clone(),
+        })
+        .collect::<Vec<_>>();
+    keys.into_iter()
+        .map(|(_, sk, _)| {
+            Party::<G, EG>::new(
+                sk,
+                nodes.clone(),
+                threshold,
+                RandomOracle::new("dkg"),
+                &mut thread_rng(),
+            )
+            .unwrap()
+        })
+        .collect()
+}
+
+/// Deliver all messages to all parties in the same order.
+async fn broadcast(mut outgoing: Receiver<Message>, incoming: Vec<Sender<Message>>) {
+    while let Some(message) = outgoing.recv().await {
+        for sender in &incoming {
+            let _ = sender.send(message.clone()).await;
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_dkg_driver_e2e() {
+    let parties = setup_parties(4, 2);
+    let threshold = parties[0].threshold();
+    let (outgoing_sender, outgoing_receiver) = channel(100);
+    let (incoming_senders, mut drivers): (Vec<_>, Vec<_>) = parties
+        .into_iter()
+        .map(|party| {
+            let (sender, receiver) = channel(100);
+            (sender, Some((DkgDriver::new(party, TIMEOUTS, 3), receiver)))
+        })
+        .unzip();
+    let mut run = |i: usize| {
+        let (driver, receiver) = drivers[i].take().unwrap();
+        let outgoing = outgoing_sender.clone();
+        async move { driver.run(receiver, outgoing, &mut thread_rng()).await }
+    };
+    let (d1, d2, d3, d4) = (run(0), run(1), run(2), run(3));
+    drop(outgoing_sender);
+    let (o1, o2, o3, o4, _) = tokio::join!(
+        d1,
+        d2,
+        d3,
+        d4,
+        broadcast(outgoing_receiver, incoming_senders)
+    );
+    let outputs = [o1.unwrap(), o2.unwrap(), o3.unwrap(), o4.unwrap()];
+    assert!(outputs.iter().all(|o| o.vss_pk == outputs[0].vss_pk));
+
+    type S = ThresholdBls12381MinSig;
+    let msg = b"test";
+    let sigs = vec![
+        S::partial_sign(&outputs[1].share, msg),
+        S::partial_sign(&outputs[3].share, msg),
+    ];
+    let sig = S::aggregate(threshold, &sigs).unwrap();
+    S::verify(outputs[0].vss_pk.c0(), msg, &sig).unwrap();
+}
+
+#[tokio::test]
+async fn test_dkg_driver_failures() {
+    let timeouts = PhaseTimeouts {
+        first_round: Duration::from_millis(50),
+        second_round: Duration::from_millis(50),
+    };
+
+    // No other party sends a message.
+    let party = setup_parties(4, 2).remove(0);
+    let (_incoming_sender, incoming) = channel(100);
+    let (outgoing, _outgoing_receiver) = channel(100);
+    let result = DkgDriver::new(party, timeouts, 3)
+        .run(incoming, outgoing, &mut thread_rng())
+        .await;
+    assert_eq!(
+        result.unwrap_err(),
+        DkgFailure {
+            phase: DkgPhase::FirstRound,
+            reason: DkgFailureReason::Timeout,
+            received_from: vec![],
+        }
+    );
+
+    // The transport is closed after our own first message was delivered.
+    let party = setup_parties(4, 2).remove(0);
+    let (incoming_sender, incoming) = channel(100);
+    let (outgoing, mut outgoing_receiver) = channel(100);
+    let run = DkgDriver::new(party, timeouts, 3).run(incoming, outgoing, &mut thread_rng());
+    let echo = async move {
+        let message = outgoing_receiver.recv().await.unwrap();
+        incoming_sender.send(message).await.unwrap();
+    };
+    let (result, _) = tokio::join!(run, echo);
+    assert_eq!(
+        result.unwrap_err(),
+        DkgFailure {
+            phase: DkgPhase::FirstRound,
+            reason: DkgFailureReason::IncomingClosed,
+            received_from: vec![ShareIndex::new(1).unwrap()],
+        }
+    );
+
+    // Nobody receives our messages.
+    let party = setup_parties(4, 2).remove(0);
+    let (_incoming_sender, incoming) = channel(100);
+    let (outgoing, outgoing_receiver) = channel(100);
+    drop(outgoing_receiver);
+    let result = DkgDriver::new(party, timeouts, 3)
+        .run(incoming, outgoing, &mut thread_rng())
+        .await;
+    assert_eq!(result.unwrap_err().reason, DkgFailureReason::OutgoingClosed);
+}
@@ -1,7 +1,9 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::dkg::{Party, PkiNode};
+use crate::dkg::{
+    Complaint, Party, PkiNode, SecondMessage, ShareEncryption, FIRST_ROUND, SECOND_ROUND,
+};
 use crate::ecies;
 use crate::random_oracle::RandomOracle;
 use crate::tbls::ThresholdBls;
@@ -29,6 +31,14 @@ fn gen_ecies_keys(n: usize) -> Vec<(ShareIndex, ecies::PrivateKey<EG>, ecies::Pu
 fn setup_party(
     id: usize,
     keys: &[(ShareIndex, ecies::PrivateKey<EG>, ecies::PublicKey<EG>)],
+) -> Party<G, EG> {
+    setup_party_in_session(id, keys, "dkg")
+}
+
+fn setup_party_in_session(
+    id: usize,
+    keys: &[(ShareIndex, ecies::PrivateKey<EG>, ecies::PublicKey<EG>)],
+    session: &str,
 ) -> Party<G, EG> {
     let nodes = keys
         .iter()
@@ -41,7 +51,7 @@ fn setup_party(
         keys.get(id - 1).unwrap().1.clone(),
         nodes,
         2,
-        RandomOracle::new("dkg"),
+        RandomOracle::new(session),
         &mut thread_rng(),
     )
     .unwrap()
@@ -121,3 +131,132 @@ fn test_dkg_e2e_4_parties_threshold_2() {
     let sig = S::aggregate(d1.threshold(), &sigs).unwrap();
     S::verify(o1.vss_pk.c0(), &MSG, &sig).unwrap();
 }
+
+#[test]
+fn test_dkg_e2e_anonymous_shares() {
+    let keys = gen_ecies_keys(4);
+    let setup = |id| setup_party(id, &keys).with_share_encryption(ShareEncryption::Anonymous);
+    let d1 = setup(1);
+    let d2 = setup(2);
+    let d3 = setup(3);
+    let d4 = setup(4);
+
+    let r1m1 = d1.create_first_message(&mut thread_rng());
+    assert!(r1m1.encrypted_shares.is_empty());
+    assert_eq!(r1m1.anonymous_shares.len(), 3);
+    // Messages with addressed shares are ignored.
+    let addressed = setup_party(3, &keys).create_first_message(&mut thread_rng());
+    let (shares, r2m1) = d1
+        .create_second_message(&[r1m1.clone(), addressed], &mut thread_rng())
+        .unwrap();
+    assert_eq!(shares.len(), 1);
+    assert!(r2m1.complaints.is_empty());
+
+    // Replace one of the shares of d2 by garbage (emulating a cheating party).
+    let mut r1m2 = d2.create_first_message(&mut thread_rng());
+    r1m2.anonymous_shares[0] = keys[0].2.encrypt(&[0u8; 64], &mut thread_rng());
+    let r1_all = vec![r1m1, r1m2];
+
+    let (shares1, r2m1) = d1
+        .create_second_message(&r1_all[..], &mut thread_rng())
+        .unwrap();
+    let (shares3, r2m3) = d3
+        .create_second_message(&r1_all[..], &mut thread_rng())
+        .unwrap();
+    let (shares4, r2m4) = d4
+        .create_second_message(&r1_all[..], &mut thread_rng())
+        .unwrap();
+
+    // Exactly one of the receivers of d2 complains.
+    let complaints = [&r2m1, &r2m3, &r2m4]
+        .iter()
+        .map(|m| m.complaints.len())
+        .sum::<usize>();
+    assert_eq!(complaints, 1);
+
+    // A false complaint against d1 is rejected and d3's own complaints are ignored.
+    let false_complaint = SecondMessage {
+        header: d3.header(SECOND_ROUND),
+        sender: ShareIndex::new(3).unwrap(),
+        transcript: d3.transcript(&r1_all),
+        complaints: vec![Complaint::NoShare(ShareIndex::new(1).unwrap())],
+    };
+    let r2_all = vec![r2m1.clone(), false_complaint, r2m4.clone()];
+    let shares4_with_false_complaint = d4
+        .process_responses(&r1_all, &r2_all, shares4.clone(), 3)
+        .unwrap();
+    assert!(shares4_with_false_complaint.contains_key(&ShareIndex::new(1).unwrap()));
+
+    let r2_all = vec![r2m1, r2m3, r2m4];
+    let shares1 = d1.process_responses(&r1_all, &r2_all, shares1, 3).unwrap();
+    let shares3 = d3.process_responses(&r1_all, &r2_all, shares3, 3).unwrap();
+    let shares4 = d4.process_responses(&r1_all, &r2_all, shares4, 3).unwrap();
+    assert_eq!(shares1.len(), 1);
+    assert_eq!(shares3.len(), 1);
+    assert_eq!(shares4.len(), 1);
+
+    let o1 = d1.aggregate(&r1_all, shares1);
+    let o3 = d3.aggregate(&r1_all, shares3);
+
+    type S = ThresholdBls12381MinSig;
+    let sigs = vec![
+        S::partial_sign(&o1.share, &MSG),
+        S::partial_sign(&o3.share, &MSG),
+    ];
+    let sig = S::aggregate(d1.threshold(), &sigs).unwrap();
+    S::verify(o1.vss_pk.c0(), &MSG, &sig).unwrap();
+    assert_eq!(o1.vss_pk, o3.vss_pk);
+}
+
+#[test]
+fn test_dkg_session_binding() {
+    let keys = gen_ecies_keys(4);
+    let d1 = setup_party(1, &keys);
+    let d2 = setup_party(2, &keys);
+    let other_d2 = setup_party_in_session(2, &keys, "dkg-other-session");
+    assert_eq!(d1.session_id(), d2.session_id());
+    assert_ne!(d1.session_id(), other_d2.session_id());
+
+    let r1m1 = d1.create_first_message(&mut thread_rng());
+    let r1m2 = d2.create_first_message(&mut thread_rng());
+    assert_eq!(r1m1.header, d1.header(FIRST_ROUND));
+
+    // A first message replayed from another session is ignored.
+    let replayed = other_d2.create_first_message(&mut thread_rng());
+    let (shares, r2m1) = d1
+        .create_second_message(&[r1m1.clone(), replayed], &mut thread_rng())
+        .unwrap();
+    assert_eq!(shares.len(), 1);
+    assert!(r2m1.complaints.is_empty());
+
+    let r1_all = vec![r1m1, r1m2];
+    let (shares1, r2m1) = d1
+        .create_second_message(&r1_all, &mut thread_rng())
+        .unwrap();
+    let (_, r2m2) = d2
+        .create_second_message(&r1_all, &mut thread_rng())
+        .unwrap();
+    assert_eq!(r2m1.transcript, d2.transcript(&r1_all));
+    // The transcript does not depend on the order of the first messages.
+    assert_eq!(
+        r2m1.transcript,
+        d1.transcript(&[r1_all[1].clone(), r1_all[0].clone()])
+    );
+    assert!(d1
+        .process_responses(&r1_all, &[r2m1.clone(), r2m2.clone()], shares1.clone(), 2)
+        .is_ok());
+
+    // Second messages from another session, of another round or with another transcript are
+    // ignored, so there are too few second messages.
+    let mut r2m2_other_session = r2m2.clone();
+    r2m2_other_session.header = other_d2.header(SECOND_ROUND);
+    let mut r2m2_other_round = r2m2.clone();
+    r2m2_other_round.header = d2.header(FIRST_ROUND);
+    let mut r2m2_other_transcript = r2m2;
+    r2m2_other_transcript.transcript = [0; 32];
+    for r2m2 in [r2m2_other_session, r2m2_other_round, r2m2_other_transcript] {
+        assert!(d1
+            .process_responses(&r1_all, &[r2m1.clone(), r2m2], shares1.clone(), 2)
+            .is_err());
+    }
+}
@@ -0,0 +1,41 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::nizk::DdhTupleNizk;
+use crate::random_oracle::RandomOracle;
+use fastcrypto::groups::ristretto255::{RistrettoPoint, RistrettoScalar};
+use fastcrypto::groups::{GroupElement, Scalar};
+use rand::prelude::*;
+
+type Group = RistrettoPoint;
+
+#[test]
+fn test_ddh_tuple_nizk() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let random_oracle = RandomOracle::new("test");
+    let x = RistrettoScalar::rand(&mut rng);
+    let h = Group::generator() * RistrettoScalar::rand(&mut rng);
+    let x_g = Group::generator() * x;
+    let x_h = h * x;
+    let proof = DdhTupleNizk::create(&x, &h, &x_g, &x_h, &random_oracle, &mut rng);
+    assert!(proof.verify(&h, &x_g, &x_h, &random_oracle).is_ok());
+
+    // Different statements or random oracles.
+    assert!(proof
+        .verify(&h, &x_g, &(x_h + Group::generator()), &random_oracle)
+        .is_err());
+    assert!(proof
+        .verify(&(h + Group::generator()), &x_g, &x_h, &random_oracle)
+        .is_err());
+    assert!(proof
+        .verify(&h, &x_g, &x_h, &RandomOracle::new("other"))
+        .is_err());
+
+    // Not a DDH tuple.
+    let proof = DdhTupleNizk::create(&x, &h, &x_g, &(x_h + h), &random_oracle, &mut rng);
+    assert!(proof.verify(&h, &x_g, &(x_h + h), &random_oracle).is_err());
+
+    let bytes = bincode::serialize(&proof).unwrap();
+    let deserialized: DdhTupleNizk<Group> = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(deserialized, proof);
+}
@@ -79,3 +79,72 @@ fn interpolation_insufficient_shares() {
 
     Poly::<RistrettoScalar>::recover_c0(threshold, &shares).unwrap_err();
 }
+
+#[test]
+fn interpolation_at_arbitrary_point() {
+    let poly = Poly::<RistrettoScalar>::rand(3, &mut thread_rng());
+    let shares = [2, 5, 7, 11]
+        .iter()
+        .map(|i| poly.eval(ShareIndex::new(*i).unwrap()))
+        .collect::<Vec<_>>();
+
+    let x = ShareIndex::new(13).unwrap();
+    let expected = poly.eval(x).value;
+    let x = RistrettoScalar::from(x.get() as u64);
+    assert_eq!(interpolate_at(&x, &shares).unwrap(), expected);
+    assert_eq!(interpolate_at_zero(&shares).unwrap(), *poly.c0());
+
+    // At one of the indices, the evaluation is returned as is.
+    let x = RistrettoScalar::from(5u64);
+    assert_eq!(interpolate_at(&x, &shares).unwrap(), shares[1].value);
+}
+
+#[test]
+fn interpolation_in_the_exponent() {
+    let poly = Poly::<RistrettoScalar>::rand(2, &mut thread_rng());
+    let public_poly: Poly<RistrettoPoint> = poly.commit();
+    let shares = (1..=3)
+        .map(|i| public_poly.eval(ShareIndex::new(i).unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        interpolate_at_zero(&shares).unwrap(),
+        RistrettoPoint::generator() * poly.c0()
+    );
+}
+
+#[test]
+fn lagrange_coefficients_sum_to_one() {
+    let indices = [1, 3, 4, 9]
+        .iter()
+        .map(|i| ShareIndex::new(*i).unwrap())
+        .collect::<Vec<_>>();
+    let x = RistrettoScalar::rand(&mut thread_rng());
+    let sum = lagrange_coefficients(&x, &indices)
+        .unwrap()
+        .into_iter()
+        .fold(RistrettoScalar::zero(), |acc, l| acc + l);
+    assert_eq!(sum, RistrettoScalar::generator());
+
+    let at_zero = lagrange_coefficients_at_zero::<RistrettoScalar>(&indices[..1]).unwrap();
+    assert_eq!(at_zero, vec![RistrettoScalar::generator()]);
+}
+
+#[test]
+fn lagrange_coefficients_invalid_indices() {
+    let one = ShareIndex::new(1).unwrap();
+    let two = ShareIndex::new(2).unwrap();
+    assert!(lagrange_coefficients_at_zero::<RistrettoScalar>(&[]).is_err());
+    assert!(lagrange_coefficients_at_zero::<RistrettoScalar>(&[one, two, one]).is_err());
+
+    let shares = vec![
+        Eval {
+            index: one,
+            value: RistrettoScalar::generator(),
+        },
+        Eval {
+            index: one,
+            value: RistrettoScalar::zero(),
+        },
+    ];
+    assert!(interpolate_at_zero(&shares).is_err());
+}
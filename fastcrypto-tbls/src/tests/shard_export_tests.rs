@@ -0,0 +1,114 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::shard_export::{DecryptedShard, KeyShard};
+use fastcrypto::error::FastCryptoError;
+use rand::prelude::*;
+
+const ITERATIONS: u32 = 10;
+const KEY: &[u8] = b"a signing key which should be backed up";
+
+fn passwords() -> Vec<&'static [u8]> {
+    vec![
+        b"password 1",
+        b"password 2",
+        b"password 3",
+        b"password 4",
+        b"password 5",
+    ]
+}
+
+#[test]
+fn test_export_and_recover() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let passwords = passwords();
+    let shards = KeyShard::export(KEY, 3, &passwords, ITERATIONS, &mut rng).unwrap();
+    assert_eq!(shards.len(), passwords.len());
+    assert!(shards.iter().all(|s| s.threshold() == 3));
+
+    // Any three shards recover the key.
+    for subset in [[0, 1, 2], [0, 2, 4], [4, 3, 1]] {
+        let decrypted = subset
+            .iter()
+            .map(|&i| shards[i].decrypt(passwords[i]).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(DecryptedShard::recover(&decrypted).unwrap(), KEY);
+    }
+
+    // More shards than needed also work.
+    let decrypted = shards
+        .iter()
+        .zip(passwords.iter())
+        .map(|(s, p)| s.decrypt(p).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(DecryptedShard::recover(&decrypted).unwrap(), KEY);
+
+    // Two shards are not enough, and duplicates do not count.
+    assert!(DecryptedShard::recover(&decrypted[..2]).is_err());
+    let duplicates = vec![
+        shards[0].decrypt(passwords[0]).unwrap(),
+        shards[0].decrypt(passwords[0]).unwrap(),
+        shards[1].decrypt(passwords[1]).unwrap(),
+    ];
+    assert!(DecryptedShard::recover(&duplicates).is_err());
+}
+
+#[test]
+fn test_serialization() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let passwords = passwords();
+    let shards = KeyShard::export(KEY, 2, &passwords, ITERATIONS, &mut rng).unwrap();
+    let serialized = bincode::serialize(&shards[1]).unwrap();
+    let deserialized: KeyShard = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(deserialized, shards[1]);
+    assert_eq!(deserialized.index().get(), 2);
+
+    let decrypted = vec![
+        shards[0].decrypt(passwords[0]).unwrap(),
+        deserialized.decrypt(passwords[1]).unwrap(),
+    ];
+    assert_eq!(DecryptedShard::recover(&decrypted).unwrap(), KEY);
+}
+
+#[test]
+fn test_failures() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let passwords = passwords();
+
+    assert!(KeyShard::export(KEY, 0, &passwords, ITERATIONS, &mut rng).is_err());
+    assert!(KeyShard::export(KEY, 6, &passwords, ITERATIONS, &mut rng).is_err());
+    assert!(KeyShard::export(KEY, 3, &passwords, 0, &mut rng).is_err());
+
+    let shards = KeyShard::export(KEY, 2, &passwords, ITERATIONS, &mut rng).unwrap();
+
+    // Wrong password.
+    assert_eq!(
+        shards[0].decrypt(passwords[1]).unwrap_err(),
+        FastCryptoError::GeneralOpaqueError
+    );
+
+    // Shards from different exports cannot be combined.
+    let other_shards = KeyShard::export(KEY, 2, &passwords, ITERATIONS, &mut rng).unwrap();
+    let decrypted = vec![
+        shards[0].decrypt(passwords[0]).unwrap(),
+        other_shards[1].decrypt(passwords[1]).unwrap(),
+    ];
+    assert!(DecryptedShard::recover(&decrypted).is_err());
+
+    // Modified shards are rejected.
+    let serialized = bincode::serialize(&shards[0]).unwrap();
+    let mut modified = serialized.clone();
+    *modified.last_mut().unwrap() ^= 1;
+    let modified: KeyShard = bincode::deserialize(&modified).unwrap();
+    assert!(modified.decrypt(passwords[0]).is_err());
+    let mut modified = serialized;
+    // The threshold is encoded after the version and the index.
+    modified[5] ^= 1;
+    let modified: KeyShard = bincode::deserialize(&modified).unwrap();
+    assert_eq!(
+        modified.decrypt(passwords[0]).unwrap_err(),
+        FastCryptoError::InvalidInput
+    );
+
+    assert!(DecryptedShard::recover(&[]).is_err());
+}
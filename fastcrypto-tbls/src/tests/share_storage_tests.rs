@@ -0,0 +1,165 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::dkg::{DkgOutput, Party, PkiNode, SharesMap};
+use crate::ecies;
+use crate::random_oracle::RandomOracle;
+use crate::share_storage::*;
+use crate::types::ShareIndex;
+use fastcrypto::error::FastCryptoError;
+use fastcrypto::groups::bls12381::G2Element;
+use fastcrypto::groups::ristretto255::RistrettoPoint;
+use rand::prelude::*;
+
+type G = G2Element;
+type EG = RistrettoPoint;
+
+const ITERATIONS: u32 = 10;
+const PASSPHRASE: &[u8] = b"correct horse battery staple";
+
+fn setup_parties(n: u32) -> Vec<Party<G, EG>> {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let sks = (0..n)
+        .map(|_| ecies::PrivateKey::<EG>::new(&mut rng))
+        .collect::<Vec<_>>();
+    let nodes = sks
+        .iter()
+        .enumerate()
+        .map(|(i, sk)| PkiNode {
+            id: ShareIndex::new(i as u32 + 1).unwrap(),
+            pk: ecies::PublicKey::from_private_key(sk),
+        })
+        .collect::<Vec<_>>();
+    sks.into_iter()
+        .map(|sk| Party::new(sk, nodes.clone(), 2, RandomOracle::new("dkg"), &mut rng).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_seal_and_open_dkg_state_and_output() {
+    let mut rng = StdRng::from_seed([1; 32]);
+    let parties = setup_parties(3);
+    let first_messages = vec![
+        parties[0].create_first_message(&mut rng),
+        parties[1].create_first_message(&mut rng),
+    ];
+    let (shares, _) = parties[0]
+        .create_second_message(&first_messages, &mut rng)
+        .unwrap();
+
+    // Persist the state after the first round and continue from the restored state.
+    let state = (parties[0].clone(), shares.clone());
+    let sealed = SealedState::seal(
+        StateKind::DkgState,
+        &state,
+        PASSPHRASE,
+        ITERATIONS,
+        &mut rng,
+    )
+    .unwrap();
+    assert_eq!(sealed.kind(), StateKind::DkgState);
+    let restored = SealedState::from_bytes(&sealed.to_bytes()).unwrap();
+    let (party, restored_shares): (Party<G, EG>, SharesMap<G>) =
+        restored.open(StateKind::DkgState, PASSPHRASE).unwrap();
+    assert!(party == parties[0]);
+    assert_eq!(restored_shares, shares);
+
+    let output = party.aggregate(&first_messages, restored_shares);
+    let sealed = SealedState::seal(
+        StateKind::KeyShare,
+        &output,
+        PASSPHRASE,
+        ITERATIONS,
+        &mut rng,
+    )
+    .unwrap();
+    let restored: DkgOutput<G, EG> = sealed.open(StateKind::KeyShare, PASSPHRASE).unwrap();
+    assert_eq!(restored.share, output.share);
+    assert_eq!(restored.vss_pk, output.vss_pk);
+    assert_eq!(restored.nodes, output.nodes);
+}
+
+#[test]
+fn test_open_fails() {
+    let mut rng = StdRng::from_seed([1; 32]);
+    let sealed = SealedState::seal(
+        StateKind::KeyShare,
+        &42u64,
+        PASSPHRASE,
+        ITERATIONS,
+        &mut rng,
+    )
+    .unwrap();
+    assert_eq!(sealed.open::<u64>(StateKind::KeyShare, PASSPHRASE), Ok(42));
+
+    // Wrong passphrase or kind.
+    assert!(sealed.open::<u64>(StateKind::KeyShare, b"wrong").is_err());
+    assert_eq!(
+        sealed.open::<u64>(StateKind::DkgState, PASSPHRASE),
+        Err(FastCryptoError::InvalidInput)
+    );
+
+    // Modifications of the salt, the ciphertext or the tag are detected. The salt starts after
+    // the magic, the version, the kind, the iterations and the length of the salt.
+    let bytes = sealed.to_bytes();
+    for i in (25..57).chain(bytes.len() - 24..bytes.len()) {
+        let mut modified = bytes.clone();
+        modified[i] ^= 1;
+        if let Ok(modified) = SealedState::from_bytes(&modified) {
+            assert!(modified
+                .open::<u64>(StateKind::KeyShare, PASSPHRASE)
+                .is_err());
+        }
+    }
+    assert!(SealedState::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+    // Too many iterations are rejected before running PBKDF2. The iterations follow the magic, the
+    // version and the kind.
+    let mut modified = bytes.clone();
+    modified[13..17].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert_eq!(
+        SealedState::from_bytes(&modified)
+            .unwrap()
+            .open::<u64>(StateKind::KeyShare, PASSPHRASE),
+        Err(FastCryptoError::InvalidInput)
+    );
+
+    assert_eq!(
+        SealedState::seal(StateKind::KeyShare, &42u64, PASSPHRASE, 0, &mut rng),
+        Err(FastCryptoError::InvalidInput)
+    );
+    assert_eq!(
+        SealedState::seal(
+            StateKind::KeyShare,
+            &42u64,
+            PASSPHRASE,
+            MAX_PBKDF2_ITERATIONS + 1,
+            &mut rng
+        ),
+        Err(FastCryptoError::InvalidInput)
+    );
+}
+
+#[test]
+fn test_write_and_read_file() {
+    let mut rng = StdRng::from_seed([1; 32]);
+    let dir = std::env::temp_dir().join(format!("fastcrypto-tbls-{}", rng.gen::<u64>()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("share");
+
+    let sealed =
+        SealedState::seal(StateKind::KeyShare, &1u64, PASSPHRASE, ITERATIONS, &mut rng).unwrap();
+    sealed.write_to_file(&path).unwrap();
+    assert_eq!(SealedState::read_from_file(&path).unwrap(), sealed);
+
+    // Writing again replaces the file and leaves no temporary file behind.
+    let sealed =
+        SealedState::seal(StateKind::KeyShare, &2u64, PASSPHRASE, ITERATIONS, &mut rng).unwrap();
+    sealed.write_to_file(&path).unwrap();
+    let read = SealedState::read_from_file(&path).unwrap();
+    assert_eq!(read.open::<u64>(StateKind::KeyShare, PASSPHRASE), Ok(2));
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+    assert!(SealedState::read_from_file(&dir.join("missing")).is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
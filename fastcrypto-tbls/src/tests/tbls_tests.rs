@@ -42,3 +42,67 @@ fn test_tbls_e2e() {
         ThresholdBls12381MinSig::sign(private_poly.c0(), msg)
     );
 }
+
+#[test]
+fn test_find_invalid_partial_signatures() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let t = 4;
+    let private_poly = Poly::<bls12381::Scalar>::rand(t - 1, &mut rng);
+    let public_poly = private_poly.commit();
+
+    let msg = b"test";
+    let mut partials = (1..=10)
+        .map(|i| {
+            let share = private_poly.eval(NonZeroU32::new(i).unwrap());
+            ThresholdBls12381MinSig::partial_sign(&share, msg)
+        })
+        .collect::<Vec<_>>();
+    assert!(ThresholdBls12381MinSig::find_invalid_partial_signatures(
+        &public_poly,
+        msg,
+        &partials,
+        &mut rng
+    )
+    .is_empty());
+    assert!(ThresholdBls12381MinSig::find_invalid_partial_signatures(
+        &public_poly,
+        msg,
+        &[],
+        &mut rng
+    )
+    .is_empty());
+
+    // A signature on another message and a signature with another share.
+    let other_share = private_poly.eval(NonZeroU32::new(5).unwrap());
+    partials[2].value = ThresholdBls12381MinSig::sign(
+        &private_poly.eval(partials[2].index).value,
+        b"other message",
+    );
+    partials[7].value = ThresholdBls12381MinSig::sign(&other_share.value, msg);
+    let invalid = ThresholdBls12381MinSig::find_invalid_partial_signatures(
+        &public_poly,
+        msg,
+        &partials,
+        &mut rng,
+    );
+    assert_eq!(invalid, vec![partials[2].index, partials[7].index]);
+    assert!(ThresholdBls12381MinSig::verify(
+        public_poly.c0(),
+        msg,
+        &ThresholdBls12381MinSig::aggregate(t, &partials[..4]).unwrap()
+    )
+    .is_err());
+
+    // Invalid partial signatures which cancel out in a plain sum are found too.
+    partials[2] = ThresholdBls12381MinSig::partial_sign(&private_poly.eval(partials[2].index), msg);
+    partials[7] = ThresholdBls12381MinSig::partial_sign(&private_poly.eval(partials[7].index), msg);
+    partials[1].value += bls12381::G1Element::generator();
+    partials[3].value -= bls12381::G1Element::generator();
+    let invalid = ThresholdBls12381MinSig::find_invalid_partial_signatures(
+        &public_poly,
+        msg,
+        &partials,
+        &mut rng,
+    );
+    assert_eq!(invalid, vec![partials[1].index, partials[3].index]);
+}
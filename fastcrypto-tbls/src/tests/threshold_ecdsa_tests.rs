@@ -0,0 +1,199 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::random_oracle::RandomOracle;
+use crate::threshold_ecdsa::{
+    AbortError, KeyShare, KeygenParty, PartialSignature, PresignParty, PresignReveal, Presignature,
+};
+use crate::types::ShareIndex;
+use fastcrypto::groups::secp256k1::Secp256k1Scalar;
+use fastcrypto::groups::GroupElement;
+use fastcrypto::traits::VerifyingKey;
+use rand::prelude::*;
+
+const MSG: &[u8] = b"Hello, world!";
+
+fn index(i: u32) -> ShareIndex {
+    ShareIndex::new(i).unwrap()
+}
+
+fn keygen(n: u32, threshold: u32, rng: &mut StdRng) -> Vec<KeyShare> {
+    let mut parties = Vec::new();
+    let mut broadcasts = Vec::new();
+    let mut shares = Vec::new();
+    for i in 1..=n {
+        let (party, broadcast, party_shares) =
+            KeygenParty::new(index(i), n, threshold, RandomOracle::new("test"), rng).unwrap();
+        parties.push(party);
+        broadcasts.push(broadcast);
+        shares.extend(party_shares);
+    }
+    parties
+        .into_iter()
+        .map(|p| p.finish(&broadcasts, &shares).unwrap())
+        .collect()
+}
+
+/// Run presigning where the reveal of each signer can be modified before it is sent.
+fn presign_with(
+    keys: &[KeyShare],
+    signers: &[ShareIndex],
+    rng: &mut StdRng,
+    modify: impl Fn(&mut PresignReveal),
+) -> Vec<Result<Presignature, AbortError>> {
+    let keys = signers
+        .iter()
+        .map(|s| keys.iter().find(|k| k.id() == *s).unwrap())
+        .collect::<Vec<_>>();
+    let random_oracle = RandomOracle::new("test");
+
+    let mut parties = Vec::new();
+    let mut broadcasts = Vec::new();
+    let mut shares = Vec::new();
+    for key in &keys {
+        let (party, broadcast, party_shares) =
+            PresignParty::new(key, signers, random_oracle.clone(), rng).unwrap();
+        parties.push(party);
+        broadcasts.push(broadcast);
+        shares.extend(party_shares);
+    }
+
+    let mut states = Vec::new();
+    let mut reveals = Vec::new();
+    for party in parties {
+        let (state, mut reveal) = party.reveal(&broadcasts, &shares, rng).unwrap();
+        modify(&mut reveal);
+        states.push(state);
+        reveals.push(reveal);
+    }
+    states.into_iter().map(|s| s.finish(&reveals)).collect()
+}
+
+fn presign(keys: &[KeyShare], signers: &[ShareIndex], rng: &mut StdRng) -> Vec<Presignature> {
+    presign_with(keys, signers, rng, |_| ())
+        .into_iter()
+        .map(|p| p.unwrap())
+        .collect()
+}
+
+#[test]
+fn test_sign() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let keys = keygen(5, 2, &mut rng);
+    let public_key = keys[0].public_key();
+    assert!(keys.iter().all(|k| k.public_key() == public_key));
+
+    for signers in [vec![1, 2, 3], vec![2, 4, 5], vec![1, 2, 3, 4, 5]] {
+        let signers = signers.into_iter().map(index).collect::<Vec<_>>();
+        let presignatures = presign(&keys, &signers, &mut rng);
+
+        let (partial_signatures, combiners): (Vec<_>, Vec<_>) =
+            presignatures.into_iter().map(|p| p.sign(MSG)).unzip();
+        for combiner in combiners {
+            let signature = combiner.combine(&partial_signatures).unwrap();
+            assert!(public_key.verify(MSG, &signature).is_ok());
+        }
+    }
+}
+
+#[test]
+fn test_invalid_parameters() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let random_oracle = RandomOracle::new("test");
+    assert!(KeygenParty::new(index(1), 4, 0, random_oracle.clone(), &mut rng).is_err());
+    assert!(KeygenParty::new(index(1), 4, 3, random_oracle.clone(), &mut rng).is_err());
+    assert!(KeygenParty::new(index(5), 4, 2, random_oracle.clone(), &mut rng).is_err());
+
+    let keys = keygen(4, 2, &mut rng);
+    // Too few signers.
+    assert!(PresignParty::new(
+        &keys[0],
+        &[index(1), index(2)],
+        random_oracle.clone(),
+        &mut rng
+    )
+    .is_err());
+    // The party is not a signer.
+    assert!(PresignParty::new(
+        &keys[0],
+        &[index(2), index(3), index(4)],
+        random_oracle,
+        &mut rng
+    )
+    .is_err());
+}
+
+#[test]
+fn test_keygen_identifiable_abort() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let mut parties = Vec::new();
+    let mut broadcasts = Vec::new();
+    let mut shares = Vec::new();
+    for i in 1..=3 {
+        let (party, broadcast, party_shares) =
+            KeygenParty::new(index(i), 3, 2, RandomOracle::new("test"), &mut rng).unwrap();
+        parties.push(party);
+        broadcasts.push(broadcast);
+        shares.extend(party_shares);
+    }
+
+    // Party 2 sends an invalid share to party 1.
+    shares
+        .iter_mut()
+        .find(|s| s.sender == index(2) && s.receiver == index(1))
+        .unwrap()
+        .share += Secp256k1Scalar::generator();
+    let mut parties = parties.into_iter();
+    assert_eq!(
+        parties.next().unwrap().finish(&broadcasts, &shares),
+        Err(AbortError::Identified(vec![index(2)]))
+    );
+
+    // Party 3 does not send a broadcast message.
+    assert_eq!(
+        parties.next().unwrap().finish(&broadcasts[..2], &shares),
+        Err(AbortError::Identified(vec![index(3)]))
+    );
+}
+
+#[test]
+fn test_presign_identifiable_abort() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let keys = keygen(3, 2, &mut rng);
+    let signers = [index(1), index(2), index(3)];
+
+    // Signer 3 reveals an invalid mu.
+    let results = presign_with(&keys, &signers, &mut rng, |reveal| {
+        if reveal.sender == index(3) {
+            reveal.mu += Secp256k1Scalar::generator();
+        }
+    });
+    for result in results {
+        assert_eq!(
+            result.err().unwrap(),
+            AbortError::Identified(vec![index(3)])
+        );
+    }
+}
+
+#[test]
+fn test_sign_identifiable_abort() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let keys = keygen(3, 2, &mut rng);
+    let signers = [index(1), index(2), index(3)];
+    let presignatures = presign(&keys, &signers, &mut rng);
+
+    let (mut partial_signatures, combiners): (Vec<PartialSignature>, Vec<_>) =
+        presignatures.into_iter().map(|p| p.sign(MSG)).unzip();
+    partial_signatures[1].s += Secp256k1Scalar::generator();
+    let mut combiners = combiners.into_iter();
+    assert_eq!(
+        combiners.next().unwrap().combine(&partial_signatures),
+        Err(AbortError::Identified(vec![index(2)]))
+    );
+    // A missing partial signature is also identified.
+    assert_eq!(
+        combiners.next().unwrap().combine(&partial_signatures[..2]),
+        Err(AbortError::Identified(vec![index(3)]))
+    );
+}
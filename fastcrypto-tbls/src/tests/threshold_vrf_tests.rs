@@ -0,0 +1,128 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::polynomial::Poly;
+use crate::random_oracle::RandomOracle;
+use crate::tbls::Share;
+use crate::threshold_vrf::*;
+use crate::types::ShareIndex;
+use fastcrypto::groups::ristretto255::{RistrettoPoint, RistrettoScalar};
+use fastcrypto::groups::GroupElement;
+use rand::prelude::*;
+
+const INPUT: &[u8] = b"input";
+
+type Group = RistrettoPoint;
+
+fn setup(n: u32, t: u32, rng: &mut StdRng) -> (ThresholdVrf<Group>, Vec<Share<RistrettoScalar>>) {
+    let private_poly = Poly::<RistrettoScalar>::rand(t - 1, rng);
+    let vrf = ThresholdVrf::new(private_poly.commit(), RandomOracle::new("test"));
+    let shares = (1..=n)
+        .map(|i| private_poly.eval(ShareIndex::new(i).unwrap()))
+        .collect();
+    (vrf, shares)
+}
+
+fn evaluate(
+    vrf: &ThresholdVrf<Group>,
+    shares: &[Share<RistrettoScalar>],
+    input: &[u8],
+    rng: &mut StdRng,
+) -> Vec<PartialEvaluation<Group>> {
+    shares
+        .iter()
+        .map(|share| vrf.partial_evaluate(share, input, rng))
+        .collect()
+}
+
+#[test]
+fn test_threshold_vrf() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let (vrf, shares) = setup(5, 3, &mut rng);
+    assert_eq!(vrf.threshold(), 3);
+    let partials = evaluate(&vrf, &shares, INPUT, &mut rng);
+    for partial in &partials {
+        assert!(vrf.verify_partial(INPUT, partial).is_ok());
+        assert!(vrf.verify_partial(b"other input", partial).is_err());
+    }
+
+    // Any threshold of partial evaluations gives the same output.
+    let output = vrf.aggregate(INPUT, &partials[..3]).unwrap();
+    let other = vrf.aggregate(INPUT, &partials[2..]).unwrap();
+    assert_eq!(output.value, other.value);
+    assert_eq!(
+        vrf.output_bytes(INPUT, &output),
+        vrf.output_bytes(INPUT, &other)
+    );
+    assert!(vrf.verify_output(INPUT, &output).is_ok());
+    assert!(vrf.verify_output(INPUT, &other).is_ok());
+    assert!(vrf.verify_output(b"other input", &output).is_err());
+
+    // The output does not depend on the randomness of the proofs, but on the input.
+    let again = evaluate(&vrf, &shares, INPUT, &mut rng);
+    assert_ne!(again[0].proof, partials[0].proof);
+    let again = vrf.aggregate(INPUT, &again[1..4]).unwrap();
+    assert_eq!(again.value, output.value);
+    let partials_other = evaluate(&vrf, &shares, b"other input", &mut rng);
+    let output_other = vrf.aggregate(b"other input", &partials_other[..3]).unwrap();
+    assert_ne!(output_other.value, output.value);
+    assert_ne!(
+        vrf.output_bytes(b"other input", &output_other),
+        vrf.output_bytes(INPUT, &output)
+    );
+
+    // Fewer, more or repeated partial evaluations.
+    assert!(vrf.aggregate(INPUT, &partials[..2]).is_err());
+    assert!(vrf.aggregate(INPUT, &partials[..4]).is_err());
+    let repeated = vec![
+        partials[0].clone(),
+        partials[0].clone(),
+        partials[1].clone(),
+    ];
+    assert!(vrf.aggregate(INPUT, &repeated).is_err());
+}
+
+#[test]
+fn test_invalid_partials() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let (vrf, shares) = setup(5, 3, &mut rng);
+    let mut partials = evaluate(&vrf, &shares, INPUT, &mut rng);
+
+    // A wrong value with the proof of the correct one.
+    partials[1].value += Group::generator();
+    // A proof for the wrong party.
+    partials[3].index = ShareIndex::new(5).unwrap();
+    partials.pop();
+
+    assert!(vrf.verify_partial(INPUT, &partials[1]).is_err());
+    assert!(vrf.verify_partial(INPUT, &partials[3]).is_err());
+    assert_eq!(
+        vrf.find_invalid_partials(INPUT, &partials),
+        vec![partials[1].index, partials[3].index]
+    );
+    assert!(vrf.aggregate(INPUT, &partials[..3]).is_err());
+    assert!(vrf.aggregate(INPUT, &partials[1..]).is_err());
+
+    // A partial evaluation of another instance.
+    let (other_vrf, other_shares) = setup(5, 3, &mut rng);
+    let other = other_vrf.partial_evaluate(&other_shares[0], INPUT, &mut rng);
+    assert!(vrf.verify_partial(INPUT, &other).is_err());
+
+    // The output must match its partial evaluations.
+    let partials = evaluate(&vrf, &shares, INPUT, &mut rng);
+    let mut output = vrf.aggregate(INPUT, &partials[..3]).unwrap();
+    output.value += Group::generator();
+    assert!(vrf.verify_output(INPUT, &output).is_err());
+}
+
+#[test]
+fn test_serialization() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let (vrf, shares) = setup(3, 2, &mut rng);
+    let partials = evaluate(&vrf, &shares, INPUT, &mut rng);
+    let output = vrf.aggregate(INPUT, &partials[..2]).unwrap();
+    let bytes = bincode::serialize(&output).unwrap();
+    let deserialized: Output<Group> = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(deserialized, output);
+    assert!(vrf.verify_output(INPUT, &deserialized).is_ok());
+}
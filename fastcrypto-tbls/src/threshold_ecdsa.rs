@@ -0,0 +1,681 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Threshold ECDSA over secp256k1 with identifiable aborts.
+//!
+//! The protocol follows Gennaro, Jarecki, Krawczyk and Rabin, "Robust Threshold DSS Signatures"
+//! (EUROCRYPT '96), and only uses secret sharing, so it requires an honest majority among the
+//! signers: a key shared with threshold `t` can be used by any set of at least `2t - 1` signers.
+//! All private messages ([KeygenShare] and [PresignShare]) must be sent over authenticated and
+//! encrypted channels, and broadcast messages must be delivered consistently to all parties.
+//!
+//! - Keygen (one round): every party deals a Feldman VSS of a random secret with a proof of
+//!   knowledge of the secret. The private key x is the sum of the secrets.
+//! - Presigning (two rounds, independent of the message): the signers jointly share random k and
+//!   a, and a random sharing of zero b of degree 2t - 2. Every signer then reveals
+//!   mu_i = k_i a_i + b_i together with a proof of correctness, so that mu = ka can be interpolated.
+//!   The presignature consists of R = g^k, mu^{-1} and the shares a_i and x_i.
+//! - Signing (one round): every signer sends s_i = mu^{-1} a_i (H(m) + r x_i), which is a share of
+//!   s = k^{-1} (H(m) + r x) on a polynomial of degree 2t - 2.
+//!
+//! Every message except for the private shares can be verified publicly, and a party which sends
+//! an invalid or no message is reported in [AbortError::Identified].
+//!
+//! Presignatures must be used at most once. [Presignature::sign] consumes the presignature.
+
+use crate::polynomial::{interpolate_at_zero, Eval, Poly, PrivatePoly, PublicPoly};
+use crate::random_oracle::RandomOracle;
+use crate::types::ShareIndex;
+use fastcrypto::error::FastCryptoError;
+use fastcrypto::groups::secp256k1::{Secp256k1Point, Secp256k1Scalar};
+use fastcrypto::groups::{GroupElement, Scalar};
+use fastcrypto::hash::HashFunction;
+use fastcrypto::secp256k1::{
+    DefaultHash, Secp256k1PublicKey, Secp256k1Signature, SECP256K1_SIGNATURE_LENGTH,
+};
+use fastcrypto::serde_helpers::ToFromByteArray;
+use fastcrypto::traits::{AllowedRng, ToFromBytes, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Error returned when one of the protocols aborts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AbortError {
+    /// The given parties sent invalid messages or did not send an expected message.
+    Identified(Vec<ShareIndex>),
+    /// The protocol failed for a reason that cannot be attributed to a party.
+    Unidentified(FastCryptoError),
+}
+
+impl From<FastCryptoError> for AbortError {
+    fn from(e: FastCryptoError) -> Self {
+        AbortError::Unidentified(e)
+    }
+}
+
+/// A Schnorr proof of knowledge of the discrete log of a point.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KnowledgeProof {
+    commitment: Secp256k1Point,
+    response: Secp256k1Scalar,
+}
+
+/// A proof that a list of points has the same discrete log with respect to a list of bases.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DleqProof {
+    challenge: Secp256k1Scalar,
+    response: Secp256k1Scalar,
+}
+
+fn challenge<T: Serialize>(random_oracle: &RandomOracle, input: &T) -> Secp256k1Scalar {
+    Secp256k1Scalar::from_bytes_mod_order_wide(&random_oracle.evaluate(input))
+}
+
+impl KnowledgeProof {
+    fn prove<R: AllowedRng>(
+        secret: &Secp256k1Scalar,
+        random_oracle: &RandomOracle,
+        prover: ShareIndex,
+        rng: &mut R,
+    ) -> Self {
+        let nonce = Secp256k1Scalar::rand(rng);
+        let commitment = Secp256k1Point::generator() * nonce;
+        let public = Secp256k1Point::generator() * secret;
+        let c = challenge(random_oracle, &(prover, public, commitment));
+        Self {
+            commitment,
+            response: nonce + c * secret,
+        }
+    }
+
+    fn verify(
+        &self,
+        public: &Secp256k1Point,
+        random_oracle: &RandomOracle,
+        prover: ShareIndex,
+    ) -> Result<(), FastCryptoError> {
+        let c = challenge(random_oracle, &(prover, public, self.commitment));
+        if Secp256k1Point::generator() * self.response != self.commitment + *public * c {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        Ok(())
+    }
+}
+
+impl DleqProof {
+    fn prove<R: AllowedRng>(
+        secret: &Secp256k1Scalar,
+        bases: &[Secp256k1Point],
+        random_oracle: &RandomOracle,
+        prover: ShareIndex,
+        rng: &mut R,
+    ) -> Self {
+        let nonce = Secp256k1Scalar::rand(rng);
+        let publics = bases.iter().map(|b| *b * secret).collect::<Vec<_>>();
+        let commitments = bases.iter().map(|b| *b * nonce).collect::<Vec<_>>();
+        let c = challenge(random_oracle, &(prover, bases, publics, commitments));
+        Self {
+            challenge: c,
+            response: nonce + c * secret,
+        }
+    }
+
+    fn verify(
+        &self,
+        bases: &[Secp256k1Point],
+        publics: &[Secp256k1Point],
+        random_oracle: &RandomOracle,
+        prover: ShareIndex,
+    ) -> Result<(), FastCryptoError> {
+        let commitments = bases
+            .iter()
+            .zip(publics.iter())
+            .map(|(b, p)| *b * self.response - *p * self.challenge)
+            .collect::<Vec<_>>();
+        if challenge(random_oracle, &(prover, bases, publics, commitments)) != self.challenge {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        Ok(())
+    }
+}
+
+/// Collect messages by sender from the given parties, identifying parties which sent no or
+/// multiple messages.
+fn by_sender<'a, T>(
+    messages: impl Iterator<Item = (ShareIndex, &'a T)>,
+    parties: &[ShareIndex],
+) -> Result<BTreeMap<ShareIndex, &'a T>, AbortError> {
+    let mut result = BTreeMap::new();
+    let mut culprits = BTreeSet::new();
+    for (sender, message) in messages {
+        // Messages from parties which are not part of the protocol are ignored.
+        if parties.contains(&sender) && result.insert(sender, message).is_some() {
+            culprits.insert(sender);
+        }
+    }
+    culprits.extend(parties.iter().filter(|p| !result.contains_key(*p)));
+    if !culprits.is_empty() {
+        return Err(AbortError::Identified(culprits.into_iter().collect()));
+    }
+    Ok(result)
+}
+
+fn check_culprits(culprits: Vec<ShareIndex>) -> Result<(), AbortError> {
+    match culprits.is_empty() {
+        true => Ok(()),
+        false => Err(AbortError::Identified(culprits)),
+    }
+}
+
+/// Sum polynomials.
+fn sum<C: GroupElement>(polynomials: impl Iterator<Item = PublicPoly<C>>) -> PublicPoly<C> {
+    polynomials.fold(Poly::zero(), |mut acc, p| {
+        acc.add(&p);
+        acc
+    })
+}
+
+fn parties(n: u32) -> Vec<ShareIndex> {
+    (1..=n)
+        .map(|i| ShareIndex::new(i).expect("Indices start at 1"))
+        .collect()
+}
+
+/// Interpolate the value at 0 of a polynomial of degree less than the number of evaluations.
+fn interpolate(evaluations: Vec<Eval<Secp256k1Scalar>>) -> Result<Secp256k1Scalar, AbortError> {
+    Ok(interpolate_at_zero(&evaluations)?)
+}
+
+//// Keygen
+
+/// The broadcast message of a party in keygen.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeygenBroadcast {
+    pub sender: ShareIndex,
+    pub commitment: PublicPoly<Secp256k1Point>,
+    pub proof: KnowledgeProof,
+}
+
+/// A share sent privately from one party to another in keygen.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeygenShare {
+    pub sender: ShareIndex,
+    pub receiver: ShareIndex,
+    pub share: Secp256k1Scalar,
+}
+
+/// The state of a party during keygen.
+pub struct KeygenParty {
+    id: ShareIndex,
+    n: u32,
+    threshold: u32,
+    random_oracle: RandomOracle,
+}
+
+/// The output of keygen for a single party.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyShare {
+    id: ShareIndex,
+    n: u32,
+    threshold: u32,
+    share: Secp256k1Scalar,
+    vss_pk: PublicPoly<Secp256k1Point>,
+}
+
+impl KeygenParty {
+    /// Start keygen for party `id` among `n` parties, such that `threshold` parties are needed to
+    /// reconstruct the private key. Returns the state of the party, its broadcast message and the
+    /// private shares for all other parties.
+    pub fn new<R: AllowedRng>(
+        id: ShareIndex,
+        n: u32,
+        threshold: u32,
+        random_oracle: RandomOracle,
+        rng: &mut R,
+    ) -> Result<(Self, KeygenBroadcast, Vec<KeygenShare>), FastCryptoError> {
+        if threshold == 0 || 2 * threshold - 1 > n || id.get() > n {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let random_oracle = random_oracle.extend("keygen");
+        let polynomial = PrivatePoly::<Secp256k1Point>::rand(threshold - 1, rng);
+        let broadcast = KeygenBroadcast {
+            sender: id,
+            commitment: polynomial.commit(),
+            proof: KnowledgeProof::prove(polynomial.c0(), &random_oracle, id, rng),
+        };
+        let shares = parties(n)
+            .into_iter()
+            .map(|receiver| KeygenShare {
+                sender: id,
+                receiver,
+                share: polynomial.eval(receiver).value,
+            })
+            .collect();
+        Ok((
+            Self {
+                id,
+                n,
+                threshold,
+                random_oracle,
+            },
+            broadcast,
+            shares,
+        ))
+    }
+
+    /// Finish keygen using the broadcast messages of all parties and the shares sent to this party.
+    pub fn finish(
+        self,
+        broadcasts: &[KeygenBroadcast],
+        shares: &[KeygenShare],
+    ) -> Result<KeyShare, AbortError> {
+        let parties = parties(self.n);
+        let broadcasts = by_sender(broadcasts.iter().map(|b| (b.sender, b)), &parties)?;
+        let shares = by_sender(
+            shares
+                .iter()
+                .filter(|s| s.receiver == self.id)
+                .map(|s| (s.sender, s)),
+            &parties,
+        )?;
+
+        let culprits = parties
+            .iter()
+            .filter(|&&j| {
+                let broadcast = broadcasts[&j];
+                broadcast.commitment.degree() != self.threshold - 1
+                    || broadcast
+                        .proof
+                        .verify(broadcast.commitment.c0(), &self.random_oracle, j)
+                        .is_err()
+                    || !broadcast
+                        .commitment
+                        .is_valid_share(self.id, &shares[&j].share)
+            })
+            .copied()
+            .collect();
+        check_culprits(culprits)?;
+
+        Ok(KeyShare {
+            id: self.id,
+            n: self.n,
+            threshold: self.threshold,
+            share: shares
+                .values()
+                .fold(Secp256k1Scalar::zero(), |acc, s| acc + s.share),
+            vss_pk: sum(broadcasts.values().map(|b| b.commitment.clone())),
+        })
+    }
+}
+
+impl KeyShare {
+    /// The index of this party.
+    pub fn id(&self) -> ShareIndex {
+        self.id
+    }
+
+    /// The number of parties needed to reconstruct the private key. Signing requires at least
+    /// `2 * threshold - 1` parties.
+    pub fn threshold(&self) -> u32 {
+        self.threshold
+    }
+
+    /// The joint public key.
+    pub fn public_key(&self) -> Secp256k1PublicKey {
+        Secp256k1PublicKey::from_bytes(&self.vss_pk.c0().to_byte_array())
+            .expect("The public key is a valid point")
+    }
+
+    fn public_share(&self, id: ShareIndex) -> Secp256k1Point {
+        self.vss_pk.eval(id).value
+    }
+}
+
+//// Presigning
+
+/// The broadcast message of a signer in the first round of presigning.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PresignBroadcast {
+    pub sender: ShareIndex,
+    pub k_commitment: PublicPoly<Secp256k1Point>,
+    pub a_commitment: PublicPoly<Secp256k1Point>,
+    pub b_commitment: PublicPoly<Secp256k1Point>,
+    pub k_proof: KnowledgeProof,
+    pub a_proof: KnowledgeProof,
+}
+
+/// Shares sent privately from one signer to another in the first round of presigning.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PresignShare {
+    pub sender: ShareIndex,
+    pub receiver: ShareIndex,
+    pub k: Secp256k1Scalar,
+    pub a: Secp256k1Scalar,
+    pub b: Secp256k1Scalar,
+}
+
+/// The broadcast message of a signer in the second round of presigning. It contains
+/// `mu_i = k_i a_i + b_i`, `M_i = g^{k_i a_i}` and `Z_i = g^{x_i a_i}` with a proof that `M_i` and
+/// `Z_i` are computed using the same `a_i`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PresignReveal {
+    pub sender: ShareIndex,
+    pub mu: Secp256k1Scalar,
+    pub m: Secp256k1Point,
+    pub z: Secp256k1Point,
+    pub proof: DleqProof,
+}
+
+/// The state of a signer in the first round of presigning.
+pub struct PresignParty {
+    key: KeyShare,
+    signers: Vec<ShareIndex>,
+    random_oracle: RandomOracle,
+}
+
+/// The state of a signer in the second round of presigning.
+pub struct PresignRevealState {
+    key: KeyShare,
+    signers: Vec<ShareIndex>,
+    random_oracle: RandomOracle,
+    k_pk: PublicPoly<Secp256k1Point>,
+    a_pk: PublicPoly<Secp256k1Point>,
+    b_pk: PublicPoly<Secp256k1Point>,
+    a: Secp256k1Scalar,
+}
+
+/// A presignature which can be used to sign a single message.
+pub struct Presignature {
+    key: KeyShare,
+    signers: Vec<ShareIndex>,
+    r: Secp256k1Scalar,
+    mu_inverse: Secp256k1Scalar,
+    a: Secp256k1Scalar,
+    a_points: BTreeMap<ShareIndex, Secp256k1Point>,
+    z_points: BTreeMap<ShareIndex, Secp256k1Point>,
+}
+
+impl PresignParty {
+    /// Start presigning with the given set of signers, which must include this party and have at
+    /// least `2 * threshold - 1` members. The random oracle must be unique for every presignature.
+    pub fn new<R: AllowedRng>(
+        key: &KeyShare,
+        signers: &[ShareIndex],
+        random_oracle: RandomOracle,
+        rng: &mut R,
+    ) -> Result<(Self, PresignBroadcast, Vec<PresignShare>), FastCryptoError> {
+        let signers = signers.iter().copied().collect::<BTreeSet<_>>();
+        if (signers.len() as u32) < 2 * key.threshold - 1
+            || !signers.contains(&key.id)
+            || signers.iter().any(|s| s.get() > key.n)
+        {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let signers = signers.into_iter().collect::<Vec<_>>();
+        let random_oracle = random_oracle.extend("presign");
+
+        let k = PrivatePoly::<Secp256k1Point>::rand(key.threshold - 1, rng);
+        let a = PrivatePoly::<Secp256k1Point>::rand(key.threshold - 1, rng);
+        let mut b: Vec<Secp256k1Scalar> =
+            PrivatePoly::<Secp256k1Point>::rand(2 * key.threshold - 2, rng).into();
+        b[0] = Secp256k1Scalar::zero();
+        let b = Poly::from(b);
+
+        let broadcast = PresignBroadcast {
+            sender: key.id,
+            k_commitment: k.commit(),
+            a_commitment: a.commit(),
+            b_commitment: b.commit(),
+            k_proof: KnowledgeProof::prove(k.c0(), &random_oracle.extend("k"), key.id, rng),
+            a_proof: KnowledgeProof::prove(a.c0(), &random_oracle.extend("a"), key.id, rng),
+        };
+        let shares = signers
+            .iter()
+            .map(|&receiver| PresignShare {
+                sender: key.id,
+                receiver,
+                k: k.eval(receiver).value,
+                a: a.eval(receiver).value,
+                b: b.eval(receiver).value,
+            })
+            .collect();
+        Ok((
+            Self {
+                key: key.clone(),
+                signers,
+                random_oracle,
+            },
+            broadcast,
+            shares,
+        ))
+    }
+
+    /// Process the first round messages of all signers and return the message for the second
+    /// round.
+    pub fn reveal<R: AllowedRng>(
+        self,
+        broadcasts: &[PresignBroadcast],
+        shares: &[PresignShare],
+        rng: &mut R,
+    ) -> Result<(PresignRevealState, PresignReveal), AbortError> {
+        let id = self.key.id;
+        let t = self.key.threshold;
+        let broadcasts = by_sender(broadcasts.iter().map(|b| (b.sender, b)), &self.signers)?;
+        let shares = by_sender(
+            shares
+                .iter()
+                .filter(|s| s.receiver == id)
+                .map(|s| (s.sender, s)),
+            &self.signers,
+        )?;
+
+        let culprits = self
+            .signers
+            .iter()
+            .filter(|&&j| {
+                let (b, s) = (broadcasts[&j], shares[&j]);
+                b.k_commitment.degree() != t - 1
+                    || b.a_commitment.degree() != t - 1
+                    || b.b_commitment.degree() != 2 * t - 2
+                    || *b.b_commitment.c0() != Secp256k1Point::zero()
+                    || b.k_proof
+                        .verify(b.k_commitment.c0(), &self.random_oracle.extend("k"), j)
+                        .is_err()
+                    || b.a_proof
+                        .verify(b.a_commitment.c0(), &self.random_oracle.extend("a"), j)
+                        .is_err()
+                    || !b.k_commitment.is_valid_share(id, &s.k)
+                    || !b.a_commitment.is_valid_share(id, &s.a)
+                    || !b.b_commitment.is_valid_share(id, &s.b)
+            })
+            .copied()
+            .collect();
+        check_culprits(culprits)?;
+
+        let k = shares
+            .values()
+            .fold(Secp256k1Scalar::zero(), |acc, s| acc + s.k);
+        let a = shares
+            .values()
+            .fold(Secp256k1Scalar::zero(), |acc, s| acc + s.a);
+        let b = shares
+            .values()
+            .fold(Secp256k1Scalar::zero(), |acc, s| acc + s.b);
+        let k_pk = sum(broadcasts.values().map(|b| b.k_commitment.clone()));
+        let a_pk = sum(broadcasts.values().map(|b| b.a_commitment.clone()));
+        let b_pk = sum(broadcasts.values().map(|b| b.b_commitment.clone()));
+
+        let bases = [
+            Secp256k1Point::generator(),
+            k_pk.eval(id).value,
+            self.key.public_share(id),
+        ];
+        let reveal = PresignReveal {
+            sender: id,
+            mu: k * a + b,
+            m: bases[1] * a,
+            z: bases[2] * a,
+            proof: DleqProof::prove(&a, &bases, &self.random_oracle, id, rng),
+        };
+        Ok((
+            PresignRevealState {
+                key: self.key,
+                signers: self.signers,
+                random_oracle: self.random_oracle,
+                k_pk,
+                a_pk,
+                b_pk,
+                a,
+            },
+            reveal,
+        ))
+    }
+}
+
+impl PresignRevealState {
+    /// Process the second round messages of all signers and return the presignature.
+    pub fn finish(self, reveals: &[PresignReveal]) -> Result<Presignature, AbortError> {
+        let reveals = by_sender(reveals.iter().map(|r| (r.sender, r)), &self.signers)?;
+
+        let mut a_points = BTreeMap::new();
+        let mut z_points = BTreeMap::new();
+        let mut culprits = Vec::new();
+        for (&j, reveal) in reveals.iter() {
+            let a_j = self.a_pk.eval(j).value;
+            let bases = [
+                Secp256k1Point::generator(),
+                self.k_pk.eval(j).value,
+                self.key.public_share(j),
+            ];
+            let valid = reveal
+                .proof
+                .verify(&bases, &[a_j, reveal.m, reveal.z], &self.random_oracle, j)
+                .is_ok()
+                && Secp256k1Point::generator() * reveal.mu == reveal.m + self.b_pk.eval(j).value;
+            if !valid {
+                culprits.push(j);
+            }
+            a_points.insert(j, a_j);
+            z_points.insert(j, reveal.z);
+        }
+        check_culprits(culprits)?;
+
+        let mu = interpolate(
+            reveals
+                .iter()
+                .map(|(&index, reveal)| Eval {
+                    index,
+                    value: reveal.mu,
+                })
+                .collect(),
+        )?;
+        let mu_inverse = (Secp256k1Scalar::generator() / mu)?;
+        let r = self.k_pk.c0().x_as_scalar()?;
+        if r == Secp256k1Scalar::zero() {
+            return Err(AbortError::Unidentified(
+                FastCryptoError::GeneralOpaqueError,
+            ));
+        }
+
+        Ok(Presignature {
+            key: self.key,
+            signers: self.signers,
+            r,
+            mu_inverse,
+            a: self.a,
+            a_points,
+            z_points,
+        })
+    }
+}
+
+//// Signing
+
+/// A partial signature created by a signer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartialSignature {
+    pub sender: ShareIndex,
+    pub s: Secp256k1Scalar,
+}
+
+/// The state needed to verify and combine partial signatures on a message.
+pub struct SignatureCombiner {
+    public_key: Secp256k1PublicKey,
+    message: Vec<u8>,
+    signers: Vec<ShareIndex>,
+    r: Secp256k1Scalar,
+    h: Secp256k1Scalar,
+    mu_inverse: Secp256k1Scalar,
+    a_points: BTreeMap<ShareIndex, Secp256k1Point>,
+    z_points: BTreeMap<ShareIndex, Secp256k1Point>,
+}
+
+fn hash_message(message: &[u8]) -> Secp256k1Scalar {
+    Secp256k1Scalar::from_bytes_mod_order(&DefaultHash::digest(message).digest)
+}
+
+impl Presignature {
+    /// Create a partial signature on a message. As for [Secp256k1PublicKey::verify], the message is
+    /// hashed using Sha256. The presignature is consumed, since using it for two messages reveals
+    /// the private key.
+    pub fn sign(self, message: &[u8]) -> (PartialSignature, SignatureCombiner) {
+        let h = hash_message(message);
+        let partial_signature = PartialSignature {
+            sender: self.key.id,
+            s: self.mu_inverse * self.a * (h + self.r * self.key.share),
+        };
+        (
+            partial_signature,
+            SignatureCombiner {
+                public_key: self.key.public_key(),
+                message: message.to_vec(),
+                signers: self.signers,
+                r: self.r,
+                h,
+                mu_inverse: self.mu_inverse,
+                a_points: self.a_points,
+                z_points: self.z_points,
+            },
+        )
+    }
+}
+
+impl SignatureCombiner {
+    /// Verify the partial signatures of all signers and combine them into a signature.
+    pub fn combine(
+        self,
+        partial_signatures: &[PartialSignature],
+    ) -> Result<Secp256k1Signature, AbortError> {
+        let partial_signatures = by_sender(
+            partial_signatures.iter().map(|p| (p.sender, p)),
+            &self.signers,
+        )?;
+
+        // g^{s_j} = mu^{-1} (H(m) A_j + r Z_j)
+        let culprits = partial_signatures
+            .iter()
+            .filter(|(j, p)| {
+                Secp256k1Point::generator() * p.s
+                    != (self.a_points[*j] * self.h + self.z_points[*j] * self.r) * self.mu_inverse
+            })
+            .map(|(j, _)| *j)
+            .collect();
+        check_culprits(culprits)?;
+
+        let mut s = interpolate(
+            partial_signatures
+                .iter()
+                .map(|(&index, p)| Eval { index, value: p.s })
+                .collect(),
+        )?;
+        if s.is_high() {
+            s = -s;
+        }
+        let mut bytes = [0u8; SECP256K1_SIGNATURE_LENGTH];
+        bytes[..32].copy_from_slice(&self.r.to_byte_array());
+        bytes[32..].copy_from_slice(&s.to_byte_array());
+        let signature = Secp256k1Signature::from_bytes(&bytes)?;
+        self.public_key.verify(&self.message, &signature)?;
+        Ok(signature)
+    }
+}
@@ -0,0 +1,194 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A threshold VRF from shares of a secret key, e.g. the output of a DKG.
+//!
+//! For a secret s shared with a polynomial p of degree t - 1, the VRF output of an input m is
+//! derived from gamma = s H(m), where H hashes the input to the group. Party i sends the partial
+//! evaluation gamma_i = p(i) H(m) together with a DLEQ proof that gamma_i has the same discrete log
+//! with respect to H(m) as its public share p(i) G has with respect to the generator G. Any t valid
+//! partial evaluations interpolate to gamma, so the output is unique for the public key and input
+//! and cannot be computed by fewer than t parties.
+//!
+//! An [Output] keeps the partial evaluations it was combined from, so anyone with the public
+//! polynomial can verify it.
+//!
+//! The random oracle must be unique for the instance, and the same random oracle must be used for
+//! evaluating and verifying. The group must have a hash to its scalars, e.g. [RistrettoPoint].
+//!
+//! [RistrettoPoint]: fastcrypto::groups::ristretto255::RistrettoPoint
+
+use crate::nizk::DdhTupleNizk;
+use crate::polynomial::{Eval, Poly, PublicPoly};
+use crate::random_oracle::RandomOracle;
+use crate::tbls::Share;
+use crate::types::ShareIndex;
+use fastcrypto::error::FastCryptoError;
+use fastcrypto::groups::{GroupElement, HashToGroupElement};
+use fastcrypto::traits::AllowedRng;
+use serde::{Deserialize, Serialize};
+
+/// A partial evaluation gamma_i = s_i H(m) of party i with a proof that [G, H(m), pk_i, gamma_i]
+/// is a DDH tuple for its public share pk_i.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartialEvaluation<G: GroupElement> {
+    pub index: ShareIndex,
+    pub value: G,
+    pub proof: DdhTupleNizk<G>,
+}
+
+/// The output gamma = s H(m) of the VRF together with the partial evaluations it was combined from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Output<G: GroupElement> {
+    pub value: G,
+    pub partials: Vec<PartialEvaluation<G>>,
+}
+
+/// A threshold VRF for the secret shared by the given public polynomial.
+#[derive(Clone)]
+pub struct ThresholdVrf<G: GroupElement> {
+    vss_pk: PublicPoly<G>,
+    random_oracle: RandomOracle,
+}
+
+impl<G> ThresholdVrf<G>
+where
+    G: GroupElement + HashToGroupElement + Serialize,
+    G::ScalarType: HashToGroupElement,
+{
+    pub fn new(vss_pk: PublicPoly<G>, random_oracle: RandomOracle) -> Self {
+        Self {
+            vss_pk,
+            random_oracle,
+        }
+    }
+
+    /// The number of partial evaluations needed to compute an output.
+    pub fn threshold(&self) -> u32 {
+        self.vss_pk.degree() + 1
+    }
+
+    /// The public key of the VRF.
+    pub fn public_key(&self) -> &G {
+        self.vss_pk.c0()
+    }
+
+    /// Compute the partial evaluation of the given share on an input.
+    pub fn partial_evaluate<R: AllowedRng>(
+        &self,
+        share: &Share<G::ScalarType>,
+        input: &[u8],
+        rng: &mut R,
+    ) -> PartialEvaluation<G> {
+        let h = self.hash_input(input);
+        let pk_i = G::generator() * share.value;
+        let value = h * share.value;
+        let proof = DdhTupleNizk::create(
+            &share.value,
+            &h,
+            &pk_i,
+            &value,
+            &self.random_oracle.extend("dleq"),
+            rng,
+        );
+        PartialEvaluation {
+            index: share.index,
+            value,
+            proof,
+        }
+    }
+
+    /// Verify a single partial evaluation.
+    pub fn verify_partial(
+        &self,
+        input: &[u8],
+        partial: &PartialEvaluation<G>,
+    ) -> Result<(), FastCryptoError> {
+        let h = self.hash_input(input);
+        let pk_i = self.vss_pk.eval(partial.index).value;
+        partial.proof.verify(
+            &h,
+            &pk_i,
+            &partial.value,
+            &self.random_oracle.extend("dleq"),
+        )
+    }
+
+    /// Returns the indices of the invalid partial evaluations.
+    pub fn find_invalid_partials(
+        &self,
+        input: &[u8],
+        partials: &[PartialEvaluation<G>],
+    ) -> Vec<ShareIndex> {
+        partials
+            .iter()
+            .filter(|partial| self.verify_partial(input, partial).is_err())
+            .map(|partial| partial.index)
+            .collect()
+    }
+
+    /// Combine exactly [ThresholdVrf::threshold] partial evaluations from distinct parties into the
+    /// output of the VRF. Fails if the partial evaluations are invalid, in which case
+    /// [ThresholdVrf::find_invalid_partials] identifies the invalid ones.
+    pub fn aggregate(
+        &self,
+        input: &[u8],
+        partials: &[PartialEvaluation<G>],
+    ) -> Result<Output<G>, FastCryptoError> {
+        if partials.len() != self.threshold() as usize {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        self.verify_partials(input, partials)?;
+        Ok(Output {
+            value: Self::interpolate(self.threshold(), partials)?,
+            partials: partials.to_vec(),
+        })
+    }
+
+    /// Verify that an output was combined from valid partial evaluations on the given input.
+    pub fn verify_output(&self, input: &[u8], output: &Output<G>) -> Result<(), FastCryptoError> {
+        if output.partials.len() != self.threshold() as usize {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        self.verify_partials(input, &output.partials)?;
+        if Self::interpolate(self.threshold(), &output.partials)? != output.value {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        Ok(())
+    }
+
+    /// The pseudorandom bytes of a verified output on the given input.
+    pub fn output_bytes(&self, input: &[u8], output: &Output<G>) -> [u8; 64] {
+        self.random_oracle
+            .extend("output")
+            .evaluate(&(input, output.value))
+    }
+
+    fn verify_partials(
+        &self,
+        input: &[u8],
+        partials: &[PartialEvaluation<G>],
+    ) -> Result<(), FastCryptoError> {
+        partials
+            .iter()
+            .try_for_each(|partial| self.verify_partial(input, partial))
+    }
+
+    fn interpolate(
+        threshold: u32,
+        partials: &[PartialEvaluation<G>],
+    ) -> Result<G, FastCryptoError> {
+        let evaluations = partials
+            .iter()
+            .map(|partial| Eval {
+                index: partial.index,
+                value: partial.value,
+            })
+            .collect::<Vec<_>>();
+        Poly::<G>::recover_c0(threshold, &evaluations)
+    }
+
+    fn hash_input(&self, input: &[u8]) -> G {
+        G::hash_to_group_element(&self.random_oracle.extend("input").evaluate(&input))
+    }
+}
@@ -0,0 +1,170 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Benchmarks of the verifiers on the circuits of [fastcrypto_zkp::bench_circuits::corpus].
+//!
+//! The keys and proofs of the circuits are generated deterministically the first time the
+//! benchmarks run and are stored as fixtures in the temporary directory of the target, so later
+//! runs skip the setup and proving. Set `FASTCRYPTO_ZKP_FULL_CORPUS` to use the full number of
+//! constraints of the shaped circuits, which only makes the first run slower.
+use ark_bls12_381::Bls12_381;
+use ark_bn254::Bn254;
+use ark_crypto_primitives::snark::SNARK;
+use ark_ec::pairing::Pairing;
+use ark_groth16::{Groth16, Proof, VerifyingKey};
+use ark_relations::r1cs::ConstraintSynthesizer;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use criterion::{
+    criterion_group, criterion_main, measurement::Measurement, BenchmarkGroup, BenchmarkId,
+    Criterion,
+};
+use fastcrypto_zkp::bench_circuits::corpus;
+use fastcrypto_zkp::poseidon::{bls12381_instance, bn254_instance};
+use fastcrypto_zkp::{bls12381, bn254};
+use std::fs;
+use std::path::PathBuf;
+
+/// A verifying key with a proof and its public inputs.
+struct Fixture<E: Pairing> {
+    vk: VerifyingKey<E>,
+    proof: Proof<E>,
+    public_inputs: Vec<E::ScalarField>,
+}
+
+impl<E: Pairing> Fixture<E> {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.vk.serialize_compressed(&mut bytes).unwrap();
+        self.proof.serialize_compressed(&mut bytes).unwrap();
+        self.public_inputs.serialize_compressed(&mut bytes).unwrap();
+        bytes
+    }
+
+    fn from_bytes(mut bytes: &[u8]) -> Option<Self> {
+        Some(Self {
+            vk: VerifyingKey::deserialize_compressed(&mut bytes).ok()?,
+            proof: Proof::deserialize_compressed(&mut bytes).ok()?,
+            public_inputs: Vec::deserialize_compressed(&mut bytes).ok()?,
+        })
+    }
+}
+
+fn full_size() -> bool {
+    std::env::var_os("FASTCRYPTO_ZKP_FULL_CORPUS").is_some()
+}
+
+/// Load the fixture of a circuit, or generate and store it if there is none.
+fn fixture<E: Pairing, C: ConstraintSynthesizer<E::ScalarField> + Clone>(
+    curve: &str,
+    name: &str,
+    circuit: C,
+    public_inputs: Vec<E::ScalarField>,
+) -> Fixture<E> {
+    let size = if full_size() { "full" } else { "reduced" };
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR"))
+        .join("bench-fixtures")
+        .join(format!("{}-{}-{}.bin", curve, name.replace(' ', "-"), size));
+    if let Some(fixture) = fs::read(&path)
+        .ok()
+        .and_then(|bytes| Fixture::from_bytes(&bytes))
+    {
+        return fixture;
+    }
+
+    let rng = &mut StdRng::from_seed([0; 32]);
+    let (pk, vk) = Groth16::<E>::circuit_specific_setup(circuit.clone(), rng).unwrap();
+    let proof = Groth16::<E>::prove(&pk, circuit, rng).unwrap();
+    let fixture = Fixture {
+        vk,
+        proof,
+        public_inputs,
+    };
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, fixture.to_bytes()).unwrap();
+    fixture
+}
+
+fn bench_bn254<M: Measurement>(grp: &mut BenchmarkGroup<M>) {
+    let rng = &mut StdRng::from_seed([0; 32]);
+    for (name, circuit) in corpus(bn254_instance(2).unwrap(), full_size(), rng).unwrap() {
+        let public_inputs = circuit.public_inputs();
+        let fixture = fixture::<Bn254, _>("bn254", name, circuit, public_inputs);
+
+        let ark_pvk = Groth16::<Bn254>::process_vk(&fixture.vk).unwrap();
+        grp.bench_function(BenchmarkId::new("Arkworks verify", name), |b| {
+            b.iter(|| {
+                Groth16::<Bn254>::verify_with_processed_vk(
+                    &ark_pvk,
+                    &fixture.public_inputs,
+                    &fixture.proof,
+                )
+                .unwrap()
+            })
+        });
+
+        let pvk = bn254::verifier::process_vk_special(&fixture.vk.clone().into());
+        let public_inputs = fixture
+            .public_inputs
+            .iter()
+            .map(|x| (*x).into())
+            .collect::<Vec<bn254::FieldElement>>();
+        let proof = fixture.proof.clone().into();
+        grp.bench_function(BenchmarkId::new("Fastcrypto verify", name), |b| {
+            b.iter(|| {
+                bn254::verifier::verify_with_processed_vk(&pvk, &public_inputs, &proof).unwrap()
+            })
+        });
+    }
+}
+
+fn bench_bls12381<M: Measurement>(grp: &mut BenchmarkGroup<M>) {
+    let rng = &mut StdRng::from_seed([0; 32]);
+    for (name, circuit) in corpus(bls12381_instance(2).unwrap(), full_size(), rng).unwrap() {
+        let public_inputs = circuit.public_inputs();
+        let fixture = fixture::<Bls12_381, _>("bls12381", name, circuit, public_inputs);
+
+        let ark_pvk = Groth16::<Bls12_381>::process_vk(&fixture.vk).unwrap();
+        grp.bench_function(BenchmarkId::new("Arkworks verify", name), |b| {
+            b.iter(|| {
+                Groth16::<Bls12_381>::verify_with_processed_vk(
+                    &ark_pvk,
+                    &fixture.public_inputs,
+                    &fixture.proof,
+                )
+                .unwrap()
+            })
+        });
+
+        let pvk = bls12381::verifier::process_vk_special(&fixture.vk.clone().into());
+        let public_inputs = fixture
+            .public_inputs
+            .iter()
+            .map(|x| (*x).into())
+            .collect::<Vec<bls12381::FieldElement>>();
+        let proof = fixture.proof.clone().into();
+        grp.bench_function(BenchmarkId::new("BLST-based verify", name), |b| {
+            b.iter(|| {
+                bls12381::verifier::verify_with_processed_vk(&pvk, &public_inputs, &proof).unwrap()
+            })
+        });
+    }
+}
+
+fn verify(c: &mut Criterion) {
+    let mut group: BenchmarkGroup<_> = c.benchmark_group("BN254 Corpus Verification");
+    bench_bn254(&mut group);
+    group.finish();
+
+    let mut group: BenchmarkGroup<_> = c.benchmark_group("BLS12-381 Corpus Verification");
+    bench_bls12381(&mut group);
+    group.finish();
+}
+
+criterion_group! {
+    name = circuit_benches;
+    config = Criterion::default();
+    targets = verify,
+}
+
+criterion_main!(circuit_benches);
@@ -0,0 +1,713 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Aggregation of Groth16 proofs for the same circuit following
+//! [SnarkPack](https://eprint.iacr.org/2021/529), over any pairing-friendly curve supported by
+//! arkworks.
+//!
+//! An aggregate of `n` proofs `(A_i, B_i, C_i)` consists of a constant number of group elements and
+//! `O(log n)` elements for the inner product arguments, and is verified with a constant number of
+//! pairings and `O(log n)` exponentiations in the target group. The number of proofs must be a
+//! power of two at least 2 and at most [ProverSrs::max_proofs].
+//!
+//! The prover commits to the vectors `A`, `B` and `C` using pairing based commitments with keys
+//! `v = (h^(a^i), h^(b^i))` and `w = (g^(a^(n+i)), g^(b^(n+i)))`, derives a random `r` and proves
+//! with TIPP that `Z_AB = prod e(A_i, B_i)^(r^i)` and with MIPP that `Z_C = prod C_i^(r^i)` for the
+//! committed vectors. The folded commitment keys are checked with KZG openings at a random point.
+//! The verifier then checks the randomized Groth16 equation
+//! `Z_AB = e(alpha, beta)^(sum r^i) * e(sum r^i S_i, gamma) * e(Z_C, delta)`, where `S_i` is the
+//! linear combination of the public inputs of the `i`'th proof with the verifying key.
+//!
+//! The structured reference string with the secrets `a` and `b` must come from a trusted setup,
+//! e.g. from two independent powers-of-tau ceremonies, and be at least twice as large as the
+//! maximal number of proofs. All challenges are derived with Fiat–Shamir from a transcript binding
+//! the verifying key, the public inputs and all messages of the prover.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto_zkp::aggregation::*;
+//! # use fastcrypto_zkp::dummy_circuits::DummyCircuit;
+//! # use ark_bls12_381::{Bls12_381, Fr};
+//! # use ark_crypto_primitives::snark::SNARK;
+//! # use ark_groth16::Groth16;
+//! # use ark_std::rand::thread_rng;
+//! # use ark_std::UniformRand;
+//! let rng = &mut thread_rng();
+//! let circuit = |a: Fr, b: Fr| DummyCircuit {
+//!     a: Some(a),
+//!     b: Some(b),
+//!     num_variables: 4,
+//!     num_constraints: 10,
+//! };
+//! let (pk, vk) = Groth16::<Bls12_381>::circuit_specific_setup(circuit(Fr::rand(rng), Fr::rand(rng)), rng).unwrap();
+//! let mut proofs = Vec::new();
+//! let mut inputs = Vec::new();
+//! for _ in 0..4 {
+//!     let (a, b) = (Fr::rand(rng), Fr::rand(rng));
+//!     proofs.push(Groth16::<Bls12_381>::prove(&pk, circuit(a, b), rng).unwrap());
+//!     inputs.push(vec![a * b]);
+//! }
+//!
+//! // Only for testing. Use an SRS from a trusted setup in production.
+//! let srs = ProverSrs::<Bls12_381>::setup_insecure(8, rng).unwrap();
+//! let aggregate = aggregate_proofs(&srs, &vk, &inputs, &proofs).unwrap();
+//! assert!(verify_aggregate_proof(&srs.verifier_srs(), &vk, &inputs, &aggregate).unwrap());
+//! ```
+
+use crate::plonk::Transcript;
+use crate::serialization::{Decoder, Encoder};
+use ark_ec::pairing::{Pairing, PairingOutput};
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{Field, One, UniformRand, Zero};
+use ark_groth16::{Proof, VerifyingKey};
+use ark_std::rand::Rng;
+use fastcrypto::error::FastCryptoError;
+use fastcrypto::limits::{enforce, Limit};
+
+#[cfg(test)]
+#[path = "unit_tests/aggregation_tests.rs"]
+mod aggregation_tests;
+
+/// Domain separation tag for the Fiat–Shamir transcript.
+const TRANSCRIPT_DST: &[u8] = b"fastcrypto-zkp-snarkpack-v1";
+
+/// The maximal number of rounds of the inner product arguments, i.e., the logarithm of the
+/// maximal number of proofs in an aggregate.
+const MAX_ROUNDS: usize = 32;
+
+/// The structured reference string used to aggregate proofs, consisting of the powers
+/// `g^(a^i)` and `g^(b^i)` for `i < 2n` and `h^(a^i)` and `h^(b^i)` for `i < n`, where `n` is the
+/// maximal number of proofs in an aggregate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProverSrs<E: Pairing> {
+    g_a: Vec<E::G1Affine>,
+    g_b: Vec<E::G1Affine>,
+    h_a: Vec<E::G2Affine>,
+    h_b: Vec<E::G2Affine>,
+}
+
+/// The part of the structured reference string needed to verify aggregates.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifierSrs<E: Pairing> {
+    /// The generator `g` of `E::G1`.
+    pub g: E::G1Affine,
+    /// The generator `h` of `E::G2`.
+    pub h: E::G2Affine,
+    /// The element `g^a`.
+    pub g_a: E::G1Affine,
+    /// The element `g^b`.
+    pub g_b: E::G1Affine,
+    /// The element `h^a`.
+    pub h_a: E::G2Affine,
+    /// The element `h^b`.
+    pub h_b: E::G2Affine,
+}
+
+/// The messages of one round of the inner product arguments: The commitments to and the inner
+/// products of the cross terms of the left and right halves of the vectors.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GipaRound<E: Pairing> {
+    /// Commitments to `(A_R, B_L)` with the keys `(v_L, w_R)`.
+    pub com_ab_left: [PairingOutput<E>; 2],
+    /// Commitments to `(A_L, B_R)` with the keys `(v_R, w_L)`.
+    pub com_ab_right: [PairingOutput<E>; 2],
+    /// The inner product of `A_R` and `B_L`.
+    pub z_ab_left: PairingOutput<E>,
+    /// The inner product of `A_L` and `B_R`.
+    pub z_ab_right: PairingOutput<E>,
+    /// Commitments to `C_R` with the keys `v_L`.
+    pub com_c_left: [PairingOutput<E>; 2],
+    /// Commitments to `C_L` with the keys `v_R`.
+    pub com_c_right: [PairingOutput<E>; 2],
+    /// The inner product of `C_R` and the left half of the scalars.
+    pub z_c_left: E::G1Affine,
+    /// The inner product of `C_L` and the right half of the scalars.
+    pub z_c_right: E::G1Affine,
+}
+
+/// An aggregate of Groth16 proofs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AggregateProof<E: Pairing> {
+    /// Commitments to the vectors `A` and `B` of the proofs.
+    pub com_ab: [PairingOutput<E>; 2],
+    /// Commitments to the vector `C` of the proofs.
+    pub com_c: [PairingOutput<E>; 2],
+    /// The randomized inner product `prod e(A_i, B_i)^(r^i)`.
+    pub z_ab: PairingOutput<E>,
+    /// The randomized sum `prod C_i^(r^i)`.
+    pub z_c: E::G1Affine,
+    /// The rounds of the inner product arguments. The number of proofs is two to the power of the
+    /// number of rounds.
+    pub rounds: Vec<GipaRound<E>>,
+    /// The element of the folded vector `A`.
+    pub final_a: E::G1Affine,
+    /// The element of the folded vector `B`.
+    pub final_b: E::G2Affine,
+    /// The element of the folded vector `C`.
+    pub final_c: E::G1Affine,
+    /// The folded commitment keys `v`.
+    pub final_v: [E::G2Affine; 2],
+    /// The folded commitment keys `w`.
+    pub final_w: [E::G1Affine; 2],
+    /// KZG opening proofs for the folded keys `v`.
+    pub opening_v: [E::G2Affine; 2],
+    /// KZG opening proofs for the folded keys `w`.
+    pub opening_w: [E::G1Affine; 2],
+}
+
+impl<E: Pairing> ProverSrs<E> {
+    /// Create an SRS from the powers `g^(a^i)` and `g^(b^i)` for `i < 2n`, and `h^(a^i)` and
+    /// `h^(b^i)` for `i < n`, where `n` is a power of two at least 2. Fails if the lengths are
+    /// inconsistent or the powers do not start with the same generators. The powers are not checked
+    /// to be consistent, so they must come from a trusted source.
+    pub fn new(
+        g_a: Vec<E::G1Affine>,
+        g_b: Vec<E::G1Affine>,
+        h_a: Vec<E::G2Affine>,
+        h_b: Vec<E::G2Affine>,
+    ) -> Result<Self, FastCryptoError> {
+        let n = h_a.len();
+        if n < 2
+            || !n.is_power_of_two()
+            || h_b.len() != n
+            || g_a.len() != 2 * n
+            || g_b.len() != 2 * n
+            || g_a[0] != g_b[0]
+            || h_a[0] != h_b[0]
+            || g_a[0].is_zero()
+            || h_a[0].is_zero()
+        {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(Self { g_a, g_b, h_a, h_b })
+    }
+
+    /// Generate an SRS for up to `max_proofs` proofs, which must be a power of two at least 2. The
+    /// secrets are sampled from the given RNG and known to the caller, so this must only be used
+    /// for testing.
+    pub fn setup_insecure<R: Rng>(max_proofs: usize, rng: &mut R) -> Result<Self, FastCryptoError> {
+        if max_proofs < 2 || !max_proofs.is_power_of_two() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let a = E::ScalarField::rand(rng);
+        let b = E::ScalarField::rand(rng);
+        let g = E::G1Affine::generator();
+        let h = E::G2Affine::generator();
+        Self::new(
+            powers::<E::G1Affine>(g, a, 2 * max_proofs),
+            powers::<E::G1Affine>(g, b, 2 * max_proofs),
+            powers::<E::G2Affine>(h, a, max_proofs),
+            powers::<E::G2Affine>(h, b, max_proofs),
+        )
+    }
+
+    /// The maximal number of proofs which can be aggregated with this SRS.
+    pub fn max_proofs(&self) -> usize {
+        self.h_a.len()
+    }
+
+    /// The part of this SRS needed to verify aggregates.
+    pub fn verifier_srs(&self) -> VerifierSrs<E> {
+        VerifierSrs {
+            g: self.g_a[0],
+            h: self.h_a[0],
+            g_a: self.g_a[1],
+            g_b: self.g_b[1],
+            h_a: self.h_a[1],
+            h_b: self.h_b[1],
+        }
+    }
+}
+
+impl<E: Pairing> AggregateProof<E> {
+    /// The number of aggregated proofs.
+    pub fn num_proofs(&self) -> usize {
+        1 << self.rounds.len()
+    }
+
+    /// Serialize this aggregate as the version byte, the number of rounds and the compressed
+    /// encodings of all elements in the order of the fields. See [crate::serialization].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut encoder = Encoder::new();
+        encoder.write(&(self.rounds.len() as u8));
+        self.com_ab.iter().for_each(|x| encoder.write(x));
+        self.com_c.iter().for_each(|x| encoder.write(x));
+        encoder.write(&self.z_ab);
+        encoder.write(&self.z_c);
+        for round in &self.rounds {
+            round.com_ab_left.iter().for_each(|x| encoder.write(x));
+            round.com_ab_right.iter().for_each(|x| encoder.write(x));
+            encoder.write(&round.z_ab_left);
+            encoder.write(&round.z_ab_right);
+            round.com_c_left.iter().for_each(|x| encoder.write(x));
+            round.com_c_right.iter().for_each(|x| encoder.write(x));
+            encoder.write(&round.z_c_left);
+            encoder.write(&round.z_c_right);
+        }
+        encoder.write(&self.final_a);
+        encoder.write(&self.final_b);
+        encoder.write(&self.final_c);
+        self.final_v.iter().for_each(|x| encoder.write(x));
+        self.final_w.iter().for_each(|x| encoder.write(x));
+        self.opening_v.iter().for_each(|x| encoder.write(x));
+        self.opening_w.iter().for_each(|x| encoder.write(x));
+        encoder.finish()
+    }
+
+    /// Deserialize an aggregate serialized with [AggregateProof::to_bytes]. Fails if the encoding
+    /// is longer than allowed by [Limit::ProofBytes] or if the aggregate has more proofs than
+    /// allowed by [Limit::AggregateParticipants].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        enforce(Limit::ProofBytes, bytes.len())?;
+        let mut decoder = Decoder::new(bytes)?;
+        let num_rounds = decoder.read::<u8>()? as usize;
+        if num_rounds == 0 || num_rounds > MAX_ROUNDS {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        enforce(Limit::AggregateParticipants, 1 << num_rounds)?;
+        let com_ab = [decoder.read()?, decoder.read()?];
+        let com_c = [decoder.read()?, decoder.read()?];
+        let z_ab = decoder.read()?;
+        let z_c = decoder.read()?;
+        let rounds = (0..num_rounds)
+            .map(|_| {
+                Ok(GipaRound {
+                    com_ab_left: [decoder.read()?, decoder.read()?],
+                    com_ab_right: [decoder.read()?, decoder.read()?],
+                    z_ab_left: decoder.read()?,
+                    z_ab_right: decoder.read()?,
+                    com_c_left: [decoder.read()?, decoder.read()?],
+                    com_c_right: [decoder.read()?, decoder.read()?],
+                    z_c_left: decoder.read()?,
+                    z_c_right: decoder.read()?,
+                })
+            })
+            .collect::<Result<Vec<_>, FastCryptoError>>()?;
+        let proof = Self {
+            com_ab,
+            com_c,
+            z_ab,
+            z_c,
+            rounds,
+            final_a: decoder.read()?,
+            final_b: decoder.read()?,
+            final_c: decoder.read()?,
+            final_v: [decoder.read()?, decoder.read()?],
+            final_w: [decoder.read()?, decoder.read()?],
+            opening_v: [decoder.read()?, decoder.read()?],
+            opening_w: [decoder.read()?, decoder.read()?],
+        };
+        decoder.finish()?;
+        Ok(proof)
+    }
+}
+
+/// Aggregate Groth16 proofs for the given verifying key and public inputs. The number of proofs
+/// must be a power of two between 2 and [ProverSrs::max_proofs]. The proofs are not verified, so
+/// the aggregate is only valid if all proofs are valid.
+pub fn aggregate_proofs<E: Pairing>(
+    srs: &ProverSrs<E>,
+    vk: &VerifyingKey<E>,
+    public_inputs: &[Vec<E::ScalarField>],
+    proofs: &[Proof<E>],
+) -> Result<AggregateProof<E>, FastCryptoError> {
+    let n = proofs.len();
+    if n < 2 || !n.is_power_of_two() || n > srs.max_proofs() || public_inputs.len() != n {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    check_public_inputs(vk, public_inputs)?;
+
+    let a = proofs.iter().map(|p| p.a).collect::<Vec<_>>();
+    let b = proofs.iter().map(|p| p.b).collect::<Vec<_>>();
+    let c = proofs.iter().map(|p| p.c).collect::<Vec<_>>();
+    let v = [srs.h_a[..n].to_vec(), srs.h_b[..n].to_vec()];
+    let w = [srs.g_a[n..2 * n].to_vec(), srs.g_b[n..2 * n].to_vec()];
+
+    let com_ab = [0, 1].map(|k| pair_commitment::<E>(&a, &b, &v[k], &w[k]));
+    let com_c = [0, 1].map(|k| inner_pairing::<E>(&c, &v[k]));
+    let mut transcript = new_transcript(vk, public_inputs, &com_ab, &com_c);
+    let r = transcript.challenge::<E::ScalarField>();
+    let r_inv = r.inverse().ok_or(FastCryptoError::GeneralOpaqueError)?;
+
+    // Rescale A and C by the powers of r and the keys v by the inverse powers, so the commitments
+    // are also commitments to the rescaled vectors.
+    let mut a = scale(&a, &scalar_powers(r, n));
+    let mut b = b;
+    let mut c = scale(&c, &scalar_powers(r, n));
+    let mut v = v.map(|v| scale(&v, &scalar_powers(r_inv, n)));
+    let mut w = w;
+    let z_ab = inner_pairing::<E>(&a, &b);
+    let z_c = sum(&c);
+    transcript.append(&z_ab);
+    transcript.append(&z_c);
+
+    // The scalars of MIPP are initially all one and remain equal to each other when folded.
+    let mut s = E::ScalarField::one();
+    let mut rounds = Vec::new();
+    let mut challenges = Vec::new();
+    while a.len() > 1 {
+        let half = a.len() / 2;
+        let (a_l, a_r) = a.split_at(half);
+        let (b_l, b_r) = b.split_at(half);
+        let (c_l, c_r) = c.split_at(half);
+        let round = GipaRound {
+            com_ab_left: [0, 1]
+                .map(|k| pair_commitment::<E>(a_r, b_l, &v[k][..half], &w[k][half..])),
+            com_ab_right: [0, 1]
+                .map(|k| pair_commitment::<E>(a_l, b_r, &v[k][half..], &w[k][..half])),
+            z_ab_left: inner_pairing::<E>(a_r, b_l),
+            z_ab_right: inner_pairing::<E>(a_l, b_r),
+            com_c_left: [0, 1].map(|k| inner_pairing::<E>(c_r, &v[k][..half])),
+            com_c_right: [0, 1].map(|k| inner_pairing::<E>(c_l, &v[k][half..])),
+            z_c_left: (sum::<E::G1Affine>(c_r) * s).into_affine(),
+            z_c_right: (sum::<E::G1Affine>(c_l) * s).into_affine(),
+        };
+        append_round(&mut transcript, &round);
+        let x = transcript.challenge::<E::ScalarField>();
+        let x_inv = x.inverse().ok_or(FastCryptoError::GeneralOpaqueError)?;
+
+        a = fold(&a, x);
+        b = fold(&b, x_inv);
+        c = fold(&c, x);
+        v = v.map(|v| fold(&v, x_inv));
+        w = w.map(|w| fold(&w, x));
+        s *= E::ScalarField::one() + x_inv;
+        rounds.push(round);
+        challenges.push(x);
+    }
+
+    let final_v = [v[0][0], v[1][0]];
+    let final_w = [w[0][0], w[1][0]];
+    append_final::<E>(&mut transcript, a[0], b[0], c[0], &final_v, &final_w);
+    let z = transcript.challenge::<E::ScalarField>();
+
+    let v_polynomial = key_v_polynomial(&challenges, r_inv)?;
+    let w_polynomial = key_w_polynomial(&challenges);
+    let v_quotient = divide_by_linear(&v_polynomial, z);
+    let w_quotient = divide_by_linear(&w_polynomial, z);
+    let opening_v = [&srs.h_a, &srs.h_b]
+        .map(|bases| msm::<E::G2Affine>(&bases[..v_quotient.len()], &v_quotient));
+    let opening_w = [&srs.g_a, &srs.g_b]
+        .map(|bases| msm::<E::G1Affine>(&bases[..w_quotient.len()], &w_quotient));
+
+    Ok(AggregateProof {
+        com_ab,
+        com_c,
+        z_ab,
+        z_c,
+        rounds,
+        final_a: a[0],
+        final_b: b[0],
+        final_c: c[0],
+        final_v,
+        final_w,
+        opening_v,
+        opening_w,
+    })
+}
+
+/// Verify an aggregate of Groth16 proofs for the given verifying key and public inputs. Returns an
+/// error if the number of public inputs does not match the aggregate or the verifying key, or if
+/// there are more proofs than allowed by [Limit::AggregateParticipants].
+pub fn verify_aggregate_proof<E: Pairing>(
+    srs: &VerifierSrs<E>,
+    vk: &VerifyingKey<E>,
+    public_inputs: &[Vec<E::ScalarField>],
+    proof: &AggregateProof<E>,
+) -> Result<bool, FastCryptoError> {
+    if proof.rounds.is_empty()
+        || proof.rounds.len() > MAX_ROUNDS
+        || public_inputs.len() != proof.num_proofs()
+    {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    enforce(Limit::AggregateParticipants, public_inputs.len())?;
+    check_public_inputs(vk, public_inputs)?;
+    let n = proof.num_proofs();
+
+    let mut transcript = new_transcript(vk, public_inputs, &proof.com_ab, &proof.com_c);
+    let r = transcript.challenge::<E::ScalarField>();
+    let r_inv = r.inverse().ok_or(FastCryptoError::GeneralOpaqueError)?;
+    transcript.append(&proof.z_ab);
+    transcript.append(&proof.z_c);
+
+    // The randomized Groth16 equation.
+    let r_powers = scalar_powers(r, n);
+    let r_sum = r_powers.iter().sum::<E::ScalarField>();
+    let input_scalars = (0..vk.gamma_abc_g1.len() - 1)
+        .map(|j| {
+            r_powers
+                .iter()
+                .zip(public_inputs)
+                .map(|(r_i, x)| *r_i * x[j])
+                .sum()
+        })
+        .collect::<Vec<_>>();
+    let inputs =
+        vk.gamma_abc_g1[0] * r_sum + msm::<E::G1Affine>(&vk.gamma_abc_g1[1..], &input_scalars);
+    let expected = E::pairing(vk.alpha_g1, vk.beta_g2) * r_sum
+        + E::multi_pairing(
+            [inputs.into_affine(), proof.z_c],
+            [vk.gamma_g2, vk.delta_g2],
+        );
+    if expected != proof.z_ab {
+        return Ok(false);
+    }
+
+    // Fold the commitments and inner products with the challenges of each round.
+    let mut com_ab = proof.com_ab;
+    let mut com_c = proof.com_c;
+    let mut z_ab = proof.z_ab;
+    let mut z_c = proof.z_c.into_group();
+    let mut s = E::ScalarField::one();
+    let mut challenges = Vec::new();
+    for round in &proof.rounds {
+        append_round(&mut transcript, round);
+        let x = transcript.challenge::<E::ScalarField>();
+        let x_inv = x.inverse().ok_or(FastCryptoError::GeneralOpaqueError)?;
+        for k in 0..2 {
+            com_ab[k] += round.com_ab_left[k] * x + round.com_ab_right[k] * x_inv;
+            com_c[k] += round.com_c_left[k] * x + round.com_c_right[k] * x_inv;
+        }
+        z_ab += round.z_ab_left * x + round.z_ab_right * x_inv;
+        z_c += round.z_c_left * x + round.z_c_right * x_inv;
+        s *= E::ScalarField::one() + x_inv;
+        challenges.push(x);
+    }
+    append_final::<E>(
+        &mut transcript,
+        proof.final_a,
+        proof.final_b,
+        proof.final_c,
+        &proof.final_v,
+        &proof.final_w,
+    );
+    let z = transcript.challenge::<E::ScalarField>();
+
+    // The final elements must open the folded commitments and inner products.
+    for k in 0..2 {
+        if com_ab[k]
+            != E::multi_pairing(
+                [proof.final_a, proof.final_w[k]],
+                [proof.final_v[k], proof.final_b],
+            )
+            || com_c[k] != E::pairing(proof.final_c, proof.final_v[k])
+        {
+            return Ok(false);
+        }
+    }
+    if z_ab != E::pairing(proof.final_a, proof.final_b) || z_c != proof.final_c * s {
+        return Ok(false);
+    }
+
+    // The folded keys must be the evaluations of the key polynomials at the secrets of the SRS.
+    let v_eval = evaluate_key_v_polynomial(&challenges, r_inv, z);
+    let w_eval = evaluate_key_w_polynomial(&challenges, z);
+    for (k, (g_secret, h_secret)) in [(srs.g_a, srs.h_a), (srs.g_b, srs.h_b)]
+        .into_iter()
+        .enumerate()
+    {
+        let v_check = E::multi_pairing(
+            [srs.g.into_group(), srs.g * z - g_secret],
+            [
+                proof.final_v[k].into_group() - srs.h * v_eval,
+                proof.opening_v[k].into_group(),
+            ],
+        );
+        let w_check = E::multi_pairing(
+            [
+                proof.final_w[k].into_group() - srs.g * w_eval,
+                proof.opening_w[k].into_group(),
+            ],
+            [srs.h.into_group(), srs.h * z - h_secret],
+        );
+        if !v_check.is_zero() || !w_check.is_zero() {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+fn check_public_inputs<E: Pairing>(
+    vk: &VerifyingKey<E>,
+    public_inputs: &[Vec<E::ScalarField>],
+) -> Result<(), FastCryptoError> {
+    if vk.gamma_abc_g1.is_empty()
+        || public_inputs
+            .iter()
+            .any(|x| x.len() + 1 != vk.gamma_abc_g1.len())
+    {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    Ok(())
+}
+
+fn new_transcript<E: Pairing>(
+    vk: &VerifyingKey<E>,
+    public_inputs: &[Vec<E::ScalarField>],
+    com_ab: &[PairingOutput<E>; 2],
+    com_c: &[PairingOutput<E>; 2],
+) -> Transcript {
+    let mut transcript = Transcript(TRANSCRIPT_DST.to_vec());
+    transcript.append(vk);
+    transcript.append(&public_inputs.to_vec());
+    com_ab
+        .iter()
+        .chain(com_c)
+        .for_each(|x| transcript.append(x));
+    transcript
+}
+
+fn append_round<E: Pairing>(transcript: &mut Transcript, round: &GipaRound<E>) {
+    round
+        .com_ab_left
+        .iter()
+        .chain(&round.com_ab_right)
+        .chain([&round.z_ab_left, &round.z_ab_right])
+        .chain(&round.com_c_left)
+        .chain(&round.com_c_right)
+        .for_each(|x| transcript.append(x));
+    transcript.append(&round.z_c_left);
+    transcript.append(&round.z_c_right);
+}
+
+fn append_final<E: Pairing>(
+    transcript: &mut Transcript,
+    a: E::G1Affine,
+    b: E::G2Affine,
+    c: E::G1Affine,
+    v: &[E::G2Affine; 2],
+    w: &[E::G1Affine; 2],
+) {
+    transcript.append(&a);
+    transcript.append(&b);
+    transcript.append(&c);
+    v.iter().for_each(|x| transcript.append(x));
+    w.iter().for_each(|x| transcript.append(x));
+}
+
+/// The inner product `prod e(a_i, b_i)` of the vectors `a` and `b`.
+fn inner_pairing<E: Pairing>(a: &[E::G1Affine], b: &[E::G2Affine]) -> PairingOutput<E> {
+    E::multi_pairing(a.iter().copied(), b.iter().copied())
+}
+
+/// The commitment `prod e(a_i, v_i) * prod e(w_i, b_i)` to the vectors `a` and `b`.
+fn pair_commitment<E: Pairing>(
+    a: &[E::G1Affine],
+    b: &[E::G2Affine],
+    v: &[E::G2Affine],
+    w: &[E::G1Affine],
+) -> PairingOutput<E> {
+    E::multi_pairing(a.iter().chain(w).copied(), v.iter().chain(b).copied())
+}
+
+/// The powers `1, x, ..., x^(n-1)`.
+fn scalar_powers<F: Field>(x: F, n: usize) -> Vec<F> {
+    std::iter::successors(Some(F::one()), |p| Some(*p * x))
+        .take(n)
+        .collect()
+}
+
+/// The elements `g * x^i` for `i < n`.
+fn powers<A: AffineRepr>(g: A, x: A::ScalarField, n: usize) -> Vec<A> {
+    scale(&vec![g; n], &scalar_powers(x, n))
+}
+
+fn scale<A: AffineRepr>(points: &[A], scalars: &[A::ScalarField]) -> Vec<A> {
+    let scaled = points
+        .iter()
+        .zip(scalars)
+        .map(|(p, s)| *p * s)
+        .collect::<Vec<_>>();
+    A::Group::normalize_batch(&scaled)
+}
+
+fn sum<A: AffineRepr>(points: &[A]) -> A::Group {
+    points.iter().map(|p| p.into_group()).sum()
+}
+
+fn msm<A: AffineRepr>(bases: &[A], scalars: &[A::ScalarField]) -> A {
+    A::Group::msm(bases, scalars)
+        .expect("Lengths are equal")
+        .into_affine()
+}
+
+/// Fold a vector `(L, R)` to `L + R * x`.
+fn fold<A: AffineRepr>(points: &[A], x: A::ScalarField) -> Vec<A> {
+    let (left, right) = points.split_at(points.len() / 2);
+    let folded = left
+        .iter()
+        .zip(right)
+        .map(|(l, r)| *r * x + l)
+        .collect::<Vec<_>>();
+    A::Group::normalize_batch(&folded)
+}
+
+/// The coefficients of `prod_j (1 + c_j X^(2^j))`, where the factors are given from the highest
+/// to the lowest power `2^j`.
+fn product_polynomial<F: Field>(factors: &[F]) -> Vec<F> {
+    let mut coefficients = vec![F::one()];
+    for c in factors.iter().rev() {
+        let shifted = coefficients.iter().map(|x| *x * c).collect::<Vec<_>>();
+        coefficients.extend(shifted);
+    }
+    coefficients
+}
+
+/// The polynomial `f_v` such that the folded key `v` is `h^(f_v(a))`. The keys are rescaled by the
+/// inverse powers of `r` and folded by the inverses of the challenges, so
+/// `f_v(X) = prod_j (1 + x_j^(-1) r^(-n/2^j) X^(n/2^j))`.
+fn key_v_polynomial<F: Field>(challenges: &[F], r_inv: F) -> Result<Vec<F>, FastCryptoError> {
+    let n = 1usize << challenges.len();
+    let factors = challenges
+        .iter()
+        .enumerate()
+        .map(|(j, x)| {
+            let x_inv = x.inverse().ok_or(FastCryptoError::GeneralOpaqueError)?;
+            Ok(x_inv * r_inv.pow([(n >> (j + 1)) as u64]))
+        })
+        .collect::<Result<Vec<_>, FastCryptoError>>()?;
+    Ok(product_polynomial(&factors))
+}
+
+/// The polynomial `f_w` such that the folded key `w` is `g^(f_w(a))`, i.e.,
+/// `f_w(X) = X^n prod_j (1 + x_j X^(n/2^j))`.
+fn key_w_polynomial<F: Field>(challenges: &[F]) -> Vec<F> {
+    let n = 1usize << challenges.len();
+    let mut coefficients = vec![F::zero(); n];
+    coefficients.extend(product_polynomial(challenges));
+    coefficients
+}
+
+fn evaluate_key_v_polynomial<F: Field>(challenges: &[F], r_inv: F, z: F) -> F {
+    let n = 1usize << challenges.len();
+    challenges
+        .iter()
+        .enumerate()
+        .map(|(j, x)| {
+            let power = [(n >> (j + 1)) as u64];
+            F::one() + x.inverse().expect("Challenges are non-zero") * (r_inv * z).pow(power)
+        })
+        .product()
+}
+
+fn evaluate_key_w_polynomial<F: Field>(challenges: &[F], z: F) -> F {
+    let n = 1usize << challenges.len();
+    z.pow([n as u64])
+        * challenges
+            .iter()
+            .enumerate()
+            .map(|(j, x)| F::one() + *x * z.pow([(n >> (j + 1)) as u64]))
+            .product::<F>()
+}
+
+/// The quotient of the polynomial with the given coefficients by `X - z`.
+fn divide_by_linear<F: Field>(coefficients: &[F], z: F) -> Vec<F> {
+    let mut quotient = vec![F::zero(); coefficients.len() - 1];
+    let mut carry = F::zero();
+    for i in (1..coefficients.len()).rev() {
+        carry = coefficients[i] + carry * z;
+        quotient[i - 1] = carry;
+    }
+    quotient
+}
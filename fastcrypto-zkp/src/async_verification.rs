@@ -0,0 +1,129 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Async wrappers of the Groth16 verifiers, which run the verification on the blocking thread pool
+//! of tokio using a [VerificationService], so that async services do not starve their executors
+//! while verifying proofs. Requires the `tokio` feature.
+//!
+//! Prepared verifying keys are taken as [Arc]s, so keys from a [crate::pvk_cache::PvkCache] can be
+//! used without copying them. Like the synchronous verifiers, the wrappers of
+//! `verify_groth16_in_bytes` report failures to the hook set with
+//! [crate::verification_hook::set_verification_failure_hook].
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::verification_service::VerificationService;
+//! # use fastcrypto_zkp::async_verification::*;
+//! # use fastcrypto_zkp::bn254::{verifier::process_vk_special, VerifyingKey};
+//! # use fastcrypto_zkp::dummy_circuits::DummyCircuit;
+//! # use ark_bn254::{Bn254, Fr};
+//! # use ark_crypto_primitives::snark::SNARK;
+//! # use ark_ff::One;
+//! # use ark_groth16::Groth16;
+//! # use ark_std::rand::thread_rng;
+//! # use std::sync::Arc;
+//! # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+//! let circuit = DummyCircuit::<Fr> {
+//!     a: Some(Fr::one()),
+//!     b: Some(Fr::one()),
+//!     num_variables: 4,
+//!     num_constraints: 10,
+//! };
+//! let (pk, vk) = Groth16::<Bn254>::circuit_specific_setup(circuit, &mut thread_rng()).unwrap();
+//! let proof = Groth16::<Bn254>::prove(&pk, circuit, &mut thread_rng()).unwrap();
+//! let pvk = Arc::new(process_vk_special(&VerifyingKey::from(vk)));
+//!
+//! let service = VerificationService::new(4);
+//! let valid = bn254_verify_with_processed_vk(&service, pvk, vec![Fr::one().into()], proof.into());
+//! assert!(valid.await.unwrap());
+//! # });
+//! ```
+
+use crate::{bls12381, bn254};
+use fastcrypto::error::{FastCryptoError, FastCryptoResult};
+use fastcrypto::verification_service::VerificationService;
+use std::sync::Arc;
+
+#[cfg(test)]
+#[path = "unit_tests/async_verification_tests.rs"]
+mod async_verification_tests;
+
+/// Verify a BN254 Groth16 proof with a prepared verifying key on the blocking thread pool, see
+/// [bn254::verifier::verify_with_processed_vk].
+pub async fn bn254_verify_with_processed_vk(
+    service: &VerificationService,
+    pvk: Arc<bn254::verifier::PreparedVerifyingKey>,
+    public_inputs: Vec<bn254::FieldElement>,
+    proof: bn254::Proof,
+) -> FastCryptoResult<bool> {
+    service
+        .verify(move || bn254::verifier::verify_with_processed_vk(&pvk, &public_inputs, &proof))
+        .await
+}
+
+/// Verify a BLS12-381 Groth16 proof with a prepared verifying key on the blocking thread pool, see
+/// [bls12381::verifier::verify_with_processed_vk].
+pub async fn bls12381_verify_with_processed_vk(
+    service: &VerificationService,
+    pvk: Arc<bls12381::verifier::PreparedVerifyingKey>,
+    public_inputs: Vec<bls12381::FieldElement>,
+    proof: bls12381::Proof,
+) -> FastCryptoResult<bool> {
+    service
+        .verify(move || bls12381::verifier::verify_with_processed_vk(&pvk, &public_inputs, &proof))
+        .await
+}
+
+/// Verify a serialized BN254 Groth16 proof on the blocking thread pool, where `pvk_bytes` are the
+/// four parts returned by [bn254::api::prepare_pvk_bytes]. See [bn254::api::verify_groth16_in_bytes].
+pub async fn bn254_verify_groth16_in_bytes(
+    service: &VerificationService,
+    pvk_bytes: Arc<Vec<Vec<u8>>>,
+    proof_public_inputs_as_bytes: Vec<u8>,
+    proof_points_as_bytes: Vec<u8>,
+) -> FastCryptoResult<bool> {
+    check_pvk_bytes(&pvk_bytes)?;
+    service
+        .verify(move || {
+            bn254::api::verify_groth16_in_bytes(
+                &pvk_bytes[0],
+                &pvk_bytes[1],
+                &pvk_bytes[2],
+                &pvk_bytes[3],
+                &proof_public_inputs_as_bytes,
+                &proof_points_as_bytes,
+            )
+        })
+        .await
+}
+
+/// Verify a serialized BLS12-381 Groth16 proof on the blocking thread pool, where `pvk_bytes` are
+/// the four parts returned by [bls12381::api::prepare_pvk_bytes]. See
+/// [bls12381::api::verify_groth16_in_bytes].
+pub async fn bls12381_verify_groth16_in_bytes(
+    service: &VerificationService,
+    pvk_bytes: Arc<Vec<Vec<u8>>>,
+    proof_public_inputs_as_bytes: Vec<u8>,
+    proof_points_as_bytes: Vec<u8>,
+) -> FastCryptoResult<bool> {
+    check_pvk_bytes(&pvk_bytes)?;
+    service
+        .verify(move || {
+            bls12381::api::verify_groth16_in_bytes(
+                &pvk_bytes[0],
+                &pvk_bytes[1],
+                &pvk_bytes[2],
+                &pvk_bytes[3],
+                &proof_public_inputs_as_bytes,
+                &proof_points_as_bytes,
+            )
+        })
+        .await
+}
+
+fn check_pvk_bytes(pvk_bytes: &[Vec<u8>]) -> FastCryptoResult<()> {
+    if pvk_bytes.len() != 4 {
+        return Err(FastCryptoError::InputLengthWrong(4));
+    }
+    Ok(())
+}
@@ -0,0 +1,420 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Circuits with the shapes of the statements verified in practice, so that verifiers are
+//! benchmarked on realistic workloads and not only on [crate::dummy_circuits::DummyCircuit]s:
+//! * [HashPreimage] proves knowledge of a preimage of a Poseidon digest,
+//! * [MerkleMembership] proves that a secret leaf is in a Poseidon Merkle tree with a public root,
+//! * [Shaped] circuits have a given number of public inputs and constraints, and [corpus] uses them
+//!   to imitate the verification of an ECDSA signature over secp256k1 with circom-ecdsa and a
+//!   zkLogin proof.
+//!
+//! The verification cost of Groth16 depends only on the number of public inputs, so the
+//! constraints of the [Shaped] circuits are arbitrary and only affect the proving time.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto_zkp::bench_circuits::*;
+//! # use fastcrypto_zkp::poseidon::bn254_instance;
+//! # use ark_bn254::Bn254;
+//! # use ark_crypto_primitives::snark::SNARK;
+//! # use ark_groth16::Groth16;
+//! # use ark_std::rand::thread_rng;
+//! let circuit = MerkleMembership::random(bn254_instance(2).unwrap(), 4, &mut thread_rng()).unwrap();
+//! let public_inputs = circuit.public_inputs();
+//!
+//! let (pk, vk) = Groth16::<Bn254>::circuit_specific_setup(circuit.clone(), &mut thread_rng()).unwrap();
+//! let proof = Groth16::<Bn254>::prove(&pk, circuit, &mut thread_rng()).unwrap();
+//! assert!(Groth16::<Bn254>::verify(&vk, &public_inputs, &proof).unwrap());
+//! ```
+
+use crate::poseidon::Poseidon;
+use ark_ff::PrimeField;
+use ark_relations::lc;
+use ark_relations::r1cs::{
+    ConstraintSynthesizer, ConstraintSystemRef, LinearCombination, SynthesisError, Variable,
+};
+use ark_std::rand::Rng;
+use fastcrypto::error::FastCryptoError;
+
+#[cfg(test)]
+#[path = "unit_tests/bench_circuits_tests.rs"]
+mod bench_circuits_tests;
+
+/// The number of public inputs of the ECDSA verification circuit of circom-ecdsa, which takes the
+/// signature `(r, s)`, the message hash and the two coordinates of the public key as four 64 bit
+/// limbs each.
+pub const ECDSA_SECP256K1_PUBLIC_INPUTS: usize = 20;
+
+/// The approximate number of constraints of the ECDSA verification circuit of circom-ecdsa.
+pub const ECDSA_SECP256K1_CONSTRAINTS: usize = 1 << 21;
+
+/// The number of public inputs of zkLogin circuits, see [crate::zklogin].
+pub const ZKLOGIN_PUBLIC_INPUTS: usize = 1;
+
+/// The approximate number of constraints of zkLogin circuits.
+pub const ZKLOGIN_CONSTRAINTS: usize = 1 << 20;
+
+/// The depth of the Merkle tree in [corpus], which fits a million leaves.
+pub const CORPUS_MERKLE_DEPTH: usize = 20;
+
+/// Proves knowledge of inputs to a Poseidon instance hashing to the public digest.
+#[derive(Clone, Debug)]
+pub struct HashPreimage<F: PrimeField> {
+    poseidon: Poseidon<F>,
+    preimage: Vec<F>,
+}
+
+/// Proves that the secret leaf is in the Merkle tree with the public root, where the nodes are the
+/// Poseidon hashes of their two children.
+#[derive(Clone, Debug)]
+pub struct MerkleMembership<F: PrimeField> {
+    poseidon: Poseidon<F>,
+    leaf: F,
+    /// The siblings from the leaf to the root, and whether the node on the path is the right child.
+    path: Vec<(F, bool)>,
+}
+
+/// A circuit with the given public inputs and number of constraints, which form a chain of
+/// multiplications `w_{i+1} = w_i (w_i + x_{i mod n})` of the public inputs `x_0, ..., x_{n-1}`
+/// starting from `w_0 = x_0`. Without public inputs, the chain is `w_{i+1} = w_i w_i` starting from
+/// `w_0 = 1`.
+#[derive(Clone, Debug)]
+pub struct Shaped<F: PrimeField> {
+    public_inputs: Vec<F>,
+    num_constraints: usize,
+}
+
+/// The circuits of the benchmark corpus, see [corpus].
+#[derive(Clone, Debug)]
+pub enum BenchmarkCircuit<F: PrimeField> {
+    HashPreimage(HashPreimage<F>),
+    MerkleMembership(MerkleMembership<F>),
+    Shaped(Shaped<F>),
+}
+
+impl<F: PrimeField> HashPreimage<F> {
+    /// Fails if the instance does not hash as many elements as given.
+    pub fn new(poseidon: &Poseidon<F>, preimage: Vec<F>) -> Result<Self, FastCryptoError> {
+        if preimage.len() + 1 != poseidon.width() {
+            return Err(FastCryptoError::InputLengthWrong(poseidon.width() - 1));
+        }
+        Ok(Self {
+            poseidon: poseidon.clone(),
+            preimage,
+        })
+    }
+
+    /// The single public input, which is the digest.
+    pub fn public_inputs(&self) -> Vec<F> {
+        vec![self
+            .poseidon
+            .hash(&self.preimage)
+            .expect("The length is checked in the constructor")]
+    }
+}
+
+impl<F: PrimeField> MerkleMembership<F> {
+    /// Fails unless the instance hashes two elements.
+    pub fn new(
+        poseidon: &Poseidon<F>,
+        leaf: F,
+        path: Vec<(F, bool)>,
+    ) -> Result<Self, FastCryptoError> {
+        if poseidon.width() != 3 {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(Self {
+            poseidon: poseidon.clone(),
+            leaf,
+            path,
+        })
+    }
+
+    /// A random leaf and path of the given depth.
+    pub fn random<R: Rng>(
+        poseidon: &Poseidon<F>,
+        depth: usize,
+        rng: &mut R,
+    ) -> Result<Self, FastCryptoError> {
+        let path = (0..depth).map(|_| (F::rand(rng), rng.gen())).collect();
+        Self::new(poseidon, F::rand(rng), path)
+    }
+
+    /// The single public input, which is the root.
+    pub fn public_inputs(&self) -> Vec<F> {
+        let root = self
+            .path
+            .iter()
+            .fold(self.leaf, |node, (sibling, is_right)| {
+                let children = if *is_right {
+                    [*sibling, node]
+                } else {
+                    [node, *sibling]
+                };
+                self.poseidon
+                    .hash(&children)
+                    .expect("The width is checked in the constructor")
+            });
+        vec![root]
+    }
+}
+
+impl<F: PrimeField> Shaped<F> {
+    /// A circuit with the given public inputs. Fails if there are no constraints.
+    pub fn new(public_inputs: Vec<F>, num_constraints: usize) -> Result<Self, FastCryptoError> {
+        if num_constraints == 0 {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(Self {
+            public_inputs,
+            num_constraints,
+        })
+    }
+
+    /// A circuit with random public inputs. Fails if there are no public inputs or no constraints.
+    pub fn random<R: Rng>(
+        num_public_inputs: usize,
+        num_constraints: usize,
+        rng: &mut R,
+    ) -> Result<Self, FastCryptoError> {
+        if num_public_inputs == 0 || num_constraints == 0 {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(Self {
+            public_inputs: (0..num_public_inputs).map(|_| F::rand(rng)).collect(),
+            num_constraints,
+        })
+    }
+
+    pub fn public_inputs(&self) -> Vec<F> {
+        self.public_inputs.clone()
+    }
+}
+
+impl<F: PrimeField> BenchmarkCircuit<F> {
+    pub fn public_inputs(&self) -> Vec<F> {
+        match self {
+            Self::HashPreimage(circuit) => circuit.public_inputs(),
+            Self::MerkleMembership(circuit) => circuit.public_inputs(),
+            Self::Shaped(circuit) => circuit.public_inputs(),
+        }
+    }
+}
+
+/// The benchmark corpus with a name for each circuit:
+/// * the preimage of a Poseidon hash of two elements,
+/// * membership in a Merkle tree of depth [CORPUS_MERKLE_DEPTH],
+/// * the shape of the verification of an ECDSA signature over secp256k1, and
+/// * the shape of a zkLogin proof.
+///
+/// The poseidon instance must hash two elements. Unless `full_size` is set, the constraints of the
+/// shaped circuits are reduced by a factor of 16, which does not change their verification time.
+pub fn corpus<F: PrimeField, R: Rng>(
+    poseidon: &Poseidon<F>,
+    full_size: bool,
+    rng: &mut R,
+) -> Result<Vec<(&'static str, BenchmarkCircuit<F>)>, FastCryptoError> {
+    let scale = if full_size { 1 } else { 16 };
+    Ok(vec![
+        (
+            "hash preimage",
+            BenchmarkCircuit::HashPreimage(HashPreimage::new(
+                poseidon,
+                vec![F::rand(rng), F::rand(rng)],
+            )?),
+        ),
+        (
+            "merkle membership",
+            BenchmarkCircuit::MerkleMembership(MerkleMembership::random(
+                poseidon,
+                CORPUS_MERKLE_DEPTH,
+                rng,
+            )?),
+        ),
+        (
+            "ecdsa secp256k1",
+            BenchmarkCircuit::Shaped(Shaped::random(
+                ECDSA_SECP256K1_PUBLIC_INPUTS,
+                ECDSA_SECP256K1_CONSTRAINTS / scale,
+                rng,
+            )?),
+        ),
+        (
+            "zklogin",
+            BenchmarkCircuit::Shaped(Shaped::random(
+                ZKLOGIN_PUBLIC_INPUTS,
+                ZKLOGIN_CONSTRAINTS / scale,
+                rng,
+            )?),
+        ),
+    ])
+}
+
+impl<F: PrimeField> ConstraintSynthesizer<F> for HashPreimage<F> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> Result<(), SynthesisError> {
+        let digest = self.public_inputs()[0];
+        let digest = cs.new_input_variable(|| Ok(digest))?;
+        let inputs = self
+            .preimage
+            .iter()
+            .map(|value| Element::witness(&cs, *value))
+            .collect::<Result<Vec<_>, _>>()?;
+        let output = poseidon_hash(&cs, &self.poseidon, inputs)?;
+        cs.enforce_constraint(output.lc, lc!() + Variable::One, lc!() + digest)
+    }
+}
+
+impl<F: PrimeField> ConstraintSynthesizer<F> for MerkleMembership<F> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> Result<(), SynthesisError> {
+        let root = self.public_inputs()[0];
+        let root = cs.new_input_variable(|| Ok(root))?;
+        let mut node = Element::witness(&cs, self.leaf)?;
+        for (sibling, is_right) in self.path {
+            let sibling = Element::witness(&cs, sibling)?;
+            let bit_value = F::from(is_right);
+            let bit = cs.new_witness_variable(|| Ok(bit_value))?;
+            cs.enforce_constraint(lc!() + bit, lc!() + Variable::One - bit, lc!())?;
+
+            // The left child is node + bit * (sibling - node) and the right child is the other one.
+            let difference_value = bit_value * (sibling.value - node.value);
+            let difference = cs.new_witness_variable(|| Ok(difference_value))?;
+            cs.enforce_constraint(
+                lc!() + bit,
+                sibling.lc.clone() - &node.lc,
+                lc!() + difference,
+            )?;
+            let left = Element {
+                value: node.value + difference_value,
+                lc: node.lc + difference,
+            };
+            let right = Element {
+                value: sibling.value - difference_value,
+                lc: sibling.lc - difference,
+            };
+            node = poseidon_hash(&cs, &self.poseidon, vec![left, right])?;
+        }
+        cs.enforce_constraint(node.lc, lc!() + Variable::One, lc!() + root)
+    }
+}
+
+impl<F: PrimeField> ConstraintSynthesizer<F> for Shaped<F> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> Result<(), SynthesisError> {
+        let inputs = self
+            .public_inputs
+            .iter()
+            .map(|value| Ok((*value, cs.new_input_variable(|| Ok(*value))?)))
+            .collect::<Result<Vec<_>, SynthesisError>>()?;
+        let (mut value, mut variable) =
+            inputs.first().copied().unwrap_or((F::one(), Variable::One));
+        for i in 0..self.num_constraints {
+            let mut factor = lc!() + variable;
+            let mut factor_value = value;
+            if !inputs.is_empty() {
+                let (input_value, input) = inputs[i % inputs.len()];
+                factor = factor + input;
+                factor_value += input_value;
+            }
+            let next_value = value * factor_value;
+            let next = cs.new_witness_variable(|| Ok(next_value))?;
+            cs.enforce_constraint(lc!() + variable, factor, lc!() + next)?;
+            value = next_value;
+            variable = next;
+        }
+        Ok(())
+    }
+}
+
+impl<F: PrimeField> ConstraintSynthesizer<F> for BenchmarkCircuit<F> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> Result<(), SynthesisError> {
+        match self {
+            Self::HashPreimage(circuit) => circuit.generate_constraints(cs),
+            Self::MerkleMembership(circuit) => circuit.generate_constraints(cs),
+            Self::Shaped(circuit) => circuit.generate_constraints(cs),
+        }
+    }
+}
+
+/// A value in a circuit together with the linear combination of variables it is assigned to.
+#[derive(Clone)]
+struct Element<F: PrimeField> {
+    value: F,
+    lc: LinearCombination<F>,
+}
+
+impl<F: PrimeField> Element<F> {
+    fn witness(cs: &ConstraintSystemRef<F>, value: F) -> Result<Self, SynthesisError> {
+        let variable = cs.new_witness_variable(|| Ok(value))?;
+        Ok(Self {
+            value,
+            lc: lc!() + variable,
+        })
+    }
+}
+
+/// The constraints of [Poseidon::hash], which take three multiplications per S-box.
+fn poseidon_hash<F: PrimeField>(
+    cs: &ConstraintSystemRef<F>,
+    poseidon: &Poseidon<F>,
+    inputs: Vec<Element<F>>,
+) -> Result<Element<F>, SynthesisError> {
+    let width = poseidon.width();
+    let mut state = Vec::with_capacity(width);
+    state.push(Element {
+        value: F::zero(),
+        lc: lc!(),
+    });
+    state.extend(inputs);
+
+    let half_full_rounds = poseidon.full_rounds() / 2;
+    for (round, constants) in poseidon.round_constants().chunks(width).enumerate() {
+        for (s, c) in state.iter_mut().zip(constants) {
+            s.value += c;
+            s.lc = s.lc.clone() + (*c, Variable::One);
+        }
+        if round < half_full_rounds || round >= half_full_rounds + poseidon.partial_rounds() {
+            for s in state.iter_mut() {
+                *s = sbox(cs, s)?;
+            }
+        } else {
+            state[0] = sbox(cs, &state[0])?;
+        }
+        state = poseidon
+            .mds()
+            .iter()
+            .map(|row| {
+                row.iter().zip(state.iter()).fold(
+                    Element {
+                        value: F::zero(),
+                        lc: lc!(),
+                    },
+                    |sum, (m, s)| Element {
+                        value: sum.value + *m * s.value,
+                        lc: sum.lc + (*m, &s.lc),
+                    },
+                )
+            })
+            .collect();
+    }
+    Ok(state.swap_remove(0))
+}
+
+/// The constraints of `x^5`.
+fn sbox<F: PrimeField>(
+    cs: &ConstraintSystemRef<F>,
+    x: &Element<F>,
+) -> Result<Element<F>, SynthesisError> {
+    let square_value = x.value.square();
+    let square = cs.new_witness_variable(|| Ok(square_value))?;
+    cs.enforce_constraint(x.lc.clone(), x.lc.clone(), lc!() + square)?;
+    let fourth_value = square_value.square();
+    let fourth = cs.new_witness_variable(|| Ok(fourth_value))?;
+    cs.enforce_constraint(lc!() + square, lc!() + square, lc!() + fourth)?;
+    let fifth_value = fourth_value * x.value;
+    let fifth = cs.new_witness_variable(|| Ok(fifth_value))?;
+    cs.enforce_constraint(lc!() + fourth, x.lc.clone(), lc!() + fifth)?;
+    Ok(Element {
+        value: fifth_value,
+        lc: lc!() + fifth,
+    })
+}
@@ -3,13 +3,17 @@
 
 use ark_bls12_381::Bls12_381;
 use ark_groth16::{Proof, VerifyingKey};
-use ark_serialize::CanonicalDeserialize;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use fastcrypto::error::FastCryptoError;
+use fastcrypto::limits::Limit;
 
 use crate::bls12381::conversions::{BlsFr, SCALAR_SIZE};
 use crate::bls12381::verifier::{
     process_vk_special, verify_with_processed_vk, PreparedVerifyingKey,
 };
+use crate::error::{enforce, to_bool_result, ZkError, ZkResult};
+use crate::public_inputs::field_elements_from_strs;
+use crate::verification_hook::report_failure;
 
 #[cfg(test)]
 #[path = "unit_tests/api_tests.rs"]
@@ -26,7 +30,12 @@ pub fn prepare_pvk_bytes(vk_bytes: &[u8]) -> Result<Vec<Vec<u8>>, FastCryptoErro
 /// Verify Groth16 proof using the serialized form of the four components in a prepared verifying key
 /// (see more at [`crate::verifier::PreparedVerifyingKey`]), serialized proof public input, which should
 /// be concatenated serialized field elements of the scalar field of [`crate::conversions::SCALAR_SIZE`]
-/// bytes each, and serialized proof points.
+/// bytes each, and serialized proof points. Returns `Ok(false)` if the proof is invalid but all inputs
+/// are well-formed. Failures are reported to the hook set with
+/// [`crate::verification_hook::set_verification_failure_hook`].
+///
+/// Deserializing the verifying key and the public inputs allocates vectors of their elements. To
+/// avoid this for every proof, deserialize the key once and use `verifier::verify_with_processed_vk`.
 pub fn verify_groth16_in_bytes(
     vk_gamma_abc_g1_bytes: &[u8],
     alpha_g1_beta_g2_bytes: &[u8],
@@ -35,29 +44,111 @@ pub fn verify_groth16_in_bytes(
     proof_public_inputs_as_bytes: &[u8],
     proof_points_as_bytes: &[u8],
 ) -> Result<bool, FastCryptoError> {
+    to_bool_result(verify_groth16_in_bytes_checked(
+        vk_gamma_abc_g1_bytes,
+        alpha_g1_beta_g2_bytes,
+        gamma_g2_neg_pc_bytes,
+        delta_g2_neg_pc_bytes,
+        proof_public_inputs_as_bytes,
+        proof_points_as_bytes,
+    ))
+}
+
+/// Same as [verify_groth16_in_bytes], but an invalid proof is an error and the errors tell apart
+/// why the verification failed, see [crate::error].
+pub fn verify_groth16_in_bytes_checked(
+    vk_gamma_abc_g1_bytes: &[u8],
+    alpha_g1_beta_g2_bytes: &[u8],
+    gamma_g2_neg_pc_bytes: &[u8],
+    delta_g2_neg_pc_bytes: &[u8],
+    proof_public_inputs_as_bytes: &[u8],
+    proof_points_as_bytes: &[u8],
+) -> ZkResult<()> {
+    let result = verify_groth16_in_bytes_unreported(
+        vk_gamma_abc_g1_bytes,
+        alpha_g1_beta_g2_bytes,
+        gamma_g2_neg_pc_bytes,
+        delta_g2_neg_pc_bytes,
+        proof_public_inputs_as_bytes,
+        proof_points_as_bytes,
+    );
+    report_failure(
+        "groth16-bls12381",
+        &[
+            vk_gamma_abc_g1_bytes,
+            alpha_g1_beta_g2_bytes,
+            gamma_g2_neg_pc_bytes,
+            delta_g2_neg_pc_bytes,
+        ],
+        proof_public_inputs_as_bytes.len(),
+        &result,
+    );
+    result
+}
+
+fn verify_groth16_in_bytes_unreported(
+    vk_gamma_abc_g1_bytes: &[u8],
+    alpha_g1_beta_g2_bytes: &[u8],
+    gamma_g2_neg_pc_bytes: &[u8],
+    delta_g2_neg_pc_bytes: &[u8],
+    proof_public_inputs_as_bytes: &[u8],
+    proof_points_as_bytes: &[u8],
+) -> ZkResult<()> {
+    let blst_pvk = PreparedVerifyingKey::deserialize(
+        vk_gamma_abc_g1_bytes,
+        alpha_g1_beta_g2_bytes,
+        gamma_g2_neg_pc_bytes,
+        delta_g2_neg_pc_bytes,
+    )
+    .map_err(|_| ZkError::MalformedVerifyingKey)?;
+    if blst_pvk.vk_gamma_abc_g1.is_empty() {
+        return Err(ZkError::MalformedVerifyingKey);
+    }
+
     if proof_public_inputs_as_bytes.len() % SCALAR_SIZE != 0 {
-        return Err(FastCryptoError::InputLengthWrong(SCALAR_SIZE));
+        return Err(ZkError::NonCanonicalPublicInput);
+    }
+    let number_of_inputs = proof_public_inputs_as_bytes.len() / SCALAR_SIZE;
+    enforce(Limit::PublicInputs, number_of_inputs)?;
+    // The first scalar coefficient is 1 and is not sent.
+    if number_of_inputs + 1 != blst_pvk.vk_gamma_abc_g1.len() {
+        return Err(ZkError::WrongNumberOfPublicInputs {
+            expected: blst_pvk.vk_gamma_abc_g1.len() - 1,
+            actual: number_of_inputs,
+        });
     }
-    let mut x = Vec::new();
+    let mut x = Vec::with_capacity(number_of_inputs);
     for chunk in proof_public_inputs_as_bytes.chunks(SCALAR_SIZE) {
         x.push(
             BlsFr::deserialize_compressed(chunk)
-                .map_err(|_| FastCryptoError::InvalidInput)?
+                .map_err(|_| ZkError::NonCanonicalPublicInput)?
                 .into(),
         );
     }
 
+    enforce(Limit::ProofBytes, proof_points_as_bytes.len())?;
     let proof = Proof::<Bls12_381>::deserialize_compressed(proof_points_as_bytes)
-        .map_err(|_| FastCryptoError::InvalidInput)?
+        .map_err(|_| ZkError::MalformedProof)?
         .into();
 
-    let blst_pvk = PreparedVerifyingKey::deserialize(
-        vk_gamma_abc_g1_bytes,
-        alpha_g1_beta_g2_bytes,
-        gamma_g2_neg_pc_bytes,
-        delta_g2_neg_pc_bytes,
-    )?;
+    match verify_with_processed_vk(&blst_pvk, &x, &proof) {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(ZkError::VerificationFailed),
+        // The number of public inputs has been checked above.
+        Err(_) => Err(ZkError::MalformedVerifyingKey),
+    }
+}
 
-    verify_with_processed_vk(&blst_pvk, &x, &proof)
-        .map_err(|e| FastCryptoError::GeneralError(e.to_string()))
+/// Serialize public inputs given as decimal or `0x`-prefixed hexadecimal strings, as emitted by
+/// snarkjs, to the format expected by [verify_groth16_in_bytes]. Fails if an input is not a valid
+/// scalar (see [`crate::public_inputs::field_element_from_str`]).
+pub fn serialize_public_inputs_from_strs<S: AsRef<str>>(
+    inputs: &[S],
+) -> Result<Vec<u8>, FastCryptoError> {
+    let mut bytes = Vec::with_capacity(inputs.len() * SCALAR_SIZE);
+    for x in field_elements_from_strs::<BlsFr, S>(inputs)? {
+        x.serialize_compressed(&mut bytes)
+            .map_err(|_| FastCryptoError::GeneralOpaqueError)?;
+    }
+    Ok(bytes)
 }
@@ -1,10 +1,15 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
+
+// The limbs of blst and Arkworks field elements are native integers, so their in-memory byte
+// layout depends on the endianness of the host. All conversions below therefore go through
+// explicitly little- or big-endian byte encodings, and never reinterpret limbs as bytes.
+
 use ark_ff::{BigInteger384, Fp384, PrimeField, Zero};
 use ark_serialize::{CanonicalSerialize, CanonicalSerializeWithFlags, Compress, EmptyFlags};
 use blst::{blst_fp, blst_fp12, blst_fp6, blst_fp_from_lendian, blst_p1_affine};
 use blst::{blst_fp2, blst_p1_deserialize};
-use blst::{blst_p1_affine_serialize, blst_uint64_from_fp};
+use blst::{blst_lendian_from_fp, blst_p1_affine_serialize};
 use blst::{blst_p2_affine, blst_p2_affine_serialize, blst_p2_deserialize, BLST_ERROR};
 
 pub use ark_bls12_381::Fr as BlsFr;
@@ -14,8 +19,10 @@ use ark_bls12_381::{Fq6, G1Affine as BlsG1Affine};
 use ark_ec::AffineRepr;
 
 use ark_serialize::CanonicalDeserialize;
-use blst::{blst_fr, blst_fr_from_uint64, blst_uint64_from_fr};
-use byte_slice_cast::AsByteSlice;
+use blst::{
+    blst_fr, blst_fr_from_uint64, blst_lendian_from_scalar, blst_scalar, blst_scalar_from_fr,
+};
+use std::fmt::Debug;
 
 /// Size of scalar elements.
 pub const SCALAR_SIZE: usize = 32;
@@ -52,11 +59,14 @@ pub fn bls_fr_to_blst_fr(fe: &BlsFr) -> blst_fr {
 
 /// Convert a blst scalar field element to an Arkworks BLS12-381 scalar field element.
 pub fn blst_fr_to_bls_fr(fe: &blst_fr) -> BlsFr {
-    let mut out = [0u64; 4];
-    unsafe { blst_uint64_from_fr(out.as_mut_ptr(), fe) };
-    let bytes = out.as_byte_slice();
+    let mut scalar = blst_scalar::default();
+    let mut bytes = [0u8; SCALAR_SIZE];
+    unsafe {
+        blst_scalar_from_fr(&mut scalar, fe);
+        blst_lendian_from_scalar(bytes.as_mut_ptr(), &scalar);
+    }
 
-    BlsFr::from_le_bytes_mod_order(bytes)
+    BlsFr::from_le_bytes_mod_order(&bytes)
 }
 
 // Base Field conversions
@@ -75,10 +85,9 @@ pub fn bls_fq_to_blst_fp(f: &Fq) -> blst_fp {
 
 /// Convert a blst prime field element to an Arkworks BLS12-381 prime field element.
 pub fn blst_fp_to_bls_fq(f: &blst_fp) -> Fq {
-    let mut out = [0u64; 6];
-    unsafe { blst_uint64_from_fp(out.as_mut_ptr(), f) };
-    let bytes = out.as_byte_slice();
-    Fq::deserialize_compressed(bytes).unwrap()
+    let mut bytes = [0u8; G1_UNCOMPRESSED_SIZE / 2];
+    unsafe { blst_lendian_from_fp(bytes.as_mut_ptr(), f) };
+    Fq::deserialize_compressed(&bytes[..]).unwrap()
 }
 
 // QFE conversions
@@ -286,6 +295,17 @@ pub fn blst_g2_affine_to_bls_g2_affine(pt: &blst_p2_affine) -> BlsG2Affine {
     }
 }
 
+/// Assert that converting `value` with `forward` and converting the result back with `backward`
+/// gives `value` again. Used to test conversions between representations, e.g., by passing
+/// [bls_fr_to_blst_fr] and [blst_fr_to_bls_fr] or the same functions in the other order.
+pub fn assert_roundtrip<A: PartialEq + Debug, B>(
+    value: &A,
+    forward: impl Fn(&A) -> B,
+    backward: impl Fn(&B) -> A,
+) {
+    assert_eq!(&backward(&forward(value)), value, "roundtrip failed");
+}
+
 /////////////////////////////////////////////////////////////
 // Zcash point encodings to Arkworks points and back       //
 /////////////////////////////////////////////////////////////
@@ -451,8 +471,8 @@ fn obtain_x_coordinate(bytes: &[u8]) -> Option<Fq> {
 pub(crate) mod tests {
     use super::*;
     use ark_bls12_381::Fr as BlsFr;
-    use ark_ec::AffineRepr;
-    use ark_ff::Field;
+    use ark_ec::{AffineRepr, CurveGroup};
+    use ark_ff::{Field, One};
     use blst::{
         blst_encode_to_g1, blst_encode_to_g2, blst_fp_from_uint64, blst_fr, blst_fr_from_uint64,
         blst_p1, blst_p1_affine_compress, blst_p1_to_affine, blst_p1_uncompress, blst_p2,
@@ -481,6 +501,24 @@ pub(crate) mod tests {
             .no_shrink() // this is arbitrary.
     }
 
+    #[test]
+    fn bls_fr_known_answer() {
+        // The limbs are given least significant first, independently of the endianness of the host.
+        let limbs = [
+            0x0706050403020100u64,
+            0x0f0e0d0c0b0a0908,
+            0x1716151413121110,
+            0x1f1e1d1c1b1a1918,
+        ];
+        let mut blst_variant = blst_fr::default();
+        unsafe { blst_fr_from_uint64(&mut blst_variant, limbs.as_ptr()) };
+        let bytes = (0..32u8).collect::<Vec<_>>();
+        let bls_variant = BlsFr::from_le_bytes_mod_order(&bytes);
+
+        assert_eq!(bls_fr_to_blst_fr(&bls_variant), blst_variant);
+        assert_eq!(blst_fr_to_bls_fr(&blst_variant), bls_variant);
+    }
+
     proptest! {
         #[test]
         fn roundtrip_bls_fr(b in arb_bls_fr()) {
@@ -517,6 +555,26 @@ pub(crate) mod tests {
             .no_shrink() // this is arbitrary.
     }
 
+    #[test]
+    fn bls_fq_known_answer() {
+        let limbs = [
+            0x0706050403020100u64,
+            0x0f0e0d0c0b0a0908,
+            0x1716151413121110,
+            0x1f1e1d1c1b1a1918,
+            0x2726252423222120,
+            0x0f2e2d2c2b2a2928,
+        ];
+        let mut blst_variant = blst_fp::default();
+        unsafe { blst_fp_from_uint64(&mut blst_variant, limbs.as_ptr()) };
+        let mut bytes = (0..48u8).collect::<Vec<_>>();
+        bytes[47] = 0x0f;
+        let bls_variant = Fq::from_le_bytes_mod_order(&bytes);
+
+        assert_eq!(bls_fq_to_blst_fp(&bls_variant), blst_variant);
+        assert_eq!(blst_fp_to_bls_fq(&blst_variant), bls_variant);
+    }
+
     proptest! {
         #[test]
         fn roundtrip_bls_fq(b in arb_bls_fq()) {
@@ -827,4 +885,218 @@ pub(crate) mod tests {
             assert!(bls_g2_affine_from_zcash_bytes(&tmp2).is_some());
         }
     }
+
+    // Roundtrips of special values.
+
+    /// Zero, one, small values, the largest elements, and values with zero limbs.
+    fn special_bls_fr() -> Vec<BlsFr> {
+        let mut values = (0..16u64).map(BlsFr::from).collect::<Vec<_>>();
+        values.extend([
+            -BlsFr::one(),
+            -BlsFr::from(2u64),
+            BlsFr::from(u64::MAX),
+            BlsFr::from(u64::MAX) + BlsFr::one(),
+            BlsFr::from(2u64).pow([128]),
+            BlsFr::from(2u64).pow([192]),
+            BlsFr::from(2u64).pow([192]) - BlsFr::one(),
+        ]);
+        values
+    }
+
+    fn special_bls_fq() -> Vec<Fq> {
+        let mut values = (0..16u64).map(Fq::from).collect::<Vec<_>>();
+        values.extend([
+            -Fq::one(),
+            -Fq::from(2u64),
+            Fq::from(u64::MAX),
+            Fq::from(2u64).pow([256]),
+            Fq::from(2u64).pow([320]),
+            Fq::from(2u64).pow([380]),
+            Fq::from(2u64).pow([380]) - Fq::one(),
+        ]);
+        values
+    }
+
+    fn special_bls_fq2() -> Vec<Fq2> {
+        let components = [Fq::zero(), Fq::one(), Fq::from(2u64), -Fq::one()];
+        components
+            .iter()
+            .flat_map(|c0| components.iter().map(|c1| Fq2::new(*c0, *c1)))
+            .collect()
+    }
+
+    fn special_bls_fq6() -> Vec<Fq6> {
+        special_bls_fq2()
+            .into_iter()
+            .flat_map(|c| {
+                [
+                    Fq6::new(c, Fq2::zero(), Fq2::zero()),
+                    Fq6::new(Fq2::zero(), c, Fq2::zero()),
+                    Fq6::new(Fq2::zero(), Fq2::zero(), c),
+                    Fq6::new(c, c, c),
+                ]
+            })
+            .collect()
+    }
+
+    fn special_bls_fq12() -> Vec<Fq12> {
+        special_bls_fq6()
+            .into_iter()
+            .flat_map(|c| {
+                [
+                    Fq12::new(c, Fq6::zero()),
+                    Fq12::new(Fq6::zero(), c),
+                    Fq12::new(c, c),
+                ]
+            })
+            .collect()
+    }
+
+    /// The identity, the generator, its negation and small multiples.
+    fn special_points<G: AffineRepr>() -> Vec<G> {
+        let mut points = vec![
+            G::zero(),
+            G::generator(),
+            (-G::generator().into_group()).into_affine(),
+        ];
+        points.extend((2..8u64).map(|k| (G::generator() * G::ScalarField::from(k)).into_affine()));
+        points.push((G::generator() * -G::ScalarField::from(2u64)).into_affine());
+        points
+    }
+
+    #[test]
+    fn roundtrip_special_scalars() {
+        for b in special_bls_fr() {
+            assert_roundtrip(&b, bls_fr_to_blst_fr, blst_fr_to_bls_fr);
+            assert_roundtrip(&bls_fr_to_blst_fr(&b), blst_fr_to_bls_fr, bls_fr_to_blst_fr);
+        }
+
+        let mut one = blst_fr::default();
+        unsafe { blst_fr_from_uint64(&mut one, [1u64, 0, 0, 0].as_ptr()) };
+        assert_eq!(blst_fr_to_bls_fr(&blst_fr::default()), BlsFr::zero());
+        assert_eq!(blst_fr_to_bls_fr(&one), BlsFr::one());
+    }
+
+    #[test]
+    fn roundtrip_special_base_field_elements() {
+        for b in special_bls_fq() {
+            assert_roundtrip(&b, bls_fq_to_blst_fp, blst_fp_to_bls_fq);
+            assert_roundtrip(&bls_fq_to_blst_fp(&b), blst_fp_to_bls_fq, bls_fq_to_blst_fp);
+            assert_eq!(bls_fq_from_zcash_bytes(&bls_fq_to_zcash_bytes(&b)), Some(b));
+        }
+
+        let mut one = blst_fp::default();
+        unsafe { blst_fp_from_uint64(&mut one, [1u64, 0, 0, 0, 0, 0].as_ptr()) };
+        assert_eq!(blst_fp_to_bls_fq(&blst_fp::default()), Fq::zero());
+        assert_eq!(blst_fp_to_bls_fq(&one), Fq::one());
+    }
+
+    #[test]
+    fn roundtrip_special_extension_field_elements() {
+        for b in special_bls_fq2() {
+            assert_roundtrip(&b, bls_fq2_to_blst_fp2, blst_fp2_to_bls_fq2);
+            assert_roundtrip(
+                &bls_fq2_to_blst_fp2(&b),
+                blst_fp2_to_bls_fq2,
+                bls_fq2_to_blst_fp2,
+            );
+        }
+        for b in special_bls_fq6() {
+            assert_roundtrip(&b, bls_fq6_to_blst_fp6, blst_fp6_to_bls_fq6);
+            assert_roundtrip(
+                &bls_fq6_to_blst_fp6(&b),
+                blst_fp6_to_bls_fq6,
+                bls_fq6_to_blst_fp6,
+            );
+        }
+        for b in special_bls_fq12() {
+            assert_roundtrip(&b, bls_fq12_to_blst_fp12, blst_fp12_to_bls_fq12);
+            assert_roundtrip(
+                &bls_fq12_to_blst_fp12(&b),
+                blst_fp12_to_bls_fq12,
+                bls_fq12_to_blst_fp12,
+            );
+        }
+    }
+
+    #[test]
+    fn roundtrip_special_g1_points() {
+        for b in special_points::<BlsG1Affine>() {
+            assert_roundtrip(
+                &b,
+                bls_g1_affine_to_blst_g1_affine,
+                blst_g1_affine_to_bls_g1_affine,
+            );
+            assert_roundtrip(
+                &bls_g1_affine_to_blst_g1_affine(&b),
+                blst_g1_affine_to_bls_g1_affine,
+                bls_g1_affine_to_blst_g1_affine,
+            );
+            assert_roundtrip(&b, bls_g1_affine_to_zcash_bytes, |bytes| {
+                bls_g1_affine_from_zcash_bytes(bytes).unwrap()
+            });
+        }
+
+        // The generators and identities agree.
+        let generator = unsafe { *blst::blst_p1_affine_generator() };
+        assert_eq!(
+            blst_g1_affine_to_bls_g1_affine(&generator),
+            BlsG1Affine::generator()
+        );
+        assert_eq!(
+            bls_g1_affine_to_blst_g1_affine(&BlsG1Affine::generator()),
+            generator
+        );
+        assert_eq!(
+            bls_g1_affine_to_blst_g1_affine(&BlsG1Affine::zero()),
+            blst_g1_affine_infinity()
+        );
+        assert_eq!(
+            blst_g1_affine_to_bls_g1_affine(&blst_g1_affine_infinity()),
+            BlsG1Affine::zero()
+        );
+    }
+
+    #[test]
+    fn roundtrip_special_g2_points() {
+        for b in special_points::<BlsG2Affine>() {
+            assert_roundtrip(
+                &b,
+                bls_g2_affine_to_blst_g2_affine,
+                blst_g2_affine_to_bls_g2_affine,
+            );
+            assert_roundtrip(
+                &bls_g2_affine_to_blst_g2_affine(&b),
+                blst_g2_affine_to_bls_g2_affine,
+                bls_g2_affine_to_blst_g2_affine,
+            );
+            assert_roundtrip(&b, bls_g2_affine_to_zcash_bytes, |bytes| {
+                bls_g2_affine_from_zcash_bytes(bytes).unwrap()
+            });
+        }
+
+        let generator = unsafe { *blst::blst_p2_affine_generator() };
+        assert_eq!(
+            blst_g2_affine_to_bls_g2_affine(&generator),
+            BlsG2Affine::generator()
+        );
+        assert_eq!(
+            bls_g2_affine_to_blst_g2_affine(&BlsG2Affine::generator()),
+            generator
+        );
+        assert_eq!(
+            bls_g2_affine_to_blst_g2_affine(&BlsG2Affine::zero()),
+            blst_g2_affine_infinity()
+        );
+        assert_eq!(
+            blst_g2_affine_to_bls_g2_affine(&blst_g2_affine_infinity()),
+            BlsG2Affine::zero()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "roundtrip failed")]
+    fn assert_roundtrip_fails() {
+        assert_roundtrip(&BlsFr::one(), bls_fr_to_blst_fr, |_| BlsFr::zero());
+    }
 }
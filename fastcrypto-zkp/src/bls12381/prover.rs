@@ -0,0 +1,70 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use ark_bls12_381::{Bls12_381, Fr as BlsFr};
+use ark_crypto_primitives::snark::SNARK;
+use ark_groth16::Groth16;
+use ark_relations::r1cs::ConstraintSynthesizer;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use derive_more::From;
+use fastcrypto::error::FastCryptoError;
+use fastcrypto::traits::AllowedRng;
+
+use crate::bls12381::{Proof, VerifyingKey};
+
+#[cfg(test)]
+#[path = "unit_tests/prover_tests.rs"]
+mod prover_tests;
+
+/// A Groth16 proving key in the BLS12-381 construction. Thin wrapper around `ark_groth16::ProvingKey::<ark_bls12_381::Bls12_381>`.
+#[derive(Debug, Clone, PartialEq, Eq, From)]
+pub struct ProvingKey(pub(crate) ark_groth16::ProvingKey<Bls12_381>);
+
+impl ProvingKey {
+    /// Deserialize a proving key in the compressed arkworks format, checking that all points are
+    /// in the prime order subgroup.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        ark_groth16::ProvingKey::<Bls12_381>::deserialize_compressed(bytes)
+            .map(Self)
+            .map_err(|_| FastCryptoError::InvalidInput)
+    }
+
+    /// Serialize this proving key in the compressed arkworks format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.0
+            .serialize_compressed(&mut bytes)
+            .expect("Writing to a vector never fails");
+        bytes
+    }
+
+    /// The verifying key corresponding to this proving key.
+    pub fn verifying_key(&self) -> VerifyingKey {
+        VerifyingKey(self.0.vk.clone())
+    }
+}
+
+/// Create a Groth16 proof for the given circuit, whose assignment must be set. Fails if the
+/// constraints can not be synthesized, e.g. because an assignment is missing. Whether the assignment
+/// satisfies the constraints is not checked, so the proof is only valid if it does.
+pub fn prove<C: ConstraintSynthesizer<BlsFr>, R: AllowedRng>(
+    pk: &ProvingKey,
+    circuit: C,
+    rng: &mut R,
+) -> Result<Proof, FastCryptoError> {
+    Groth16::<Bls12_381>::prove(&pk.0, circuit, rng)
+        .map(Proof)
+        .map_err(|e| FastCryptoError::GeneralError(e.to_string()))
+}
+
+/// Create a Groth16 proof for the given circuit using a proving key serialized as in
+/// [ProvingKey::from_bytes], and return the proof serialized as in [Proof::to_bytes], which is the
+/// format expected by [Proof::from_bytes].
+pub fn prove_in_bytes<C: ConstraintSynthesizer<BlsFr>, R: AllowedRng>(
+    pk_bytes: &[u8],
+    circuit: C,
+    rng: &mut R,
+) -> Result<Vec<u8>, FastCryptoError> {
+    let pk = ProvingKey::from_bytes(pk_bytes)?;
+    Ok(prove(&pk, circuit, rng)?.to_bytes())
+}
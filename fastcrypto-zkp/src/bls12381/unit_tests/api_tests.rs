@@ -1,16 +1,20 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::bls12381::api::{prepare_pvk_bytes, verify_groth16_in_bytes};
+use crate::bls12381::api::{
+    prepare_pvk_bytes, verify_groth16_in_bytes, verify_groth16_in_bytes_checked,
+};
 use crate::bls12381::verifier::{process_vk_special, verify_with_processed_vk};
 use crate::bls12381::FieldElement;
 use crate::dummy_circuits::{DummyCircuit, Fibonacci};
+use crate::error::ZkError;
 use ark_bls12_381::{Bls12_381, Fr};
 use ark_crypto_primitives::snark::SNARK;
 use ark_groth16::Groth16;
 use ark_serialize::CanonicalSerialize;
 use ark_std::rand::thread_rng;
 use ark_std::UniformRand;
+use fastcrypto::error::FastCryptoError;
 use std::ops::Mul;
 
 #[test]
@@ -175,3 +179,97 @@ fn test_verify_groth16_in_bytes_multiple_inputs() {
     )
     .unwrap());
 }
+
+#[test]
+fn test_verify_groth16_in_bytes_checked() {
+    let rng = &mut thread_rng();
+    let c = DummyCircuit::<Fr> {
+        a: Some(<Fr>::rand(rng)),
+        b: Some(<Fr>::rand(rng)),
+        num_variables: 8,
+        num_constraints: 10,
+    };
+    let (pk, vk) = Groth16::<Bls12_381>::circuit_specific_setup(c, rng).unwrap();
+    let proof = Groth16::<Bls12_381>::prove(&pk, c, rng).unwrap();
+    let v = c.a.unwrap().mul(c.b.unwrap());
+    let pvk = process_vk_special(&vk.into()).as_serialized().unwrap();
+
+    let mut inputs = vec![];
+    v.serialize_compressed(&mut inputs).unwrap();
+    let mut proof_bytes = vec![];
+    proof.serialize_compressed(&mut proof_bytes).unwrap();
+    let verify = |vk_gamma_abc_g1: &[u8], inputs: &[u8], proof: &[u8]| {
+        verify_groth16_in_bytes_checked(vk_gamma_abc_g1, &pvk[1], &pvk[2], &pvk[3], inputs, proof)
+    };
+
+    assert_eq!(verify(&pvk[0], &inputs, &proof_bytes), Ok(()));
+
+    // The proof is valid only for its public inputs.
+    let mut other_inputs = vec![];
+    (v + Fr::from(1u64))
+        .serialize_compressed(&mut other_inputs)
+        .unwrap();
+    assert_eq!(
+        verify(&pvk[0], &other_inputs, &proof_bytes),
+        Err(ZkError::VerificationFailed)
+    );
+    assert_eq!(
+        verify_groth16_in_bytes(
+            &pvk[0],
+            &pvk[1],
+            &pvk[2],
+            &pvk[3],
+            &other_inputs,
+            &proof_bytes
+        ),
+        Ok(false)
+    );
+
+    let mut modified = pvk[0].clone();
+    modified.pop();
+    assert_eq!(
+        verify(&modified, &inputs, &proof_bytes),
+        Err(ZkError::MalformedVerifyingKey)
+    );
+    assert_eq!(
+        verify(&[], &inputs, &proof_bytes),
+        Err(ZkError::MalformedVerifyingKey)
+    );
+
+    assert_eq!(
+        verify(&pvk[0], &inputs[1..], &proof_bytes),
+        Err(ZkError::NonCanonicalPublicInput)
+    );
+    assert_eq!(
+        verify(&pvk[0], &[0xff; 32], &proof_bytes),
+        Err(ZkError::NonCanonicalPublicInput)
+    );
+    assert_eq!(
+        verify(
+            &pvk[0],
+            &[inputs.clone(), inputs.clone()].concat(),
+            &proof_bytes
+        ),
+        Err(ZkError::WrongNumberOfPublicInputs {
+            expected: 1,
+            actual: 2
+        })
+    );
+
+    assert_eq!(
+        verify(&pvk[0], &inputs, &proof_bytes[1..]),
+        Err(ZkError::MalformedProof)
+    );
+    // The errors of the unchecked API do not tell these cases apart.
+    assert_eq!(
+        verify_groth16_in_bytes(
+            &pvk[0],
+            &pvk[1],
+            &pvk[2],
+            &pvk[3],
+            &inputs,
+            &proof_bytes[1..]
+        ),
+        Err(FastCryptoError::InvalidInput)
+    );
+}
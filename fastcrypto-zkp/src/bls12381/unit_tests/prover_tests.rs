@@ -0,0 +1,45 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::bls12381::prover::{prove, prove_in_bytes, ProvingKey};
+use crate::bls12381::verifier::{process_vk_special, verify_with_processed_vk};
+use crate::bls12381::{Proof, VerifyingKey};
+use crate::dummy_circuits::DummyCircuit;
+use ark_bls12_381::{Bls12_381, Fr};
+use ark_crypto_primitives::snark::SNARK;
+use ark_groth16::Groth16;
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use ark_std::UniformRand;
+use std::ops::Mul;
+
+#[test]
+fn test_prove_in_bytes() {
+    let rng = &mut StdRng::from_seed([0; 32]);
+    let c = DummyCircuit::<Fr> {
+        a: Some(<Fr>::rand(rng)),
+        b: Some(<Fr>::rand(rng)),
+        num_variables: 4,
+        num_constraints: 10,
+    };
+    let (pk, vk) = Groth16::<Bls12_381>::circuit_specific_setup(c, rng).unwrap();
+    let pk: ProvingKey = pk.into();
+    assert_eq!(pk.verifying_key(), VerifyingKey::from(vk.clone()));
+    let pk_bytes = pk.to_bytes();
+    assert_eq!(pk, ProvingKey::from_bytes(&pk_bytes).unwrap());
+
+    let pvk = process_vk_special(&vk.into());
+    let v = c.a.unwrap().mul(c.b.unwrap());
+    let proof_bytes = prove_in_bytes(&pk_bytes, c, rng).unwrap();
+    let proof = Proof::from_bytes(&proof_bytes).unwrap();
+    assert!(verify_with_processed_vk(&pvk, &[v.into()], &proof).unwrap());
+    assert!(!verify_with_processed_vk(&pvk, &[c.a.unwrap().into()], &proof).unwrap());
+
+    // Proofs are randomized.
+    assert_ne!(proof, prove(&pk, c, rng).unwrap());
+
+    // Invalid proving keys and missing assignments are rejected.
+    assert!(prove_in_bytes(&pk_bytes[1..], c, rng).is_err());
+    assert!(ProvingKey::from_bytes(&[0u8; 10]).is_err());
+    let unassigned = DummyCircuit::<Fr> { a: None, ..c };
+    assert!(prove(&pk, unassigned, rng).is_err());
+}
@@ -28,8 +28,10 @@ use crate::{
     },
     bls12381::verifier::{
         g1_linear_combination, multipairing_with_processed_vk, process_vk_special,
-        verify_with_processed_vk, BLST_FR_ONE,
+        verify_batch_with_processed_vk, verify_proofs_with_shared_inputs, verify_with_processed_vk,
+        BLST_FR_ONE,
     },
+    bls12381::{FieldElement, Proof},
     dummy_circuits::DummyCircuit,
 };
 
@@ -255,3 +257,189 @@ fn test_multipairing_with_processed_vk() {
 
     assert_eq!(bls_fq12_to_blst_fp12(&ark_fe), blst_fe);
 }
+
+#[test]
+fn test_verify_proofs_with_shared_inputs() {
+    let rng = &mut thread_rng();
+    let c = DummyCircuit::<Fr> {
+        a: Some(<Fr>::rand(rng)),
+        b: Some(<Fr>::rand(rng)),
+        num_variables: 16,
+        num_constraints: 1024,
+    };
+
+    let (pk, vk) = Groth16::<Bls12_381>::circuit_specific_setup(c, rng).unwrap();
+    let v = c.a.unwrap().mul(c.b.unwrap());
+    let pvk = process_vk_special(&vk.into());
+
+    // A proof for other inputs is invalid for these inputs.
+    let other = DummyCircuit::<Fr> {
+        a: Some(<Fr>::rand(rng)),
+        ..c
+    };
+    let proofs: Vec<Proof> = vec![
+        Groth16::<Bls12_381>::prove(&pk, c, rng).unwrap().into(),
+        Groth16::<Bls12_381>::prove(&pk, other, rng).unwrap().into(),
+        Groth16::<Bls12_381>::prove(&pk, c, rng).unwrap().into(),
+    ];
+    let results = verify_proofs_with_shared_inputs(&pvk, &[v.into()], &proofs).unwrap();
+    assert_eq!(results, vec![true, false, true]);
+    for (proof, result) in proofs.iter().zip(results) {
+        assert_eq!(
+            verify_with_processed_vk(&pvk, &[v.into()], proof).unwrap(),
+            result
+        );
+    }
+
+    // Wrong number of public inputs.
+    assert!(verify_proofs_with_shared_inputs(&pvk, &[], &proofs).is_err());
+}
+
+#[test]
+fn test_verify_batch_with_processed_vk() {
+    let rng = &mut thread_rng();
+    let c = DummyCircuit::<Fr> {
+        a: Some(<Fr>::rand(rng)),
+        b: Some(<Fr>::rand(rng)),
+        num_variables: 16,
+        num_constraints: 1024,
+    };
+    let (pk, vk) = Groth16::<Bls12_381>::circuit_specific_setup(c, rng).unwrap();
+    let pvk = process_vk_special(&vk.into());
+
+    // Proofs for different public inputs.
+    let circuits = (0..4)
+        .map(|_| DummyCircuit::<Fr> {
+            a: Some(<Fr>::rand(rng)),
+            ..c
+        })
+        .collect::<Vec<_>>();
+    let inputs: Vec<Vec<FieldElement>> = circuits
+        .iter()
+        .map(|c| vec![c.a.unwrap().mul(c.b.unwrap()).into()])
+        .collect();
+    let proofs: Vec<Proof> = circuits
+        .iter()
+        .map(|c| Groth16::<Bls12_381>::prove(&pk, *c, rng).unwrap().into())
+        .collect();
+    let batch = inputs
+        .iter()
+        .zip(&proofs)
+        .map(|(x, proof)| (x.as_slice(), proof))
+        .collect::<Vec<_>>();
+    assert!(verify_batch_with_processed_vk(&pvk, &batch).unwrap());
+    assert!(verify_batch_with_processed_vk(&pvk, &batch[..1]).unwrap());
+
+    // Swapping the inputs of two proofs invalidates the batch.
+    let mut swapped = batch.clone();
+    swapped[0].0 = batch[1].0;
+    swapped[1].0 = batch[0].0;
+    assert!(!verify_batch_with_processed_vk(&pvk, &swapped).unwrap());
+
+    // A single invalid proof invalidates the batch.
+    let mut invalid = batch.clone();
+    invalid[2].1 = &proofs[3];
+    assert!(!verify_batch_with_processed_vk(&pvk, &invalid).unwrap());
+    assert!(!verify_with_processed_vk(&pvk, invalid[2].0, invalid[2].1).unwrap());
+
+    // Empty batches and wrong numbers of public inputs.
+    assert!(verify_batch_with_processed_vk(&pvk, &[]).is_err());
+    let mut wrong_length = batch.clone();
+    wrong_length[1].0 = &[];
+    assert!(verify_batch_with_processed_vk(&pvk, &wrong_length).is_err());
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_parallel_pippenger() {
+    use crate::bls12381::verifier::{parallel_pippenger, PARALLEL_MSM_THRESHOLD};
+    use ark_bls12_381::G1Affine;
+    use ark_ff::Zero;
+    use blst::{blst_scalar, blst_scalar_from_fr};
+
+    let rng = &mut thread_rng();
+    for n in [1, 3, 31, PARALLEL_MSM_THRESHOLD, 300] {
+        let mut points: Vec<G1Affine> = (0..n)
+            .map(|_| G1Projective::rand(rng).into_affine())
+            .collect();
+        let mut frs: Vec<Fr> = (0..n).map(|_| Fr::rand(rng)).collect();
+        // Include a zero scalar, the largest scalar and the point at infinity.
+        frs[0] = Fr::zero();
+        if n > 2 {
+            frs[1] = -Fr::one();
+            points[2] = G1Affine::zero();
+        }
+
+        let scalars: Vec<blst_scalar> = frs
+            .iter()
+            .map(|f| {
+                let mut s = blst_scalar::default();
+                unsafe { blst_scalar_from_fr(&mut s, &bls_fr_to_blst_fr(f)) };
+                s
+            })
+            .collect();
+        let blst_points: Vec<blst_p1_affine> =
+            points.iter().map(bls_g1_affine_to_blst_g1_affine).collect();
+        let result = parallel_pippenger(&blst_points, &scalars);
+        let mut result_affine = blst_p1_affine::default();
+        unsafe { blst_p1_to_affine(&mut result_affine, &result) };
+
+        let expected = points
+            .iter()
+            .zip(frs.iter())
+            .fold(G1Projective::zero(), |acc, (p, s)| acc + p.mul(s));
+        assert_eq!(
+            result_affine,
+            bls_g1_affine_to_blst_g1_affine(&expected.into_affine())
+        );
+    }
+}
+
+#[test]
+fn test_validate() {
+    use crate::bls12381::verifier::process_vk_special_strict;
+    use crate::bls12381::VerifyingKey;
+    use ark_bls12_381::{Fq, G1Affine};
+    use ark_ff::Field;
+
+    let rng = &mut thread_rng();
+    let c = DummyCircuit::<Fr> {
+        a: Some(<Fr>::rand(rng)),
+        b: Some(<Fr>::rand(rng)),
+        num_variables: 4,
+        num_constraints: 10,
+    };
+    let (pk, vk) = Groth16::<Bls12_381>::circuit_specific_setup(c, rng).unwrap();
+    let proof: Proof = Groth16::<Bls12_381>::prove(&pk, c, rng).unwrap().into();
+    assert!(proof.validate().is_ok());
+    assert!(VerifyingKey::from(vk.clone()).validate().is_ok());
+    assert!(process_vk_special_strict(&vk.clone().into()).is_ok());
+
+    // A point on the curve which is not in the prime order subgroup of G1.
+    let mut x = Fq::ONE;
+    let point = loop {
+        if let Some(point) = G1Affine::get_point_from_x_unchecked(x, false) {
+            if !point.is_in_correct_subgroup_assuming_on_curve() {
+                break point;
+            }
+        }
+        x += Fq::ONE;
+    };
+    let mut invalid = proof.clone();
+    invalid.0.c = point;
+    assert!(invalid.validate().is_err());
+
+    // A point which is not on the curve.
+    let mut invalid = proof;
+    invalid.0.a = G1Affine::new_unchecked(invalid.0.a.x, invalid.0.a.y + Fq::ONE);
+    assert!(invalid.validate().is_err());
+
+    let mut invalid_vk = vk.clone();
+    invalid_vk.gamma_abc_g1[0] = point;
+    assert!(VerifyingKey::from(invalid_vk.clone()).validate().is_err());
+    assert!(process_vk_special_strict(&invalid_vk.into()).is_err());
+
+    let mut invalid_vk = vk;
+    invalid_vk.gamma_abc_g1.clear();
+    assert!(process_vk_special_strict(&invalid_vk.into()).is_err());
+}
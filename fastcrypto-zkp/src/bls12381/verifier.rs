@@ -6,10 +6,13 @@ use ark_bls12_381::{Bls12_381, Fq12, Fr as BlsFr, G1Affine, G2Affine};
 
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use blst::{
-    blst_final_exp, blst_fp, blst_fp12, blst_fr, blst_miller_loop, blst_p1, blst_p1_add_or_double,
-    blst_p1_affine, blst_p1_from_affine, blst_p1_mult, blst_p1_to_affine, blst_p1s_mult_pippenger,
-    blst_p1s_mult_pippenger_scratch_sizeof, blst_scalar, blst_scalar_from_fr, limb_t, Pairing,
+    blst_final_exp, blst_fp, blst_fp12, blst_fp12_mul, blst_fp12_one, blst_fp12_sqr, blst_fr,
+    blst_fr_add, blst_fr_from_uint64, blst_fr_mul, blst_miller_loop, blst_p1,
+    blst_p1_add_or_double, blst_p1_affine, blst_p1_from_affine, blst_p1_mult, blst_p1_to_affine,
+    blst_p1s_mult_pippenger, blst_p1s_mult_pippenger_scratch_sizeof, blst_p2_affine, blst_scalar,
+    blst_scalar_from_fr, limb_t, Pairing,
 };
+use fastcrypto::hash::{HashFunction, Sha256};
 use fastcrypto::{error::FastCryptoError, utils::log2_byte};
 
 use crate::bls12381::conversions::{
@@ -17,6 +20,7 @@ use crate::bls12381::conversions::{
     bls_g2_affine_to_blst_g2_affine, blst_fp12_to_bls_fq12, G1_COMPRESSED_SIZE,
 };
 use crate::bls12381::{FieldElement, Proof, VerifyingKey};
+use crate::serialization::{serde_with_to_from_bytes, Decoder, Encoder};
 
 #[cfg(test)]
 #[path = "unit_tests/verifier_tests.rs"]
@@ -41,6 +45,32 @@ pub struct PreparedVerifyingKey {
 }
 
 impl PreparedVerifyingKey {
+    /// Serialize this prepared verifying key as the version byte followed by the compressed
+    /// encodings of `alpha_g1_beta_g2`, `gamma_g2_neg_pc`, `delta_g2_neg_pc` and `vk_gamma_abc_g1`.
+    /// See [crate::serialization].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut encoder = Encoder::new();
+        encoder.write(&blst_fp12_to_bls_fq12(&self.alpha_g1_beta_g2));
+        encoder.write(&self.gamma_g2_neg_pc);
+        encoder.write(&self.delta_g2_neg_pc);
+        self.vk_gamma_abc_g1.iter().for_each(|p| encoder.write(p));
+        encoder.finish()
+    }
+
+    /// Deserialize a prepared verifying key serialized with [PreparedVerifyingKey::to_bytes].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        let mut decoder = Decoder::new(bytes)?;
+        let alpha_g1_beta_g2 = bls_fq12_to_blst_fp12(&decoder.read()?);
+        let gamma_g2_neg_pc = decoder.read()?;
+        let delta_g2_neg_pc = decoder.read()?;
+        Ok(PreparedVerifyingKey {
+            vk_gamma_abc_g1: decoder.read_remaining()?,
+            alpha_g1_beta_g2,
+            gamma_g2_neg_pc,
+            delta_g2_neg_pc,
+        })
+    }
+
     /// Deserialize the prepared verifying key from the serialized fields of vk_gamma_abc_g1, alpha_g1_beta_g2, gamma_g2_neg_pc, delta_g2_neg_pc
     pub fn deserialize(
         vk_gamma_abc_g1_bytes: &[u8],
@@ -111,6 +141,8 @@ impl PreparedVerifyingKey {
     }
 }
 
+serde_with_to_from_bytes!(PreparedVerifyingKey);
+
 /// Takes an input [`ark_groth16::VerifyingKey`] `vk` and returns a `PreparedVerifyingKey`. This is roughly homologous to
 /// [`ark_groth16::PreparedVerifyingKey::process_vk`], but uses a blst representation of the elements.
 ///
@@ -151,11 +183,27 @@ pub fn process_vk_special(vk: &VerifyingKey) -> PreparedVerifyingKey {
     }
 }
 
+/// Like [process_vk_special], but refuses verifying keys which are malformed, i.e., which have
+/// points not on the curve or not in the prime order subgroup or no elements in `gamma_abc_g1`.
+/// See [VerifyingKey::validate].
+pub fn process_vk_special_strict(
+    vk: &VerifyingKey,
+) -> Result<PreparedVerifyingKey, FastCryptoError> {
+    vk.validate()?;
+    Ok(process_vk_special(vk))
+}
+
+/// Domain separation tag used when deriving the randomizers of a batch verification.
+const BATCH_VERIFICATION_DST: &[u8] = b"fastcrypto-zkp-groth16-bls12381-batch-v1";
+
+/// The number of limbs of a base field element, which depends on the limb size of the target.
+const FP_LIMBS: usize = 48 / std::mem::size_of::<limb_t>();
+
 /// This helper constant makes it easier to use compute the linear combination involved in the pairing inputs.
 const G1_IDENTITY: blst_p1 = blst_p1 {
-    x: blst_fp { l: [0; 6] },
-    y: blst_fp { l: [0; 6] },
-    z: blst_fp { l: [0; 6] },
+    x: blst_fp { l: [0; FP_LIMBS] },
+    y: blst_fp { l: [0; FP_LIMBS] },
+    z: blst_fp { l: [0; FP_LIMBS] },
 };
 
 /// Returns a single scalar multiplication of `pt` by `b`.
@@ -233,6 +281,12 @@ fn g1_linear_combination(
             scalars[i] = scalar
         }
 
+        #[cfg(feature = "parallel")]
+        if len >= PARALLEL_MSM_THRESHOLD {
+            *out = parallel_pippenger(&p_affine[..len], &scalars);
+            return;
+        }
+
         let scalars_arg: [*const blst_scalar; 2] = [scalars.as_ptr(), ptr::null()];
         let points_arg: [*const blst_p1_affine; 2] = [p_affine.as_ptr(), ptr::null()];
         unsafe {
@@ -248,7 +302,85 @@ fn g1_linear_combination(
     }
 }
 
+/// The smallest number of points for which [parallel_pippenger] is used instead of blst's
+/// single-threaded multi-scalar multiplication.
+#[cfg(feature = "parallel")]
+pub(crate) const PARALLEL_MSM_THRESHOLD: usize = 64;
+
+/// Computes `\sum p_i * s_i` using Pippenger's bucket method, where the windows of the scalars are
+/// processed in parallel using rayon.
+#[cfg(feature = "parallel")]
+pub(crate) fn parallel_pippenger(p_affine: &[blst_p1_affine], scalars: &[blst_scalar]) -> blst_p1 {
+    use rayon::prelude::*;
+
+    // The same window size as arkworks, roughly ln(n) + 2.
+    let log_n = (usize::BITS - p_affine.len().leading_zeros()) as usize;
+    let c = if p_affine.len() < 32 {
+        3
+    } else {
+        log_n * 69 / 100 + 2
+    };
+
+    let windows: Vec<blst_p1> = (0..256)
+        .step_by(c)
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map(|start| {
+            // Bucket i contains the sum of the points whose window equals i + 1.
+            let mut buckets = vec![G1_IDENTITY; (1 << c) - 1];
+            for (p, s) in p_affine.iter().zip(scalars) {
+                let digit = window(s, start, c);
+                if digit != 0 {
+                    buckets[digit - 1] = add_or_dbl_affine(&buckets[digit - 1], p);
+                }
+            }
+            // Compute sum (i + 1) * buckets[i] using running sums from the largest bucket.
+            let mut running_sum = G1_IDENTITY;
+            let mut sum = G1_IDENTITY;
+            for bucket in buckets.iter().rev() {
+                running_sum = add_or_dbl(&running_sum, bucket);
+                sum = add_or_dbl(&sum, &running_sum);
+            }
+            sum
+        })
+        .collect();
+
+    windows.iter().rev().fold(G1_IDENTITY, |acc, window_sum| {
+        let acc = (0..c).fold(acc, |acc, _| dbl(&acc));
+        add_or_dbl(&acc, window_sum)
+    })
+}
+
+/// The bits `start..start + c` of a scalar.
+#[cfg(feature = "parallel")]
+fn window(s: &blst_scalar, start: usize, c: usize) -> usize {
+    (start..(start + c).min(256)).rev().fold(0, |acc, i| {
+        (acc << 1) | ((s.b[i / 8] >> (i % 8)) & 1) as usize
+    })
+}
+
+/// Facade for the blst_p1_add_or_double_affine function.
+#[cfg(feature = "parallel")]
+fn add_or_dbl_affine(a: &blst_p1, b: &blst_p1_affine) -> blst_p1 {
+    let mut ret = blst_p1::default();
+    unsafe {
+        blst::blst_p1_add_or_double_affine(&mut ret, a, b);
+    }
+    ret
+}
+
+/// Facade for the blst_p1_double function.
+#[cfg(feature = "parallel")]
+fn dbl(a: &blst_p1) -> blst_p1 {
+    let mut ret = blst_p1::default();
+    unsafe {
+        blst::blst_p1_double(&mut ret, a);
+    }
+    ret
+}
+
 /// This represents the multiplicative unit scalar, see fr_one_test
+#[cfg(target_pointer_width = "64")]
 pub(crate) const BLST_FR_ONE: blst_fr = blst_fr {
     l: [
         8589934590,
@@ -258,6 +390,64 @@ pub(crate) const BLST_FR_ONE: blst_fr = blst_fr {
     ],
 };
 
+/// This represents the multiplicative unit scalar with 32-bit limbs, see fr_one_test
+#[cfg(not(target_pointer_width = "64"))]
+pub(crate) const BLST_FR_ONE: blst_fr = blst_fr {
+    l: [
+        4294967294, 1, 215042, 1485092858, 3971764213, 2576109551, 2898593135, 405057881,
+    ],
+};
+
+/// The elements of the verification equation which only depend on the prepared verifying key and
+/// the public inputs, and not on the proof, in blst representation.
+struct PreparedInputs {
+    /// The linear combination of the elements of `pvk.vk_gamma_abc_g1` with the public inputs.
+    inputs_g1: blst_p1_affine,
+    gamma_g2_neg_pc: blst_p2_affine,
+    delta_g2_neg_pc: blst_p2_affine,
+}
+
+/// Computes the linear combination of the public inputs with `pvk.vk_gamma_abc_g1` and converts the
+/// remaining elements of the verifying key used in the multi-pairing to blst representation.
+fn prepare_inputs<'a>(
+    pvk: &PreparedVerifyingKey,
+    x: impl IntoIterator<Item = &'a BlsFr>,
+) -> PreparedInputs {
+    // Linear combination: note that the arkworks interface assumes the 1st scalar is an implicit 1
+    let one = BLST_FR_ONE;
+    let ss = iter::once(one).chain(x.into_iter().map(bls_fr_to_blst_fr));
+    let mut out = blst_p1::default();
+    if pvk.vk_gamma_abc_g1.len() < 8 {
+        // Same as the direct approach in g1_linear_combination, but without collecting the points
+        // and scalars on the heap.
+        out = G1_IDENTITY;
+        for (pt, s) in pvk.vk_gamma_abc_g1.iter().zip(ss) {
+            let mut p = blst_p1::default();
+            unsafe { blst_p1_from_affine(&mut p, &bls_g1_affine_to_blst_g1_affine(pt)) };
+            out = add_or_dbl(&out, &mul(&p, &s));
+        }
+    } else {
+        // The multi-scalar multiplication of blst needs the points and scalars in contiguous
+        // arrays and a scratch buffer, so this makes a fixed number of allocations whose sizes are
+        // linear in the number of public inputs.
+        let pts: Vec<blst_p1_affine> = pvk
+            .vk_gamma_abc_g1
+            .iter()
+            .map(bls_g1_affine_to_blst_g1_affine)
+            .collect();
+        let ss: Vec<blst_fr> = ss.collect();
+        g1_linear_combination(&mut out, &pts, &ss[..], ss.len());
+    }
+
+    let mut inputs_g1 = blst_p1_affine::default();
+    unsafe { blst_p1_to_affine(&mut inputs_g1, &out) };
+    PreparedInputs {
+        inputs_g1,
+        gamma_g2_neg_pc: bls_g2_affine_to_blst_g2_affine(&pvk.gamma_g2_neg_pc),
+        delta_g2_neg_pc: bls_g2_affine_to_blst_g2_affine(&pvk.delta_g2_neg_pc),
+    }
+}
+
 /// Returns the result of the multi-pairing involved in the verification equation. This will then be compared to the pre-computed term
 /// pvk.alpha_g1_beta_g2 to check the validity of the proof.
 ///
@@ -270,45 +460,40 @@ pub(crate) const BLST_FR_ONE: blst_fr = blst_fr {
 ///
 /// Eventually, we will compare this value to  e(g * alpha, h * beta)
 ///
-fn multipairing_with_processed_vk(
+pub(crate) fn multipairing_with_processed_vk<'a>(
     pvk: &PreparedVerifyingKey,
-    x: &[BlsFr],
+    x: impl IntoIterator<Item = &'a BlsFr>,
     proof: &ark_groth16::Proof<Bls12_381>,
 ) -> blst_fp12 {
-    // Linear combination: note that the arkworks interface assumes the 1st scalar is an implicit 1
-    let pts: Vec<blst_p1_affine> = pvk
-        .vk_gamma_abc_g1
-        .iter()
-        .map(bls_g1_affine_to_blst_g1_affine)
-        .collect();
-    let one = BLST_FR_ONE;
-    let ss: Vec<blst_fr> = iter::once(one)
-        .chain(x.iter().map(bls_fr_to_blst_fr))
-        .collect();
-    let mut out = blst_p1::default();
-    g1_linear_combination(&mut out, &pts, &ss[..], ss.len());
+    multipairing_with_prepared_inputs(&prepare_inputs(pvk, x), proof)
+}
 
+/// Same as [`multipairing_with_processed_vk`], but with the parts of the equation which do not
+/// depend on the proof already computed.
+fn multipairing_with_prepared_inputs(
+    prepared_inputs: &PreparedInputs,
+    proof: &ark_groth16::Proof<Bls12_381>,
+) -> blst_fp12 {
     let blst_proof_a = bls_g1_affine_to_blst_g1_affine(&proof.a);
     let blst_proof_b = bls_g2_affine_to_blst_g2_affine(&proof.b);
-
-    let mut blst_proof_1_g1 = blst_p1_affine::default();
-    unsafe { blst_p1_to_affine(&mut blst_proof_1_g1, &out) };
-    let blst_proof_1_g2 = bls_g2_affine_to_blst_g2_affine(&pvk.gamma_g2_neg_pc);
-
-    let blst_proof_2_g1 = bls_g1_affine_to_blst_g1_affine(&proof.c);
-    let blst_proof_2_g2 = bls_g2_affine_to_blst_g2_affine(&pvk.delta_g2_neg_pc);
+    let blst_proof_c = bls_g1_affine_to_blst_g1_affine(&proof.c);
 
     let dst = [0u8; 3];
     let mut pairing_blst = Pairing::new(false, &dst);
     pairing_blst.raw_aggregate(&blst_proof_b, &blst_proof_a);
-    pairing_blst.raw_aggregate(&blst_proof_1_g2, &blst_proof_1_g1);
-    pairing_blst.raw_aggregate(&blst_proof_2_g2, &blst_proof_2_g1);
+    pairing_blst.raw_aggregate(&prepared_inputs.gamma_g2_neg_pc, &prepared_inputs.inputs_g1);
+    pairing_blst.raw_aggregate(&prepared_inputs.delta_g2_neg_pc, &blst_proof_c);
     pairing_blst.as_fp12().final_exp()
 }
 
 /// Returns the validity of the Groth16 proof passed as argument. The format of the inputs is assumed to be in arkworks format.
 /// See [`multipairing_with_processed_vk`] for the actual pairing computation details.
 ///
+/// The only heap allocation for fewer than seven public inputs is the pairing context of blst.
+/// With seven or more public inputs, the multi-scalar multiplication of the inputs also allocates
+/// buffers for the points and the scalars. The number of allocations does not depend on the
+/// number of public inputs otherwise.
+///
 /// ## Example
 /// ```
 /// use fastcrypto_zkp::{dummy_circuits::Fibonacci, bls12381::verifier::{ process_vk_special, verify_with_processed_vk }};
@@ -351,8 +536,224 @@ pub fn verify_with_processed_vk(
     if (x.len() + 1) != pvk.vk_gamma_abc_g1.len() {
         return Err(FastCryptoError::InvalidInput);
     }
-    let x: Vec<BlsFr> = x.iter().map(|x| x.0).collect();
-
-    let res = multipairing_with_processed_vk(pvk, &x, &proof.0);
+    let res = multipairing_with_processed_vk(pvk, x.iter().map(|x| &x.0), &proof.0);
     Ok(res == pvk.alpha_g1_beta_g2)
 }
+
+/// Verify several Groth16 proofs for the same public inputs, e.g. from redundant provers. The
+/// linear combination of the public inputs with the verifying key is computed once and shared by
+/// all proofs, so this is faster than calling [`verify_with_processed_vk`] for each proof. Returns
+/// whether each of the proofs is valid.
+///
+/// ## Example
+/// ```
+/// use fastcrypto_zkp::{dummy_circuits::Fibonacci, bls12381::verifier::{ process_vk_special, verify_proofs_with_shared_inputs }};
+/// use ark_bls12_381::{Bls12_381, Fr};
+/// use ark_ff::One;
+/// use ark_groth16::Groth16;
+/// use ark_std::rand::thread_rng;
+/// use fastcrypto_zkp::bls12381::{FieldElement, Proof};
+///
+/// let mut rng = thread_rng();
+/// let params = {
+///     let circuit = Fibonacci::<Fr>::new(42, Fr::one(), Fr::one());
+///     Groth16::<Bls12_381>::generate_random_parameters_with_reduction(circuit, &mut rng).unwrap()
+/// };
+/// let proofs = (0..3)
+///     .map(|_| {
+///         let circuit = Fibonacci::<Fr>::new(42, Fr::one(), Fr::one());
+///         Groth16::<Bls12_381>::create_random_proof_with_reduction(circuit, &params, &mut rng)
+///             .unwrap()
+///             .into()
+///     })
+///     .collect::<Vec<Proof>>();
+///
+/// let pvk = process_vk_special(&params.vk.into());
+/// let inputs: Vec<FieldElement> = [Fr::one().into(); 2].to_vec();
+/// let results = verify_proofs_with_shared_inputs(&pvk, &inputs, &proofs).unwrap();
+/// assert_eq!(results, vec![true; 3]);
+/// ```
+pub fn verify_proofs_with_shared_inputs(
+    pvk: &PreparedVerifyingKey,
+    x: &[FieldElement],
+    proofs: &[Proof],
+) -> Result<Vec<bool>, FastCryptoError> {
+    // Note the "+1" : this API implies the first scalar coefficient is 1 and not sent
+    if (x.len() + 1) != pvk.vk_gamma_abc_g1.len() {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    let prepared_inputs = prepare_inputs(pvk, x.iter().map(|x| &x.0));
+    Ok(proofs
+        .iter()
+        .map(|proof| {
+            multipairing_with_prepared_inputs(&prepared_inputs, &proof.0) == pvk.alpha_g1_beta_g2
+        })
+        .collect())
+}
+
+/// Verify a batch of Groth16 proofs, each with its own public inputs, against the same verifying
+/// key with a single multi-pairing. The verification equations are combined with random
+/// coefficients, so the result is true if and only if all proofs are valid, except with negligible
+/// probability. The 128-bit coefficients are derived by hashing the verifying key and the batch,
+/// so a prover cannot choose proofs which cancel out. If the batch is invalid, the invalid proofs
+/// can be identified with [`verify_with_processed_vk`].
+///
+/// ## Example
+/// ```
+/// use fastcrypto_zkp::{dummy_circuits::Fibonacci, bls12381::verifier::{ process_vk_special, verify_batch_with_processed_vk }};
+/// use ark_bls12_381::{Bls12_381, Fr};
+/// use ark_ff::One;
+/// use ark_groth16::Groth16;
+/// use ark_std::rand::thread_rng;
+/// use fastcrypto_zkp::bls12381::{FieldElement, Proof};
+///
+/// let mut rng = thread_rng();
+/// let params = {
+///     let circuit = Fibonacci::<Fr>::new(42, Fr::one(), Fr::one());
+///     Groth16::<Bls12_381>::generate_random_parameters_with_reduction(circuit, &mut rng).unwrap()
+/// };
+/// let proofs = (0..3)
+///     .map(|_| {
+///         let circuit = Fibonacci::<Fr>::new(42, Fr::one(), Fr::one());
+///         Groth16::<Bls12_381>::create_random_proof_with_reduction(circuit, &params, &mut rng)
+///             .unwrap()
+///             .into()
+///     })
+///     .collect::<Vec<Proof>>();
+///
+/// let pvk = process_vk_special(&params.vk.into());
+/// let inputs: Vec<FieldElement> = [Fr::one().into(); 2].to_vec();
+/// let batch = proofs.iter().map(|proof| (inputs.as_slice(), proof)).collect::<Vec<_>>();
+/// assert!(verify_batch_with_processed_vk(&pvk, &batch).unwrap());
+/// ```
+pub fn verify_batch_with_processed_vk(
+    pvk: &PreparedVerifyingKey,
+    batch: &[(&[FieldElement], &Proof)],
+) -> Result<bool, FastCryptoError> {
+    // Note the "+1" : this API implies the first scalar coefficient is 1 and not sent
+    if batch.is_empty()
+        || batch
+            .iter()
+            .any(|(x, _)| x.len() + 1 != pvk.vk_gamma_abc_g1.len())
+    {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    let randomizers = batch_randomizers(pvk, batch)?;
+
+    // With randomizers r_i, we check that
+    //   prod e(r_i * A_i, B_i) * e(sum r_i * L_i, -gamma) * e(sum r_i * C_i, -delta)
+    //     = e(alpha, beta)^(sum r_i)
+    // where L_i is the linear combination of vk_gamma_abc_g1 with the inputs of the i-th proof.
+    // The sum of the L_i is computed as a single linear combination of vk_gamma_abc_g1.
+    let mut input_scalars = vec![blst_fr::default(); pvk.vk_gamma_abc_g1.len()];
+    for ((x, _), r) in batch.iter().zip(&randomizers) {
+        unsafe { blst_fr_add(&mut input_scalars[0], &input_scalars[0], r) };
+        for (s, x) in input_scalars[1..].iter_mut().zip(x.iter()) {
+            let mut term = blst_fr::default();
+            unsafe {
+                blst_fr_mul(&mut term, r, &bls_fr_to_blst_fr(&x.0));
+                blst_fr_add(s, s, &term);
+            }
+        }
+    }
+    let vk_points: Vec<blst_p1_affine> = pvk
+        .vk_gamma_abc_g1
+        .iter()
+        .map(bls_g1_affine_to_blst_g1_affine)
+        .collect();
+    let mut inputs = blst_p1::default();
+    g1_linear_combination(&mut inputs, &vk_points, &input_scalars, vk_points.len());
+
+    let c_points: Vec<blst_p1_affine> = batch
+        .iter()
+        .map(|(_, proof)| bls_g1_affine_to_blst_g1_affine(&proof.0.c))
+        .collect();
+    let mut c = blst_p1::default();
+    g1_linear_combination(&mut c, &c_points, &randomizers, c_points.len());
+
+    let dst = [0u8; 3];
+    let mut pairing_blst = Pairing::new(false, &dst);
+    for ((_, proof), r) in batch.iter().zip(&randomizers) {
+        let mut a = blst_p1::default();
+        unsafe { blst_p1_from_affine(&mut a, &bls_g1_affine_to_blst_g1_affine(&proof.0.a)) };
+        let a = to_affine(&mul(&a, r));
+        pairing_blst.raw_aggregate(&bls_g2_affine_to_blst_g2_affine(&proof.0.b), &a);
+    }
+    pairing_blst.raw_aggregate(
+        &bls_g2_affine_to_blst_g2_affine(&pvk.gamma_g2_neg_pc),
+        &to_affine(&inputs),
+    );
+    pairing_blst.raw_aggregate(
+        &bls_g2_affine_to_blst_g2_affine(&pvk.delta_g2_neg_pc),
+        &to_affine(&c),
+    );
+    Ok(pairing_blst.as_fp12().final_exp() == fp12_pow(&pvk.alpha_g1_beta_g2, &input_scalars[0]))
+}
+
+/// Derive a 128-bit randomizer for each proof of a batch from a hash of the verifying key and the
+/// batch.
+fn batch_randomizers(
+    pvk: &PreparedVerifyingKey,
+    batch: &[(&[FieldElement], &Proof)],
+) -> Result<Vec<blst_fr>, FastCryptoError> {
+    let mut hash = Sha256::new();
+    hash.update(BATCH_VERIFICATION_DST);
+    for component in pvk.as_serialized()? {
+        hash.update((component.len() as u64).to_le_bytes());
+        hash.update(component);
+    }
+    // Proofs and the public inputs have a fixed size, so their concatenation is unambiguous.
+    for (x, proof) in batch {
+        let mut bytes = Vec::new();
+        proof
+            .0
+            .serialize_compressed(&mut bytes)
+            .map_err(|_| FastCryptoError::InvalidInput)?;
+        for x in x.iter() {
+            x.0.serialize_compressed(&mut bytes)
+                .map_err(|_| FastCryptoError::InvalidInput)?;
+        }
+        hash.update(bytes);
+    }
+    let seed = hash.finalize().digest;
+
+    Ok((0..batch.len() as u64)
+        .map(|i| {
+            let mut hash = Sha256::new();
+            hash.update(seed);
+            hash.update(i.to_le_bytes());
+            let digest = hash.finalize().digest;
+            let limbs = [
+                u64::from_le_bytes(digest[0..8].try_into().expect("Length is 8")),
+                u64::from_le_bytes(digest[8..16].try_into().expect("Length is 8")),
+                0,
+                0,
+            ];
+            let mut r = blst_fr::default();
+            unsafe { blst_fr_from_uint64(&mut r, limbs.as_ptr()) };
+            r
+        })
+        .collect())
+}
+
+fn to_affine(p: &blst_p1) -> blst_p1_affine {
+    let mut affine = blst_p1_affine::default();
+    unsafe { blst_p1_to_affine(&mut affine, p) };
+    affine
+}
+
+/// Compute `base^exponent` in the multiplicative group of Fp12.
+fn fp12_pow(base: &blst_fp12, exponent: &blst_fr) -> blst_fp12 {
+    let mut scalar = blst_scalar::default();
+    unsafe { blst_scalar_from_fr(&mut scalar, exponent) };
+    let mut result = unsafe { *blst_fp12_one() };
+    for byte in scalar.b.iter().rev() {
+        for bit in (0..8).rev() {
+            unsafe { blst_fp12_sqr(&mut result, &result) };
+            if (byte >> bit) & 1 == 1 {
+                unsafe { blst_fp12_mul(&mut result, &result, base) };
+            }
+        }
+    }
+    result
+}
@@ -2,10 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::bn254::verifier::{process_vk_special, verify_with_processed_vk, PreparedVerifyingKey};
+use crate::error::{enforce, to_bool_result, ZkError, ZkResult};
+use crate::public_inputs::field_elements_from_strs;
+use crate::verification_hook::report_failure;
 pub use ark_bn254::{Bn254, Fr as Bn254Fr};
 use ark_groth16::Proof as ArkProof;
-use ark_serialize::CanonicalDeserialize;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use fastcrypto::error::FastCryptoError;
+use fastcrypto::limits::Limit;
 
 #[cfg(test)]
 #[path = "unit_tests/api_tests.rs"]
@@ -24,7 +28,11 @@ pub fn prepare_pvk_bytes(vk_bytes: &[u8]) -> Result<Vec<Vec<u8>>, FastCryptoErro
 
 /// Verify Groth16 proof using the serialized form of the prepared verifying key (see more at
 /// [`crate::bn254::verifier::PreparedVerifyingKey`]), serialized proof public input and serialized
-/// proof points.
+/// proof points. Returns `Ok(false)` if the proof is invalid but all inputs are well-formed. Failures
+/// are reported to the hook set with [`crate::verification_hook::set_verification_failure_hook`].
+///
+/// Deserializing the verifying key and the public inputs allocates vectors of their elements. To
+/// avoid this for every proof, deserialize the key once and use `verifier::verify_with_processed_vk`.
 pub fn verify_groth16_in_bytes(
     vk_gamma_abc_g1_bytes: &[u8],
     alpha_g1_beta_g2_bytes: &[u8],
@@ -33,29 +41,111 @@ pub fn verify_groth16_in_bytes(
     proof_public_inputs_as_bytes: &[u8],
     proof_points_as_bytes: &[u8],
 ) -> Result<bool, FastCryptoError> {
-    // Deserialize public inputs
+    to_bool_result(verify_groth16_in_bytes_checked(
+        vk_gamma_abc_g1_bytes,
+        alpha_g1_beta_g2_bytes,
+        gamma_g2_neg_pc_bytes,
+        delta_g2_neg_pc_bytes,
+        proof_public_inputs_as_bytes,
+        proof_points_as_bytes,
+    ))
+}
+
+/// Same as [verify_groth16_in_bytes], but an invalid proof is an error and the errors tell apart
+/// why the verification failed, see [crate::error].
+pub fn verify_groth16_in_bytes_checked(
+    vk_gamma_abc_g1_bytes: &[u8],
+    alpha_g1_beta_g2_bytes: &[u8],
+    gamma_g2_neg_pc_bytes: &[u8],
+    delta_g2_neg_pc_bytes: &[u8],
+    proof_public_inputs_as_bytes: &[u8],
+    proof_points_as_bytes: &[u8],
+) -> ZkResult<()> {
+    let result = verify_groth16_in_bytes_unreported(
+        vk_gamma_abc_g1_bytes,
+        alpha_g1_beta_g2_bytes,
+        gamma_g2_neg_pc_bytes,
+        delta_g2_neg_pc_bytes,
+        proof_public_inputs_as_bytes,
+        proof_points_as_bytes,
+    );
+    report_failure(
+        "groth16-bn254",
+        &[
+            vk_gamma_abc_g1_bytes,
+            alpha_g1_beta_g2_bytes,
+            gamma_g2_neg_pc_bytes,
+            delta_g2_neg_pc_bytes,
+        ],
+        proof_public_inputs_as_bytes.len(),
+        &result,
+    );
+    result
+}
+
+fn verify_groth16_in_bytes_unreported(
+    vk_gamma_abc_g1_bytes: &[u8],
+    alpha_g1_beta_g2_bytes: &[u8],
+    gamma_g2_neg_pc_bytes: &[u8],
+    delta_g2_neg_pc_bytes: &[u8],
+    proof_public_inputs_as_bytes: &[u8],
+    proof_points_as_bytes: &[u8],
+) -> ZkResult<()> {
+    let pvk = PreparedVerifyingKey::deserialize(
+        vk_gamma_abc_g1_bytes,
+        alpha_g1_beta_g2_bytes,
+        gamma_g2_neg_pc_bytes,
+        delta_g2_neg_pc_bytes,
+    )
+    .map_err(|_| ZkError::MalformedVerifyingKey)?;
+    if pvk.vk_gamma_abc_g1.is_empty() {
+        return Err(ZkError::MalformedVerifyingKey);
+    }
+
     if proof_public_inputs_as_bytes.len() % SCALAR_SIZE != 0 {
-        return Err(FastCryptoError::InputLengthWrong(SCALAR_SIZE));
+        return Err(ZkError::NonCanonicalPublicInput);
+    }
+    let number_of_inputs = proof_public_inputs_as_bytes.len() / SCALAR_SIZE;
+    enforce(Limit::PublicInputs, number_of_inputs)?;
+    // The first scalar coefficient is 1 and is not sent.
+    if number_of_inputs + 1 != pvk.vk_gamma_abc_g1.len() {
+        return Err(ZkError::WrongNumberOfPublicInputs {
+            expected: pvk.vk_gamma_abc_g1.len() - 1,
+            actual: number_of_inputs,
+        });
     }
-    let mut x = Vec::new();
+    let mut x = Vec::with_capacity(number_of_inputs);
     for chunk in proof_public_inputs_as_bytes.chunks(SCALAR_SIZE) {
         x.push(
             Bn254Fr::deserialize_compressed(chunk)
-                .map_err(|_| FastCryptoError::InvalidInput)?
+                .map_err(|_| ZkError::NonCanonicalPublicInput)?
                 .into(),
         );
     }
 
-    let pvk = PreparedVerifyingKey::deserialize(
-        vk_gamma_abc_g1_bytes,
-        alpha_g1_beta_g2_bytes,
-        gamma_g2_neg_pc_bytes,
-        delta_g2_neg_pc_bytes,
-    )?;
-
+    enforce(Limit::ProofBytes, proof_points_as_bytes.len())?;
     let proof = ArkProof::deserialize_compressed(proof_points_as_bytes)
-        .map_err(|_| FastCryptoError::InvalidInput)?
+        .map_err(|_| ZkError::MalformedProof)?
         .into();
 
-    verify_with_processed_vk(&pvk, &x, &proof)
+    match verify_with_processed_vk(&pvk, &x, &proof) {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(ZkError::VerificationFailed),
+        // The number of public inputs has been checked above.
+        Err(_) => Err(ZkError::MalformedVerifyingKey),
+    }
+}
+
+/// Serialize public inputs given as decimal or `0x`-prefixed hexadecimal strings, as emitted by
+/// snarkjs, to the format expected by [verify_groth16_in_bytes]. Fails if an input is not a valid
+/// scalar (see [`crate::public_inputs::field_element_from_str`]).
+pub fn serialize_public_inputs_from_strs<S: AsRef<str>>(
+    inputs: &[S],
+) -> Result<Vec<u8>, FastCryptoError> {
+    let mut bytes = Vec::with_capacity(inputs.len() * SCALAR_SIZE);
+    for x in field_elements_from_strs::<Bn254Fr, S>(inputs)? {
+        x.serialize_compressed(&mut bytes)
+            .map_err(|_| FastCryptoError::GeneralOpaqueError)?;
+    }
+    Ok(bytes)
 }
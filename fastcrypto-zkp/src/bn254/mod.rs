@@ -5,7 +5,14 @@
 
 //! Groth16 verifier over the BN254 elliptic curve construction.
 
+use crate::inspect::{inspect_ark_proof, inspect_ark_verifying_key, Curve, Inspection};
+use crate::public_inputs::{field_element_from_bytes, field_elements_from_bytes, Endianness};
+use crate::serialization::{serde_with_to_from_bytes, verifying_key_digest, Decoder, Encoder};
+use ark_serialize::Valid;
 use derive_more::From;
+use fastcrypto::error::FastCryptoError;
+use fastcrypto::limits::{enforce, Limit};
+use std::fmt::{Display, Formatter};
 
 /// API that takes in serialized inputs
 pub mod api;
@@ -17,10 +24,128 @@ pub mod verifier;
 #[derive(Debug, From)]
 pub struct FieldElement(pub(crate) api::Bn254Fr);
 
+impl FieldElement {
+    /// Decode a field element from its 32 byte encoding in the given byte order. Fails if the value
+    /// is not smaller than the modulus.
+    pub fn from_bytes(bytes: &[u8], endianness: Endianness) -> Result<Self, FastCryptoError> {
+        field_element_from_bytes(bytes, endianness).map(Self)
+    }
+
+    /// Decode the public inputs of a proof from the concatenation of their 32 byte encodings in the
+    /// given byte order. See [crate::public_inputs::field_elements_from_bytes].
+    pub fn vec_from_bytes(
+        bytes: &[u8],
+        endianness: Endianness,
+    ) -> Result<Vec<Self>, FastCryptoError> {
+        Ok(field_elements_from_bytes(bytes, endianness)?
+            .into_iter()
+            .map(Self)
+            .collect())
+    }
+}
+
 /// A Groth16 proof in the BN254 construction. Thin wrapper around `ark_groth16::Proof::<ark_bn254::Bn254>`.
-#[derive(Debug, From)]
+#[derive(Debug, Clone, PartialEq, Eq, From)]
 pub struct Proof(pub(crate) ark_groth16::Proof<ark_bn254::Bn254>);
 
 /// A Groth16 verifying key in the BN254 construction. Thin wrapper around `ark_groth16::VerifyingKey::<ark_bn254::Bn254>`.
-#[derive(Debug, From)]
+#[derive(Debug, Clone, PartialEq, Eq, From)]
 pub struct VerifyingKey(pub(crate) ark_groth16::VerifyingKey<ark_bn254::Bn254>);
+
+impl Proof {
+    /// Serialize this proof as the version byte followed by the compressed points `a`, `b` and
+    /// `c`. See [crate::serialization].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut encoder = Encoder::new();
+        encoder.write(&self.0);
+        encoder.finish()
+    }
+
+    /// Deserialize a proof serialized with [Proof::to_bytes]. Fails if the encoding is longer than
+    /// allowed by [Limit::ProofBytes].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        enforce(Limit::ProofBytes, bytes.len())?;
+        let mut decoder = Decoder::new(bytes)?;
+        let proof = decoder.read()?;
+        decoder.finish()?;
+        Ok(Self(proof))
+    }
+
+    /// Report the validity of the points and the digest of this proof.
+    pub fn inspect(&self) -> Inspection {
+        inspect_ark_proof(Curve::Bn254, &self.0)
+    }
+
+    /// Check that all points of this proof are on the curve and in the prime order subgroup.
+    /// Proofs deserialized with [Proof::from_bytes] are always valid, but proofs converted from
+    /// arkworks types need not be.
+    pub fn validate(&self) -> Result<(), FastCryptoError> {
+        self.0.check().map_err(|_| FastCryptoError::InvalidInput)
+    }
+}
+
+impl Display for Proof {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.inspect())
+    }
+}
+
+impl VerifyingKey {
+    /// Serialize this verifying key as the version byte followed by the compressed points
+    /// `alpha_g1`, `beta_g2`, `gamma_g2`, `delta_g2` and `gamma_abc_g1`. See
+    /// [crate::serialization].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut encoder = Encoder::new();
+        encoder.write(&self.0.alpha_g1);
+        encoder.write(&self.0.beta_g2);
+        encoder.write(&self.0.gamma_g2);
+        encoder.write(&self.0.delta_g2);
+        self.0.gamma_abc_g1.iter().for_each(|p| encoder.write(p));
+        encoder.finish()
+    }
+
+    /// Deserialize a verifying key serialized with [VerifyingKey::to_bytes].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        let mut decoder = Decoder::new(bytes)?;
+        Ok(Self(ark_groth16::VerifyingKey {
+            alpha_g1: decoder.read()?,
+            beta_g2: decoder.read()?,
+            gamma_g2: decoder.read()?,
+            delta_g2: decoder.read()?,
+            gamma_abc_g1: decoder.read_remaining()?,
+        }))
+    }
+
+    /// A 32 byte digest identifying this verifying key, which is computed from the encoding of
+    /// [VerifyingKey::to_bytes], see [crate::serialization]. It differs from the digest of
+    /// [VerifyingKey::inspect], which hashes the arkworks serialization.
+    pub fn digest(&self) -> [u8; 32] {
+        verifying_key_digest(Curve::Bn254, &self.to_bytes())
+    }
+
+    /// Report the number of public inputs, the validity of the points and the digest of this
+    /// verifying key.
+    pub fn inspect(&self) -> Inspection {
+        inspect_ark_verifying_key(Curve::Bn254, &self.0)
+    }
+
+    /// Check that all points of this verifying key are on the curve and in the prime order
+    /// subgroup, and that `gamma_abc_g1` is not empty. Verifying keys deserialized with
+    /// [VerifyingKey::from_bytes] always have valid points, but verifying keys converted from
+    /// arkworks types need not.
+    pub fn validate(&self) -> Result<(), FastCryptoError> {
+        if self.0.gamma_abc_g1.is_empty() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        self.0.check().map_err(|_| FastCryptoError::InvalidInput)
+    }
+}
+
+impl Display for VerifyingKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.inspect())
+    }
+}
+
+serde_with_to_from_bytes!(Proof);
+serde_with_to_from_bytes!(VerifyingKey);
@@ -1,16 +1,21 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::bn254::api::{prepare_pvk_bytes, verify_groth16_in_bytes};
+use crate::bn254::api::{
+    prepare_pvk_bytes, serialize_public_inputs_from_strs, verify_groth16_in_bytes,
+    verify_groth16_in_bytes_checked,
+};
 use crate::bn254::verifier::process_vk_special;
 use crate::bn254::VerifyingKey;
 use crate::dummy_circuits::{DummyCircuit, Fibonacci};
+use crate::error::ZkError;
 use ark_bn254::{Bn254, Fr};
 use ark_crypto_primitives::snark::SNARK;
 use ark_groth16::Groth16;
 use ark_serialize::CanonicalSerialize;
 use ark_std::rand::thread_rng;
 use ark_std::UniformRand;
+use fastcrypto::error::FastCryptoError;
 use std::ops::Mul;
 
 #[path = "./utils.rs"]
@@ -68,6 +73,25 @@ fn test_verify_groth16_in_bytes_api() {
         &proof_points_bytes
     )
     .is_ok());
+
+    // Public inputs given as a decimal string, as emitted by snarkjs.
+    assert_eq!(
+        serialize_public_inputs_from_strs(&[v.to_string()]).unwrap(),
+        proof_inputs_bytes
+    );
+}
+
+#[test]
+fn test_serialize_public_inputs_from_strs() {
+    let bytes = serialize_public_inputs_from_strs(&["1", "0x02"]).unwrap();
+    let mut expected = vec![];
+    Fr::from(1u64).serialize_compressed(&mut expected).unwrap();
+    Fr::from(2u64).serialize_compressed(&mut expected).unwrap();
+    assert_eq!(bytes, expected);
+    assert!(serialize_public_inputs_from_strs::<&str>(&[])
+        .unwrap()
+        .is_empty());
+    assert!(serialize_public_inputs_from_strs(&["-1"]).is_err());
 }
 
 #[test]
@@ -561,3 +585,99 @@ fn fail_verify_groth16_invalid_elusiv_proof_in_bytes_api() {
     )
     .is_err());
 }
+
+#[test]
+fn test_verify_groth16_in_bytes_checked() {
+    let rng = &mut thread_rng();
+    let c = DummyCircuit::<Fr> {
+        a: Some(<Fr>::rand(rng)),
+        b: Some(<Fr>::rand(rng)),
+        num_variables: 8,
+        num_constraints: 10,
+    };
+    let (pk, vk) = Groth16::<Bn254>::circuit_specific_setup(c, rng).unwrap();
+    let proof = Groth16::<Bn254>::prove(&pk, c, rng).unwrap();
+    let v = c.a.unwrap().mul(c.b.unwrap());
+    let pvk = process_vk_special(&VerifyingKey(vk))
+        .as_serialized()
+        .unwrap();
+
+    let mut inputs = vec![];
+    v.serialize_compressed(&mut inputs).unwrap();
+    let mut proof_bytes = vec![];
+    proof.serialize_compressed(&mut proof_bytes).unwrap();
+    let verify = |vk_gamma_abc_g1: &[u8], inputs: &[u8], proof: &[u8]| {
+        verify_groth16_in_bytes_checked(vk_gamma_abc_g1, &pvk[1], &pvk[2], &pvk[3], inputs, proof)
+    };
+
+    assert_eq!(verify(&pvk[0], &inputs, &proof_bytes), Ok(()));
+
+    // The proof is valid only for its public inputs.
+    let mut other_inputs = vec![];
+    (v + Fr::from(1u64))
+        .serialize_compressed(&mut other_inputs)
+        .unwrap();
+    assert_eq!(
+        verify(&pvk[0], &other_inputs, &proof_bytes),
+        Err(ZkError::VerificationFailed)
+    );
+    assert_eq!(
+        verify_groth16_in_bytes(
+            &pvk[0],
+            &pvk[1],
+            &pvk[2],
+            &pvk[3],
+            &other_inputs,
+            &proof_bytes
+        ),
+        Ok(false)
+    );
+
+    let mut modified = pvk[0].clone();
+    modified.pop();
+    assert_eq!(
+        verify(&modified, &inputs, &proof_bytes),
+        Err(ZkError::MalformedVerifyingKey)
+    );
+    assert_eq!(
+        verify(&[], &inputs, &proof_bytes),
+        Err(ZkError::MalformedVerifyingKey)
+    );
+
+    assert_eq!(
+        verify(&pvk[0], &inputs[1..], &proof_bytes),
+        Err(ZkError::NonCanonicalPublicInput)
+    );
+    assert_eq!(
+        verify(&pvk[0], &[0xff; 32], &proof_bytes),
+        Err(ZkError::NonCanonicalPublicInput)
+    );
+    assert_eq!(
+        verify(
+            &pvk[0],
+            &[inputs.clone(), inputs.clone()].concat(),
+            &proof_bytes
+        ),
+        Err(ZkError::WrongNumberOfPublicInputs {
+            expected: 1,
+            actual: 2
+        })
+    );
+
+    assert_eq!(
+        verify(&pvk[0], &inputs, &proof_bytes[1..]),
+        Err(ZkError::MalformedProof)
+    );
+    // The errors of the unchecked API do not tell these cases apart.
+    assert_eq!(
+        verify_groth16_in_bytes(
+            &pvk[0],
+            &pvk[1],
+            &pvk[2],
+            &pvk[3],
+            &inputs,
+            &proof_bytes[1..]
+        ),
+        Err(FastCryptoError::InvalidInput)
+    );
+}
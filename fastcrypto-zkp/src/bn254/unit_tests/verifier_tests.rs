@@ -7,6 +7,8 @@ use ark_groth16::Groth16;
 use ark_std::rand::thread_rng;
 use std::ops::Mul;
 
+use crate::bn254::verifier::{process_vk_special, verify_proofs_with_shared_inputs};
+use crate::bn254::Proof;
 use crate::dummy_circuits::DummyCircuit;
 
 #[test]
@@ -26,3 +28,88 @@ fn test_verify() {
 
     assert!(Groth16::<Bn254>::verify(&vk, &[v], &proof).unwrap());
 }
+
+#[test]
+fn test_verify_proofs_with_shared_inputs() {
+    let rng = &mut thread_rng();
+    let c = DummyCircuit::<Fr> {
+        a: Some(<Fr>::rand(rng)),
+        b: Some(<Fr>::rand(rng)),
+        num_variables: 16,
+        num_constraints: 1024,
+    };
+
+    let (pk, vk) = Groth16::<Bn254>::circuit_specific_setup(c, rng).unwrap();
+    let v = c.a.unwrap().mul(c.b.unwrap());
+    let pvk = process_vk_special(&vk.into());
+
+    // A proof for other inputs is invalid for these inputs.
+    let other = DummyCircuit::<Fr> {
+        a: Some(<Fr>::rand(rng)),
+        ..c
+    };
+    let proofs: Vec<Proof> = vec![
+        Groth16::<Bn254>::prove(&pk, c, rng).unwrap().into(),
+        Groth16::<Bn254>::prove(&pk, other, rng).unwrap().into(),
+        Groth16::<Bn254>::prove(&pk, c, rng).unwrap().into(),
+    ];
+    assert_eq!(
+        verify_proofs_with_shared_inputs(&pvk, &[v.into()], &proofs).unwrap(),
+        vec![true, false, true]
+    );
+    assert!(verify_proofs_with_shared_inputs(&pvk, &[v.into()], &[])
+        .unwrap()
+        .is_empty());
+
+    // Wrong number of public inputs.
+    assert!(verify_proofs_with_shared_inputs(&pvk, &[v.into(), v.into()], &proofs).is_err());
+}
+
+#[test]
+fn test_validate() {
+    use crate::bn254::verifier::process_vk_special_strict;
+    use crate::bn254::VerifyingKey;
+    use ark_bn254::{Fq, Fq2, G1Affine, G2Affine};
+    use ark_ff::{Field, One};
+
+    let rng = &mut thread_rng();
+    let c = DummyCircuit::<Fr> {
+        a: Some(<Fr>::rand(rng)),
+        b: Some(<Fr>::rand(rng)),
+        num_variables: 4,
+        num_constraints: 10,
+    };
+    let (pk, vk) = Groth16::<Bn254>::circuit_specific_setup(c, rng).unwrap();
+    let proof: Proof = Groth16::<Bn254>::prove(&pk, c, rng).unwrap().into();
+    assert!(proof.validate().is_ok());
+    assert!(VerifyingKey::from(vk.clone()).validate().is_ok());
+    assert!(process_vk_special_strict(&vk.clone().into()).is_ok());
+
+    // A point which is not on the curve.
+    let mut invalid = proof.clone();
+    invalid.0.a = G1Affine::new_unchecked(invalid.0.a.x, invalid.0.a.y + Fq::one());
+    assert!(invalid.validate().is_err());
+
+    // A point on the curve which is not in the prime order subgroup of G2.
+    let mut x = Fq2::ONE;
+    let point = loop {
+        if let Some(point) = G2Affine::get_point_from_x_unchecked(x, false) {
+            if !point.is_in_correct_subgroup_assuming_on_curve() {
+                break point;
+            }
+        }
+        x += Fq2::ONE;
+    };
+    let mut invalid = proof;
+    invalid.0.b = point;
+    assert!(invalid.validate().is_err());
+
+    let mut invalid_vk = vk.clone();
+    invalid_vk.delta_g2 = point;
+    assert!(VerifyingKey::from(invalid_vk.clone()).validate().is_err());
+    assert!(process_vk_special_strict(&invalid_vk.into()).is_err());
+
+    let mut invalid_vk = vk;
+    invalid_vk.gamma_abc_g1.clear();
+    assert!(process_vk_special_strict(&invalid_vk.into()).is_err());
+}
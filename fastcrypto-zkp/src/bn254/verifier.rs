@@ -10,6 +10,7 @@ use ark_groth16::{Groth16, PreparedVerifyingKey as ArkPreparedVerifyingKey};
 
 use crate::bn254::api::{Bn254Fr, SCALAR_SIZE};
 use crate::bn254::{FieldElement, Proof, VerifyingKey};
+use crate::serialization::{serde_with_to_from_bytes, Decoder, Encoder};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use fastcrypto::error::FastCryptoError;
 
@@ -35,6 +36,32 @@ pub struct PreparedVerifyingKey {
 }
 
 impl PreparedVerifyingKey {
+    /// Serialize this prepared verifying key as the version byte followed by the compressed
+    /// encodings of `alpha_g1_beta_g2`, `gamma_g2_neg_pc`, `delta_g2_neg_pc` and `vk_gamma_abc_g1`.
+    /// See [crate::serialization].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut encoder = Encoder::new();
+        encoder.write(&self.alpha_g1_beta_g2);
+        encoder.write(&self.gamma_g2_neg_pc);
+        encoder.write(&self.delta_g2_neg_pc);
+        self.vk_gamma_abc_g1.iter().for_each(|p| encoder.write(p));
+        encoder.finish()
+    }
+
+    /// Deserialize a prepared verifying key serialized with [PreparedVerifyingKey::to_bytes].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        let mut decoder = Decoder::new(bytes)?;
+        let alpha_g1_beta_g2 = decoder.read()?;
+        let gamma_g2_neg_pc = decoder.read()?;
+        let delta_g2_neg_pc = decoder.read()?;
+        Ok(PreparedVerifyingKey {
+            vk_gamma_abc_g1: decoder.read_remaining()?,
+            alpha_g1_beta_g2,
+            gamma_g2_neg_pc,
+            delta_g2_neg_pc,
+        })
+    }
+
     /// Serialize the prepared verifying key to its vectors form.
     pub fn as_serialized(&self) -> Result<Vec<Vec<u8>>, FastCryptoError> {
         let mut res = Vec::new();
@@ -117,6 +144,8 @@ impl PreparedVerifyingKey {
     }
 }
 
+serde_with_to_from_bytes!(PreparedVerifyingKey);
+
 /// Takes an input [`ark_groth16::VerifyingKey`] `vk` and returns a `PreparedVerifyingKey`. This is roughly homologous to
 /// [`ark_groth16::PreparedVerifyingKey::process_vk`].
 ///
@@ -148,9 +177,25 @@ pub fn process_vk_special(vk: &VerifyingKey) -> PreparedVerifyingKey {
     }
 }
 
+/// Like [process_vk_special], but refuses verifying keys which are malformed, i.e., which have
+/// points not on the curve or not in the prime order subgroup or no elements in `gamma_abc_g1`.
+/// See [VerifyingKey::validate].
+pub fn process_vk_special_strict(
+    vk: &VerifyingKey,
+) -> Result<PreparedVerifyingKey, FastCryptoError> {
+    vk.validate()?;
+    Ok(process_vk_special(vk))
+}
+
 /// Verify Groth16 proof using the prepared verifying key (see more at
 /// [`crate::bn254::verifier::PreparedVerifyingKey`]), a vector of public inputs and
 /// the proof.
+///
+/// This uses the verifier of arkworks, which allocates on the heap: the public inputs are copied
+/// to a vector, the verifying key is converted to the prepared key of arkworks, which clones
+/// `vk_gamma_abc_g1` and precomputes the line coefficients of the G2 points, and the Miller loop
+/// collects its pairs and may run on the rayon thread pool. The BLS12-381 verifier in
+/// [`crate::bls12381::verifier`] avoids most of these allocations.
 pub fn verify_with_processed_vk(
     pvk: &PreparedVerifyingKey,
     public_inputs: &[FieldElement],
@@ -160,3 +205,29 @@ pub fn verify_with_processed_vk(
     Groth16::<Bn254>::verify_with_processed_vk(&pvk.as_arkworks_pvk(), &x, &proof.0)
         .map_err(|e| FastCryptoError::GeneralError(e.to_string()))
 }
+
+/// Verify several Groth16 proofs for the same public inputs, e.g. from redundant provers. The
+/// linear combination of the public inputs with the verifying key is computed once and shared by
+/// all proofs, so this is faster than calling [`verify_with_processed_vk`] for each proof. Returns
+/// whether each of the proofs is valid.
+pub fn verify_proofs_with_shared_inputs(
+    pvk: &PreparedVerifyingKey,
+    public_inputs: &[FieldElement],
+    proofs: &[Proof],
+) -> Result<Vec<bool>, FastCryptoError> {
+    let x: Vec<Bn254Fr> = public_inputs.iter().map(|x| x.0).collect();
+    let ark_pvk = pvk.as_arkworks_pvk();
+    let prepared_inputs = Groth16::<Bn254>::prepare_inputs(&ark_pvk, &x)
+        .map_err(|e| FastCryptoError::GeneralError(e.to_string()))?;
+    proofs
+        .iter()
+        .map(|proof| {
+            Groth16::<Bn254>::verify_proof_with_prepared_inputs(
+                &ark_pvk,
+                &proof.0,
+                &prepared_inputs,
+            )
+            .map_err(|e| FastCryptoError::GeneralError(e.to_string()))
+        })
+        .collect()
+}
@@ -0,0 +1,85 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Typed errors of the verifiers of serialized proofs.
+//!
+//! The verifiers returning `Result<bool, FastCryptoError>` do not tell apart a malformed verifying
+//! key from a malformed proof, and report an invalid proof as `Ok(false)`. The `_checked` variants
+//! of the binary APIs, e.g. [crate::bn254::api::verify_groth16_in_bytes_checked], instead return a
+//! [ZkError] for every reason a verification can fail, so that callers can map them to different
+//! abort codes. A [ZkError] converts to a [FastCryptoError] to be used with `?` in functions
+//! returning the latter.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto_zkp::bn254::api::verify_groth16_in_bytes_checked;
+//! # use fastcrypto_zkp::error::ZkError;
+//! let result = verify_groth16_in_bytes_checked(&[0; 31], &[], &[], &[], &[], &[]);
+//! assert_eq!(result, Err(ZkError::MalformedVerifyingKey));
+//! ```
+
+use fastcrypto::error::FastCryptoError;
+use fastcrypto::limits::{self, Limit};
+use thiserror::Error;
+
+pub type ZkResult<T> = Result<T, ZkError>;
+
+/// The reasons a verification of a serialized proof can fail.
+#[derive(Clone, Copy, Debug, Error, Eq, PartialEq, Hash)]
+pub enum ZkError {
+    /// The verifying key is not a valid encoding, e.g. it has a point which is not on the curve.
+    #[error("Malformed verifying key")]
+    MalformedVerifyingKey,
+
+    /// The proof is not a valid encoding, e.g. it has the wrong length or a point which is not on
+    /// the curve.
+    #[error("Malformed proof")]
+    MalformedProof,
+
+    /// A public input is not the canonical encoding of a scalar, i.e., it is not smaller than the
+    /// modulus, or the length of the public inputs is not a multiple of the length of a scalar.
+    #[error("Non-canonical public input")]
+    NonCanonicalPublicInput,
+
+    /// The number of public inputs differs from the number expected by the verifying key.
+    #[error("Expected {expected} public inputs but got {actual}")]
+    WrongNumberOfPublicInputs { expected: usize, actual: usize },
+
+    /// An input exceeds a limit of this process, see [fastcrypto::limits].
+    #[error("Input exceeds the limit on {0}")]
+    LimitExceeded(Limit),
+
+    /// All inputs are well-formed but the proof is invalid.
+    #[error("Invalid proof")]
+    VerificationFailed,
+}
+
+impl From<ZkError> for FastCryptoError {
+    fn from(e: ZkError) -> Self {
+        match e {
+            ZkError::MalformedVerifyingKey
+            | ZkError::MalformedProof
+            | ZkError::NonCanonicalPublicInput
+            | ZkError::WrongNumberOfPublicInputs { .. } => FastCryptoError::InvalidInput,
+            ZkError::LimitExceeded(limit) => {
+                FastCryptoError::InputTooLong(limits::current().maximum(limit))
+            }
+            ZkError::VerificationFailed => FastCryptoError::InvalidProof,
+        }
+    }
+}
+
+/// Enforce a limit of this process, see [fastcrypto::limits::enforce].
+pub(crate) fn enforce(limit: Limit, size: usize) -> ZkResult<()> {
+    limits::enforce(limit, size).map_err(|_| ZkError::LimitExceeded(limit))
+}
+
+/// The result of a `_checked` verifier in the form returned by the other verifiers, where an invalid
+/// proof is `Ok(false)`.
+pub(crate) fn to_bool_result(result: ZkResult<()>) -> Result<bool, FastCryptoError> {
+    match result {
+        Ok(()) => Ok(true),
+        Err(ZkError::VerificationFailed) => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
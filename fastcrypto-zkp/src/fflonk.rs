@@ -0,0 +1,417 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A verifier for [fflonk](https://eprint.iacr.org/2021/1167) proofs as produced by the `fflonk`
+//! protocol of [snarkjs](https://github.com/iden3/snarkjs). fflonk is a variant of PLONK where the
+//! polynomials are combined into three commitments, all of which are opened with a single pairing
+//! check.
+//!
+//! Circuits use the same arithmetic gate and permutation argument as [crate::plonk]. The
+//! preprocessed polynomials are combined into
+//! `C_0(X) = q_L(X^8) + X q_R(X^8) + X^2 q_O(X^8) + X^3 q_M(X^8) + X^4 q_C(X^8) + X^5 σ_1(X^8) + X^6 σ_2(X^8) + X^7 σ_3(X^8)`,
+//! which is committed to in the verifying key, and the prover commits to
+//! `C_1(X) = a(X^4) + X b(X^4) + X^2 c(X^4) + X^3 t_0(X^4)` and
+//! `C_2(X) = z(X^3) + X t_1(X^3) + X^2 t_2(X^3)`, where `t_0`, `t_1` and `t_2` are the quotients of
+//! the gate, the first permutation constraint and the permutation argument by the vanishing
+//! polynomial. Given the evaluations of these polynomials at the challenge `ξ` (and `ξω` for `z`,
+//! `t_1` and `t_2`), `C_0`, `C_1` and `C_2` are opened at the 8th roots of `ξ`, the 4th roots of `ξ`
+//! and the cube roots of `ξ` and `ξω`, using the batched KZG openings of
+//! [Boneh et al.](https://eprint.iacr.org/2020/081).
+//!
+//! Challenges are derived with Keccak-256 exactly as snarkjs does, so verifying keys, proofs and
+//! public inputs can be parsed from the JSON artifacts of snarkjs with
+//! [crate::snarkjs::bn254_fflonk_verifying_key_from_json],
+//! [crate::snarkjs::bn254_fflonk_proof_from_json] and
+//! [crate::snarkjs::bn254_fflonk_public_inputs_from_json]. The `inv` evaluation of snarkjs proofs is
+//! only used by the Solidity verifier to avoid inversions and is ignored.
+//!
+//! Like for PLONK, a verifying key is first processed into a [PreparedVerifyingKey] with
+//! [process_vk_special], after which proofs are verified with [verify_with_processed_vk].
+
+use crate::polynomial::Polynomial;
+use ark_ec::pairing::Pairing;
+use ark_ec::short_weierstrass::{Affine, SWCurveConfig};
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{batch_inversion, BigInteger, FftField, Field, One, PrimeField, Zero};
+use fastcrypto::error::FastCryptoError;
+use fastcrypto::hash::{HashFunction, Keccak256};
+
+#[cfg(test)]
+#[path = "unit_tests/fflonk_tests.rs"]
+mod fflonk_tests;
+
+/// An fflonk verifying key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifyingKey<E: Pairing> {
+    /// The base two logarithm of the size `n` of the domain.
+    pub power: u32,
+    /// The number of public inputs `l`.
+    pub num_public_inputs: usize,
+    /// The generator of the second coset of the domain.
+    pub k1: E::ScalarField,
+    /// The generator of the third coset of the domain.
+    pub k2: E::ScalarField,
+    /// The generator `ω` of the domain, which has order `n`.
+    pub w: E::ScalarField,
+    /// A primitive cube root of unity.
+    pub w3: E::ScalarField,
+    /// A primitive 4th root of unity.
+    pub w4: E::ScalarField,
+    /// A primitive 8th root of unity.
+    pub w8: E::ScalarField,
+    /// A cube root of `ω`.
+    pub wr: E::ScalarField,
+    /// Commitment to the combined preprocessed polynomial `C_0`.
+    pub c0: E::G1Affine,
+    /// The element `x * H` in `E::G2`, where `x` is the secret of the KZG setup.
+    pub x_g2: E::G2Affine,
+}
+
+/// An fflonk proof.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Proof<E: Pairing> {
+    /// Commitment to the combined polynomial `C_1`.
+    pub c1: E::G1Affine,
+    /// Commitment to the combined polynomial `C_2`.
+    pub c2: E::G1Affine,
+    /// The first opening proof.
+    pub w1: E::G1Affine,
+    /// The second opening proof.
+    pub w2: E::G1Affine,
+    /// The evaluation `q_L(ξ)`.
+    pub q_l_eval: E::ScalarField,
+    /// The evaluation `q_R(ξ)`.
+    pub q_r_eval: E::ScalarField,
+    /// The evaluation `q_M(ξ)`.
+    pub q_m_eval: E::ScalarField,
+    /// The evaluation `q_O(ξ)`.
+    pub q_o_eval: E::ScalarField,
+    /// The evaluation `q_C(ξ)`.
+    pub q_c_eval: E::ScalarField,
+    /// The evaluation `σ_1(ξ)`.
+    pub sigma_1_eval: E::ScalarField,
+    /// The evaluation `σ_2(ξ)`.
+    pub sigma_2_eval: E::ScalarField,
+    /// The evaluation `σ_3(ξ)`.
+    pub sigma_3_eval: E::ScalarField,
+    /// The evaluation `a(ξ)`.
+    pub a_eval: E::ScalarField,
+    /// The evaluation `b(ξ)`.
+    pub b_eval: E::ScalarField,
+    /// The evaluation `c(ξ)`.
+    pub c_eval: E::ScalarField,
+    /// The evaluation `z(ξ)`.
+    pub z_eval: E::ScalarField,
+    /// The evaluation `z(ξω)`.
+    pub z_omega_eval: E::ScalarField,
+    /// The evaluation `t_1(ξω)`.
+    pub t1_omega_eval: E::ScalarField,
+    /// The evaluation `t_2(ξω)`.
+    pub t2_omega_eval: E::ScalarField,
+}
+
+/// A pre-processed verifying key with the prepared G2 elements used in the final pairing check.
+#[derive(Clone, Debug)]
+pub struct PreparedVerifyingKey<E: Pairing> {
+    /// The verifying key.
+    pub vk: VerifyingKey<E>,
+    x_g2_prepared: E::G2Prepared,
+    g2_prepared: E::G2Prepared,
+}
+
+/// Takes an fflonk verifying key and returns a [PreparedVerifyingKey]. Fails if the domain is not
+/// supported by the scalar field or if the roots of unity do not have the right orders.
+pub fn process_vk_special<E: Pairing>(
+    vk: &VerifyingKey<E>,
+) -> Result<PreparedVerifyingKey<E>, FastCryptoError> {
+    let one = E::ScalarField::one();
+    if vk.power == 0
+        || vk.power > E::ScalarField::TWO_ADICITY
+        || vk.num_public_inputs as u64 > 1u64 << vk.power
+        || !has_order_power_of_two(&vk.w, vk.power)
+        || !has_order_power_of_two(&vk.w4, 2)
+        || !has_order_power_of_two(&vk.w8, 3)
+        || vk.w3 == one
+        || vk.w3.pow([3]) != one
+        || vk.wr.pow([3]) != vk.w
+    {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    Ok(PreparedVerifyingKey {
+        vk: vk.clone(),
+        x_g2_prepared: vk.x_g2.into(),
+        g2_prepared: E::G2Affine::generator().into(),
+    })
+}
+
+/// Verify an fflonk proof for the given public inputs. Returns `Ok(false)` if the proof is invalid
+/// and fails if the number of public inputs is wrong.
+pub fn verify_with_processed_vk<E, P>(
+    pvk: &PreparedVerifyingKey<E>,
+    public_inputs: &[E::ScalarField],
+    proof: &Proof<E>,
+) -> Result<bool, FastCryptoError>
+where
+    E: Pairing<G1Affine = Affine<P>>,
+    P: SWCurveConfig,
+    P::BaseField: PrimeField,
+{
+    let vk = &pvk.vk;
+    if public_inputs.len() != vk.num_public_inputs {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    let one = E::ScalarField::one();
+
+    let Challenges {
+        beta,
+        gamma,
+        xi_seed,
+        alpha,
+        y,
+    } = Challenges::derive(&vk.c0, public_inputs, proof);
+
+    // The points at which C_0, C_1 and C_2 are opened. With h_0 = ξ_seed^3, h_1 = h_0^2 and
+    // h_2 = h_1 ξ_seed^2 we have h_0^8 = h_1^4 = h_2^3 = ξ = ξ_seed^24, and h_3 = h_2 ω_r satisfies
+    // h_3^3 = ξω.
+    let h0 = xi_seed.square() * xi_seed;
+    let h1 = h0.square();
+    let h2 = h1 * xi_seed.square();
+    let h3 = h2 * vk.wr;
+    let xi = h2.square() * h2;
+    let s0 = coset(h0, vk.w8, 8);
+    let s1 = coset(h1, vk.w4, 4);
+    let s2 = [coset(h2, vk.w3, 3), coset(h3, vk.w3, 3)].concat();
+
+    // Evaluate the vanishing polynomial, the first Lagrange polynomial and the public input
+    // polynomial at ξ, using L_i(ξ) = ω^i (ξ^n - 1) / (n (ξ - ω^i)).
+    let n = E::ScalarField::from(1u64 << vk.power);
+    let z_h = xi.pow([1u64 << vk.power]) - one;
+    let z_h_inverse = match z_h.inverse() {
+        Some(inverse) => inverse,
+        // ξ is in the domain, which only happens with negligible probability.
+        None => return Ok(false),
+    };
+    let elements = std::iter::successors(Some(one), |w| Some(*w * vk.w))
+        .take(public_inputs.len().max(1))
+        .collect::<Vec<_>>();
+    let mut lagrange = elements.iter().map(|w| n * (xi - w)).collect::<Vec<_>>();
+    batch_inversion(&mut lagrange);
+    lagrange
+        .iter_mut()
+        .zip(&elements)
+        .for_each(|(l, w)| *l *= z_h * w);
+    let l_1 = lagrange[0];
+    let pi = -public_inputs
+        .iter()
+        .zip(&lagrange)
+        .map(|(x, l)| *x * l)
+        .sum::<E::ScalarField>();
+
+    // The quotients at ξ, which are not part of the proof.
+    let t0 = (proof.q_l_eval * proof.a_eval
+        + proof.q_r_eval * proof.b_eval
+        + proof.q_m_eval * proof.a_eval * proof.b_eval
+        + proof.q_o_eval * proof.c_eval
+        + proof.q_c_eval
+        + pi)
+        * z_h_inverse;
+    let t1 = (proof.z_eval - one) * l_1 * z_h_inverse;
+    let beta_xi = beta * xi;
+    let t2 = ((proof.a_eval + beta_xi + gamma)
+        * (proof.b_eval + beta_xi * vk.k1 + gamma)
+        * (proof.c_eval + beta_xi * vk.k2 + gamma)
+        * proof.z_eval
+        - (proof.a_eval + beta * proof.sigma_1_eval + gamma)
+            * (proof.b_eval + beta * proof.sigma_2_eval + gamma)
+            * (proof.c_eval + beta * proof.sigma_3_eval + gamma)
+            * proof.z_omega_eval)
+        * z_h_inverse;
+
+    // The evaluations of C_0, C_1 and C_2 at their opening points, and the polynomials r_0, r_1 and
+    // r_2 interpolating them evaluated at y.
+    let c0_parts = [
+        proof.q_l_eval,
+        proof.q_r_eval,
+        proof.q_o_eval,
+        proof.q_m_eval,
+        proof.q_c_eval,
+        proof.sigma_1_eval,
+        proof.sigma_2_eval,
+        proof.sigma_3_eval,
+    ];
+    let c1_parts = [proof.a_eval, proof.b_eval, proof.c_eval, t0];
+    let c2_parts = [proof.z_eval, t1, t2];
+    let c2_omega_parts = [proof.z_omega_eval, proof.t1_omega_eval, proof.t2_omega_eval];
+    let c0_evals = s0.iter().map(|h| combine(&c0_parts, h)).collect::<Vec<_>>();
+    let c1_evals = s1.iter().map(|h| combine(&c1_parts, h)).collect::<Vec<_>>();
+    let c2_evals = s2
+        .iter()
+        .enumerate()
+        .map(|(i, h)| match i < 3 {
+            true => combine(&c2_parts, h),
+            false => combine(&c2_omega_parts, h),
+        })
+        .collect::<Vec<_>>();
+    let (r0, r1, r2) = match (
+        Polynomial::interpolate(&s0, &c0_evals),
+        Polynomial::interpolate(&s1, &c1_evals),
+        Polynomial::interpolate(&s2, &c2_evals),
+    ) {
+        (Ok(r0), Ok(r1), Ok(r2)) => (r0.evaluate(&y), r1.evaluate(&y), r2.evaluate(&y)),
+        // The opening points are not distinct, which only happens if ξ = 0.
+        _ => return Ok(false),
+    };
+
+    // The vanishing polynomials of the opening points at y.
+    let z_s0 = s0.iter().map(|h| y - h).product::<E::ScalarField>();
+    let z_s1 = s1.iter().map(|h| y - h).product::<E::ScalarField>();
+    let z_s2 = s2.iter().map(|h| y - h).product::<E::ScalarField>();
+    let (z_s1_inverse, z_s2_inverse) = match (z_s1.inverse(), z_s2.inverse()) {
+        (Some(z_s1_inverse), Some(z_s2_inverse)) => (z_s1_inverse, z_s2_inverse),
+        // y is an opening point, which only happens with negligible probability.
+        _ => return Ok(false),
+    };
+    let quotient_1 = alpha * z_s0 * z_s1_inverse;
+    let quotient_2 = alpha.square() * z_s0 * z_s2_inverse;
+
+    // [F] - [E] - [J] + y [W_2] where [F] = [C_0] + q_1 [C_1] + q_2 [C_2],
+    // [E] = (r_0 + q_1 r_1 + q_2 r_2) [1] and [J] = Z_S0(y) [W_1].
+    let e = r0 + quotient_1 * r1 + quotient_2 * r2;
+    let bases = [
+        vk.c0,
+        proof.c1,
+        proof.c2,
+        E::G1Affine::generator(),
+        proof.w1,
+        proof.w2,
+    ];
+    let scalars = [one, quotient_1, quotient_2, -e, -z_s0, y];
+    let lhs = E::G1::msm_unchecked(&bases, &scalars);
+
+    // e([F] - [E] - [J] + y [W_2], [1]) = e([W_2], [x]).
+    let result = E::multi_miller_loop(
+        [(-lhs).into_affine(), proof.w2],
+        [pvk.g2_prepared.clone(), pvk.x_g2_prepared.clone()],
+    );
+    Ok(E::final_exponentiation(result)
+        .map(|output| output.is_zero())
+        .unwrap_or(false))
+}
+
+/// The Fiat–Shamir challenges of a proof.
+pub(crate) struct Challenges<F> {
+    pub(crate) beta: F,
+    pub(crate) gamma: F,
+    pub(crate) xi_seed: F,
+    pub(crate) alpha: F,
+    pub(crate) y: F,
+}
+
+impl<F: PrimeField> Challenges<F> {
+    /// Derive the challenges in the order the prover would receive them.
+    pub(crate) fn derive<E, P>(c0: &Affine<P>, public_inputs: &[F], proof: &Proof<E>) -> Self
+    where
+        E: Pairing<ScalarField = F, G1Affine = Affine<P>>,
+        P: SWCurveConfig,
+        P::BaseField: PrimeField,
+    {
+        let mut transcript = Transcript::default();
+        transcript.append_point(c0);
+        public_inputs
+            .iter()
+            .for_each(|x| transcript.append_field(x));
+        transcript.append_point(&proof.c1);
+        let beta = transcript.challenge();
+        let gamma = transcript.challenge();
+        transcript.append_point(&proof.c2);
+        let xi_seed = transcript.challenge();
+        [
+            proof.q_l_eval,
+            proof.q_r_eval,
+            proof.q_m_eval,
+            proof.q_o_eval,
+            proof.q_c_eval,
+            proof.sigma_1_eval,
+            proof.sigma_2_eval,
+            proof.sigma_3_eval,
+            proof.a_eval,
+            proof.b_eval,
+            proof.c_eval,
+            proof.z_eval,
+            proof.z_omega_eval,
+            proof.t1_omega_eval,
+            proof.t2_omega_eval,
+        ]
+        .iter()
+        .for_each(|x| transcript.append_field(x));
+        let alpha = transcript.challenge();
+        transcript.append_point(&proof.w1);
+        let y = transcript.challenge();
+        Self {
+            beta,
+            gamma,
+            xi_seed,
+            alpha,
+            y,
+        }
+    }
+}
+
+/// The Keccak-256 transcript of snarkjs. Field elements are appended in big-endian and points as
+/// their affine coordinates, where the point at infinity has zero coordinates. Each challenge is
+/// the digest of the transcript reduced modulo the order of the field, after which the transcript
+/// restarts with the challenge.
+#[derive(Default)]
+pub(crate) struct Transcript(Vec<u8>);
+
+impl Transcript {
+    pub(crate) fn append_field<F: PrimeField>(&mut self, x: &F) {
+        self.0.extend(x.into_bigint().to_bytes_be());
+    }
+
+    pub(crate) fn append_point<P: SWCurveConfig>(&mut self, p: &Affine<P>)
+    where
+        P::BaseField: PrimeField,
+    {
+        match p.xy() {
+            Some((x, y)) => {
+                self.append_field(x);
+                self.append_field(y);
+            }
+            None => {
+                self.append_field(&P::BaseField::zero());
+                self.append_field(&P::BaseField::zero());
+            }
+        }
+    }
+
+    pub(crate) fn challenge<F: PrimeField>(&mut self) -> F {
+        let challenge = F::from_be_bytes_mod_order(&Keccak256::digest(&self.0).digest);
+        self.0.clear();
+        self.append_field(&challenge);
+        challenge
+    }
+}
+
+/// The `size` points `h, h w, h w^2, ...`.
+pub(crate) fn coset<F: Field>(h: F, w: F, size: usize) -> Vec<F> {
+    std::iter::successors(Some(h), |x| Some(*x * w))
+        .take(size)
+        .collect()
+}
+
+/// The evaluation at `x` of `sum_i x^i p_i(X^k)` where `k` is the number of parts, given the
+/// evaluations `p_i(x^k)`.
+fn combine<F: Field>(parts: &[F], x: &F) -> F {
+    parts.iter().rev().fold(F::zero(), |acc, p| acc * x + p)
+}
+
+/// Whether `x` has order exactly `2^log_order`.
+fn has_order_power_of_two<F: Field>(x: &F, log_order: u32) -> bool {
+    // x^(2^(k-1)) = -1 holds if and only if x has order 2^k.
+    let mut y = *x;
+    (1..log_order).for_each(|_| {
+        y.square_in_place();
+    });
+    log_order > 0 && y == -F::one()
+}
@@ -0,0 +1,297 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Parsers for the binary encodings of Groth16 proofs and verifying keys written by the `WriteTo`
+//! and `WriteRawTo` methods of [gnark](https://github.com/Consensys/gnark).
+//!
+//! A proof is encoded as the points `Ar`, `Bs` and `Krs`, and a verifying key as the points
+//! `G1.Alpha`, `G1.Beta`, `G2.Beta`, `G2.Gamma`, `G1.Delta` and `G2.Delta` followed by `G1.K`,
+//! which is a 4 byte big-endian length followed by the points. Newer versions of gnark append data
+//! about Pedersen commitments, which is accepted as long as the circuit has no commitments.
+//!
+//! Points are encoded as the big-endian x-coordinate, followed by the y-coordinate if the point is
+//! uncompressed, and elements of the quadratic extension field as `c1 || c0`. The most significant
+//! bits of the first byte are flags as in [gnark-crypto](https://github.com/Consensys/gnark-crypto):
+//! For BN254, the top two bits are `00` for uncompressed points, `10` and `11` for compressed
+//! points with the lexicographically smallest and largest y-coordinate, and `01` for the point at
+//! infinity. For BLS12-381, the flags are the top three bits as in the zcash encoding. Every point
+//! must be on the curve and in the prime order subgroup.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto_zkp::gnark::*;
+//! // A proof with all points at infinity.
+//! let mut bytes = [0u8; 128];
+//! bytes[0] = 0x40;
+//! bytes[32] = 0x40;
+//! bytes[96] = 0x40;
+//! assert!(bn254_proof_from_gnark_bytes(&bytes).is_ok());
+//! assert!(bn254_proof_from_gnark_bytes(&bytes[..127]).is_err());
+//! ```
+
+use crate::{bls12381, bn254};
+use ark_ec::short_weierstrass::{Affine, SWCurveConfig};
+use ark_ec::AffineRepr;
+use ark_ff::{PrimeField, QuadExtConfig, QuadExtField, Zero};
+use fastcrypto::error::FastCryptoError;
+use num_bigint::BigUint;
+
+#[cfg(test)]
+#[path = "unit_tests/gnark_tests.rs"]
+mod gnark_tests;
+
+/// Parse a BN254 proof written by gnark.
+pub fn bn254_proof_from_gnark_bytes(bytes: &[u8]) -> Result<bn254::Proof, FastCryptoError> {
+    Reader::new(bytes, Flags::Bn254)
+        .read_proof::<ark_bn254::Bn254, _, _, _>()
+        .map(bn254::Proof)
+}
+
+/// Parse a BN254 verifying key written by gnark.
+pub fn bn254_verifying_key_from_gnark_bytes(
+    bytes: &[u8],
+) -> Result<bn254::VerifyingKey, FastCryptoError> {
+    Reader::new(bytes, Flags::Bn254)
+        .read_verifying_key::<ark_bn254::Bn254, _, _, _>()
+        .map(bn254::VerifyingKey)
+}
+
+/// Parse a BLS12-381 proof written by gnark.
+pub fn bls12381_proof_from_gnark_bytes(bytes: &[u8]) -> Result<bls12381::Proof, FastCryptoError> {
+    Reader::new(bytes, Flags::Bls12381)
+        .read_proof::<ark_bls12_381::Bls12_381, _, _, _>()
+        .map(bls12381::Proof)
+}
+
+/// Parse a BLS12-381 verifying key written by gnark.
+pub fn bls12381_verifying_key_from_gnark_bytes(
+    bytes: &[u8],
+) -> Result<bls12381::VerifyingKey, FastCryptoError> {
+    Reader::new(bytes, Flags::Bls12381)
+        .read_verifying_key::<ark_bls12_381::Bls12_381, _, _, _>()
+        .map(bls12381::VerifyingKey)
+}
+
+/// The flags of a curve in the first byte of an encoded point.
+#[derive(Clone, Copy)]
+enum Flags {
+    Bn254,
+    Bls12381,
+}
+
+/// The encoding of a point given by its flags.
+enum PointEncoding {
+    Uncompressed,
+    UncompressedInfinity,
+    Compressed { largest: bool },
+    CompressedInfinity,
+}
+
+impl Flags {
+    /// The size in bytes of an element of the base field.
+    fn field_size(&self) -> usize {
+        match self {
+            Flags::Bn254 => 32,
+            Flags::Bls12381 => 48,
+        }
+    }
+
+    fn mask(&self) -> u8 {
+        match self {
+            Flags::Bn254 => 0b11 << 6,
+            Flags::Bls12381 => 0b111 << 5,
+        }
+    }
+
+    fn decode(&self, byte: u8) -> Result<PointEncoding, FastCryptoError> {
+        match (self, byte & self.mask()) {
+            (Flags::Bn254, 0x00) => Ok(PointEncoding::Uncompressed),
+            (Flags::Bn254, 0x40) => Ok(PointEncoding::CompressedInfinity),
+            (Flags::Bn254, 0x80) => Ok(PointEncoding::Compressed { largest: false }),
+            (Flags::Bn254, 0xc0) => Ok(PointEncoding::Compressed { largest: true }),
+            (Flags::Bls12381, 0x00) => Ok(PointEncoding::Uncompressed),
+            (Flags::Bls12381, 0x40) => Ok(PointEncoding::UncompressedInfinity),
+            (Flags::Bls12381, 0x80) => Ok(PointEncoding::Compressed { largest: false }),
+            (Flags::Bls12381, 0xa0) => Ok(PointEncoding::Compressed { largest: true }),
+            (Flags::Bls12381, 0xc0) => Ok(PointEncoding::CompressedInfinity),
+            _ => Err(FastCryptoError::InvalidInput),
+        }
+    }
+}
+
+fn fq_from_bytes<F: PrimeField>(bytes: &[u8]) -> Result<F, FastCryptoError> {
+    let value = BigUint::from_bytes_be(bytes);
+    if value >= F::MODULUS.into() {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    Ok(F::from(value))
+}
+
+fn fq2_from_bytes<Q>(bytes: &[u8]) -> Result<QuadExtField<Q>, FastCryptoError>
+where
+    Q: QuadExtConfig,
+    Q::BaseField: PrimeField,
+{
+    let (c1, c0) = bytes.split_at(bytes.len() / 2);
+    Ok(QuadExtField::new(fq_from_bytes(c0)?, fq_from_bytes(c1)?))
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    flags: Flags,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8], flags: Flags) -> Self {
+        Self { bytes, flags }
+    }
+
+    fn take(&mut self, length: usize) -> Result<&'a [u8], FastCryptoError> {
+        if self.bytes.len() < length {
+            return Err(FastCryptoError::InputTooShort(length));
+        }
+        let (head, tail) = self.bytes.split_at(length);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn read_u32(&mut self) -> Result<u32, FastCryptoError> {
+        Ok(u32::from_be_bytes(
+            self.take(4)?.try_into().expect("Length is 4"),
+        ))
+    }
+
+    /// Read a point whose coordinates are encoded using `coordinate_size` bytes each.
+    fn read_point<P: SWCurveConfig>(
+        &mut self,
+        coordinate_size: usize,
+        coordinate: fn(&[u8]) -> Result<P::BaseField, FastCryptoError>,
+    ) -> Result<Affine<P>, FastCryptoError> {
+        let encoding = self.flags.decode(
+            *self
+                .bytes
+                .first()
+                .ok_or(FastCryptoError::InputTooShort(1))?,
+        )?;
+        let size = match encoding {
+            PointEncoding::Uncompressed | PointEncoding::UncompressedInfinity => {
+                2 * coordinate_size
+            }
+            _ => coordinate_size,
+        };
+        let mut data = self.take(size)?.to_vec();
+        data[0] &= !self.flags.mask();
+
+        let point = match encoding {
+            PointEncoding::UncompressedInfinity | PointEncoding::CompressedInfinity => {
+                if data.iter().any(|b| *b != 0) {
+                    return Err(FastCryptoError::InvalidInput);
+                }
+                Affine::identity()
+            }
+            PointEncoding::Uncompressed => {
+                let x = coordinate(&data[..coordinate_size])?;
+                let y = coordinate(&data[coordinate_size..])?;
+                // Older versions of gnark encode the uncompressed point at infinity as zeros.
+                if x.is_zero() && y.is_zero() {
+                    Affine::identity()
+                } else {
+                    Affine::new_unchecked(x, y)
+                }
+            }
+            PointEncoding::Compressed { largest } => {
+                Affine::get_point_from_x_unchecked(coordinate(&data)?, largest)
+                    .ok_or(FastCryptoError::InvalidInput)?
+            }
+        };
+        if !point.is_on_curve() || !point.is_in_correct_subgroup_assuming_on_curve() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(point)
+    }
+
+    fn read_g1<P>(&mut self) -> Result<Affine<P>, FastCryptoError>
+    where
+        P: SWCurveConfig,
+        P::BaseField: PrimeField,
+    {
+        self.read_point(self.flags.field_size(), fq_from_bytes)
+    }
+
+    fn read_g2<P, Q>(&mut self) -> Result<Affine<P>, FastCryptoError>
+    where
+        P: SWCurveConfig<BaseField = QuadExtField<Q>>,
+        Q: QuadExtConfig,
+        Q::BaseField: PrimeField,
+    {
+        self.read_point(2 * self.flags.field_size(), fq2_from_bytes)
+    }
+
+    fn read_proof<E, P1, P2, Q>(mut self) -> Result<ark_groth16::Proof<E>, FastCryptoError>
+    where
+        E: ark_ec::pairing::Pairing<G1Affine = Affine<P1>, G2Affine = Affine<P2>>,
+        P1: SWCurveConfig,
+        P1::BaseField: PrimeField,
+        P2: SWCurveConfig<BaseField = QuadExtField<Q>>,
+        Q: QuadExtConfig,
+        Q::BaseField: PrimeField,
+    {
+        let proof = ark_groth16::Proof {
+            a: self.read_g1()?,
+            b: self.read_g2()?,
+            c: self.read_g1()?,
+        };
+        // Newer versions of gnark append the commitments and a proof of knowledge for them, which
+        // is the point at infinity if there are no commitments.
+        if !self.bytes.is_empty() && (self.read_u32()? != 0 || !self.read_g1::<P1>()?.is_zero()) {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        self.finish()?;
+        Ok(proof)
+    }
+
+    fn read_verifying_key<E, P1, P2, Q>(
+        mut self,
+    ) -> Result<ark_groth16::VerifyingKey<E>, FastCryptoError>
+    where
+        E: ark_ec::pairing::Pairing<G1Affine = Affine<P1>, G2Affine = Affine<P2>>,
+        P1: SWCurveConfig,
+        P1::BaseField: PrimeField,
+        P2: SWCurveConfig<BaseField = QuadExtField<Q>>,
+        Q: QuadExtConfig,
+        Q::BaseField: PrimeField,
+    {
+        let alpha_g1 = self.read_g1()?;
+        let _beta_g1: Affine<P1> = self.read_g1()?;
+        let beta_g2 = self.read_g2()?;
+        let gamma_g2 = self.read_g2()?;
+        let _delta_g1: Affine<P1> = self.read_g1()?;
+        let delta_g2 = self.read_g2()?;
+        let gamma_abc_g1 = (0..self.read_u32()?)
+            .map(|_| self.read_g1())
+            .collect::<Result<Vec<_>, _>>()?;
+        if gamma_abc_g1.is_empty() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        // Newer versions of gnark append the public inputs committed to by each commitment and the
+        // commitment keys, each prefixed by their number.
+        if !self.bytes.is_empty() && (self.read_u32()? != 0 || self.read_u32()? != 0) {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        self.finish()?;
+        Ok(ark_groth16::VerifyingKey {
+            alpha_g1,
+            beta_g2,
+            gamma_g2,
+            delta_g2,
+            gamma_abc_g1,
+        })
+    }
+
+    fn finish(self) -> Result<(), FastCryptoError> {
+        if !self.bytes.is_empty() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(())
+    }
+}
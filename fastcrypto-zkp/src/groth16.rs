@@ -0,0 +1,194 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Groth16 verification which is generic over the pairing engine.
+//!
+//! A [Groth16Verifier] bundles a pairing engine with an implementation of the verification
+//! equation. [Blst] uses the blst-accelerated verifier of [crate::bls12381] and [Arkworks] is a
+//! pure arkworks fallback which works for any [Pairing] engine, e.g. BN254 or BLS12-377. Code which
+//! should support several curves can be written once using the generic functions of this module.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto_zkp::dummy_circuits::Fibonacci;
+//! # use fastcrypto_zkp::groth16::*;
+//! # use ark_bls12_381::{Bls12_381, Fr};
+//! # use ark_ec::pairing::Pairing;
+//! # use ark_ff::One;
+//! # use ark_groth16::{Groth16, Proof, VerifyingKey};
+//! # use ark_std::rand::thread_rng;
+//! fn verify<V: Groth16Verifier>(
+//!     vk: &VerifyingKey<V::Engine>,
+//!     inputs: &[<V::Engine as Pairing>::ScalarField],
+//!     proof: &Proof<V::Engine>,
+//! ) -> bool {
+//!     let pvk = process_vk_special::<V>(vk);
+//!     verify_with_processed_vk::<V>(&pvk, inputs, proof).unwrap()
+//! }
+//!
+//! let mut rng = thread_rng();
+//! let circuit = Fibonacci::<Fr>::new(42, Fr::one(), Fr::one());
+//! let params =
+//!     Groth16::<Bls12_381>::generate_random_parameters_with_reduction(circuit, &mut rng).unwrap();
+//! let proof =
+//!     Groth16::<Bls12_381>::create_random_proof_with_reduction(circuit, &params, &mut rng).unwrap();
+//! let inputs = [Fr::one(); 2];
+//! assert!(verify::<Blst>(&params.vk, &inputs, &proof));
+//! assert!(verify::<Arkworks<Bls12_381>>(&params.vk, &inputs, &proof));
+//! ```
+
+use crate::bls12381;
+use crate::bls12381::verifier::multipairing_with_processed_vk as blst_multipairing;
+use ark_bls12_381::{Bls12_381, Fr as BlsFr};
+use ark_ec::pairing::Pairing;
+use ark_groth16::{Groth16, PreparedVerifyingKey as ArkPreparedVerifyingKey, Proof, VerifyingKey};
+use blst::blst_fp12;
+use fastcrypto::error::FastCryptoError;
+use std::marker::PhantomData;
+
+#[cfg(test)]
+#[path = "unit_tests/groth16_tests.rs"]
+mod groth16_tests;
+
+/// An implementation of Groth16 verification for the pairing engine [Groth16Verifier::Engine].
+pub trait Groth16Verifier {
+    /// The pairing engine of the proofs and verifying keys.
+    type Engine: Pairing;
+
+    /// A verifying key prepared for repeated verifications.
+    type PreparedVerifyingKey;
+
+    /// The representation of elements of the target group used by this implementation.
+    type TargetGroup: PartialEq;
+
+    /// Prepare a verifying key. See [process_vk_special].
+    fn process_vk_special(vk: &VerifyingKey<Self::Engine>) -> Self::PreparedVerifyingKey;
+
+    /// The number of public inputs of proofs for the given verifying key.
+    fn num_public_inputs(pvk: &Self::PreparedVerifyingKey) -> usize;
+
+    /// The pairing `e(alpha * G, beta * H)` which the result of the multi-pairing of a valid proof
+    /// is equal to.
+    fn alpha_g1_beta_g2(pvk: &Self::PreparedVerifyingKey) -> &Self::TargetGroup;
+
+    /// Compute the multi-pairing of the verification equation. The number of public inputs is
+    /// assumed to match the verifying key. See [multipairing_with_processed_vk].
+    fn multipairing_with_processed_vk(
+        pvk: &Self::PreparedVerifyingKey,
+        x: &[<Self::Engine as Pairing>::ScalarField],
+        proof: &Proof<Self::Engine>,
+    ) -> Result<Self::TargetGroup, FastCryptoError>;
+}
+
+/// Prepare a verifying key for the verification of proofs with [verify_with_processed_vk].
+pub fn process_vk_special<V: Groth16Verifier>(
+    vk: &VerifyingKey<V::Engine>,
+) -> V::PreparedVerifyingKey {
+    V::process_vk_special(vk)
+}
+
+/// Compute `e(A, B) + e(g * f, h * - gamma) + e(C, h * - delta)`, where `f` is the linear
+/// combination of the public inputs with the points of the verifying key. This is equal to
+/// `e(g * alpha, h * beta)` if the proof is valid. Fails if the number of public inputs does not
+/// match the verifying key.
+pub fn multipairing_with_processed_vk<V: Groth16Verifier>(
+    pvk: &V::PreparedVerifyingKey,
+    x: &[<V::Engine as Pairing>::ScalarField],
+    proof: &Proof<V::Engine>,
+) -> Result<V::TargetGroup, FastCryptoError> {
+    if x.len() != V::num_public_inputs(pvk) {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    V::multipairing_with_processed_vk(pvk, x, proof)
+}
+
+/// Returns the validity of a Groth16 proof for the given public inputs. Fails if the number of
+/// public inputs does not match the verifying key.
+pub fn verify_with_processed_vk<V: Groth16Verifier>(
+    pvk: &V::PreparedVerifyingKey,
+    x: &[<V::Engine as Pairing>::ScalarField],
+    proof: &Proof<V::Engine>,
+) -> Result<bool, FastCryptoError> {
+    Ok(&multipairing_with_processed_vk::<V>(pvk, x, proof)? == V::alpha_g1_beta_g2(pvk))
+}
+
+/// The blst-accelerated verifier for BLS12-381 of [crate::bls12381::verifier].
+#[derive(Debug)]
+pub struct Blst;
+
+impl Groth16Verifier for Blst {
+    type Engine = Bls12_381;
+    type PreparedVerifyingKey = bls12381::verifier::PreparedVerifyingKey;
+    type TargetGroup = blst_fp12;
+
+    fn process_vk_special(vk: &VerifyingKey<Bls12_381>) -> Self::PreparedVerifyingKey {
+        bls12381::verifier::process_vk_special(&bls12381::VerifyingKey(vk.clone()))
+    }
+
+    fn num_public_inputs(pvk: &Self::PreparedVerifyingKey) -> usize {
+        pvk.vk_gamma_abc_g1.len().saturating_sub(1)
+    }
+
+    fn alpha_g1_beta_g2(pvk: &Self::PreparedVerifyingKey) -> &blst_fp12 {
+        &pvk.alpha_g1_beta_g2
+    }
+
+    fn multipairing_with_processed_vk(
+        pvk: &Self::PreparedVerifyingKey,
+        x: &[BlsFr],
+        proof: &Proof<Bls12_381>,
+    ) -> Result<blst_fp12, FastCryptoError> {
+        Ok(blst_multipairing(pvk, x, proof))
+    }
+}
+
+/// A verifier using only arkworks, which works for any pairing engine `E`.
+#[derive(Debug)]
+pub struct Arkworks<E: Pairing>(PhantomData<E>);
+
+impl<E: Pairing> Groth16Verifier for Arkworks<E> {
+    type Engine = E;
+    type PreparedVerifyingKey = ArkPreparedVerifyingKey<E>;
+    type TargetGroup = E::TargetField;
+
+    fn process_vk_special(vk: &VerifyingKey<E>) -> ArkPreparedVerifyingKey<E> {
+        ArkPreparedVerifyingKey {
+            vk: vk.clone(),
+            alpha_g1_beta_g2: E::pairing(vk.alpha_g1, vk.beta_g2).0,
+            gamma_g2_neg_pc: (-vk.gamma_g2).into(),
+            delta_g2_neg_pc: (-vk.delta_g2).into(),
+        }
+    }
+
+    fn num_public_inputs(pvk: &ArkPreparedVerifyingKey<E>) -> usize {
+        pvk.vk.gamma_abc_g1.len().saturating_sub(1)
+    }
+
+    fn alpha_g1_beta_g2(pvk: &ArkPreparedVerifyingKey<E>) -> &E::TargetField {
+        &pvk.alpha_g1_beta_g2
+    }
+
+    fn multipairing_with_processed_vk(
+        pvk: &ArkPreparedVerifyingKey<E>,
+        x: &[E::ScalarField],
+        proof: &Proof<E>,
+    ) -> Result<E::TargetField, FastCryptoError> {
+        let prepared_inputs = Groth16::<E>::prepare_inputs(pvk, x)
+            .map_err(|e| FastCryptoError::GeneralError(e.to_string()))?;
+        let miller_loop = E::multi_miller_loop(
+            [
+                E::G1Prepared::from(proof.a),
+                E::G1Prepared::from(prepared_inputs),
+                E::G1Prepared::from(proof.c),
+            ],
+            [
+                E::G2Prepared::from(proof.b),
+                pvk.gamma_g2_neg_pc.clone(),
+                pvk.delta_g2_neg_pc.clone(),
+            ],
+        );
+        E::final_exponentiation(miller_loop)
+            .map(|output| output.0)
+            .ok_or(FastCryptoError::GeneralOpaqueError)
+    }
+}
@@ -0,0 +1,680 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verification of the polynomial commitment openings of
+//! [halo2](https://github.com/privacy-scaling-explorations/halo2) proofs created with the KZG
+//! commitment scheme of the PSE fork of halo2.
+//!
+//! A halo2 verifier has two parts. The first part is specific to the circuit: It reads the
+//! commitments and evaluations of a proof from the transcript, checks that the gates, lookups and
+//! permutation arguments of the circuit are satisfied at a random point and outputs a list of
+//! queries, i.e., claims that the committed polynomials evaluate to the given values at the given
+//! points. Halo2 verifying keys do not contain the constraint system, which is recomputed from the
+//! circuit code, so this part is not implemented here. The second part checks all queries with a
+//! multi-opening argument and a single pairing check, and is implemented here for both multi-opening
+//! schemes of halo2: [verify_gwc] for `VerifierGWC` from [GWC19](https://eprint.iacr.org/2019/953)
+//! and [verify_shplonk] for `VerifierSHPLONK` from [BDFG20](https://eprint.iacr.org/2020/081).
+//!
+//! The multi-opening arguments return a [DualMsm] for the final pairing check, which can be
+//! accumulated over several proofs. Like for Groth16, the parameters are first processed into
+//! [PreparedParams] with [process_params], after which the pairing check is done with
+//! [DualMsm::check]. The parameters for BN254, the curve used with halo2, are read from the
+//! `ParamsKZG` files of halo2 with [bn254_params_from_bytes], and proofs written with halo2's
+//! `Blake2bWrite` transcript are read with [Blake2bTranscript].
+
+use ark_bn254::{Bn254, Fq, Fq2, Fr, G1Affine, G2Affine};
+use ark_ec::pairing::Pairing;
+use ark_ec::short_weierstrass::SWCurveConfig;
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{BigInt, BigInteger, Field, One, PrimeField, Zero};
+use blake2::digest::core_api::{Buffer, UpdateCore, VariableOutputCore};
+use blake2::Blake2bVarCore;
+use fastcrypto::error::FastCryptoError;
+use std::collections::BTreeSet;
+
+#[cfg(test)]
+#[path = "unit_tests/halo2_tests.rs"]
+mod halo2_tests;
+
+/// The largest `k` of parameters which can be read, i.e., the parameters are for circuits with at
+/// most `2^MAX_K` rows. This is the two-adicity of the scalar field of BN254.
+pub const MAX_K: u32 = 28;
+
+/// The personalization of the Blake2b transcript.
+const BLAKE2B_PERSONALIZATION: &[u8] = b"Halo2-Transcript";
+
+/// Prefixes of the messages added to the Blake2b transcript.
+const BLAKE2B_PREFIX_CHALLENGE: u8 = 0;
+const BLAKE2B_PREFIX_POINT: u8 = 1;
+const BLAKE2B_PREFIX_SCALAR: u8 = 2;
+
+/// The length of a compressed point in `G1` of BN254.
+const G1_COMPRESSED_LENGTH: usize = 32;
+
+/// The length of a compressed point in `G2` of BN254.
+const G2_COMPRESSED_LENGTH: usize = 64;
+
+/// The length of a scalar of BN254.
+const SCALAR_LENGTH: usize = 32;
+
+/// The verifier parameters of the KZG commitment scheme, `ParamsVerifierKZG` in halo2.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Params<E: Pairing> {
+    /// The circuits have at most `2^k` rows.
+    pub k: u32,
+    /// The generator `[1]_1`.
+    pub g: E::G1Affine,
+    /// The generator `[1]_2`.
+    pub g2: E::G2Affine,
+    /// The element `[s]_2`, where `s` is the secret of the setup.
+    pub s_g2: E::G2Affine,
+}
+
+/// Parameters with the prepared G2 elements used in the final pairing check.
+#[derive(Clone, Debug)]
+pub struct PreparedParams<E: Pairing> {
+    /// The parameters.
+    pub params: Params<E>,
+    s_g2_prepared: E::G2Prepared,
+    neg_g2_prepared: E::G2Prepared,
+}
+
+/// Takes the parameters and returns [PreparedParams].
+pub fn process_params<E: Pairing>(params: &Params<E>) -> PreparedParams<E> {
+    PreparedParams {
+        params: params.clone(),
+        s_g2_prepared: params.s_g2.into(),
+        neg_g2_prepared: (-params.g2.into_group()).into_affine().into(),
+    }
+}
+
+/// The serialization formats of points in halo2 parameters, `SerdeFormat` in halo2.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SerdeFormat {
+    /// Compressed points, as written by `ParamsKZG::write_custom` with `SerdeFormat::Processed`.
+    Processed,
+    /// Uncompressed points with coordinates in Montgomery form, as written by `ParamsKZG::write`.
+    RawBytes,
+}
+
+impl SerdeFormat {
+    fn g1_length(&self) -> usize {
+        match self {
+            SerdeFormat::Processed => G1_COMPRESSED_LENGTH,
+            SerdeFormat::RawBytes => 2 * G1_COMPRESSED_LENGTH,
+        }
+    }
+
+    fn g2_length(&self) -> usize {
+        match self {
+            SerdeFormat::Processed => G2_COMPRESSED_LENGTH,
+            SerdeFormat::RawBytes => 2 * G2_COMPRESSED_LENGTH,
+        }
+    }
+}
+
+/// Read the verifier parameters from a `ParamsKZG` file of halo2 for BN254, which consists of `k`
+/// as a little-endian `u32`, the `2^k` powers `[s^i]_1`, the `2^k` Lagrange basis commitments,
+/// `[1]_2` and `[s]_2`. Only the points used by the verifier are parsed.
+pub fn bn254_params_from_bytes(
+    bytes: &[u8],
+    format: SerdeFormat,
+) -> Result<Params<Bn254>, FastCryptoError> {
+    if bytes.len() < 4 {
+        return Err(FastCryptoError::InputTooShort(4));
+    }
+    let k = u32::from_le_bytes(bytes[..4].try_into().expect("Length is 4"));
+    if k > MAX_K {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    let g2_start = (1usize << k)
+        .checked_mul(2 * format.g1_length())
+        .and_then(|length| length.checked_add(4))
+        .ok_or(FastCryptoError::InvalidInput)?;
+    let expected_length = g2_start + 2 * format.g2_length();
+    if bytes.len() != expected_length {
+        return Err(FastCryptoError::InputLengthWrong(expected_length));
+    }
+    let g2_end = g2_start + format.g2_length();
+    let params = Params {
+        k,
+        g: g1_from_bytes(&bytes[4..4 + format.g1_length()], format)?,
+        g2: g2_from_bytes(&bytes[g2_start..g2_end], format)?,
+        s_g2: g2_from_bytes(&bytes[g2_end..], format)?,
+    };
+    if params.g.is_zero() || params.g2.is_zero() || params.s_g2.is_zero() {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    Ok(params)
+}
+
+/// A linear combination of points in `E::G1`, `MSMKZG` in halo2.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Msm<E: Pairing> {
+    scalars: Vec<E::ScalarField>,
+    bases: Vec<E::G1Affine>,
+}
+
+impl<E: Pairing> Default for Msm<E> {
+    fn default() -> Self {
+        Self {
+            scalars: Vec::new(),
+            bases: Vec::new(),
+        }
+    }
+}
+
+impl<E: Pairing> Msm<E> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The linear combination consisting only of the given point, e.g. a commitment read from the
+    /// transcript.
+    pub fn from_point(point: E::G1Affine) -> Self {
+        let mut msm = Self::new();
+        msm.append_term(E::ScalarField::one(), point);
+        msm
+    }
+
+    /// Add `scalar * point` to this linear combination.
+    pub fn append_term(&mut self, scalar: E::ScalarField, point: E::G1Affine) {
+        self.scalars.push(scalar);
+        self.bases.push(point);
+    }
+
+    /// Add another linear combination to this one.
+    pub fn add_msm(&mut self, other: &Self) {
+        self.scalars.extend_from_slice(&other.scalars);
+        self.bases.extend_from_slice(&other.bases);
+    }
+
+    /// Multiply this linear combination by `factor`.
+    pub fn scale(&mut self, factor: E::ScalarField) {
+        self.scalars.iter_mut().for_each(|s| *s *= factor);
+    }
+
+    /// Compute the linear combination.
+    pub fn eval(&self) -> E::G1 {
+        E::G1::msm_unchecked(&self.bases, &self.scalars)
+    }
+}
+
+/// The linear combinations `left` and `right` of the pairing check `e(left, [s]_2) = e(right, [1]_2)`
+/// to which the multi-opening arguments are reduced, `DualMSM` in halo2.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DualMsm<E: Pairing> {
+    pub left: Msm<E>,
+    pub right: Msm<E>,
+}
+
+impl<E: Pairing> Default for DualMsm<E> {
+    fn default() -> Self {
+        Self {
+            left: Msm::new(),
+            right: Msm::new(),
+        }
+    }
+}
+
+impl<E: Pairing> DualMsm<E> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Multiply both sides of the pairing check by `factor`.
+    pub fn scale(&mut self, factor: E::ScalarField) {
+        self.left.scale(factor);
+        self.right.scale(factor);
+    }
+
+    /// Accumulate the pairing check of another proof into this one. To check several proofs at
+    /// once, the accumulated checks must be scaled by independent random factors first.
+    pub fn add(&mut self, other: &Self) {
+        self.left.add_msm(&other.left);
+        self.right.add_msm(&other.right);
+    }
+
+    /// Returns whether `e(left, [s]_2) = e(right, [1]_2)`.
+    pub fn check(&self, pvk: &PreparedParams<E>) -> bool {
+        let result = E::multi_miller_loop(
+            [
+                self.left.eval().into_affine(),
+                self.right.eval().into_affine(),
+            ],
+            [pvk.s_g2_prepared.clone(), pvk.neg_g2_prepared.clone()],
+        );
+        E::final_exponentiation(result)
+            .map(|output| output.is_zero())
+            .unwrap_or(false)
+    }
+}
+
+/// A claim that the `commitment`'th of the commitments given to the verifier evaluates to `eval` at
+/// `point`, `VerifierQuery` in halo2. Like in halo2, two queries are for the same commitment only if
+/// they refer to the same entry of the commitments, even if two entries are equal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VerifierQuery<F> {
+    pub commitment: usize,
+    pub point: F,
+    pub eval: F,
+}
+
+/// A transcript from which the verifier reads a proof, `TranscriptRead` in halo2.
+pub trait TranscriptRead<E: Pairing> {
+    /// Read a point from the proof and add it to the transcript.
+    fn read_point(&mut self) -> Result<E::G1Affine, FastCryptoError>;
+
+    /// Read a scalar from the proof and add it to the transcript.
+    fn read_scalar(&mut self) -> Result<E::ScalarField, FastCryptoError>;
+
+    /// Add a point known to the verifier to the transcript. Fails for the point at infinity.
+    fn common_point(&mut self, point: &E::G1Affine) -> Result<(), FastCryptoError>;
+
+    /// Add a scalar known to the verifier to the transcript.
+    fn common_scalar(&mut self, scalar: &E::ScalarField);
+
+    /// Derive a challenge from the transcript.
+    fn squeeze_challenge(&mut self) -> E::ScalarField;
+}
+
+/// The transcript `Blake2bRead` of halo2 with `Challenge255` challenges for BN254. Points are
+/// compressed and scalars are in little-endian canonical form.
+#[derive(Clone)]
+pub struct Blake2bTranscript<'a> {
+    proof: &'a [u8],
+    state: Blake2bState,
+}
+
+impl<'a> Blake2bTranscript<'a> {
+    pub fn new(proof: &'a [u8]) -> Self {
+        Self {
+            proof,
+            state: Blake2bState::new(),
+        }
+    }
+
+    /// Fail if the proof has not been read completely.
+    pub fn finish(self) -> Result<(), FastCryptoError> {
+        if !self.proof.is_empty() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(())
+    }
+
+    fn take(&mut self, length: usize) -> Result<&'a [u8], FastCryptoError> {
+        if self.proof.len() < length {
+            return Err(FastCryptoError::InputTooShort(length));
+        }
+        let (head, tail) = self.proof.split_at(length);
+        self.proof = tail;
+        Ok(head)
+    }
+}
+
+impl<'a> TranscriptRead<Bn254> for Blake2bTranscript<'a> {
+    fn read_point(&mut self) -> Result<G1Affine, FastCryptoError> {
+        let point = g1_from_compressed(self.take(G1_COMPRESSED_LENGTH)?)?;
+        self.common_point(&point)?;
+        Ok(point)
+    }
+
+    fn read_scalar(&mut self) -> Result<Fr, FastCryptoError> {
+        let scalar = field_from_le_bytes(self.take(SCALAR_LENGTH)?)?;
+        self.common_scalar(&scalar);
+        Ok(scalar)
+    }
+
+    fn common_point(&mut self, point: &G1Affine) -> Result<(), FastCryptoError> {
+        let (x, y) = point.xy().ok_or(FastCryptoError::InvalidInput)?;
+        self.state.update(&[BLAKE2B_PREFIX_POINT]);
+        self.state.update(&x.into_bigint().to_bytes_le());
+        self.state.update(&y.into_bigint().to_bytes_le());
+        Ok(())
+    }
+
+    fn common_scalar(&mut self, scalar: &Fr) {
+        self.state.update(&[BLAKE2B_PREFIX_SCALAR]);
+        self.state.update(&scalar.into_bigint().to_bytes_le());
+    }
+
+    fn squeeze_challenge(&mut self) -> Fr {
+        self.state.update(&[BLAKE2B_PREFIX_CHALLENGE]);
+        Fr::from_le_bytes_mod_order(&self.state.clone().finalize())
+    }
+}
+
+/// Verify the openings of the queries with the multi-opening argument of `VerifierGWC` and return
+/// the pairing check to which it is reduced. Fails if there are no queries, if a query refers to a
+/// missing commitment or if the proof cannot be read from the transcript.
+pub fn verify_gwc<E: Pairing, T: TranscriptRead<E>>(
+    params: &Params<E>,
+    transcript: &mut T,
+    commitments: &[Msm<E>],
+    queries: &[VerifierQuery<E::ScalarField>],
+) -> Result<DualMsm<E>, FastCryptoError> {
+    check_queries(commitments, queries)?;
+
+    // Group the queries by their points, in the order in which the points first occur.
+    let mut point_queries: Vec<(E::ScalarField, Vec<&VerifierQuery<E::ScalarField>>)> = Vec::new();
+    for query in queries {
+        match point_queries
+            .iter_mut()
+            .find(|(point, _)| *point == query.point)
+        {
+            Some((_, queries)) => queries.push(query),
+            None => point_queries.push((query.point, vec![query])),
+        }
+    }
+
+    let v = transcript.squeeze_challenge();
+    let witnesses = point_queries
+        .iter()
+        .map(|_| transcript.read_point())
+        .collect::<Result<Vec<_>, _>>()?;
+    let u = transcript.squeeze_challenge();
+
+    // For each point z with witness W and queries for C_j evaluating to y_j, we have
+    // s W = z W + sum_j v^j (C_j - y_j [1]), combined using powers of u.
+    let mut result = DualMsm::new();
+    let mut eval = E::ScalarField::zero();
+    for (((point, queries), witness), power_of_u) in
+        point_queries.iter().zip(witnesses).zip(powers(u))
+    {
+        for (query, power_of_v) in queries.iter().zip(powers(v)) {
+            let mut commitment = commitments[query.commitment].clone();
+            commitment.scale(power_of_u * power_of_v);
+            result.right.add_msm(&commitment);
+            eval += power_of_u * power_of_v * query.eval;
+        }
+        result.left.append_term(power_of_u, witness);
+        result.right.append_term(power_of_u * point, witness);
+    }
+    result.right.append_term(-eval, params.g);
+    Ok(result)
+}
+
+/// Verify the openings of the queries with the multi-opening argument of `VerifierSHPLONK` and
+/// return the pairing check to which it is reduced. Fails if there are no queries, if a query refers
+/// to a missing commitment or if the proof cannot be read from the transcript.
+pub fn verify_shplonk<E: Pairing, T: TranscriptRead<E>>(
+    params: &Params<E>,
+    transcript: &mut T,
+    commitments: &[Msm<E>],
+    queries: &[VerifierQuery<E::ScalarField>],
+) -> Result<DualMsm<E>, FastCryptoError> {
+    check_queries(commitments, queries)?;
+    let (rotation_sets, super_point_set) = rotation_sets(queries);
+
+    let y = transcript.squeeze_challenge();
+    let v = transcript.squeeze_challenge();
+    let h1 = transcript.read_point()?;
+    let u = transcript.squeeze_challenge();
+    let h2 = transcript.read_point()?;
+
+    // The linearisation of the quotient argument, normalized by the vanishing polynomial of the
+    // points outside of the first rotation set.
+    let mut right = Msm::new();
+    let mut r_outer = E::ScalarField::zero();
+    let mut z_0 = E::ScalarField::zero();
+    let mut z_0_diff_inverse = E::ScalarField::zero();
+    for (i, (set, power_of_v)) in rotation_sets.iter().zip(powers(v)).enumerate() {
+        let mut z_diff = vanishing_polynomial(
+            super_point_set.iter().filter(|p| !set.points.contains(p)),
+            &u,
+        );
+        if i == 0 {
+            z_0 = vanishing_polynomial(set.points.iter(), &u);
+            z_0_diff_inverse = z_diff.inverse().ok_or(FastCryptoError::InvalidProof)?;
+            z_diff = E::ScalarField::one();
+        } else {
+            z_diff *= z_0_diff_inverse;
+        }
+        let mut r_inner = E::ScalarField::zero();
+        for ((commitment, evals), power_of_y) in set.commitments.iter().zip(powers(y)) {
+            r_inner += power_of_y * interpolate(&set.points, evals, &u);
+            let mut msm = commitments[*commitment].clone();
+            msm.scale(power_of_v * z_diff * power_of_y);
+            right.add_msm(&msm);
+        }
+        r_outer += power_of_v * z_diff * r_inner;
+    }
+    right.append_term(-r_outer, params.g);
+    right.append_term(-z_0, h1);
+    right.append_term(u, h2);
+    Ok(DualMsm {
+        left: Msm::from_point(h2),
+        right,
+    })
+}
+
+fn check_queries<E: Pairing>(
+    commitments: &[Msm<E>],
+    queries: &[VerifierQuery<E::ScalarField>],
+) -> Result<(), FastCryptoError> {
+    if queries.is_empty() || queries.iter().any(|q| q.commitment >= commitments.len()) {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    Ok(())
+}
+
+/// The commitments which are queried at the same set of points, with their evaluations at the points.
+struct RotationSet<F> {
+    points: Vec<F>,
+    commitments: Vec<(usize, Vec<F>)>,
+}
+
+/// Group the commitments by the sets of points at which they are queried, and return the groups in
+/// the order in which the commitments first occur together with the set of all points.
+fn rotation_sets<F: PrimeField>(queries: &[VerifierQuery<F>]) -> (Vec<RotationSet<F>>, Vec<F>) {
+    let mut super_point_set = BTreeSet::new();
+    let mut commitment_points: Vec<(usize, BTreeSet<F>)> = Vec::new();
+    for query in queries {
+        super_point_set.insert(query.point);
+        match commitment_points
+            .iter_mut()
+            .find(|(commitment, _)| *commitment == query.commitment)
+        {
+            Some((_, points)) => {
+                points.insert(query.point);
+            }
+            None => commitment_points.push((query.commitment, BTreeSet::from([query.point]))),
+        }
+    }
+
+    let mut sets: Vec<(BTreeSet<F>, Vec<usize>)> = Vec::new();
+    for (commitment, points) in commitment_points {
+        match sets.iter_mut().find(|(set, _)| *set == points) {
+            Some((_, commitments)) => commitments.push(commitment),
+            None => sets.push((points, vec![commitment])),
+        }
+    }
+
+    // If a commitment is queried several times at the same point, the first query is used.
+    let eval = |commitment: usize, point: &F| {
+        queries
+            .iter()
+            .find(|q| q.commitment == commitment && q.point == *point)
+            .expect("All queried points are in the set")
+            .eval
+    };
+    let rotation_sets = sets
+        .into_iter()
+        .map(|(points, commitments)| {
+            let points = points.into_iter().collect::<Vec<_>>();
+            RotationSet {
+                commitments: commitments
+                    .into_iter()
+                    .map(|c| (c, points.iter().map(|p| eval(c, p)).collect()))
+                    .collect(),
+                points,
+            }
+        })
+        .collect();
+    (rotation_sets, super_point_set.into_iter().collect())
+}
+
+/// Evaluate the polynomial `prod_i (X - roots_i)` at `x`.
+fn vanishing_polynomial<'a, F: Field>(roots: impl Iterator<Item = &'a F>, x: &F) -> F {
+    roots.fold(F::one(), |acc, root| acc * (*x - root))
+}
+
+/// Evaluate the polynomial of the lowest degree which is `evals_i` at `points_i` at `x`. The points
+/// must be distinct.
+fn interpolate<F: Field>(points: &[F], evals: &[F], x: &F) -> F {
+    points
+        .iter()
+        .zip(evals)
+        .enumerate()
+        .map(|(i, (point, eval))| {
+            let (numerator, denominator) = points
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .fold((F::one(), F::one()), |(n, d), (_, other)| {
+                    (n * (*x - other), d * (*point - other))
+                });
+            *eval * numerator * denominator.inverse().expect("The points are distinct")
+        })
+        .sum()
+}
+
+/// The powers `1, x, x^2, ...`.
+fn powers<F: Field>(x: F) -> impl Iterator<Item = F> {
+    std::iter::successors(Some(F::one()), move |power| Some(*power * x))
+}
+
+/// The Blake2b state of the transcript with a 64 byte output and the halo2 personalization.
+#[derive(Clone)]
+struct Blake2bState {
+    core: Blake2bVarCore,
+    buffer: Buffer<Blake2bVarCore>,
+}
+
+impl Blake2bState {
+    fn new() -> Self {
+        Self {
+            core: Blake2bVarCore::new_with_params(&[], BLAKE2B_PERSONALIZATION, 0, 64),
+            buffer: Default::default(),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        let Self { core, buffer } = self;
+        buffer.digest_blocks(data, |blocks| core.update_blocks(blocks));
+    }
+
+    fn finalize(mut self) -> Vec<u8> {
+        let mut output = Default::default();
+        self.core
+            .finalize_variable_core(&mut self.buffer, &mut output);
+        output.to_vec()
+    }
+}
+
+/// Parse a canonical little-endian encoding of a field element.
+fn field_from_le_bytes<F: PrimeField>(bytes: &[u8]) -> Result<F, FastCryptoError> {
+    let element = F::from_le_bytes_mod_order(bytes);
+    if element.into_bigint().to_bytes_le() != bytes {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    Ok(element)
+}
+
+/// Parse a coordinate in Montgomery form, i.e., the little-endian limbs of `x * 2^256 mod p`.
+fn fq_from_raw_bytes(bytes: &[u8]) -> Result<Fq, FastCryptoError> {
+    let mut limbs = [0u64; 4];
+    limbs
+        .iter_mut()
+        .zip(bytes.chunks_exact(8))
+        .for_each(|(limb, chunk)| {
+            *limb = u64::from_le_bytes(chunk.try_into().expect("Length is 8"))
+        });
+    let montgomery = BigInt::new(limbs);
+    if montgomery >= Fq::MODULUS {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    Ok(Fq::new_unchecked(montgomery))
+}
+
+/// Parse a compressed point in `G1` as encoded by halo2curves: The little-endian encoding of `x`,
+/// with the top bit set if `y` is odd. The point at infinity is encoded as zeros.
+fn g1_from_compressed(bytes: &[u8]) -> Result<G1Affine, FastCryptoError> {
+    let mut x_bytes = bytes.to_vec();
+    let y_odd = x_bytes[G1_COMPRESSED_LENGTH - 1] >> 7 == 1;
+    x_bytes[G1_COMPRESSED_LENGTH - 1] &= 0x7f;
+    let x: Fq = field_from_le_bytes(&x_bytes)?;
+    if x.is_zero() && !y_odd {
+        return Ok(G1Affine::zero());
+    }
+    let y = (x.square() * x + ark_bn254::g1::Config::COEFF_B)
+        .sqrt()
+        .ok_or(FastCryptoError::InvalidInput)?;
+    let y = if y.into_bigint().is_odd() == y_odd {
+        y
+    } else {
+        -y
+    };
+    // All points on the curve are in G1.
+    Ok(G1Affine::new_unchecked(x, y))
+}
+
+/// Parse a compressed point in `G2` as encoded by halo2curves: The little-endian encodings of `x.c0`
+/// and `x.c1`, with the top bit set if `y.c0` is odd. The point at infinity is encoded as zeros.
+fn g2_from_compressed(bytes: &[u8]) -> Result<G2Affine, FastCryptoError> {
+    let mut x_bytes = bytes.to_vec();
+    let y_odd = x_bytes[G2_COMPRESSED_LENGTH - 1] >> 7 == 1;
+    x_bytes[G2_COMPRESSED_LENGTH - 1] &= 0x7f;
+    let (c0, c1) = x_bytes.split_at(G2_COMPRESSED_LENGTH / 2);
+    let x = Fq2::new(field_from_le_bytes(c0)?, field_from_le_bytes(c1)?);
+    if x.is_zero() && !y_odd {
+        return Ok(G2Affine::zero());
+    }
+    let y = (x.square() * x + ark_bn254::g2::Config::COEFF_B)
+        .sqrt()
+        .ok_or(FastCryptoError::InvalidInput)?;
+    let y = if y.c0.into_bigint().is_odd() == y_odd {
+        y
+    } else {
+        -y
+    };
+    let point = G2Affine::new_unchecked(x, y);
+    if !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    Ok(point)
+}
+
+fn g1_from_bytes(bytes: &[u8], format: SerdeFormat) -> Result<G1Affine, FastCryptoError> {
+    match format {
+        SerdeFormat::Processed => g1_from_compressed(bytes),
+        SerdeFormat::RawBytes => {
+            let (x, y) = bytes.split_at(G1_COMPRESSED_LENGTH);
+            let point = G1Affine::new_unchecked(fq_from_raw_bytes(x)?, fq_from_raw_bytes(y)?);
+            if !point.is_on_curve() {
+                return Err(FastCryptoError::InvalidInput);
+            }
+            Ok(point)
+        }
+    }
+}
+
+fn g2_from_bytes(bytes: &[u8], format: SerdeFormat) -> Result<G2Affine, FastCryptoError> {
+    match format {
+        SerdeFormat::Processed => g2_from_compressed(bytes),
+        SerdeFormat::RawBytes => {
+            let coordinates = bytes
+                .chunks_exact(G1_COMPRESSED_LENGTH)
+                .map(fq_from_raw_bytes)
+                .collect::<Result<Vec<_>, _>>()?;
+            let point = G2Affine::new_unchecked(
+                Fq2::new(coordinates[0], coordinates[1]),
+                Fq2::new(coordinates[2], coordinates[3]),
+            );
+            if !point.is_on_curve() || !point.is_in_correct_subgroup_assuming_on_curve() {
+                return Err(FastCryptoError::InvalidInput);
+            }
+            Ok(point)
+        }
+    }
+}
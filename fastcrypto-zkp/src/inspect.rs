@@ -0,0 +1,214 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Inspection of serialized Groth16 verifying keys and proofs, e.g. to debug a failing verification.
+//!
+//! An [Inspection] reports the curve, the number of public inputs a verifying key expects, which
+//! points are not on the curve or not in the prime order subgroup, and the SHA-256 digest of the
+//! serialization. Inputs are parsed without validating the points so invalid points can be
+//! reported instead of rejected.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto_zkp::inspect::*;
+//! // A compressed BN254 proof must be 128 bytes.
+//! assert!(inspect_proof(Curve::Bn254, &[0u8; 10]).is_err());
+//! ```
+
+use ark_ec::pairing::Pairing;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Valid};
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::error::FastCryptoError;
+use fastcrypto::hash::{HashFunction, Sha256};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[cfg(test)]
+#[path = "unit_tests/inspect_tests.rs"]
+mod inspect_tests;
+
+/// The supported curves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Curve {
+    /// BLS12-381.
+    Bls12381,
+    /// BN254.
+    Bn254,
+}
+
+impl Display for Curve {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Curve::Bls12381 => write!(f, "bls12381"),
+            Curve::Bn254 => write!(f, "bn254"),
+        }
+    }
+}
+
+impl FromStr for Curve {
+    type Err = FastCryptoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bls12381" => Ok(Curve::Bls12381),
+            "bn254" => Ok(Curve::Bn254),
+            _ => Err(FastCryptoError::InvalidInput),
+        }
+    }
+}
+
+/// The kind of an inspected value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArtifactKind {
+    /// A Groth16 verifying key.
+    VerifyingKey,
+    /// A Groth16 proof.
+    Proof,
+}
+
+impl Display for ArtifactKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArtifactKind::VerifyingKey => write!(f, "verifying key"),
+            ArtifactKind::Proof => write!(f, "proof"),
+        }
+    }
+}
+
+/// A report on a verifying key or a proof.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Inspection {
+    /// The curve of the points.
+    pub curve: Curve,
+    /// Whether this is a verifying key or a proof.
+    pub kind: ArtifactKind,
+    /// The number of public inputs a verifying key expects. `None` for proofs.
+    pub public_inputs: Option<usize>,
+    /// The names of the points which are not on the curve or not in the prime order subgroup,
+    /// e.g. `gamma_abc_g1[2]`.
+    pub invalid_points: Vec<String>,
+    /// The SHA-256 digest of the compressed serialization.
+    pub digest: [u8; 32],
+}
+
+impl Inspection {
+    /// Whether all points are valid.
+    pub fn points_valid(&self) -> bool {
+        self.invalid_points.is_empty()
+    }
+}
+
+impl Display for Inspection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Curve: {}", self.curve)?;
+        writeln!(f, "Kind: {}", self.kind)?;
+        if let Some(public_inputs) = self.public_inputs {
+            writeln!(f, "Public inputs: {}", public_inputs)?;
+        }
+        match self.points_valid() {
+            true => writeln!(f, "Points valid: true")?,
+            false => writeln!(
+                f,
+                "Points valid: false ({})",
+                self.invalid_points.join(", ")
+            )?,
+        }
+        write!(f, "Digest: {}", Hex::encode(self.digest))
+    }
+}
+
+/// Inspect a verifying key serialized in compressed arkworks format.
+pub fn inspect_verifying_key(curve: Curve, bytes: &[u8]) -> Result<Inspection, FastCryptoError> {
+    match curve {
+        Curve::Bls12381 => Ok(inspect_ark_verifying_key(
+            curve,
+            &deserialize_unchecked::<ark_groth16::VerifyingKey<ark_bls12_381::Bls12_381>>(bytes)?,
+        )),
+        Curve::Bn254 => Ok(inspect_ark_verifying_key(
+            curve,
+            &deserialize_unchecked::<ark_groth16::VerifyingKey<ark_bn254::Bn254>>(bytes)?,
+        )),
+    }
+}
+
+/// Inspect a proof serialized in compressed arkworks format.
+pub fn inspect_proof(curve: Curve, bytes: &[u8]) -> Result<Inspection, FastCryptoError> {
+    match curve {
+        Curve::Bls12381 => Ok(inspect_ark_proof(
+            curve,
+            &deserialize_unchecked::<ark_groth16::Proof<ark_bls12_381::Bls12_381>>(bytes)?,
+        )),
+        Curve::Bn254 => Ok(inspect_ark_proof(
+            curve,
+            &deserialize_unchecked::<ark_groth16::Proof<ark_bn254::Bn254>>(bytes)?,
+        )),
+    }
+}
+
+/// Deserialize a value without validating its points. Fails if there are trailing bytes.
+fn deserialize_unchecked<T: CanonicalDeserialize>(bytes: &[u8]) -> Result<T, FastCryptoError> {
+    let mut reader = bytes;
+    let value = T::deserialize_compressed_unchecked(&mut reader)
+        .map_err(|_| FastCryptoError::InvalidInput)?;
+    if !reader.is_empty() {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    Ok(value)
+}
+
+pub(crate) fn inspect_ark_verifying_key<E: Pairing>(
+    curve: Curve,
+    vk: &ark_groth16::VerifyingKey<E>,
+) -> Inspection {
+    let mut invalid_points = Vec::new();
+    check_point(&vk.alpha_g1, "alpha_g1", &mut invalid_points);
+    check_point(&vk.beta_g2, "beta_g2", &mut invalid_points);
+    check_point(&vk.gamma_g2, "gamma_g2", &mut invalid_points);
+    check_point(&vk.delta_g2, "delta_g2", &mut invalid_points);
+    for (i, point) in vk.gamma_abc_g1.iter().enumerate() {
+        check_point(point, &format!("gamma_abc_g1[{}]", i), &mut invalid_points);
+    }
+    // The first element of gamma_abc_g1 is the constant term, so it must always be present.
+    if vk.gamma_abc_g1.is_empty() {
+        invalid_points.push("gamma_abc_g1".to_string());
+    }
+    Inspection {
+        curve,
+        kind: ArtifactKind::VerifyingKey,
+        public_inputs: Some(vk.gamma_abc_g1.len().saturating_sub(1)),
+        invalid_points,
+        digest: digest(vk),
+    }
+}
+
+pub(crate) fn inspect_ark_proof<E: Pairing>(
+    curve: Curve,
+    proof: &ark_groth16::Proof<E>,
+) -> Inspection {
+    let mut invalid_points = Vec::new();
+    check_point(&proof.a, "a", &mut invalid_points);
+    check_point(&proof.b, "b", &mut invalid_points);
+    check_point(&proof.c, "c", &mut invalid_points);
+    Inspection {
+        curve,
+        kind: ArtifactKind::Proof,
+        public_inputs: None,
+        invalid_points,
+        digest: digest(proof),
+    }
+}
+
+/// Record `name` if the point is not on the curve or not in the prime order subgroup.
+fn check_point<P: Valid>(point: &P, name: &str, invalid_points: &mut Vec<String>) {
+    if point.check().is_err() {
+        invalid_points.push(name.to_string());
+    }
+}
+
+fn digest<T: CanonicalSerialize>(value: &T) -> [u8; 32] {
+    let mut bytes = Vec::new();
+    value
+        .serialize_compressed(&mut bytes)
+        .expect("Serialization to a vector should succeed");
+    Sha256::digest(&bytes).digest
+}
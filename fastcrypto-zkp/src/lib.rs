@@ -9,5 +9,85 @@ pub mod bls12381;
 
 pub mod bn254;
 
+/// SnarkPack aggregation of Groth16 proofs
+pub mod aggregation;
+
+/// Async wrappers of the verifiers running on the blocking thread pool of tokio
+#[cfg(feature = "tokio")]
+pub mod async_verification;
+
+/// Typed errors of the verifiers of serialized proofs
+pub mod error;
+
+/// Groth16 verification generic over the pairing engine
+pub mod groth16;
+
+/// Verification of the KZG multi-opening arguments of halo2 proofs
+pub mod halo2;
+
+/// PLONK verifier with KZG polynomial commitments
+pub mod plonk;
+
+/// fflonk verifier compatible with snarkjs
+pub mod fflonk;
+
+/// The PLONK circuit shared by the tests of the PLONK and fflonk verifiers
+#[cfg(test)]
+#[path = "unit_tests/plonk_circuit.rs"]
+mod plonk_circuit;
+
+/// Verification of Nova folding proofs
+pub mod nova;
+
+/// The Poseidon hash function over the BN254 and BLS12-381 scalar fields
+pub mod poseidon;
+
+/// Polynomial arithmetic and FFTs over the scalar fields of pairing-friendly curves
+pub mod polynomial;
+
+/// A least recently used cache of prepared verifying keys
+pub mod pvk_cache;
+
+/// Prepared verifying keys which can be replaced while in use
+pub mod watchable_pvk;
+
+/// Parsing of public inputs given as strings or bytes
+pub mod public_inputs;
+
+/// Inspection of verifying keys and proofs
+pub mod inspect;
+
+/// Parsers for snarkjs and circom artifacts
+pub mod snarkjs;
+
+/// Parsers for the binary encodings of gnark proofs and verifying keys
+pub mod gnark;
+
+/// Canonical byte encodings of proofs and verifying keys
+pub mod serialization;
+
+/// A common interface of the verifiers of serialized proofs
+pub mod proof_system;
+
+/// Verification of Winterfell STARK proofs
+#[cfg(feature = "winterfell")]
+pub mod stark;
+
+/// Reporting of failed verifications
+pub mod verification_hook;
+
+/// Verification of zkLogin-style proofs binding OpenID JWTs to ephemeral keys
+pub mod zklogin;
+
+/// Verification of powers-of-tau and Groth16 trusted setup ceremonies
+pub mod trusted_setup;
+
+/// Circuits, deterministic proofs and golden vectors for tests of integrations
+#[cfg(any(test, feature = "test_helpers"))]
+pub mod test_helpers;
+
 /// Simple circuits used in benchmarks and demos
 pub mod dummy_circuits;
+
+/// Circuits with realistic shapes used in benchmarks
+pub mod bench_circuits;
@@ -0,0 +1,315 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verification of folding proofs of [Nova](https://eprint.iacr.org/2021/370), which fold
+//! instances of a rank-1 constraint system into a single relaxed instance.
+//!
+//! A relaxed R1CS instance `(comm_W, comm_E, u, x)` is satisfied by a witness `(W, E)` if
+//! `comm_W` and `comm_E` are the Pedersen commitments to `W` and `E`, and `Az ∘ Bz = u Cz + E`
+//! for `z = (W, u, x)`. A plain instance `(comm_W, x)` is the relaxed instance with `comm_E` the
+//! commitment to zero and `u = 1`. The non-interactive folding scheme of Nova folds a relaxed
+//! instance `U` and a plain instance `u` into `U + r u` using the commitment `comm_T` to a cross
+//! term given by the prover, where the challenge `r` is derived from a transcript. The folded
+//! instance is satisfiable if and only if both folded instances are, except with negligible
+//! probability.
+//!
+//! [verify] folds a sequence of plain instances into a running instance using the commitments to
+//! the cross terms in a [FoldingProof], and then decides the final instance by checking the
+//! relaxed witness of the proof. This is how the final step of the verifier of an IVC proof of
+//! Nova checks the running instance and the last instance of the augmented circuit, after having
+//! checked that the public inputs of the last instance hash the number of steps, the initial and the
+//! final state and the running instance. Compressed proofs, which replace the relaxed witness by a
+//! Spartan proof, are not supported.
+//!
+//! The variables of the R1CS are ordered as `z = (W, u, x)`, i.e., the column of the constant is
+//! the number of witness variables.
+
+use crate::plonk::Transcript;
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::PrimeField;
+use fastcrypto::error::FastCryptoError;
+use fastcrypto::hash::{HashFunction, Sha256};
+
+#[cfg(test)]
+#[path = "unit_tests/nova_tests.rs"]
+mod nova_tests;
+
+/// Domain separation tag of the transcript.
+const TRANSCRIPT_DST: &[u8] = b"fastcrypto-zkp-nova-v1";
+
+/// A sparse matrix given by the entries `(row, column, value)`.
+pub type SparseMatrix<F> = Vec<(usize, usize, F)>;
+
+/// A rank-1 constraint system `Az ∘ Bz = Cz`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct R1cs<F> {
+    num_constraints: usize,
+    num_variables: usize,
+    num_public_inputs: usize,
+    a: SparseMatrix<F>,
+    b: SparseMatrix<F>,
+    c: SparseMatrix<F>,
+}
+
+/// Generators of Pedersen commitments to vectors.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommitmentKey<G: CurveGroup> {
+    generators: Vec<G::Affine>,
+}
+
+/// An instance of an R1CS, committing to its witness.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct R1csInstance<G: CurveGroup> {
+    pub comm_w: G::Affine,
+    pub public_inputs: Vec<G::ScalarField>,
+}
+
+/// A relaxed instance of an R1CS, committing to its witness and its error vector.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RelaxedR1csInstance<G: CurveGroup> {
+    pub comm_w: G::Affine,
+    pub comm_e: G::Affine,
+    pub u: G::ScalarField,
+    pub public_inputs: Vec<G::ScalarField>,
+}
+
+/// A witness, given by the witness variables `W` and the error vector `E`, of a relaxed instance.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RelaxedR1csWitness<F> {
+    pub w: Vec<F>,
+    pub e: Vec<F>,
+}
+
+/// A proof that a sequence of instances and a running instance are satisfiable.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FoldingProof<G: CurveGroup> {
+    /// The commitments to the cross terms, one for each folded instance.
+    pub cross_terms: Vec<G::Affine>,
+    /// A witness of the folded instance.
+    pub witness: RelaxedR1csWitness<G::ScalarField>,
+}
+
+/// An R1CS together with a commitment key, as used by the verifier.
+#[derive(Clone, Debug)]
+pub struct VerifierKey<G: CurveGroup> {
+    pub r1cs: R1cs<G::ScalarField>,
+    pub ck: CommitmentKey<G>,
+    /// A digest of the R1CS and the commitment key, which starts the transcript.
+    digest: [u8; 32],
+}
+
+impl<F: PrimeField> R1cs<F> {
+    /// Fails if an entry of the matrices is out of bounds.
+    pub fn new(
+        num_constraints: usize,
+        num_variables: usize,
+        num_public_inputs: usize,
+        a: SparseMatrix<F>,
+        b: SparseMatrix<F>,
+        c: SparseMatrix<F>,
+    ) -> Result<Self, FastCryptoError> {
+        let num_columns = num_variables + 1 + num_public_inputs;
+        if [&a, &b, &c]
+            .iter()
+            .flat_map(|m| m.iter())
+            .any(|(row, column, _)| *row >= num_constraints || *column >= num_columns)
+        {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(Self {
+            num_constraints,
+            num_variables,
+            num_public_inputs,
+            a,
+            b,
+            c,
+        })
+    }
+
+    pub fn num_constraints(&self) -> usize {
+        self.num_constraints
+    }
+
+    pub fn num_variables(&self) -> usize {
+        self.num_variables
+    }
+
+    pub fn num_public_inputs(&self) -> usize {
+        self.num_public_inputs
+    }
+
+    /// Compute `Az`, `Bz` and `Cz` for `z = (w, u, x)`. The lengths must be checked by the caller.
+    pub(crate) fn multiply(&self, w: &[F], u: &F, x: &[F]) -> (Vec<F>, Vec<F>, Vec<F>) {
+        let z = w
+            .iter()
+            .chain(std::iter::once(u))
+            .chain(x.iter())
+            .collect::<Vec<_>>();
+        let multiply = |matrix: &SparseMatrix<F>| {
+            let mut result = vec![F::zero(); self.num_constraints];
+            for (row, column, value) in matrix {
+                result[*row] += *value * z[*column];
+            }
+            result
+        };
+        (multiply(&self.a), multiply(&self.b), multiply(&self.c))
+    }
+}
+
+impl<G: CurveGroup> CommitmentKey<G> {
+    /// The key with the given generators, whose discrete logarithms with respect to each other must
+    /// be unknown.
+    pub fn new(generators: Vec<G::Affine>) -> Self {
+        Self { generators }
+    }
+
+    /// The largest number of values which can be committed to.
+    pub fn len(&self) -> usize {
+        self.generators.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.generators.is_empty()
+    }
+
+    /// The commitment `sum values[i] G_i`. Fails if there are more values than generators.
+    pub fn commit(&self, values: &[G::ScalarField]) -> Result<G::Affine, FastCryptoError> {
+        if values.len() > self.generators.len() {
+            return Err(FastCryptoError::InputTooLong(self.generators.len()));
+        }
+        Ok(G::msm(&self.generators[..values.len()], values)
+            .expect("The lengths are equal")
+            .into_affine())
+    }
+}
+
+impl<G: CurveGroup> From<R1csInstance<G>> for RelaxedR1csInstance<G> {
+    fn from(instance: R1csInstance<G>) -> Self {
+        Self {
+            comm_w: instance.comm_w,
+            comm_e: G::Affine::zero(),
+            u: G::ScalarField::one(),
+            public_inputs: instance.public_inputs,
+        }
+    }
+}
+
+impl<G: CurveGroup> RelaxedR1csInstance<G> {
+    /// Fold a plain instance into this instance, i.e., compute `U + r u` with `comm_E` replaced by
+    /// `comm_E + r comm_T`. The lengths of the public inputs must be equal.
+    pub fn fold(&self, instance: &R1csInstance<G>, comm_t: &G::Affine, r: &G::ScalarField) -> Self {
+        Self {
+            comm_w: (self.comm_w.into_group() + instance.comm_w * *r).into_affine(),
+            comm_e: (self.comm_e.into_group() + *comm_t * *r).into_affine(),
+            u: self.u + r,
+            public_inputs: self
+                .public_inputs
+                .iter()
+                .zip(&instance.public_inputs)
+                .map(|(x1, x2)| *x1 + *r * x2)
+                .collect(),
+        }
+    }
+}
+
+/// Create a verifier key. Fails if the commitment key is too short to commit to the witnesses and
+/// the error vectors of the R1CS.
+pub fn process_vk<G: CurveGroup>(
+    r1cs: R1cs<G::ScalarField>,
+    ck: CommitmentKey<G>,
+) -> Result<VerifierKey<G>, FastCryptoError> {
+    if ck.len() < r1cs.num_variables.max(r1cs.num_constraints) {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    let mut transcript = Transcript(Vec::new());
+    [
+        r1cs.num_constraints,
+        r1cs.num_variables,
+        r1cs.num_public_inputs,
+    ]
+    .iter()
+    .for_each(|n| transcript.append(&(*n as u64)));
+    for matrix in [&r1cs.a, &r1cs.b, &r1cs.c] {
+        transcript.append(&(matrix.len() as u64));
+        for (row, column, value) in matrix {
+            transcript.append(&(*row as u64));
+            transcript.append(&(*column as u64));
+            transcript.append(value);
+        }
+    }
+    transcript.append(&ck.generators);
+    let digest = Sha256::digest(&transcript.0).digest;
+    Ok(VerifierKey { r1cs, ck, digest })
+}
+
+/// The challenge of folding `instance` into `running` with the given cross term.
+pub(crate) fn challenge<G: CurveGroup>(
+    vk: &VerifierKey<G>,
+    running: &RelaxedR1csInstance<G>,
+    instance: &R1csInstance<G>,
+    comm_t: &G::Affine,
+) -> G::ScalarField {
+    let mut transcript = Transcript([TRANSCRIPT_DST, vk.digest.as_slice()].concat());
+    transcript.append(&running.comm_w);
+    transcript.append(&running.comm_e);
+    transcript.append(&running.u);
+    transcript.append(&running.public_inputs);
+    transcript.append(&instance.comm_w);
+    transcript.append(&instance.public_inputs);
+    transcript.append(comm_t);
+    transcript.challenge()
+}
+
+/// Fold the instances into the running instance, which may be the relaxation of a plain instance,
+/// and check that the witness of the proof satisfies the folded instance. Fails if the numbers of
+/// instances and cross terms differ or if the public inputs or the witness have wrong lengths.
+pub fn verify<G: CurveGroup>(
+    vk: &VerifierKey<G>,
+    running: &RelaxedR1csInstance<G>,
+    instances: &[R1csInstance<G>],
+    proof: &FoldingProof<G>,
+) -> Result<bool, FastCryptoError> {
+    let r1cs = &vk.r1cs;
+    if instances.len() != proof.cross_terms.len()
+        || running.public_inputs.len() != r1cs.num_public_inputs
+        || instances
+            .iter()
+            .any(|instance| instance.public_inputs.len() != r1cs.num_public_inputs)
+    {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    let folded = instances.iter().zip(&proof.cross_terms).fold(
+        running.clone(),
+        |running, (instance, comm_t)| {
+            let r = challenge(vk, &running, instance, comm_t);
+            running.fold(instance, comm_t, &r)
+        },
+    );
+    decide(vk, &folded, &proof.witness)
+}
+
+/// Check that the witness satisfies the relaxed instance. Fails if the witness or the public inputs
+/// have wrong lengths.
+pub fn decide<G: CurveGroup>(
+    vk: &VerifierKey<G>,
+    instance: &RelaxedR1csInstance<G>,
+    witness: &RelaxedR1csWitness<G::ScalarField>,
+) -> Result<bool, FastCryptoError> {
+    let r1cs = &vk.r1cs;
+    if witness.w.len() != r1cs.num_variables
+        || witness.e.len() != r1cs.num_constraints
+        || instance.public_inputs.len() != r1cs.num_public_inputs
+    {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    if vk.ck.commit(&witness.w)? != instance.comm_w || vk.ck.commit(&witness.e)? != instance.comm_e
+    {
+        return Ok(false);
+    }
+    let (az, bz, cz) = r1cs.multiply(&witness.w, &instance.u, &instance.public_inputs);
+    Ok(az
+        .iter()
+        .zip(bz.iter())
+        .zip(cz.iter().zip(witness.e.iter()))
+        .all(|((a, b), (c, e))| *a * b == instance.u * c + e))
+}
@@ -0,0 +1,357 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A verifier for [PLONK](https://eprint.iacr.org/2019/953) proofs with KZG polynomial commitments
+//! over any pairing-friendly curve supported by arkworks, e.g. BLS12-381 and BN254.
+//!
+//! Circuits use the standard arithmetic gate `q_M a b + q_L a + q_R b + q_O c + q_C + PI = 0` over
+//! a domain of size `n`, where the `i`'th of the `l` public inputs, `x_i`, is enforced in row `i` by
+//! `PI(X) = -sum_i x_i L_i(X)`. The wires of the three columns are permuted over the cosets `H`,
+//! `k_1 H` and `k_2 H` of the domain `H`. The quotient polynomial is committed to in three parts,
+//! `t(X) = t_lo(X) + X^n t_mid(X) + X^2n t_hi(X)`.
+//!
+//! The verifier follows the version of the protocol where the prover sends the evaluations of
+//! `a, b, c, σ_1, σ_2` at the challenge `ζ` and of `z` at `ζω`, and where both evaluation proofs are
+//! checked with a single two-term pairing product. Challenges are derived with SHA-512 from a
+//! transcript starting with a digest of the verifying key and the public inputs.
+//!
+//! Like for Groth16, a verifying key is first processed into a [PreparedVerifyingKey] with
+//! [process_vk_special], after which proofs are verified with [verify_with_processed_vk].
+
+use crate::polynomial::Radix2EvaluationDomain;
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{batch_inversion, Field, One, PrimeField, Zero};
+use ark_serialize::CanonicalSerialize;
+use fastcrypto::error::FastCryptoError;
+use fastcrypto::hash::{HashFunction, Sha256, Sha512};
+
+#[cfg(test)]
+#[path = "unit_tests/plonk_tests.rs"]
+mod plonk_tests;
+
+/// Domain separation tag for the Fiat–Shamir transcript.
+const TRANSCRIPT_DST: &[u8] = b"fastcrypto-zkp-plonk-v1";
+
+/// A PLONK verifying key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifyingKey<E: Pairing> {
+    /// The size `n` of the domain, which must be a power of two.
+    pub domain_size: usize,
+    /// The number of public inputs `l`.
+    pub num_public_inputs: usize,
+    /// The generator of the second coset of the domain.
+    pub k1: E::ScalarField,
+    /// The generator of the third coset of the domain.
+    pub k2: E::ScalarField,
+    /// Commitment to the multiplication selector.
+    pub q_m: E::G1Affine,
+    /// Commitment to the left selector.
+    pub q_l: E::G1Affine,
+    /// Commitment to the right selector.
+    pub q_r: E::G1Affine,
+    /// Commitment to the output selector.
+    pub q_o: E::G1Affine,
+    /// Commitment to the constant selector.
+    pub q_c: E::G1Affine,
+    /// Commitment to the permutation of the first column.
+    pub sigma_1: E::G1Affine,
+    /// Commitment to the permutation of the second column.
+    pub sigma_2: E::G1Affine,
+    /// Commitment to the permutation of the third column.
+    pub sigma_3: E::G1Affine,
+    /// The element `x * H` in `E::G2`, where `x` is the secret of the KZG setup.
+    pub x_g2: E::G2Affine,
+}
+
+/// A PLONK proof.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Proof<E: Pairing> {
+    /// Commitment to the wire polynomial of the first column.
+    pub a: E::G1Affine,
+    /// Commitment to the wire polynomial of the second column.
+    pub b: E::G1Affine,
+    /// Commitment to the wire polynomial of the third column.
+    pub c: E::G1Affine,
+    /// Commitment to the permutation polynomial `z`.
+    pub z: E::G1Affine,
+    /// Commitment to the low part of the quotient polynomial.
+    pub t_lo: E::G1Affine,
+    /// Commitment to the middle part of the quotient polynomial.
+    pub t_mid: E::G1Affine,
+    /// Commitment to the high part of the quotient polynomial.
+    pub t_hi: E::G1Affine,
+    /// The opening proof at `ζ`.
+    pub w_zeta: E::G1Affine,
+    /// The opening proof at `ζω`.
+    pub w_zeta_omega: E::G1Affine,
+    /// The evaluation `a(ζ)`.
+    pub a_eval: E::ScalarField,
+    /// The evaluation `b(ζ)`.
+    pub b_eval: E::ScalarField,
+    /// The evaluation `c(ζ)`.
+    pub c_eval: E::ScalarField,
+    /// The evaluation `σ_1(ζ)`.
+    pub sigma_1_eval: E::ScalarField,
+    /// The evaluation `σ_2(ζ)`.
+    pub sigma_2_eval: E::ScalarField,
+    /// The evaluation `z(ζω)`.
+    pub z_omega_eval: E::ScalarField,
+}
+
+/// A pre-processed verifying key with the domain and the prepared G2 elements used in the final
+/// pairing check.
+#[derive(Clone, Debug)]
+pub struct PreparedVerifyingKey<E: Pairing> {
+    /// The verifying key.
+    pub vk: VerifyingKey<E>,
+    domain: Radix2EvaluationDomain<E::ScalarField>,
+    /// A digest of the verifying key, which starts the transcript.
+    digest: [u8; 32],
+    x_g2_prepared: E::G2Prepared,
+    g2_prepared: E::G2Prepared,
+}
+
+/// Takes a PLONK verifying key and returns a [PreparedVerifyingKey]. Fails if the domain size is
+/// not a power of two supported by the scalar field.
+pub fn process_vk_special<E: Pairing>(
+    vk: &VerifyingKey<E>,
+) -> Result<PreparedVerifyingKey<E>, FastCryptoError> {
+    let domain = Radix2EvaluationDomain::new(vk.domain_size)?;
+    if domain.size() != vk.domain_size || vk.num_public_inputs > vk.domain_size {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    Ok(PreparedVerifyingKey {
+        vk: vk.clone(),
+        domain,
+        digest: vk_digest(vk),
+        x_g2_prepared: vk.x_g2.into(),
+        g2_prepared: E::G2Affine::generator().into(),
+    })
+}
+
+/// Verify a PLONK proof for the given public inputs. Returns `Ok(false)` if the proof is invalid and
+/// fails if the number of public inputs is wrong.
+pub fn verify_with_processed_vk<E: Pairing>(
+    pvk: &PreparedVerifyingKey<E>,
+    public_inputs: &[E::ScalarField],
+    proof: &Proof<E>,
+) -> Result<bool, FastCryptoError> {
+    let vk = &pvk.vk;
+    if public_inputs.len() != vk.num_public_inputs {
+        return Err(FastCryptoError::InvalidInput);
+    }
+
+    let Challenges {
+        beta,
+        gamma,
+        alpha,
+        zeta,
+        v,
+        u,
+    } = Challenges::derive(&pvk.digest, public_inputs, proof);
+
+    // Evaluate the vanishing polynomial, the first Lagrange polynomial and the public input
+    // polynomial at ζ, using L_i(ζ) = ω^i (ζ^n - 1) / (n (ζ - ω^i)).
+    let n = E::ScalarField::from(vk.domain_size as u64);
+    let zeta_n = zeta.pow([vk.domain_size as u64]);
+    let z_h = zeta_n - E::ScalarField::one();
+    if z_h.is_zero() {
+        // ζ is in the domain, which only happens with negligible probability.
+        return Ok(false);
+    }
+    let elements = (0..public_inputs.len().max(1))
+        .map(|i| pvk.domain.element(i))
+        .collect::<Vec<_>>();
+    let mut lagrange = elements.iter().map(|w| n * (zeta - w)).collect::<Vec<_>>();
+    batch_inversion(&mut lagrange);
+    lagrange
+        .iter_mut()
+        .zip(&elements)
+        .for_each(|(l, w)| *l *= z_h * w);
+    let l_1 = lagrange[0];
+    let pi = -public_inputs
+        .iter()
+        .zip(&lagrange)
+        .map(|(x, l)| *x * l)
+        .sum::<E::ScalarField>();
+
+    let alpha_2 = alpha.square();
+    let a_term = proof.a_eval + beta * proof.sigma_1_eval + gamma;
+    let b_term = proof.b_eval + beta * proof.sigma_2_eval + gamma;
+
+    // The constant term of the linearisation polynomial.
+    let r_0 =
+        pi - l_1 * alpha_2 - alpha * a_term * b_term * (proof.c_eval + gamma) * proof.z_omega_eval;
+
+    // The commitment to the linearisation polynomial without its constant term, plus u * [z].
+    let z_scalar = alpha
+        * (proof.a_eval + beta * zeta + gamma)
+        * (proof.b_eval + beta * vk.k1 * zeta + gamma)
+        * (proof.c_eval + beta * vk.k2 * zeta + gamma)
+        + l_1 * alpha_2
+        + u;
+    let sigma_3_scalar = -alpha * a_term * b_term * beta * proof.z_omega_eval;
+    let v_powers = (0..5)
+        .scan(E::ScalarField::one(), |acc, _| {
+            *acc *= v;
+            Some(*acc)
+        })
+        .collect::<Vec<_>>();
+
+    // [F] - [E] + ζ [W_ζ] + uζω [W_ζω] where [F] = [D] + sum_i v^i [p_i] and
+    // [E] = (-r_0 + sum_i v^i p_i(ζ) + u z(ζω)) [1].
+    let e = -r_0
+        + v_powers[0] * proof.a_eval
+        + v_powers[1] * proof.b_eval
+        + v_powers[2] * proof.c_eval
+        + v_powers[3] * proof.sigma_1_eval
+        + v_powers[4] * proof.sigma_2_eval
+        + u * proof.z_omega_eval;
+    let bases = [
+        vk.q_m,
+        vk.q_l,
+        vk.q_r,
+        vk.q_o,
+        vk.q_c,
+        proof.z,
+        vk.sigma_3,
+        proof.t_lo,
+        proof.t_mid,
+        proof.t_hi,
+        proof.a,
+        proof.b,
+        proof.c,
+        vk.sigma_1,
+        vk.sigma_2,
+        E::G1Affine::generator(),
+        proof.w_zeta,
+        proof.w_zeta_omega,
+    ];
+    let scalars = [
+        proof.a_eval * proof.b_eval,
+        proof.a_eval,
+        proof.b_eval,
+        proof.c_eval,
+        E::ScalarField::one(),
+        z_scalar,
+        sigma_3_scalar,
+        -z_h,
+        -z_h * zeta_n,
+        -z_h * zeta_n.square(),
+        v_powers[0],
+        v_powers[1],
+        v_powers[2],
+        v_powers[3],
+        v_powers[4],
+        -e,
+        zeta,
+        u * zeta * pvk.domain.generator(),
+    ];
+    let rhs = E::G1::msm_unchecked(&bases, &scalars);
+    let lhs = proof.w_zeta.into_group() + proof.w_zeta_omega * u;
+
+    // e([W_ζ] + u [W_ζω], [x]) = e(rhs, [1]).
+    let result = E::multi_miller_loop(
+        [lhs.into_affine(), (-rhs).into_affine()],
+        [pvk.x_g2_prepared.clone(), pvk.g2_prepared.clone()],
+    );
+    Ok(E::final_exponentiation(result)
+        .map(|output| output.is_zero())
+        .unwrap_or(false))
+}
+
+/// The Fiat–Shamir challenges of a proof.
+pub(crate) struct Challenges<F> {
+    pub(crate) beta: F,
+    pub(crate) gamma: F,
+    pub(crate) alpha: F,
+    pub(crate) zeta: F,
+    pub(crate) v: F,
+    pub(crate) u: F,
+}
+
+impl<F: PrimeField> Challenges<F> {
+    /// Derive the challenges in the order the prover would receive them.
+    pub(crate) fn derive<E: Pairing<ScalarField = F>>(
+        vk_digest: &[u8; 32],
+        public_inputs: &[F],
+        proof: &Proof<E>,
+    ) -> Self {
+        let mut transcript = Transcript::new(vk_digest, public_inputs);
+        [proof.a, proof.b, proof.c]
+            .iter()
+            .for_each(|p| transcript.append(p));
+        let beta = transcript.challenge();
+        let gamma = transcript.challenge();
+        transcript.append(&proof.z);
+        let alpha = transcript.challenge();
+        [proof.t_lo, proof.t_mid, proof.t_hi]
+            .iter()
+            .for_each(|p| transcript.append(p));
+        let zeta = transcript.challenge();
+        [
+            proof.a_eval,
+            proof.b_eval,
+            proof.c_eval,
+            proof.sigma_1_eval,
+            proof.sigma_2_eval,
+            proof.z_omega_eval,
+        ]
+        .iter()
+        .for_each(|x| transcript.append(x));
+        let v = transcript.challenge();
+        [proof.w_zeta, proof.w_zeta_omega]
+            .iter()
+            .for_each(|p| transcript.append(p));
+        let u = transcript.challenge();
+        Self {
+            beta,
+            gamma,
+            alpha,
+            zeta,
+            v,
+            u,
+        }
+    }
+}
+
+/// A Fiat–Shamir transcript. Each challenge is the SHA-512 digest of the previous challenge and the
+/// messages appended since, reduced modulo the order of the field.
+pub(crate) struct Transcript(pub(crate) Vec<u8>);
+
+impl Transcript {
+    pub(crate) fn new<F: PrimeField>(vk_digest: &[u8; 32], public_inputs: &[F]) -> Self {
+        let mut transcript = Self([TRANSCRIPT_DST, vk_digest.as_slice()].concat());
+        transcript.append(&public_inputs.to_vec());
+        transcript
+    }
+
+    pub(crate) fn append<T: CanonicalSerialize>(&mut self, value: &T) {
+        value
+            .serialize_compressed(&mut self.0)
+            .expect("Writing to a vector never fails");
+    }
+
+    pub(crate) fn challenge<F: PrimeField>(&mut self) -> F {
+        let digest = Sha512::digest(&self.0).digest;
+        self.0 = digest.to_vec();
+        F::from_le_bytes_mod_order(&digest)
+    }
+}
+
+/// The digest of the serialization of a verifying key.
+pub(crate) fn vk_digest<E: Pairing>(vk: &VerifyingKey<E>) -> [u8; 32] {
+    let mut transcript = Transcript(Vec::new());
+    transcript.append(&(vk.domain_size as u64));
+    transcript.append(&(vk.num_public_inputs as u64));
+    transcript.append(&vk.k1);
+    transcript.append(&vk.k2);
+    [
+        vk.q_m, vk.q_l, vk.q_r, vk.q_o, vk.q_c, vk.sigma_1, vk.sigma_2, vk.sigma_3,
+    ]
+    .iter()
+    .for_each(|p| transcript.append(p));
+    transcript.append(&vk.x_g2);
+    Sha256::digest(&transcript.0).digest
+}
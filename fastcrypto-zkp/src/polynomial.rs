@@ -0,0 +1,391 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Univariate polynomials over FFT-friendly prime fields such as the scalar fields of BLS12-381
+//! (`ark_bls12_381::Fr`) and BN254 (`ark_bn254::Fr`).
+//!
+//! [Polynomial] stores coefficients in the monomial basis and supports evaluation, arithmetic,
+//! division with remainder and interpolation over arbitrary points. [Radix2EvaluationDomain] is
+//! the multiplicative subgroup of order `2^k` and converts between coefficients and evaluations
+//! over the domain in `O(n log n)` with a radix-2 FFT, which is also used to multiply large
+//! polynomials and to compute Lagrange coefficients over the domain.
+//!
+//! This module works with arkworks fields, as used by the Groth16 verifiers. The same operations
+//! for the scalars of fastcrypto's groups are in `fastcrypto::polynomial`.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto_zkp::polynomial::*;
+//! # use ark_bn254::Fr;
+//! let p = Polynomial::new(vec![Fr::from(1u64), Fr::from(2u64), Fr::from(3u64)]);
+//! let domain = Radix2EvaluationDomain::<Fr>::new(3).unwrap();
+//! assert_eq!(domain.size(), 4);
+//!
+//! let evaluations = domain.fft(p.coefficients()).unwrap();
+//! assert_eq!(evaluations[1], p.evaluate(&domain.element(1)));
+//! assert_eq!(Polynomial::new(domain.ifft(&evaluations).unwrap()), p);
+//! ```
+
+use ark_ff::{batch_inversion, FftField, One, Zero};
+use fastcrypto::error::FastCryptoError;
+use std::ops::{Add, Mul, Neg, Sub};
+
+#[cfg(test)]
+#[path = "unit_tests/polynomial_tests.rs"]
+mod polynomial_tests;
+
+/// Products of polynomials where both have at least this many coefficients are computed using
+/// FFTs instead of schoolbook multiplication.
+const FFT_MULTIPLICATION_THRESHOLD: usize = 64;
+
+/// A univariate polynomial given by its coefficients, starting with the constant term. Trailing
+/// zero coefficients are removed, so the zero polynomial has no coefficients.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct Polynomial<F: FftField> {
+    coefficients: Vec<F>,
+}
+
+/// The multiplicative subgroup `{1, ω, ..., ω^(n-1)}` of order `n = 2^k` of a field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Radix2EvaluationDomain<F: FftField> {
+    size: usize,
+    generator: F,
+    generator_inverse: F,
+    size_inverse: F,
+}
+
+impl<F: FftField> Polynomial<F> {
+    /// Create a polynomial from its coefficients, starting with the constant term.
+    pub fn new(mut coefficients: Vec<F>) -> Self {
+        while coefficients.last().map_or(false, |c| c.is_zero()) {
+            coefficients.pop();
+        }
+        Self { coefficients }
+    }
+
+    /// The zero polynomial.
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    /// Returns true if this is the zero polynomial.
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.is_empty()
+    }
+
+    /// The coefficients of this polynomial, starting with the constant term.
+    pub fn coefficients(&self) -> &[F] {
+        &self.coefficients
+    }
+
+    /// The degree of this polynomial. The degree of the zero polynomial is defined to be zero.
+    pub fn degree(&self) -> usize {
+        self.coefficients.len().saturating_sub(1)
+    }
+
+    /// Evaluate this polynomial at `x` using Horner's method.
+    pub fn evaluate(&self, x: &F) -> F {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(F::zero(), |acc, c| acc * x + c)
+    }
+
+    /// Multiply this polynomial by a constant.
+    pub fn scale(&self, factor: &F) -> Self {
+        Self::new(self.coefficients.iter().map(|c| *c * factor).collect())
+    }
+
+    /// The polynomial `prod_i (X - roots[i])`.
+    pub fn from_roots(roots: &[F]) -> Self {
+        let mut coefficients = vec![F::one()];
+        for root in roots {
+            // Multiply by (X - root).
+            coefficients.push(F::zero());
+            for j in (0..coefficients.len() - 1).rev() {
+                let c = coefficients[j];
+                coefficients[j + 1] += c;
+                coefficients[j] = -c * root;
+            }
+        }
+        Self::new(coefficients)
+    }
+
+    /// Compute the quotient and remainder of the division of this polynomial by `divisor`.
+    pub fn div_rem(&self, divisor: &Self) -> Result<(Self, Self), FastCryptoError> {
+        let leading_inverse = divisor
+            .coefficients
+            .last()
+            .and_then(|c| c.inverse())
+            .ok_or(FastCryptoError::InvalidInput)?;
+        let degree = divisor.degree();
+        if self.coefficients.len() <= degree {
+            return Ok((Self::zero(), self.clone()));
+        }
+
+        let mut remainder = self.coefficients.clone();
+        let mut quotient = vec![F::zero(); remainder.len() - degree];
+        for i in (0..quotient.len()).rev() {
+            let c = remainder[i + degree] * leading_inverse;
+            quotient[i] = c;
+            for (j, d) in divisor.coefficients.iter().enumerate() {
+                remainder[i + j] -= c * d;
+            }
+        }
+        remainder.truncate(degree);
+        Ok((Self::new(quotient), Self::new(remainder)))
+    }
+
+    /// Compute the unique polynomial of degree less than `points.len()` which takes the value
+    /// `values[i]` at `points[i]` for all `i`. The points must be distinct.
+    pub fn interpolate(points: &[F], values: &[F]) -> Result<Self, FastCryptoError> {
+        if points.len() != values.len() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+
+        // The Lagrange polynomial for x_i is Z(X) / ((X - x_i) * Z'(x_i)) where Z vanishes on all
+        // points, and Z'(x_i) = prod_{j != i} (x_i - x_j).
+        let z = Self::from_roots(points);
+        let mut denominators = points
+            .iter()
+            .enumerate()
+            .map(|(i, x_i)| {
+                points
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .fold(F::one(), |acc, (_, x_j)| acc * (*x_i - x_j))
+            })
+            .collect::<Vec<_>>();
+        if denominators.iter().any(|d| d.is_zero()) {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        batch_inversion(&mut denominators);
+
+        let mut coefficients = vec![F::zero(); points.len()];
+        for ((x_i, v_i), d_i) in points.iter().zip(values).zip(denominators) {
+            let (numerator, _) = z
+                .div_rem(&Self::from_roots(&[*x_i]))
+                .expect("Divisor is non-zero");
+            let factor = *v_i * d_i;
+            coefficients
+                .iter_mut()
+                .zip(numerator.coefficients.iter())
+                .for_each(|(c, n)| *c += *n * factor);
+        }
+        Ok(Self::new(coefficients))
+    }
+
+    fn schoolbook_mul(&self, other: &Self) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return Self::zero();
+        }
+        let mut coefficients =
+            vec![F::zero(); self.coefficients.len() + other.coefficients.len() - 1];
+        for (i, a) in self.coefficients.iter().enumerate() {
+            for (j, b) in other.coefficients.iter().enumerate() {
+                coefficients[i + j] += *a * b;
+            }
+        }
+        Self::new(coefficients)
+    }
+
+    fn fft_mul(&self, other: &Self) -> Result<Self, FastCryptoError> {
+        let domain =
+            Radix2EvaluationDomain::new(self.coefficients.len() + other.coefficients.len() - 1)?;
+        let a = domain.fft(&self.coefficients)?;
+        let b = domain.fft(&other.coefficients)?;
+        let product = a
+            .iter()
+            .zip(b.iter())
+            .map(|(a, b)| *a * b)
+            .collect::<Vec<_>>();
+        Ok(Self::new(domain.ifft(&product)?))
+    }
+}
+
+impl<F: FftField> Add<&Polynomial<F>> for &Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn add(self, rhs: &Polynomial<F>) -> Polynomial<F> {
+        let (long, short) = if self.coefficients.len() >= rhs.coefficients.len() {
+            (self, rhs)
+        } else {
+            (rhs, self)
+        };
+        let mut coefficients = long.coefficients.clone();
+        coefficients
+            .iter_mut()
+            .zip(short.coefficients.iter())
+            .for_each(|(a, b)| *a += b);
+        Polynomial::new(coefficients)
+    }
+}
+
+impl<F: FftField> Neg for &Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn neg(self) -> Polynomial<F> {
+        Polynomial::new(self.coefficients.iter().map(|c| -*c).collect())
+    }
+}
+
+impl<F: FftField> Sub<&Polynomial<F>> for &Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn sub(self, rhs: &Polynomial<F>) -> Polynomial<F> {
+        self + &(-rhs)
+    }
+}
+
+impl<F: FftField> Mul<&Polynomial<F>> for &Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn mul(self, rhs: &Polynomial<F>) -> Polynomial<F> {
+        if self.coefficients.len().min(rhs.coefficients.len()) < FFT_MULTIPLICATION_THRESHOLD {
+            return self.schoolbook_mul(rhs);
+        }
+        // Fall back to schoolbook multiplication if the field has no large enough domain.
+        self.fft_mul(rhs)
+            .unwrap_or_else(|_| self.schoolbook_mul(rhs))
+    }
+}
+
+impl<F: FftField> Radix2EvaluationDomain<F> {
+    /// Create the smallest domain with at least `min_size` elements. Fails if `min_size` is zero
+    /// or if the field has no subgroup of the required size.
+    pub fn new(min_size: usize) -> Result<Self, FastCryptoError> {
+        let size = min_size
+            .checked_next_power_of_two()
+            .ok_or(FastCryptoError::InvalidInput)?;
+        if min_size == 0 || size.trailing_zeros() > F::TWO_ADICITY {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let generator = F::get_root_of_unity(size as u64).ok_or(FastCryptoError::InvalidInput)?;
+        Ok(Self {
+            size,
+            generator,
+            generator_inverse: generator.inverse().expect("Roots of unity are non-zero"),
+            size_inverse: F::from(size as u64)
+                .inverse()
+                .expect("The size is smaller than the characteristic"),
+        })
+    }
+
+    /// The number of elements in the domain.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The generator `ω` of the domain.
+    pub fn generator(&self) -> F {
+        self.generator
+    }
+
+    /// The element `ω^i`.
+    pub fn element(&self, i: usize) -> F {
+        self.generator.pow([i as u64])
+    }
+
+    /// All elements `1, ω, ..., ω^(n-1)` of the domain.
+    pub fn elements(&self) -> Vec<F> {
+        powers(self.generator, self.size)
+    }
+
+    /// Evaluate the polynomial with the given coefficients at all elements of the domain. There
+    /// must be at most [Self::size] coefficients.
+    pub fn fft(&self, coefficients: &[F]) -> Result<Vec<F>, FastCryptoError> {
+        if coefficients.len() > self.size {
+            return Err(FastCryptoError::InputTooLong(self.size));
+        }
+        let mut values = coefficients.to_vec();
+        values.resize(self.size, F::zero());
+        fft_in_place(&mut values, self.generator);
+        Ok(values)
+    }
+
+    /// Compute the coefficients of the polynomial of degree less than [Self::size] taking the given
+    /// values at the elements of the domain. Missing values are taken to be zero.
+    pub fn ifft(&self, evaluations: &[F]) -> Result<Vec<F>, FastCryptoError> {
+        if evaluations.len() > self.size {
+            return Err(FastCryptoError::InputTooLong(self.size));
+        }
+        let mut values = evaluations.to_vec();
+        values.resize(self.size, F::zero());
+        fft_in_place(&mut values, self.generator_inverse);
+        values.iter_mut().for_each(|v| *v *= self.size_inverse);
+        Ok(values)
+    }
+
+    /// Evaluate the polynomial `X^n - 1` which vanishes on the domain at `x`.
+    pub fn evaluate_vanishing_polynomial(&self, x: &F) -> F {
+        x.pow([self.size as u64]) - F::one()
+    }
+
+    /// Compute the values `L_i(x)` of the Lagrange polynomials of the domain at `x`, such that
+    /// `p(x) = sum_i L_i(x) p(ω^i)` for any polynomial `p` of degree less than [Self::size].
+    pub fn lagrange_coefficients(&self, x: &F) -> Vec<F> {
+        let z = self.evaluate_vanishing_polynomial(x);
+        let elements = self.elements();
+        if z.is_zero() {
+            // x is in the domain.
+            return elements
+                .iter()
+                .map(|e| if e == x { F::one() } else { F::zero() })
+                .collect();
+        }
+
+        // L_i(x) = (x^n - 1) / n * ω^i / (x - ω^i).
+        let mut denominators = elements.iter().map(|e| *x - e).collect::<Vec<_>>();
+        batch_inversion(&mut denominators);
+        let factor = z * self.size_inverse;
+        elements
+            .iter()
+            .zip(denominators)
+            .map(|(e, d)| factor * e * d)
+            .collect()
+    }
+}
+
+/// The values `1, x, ..., x^(n-1)`.
+fn powers<F: FftField>(x: F, n: usize) -> Vec<F> {
+    let mut result = Vec::with_capacity(n);
+    let mut current = F::one();
+    for _ in 0..n {
+        result.push(current);
+        current *= x;
+    }
+    result
+}
+
+/// Iterative radix-2 Cooley-Tukey FFT overwriting `values` with `(sum_j values[j] root^(ij))_i`.
+/// The length of `values` must be a power of two and `root` must be a primitive root of unity of
+/// that order.
+fn fft_in_place<F: FftField>(values: &mut [F], root: F) {
+    let n = values.len();
+    debug_assert!(n.is_power_of_two());
+    if n == 1 {
+        return;
+    }
+    let log_n = n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - log_n);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    let mut half = 1;
+    while half < n {
+        let twiddles = powers(root.pow([(n / (2 * half)) as u64]), half);
+        for chunk in values.chunks_mut(2 * half) {
+            let (low, high) = chunk.split_at_mut(half);
+            for ((a, b), w) in low.iter_mut().zip(high.iter_mut()).zip(twiddles.iter()) {
+                let t = *b * w;
+                *b = *a - t;
+                *a += t;
+            }
+        }
+        half *= 2;
+    }
+}
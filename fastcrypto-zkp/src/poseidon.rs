@@ -0,0 +1,286 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The [Poseidon](https://eprint.iacr.org/2019/458.pdf) hash function over the scalar fields of
+//! BN254 and BLS12-381, which is cheap to compute in circuits and is therefore used to hash public
+//! inputs, commitments and nullifiers.
+//!
+//! The instances use the S-box `x^5`, 8 full rounds and the number of partial rounds recommended
+//! for 128 bit security, and hash between 1 and [MAX_INPUTS] field elements. Their round constants
+//! and MDS matrices are generated with the Grain LFSR exactly as by the reference implementation,
+//! so the BN254 instances agree with `Poseidon(n)` from circomlib and with circomlibjs. The
+//! BLS12-381 instances are the ones of the reference implementation for 255 bit fields. The
+//! parameters of an instance are generated the first time it is used.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto_zkp::poseidon::*;
+//! # use ark_bn254::Fr;
+//! # use std::str::FromStr;
+//! let digest = poseidon_bn254(&[Fr::from(1u64), Fr::from(2u64)]).unwrap();
+//! assert_eq!(
+//!     digest,
+//!     Fr::from_str(
+//!         "7853200120776062878684798364095072458815029376092732009249414926327459813530"
+//!     )
+//!     .unwrap()
+//! );
+//! ```
+
+use ark_ff::{BigInteger, PrimeField};
+use fastcrypto::error::FastCryptoError;
+use once_cell::sync::{Lazy, OnceCell};
+
+#[cfg(test)]
+#[path = "unit_tests/poseidon_tests.rs"]
+mod poseidon_tests;
+
+/// The largest number of field elements which can be hashed by the instances.
+pub const MAX_INPUTS: usize = 16;
+
+/// The number of full rounds of all instances.
+const FULL_ROUNDS: usize = 8;
+
+/// The number of partial rounds of the instances hashing 1, 2, ..., [MAX_INPUTS] inputs, i.e., with
+/// widths 2, 3, ..., [MAX_INPUTS] + 1. The bounds for 128 bit security give the same numbers for
+/// the 254 bit field of BN254 and the 255 bit field of BLS12-381.
+const PARTIAL_ROUNDS: [usize; MAX_INPUTS] = [
+    56, 57, 56, 60, 60, 63, 64, 63, 60, 66, 60, 65, 70, 60, 64, 68,
+];
+
+static BN254_INSTANCES: Lazy<Vec<OnceCell<Poseidon<ark_bn254::Fr>>>> =
+    Lazy::new(|| (0..MAX_INPUTS).map(|_| OnceCell::new()).collect());
+
+static BLS12381_INSTANCES: Lazy<Vec<OnceCell<Poseidon<ark_bls12_381::Fr>>>> =
+    Lazy::new(|| (0..MAX_INPUTS).map(|_| OnceCell::new()).collect());
+
+/// The Poseidon permutation of a fixed width with the S-box `x^5`, together with the sponge
+/// construction of circomlib with a single capacity element.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Poseidon<F: PrimeField> {
+    full_rounds: usize,
+    partial_rounds: usize,
+    /// The round constants, `width` for each round.
+    round_constants: Vec<F>,
+    mds: Vec<Vec<F>>,
+}
+
+impl<F: PrimeField> Poseidon<F> {
+    /// The instance with the given parameters whose constants are generated as by the reference
+    /// implementation. The MDS matrix is not checked for the weaknesses described in
+    /// <https://eprint.iacr.org/2020/500>, so this must only be used with parameters for which the
+    /// first generated matrix is known to be secure.
+    fn generate(width: usize, full_rounds: usize, partial_rounds: usize) -> Self {
+        let mut grain = GrainLfsr::new(
+            F::MODULUS_BIT_SIZE,
+            width as u32,
+            full_rounds as u32,
+            partial_rounds as u32,
+        );
+
+        // Round constants are sampled with rejection, but the MDS entries are reduced.
+        let round_constants = (0..(full_rounds + partial_rounds) * width)
+            .map(|_| loop {
+                if let Some(c) = F::from_bigint(grain.next_bigint::<F>()) {
+                    break c;
+                }
+            })
+            .collect();
+
+        // The Cauchy matrix 1 / (x_i + y_j) for distinct x_1, ..., x_t, y_1, ..., y_t.
+        let mds = loop {
+            let elements = (0..2 * width)
+                .map(|_| {
+                    F::from_be_bytes_mod_order(&BigInteger::to_bytes_be(&grain.next_bigint::<F>()))
+                })
+                .collect::<Vec<_>>();
+            if (1..elements.len()).any(|i| elements[..i].contains(&elements[i])) {
+                continue;
+            }
+            let (xs, ys) = elements.split_at(width);
+            let mds = xs
+                .iter()
+                .map(|x| ys.iter().map(|y| (*x + y).inverse()).collect())
+                .collect::<Option<Vec<Vec<F>>>>();
+            if let Some(mds) = mds {
+                break mds;
+            }
+        };
+
+        Self {
+            full_rounds,
+            partial_rounds,
+            round_constants,
+            mds,
+        }
+    }
+
+    /// The number of field elements the permutation acts on.
+    pub fn width(&self) -> usize {
+        self.mds.len()
+    }
+
+    pub(crate) fn full_rounds(&self) -> usize {
+        self.full_rounds
+    }
+
+    pub(crate) fn partial_rounds(&self) -> usize {
+        self.partial_rounds
+    }
+
+    pub(crate) fn round_constants(&self) -> &[F] {
+        &self.round_constants
+    }
+
+    pub(crate) fn mds(&self) -> &[Vec<F>] {
+        &self.mds
+    }
+
+    /// Apply the permutation to the given state. Fails if the state does not have [Self::width]
+    /// elements.
+    pub fn permute(&self, state: &mut [F]) -> Result<(), FastCryptoError> {
+        let width = self.width();
+        if state.len() != width {
+            return Err(FastCryptoError::InputLengthWrong(width));
+        }
+        let half_full_rounds = self.full_rounds / 2;
+        for (round, constants) in self.round_constants.chunks(width).enumerate() {
+            state.iter_mut().zip(constants).for_each(|(s, c)| *s += c);
+            if round < half_full_rounds || round >= half_full_rounds + self.partial_rounds {
+                state.iter_mut().for_each(|s| *s = sbox(*s));
+            } else {
+                state[0] = sbox(state[0]);
+            }
+            let mixed = self
+                .mds
+                .iter()
+                .map(|row| row.iter().zip(state.iter()).map(|(m, s)| *m * s).sum())
+                .collect::<Vec<F>>();
+            state.copy_from_slice(&mixed);
+        }
+        Ok(())
+    }
+
+    /// Hash exactly [Self::width] - 1 field elements as circomlib does, i.e., permute the inputs
+    /// after a zero capacity element and return the first element of the state.
+    pub fn hash(&self, inputs: &[F]) -> Result<F, FastCryptoError> {
+        if inputs.len() + 1 != self.width() {
+            return Err(FastCryptoError::InputLengthWrong(self.width() - 1));
+        }
+        let mut state = Vec::with_capacity(self.width());
+        state.push(F::zero());
+        state.extend_from_slice(inputs);
+        self.permute(&mut state)?;
+        Ok(state[0])
+    }
+}
+
+/// The instance over the BN254 scalar field hashing `num_inputs` elements, which is the same as
+/// `Poseidon(num_inputs)` from circomlib. Fails unless `1 <= num_inputs <= MAX_INPUTS`.
+pub fn bn254_instance(
+    num_inputs: usize,
+) -> Result<&'static Poseidon<ark_bn254::Fr>, FastCryptoError> {
+    instance(&BN254_INSTANCES, num_inputs)
+}
+
+/// The instance over the BLS12-381 scalar field hashing `num_inputs` elements. Fails unless
+/// `1 <= num_inputs <= MAX_INPUTS`.
+pub fn bls12381_instance(
+    num_inputs: usize,
+) -> Result<&'static Poseidon<ark_bls12_381::Fr>, FastCryptoError> {
+    instance(&BLS12381_INSTANCES, num_inputs)
+}
+
+/// Hash between 1 and [MAX_INPUTS] elements of the BN254 scalar field as circomlib does.
+pub fn poseidon_bn254(inputs: &[ark_bn254::Fr]) -> Result<ark_bn254::Fr, FastCryptoError> {
+    bn254_instance(inputs.len())?.hash(inputs)
+}
+
+/// Hash between 1 and [MAX_INPUTS] elements of the BLS12-381 scalar field.
+pub fn poseidon_bls12381(
+    inputs: &[ark_bls12_381::Fr],
+) -> Result<ark_bls12_381::Fr, FastCryptoError> {
+    bls12381_instance(inputs.len())?.hash(inputs)
+}
+
+fn instance<F: PrimeField>(
+    instances: &'static [OnceCell<Poseidon<F>>],
+    num_inputs: usize,
+) -> Result<&'static Poseidon<F>, FastCryptoError> {
+    if num_inputs == 0 {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    if num_inputs > MAX_INPUTS {
+        return Err(FastCryptoError::InputTooLong(MAX_INPUTS));
+    }
+    Ok(instances[num_inputs - 1].get_or_init(|| {
+        Poseidon::generate(num_inputs + 1, FULL_ROUNDS, PARTIAL_ROUNDS[num_inputs - 1])
+    }))
+}
+
+fn sbox<F: PrimeField>(x: F) -> F {
+    x.square().square() * x
+}
+
+/// The self-shrinking Grain LFSR from Appendix E of the Poseidon paper, which generates the round
+/// constants and the MDS matrix of an instance from its parameters.
+struct GrainLfsr {
+    /// The 80 bits of the state, with the oldest bit as the least significant bit.
+    state: u128,
+    field_size: u32,
+}
+
+impl GrainLfsr {
+    fn new(field_size: u32, width: u32, full_rounds: u32, partial_rounds: u32) -> Self {
+        // The state is initialized with the field type (1 for prime fields), the S-box type (0 for
+        // x^alpha), the parameters and 30 ones, each with the most significant bit first.
+        let mut state = 0u128;
+        let mut position = 0;
+        for (value, bits) in [
+            (1, 2),
+            (0, 4),
+            (field_size, 12),
+            (width, 12),
+            (full_rounds, 10),
+            (partial_rounds, 10),
+            ((1 << 30) - 1, 30),
+        ] {
+            for i in (0..bits).rev() {
+                state |= (((value >> i) & 1) as u128) << position;
+                position += 1;
+            }
+        }
+        let mut grain = Self { state, field_size };
+        for _ in 0..160 {
+            grain.update();
+        }
+        grain
+    }
+
+    fn update(&mut self) -> bool {
+        let s = self.state;
+        let bit = ((s >> 62) ^ (s >> 51) ^ (s >> 38) ^ (s >> 23) ^ (s >> 13) ^ s) & 1;
+        self.state = (s >> 1) | (bit << 79);
+        bit == 1
+    }
+
+    /// The next output bit. The bits are produced in pairs where the second bit is output only if
+    /// the first bit is set.
+    fn next_bit(&mut self) -> bool {
+        loop {
+            let keep = self.update();
+            let bit = self.update();
+            if keep {
+                return bit;
+            }
+        }
+    }
+
+    /// The integer given by the next `field_size` bits, with the most significant bit first.
+    fn next_bigint<F: PrimeField>(&mut self) -> F::BigInt {
+        let bits = (0..self.field_size)
+            .map(|_| self.next_bit())
+            .collect::<Vec<_>>();
+        <F::BigInt as BigInteger>::from_bits_be(&bits)
+    }
+}
@@ -0,0 +1,105 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A common interface of the verifiers of serialized proofs, so that callers can switch between
+//! proof systems by changing a type parameter rather than their integration.
+//!
+//! Each [ProofSystem] verifies a proof given the serialized verifying key, proof and public inputs.
+//! For Groth16 these are the compressed Arkworks encodings of the verifying key and the proof, and
+//! the concatenated 32 byte encodings of the public inputs as for `verify_groth16_in_bytes`. The
+//! STARK verifier of the `stark` module, which requires the `winterfell` feature, implements the
+//! same interface.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto_zkp::dummy_circuits::DummyCircuit;
+//! # use fastcrypto_zkp::proof_system::*;
+//! # use ark_bn254::{Bn254, Fr};
+//! # use ark_crypto_primitives::snark::SNARK;
+//! # use ark_ff::One;
+//! # use ark_groth16::Groth16;
+//! # use ark_serialize::CanonicalSerialize;
+//! # use ark_std::rand::thread_rng;
+//! let circuit = DummyCircuit::<Fr> {
+//!     a: Some(Fr::one()),
+//!     b: Some(Fr::one()),
+//!     num_variables: 4,
+//!     num_constraints: 10,
+//! };
+//! let (pk, vk) = Groth16::<Bn254>::circuit_specific_setup(circuit, &mut thread_rng()).unwrap();
+//! let proof = Groth16::<Bn254>::prove(&pk, circuit, &mut thread_rng()).unwrap();
+//!
+//! let mut vk_bytes = Vec::new();
+//! vk.serialize_compressed(&mut vk_bytes).unwrap();
+//! let mut proof_bytes = Vec::new();
+//! proof.serialize_compressed(&mut proof_bytes).unwrap();
+//! let mut public_inputs_bytes = Vec::new();
+//! Fr::one().serialize_compressed(&mut public_inputs_bytes).unwrap();
+//!
+//! fn check<P: ProofSystem>(vk: &[u8], proof: &[u8], public_inputs: &[u8]) -> bool {
+//!     P::verify(vk, proof, public_inputs).unwrap_or(false)
+//! }
+//! assert!(check::<Bn254Groth16>(&vk_bytes, &proof_bytes, &public_inputs_bytes));
+//! ```
+
+use crate::{bls12381, bn254};
+use fastcrypto::error::FastCryptoError;
+
+#[cfg(test)]
+#[path = "unit_tests/proof_system_tests.rs"]
+mod proof_system_tests;
+
+/// A verifier of serialized proofs.
+pub trait ProofSystem {
+    /// Verify a proof. Fails if the verifying key, the proof or the public inputs are malformed, and
+    /// returns `Ok(false)` if the proof is invalid.
+    fn verify(
+        vk_bytes: &[u8],
+        proof_bytes: &[u8],
+        public_inputs_bytes: &[u8],
+    ) -> Result<bool, FastCryptoError>;
+}
+
+/// Groth16 over BN254.
+#[derive(Clone, Copy, Debug)]
+pub struct Bn254Groth16;
+
+/// Groth16 over BLS12-381.
+#[derive(Clone, Copy, Debug)]
+pub struct Bls12381Groth16;
+
+impl ProofSystem for Bn254Groth16 {
+    fn verify(
+        vk_bytes: &[u8],
+        proof_bytes: &[u8],
+        public_inputs_bytes: &[u8],
+    ) -> Result<bool, FastCryptoError> {
+        let pvk = bn254::api::prepare_pvk_bytes(vk_bytes)?;
+        bn254::api::verify_groth16_in_bytes(
+            &pvk[0],
+            &pvk[1],
+            &pvk[2],
+            &pvk[3],
+            public_inputs_bytes,
+            proof_bytes,
+        )
+    }
+}
+
+impl ProofSystem for Bls12381Groth16 {
+    fn verify(
+        vk_bytes: &[u8],
+        proof_bytes: &[u8],
+        public_inputs_bytes: &[u8],
+    ) -> Result<bool, FastCryptoError> {
+        let pvk = bls12381::api::prepare_pvk_bytes(vk_bytes)?;
+        bls12381::api::verify_groth16_in_bytes(
+            &pvk[0],
+            &pvk[1],
+            &pvk[2],
+            &pvk[3],
+            public_inputs_bytes,
+            proof_bytes,
+        )
+    }
+}
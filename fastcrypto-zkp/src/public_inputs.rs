@@ -0,0 +1,127 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Parsing of Groth16 public inputs given as decimal or `0x`-prefixed hexadecimal strings, which is
+//! how snarkjs and other circom tooling emits them (e.g. in `public.json`), or as raw bytes with a
+//! fixed number of bytes per input, which is how they are typically passed to on-chain verifiers.
+//!
+//! Parsing is strict: Signs, whitespace and separators are rejected, and a value must be smaller
+//! than the modulus of the scalar field instead of being reduced, so every field element has
+//! exactly one accepted representation in each base, up to leading zeros. Byte encodings must have
+//! exactly [field_element_size] bytes per element.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto_zkp::public_inputs::{field_element_from_str, field_elements_from_bytes, Endianness};
+//! # use ark_bn254::Fr;
+//! let x: Fr = field_element_from_str("255").unwrap();
+//! assert_eq!(x, field_element_from_str::<Fr>("0xff").unwrap());
+//! assert!(field_element_from_str::<Fr>(
+//!     "21888242871839275222246405745257275088548364400416034343698204186575808495617"
+//! )
+//! .is_err());
+//!
+//! let mut bytes = [0u8; 64];
+//! bytes[0] = 0xff;
+//! bytes[32] = 1;
+//! let inputs: Vec<Fr> = field_elements_from_bytes(&bytes, Endianness::Little).unwrap();
+//! assert_eq!(inputs, vec![Fr::from(255u64), Fr::from(1u64)]);
+//! assert!(field_elements_from_bytes::<Fr>(&bytes[..63], Endianness::Little).is_err());
+//! ```
+
+use ark_ff::PrimeField;
+use fastcrypto::error::FastCryptoError;
+use fastcrypto::limits::{enforce, Limit};
+use num_bigint::BigUint;
+
+#[cfg(test)]
+#[path = "unit_tests/public_inputs_tests.rs"]
+mod public_inputs_tests;
+
+/// The maximal length of a string representation of a field element.
+pub const MAX_INPUT_LENGTH: usize = 128;
+
+/// Parse a field element from a decimal string or a `0x`-prefixed hexadecimal string. Fails if the
+/// string is empty, contains invalid characters or does not represent a value smaller than the
+/// modulus.
+pub fn field_element_from_str<F: PrimeField>(s: &str) -> Result<F, FastCryptoError> {
+    if s.len() > MAX_INPUT_LENGTH {
+        return Err(FastCryptoError::InputTooLong(MAX_INPUT_LENGTH));
+    }
+    let (digits, radix) = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(digits) => (digits, 16),
+        None => (s, 10),
+    };
+    // BigUint::parse_bytes also accepts a leading '+' and '_' as separators, so check the digits
+    // first.
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    let value =
+        BigUint::parse_bytes(digits.as_bytes(), radix).ok_or(FastCryptoError::InvalidInput)?;
+    let value = F::BigInt::try_from(value).map_err(|_| FastCryptoError::InvalidInput)?;
+    F::from_bigint(value).ok_or(FastCryptoError::InvalidInput)
+}
+
+/// Parse a list of field elements. See [field_element_from_str]. Fails if there are more elements
+/// than allowed by [Limit::PublicInputs].
+pub fn field_elements_from_strs<F: PrimeField, S: AsRef<str>>(
+    inputs: &[S],
+) -> Result<Vec<F>, FastCryptoError> {
+    enforce(Limit::PublicInputs, inputs.len())?;
+    inputs
+        .iter()
+        .map(|s| field_element_from_str(s.as_ref()))
+        .collect()
+}
+
+/// The byte order of an encoded field element.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Endianness {
+    /// Least significant byte first, as used by arkworks.
+    Little,
+    /// Most significant byte first, as used by the EVM and gnark.
+    Big,
+}
+
+/// The number of bytes of an encoded element of the given field.
+pub fn field_element_size<F: PrimeField>() -> usize {
+    (F::MODULUS_BIT_SIZE as usize + 7) / 8
+}
+
+/// Decode a field element from exactly [field_element_size] bytes. Fails if the length is wrong or
+/// if the value is not smaller than the modulus.
+pub fn field_element_from_bytes<F: PrimeField>(
+    bytes: &[u8],
+    endianness: Endianness,
+) -> Result<F, FastCryptoError> {
+    let size = field_element_size::<F>();
+    if bytes.len() != size {
+        return Err(FastCryptoError::InputLengthWrong(size));
+    }
+    let value = match endianness {
+        Endianness::Little => BigUint::from_bytes_le(bytes),
+        Endianness::Big => BigUint::from_bytes_be(bytes),
+    };
+    let value = F::BigInt::try_from(value).map_err(|_| FastCryptoError::InvalidInput)?;
+    F::from_bigint(value).ok_or(FastCryptoError::InvalidInput)
+}
+
+/// Decode a list of field elements from the concatenation of their encodings. Fails if the length
+/// is not a multiple of [field_element_size], if any element is invalid, see
+/// [field_element_from_bytes], or if there are more elements than allowed by
+/// [Limit::PublicInputs].
+pub fn field_elements_from_bytes<F: PrimeField>(
+    bytes: &[u8],
+    endianness: Endianness,
+) -> Result<Vec<F>, FastCryptoError> {
+    let size = field_element_size::<F>();
+    if bytes.len() % size != 0 {
+        return Err(FastCryptoError::InputLengthWrong(size));
+    }
+    enforce(Limit::PublicInputs, bytes.len() / size)?;
+    bytes
+        .chunks_exact(size)
+        .map(|chunk| field_element_from_bytes(chunk, endianness))
+        .collect()
+}
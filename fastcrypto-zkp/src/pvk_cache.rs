@@ -0,0 +1,184 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A thread-safe cache of prepared verifying keys for services which verify proofs for many
+//! circuits, so that `process_vk_special` runs once per verifying key rather than once per proof.
+//!
+//! Keys are identified by their digest, see [crate::serialization]. A [PvkCache] holds at most a
+//! given number of prepared keys and evicts the least recently used key when it is full. Keys are
+//! prepared without holding the lock, so a slow preparation does not block lookups of other keys.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto_zkp::{bls12381::VerifyingKey, dummy_circuits::DummyCircuit, pvk_cache::*};
+//! # use ark_bls12_381::{Bls12_381, Fr};
+//! # use ark_crypto_primitives::snark::SNARK;
+//! # use ark_ff::One;
+//! # use ark_groth16::Groth16;
+//! # use ark_std::rand::thread_rng;
+//! let circuit = DummyCircuit::<Fr> {
+//!     a: Some(Fr::one()),
+//!     b: Some(Fr::one()),
+//!     num_variables: 4,
+//!     num_constraints: 10,
+//! };
+//! let (_, vk) = Groth16::<Bls12_381>::circuit_specific_setup(circuit, &mut thread_rng()).unwrap();
+//! let vk: VerifyingKey = vk.into();
+//!
+//! let cache = Bls12381PvkCache::new(100).unwrap();
+//! let pvk = cache.get_or_prepare(&vk);
+//! assert!(std::sync::Arc::ptr_eq(&pvk, &cache.get_or_prepare(&vk)));
+//! ```
+
+use crate::{bls12381, bn254};
+use fastcrypto::error::FastCryptoError;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+#[cfg(test)]
+#[path = "unit_tests/pvk_cache_tests.rs"]
+mod pvk_cache_tests;
+
+/// A verifying key which can be prepared for verification.
+pub trait PreparableVerifyingKey {
+    /// The prepared verifying key.
+    type Prepared;
+
+    /// The digest identifying this verifying key.
+    fn digest(&self) -> [u8; 32];
+
+    /// Prepare this verifying key, i.e., run `process_vk_special`.
+    fn prepare(&self) -> Self::Prepared;
+}
+
+impl PreparableVerifyingKey for bls12381::VerifyingKey {
+    type Prepared = bls12381::verifier::PreparedVerifyingKey;
+
+    fn digest(&self) -> [u8; 32] {
+        bls12381::VerifyingKey::digest(self)
+    }
+
+    fn prepare(&self) -> Self::Prepared {
+        bls12381::verifier::process_vk_special(self)
+    }
+}
+
+impl PreparableVerifyingKey for bn254::VerifyingKey {
+    type Prepared = bn254::verifier::PreparedVerifyingKey;
+
+    fn digest(&self) -> [u8; 32] {
+        bn254::VerifyingKey::digest(self)
+    }
+
+    fn prepare(&self) -> Self::Prepared {
+        bn254::verifier::process_vk_special(self)
+    }
+}
+
+/// A cache of prepared verifying keys over BLS12-381.
+pub type Bls12381PvkCache = PvkCache<bls12381::VerifyingKey>;
+
+/// A cache of prepared verifying keys over BN254.
+pub type Bn254PvkCache = PvkCache<bn254::VerifyingKey>;
+
+/// A least recently used cache of prepared verifying keys, keyed by the digests of the keys.
+#[derive(Debug)]
+pub struct PvkCache<K: PreparableVerifyingKey> {
+    capacity: usize,
+    state: Mutex<CacheState<K::Prepared>>,
+}
+
+#[derive(Debug)]
+struct CacheState<P> {
+    /// The prepared keys and the time they were last used.
+    entries: HashMap<[u8; 32], (Arc<P>, u64)>,
+    /// Incremented on every use of an entry.
+    clock: u64,
+}
+
+impl<P> CacheState<P> {
+    fn touch(&mut self, digest: &[u8; 32]) -> Option<Arc<P>> {
+        self.clock += 1;
+        let clock = self.clock;
+        self.entries.get_mut(digest).map(|(pvk, last_used)| {
+            *last_used = clock;
+            pvk.clone()
+        })
+    }
+}
+
+impl<K: PreparableVerifyingKey> PvkCache<K> {
+    /// An empty cache holding at most `capacity` prepared keys. Fails if the capacity is zero.
+    pub fn new(capacity: usize) -> Result<Self, FastCryptoError> {
+        if capacity == 0 {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(Self {
+            capacity,
+            state: Mutex::new(CacheState {
+                entries: HashMap::new(),
+                clock: 0,
+            }),
+        })
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The number of prepared keys in the cache.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The prepared key with the given digest, if it is in the cache.
+    pub fn get(&self, digest: &[u8; 32]) -> Option<Arc<K::Prepared>> {
+        self.lock().touch(digest)
+    }
+
+    /// The prepared key of the given verifying key, which is prepared and added to the cache if it
+    /// is not in the cache yet.
+    pub fn get_or_prepare(&self, vk: &K) -> Arc<K::Prepared> {
+        let digest = vk.digest();
+        if let Some(pvk) = self.get(&digest) {
+            return pvk;
+        }
+        let pvk = Arc::new(vk.prepare());
+
+        // Another thread may have added the key in the meantime, in which case its key is kept.
+        let mut state = self.lock();
+        if let Some(existing) = state.touch(&digest) {
+            return existing;
+        }
+        if state.entries.len() >= self.capacity {
+            let oldest = state
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(digest, _)| *digest)
+                .expect("The cache is not empty");
+            state.entries.remove(&oldest);
+        }
+        let clock = state.clock;
+        state.entries.insert(digest, (pvk.clone(), clock));
+        pvk
+    }
+
+    /// Remove the prepared key with the given digest and return it, if it is in the cache.
+    pub fn remove(&self, digest: &[u8; 32]) -> Option<Arc<K::Prepared>> {
+        self.lock().entries.remove(digest).map(|(pvk, _)| pvk)
+    }
+
+    /// Remove all prepared keys.
+    pub fn clear(&self) {
+        self.lock().entries.clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheState<K::Prepared>> {
+        self.state.lock().expect("Lock is not poisoned")
+    }
+}
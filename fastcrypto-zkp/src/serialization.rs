@@ -0,0 +1,148 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Canonical byte encodings of the proofs, verifying keys and prepared verifying keys of
+//! [crate::bls12381] and [crate::bn254], e.g. to persist prepared verifying keys on-chain and reload
+//! them without running `process_vk_special` again.
+//!
+//! An encoding is a version byte, currently [VERSION], followed by the compressed encodings of the
+//! fields. Every field has a fixed length, except for the points of the public inputs,
+//! `gamma_abc_g1`, which come last and take up the rest of the encoding. Decoding fails if the
+//! version is unknown, a point is not in the prime order subgroup or there are trailing bytes.
+//!
+//! The serde implementations of the types use these encodings, as Base64 for human readable formats
+//! and as raw bytes otherwise.
+//!
+//! Verifying keys are identified by their `digest`, which is the SHA-256 hash of a domain separation
+//! tag, the name of the curve and the encoding of the key. Since the encoding is canonical, equal
+//! keys have equal digests, so the digest can be used to key caches or on-chain registries.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto_zkp::{dummy_circuits::Fibonacci, bls12381::verifier::*};
+//! # use ark_bls12_381::{Bls12_381, Fr};
+//! # use ark_ff::One;
+//! # use ark_groth16::Groth16;
+//! # use ark_std::rand::thread_rng;
+//! let params = {
+//!     let c = Fibonacci::<Fr>::new(42, Fr::one(), Fr::one());
+//!     Groth16::<Bls12_381>::generate_random_parameters_with_reduction(c, &mut thread_rng()).unwrap()
+//! };
+//! let pvk = process_vk_special(&params.vk.into());
+//! let bytes = pvk.to_bytes();
+//! assert_eq!(pvk, PreparedVerifyingKey::from_bytes(&bytes).unwrap());
+//! ```
+
+use crate::inspect::Curve;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
+use fastcrypto::error::FastCryptoError;
+use fastcrypto::hash::{HashFunction, Sha256};
+
+#[cfg(test)]
+#[path = "unit_tests/serialization_tests.rs"]
+mod serialization_tests;
+
+/// The version of the encodings.
+pub const VERSION: u8 = 1;
+
+/// Domain separation tag of the digests of verifying keys.
+const VERIFYING_KEY_DIGEST_DST: &[u8] = b"fastcrypto-zkp-verifying-key-v1";
+
+/// Writes an encoding, starting with the version byte.
+pub(crate) struct Encoder(Vec<u8>);
+
+impl Encoder {
+    pub(crate) fn new() -> Self {
+        Self(vec![VERSION])
+    }
+
+    pub(crate) fn write<T: CanonicalSerialize>(&mut self, value: &T) {
+        value
+            .serialize_compressed(&mut self.0)
+            .expect("Writing to a vector never fails");
+    }
+
+    pub(crate) fn finish(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+/// Reads an encoding written by an [Encoder].
+pub(crate) struct Decoder<'a>(&'a [u8]);
+
+impl<'a> Decoder<'a> {
+    /// Check the version byte.
+    pub(crate) fn new(bytes: &'a [u8]) -> Result<Self, FastCryptoError> {
+        match bytes.split_first() {
+            Some((&VERSION, rest)) => Ok(Self(rest)),
+            _ => Err(FastCryptoError::InvalidInput),
+        }
+    }
+
+    pub(crate) fn read<T: CanonicalDeserialize>(&mut self) -> Result<T, FastCryptoError> {
+        T::deserialize_with_mode(&mut self.0, Compress::Yes, Validate::Yes)
+            .map_err(|_| FastCryptoError::InvalidInput)
+    }
+
+    /// Read values until the encoding is exhausted.
+    pub(crate) fn read_remaining<T: CanonicalDeserialize>(
+        mut self,
+    ) -> Result<Vec<T>, FastCryptoError> {
+        let mut values = Vec::new();
+        while !self.0.is_empty() {
+            values.push(self.read()?);
+        }
+        Ok(values)
+    }
+
+    /// Fail if there are trailing bytes.
+    pub(crate) fn finish(self) -> Result<(), FastCryptoError> {
+        match self.0.is_empty() {
+            true => Ok(()),
+            false => Err(FastCryptoError::InvalidInput),
+        }
+    }
+}
+
+/// The digest of a verifying key on the given curve from its encoding.
+pub(crate) fn verifying_key_digest(curve: Curve, encoding: &[u8]) -> [u8; 32] {
+    let mut hash = Sha256::new();
+    hash.update(VERIFYING_KEY_DIGEST_DST);
+    hash.update(curve.to_string().as_bytes());
+    hash.update(encoding);
+    hash.finalize().digest
+}
+
+/// Implement `Serialize` and `Deserialize` for a type with `to_bytes` and `from_bytes` functions.
+macro_rules! serde_with_to_from_bytes {
+    ($type:ty) => {
+        impl ::serde::Serialize for $type {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                use ::fastcrypto::encoding::{Base64, Encoding};
+                let bytes = self.to_bytes();
+                match serializer.is_human_readable() {
+                    true => serializer.serialize_str(&Base64::encode(bytes)),
+                    false => serializer.serialize_bytes(&bytes),
+                }
+            }
+        }
+
+        impl<'de> ::serde::Deserialize<'de> for $type {
+            fn deserialize<D: ::serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<Self, D::Error> {
+                use ::fastcrypto::encoding::{Base64, Encoding};
+                let bytes = match deserializer.is_human_readable() {
+                    true => {
+                        let s = <String as ::serde::Deserialize>::deserialize(deserializer)?;
+                        Base64::decode(&s).map_err(::serde::de::Error::custom)?
+                    }
+                    false => <Vec<u8> as ::serde::Deserialize>::deserialize(deserializer)?,
+                };
+                Self::from_bytes(&bytes).map_err(::serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+pub(crate) use serde_with_to_from_bytes;
@@ -0,0 +1,350 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Parsers for the JSON artifacts of Groth16 circuits produced by
+//! [snarkjs](https://github.com/iden3/snarkjs) and circom: `verification_key.json`, `proof.json` and
+//! `public.json`.
+//!
+//! Points are given as arrays of decimal coordinates `[x, y, z]` where `z` is `1` for affine points
+//! and `0` for the point at infinity, and elements of the quadratic extension field are given as
+//! `[c0, c1]`. All points must be on the curve and in the prime order subgroup. The `curve` field
+//! must be `bn128` for BN254 and `bls12381` for BLS12-381, and the `protocol` field must be
+//! `groth16`.
+//!
+//! The artifacts of the `fflonk` protocol of snarkjs, which only supports BN254, are parsed into
+//! the types of [crate::fflonk] instead.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto_zkp::snarkjs::*;
+//! let inputs = bn254_public_inputs_from_json(r#"["1", "33"]"#).unwrap();
+//! assert_eq!(inputs.len(), 2);
+//! assert!(bn254_public_inputs_from_json(r#"["-1"]"#).is_err());
+//! ```
+
+use crate::public_inputs::{field_element_from_str, field_elements_from_strs};
+use crate::{bls12381, bn254, fflonk};
+use ark_ec::pairing::Pairing;
+use ark_ec::short_weierstrass::{Affine, SWCurveConfig};
+use ark_ff::{One, PrimeField, QuadExtConfig, QuadExtField, Zero};
+use fastcrypto::error::FastCryptoError;
+use serde::Deserialize;
+
+#[cfg(test)]
+#[path = "unit_tests/snarkjs_tests.rs"]
+mod snarkjs_tests;
+
+/// The `curve` field of BN254 artifacts.
+const BN254_CURVE: &str = "bn128";
+
+/// The `curve` field of BLS12-381 artifacts.
+const BLS12381_CURVE: &str = "bls12381";
+
+/// The contents of `verification_key.json`. Other fields, e.g. `vk_alphabeta_12`, are ignored.
+#[derive(Deserialize)]
+struct SnarkjsVerifyingKey {
+    protocol: String,
+    curve: String,
+    #[serde(rename = "nPublic")]
+    n_public: usize,
+    vk_alpha_1: Vec<String>,
+    vk_beta_2: Vec<Vec<String>>,
+    vk_gamma_2: Vec<Vec<String>>,
+    vk_delta_2: Vec<Vec<String>>,
+    #[serde(rename = "IC")]
+    ic: Vec<Vec<String>>,
+}
+
+/// The contents of `proof.json`.
+#[derive(Deserialize)]
+struct SnarkjsProof {
+    protocol: String,
+    curve: String,
+    pi_a: Vec<String>,
+    pi_b: Vec<Vec<String>>,
+    pi_c: Vec<String>,
+}
+
+/// The contents of `verification_key.json` of an fflonk circuit.
+#[derive(Deserialize)]
+struct SnarkjsFflonkVerifyingKey {
+    protocol: String,
+    curve: String,
+    #[serde(rename = "nPublic")]
+    n_public: usize,
+    power: u32,
+    k1: String,
+    k2: String,
+    w: String,
+    w3: String,
+    w4: String,
+    w8: String,
+    wr: String,
+    #[serde(rename = "X_2")]
+    x_2: Vec<Vec<String>>,
+    #[serde(rename = "C0")]
+    c0: Vec<String>,
+}
+
+/// The contents of `proof.json` of an fflonk circuit. The `inv` evaluation is ignored.
+#[derive(Deserialize)]
+struct SnarkjsFflonkProof {
+    protocol: String,
+    curve: String,
+    polynomials: SnarkjsFflonkCommitments,
+    evaluations: SnarkjsFflonkEvaluations,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+struct SnarkjsFflonkCommitments {
+    c1: Vec<String>,
+    c2: Vec<String>,
+    w1: Vec<String>,
+    w2: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct SnarkjsFflonkEvaluations {
+    ql: String,
+    qr: String,
+    qm: String,
+    qo: String,
+    qc: String,
+    s1: String,
+    s2: String,
+    s3: String,
+    a: String,
+    b: String,
+    c: String,
+    z: String,
+    zw: String,
+    t1w: String,
+    t2w: String,
+}
+
+/// Parse the contents of a snarkjs `verification_key.json` for a BN254 circuit.
+pub fn bn254_verifying_key_from_json(json: &str) -> Result<bn254::VerifyingKey, FastCryptoError> {
+    verifying_key_from_json(json, BN254_CURVE).map(bn254::VerifyingKey)
+}
+
+/// Parse the contents of a snarkjs `proof.json` for a BN254 circuit.
+pub fn bn254_proof_from_json(json: &str) -> Result<bn254::Proof, FastCryptoError> {
+    proof_from_json(json, BN254_CURVE).map(bn254::Proof)
+}
+
+/// Parse the contents of a snarkjs `public.json` for a BN254 circuit.
+pub fn bn254_public_inputs_from_json(
+    json: &str,
+) -> Result<Vec<bn254::FieldElement>, FastCryptoError> {
+    Ok(public_inputs_from_json::<ark_bn254::Fr>(json)?
+        .into_iter()
+        .map(bn254::FieldElement)
+        .collect())
+}
+
+/// Parse the contents of a snarkjs `verification_key.json` for a BLS12-381 circuit.
+pub fn bls12381_verifying_key_from_json(
+    json: &str,
+) -> Result<bls12381::VerifyingKey, FastCryptoError> {
+    verifying_key_from_json(json, BLS12381_CURVE).map(bls12381::VerifyingKey)
+}
+
+/// Parse the contents of a snarkjs `proof.json` for a BLS12-381 circuit.
+pub fn bls12381_proof_from_json(json: &str) -> Result<bls12381::Proof, FastCryptoError> {
+    proof_from_json(json, BLS12381_CURVE).map(bls12381::Proof)
+}
+
+/// Parse the contents of a snarkjs `public.json` for a BLS12-381 circuit.
+pub fn bls12381_public_inputs_from_json(
+    json: &str,
+) -> Result<Vec<bls12381::FieldElement>, FastCryptoError> {
+    Ok(public_inputs_from_json::<ark_bls12_381::Fr>(json)?
+        .into_iter()
+        .map(bls12381::FieldElement)
+        .collect())
+}
+
+/// Parse the contents of a snarkjs `verification_key.json` for a BN254 fflonk circuit.
+pub fn bn254_fflonk_verifying_key_from_json(
+    json: &str,
+) -> Result<fflonk::VerifyingKey<ark_bn254::Bn254>, FastCryptoError> {
+    let vk: SnarkjsFflonkVerifyingKey =
+        serde_json::from_str(json).map_err(|_| FastCryptoError::InvalidInput)?;
+    if vk.protocol != "fflonk" || vk.curve != BN254_CURVE {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    Ok(fflonk::VerifyingKey {
+        power: vk.power,
+        num_public_inputs: vk.n_public,
+        k1: field_element_from_str(&vk.k1)?,
+        k2: field_element_from_str(&vk.k2)?,
+        w: field_element_from_str(&vk.w)?,
+        w3: field_element_from_str(&vk.w3)?,
+        w4: field_element_from_str(&vk.w4)?,
+        w8: field_element_from_str(&vk.w8)?,
+        wr: field_element_from_str(&vk.wr)?,
+        c0: g1_from_strs(&vk.c0)?,
+        x_g2: g2_from_strs(&vk.x_2)?,
+    })
+}
+
+/// Parse the contents of a snarkjs `proof.json` for a BN254 fflonk circuit.
+pub fn bn254_fflonk_proof_from_json(
+    json: &str,
+) -> Result<fflonk::Proof<ark_bn254::Bn254>, FastCryptoError> {
+    let proof: SnarkjsFflonkProof =
+        serde_json::from_str(json).map_err(|_| FastCryptoError::InvalidInput)?;
+    if proof.protocol != "fflonk" || proof.curve != BN254_CURVE {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    let (p, e) = (&proof.polynomials, &proof.evaluations);
+    Ok(fflonk::Proof {
+        c1: g1_from_strs(&p.c1)?,
+        c2: g1_from_strs(&p.c2)?,
+        w1: g1_from_strs(&p.w1)?,
+        w2: g1_from_strs(&p.w2)?,
+        q_l_eval: field_element_from_str(&e.ql)?,
+        q_r_eval: field_element_from_str(&e.qr)?,
+        q_m_eval: field_element_from_str(&e.qm)?,
+        q_o_eval: field_element_from_str(&e.qo)?,
+        q_c_eval: field_element_from_str(&e.qc)?,
+        sigma_1_eval: field_element_from_str(&e.s1)?,
+        sigma_2_eval: field_element_from_str(&e.s2)?,
+        sigma_3_eval: field_element_from_str(&e.s3)?,
+        a_eval: field_element_from_str(&e.a)?,
+        b_eval: field_element_from_str(&e.b)?,
+        c_eval: field_element_from_str(&e.c)?,
+        z_eval: field_element_from_str(&e.z)?,
+        z_omega_eval: field_element_from_str(&e.zw)?,
+        t1_omega_eval: field_element_from_str(&e.t1w)?,
+        t2_omega_eval: field_element_from_str(&e.t2w)?,
+    })
+}
+
+/// Parse the contents of a snarkjs `public.json` for a BN254 fflonk circuit.
+pub fn bn254_fflonk_public_inputs_from_json(
+    json: &str,
+) -> Result<Vec<ark_bn254::Fr>, FastCryptoError> {
+    public_inputs_from_json(json)
+}
+
+fn verifying_key_from_json<E, P1, P2, Q>(
+    json: &str,
+    curve: &str,
+) -> Result<ark_groth16::VerifyingKey<E>, FastCryptoError>
+where
+    E: Pairing<G1Affine = Affine<P1>, G2Affine = Affine<P2>>,
+    P1: SWCurveConfig,
+    P1::BaseField: PrimeField,
+    P2: SWCurveConfig<BaseField = QuadExtField<Q>>,
+    Q: QuadExtConfig,
+    Q::BaseField: PrimeField,
+{
+    let vk: SnarkjsVerifyingKey =
+        serde_json::from_str(json).map_err(|_| FastCryptoError::InvalidInput)?;
+    if vk.protocol != "groth16"
+        || vk.curve != curve
+        || vk.n_public.checked_add(1) != Some(vk.ic.len())
+    {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    Ok(ark_groth16::VerifyingKey {
+        alpha_g1: g1_from_strs(&vk.vk_alpha_1)?,
+        beta_g2: g2_from_strs(&vk.vk_beta_2)?,
+        gamma_g2: g2_from_strs(&vk.vk_gamma_2)?,
+        delta_g2: g2_from_strs(&vk.vk_delta_2)?,
+        gamma_abc_g1: vk
+            .ic
+            .iter()
+            .map(|p| g1_from_strs(p))
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+fn proof_from_json<E, P1, P2, Q>(
+    json: &str,
+    curve: &str,
+) -> Result<ark_groth16::Proof<E>, FastCryptoError>
+where
+    E: Pairing<G1Affine = Affine<P1>, G2Affine = Affine<P2>>,
+    P1: SWCurveConfig,
+    P1::BaseField: PrimeField,
+    P2: SWCurveConfig<BaseField = QuadExtField<Q>>,
+    Q: QuadExtConfig,
+    Q::BaseField: PrimeField,
+{
+    let proof: SnarkjsProof =
+        serde_json::from_str(json).map_err(|_| FastCryptoError::InvalidInput)?;
+    if proof.protocol != "groth16" || proof.curve != curve {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    Ok(ark_groth16::Proof {
+        a: g1_from_strs(&proof.pi_a)?,
+        b: g2_from_strs(&proof.pi_b)?,
+        c: g1_from_strs(&proof.pi_c)?,
+    })
+}
+
+fn public_inputs_from_json<F: PrimeField>(json: &str) -> Result<Vec<F>, FastCryptoError> {
+    let inputs: Vec<String> =
+        serde_json::from_str(json).map_err(|_| FastCryptoError::InvalidInput)?;
+    field_elements_from_strs(&inputs)
+}
+
+/// Parse a point from its coordinates `[x, y, z]`, where `z` must be zero or one.
+fn point_from_coordinates<P: SWCurveConfig>(
+    x: P::BaseField,
+    y: P::BaseField,
+    z: P::BaseField,
+) -> Result<Affine<P>, FastCryptoError> {
+    let point = if z.is_zero() {
+        Affine::identity()
+    } else if z.is_one() {
+        Affine::new_unchecked(x, y)
+    } else {
+        return Err(FastCryptoError::InvalidInput);
+    };
+    if !point.is_on_curve() || !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    Ok(point)
+}
+
+fn g1_from_strs<P>(coordinates: &[String]) -> Result<Affine<P>, FastCryptoError>
+where
+    P: SWCurveConfig,
+    P::BaseField: PrimeField,
+{
+    match coordinates {
+        [x, y, z] => point_from_coordinates(
+            field_element_from_str(x)?,
+            field_element_from_str(y)?,
+            field_element_from_str(z)?,
+        ),
+        _ => Err(FastCryptoError::InvalidInput),
+    }
+}
+
+fn g2_from_strs<P, Q>(coordinates: &[Vec<String>]) -> Result<Affine<P>, FastCryptoError>
+where
+    P: SWCurveConfig<BaseField = QuadExtField<Q>>,
+    Q: QuadExtConfig,
+    Q::BaseField: PrimeField,
+{
+    let fq2 = |c: &[String]| -> Result<QuadExtField<Q>, FastCryptoError> {
+        match c {
+            [c0, c1] => Ok(QuadExtField::new(
+                field_element_from_str(c0)?,
+                field_element_from_str(c1)?,
+            )),
+            _ => Err(FastCryptoError::InvalidInput),
+        }
+    };
+    match coordinates {
+        [x, y, z] => {
+            point_from_coordinates(fq2(x.as_slice())?, fq2(y.as_slice())?, fq2(z.as_slice())?)
+        }
+        _ => Err(FastCryptoError::InvalidInput),
+    }
+}
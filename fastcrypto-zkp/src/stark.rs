@@ -0,0 +1,71 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verification of STARK proofs created with [Winterfell](https://github.com/facebook/winterfell)
+//! behind the [ProofSystem] interface of the Groth16 verifiers. Requires the `winterfell` feature.
+//!
+//! The computation is given by its AIR, which takes the role of the circuit, and [Winterfell] is
+//! parameterized by the AIR and the hash function used by the prover. The serialized verifying key
+//! is the [ProofOptions] which the verifier accepts, since proofs created with weaker options must
+//! be rejected. Proofs are serialized with [StarkProof::to_bytes] and public inputs with
+//! [Serializable].
+
+use crate::proof_system::ProofSystem;
+use fastcrypto::error::FastCryptoError;
+use std::marker::PhantomData;
+use winterfell::crypto::{DefaultRandomCoin, ElementHasher};
+use winterfell::{AcceptableOptions, Air, Deserializable, ProofOptions, StarkProof, VerifierError};
+
+#[cfg(test)]
+#[path = "unit_tests/stark_tests.rs"]
+mod stark_tests;
+
+/// Winterfell proofs for the AIR `A` using the hash function `H`.
+#[derive(Debug)]
+pub struct Winterfell<A, H> {
+    _marker: PhantomData<(A, H)>,
+}
+
+impl<A, H> ProofSystem for Winterfell<A, H>
+where
+    A: Air,
+    A::PublicInputs: Deserializable,
+    H: ElementHasher<BaseField = A::BaseField>,
+{
+    fn verify(
+        vk_bytes: &[u8],
+        proof_bytes: &[u8],
+        public_inputs_bytes: &[u8],
+    ) -> Result<bool, FastCryptoError> {
+        verify::<A, H>(vk_bytes, proof_bytes, public_inputs_bytes)
+    }
+}
+
+/// Verify a serialized Winterfell proof, where `vk_bytes` are the serialized options which the
+/// proof must have been created with. Fails if an input cannot be deserialized, and returns
+/// `Ok(false)` if the proof is invalid.
+pub fn verify<A, H>(
+    vk_bytes: &[u8],
+    proof_bytes: &[u8],
+    public_inputs_bytes: &[u8],
+) -> Result<bool, FastCryptoError>
+where
+    A: Air,
+    A::PublicInputs: Deserializable,
+    H: ElementHasher<BaseField = A::BaseField>,
+{
+    let options =
+        ProofOptions::read_from_bytes(vk_bytes).map_err(|_| FastCryptoError::InvalidInput)?;
+    let proof = StarkProof::from_bytes(proof_bytes).map_err(|_| FastCryptoError::InvalidInput)?;
+    let public_inputs = A::PublicInputs::read_from_bytes(public_inputs_bytes)
+        .map_err(|_| FastCryptoError::InvalidInput)?;
+    match winterfell::verify::<A, H, DefaultRandomCoin<H>>(
+        proof,
+        public_inputs,
+        &AcceptableOptions::OptionSet(vec![options]),
+    ) {
+        Ok(()) => Ok(true),
+        Err(VerifierError::ProofDeserializationError(_)) => Err(FastCryptoError::InvalidInput),
+        Err(_) => Ok(false),
+    }
+}
@@ -0,0 +1,238 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Circuits, deterministic keys and proofs, and golden test vectors for testing code which verifies
+//! Groth16 proofs with this crate, e.g. the plumbing of an integration, without writing arkworks
+//! circuits:
+//! * [keypair] and [prove] generate keys and proofs deterministically from a seed, and
+//!   [TestVector::generate] does both for a [Shaped] circuit with random public inputs and a given
+//!   number of constraints, and [TestVector::dummy] for a small [DummyCircuit],
+//! * [golden_vectors] are fixed serialized verifying keys, proofs and public inputs together with
+//!   the expected result of their verification.
+//!
+//! The serialized values are in the formats of [crate::proof_system]. This module requires the
+//! `test_helpers` feature. Its keys must never be used in production since they are derived from
+//! public seeds or, for the golden vectors, from known scalars, so anyone can forge proofs for them.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto_zkp::inspect::Curve;
+//! # use fastcrypto_zkp::proof_system::{Bn254Groth16, ProofSystem};
+//! # use fastcrypto_zkp::test_helpers::*;
+//! # use ark_bn254::Bn254;
+//! let vector = TestVector::<Bn254>::generate(3, 10, 0).unwrap();
+//! assert!(Bn254Groth16::verify(
+//!     &vector.vk_bytes(),
+//!     &vector.proof_bytes(),
+//!     &vector.public_inputs_bytes()
+//! )
+//! .unwrap());
+//!
+//! for vector in golden_vectors(Curve::Bn254) {
+//!     let result = Bn254Groth16::verify(&vector.vk, &vector.proof, &vector.public_inputs);
+//!     assert_eq!(result.unwrap(), vector.valid);
+//! }
+//! ```
+
+use crate::bench_circuits::Shaped;
+use crate::dummy_circuits::DummyCircuit;
+use crate::inspect::Curve;
+use ark_crypto_primitives::snark::SNARK;
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_groth16::{Groth16, Proof, ProvingKey, VerifyingKey};
+use ark_relations::r1cs::ConstraintSynthesizer;
+use ark_serialize::CanonicalSerialize;
+use ark_std::rand::rngs::StdRng;
+use ark_std::rand::SeedableRng;
+use ark_std::UniformRand;
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::error::{FastCryptoError, FastCryptoResult};
+use std::ops::Neg;
+
+#[cfg(test)]
+#[path = "unit_tests/test_helpers_tests.rs"]
+mod test_helpers_tests;
+
+/// The compressed encodings of the generators and the negated G1 generator.
+const BN254_G1: &str = "0100000000000000000000000000000000000000000000000000000000000000";
+const BN254_NEG_G1: &str = "0100000000000000000000000000000000000000000000000000000000000080";
+const BN254_G2: &str = "edf692d95cbdde46ddda5ef7d422436779445c5e66006a42761e1f12efde0018\
+                        c212f3aeb785e49712e7a9353349aaf1255dfb31b7bf60723a480d9293938e19";
+const BLS12381_G1: &str = "97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac58\
+                           6c55e83ff97a1aeffb3af00adb22c6bb";
+const BLS12381_NEG_G1: &str = "b7f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac58\
+                               6c55e83ff97a1aeffb3af00adb22c6bb";
+const BLS12381_G2: &str = "93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049\
+                           334cf11213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051\
+                           c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8";
+
+/// Generate a Groth16 proving and verifying key for a circuit deterministically from a seed.
+pub fn keypair<E: Pairing, C: ConstraintSynthesizer<E::ScalarField>>(
+    circuit: C,
+    seed: u64,
+) -> FastCryptoResult<(ProvingKey<E>, VerifyingKey<E>)> {
+    Groth16::<E>::circuit_specific_setup(circuit, &mut StdRng::seed_from_u64(seed))
+        .map_err(|e| FastCryptoError::GeneralError(e.to_string()))
+}
+
+/// Generate a Groth16 proof for a circuit deterministically from a seed. Fails if the circuit is
+/// not satisfied.
+pub fn prove<E: Pairing, C: ConstraintSynthesizer<E::ScalarField>>(
+    proving_key: &ProvingKey<E>,
+    circuit: C,
+    seed: u64,
+) -> FastCryptoResult<Proof<E>> {
+    Groth16::<E>::prove(proving_key, circuit, &mut StdRng::seed_from_u64(seed))
+        .map_err(|e| FastCryptoError::GeneralError(e.to_string()))
+}
+
+/// The keys, public inputs and proof of a [Shaped] circuit.
+#[derive(Clone, Debug)]
+pub struct TestVector<E: Pairing> {
+    pub proving_key: ProvingKey<E>,
+    pub verifying_key: VerifyingKey<E>,
+    pub public_inputs: Vec<E::ScalarField>,
+    pub proof: Proof<E>,
+}
+
+impl<E: Pairing> TestVector<E> {
+    /// Generate the keys and a proof for a [Shaped] circuit with the given number of random public
+    /// inputs and constraints. The same arguments always give the same vector. Fails if there are
+    /// no constraints.
+    pub fn generate(
+        num_public_inputs: usize,
+        num_constraints: usize,
+        seed: u64,
+    ) -> FastCryptoResult<Self> {
+        let rng = &mut StdRng::seed_from_u64(seed);
+        let public_inputs = (0..num_public_inputs)
+            .map(|_| E::ScalarField::rand(rng))
+            .collect::<Vec<_>>();
+        let circuit = Shaped::new(public_inputs.clone(), num_constraints)?;
+        let (proving_key, verifying_key) = keypair::<E, _>(circuit.clone(), seed)?;
+        let proof = prove(&proving_key, circuit, seed)?;
+        Ok(Self {
+            proving_key,
+            verifying_key,
+            public_inputs,
+            proof,
+        })
+    }
+
+    /// Generate the keys and a proof for a [DummyCircuit] with four variables, ten constraints and
+    /// random witnesses `a` and `b`, whose only public input is `a * b`. The same seed always gives
+    /// the same vector.
+    pub fn dummy(seed: u64) -> FastCryptoResult<Self> {
+        let rng = &mut StdRng::seed_from_u64(seed);
+        let a = E::ScalarField::rand(rng);
+        let b = E::ScalarField::rand(rng);
+        let circuit = DummyCircuit {
+            a: Some(a),
+            b: Some(b),
+            num_variables: 4,
+            num_constraints: 10,
+        };
+        let (proving_key, verifying_key) = keypair::<E, _>(circuit, seed)?;
+        let proof = prove(&proving_key, circuit, seed)?;
+        Ok(Self {
+            proving_key,
+            verifying_key,
+            public_inputs: vec![a * b],
+            proof,
+        })
+    }
+
+    /// The compressed encoding of the verifying key.
+    pub fn vk_bytes(&self) -> Vec<u8> {
+        serialize(&self.verifying_key)
+    }
+
+    /// The compressed encodings of the points of the proof.
+    pub fn proof_bytes(&self) -> Vec<u8> {
+        serialize(&self.proof)
+    }
+
+    /// The concatenated 32 byte encodings of the public inputs.
+    pub fn public_inputs_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.public_inputs
+            .iter()
+            .for_each(|x| bytes.extend(serialize(x)));
+        bytes
+    }
+
+    /// A well-formed proof which is invalid for the public inputs of this vector.
+    pub fn invalid_proof(&self) -> Proof<E> {
+        Proof {
+            a: self.proof.a.into_group().neg().into_affine(),
+            ..self.proof.clone()
+        }
+    }
+}
+
+fn serialize<T: CanonicalSerialize>(value: &T) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    value
+        .serialize_compressed(&mut bytes)
+        .expect("Writing to a vector never fails");
+    bytes
+}
+
+/// A serialized verifying key, proof and public inputs, and whether the proof is valid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GoldenVector {
+    pub description: &'static str,
+    pub vk: Vec<u8>,
+    pub proof: Vec<u8>,
+    pub public_inputs: Vec<u8>,
+    pub valid: bool,
+}
+
+/// Golden vectors of a proof with one public input over the given curve, whose encodings do not
+/// depend on the random number generators used by arkworks. With the generators `g` of G1 and `h`
+/// of G2, the verifying key has `alpha = g`, `beta = gamma = delta = h` and `gamma_abc = [-g, g]`,
+/// and the proof `(a, b, c) = (g, h, -g)` is valid exactly for the public input `x = 2`: the
+/// verification equation `e(a, b) = e(alpha, beta) e(gamma_abc_0 + x gamma_abc_1, gamma) e(c, delta)`
+/// becomes `1 = 1 + (x - 1) - 1` in the exponents.
+pub fn golden_vectors(curve: Curve) -> Vec<GoldenVector> {
+    let decode = |s: &str| Hex::decode(s).expect("Valid hex");
+    let (g1, neg_g1, g2) = match curve {
+        Curve::Bn254 => (decode(BN254_G1), decode(BN254_NEG_G1), decode(BN254_G2)),
+        Curve::Bls12381 => (
+            decode(BLS12381_G1),
+            decode(BLS12381_NEG_G1),
+            decode(BLS12381_G2),
+        ),
+    };
+    let vk = [&g1[..], &g2, &g2, &g2, &2u64.to_le_bytes(), &neg_g1, &g1].concat();
+    let proof = |c: &[u8]| [&g1[..], &g2, c].concat();
+    let public_input = |x: u8| {
+        let mut bytes = vec![0u8; 32];
+        bytes[0] = x;
+        bytes
+    };
+    vec![
+        GoldenVector {
+            description: "valid proof",
+            vk: vk.clone(),
+            proof: proof(&neg_g1),
+            public_inputs: public_input(2),
+            valid: true,
+        },
+        GoldenVector {
+            description: "wrong public input",
+            vk: vk.clone(),
+            proof: proof(&neg_g1),
+            public_inputs: public_input(3),
+            valid: false,
+        },
+        GoldenVector {
+            description: "wrong proof",
+            vk,
+            proof: proof(&g1),
+            public_inputs: public_input(2),
+            valid: false,
+        },
+    ]
+}
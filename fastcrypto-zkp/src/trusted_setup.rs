@@ -0,0 +1,798 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verification of the contributions to powers-of-tau and Groth16 trusted setup ceremonies over
+//! BLS12-381, with the pairings computed by blst.
+//!
+//! A powers-of-tau ceremony computes an [Accumulator] with the powers `τ^i G1`, `τ^i G2`,
+//! `α τ^i G1` and `β τ^i G1` and `β G2` for secret `τ`, `α` and `β`. Each contributor multiplies
+//! the secrets by their own and publishes a [PublicKey] proving knowledge of them. The following
+//! files are supported:
+//! * The `challenge` and `response` files of the
+//!   [Zcash powers of tau](https://github.com/ebfull/powersoftau) ceremony, whose points use the
+//!   zcash encoding. See [Accumulator::from_ppot_challenge] and [Accumulator::from_ppot_response].
+//! * The `.ptau` files of [snarkjs](https://github.com/iden3/snarkjs), which contain the
+//!   accumulator and all contributions, including contributions imported from a ceremony as above.
+//!   See [Ptau].
+//! * The `.zkey` files of snarkjs with the Groth16 keys of a circuit and the contributions to the
+//!   second phase of the setup, which update `δ`. See [Zkey].
+//!
+//! All checks are ratio checks `e(a, B') == e(a', B)`, see [same_ratio]. The powers of an
+//! accumulator are checked using random linear combinations whose coefficients are derived by
+//! hashing the accumulator.
+//!
+//! The `g2_sp` points of the proofs of knowledge are derived from the transcript using a hash to G2
+//! specific to each implementation, which is not implemented here. The contributions to `τ` and `β`
+//! are instead checked against the ratios of the points in G2 before and after the contribution.
+//! The accumulator has no such points for `α`, so the contributions to `α` are not checked
+//! individually. For [Zkey], the hash to G2 must be given by the caller.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto_zkp::trusted_setup::*;
+//! # use ark_bls12_381::{G1Affine, G2Affine};
+//! # use ark_ec::AffineRepr;
+//! let g1 = G1Affine::generator();
+//! let g2 = G2Affine::generator();
+//! let g1_2 = (g1 * ark_bls12_381::Fr::from(2u64)).into();
+//! let g2_2 = (g2 * ark_bls12_381::Fr::from(2u64)).into();
+//! assert!(same_ratio((&g1, &g1_2), (&g2, &g2_2)));
+//! assert!(!same_ratio((&g1, &g1_2), (&g2, &g2)));
+//! assert!(Ptau::from_bytes(b"ptau").is_err());
+//! ```
+
+use crate::bls12381::conversions::{
+    bls_g1_affine_to_blst_g1_affine, bls_g2_affine_to_blst_g2_affine,
+    blst_g1_affine_to_bls_g1_affine, blst_g2_affine_to_bls_g2_affine,
+};
+use crate::bls12381::VerifyingKey;
+use ark_bls12_381::{Fq, Fq2, Fr, G1Affine, G2Affine};
+use ark_ec::short_weierstrass::{Affine, SWCurveConfig};
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{BigInteger, BigInteger384, PrimeField, Zero};
+use ark_serialize::CanonicalSerialize;
+use blake2::{Blake2b512, Digest};
+use blst::{
+    blst_fp12, blst_fp12_finalverify, blst_miller_loop, blst_p1_affine, blst_p1_affine_in_g1,
+    blst_p1_deserialize, blst_p2_affine, blst_p2_affine_in_g2, blst_p2_deserialize, BLST_ERROR,
+};
+use fastcrypto::error::FastCryptoError;
+use std::collections::BTreeMap;
+
+#[cfg(test)]
+#[path = "unit_tests/trusted_setup_tests.rs"]
+mod trusted_setup_tests;
+
+/// The largest supported power, i.e., accumulators have at most `2^MAX_POWER` powers in G2.
+pub const MAX_POWER: u32 = 28;
+
+/// The size of field elements of the base field.
+const FQ_SIZE: usize = 48;
+
+/// The size of field elements of the scalar field.
+const FR_SIZE: usize = 32;
+
+/// The size of the hashes in the files of the ceremonies.
+const HASH_SIZE: usize = 64;
+
+/// The size of the partial hash of the contributions in a `.ptau` file.
+const PARTIAL_HASH_SIZE: usize = 216;
+
+/// The flag of compressed points in the zcash encoding.
+const COMPRESSION_FLAG: u8 = 0x80;
+
+/// The domain separation tag of the coefficients of the random linear combinations.
+const COEFFICIENTS_DST: &[u8] = b"fastcrypto-zkp-trusted-setup-coefficients";
+
+/// The sections of `.ptau` files.
+const PTAU_HEADER: u32 = 1;
+const PTAU_TAU_G1: u32 = 2;
+const PTAU_TAU_G2: u32 = 3;
+const PTAU_ALPHA_TAU_G1: u32 = 4;
+const PTAU_BETA_TAU_G1: u32 = 5;
+const PTAU_BETA_G2: u32 = 6;
+const PTAU_CONTRIBUTIONS: u32 = 7;
+
+/// The sections of `.zkey` files.
+const ZKEY_HEADER: u32 = 1;
+const ZKEY_GROTH16_HEADER: u32 = 2;
+const ZKEY_IC: u32 = 3;
+const ZKEY_CONTRIBUTIONS: u32 = 10;
+
+/// The protocol of Groth16 keys in `.zkey` files.
+const ZKEY_GROTH16: u32 = 1;
+
+/// Returns true if `g1.1 / g1.0 == g2.1 / g2.0`, i.e., if `e(g1.0, g2.1) == e(g1.1, g2.0)`. Returns
+/// false if any of the points is the identity.
+pub fn same_ratio(g1: (&G1Affine, &G1Affine), g2: (&G2Affine, &G2Affine)) -> bool {
+    if g1.0.is_zero() || g1.1.is_zero() || g2.0.is_zero() || g2.1.is_zero() {
+        return false;
+    }
+    let mut lhs = blst_fp12::default();
+    let mut rhs = blst_fp12::default();
+    unsafe {
+        blst_miller_loop(
+            &mut lhs,
+            &bls_g2_affine_to_blst_g2_affine(g2.1),
+            &bls_g1_affine_to_blst_g1_affine(g1.0),
+        );
+        blst_miller_loop(
+            &mut rhs,
+            &bls_g2_affine_to_blst_g2_affine(g2.0),
+            &bls_g1_affine_to_blst_g1_affine(g1.1),
+        );
+        blst_fp12_finalverify(&lhs, &rhs)
+    }
+}
+
+/// The powers computed by a powers-of-tau ceremony for `2^power` powers in G2.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Accumulator {
+    /// `τ^i G1` for `i < 2^(power + 1) - 1`.
+    pub tau_g1: Vec<G1Affine>,
+    /// `τ^i G2` for `i < 2^power`.
+    pub tau_g2: Vec<G2Affine>,
+    /// `α τ^i G1` for `i < 2^power`.
+    pub alpha_tau_g1: Vec<G1Affine>,
+    /// `β τ^i G1` for `i < 2^power`.
+    pub beta_tau_g1: Vec<G1Affine>,
+    /// `β G2`.
+    pub beta_g2: G2Affine,
+}
+
+/// The points of an accumulator which are updated by a contribution, i.e., the first powers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccumulatorHead {
+    /// `τ G1`.
+    pub tau_g1: G1Affine,
+    /// `τ G2`.
+    pub tau_g2: G2Affine,
+    /// `α G1`.
+    pub alpha_g1: G1Affine,
+    /// `β G1`.
+    pub beta_g1: G1Affine,
+    /// `β G2`.
+    pub beta_g2: G2Affine,
+}
+
+/// A proof of knowledge of a secret `x` given by `s G1`, `x s G1` and `x sp G2`, where `sp G2` is
+/// derived from `s G1`, `x s G1` and the transcript of the ceremony.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContributionKey {
+    pub g1_s: G1Affine,
+    pub g1_sx: G1Affine,
+    pub g2_spx: G2Affine,
+}
+
+/// The proofs of knowledge of the secrets of a contribution to a powers-of-tau ceremony.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicKey {
+    pub tau: ContributionKey,
+    pub alpha: ContributionKey,
+    pub beta: ContributionKey,
+}
+
+impl AccumulatorHead {
+    /// The points before the first contribution, where all secrets are one.
+    pub fn initial() -> Self {
+        Self {
+            tau_g1: G1Affine::generator(),
+            tau_g2: G2Affine::generator(),
+            alpha_g1: G1Affine::generator(),
+            beta_g1: G1Affine::generator(),
+            beta_g2: G2Affine::generator(),
+        }
+    }
+}
+
+/// Returns true if `after` is `before` updated by the contribution with the given key. See the
+/// module documentation for what is checked.
+pub fn verify_transform(
+    before: &AccumulatorHead,
+    after: &AccumulatorHead,
+    key: &PublicKey,
+) -> bool {
+    same_ratio(
+        (&key.tau.g1_s, &key.tau.g1_sx),
+        (&before.tau_g2, &after.tau_g2),
+    ) && same_ratio(
+        (&before.tau_g1, &after.tau_g1),
+        (&before.tau_g2, &after.tau_g2),
+    ) && same_ratio(
+        (&key.beta.g1_s, &key.beta.g1_sx),
+        (&before.beta_g2, &after.beta_g2),
+    ) && same_ratio(
+        (&before.beta_g1, &after.beta_g1),
+        (&before.beta_g2, &after.beta_g2),
+    ) && !after.alpha_g1.is_zero()
+        && !key.alpha.g1_s.is_zero()
+        && !key.alpha.g1_sx.is_zero()
+}
+
+impl Accumulator {
+    /// The base two logarithm of the number of powers in G2. Fails if the accumulator does not have
+    /// the right number of points for any power.
+    pub fn power(&self) -> Result<u32, FastCryptoError> {
+        let n = self.tau_g2.len();
+        if !n.is_power_of_two()
+            || n < 2
+            || n > 1 << MAX_POWER
+            || self.tau_g1.len() != 2 * n - 1
+            || self.alpha_tau_g1.len() != n
+            || self.beta_tau_g1.len() != n
+        {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(n.trailing_zeros())
+    }
+
+    /// The first powers of the accumulator.
+    pub fn head(&self) -> Result<AccumulatorHead, FastCryptoError> {
+        self.power()?;
+        Ok(AccumulatorHead {
+            tau_g1: self.tau_g1[1],
+            tau_g2: self.tau_g2[1],
+            alpha_g1: self.alpha_tau_g1[0],
+            beta_g1: self.beta_tau_g1[0],
+            beta_g2: self.beta_g2,
+        })
+    }
+
+    /// Returns true if the points are the powers of some `τ`, `α` and `β` as described in
+    /// [Accumulator]. Fails if the accumulator has the wrong number of points.
+    pub fn verify(&self) -> Result<bool, FastCryptoError> {
+        self.power()?;
+        let seed = self.seed();
+        let (g1, tau_g1) = (&self.tau_g1[0], &self.tau_g1[1]);
+        let (g2, tau_g2) = (&self.tau_g2[0], &self.tau_g2[1]);
+        if *g1 != G1Affine::generator() || *g2 != G2Affine::generator() {
+            return Ok(false);
+        }
+        let (a, b) = consecutive_combinations(&self.tau_g1, &seed, 0);
+        if !same_ratio((&a, &b), (g2, tau_g2)) {
+            return Ok(false);
+        }
+        let (a, b) = consecutive_combinations(&self.tau_g2, &seed, 1);
+        if !same_ratio((g1, tau_g1), (&a, &b)) {
+            return Ok(false);
+        }
+        let (a, b) = consecutive_combinations(&self.alpha_tau_g1, &seed, 2);
+        if !same_ratio((&a, &b), (g2, tau_g2)) {
+            return Ok(false);
+        }
+        let (a, b) = consecutive_combinations(&self.beta_tau_g1, &seed, 3);
+        if !same_ratio((&a, &b), (g2, tau_g2)) {
+            return Ok(false);
+        }
+        Ok(same_ratio((g1, &self.beta_tau_g1[0]), (g2, &self.beta_g2)))
+    }
+
+    /// Parse a `challenge` file of a ceremony with `2^power` powers in G2, which is the hash of
+    /// the previous `response` file followed by the accumulator with uncompressed points.
+    pub fn from_ppot_challenge(
+        bytes: &[u8],
+        power: u32,
+    ) -> Result<([u8; HASH_SIZE], Self), FastCryptoError> {
+        let mut reader = Reader::new(bytes);
+        let hash = reader.read_hash()?;
+        let accumulator = reader.read_zcash_accumulator(power, false)?;
+        reader.finish()?;
+        Ok((hash, accumulator))
+    }
+
+    /// Parse a `response` file of a ceremony with `2^power` powers in G2, which is the hash of the
+    /// `challenge` file followed by the accumulator with compressed points and the public key of
+    /// the contribution.
+    pub fn from_ppot_response(
+        bytes: &[u8],
+        power: u32,
+    ) -> Result<([u8; HASH_SIZE], Self, PublicKey), FastCryptoError> {
+        let mut reader = Reader::new(bytes);
+        let hash = reader.read_hash()?;
+        let accumulator = reader.read_zcash_accumulator(power, true)?;
+        let g1 = (0..6)
+            .map(|_| reader.read_zcash_g1(false))
+            .collect::<Result<Vec<_>, _>>()?;
+        let g2 = (0..3)
+            .map(|_| reader.read_zcash_g2(false))
+            .collect::<Result<Vec<_>, _>>()?;
+        reader.finish()?;
+        Ok((hash, accumulator, public_key(&g1, &g2)))
+    }
+
+    /// A hash of all points, used to derive the coefficients of the random linear combinations.
+    fn seed(&self) -> [u8; HASH_SIZE] {
+        let mut bytes = Vec::new();
+        self.tau_g1
+            .serialize_compressed(&mut bytes)
+            .and_then(|_| self.tau_g2.serialize_compressed(&mut bytes))
+            .and_then(|_| self.alpha_tau_g1.serialize_compressed(&mut bytes))
+            .and_then(|_| self.beta_tau_g1.serialize_compressed(&mut bytes))
+            .and_then(|_| self.beta_g2.serialize_compressed(&mut bytes))
+            .expect("Serialization to a vector does not fail");
+        Blake2b512::new()
+            .chain_update(COEFFICIENTS_DST)
+            .chain_update(&bytes)
+            .finalize()
+            .as_slice()
+            .try_into()
+            .expect("Length is 64")
+    }
+}
+
+/// Random linear combinations `(sum r_i p_i, sum r_i p_{i+1})` of the points.
+fn consecutive_combinations<A: AffineRepr<ScalarField = Fr>>(
+    points: &[A],
+    seed: &[u8; HASH_SIZE],
+    label: u8,
+) -> (A, A) {
+    let coefficients = (0..points.len() - 1)
+        .map(|i| {
+            let hash = Blake2b512::new()
+                .chain_update(seed)
+                .chain_update([label])
+                .chain_update((i as u64).to_le_bytes())
+                .finalize();
+            Fr::from_le_bytes_mod_order(&hash)
+        })
+        .collect::<Vec<_>>();
+    let msm = |bases: &[A]| {
+        A::Group::msm(bases, &coefficients)
+            .expect("Lengths are equal")
+            .into_affine()
+    };
+    (msm(&points[..points.len() - 1]), msm(&points[1..]))
+}
+
+/// The public key given by the points `tau.g1_s, tau.g1_sx, alpha.g1_s, alpha.g1_sx, beta.g1_s,
+/// beta.g1_sx` and `tau.g2_spx, alpha.g2_spx, beta.g2_spx`.
+fn public_key(g1: &[G1Affine], g2: &[G2Affine]) -> PublicKey {
+    let key = |i: usize| ContributionKey {
+        g1_s: g1[2 * i],
+        g1_sx: g1[2 * i + 1],
+        g2_spx: g2[i],
+    };
+    PublicKey {
+        tau: key(0),
+        alpha: key(1),
+        beta: key(2),
+    }
+}
+
+/// A contribution to a powers-of-tau ceremony in a `.ptau` file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PtauContribution {
+    /// The first powers after the contribution.
+    pub after: AccumulatorHead,
+    pub key: PublicKey,
+    /// The hash of the challenge for the next contribution.
+    pub next_challenge: [u8; HASH_SIZE],
+    /// Zero for contributions with secrets chosen by the contributor, and one for random beacons.
+    pub contribution_type: u32,
+}
+
+/// The contents of a snarkjs `.ptau` file over BLS12-381.
+///
+/// The sections with the powers in Lagrange form, which are added by `snarkjs powersoftau
+/// prepare phase2`, are ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ptau {
+    /// The power of the accumulator in this file.
+    pub power: u32,
+    /// The power of the ceremony, which may be larger if the file has been truncated.
+    pub ceremony_power: u32,
+    pub accumulator: Accumulator,
+    pub contributions: Vec<PtauContribution>,
+}
+
+impl Ptau {
+    /// Parse a `.ptau` file. All points must be on the curve and in the prime order subgroup.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        let sections = read_sections(bytes, b"ptau")?;
+
+        let mut header = section(&sections, PTAU_HEADER)?;
+        header.read_field_modulus::<Fq>(FQ_SIZE)?;
+        let power = header.read_u32()?;
+        let ceremony_power = header.read_u32()?;
+        header.finish()?;
+        if power == 0 || power > MAX_POWER {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let n = 1usize << power;
+
+        let read_g1s = |section_type: u32, count: usize| -> Result<Vec<G1Affine>, _> {
+            let mut reader = section(&sections, section_type)?;
+            let points = (0..count)
+                .map(|_| reader.read_g1())
+                .collect::<Result<Vec<_>, _>>()?;
+            reader.finish()?;
+            Ok(points)
+        };
+        let mut tau_g2_reader = section(&sections, PTAU_TAU_G2)?;
+        let tau_g2 = (0..n)
+            .map(|_| tau_g2_reader.read_g2())
+            .collect::<Result<Vec<_>, _>>()?;
+        tau_g2_reader.finish()?;
+        let mut beta_g2_reader = section(&sections, PTAU_BETA_G2)?;
+        let beta_g2 = beta_g2_reader.read_g2()?;
+        beta_g2_reader.finish()?;
+        let accumulator = Accumulator {
+            tau_g1: read_g1s(PTAU_TAU_G1, 2 * n - 1)?,
+            tau_g2,
+            alpha_tau_g1: read_g1s(PTAU_ALPHA_TAU_G1, n)?,
+            beta_tau_g1: read_g1s(PTAU_BETA_TAU_G1, n)?,
+            beta_g2,
+        };
+
+        let mut reader = section(&sections, PTAU_CONTRIBUTIONS)?;
+        let contributions = (0..reader.read_u32()?)
+            .map(|_| reader.read_ptau_contribution())
+            .collect::<Result<Vec<_>, _>>()?;
+        reader.finish()?;
+
+        Ok(Self {
+            power,
+            ceremony_power,
+            accumulator,
+            contributions,
+        })
+    }
+
+    /// Returns true if the accumulator is valid, if each contribution is valid given the previous
+    /// one, and if the accumulator is the result of the last contribution. See the module
+    /// documentation for what is checked.
+    pub fn verify(&self) -> Result<bool, FastCryptoError> {
+        if !self.accumulator.verify()? {
+            return Ok(false);
+        }
+        let mut before = AccumulatorHead::initial();
+        for contribution in &self.contributions {
+            if !verify_transform(&before, &contribution.after, &contribution.key) {
+                return Ok(false);
+            }
+            before = contribution.after.clone();
+        }
+        Ok(before == self.accumulator.head()?)
+    }
+}
+
+/// A contribution to the second phase of a Groth16 setup in a `.zkey` file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZkeyContribution {
+    /// `δ G1` after the contribution.
+    pub delta_after: G1Affine,
+    pub key: ContributionKey,
+    /// The transcript from which `g2_sp` is derived.
+    pub transcript: [u8; HASH_SIZE],
+    /// Zero for contributions with secrets chosen by the contributor, and one for random beacons.
+    pub contribution_type: u32,
+}
+
+/// The verifying key and the contributions of a snarkjs `.zkey` file of a Groth16 circuit over
+/// BLS12-381. The sections with the proving key are ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Zkey {
+    pub verifying_key: VerifyingKey,
+    /// `δ G1`.
+    pub delta_g1: G1Affine,
+    /// The hash of the circuit.
+    pub circuit_hash: [u8; HASH_SIZE],
+    pub contributions: Vec<ZkeyContribution>,
+}
+
+impl Zkey {
+    /// Parse a `.zkey` file. All points must be on the curve and in the prime order subgroup.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        let sections = read_sections(bytes, b"zkey")?;
+
+        let mut header = section(&sections, ZKEY_HEADER)?;
+        if header.read_u32()? != ZKEY_GROTH16 {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        header.finish()?;
+
+        let mut header = section(&sections, ZKEY_GROTH16_HEADER)?;
+        header.read_field_modulus::<Fq>(FQ_SIZE)?;
+        header.read_field_modulus::<Fr>(FR_SIZE)?;
+        let _num_variables = header.read_u32()?;
+        let num_public_inputs = header.read_u32()? as usize;
+        let _domain_size = header.read_u32()?;
+        let alpha_g1 = header.read_g1()?;
+        let _beta_g1 = header.read_g1()?;
+        let beta_g2 = header.read_g2()?;
+        let gamma_g2 = header.read_g2()?;
+        let delta_g1 = header.read_g1()?;
+        let delta_g2 = header.read_g2()?;
+        header.finish()?;
+
+        let mut reader = section(&sections, ZKEY_IC)?;
+        let gamma_abc_g1 = (0..=num_public_inputs)
+            .map(|_| reader.read_g1())
+            .collect::<Result<Vec<_>, _>>()?;
+        reader.finish()?;
+
+        let mut reader = section(&sections, ZKEY_CONTRIBUTIONS)?;
+        let circuit_hash = reader.read_hash()?;
+        let contributions = (0..reader.read_u32()?)
+            .map(|_| reader.read_zkey_contribution())
+            .collect::<Result<Vec<_>, _>>()?;
+        reader.finish()?;
+
+        Ok(Self {
+            verifying_key: ark_groth16::VerifyingKey {
+                alpha_g1,
+                beta_g2,
+                gamma_g2,
+                delta_g2,
+                gamma_abc_g1,
+            }
+            .into(),
+            delta_g1,
+            circuit_hash,
+            contributions,
+        })
+    }
+
+    /// Returns true if each contribution updates `δ` by the secret of its proof of knowledge, and
+    /// if `δ G1` and `δ G2` are the result of the last contribution. The `g2_sp` point of each
+    /// proof of knowledge is computed from the transcript by the given function, which for snarkjs
+    /// is its `hashToG2`.
+    pub fn verify_contributions(&self, g2_sp: impl Fn(&[u8; HASH_SIZE]) -> G2Affine) -> bool {
+        let mut delta = G1Affine::generator();
+        for contribution in &self.contributions {
+            let sp = g2_sp(&contribution.transcript);
+            let key = &contribution.key;
+            if !same_ratio((&key.g1_s, &key.g1_sx), (&sp, &key.g2_spx))
+                || !same_ratio((&delta, &contribution.delta_after), (&sp, &key.g2_spx))
+            {
+                return false;
+            }
+            delta = contribution.delta_after;
+        }
+        delta == self.delta_g1
+            && same_ratio(
+                (&G1Affine::generator(), &self.delta_g1),
+                (&G2Affine::generator(), &self.verifying_key.0.delta_g2),
+            )
+    }
+}
+
+/// Split a binary file of snarkjs into its sections. The file starts with the four byte `magic`,
+/// the version and the number of sections, and each section is its type as four bytes, its size
+/// as eight bytes and its contents. All integers are little-endian.
+fn read_sections<'a>(
+    bytes: &'a [u8],
+    magic: &[u8; 4],
+) -> Result<BTreeMap<u32, &'a [u8]>, FastCryptoError> {
+    let mut reader = Reader::new(bytes);
+    if reader.take(4)? != magic || reader.read_u32()? != 1 {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    let mut sections = BTreeMap::new();
+    for _ in 0..reader.read_u32()? {
+        let section_type = reader.read_u32()?;
+        let size =
+            usize::try_from(reader.read_u64()?).map_err(|_| FastCryptoError::InvalidInput)?;
+        if sections.insert(section_type, reader.take(size)?).is_some() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+    }
+    reader.finish()?;
+    Ok(sections)
+}
+
+fn section<'a>(
+    sections: &BTreeMap<u32, &'a [u8]>,
+    section_type: u32,
+) -> Result<Reader<'a>, FastCryptoError> {
+    sections
+        .get(&section_type)
+        .map(|bytes| Reader::new(bytes))
+        .ok_or(FastCryptoError::InvalidInput)
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    fn take(&mut self, length: usize) -> Result<&'a [u8], FastCryptoError> {
+        if self.bytes.len() < length {
+            return Err(FastCryptoError::InputTooShort(length));
+        }
+        let (head, tail) = self.bytes.split_at(length);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn read_u32(&mut self) -> Result<u32, FastCryptoError> {
+        Ok(u32::from_le_bytes(
+            self.take(4)?.try_into().expect("Length is 4"),
+        ))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, FastCryptoError> {
+        Ok(u64::from_le_bytes(
+            self.take(8)?.try_into().expect("Length is 8"),
+        ))
+    }
+
+    fn read_hash(&mut self) -> Result<[u8; HASH_SIZE], FastCryptoError> {
+        Ok(self.take(HASH_SIZE)?.try_into().expect("Length is 64"))
+    }
+
+    /// Read the size of the field elements of `F` and its modulus, and check that they are the
+    /// expected ones.
+    fn read_field_modulus<F: PrimeField>(&mut self, size: usize) -> Result<(), FastCryptoError> {
+        if self.read_u32()? as usize != size || self.take(size)? != F::MODULUS.to_bytes_le() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(())
+    }
+
+    /// Read a field element in Montgomery form as used by snarkjs.
+    fn read_fq(&mut self) -> Result<Fq, FastCryptoError> {
+        let bytes = self.take(FQ_SIZE)?;
+        let limbs = core::array::from_fn(|i| {
+            u64::from_le_bytes(bytes[8 * i..8 * i + 8].try_into().expect("Length is 8"))
+        });
+        let montgomery = BigInteger384::new(limbs);
+        if montgomery >= Fq::MODULUS {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(Fq::new_unchecked(montgomery))
+    }
+
+    fn read_fq2(&mut self) -> Result<Fq2, FastCryptoError> {
+        Ok(Fq2::new(self.read_fq()?, self.read_fq()?))
+    }
+
+    /// Read a G1 point as its coordinates in Montgomery form, where the point at infinity is zeros.
+    fn read_g1(&mut self) -> Result<G1Affine, FastCryptoError> {
+        let (x, y) = (self.read_fq()?, self.read_fq()?);
+        check_point(if x.is_zero() && y.is_zero() {
+            G1Affine::identity()
+        } else {
+            G1Affine::new_unchecked(x, y)
+        })
+    }
+
+    /// Read a G2 point as its coordinates `x.c0, x.c1, y.c0, y.c1` in Montgomery form, where the
+    /// point at infinity is zeros.
+    fn read_g2(&mut self) -> Result<G2Affine, FastCryptoError> {
+        let (x, y) = (self.read_fq2()?, self.read_fq2()?);
+        check_point(if x.is_zero() && y.is_zero() {
+            G2Affine::identity()
+        } else {
+            G2Affine::new_unchecked(x, y)
+        })
+    }
+
+    /// Read contribution keys in the order `g1_s, g1_sx` of all keys followed by `g2_spx`.
+    fn read_public_key(&mut self) -> Result<PublicKey, FastCryptoError> {
+        let g1 = (0..6)
+            .map(|_| self.read_g1())
+            .collect::<Result<Vec<_>, _>>()?;
+        let g2 = (0..3)
+            .map(|_| self.read_g2())
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(public_key(&g1, &g2))
+    }
+
+    fn read_ptau_contribution(&mut self) -> Result<PtauContribution, FastCryptoError> {
+        let after = AccumulatorHead {
+            tau_g1: self.read_g1()?,
+            tau_g2: self.read_g2()?,
+            alpha_g1: self.read_g1()?,
+            beta_g1: self.read_g1()?,
+            beta_g2: self.read_g2()?,
+        };
+        let key = self.read_public_key()?;
+        let _partial_hash = self.take(PARTIAL_HASH_SIZE)?;
+        let next_challenge = self.read_hash()?;
+        let contribution_type = self.read_u32()?;
+        let params_length = self.read_u32()? as usize;
+        let _params = self.take(params_length)?;
+        Ok(PtauContribution {
+            after,
+            key,
+            next_challenge,
+            contribution_type,
+        })
+    }
+
+    fn read_zkey_contribution(&mut self) -> Result<ZkeyContribution, FastCryptoError> {
+        let delta_after = self.read_g1()?;
+        let key = ContributionKey {
+            g1_s: self.read_g1()?,
+            g1_sx: self.read_g1()?,
+            g2_spx: self.read_g2()?,
+        };
+        let transcript = self.read_hash()?;
+        let contribution_type = self.read_u32()?;
+        let params_length = self.read_u32()? as usize;
+        let _params = self.take(params_length)?;
+        Ok(ZkeyContribution {
+            delta_after,
+            key,
+            transcript,
+            contribution_type,
+        })
+    }
+
+    /// Read a G1 point in the zcash encoding.
+    fn read_zcash_g1(&mut self, compressed: bool) -> Result<G1Affine, FastCryptoError> {
+        let bytes = self.take(if compressed { FQ_SIZE } else { 2 * FQ_SIZE })?;
+        // blst reads the compression flag to decide how many bytes to read.
+        if (bytes[0] & COMPRESSION_FLAG != 0) != compressed {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let mut point = blst_p1_affine::default();
+        if unsafe { blst_p1_deserialize(&mut point, bytes.as_ptr()) } != BLST_ERROR::BLST_SUCCESS
+            || !unsafe { blst_p1_affine_in_g1(&point) }
+        {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(blst_g1_affine_to_bls_g1_affine(&point))
+    }
+
+    /// Read a G2 point in the zcash encoding.
+    fn read_zcash_g2(&mut self, compressed: bool) -> Result<G2Affine, FastCryptoError> {
+        let bytes = self.take(if compressed { 2 * FQ_SIZE } else { 4 * FQ_SIZE })?;
+        // blst reads the compression flag to decide how many bytes to read.
+        if (bytes[0] & COMPRESSION_FLAG != 0) != compressed {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let mut point = blst_p2_affine::default();
+        if unsafe { blst_p2_deserialize(&mut point, bytes.as_ptr()) } != BLST_ERROR::BLST_SUCCESS
+            || !unsafe { blst_p2_affine_in_g2(&point) }
+        {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(blst_g2_affine_to_bls_g2_affine(&point))
+    }
+
+    fn read_zcash_accumulator(
+        &mut self,
+        power: u32,
+        compressed: bool,
+    ) -> Result<Accumulator, FastCryptoError> {
+        if power == 0 || power > MAX_POWER {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let n = 1usize << power;
+        Ok(Accumulator {
+            tau_g1: (0..2 * n - 1)
+                .map(|_| self.read_zcash_g1(compressed))
+                .collect::<Result<Vec<_>, _>>()?,
+            tau_g2: (0..n)
+                .map(|_| self.read_zcash_g2(compressed))
+                .collect::<Result<Vec<_>, _>>()?,
+            alpha_tau_g1: (0..n)
+                .map(|_| self.read_zcash_g1(compressed))
+                .collect::<Result<Vec<_>, _>>()?,
+            beta_tau_g1: (0..n)
+                .map(|_| self.read_zcash_g1(compressed))
+                .collect::<Result<Vec<_>, _>>()?,
+            beta_g2: self.read_zcash_g2(compressed)?,
+        })
+    }
+
+    fn finish(self) -> Result<(), FastCryptoError> {
+        if !self.bytes.is_empty() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(())
+    }
+}
+
+fn check_point<P: SWCurveConfig>(point: Affine<P>) -> Result<Affine<P>, FastCryptoError> {
+    if !point.is_on_curve() || !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    Ok(point)
+}
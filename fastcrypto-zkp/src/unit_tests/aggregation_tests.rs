@@ -0,0 +1,141 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::aggregation::*;
+use crate::dummy_circuits::DummyCircuit;
+use ark_crypto_primitives::snark::SNARK;
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_groth16::{Groth16, Proof, VerifyingKey};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use ark_std::UniformRand;
+
+/// A verifying key and `n` proofs with their public inputs.
+fn proofs<E: Pairing>(
+    n: usize,
+    rng: &mut StdRng,
+) -> (VerifyingKey<E>, Vec<Proof<E>>, Vec<Vec<E::ScalarField>>) {
+    let circuit = |a: E::ScalarField, b: E::ScalarField| DummyCircuit::<E::ScalarField> {
+        a: Some(a),
+        b: Some(b),
+        num_variables: 4,
+        num_constraints: 10,
+    };
+    let (pk, vk) = Groth16::<E>::circuit_specific_setup(
+        circuit(E::ScalarField::rand(rng), E::ScalarField::rand(rng)),
+        rng,
+    )
+    .unwrap();
+    let mut proofs = Vec::new();
+    let mut inputs = Vec::new();
+    for _ in 0..n {
+        let (a, b) = (E::ScalarField::rand(rng), E::ScalarField::rand(rng));
+        proofs.push(Groth16::<E>::prove(&pk, circuit(a, b), rng).unwrap());
+        inputs.push(vec![a * b]);
+    }
+    (vk, proofs, inputs)
+}
+
+fn check_aggregation<E: Pairing>() {
+    let rng = &mut StdRng::from_seed([0; 32]);
+    let srs = ProverSrs::<E>::setup_insecure(8, rng).unwrap();
+    let verifier_srs = srs.verifier_srs();
+
+    for n in [2, 4, 8] {
+        let (vk, proofs, inputs) = proofs::<E>(n, rng);
+        let aggregate = aggregate_proofs(&srs, &vk, &inputs, &proofs).unwrap();
+        assert_eq!(aggregate.num_proofs(), n);
+        assert!(verify_aggregate_proof(&verifier_srs, &vk, &inputs, &aggregate).unwrap());
+
+        // Serialization.
+        let bytes = aggregate.to_bytes();
+        assert_eq!(AggregateProof::<E>::from_bytes(&bytes).unwrap(), aggregate);
+        assert!(AggregateProof::<E>::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(AggregateProof::<E>::from_bytes(&[bytes.as_slice(), &[0]].concat()).is_err());
+
+        // Wrong public inputs.
+        let mut wrong_inputs = inputs.clone();
+        wrong_inputs.swap(0, 1);
+        assert!(!verify_aggregate_proof(&verifier_srs, &vk, &wrong_inputs, &aggregate).unwrap());
+        assert!(verify_aggregate_proof(&verifier_srs, &vk, &inputs[1..], &aggregate).is_err());
+        let mut short_inputs = inputs.clone();
+        short_inputs[0].clear();
+        assert!(verify_aggregate_proof(&verifier_srs, &vk, &short_inputs, &aggregate).is_err());
+
+        // Tampered aggregates.
+        let mut tampered = aggregate.clone();
+        tampered.final_a = (tampered.final_a.into_group() + E::G1Affine::generator()).into_affine();
+        assert!(!verify_aggregate_proof(&verifier_srs, &vk, &inputs, &tampered).unwrap());
+        let mut tampered = aggregate.clone();
+        tampered.rounds[0].z_c_left =
+            (tampered.rounds[0].z_c_left.into_group() + E::G1Affine::generator()).into_affine();
+        assert!(!verify_aggregate_proof(&verifier_srs, &vk, &inputs, &tampered).unwrap());
+        let mut tampered = aggregate.clone();
+        tampered.opening_w.swap(0, 1);
+        assert!(!verify_aggregate_proof(&verifier_srs, &vk, &inputs, &tampered).unwrap());
+
+        // An aggregate including an invalid proof.
+        let mut invalid = proofs.clone();
+        invalid[n - 1].c = proofs[0].c;
+        let aggregate = aggregate_proofs(&srs, &vk, &inputs, &invalid).unwrap();
+        assert!(!verify_aggregate_proof(&verifier_srs, &vk, &inputs, &aggregate).unwrap());
+    }
+
+    // Invalid numbers of proofs.
+    let (vk, proofs, inputs) = proofs::<E>(16, rng);
+    for n in [0, 1, 3, 6, 16] {
+        assert!(aggregate_proofs(&srs, &vk, &inputs[..n], &proofs[..n]).is_err());
+    }
+    assert!(aggregate_proofs(&srs, &vk, &inputs[..2], &proofs[..4]).is_err());
+}
+
+#[test]
+fn test_bls12381_aggregation() {
+    check_aggregation::<ark_bls12_381::Bls12_381>();
+}
+
+#[test]
+fn test_bn254_aggregation() {
+    check_aggregation::<ark_bn254::Bn254>();
+}
+
+#[test]
+fn test_srs() {
+    let rng = &mut StdRng::from_seed([0; 32]);
+    assert!(ProverSrs::<ark_bn254::Bn254>::setup_insecure(0, rng).is_err());
+    assert!(ProverSrs::<ark_bn254::Bn254>::setup_insecure(1, rng).is_err());
+    assert!(ProverSrs::<ark_bn254::Bn254>::setup_insecure(6, rng).is_err());
+
+    let srs = ProverSrs::<ark_bn254::Bn254>::setup_insecure(4, rng).unwrap();
+    assert_eq!(srs.max_proofs(), 4);
+    let verifier_srs = srs.verifier_srs();
+    assert_eq!(verifier_srs.g, ark_bn254::G1Affine::generator());
+    assert_eq!(verifier_srs.h, ark_bn254::G2Affine::generator());
+
+    // The powers must have consistent lengths.
+    let g = vec![ark_bn254::G1Affine::generator(); 8];
+    let h = vec![ark_bn254::G2Affine::generator(); 4];
+    assert!(ProverSrs::<ark_bn254::Bn254>::new(g.clone(), g.clone(), h.clone(), h.clone()).is_ok());
+    assert!(
+        ProverSrs::<ark_bn254::Bn254>::new(g[1..].to_vec(), g.clone(), h.clone(), h.clone())
+            .is_err()
+    );
+    assert!(
+        ProverSrs::<ark_bn254::Bn254>::new(g.clone(), g.clone(), h[1..].to_vec(), h.clone())
+            .is_err()
+    );
+    assert!(ProverSrs::<ark_bn254::Bn254>::new(
+        g[..6].to_vec(),
+        g[..6].to_vec(),
+        h[..3].to_vec(),
+        h[..3].to_vec()
+    )
+    .is_err());
+    assert!(ProverSrs::<ark_bn254::Bn254>::new(
+        vec![ark_bn254::G1Affine::zero(); 8],
+        vec![ark_bn254::G1Affine::zero(); 8],
+        h.clone(),
+        h
+    )
+    .is_err());
+}
@@ -0,0 +1,109 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::async_verification::*;
+use crate::test_helpers::TestVector;
+use crate::{bls12381, bn254};
+use ark_serialize::CanonicalSerialize;
+use fastcrypto::error::FastCryptoError;
+use fastcrypto::verification_service::VerificationService;
+use std::sync::Arc;
+
+/// The compressed points of a proof, as expected by `verify_groth16_in_bytes`.
+fn proof_points_bytes<E: ark_ec::pairing::Pairing>(proof: &ark_groth16::Proof<E>) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    proof.a.serialize_compressed(&mut bytes).unwrap();
+    proof.b.serialize_compressed(&mut bytes).unwrap();
+    proof.c.serialize_compressed(&mut bytes).unwrap();
+    bytes
+}
+
+#[tokio::test]
+async fn test_bn254() {
+    let TestVector {
+        verifying_key: vk,
+        proof,
+        public_inputs,
+        ..
+    } = TestVector::<ark_bn254::Bn254>::dummy(0).unwrap();
+    let public_input = public_inputs[0];
+    let service = VerificationService::new(2);
+
+    let pvk = Arc::new(bn254::verifier::process_vk_special(&vk.into()));
+    assert!(bn254_verify_with_processed_vk(
+        &service,
+        pvk.clone(),
+        vec![public_input.into()],
+        proof.clone().into()
+    )
+    .await
+    .unwrap());
+    assert!(!bn254_verify_with_processed_vk(
+        &service,
+        pvk.clone(),
+        vec![(public_input + ark_bn254::Fr::from(1u64)).into()],
+        proof.clone().into()
+    )
+    .await
+    .unwrap());
+
+    let pvk_bytes = Arc::new(pvk.as_serialized().unwrap());
+    let mut public_input_bytes = Vec::new();
+    public_input
+        .serialize_compressed(&mut public_input_bytes)
+        .unwrap();
+    assert!(bn254_verify_groth16_in_bytes(
+        &service,
+        pvk_bytes.clone(),
+        public_input_bytes.clone(),
+        proof_points_bytes(&proof)
+    )
+    .await
+    .unwrap());
+    assert_eq!(
+        bn254_verify_groth16_in_bytes(
+            &service,
+            Arc::new(pvk_bytes[..3].to_vec()),
+            public_input_bytes,
+            proof_points_bytes(&proof)
+        )
+        .await,
+        Err(FastCryptoError::InputLengthWrong(4))
+    );
+}
+
+#[tokio::test]
+async fn test_bls12381() {
+    let TestVector {
+        verifying_key: vk,
+        proof,
+        public_inputs,
+        ..
+    } = TestVector::<ark_bls12_381::Bls12_381>::dummy(0).unwrap();
+    let public_input = public_inputs[0];
+    let service = VerificationService::new(2);
+
+    let pvk = Arc::new(bls12381::verifier::process_vk_special(&vk.into()));
+    assert!(bls12381_verify_with_processed_vk(
+        &service,
+        pvk.clone(),
+        vec![public_input.into()],
+        proof.clone().into()
+    )
+    .await
+    .unwrap());
+
+    let pvk_bytes = Arc::new(pvk.as_serialized().unwrap());
+    let mut public_input_bytes = Vec::new();
+    public_input
+        .serialize_compressed(&mut public_input_bytes)
+        .unwrap();
+    assert!(bls12381_verify_groth16_in_bytes(
+        &service,
+        pvk_bytes,
+        public_input_bytes,
+        proof_points_bytes(&proof)
+    )
+    .await
+    .unwrap());
+}
@@ -0,0 +1,111 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::bench_circuits::*;
+use crate::poseidon::{bls12381_instance, bn254_instance, Poseidon};
+use ark_bn254::Bn254;
+use ark_crypto_primitives::snark::SNARK;
+use ark_ff::{Field, PrimeField, UniformRand};
+use ark_groth16::Groth16;
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use fastcrypto::error::FastCryptoError;
+
+/// Check that the constraints are satisfied and that the instance is one followed by the public
+/// inputs.
+fn check_satisfied<F: PrimeField, C: ConstraintSynthesizer<F>>(circuit: C, public_inputs: &[F]) {
+    let cs = ConstraintSystem::<F>::new_ref();
+    circuit.generate_constraints(cs.clone()).unwrap();
+    assert!(cs.is_satisfied().unwrap());
+    let instance = cs.borrow().unwrap().instance_assignment.clone();
+    assert_eq!(instance[0], F::one());
+    assert_eq!(&instance[1..], public_inputs);
+}
+
+fn test_circuits<F: PrimeField>(poseidon: &Poseidon<F>) {
+    let mut rng = StdRng::from_seed([0; 32]);
+
+    let preimage = vec![F::rand(&mut rng), F::rand(&mut rng)];
+    let circuit = HashPreimage::new(poseidon, preimage.clone()).unwrap();
+    assert_eq!(
+        circuit.public_inputs(),
+        vec![poseidon.hash(&preimage).unwrap()]
+    );
+    check_satisfied(circuit.clone(), &circuit.public_inputs());
+
+    let circuit = MerkleMembership::random(poseidon, 3, &mut rng).unwrap();
+    check_satisfied(circuit.clone(), &circuit.public_inputs());
+
+    // The root of a tree with four leaves.
+    let leaves = (0..4).map(|_| F::rand(&mut rng)).collect::<Vec<_>>();
+    let left = poseidon.hash(&leaves[..2]).unwrap();
+    let right = poseidon.hash(&leaves[2..]).unwrap();
+    let root = poseidon.hash(&[left, right]).unwrap();
+    let circuit =
+        MerkleMembership::new(poseidon, leaves[2], vec![(leaves[3], false), (left, true)]).unwrap();
+    assert_eq!(circuit.public_inputs(), vec![root]);
+    check_satisfied(circuit, &[root]);
+
+    let circuit = Shaped::<F>::random(3, 10, &mut rng).unwrap();
+    let cs = ConstraintSystem::<F>::new_ref();
+    circuit.clone().generate_constraints(cs.clone()).unwrap();
+    assert!(cs.is_satisfied().unwrap());
+    assert_eq!(cs.num_constraints(), 10);
+    assert_eq!(cs.num_instance_variables(), 4);
+    check_satisfied(circuit.clone(), &circuit.public_inputs());
+
+    for (num_public_inputs, num_constraints) in [(0, 1), (1, 10), (3, 2)] {
+        let public_inputs = (0..num_public_inputs)
+            .map(|i| F::from(i as u64 + 2))
+            .collect::<Vec<_>>();
+        let circuit = Shaped::new(public_inputs.clone(), num_constraints).unwrap();
+        assert_eq!(circuit.public_inputs(), public_inputs);
+        let cs = ConstraintSystem::<F>::new_ref();
+        circuit.generate_constraints(cs.clone()).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+        assert_eq!(cs.num_constraints(), num_constraints);
+        assert_eq!(cs.num_instance_variables(), num_public_inputs + 1);
+    }
+
+    for (_, circuit) in corpus(poseidon, false, &mut rng).unwrap() {
+        if !matches!(circuit, BenchmarkCircuit::Shaped(_)) {
+            check_satisfied(circuit.clone(), &circuit.public_inputs());
+        }
+    }
+}
+
+#[test]
+fn test_bn254_circuits() {
+    test_circuits(bn254_instance(2).unwrap());
+}
+
+#[test]
+fn test_bls12381_circuits() {
+    test_circuits(bls12381_instance(2).unwrap());
+}
+
+#[test]
+fn test_invalid_parameters() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let poseidon = bn254_instance(3).unwrap();
+    assert_eq!(
+        HashPreimage::new(poseidon, vec![ark_bn254::Fr::from(1u64)]).unwrap_err(),
+        FastCryptoError::InputLengthWrong(3)
+    );
+    assert!(MerkleMembership::random(poseidon, 3, &mut rng).is_err());
+    assert!(Shaped::<ark_bn254::Fr>::random(0, 10, &mut rng).is_err());
+    assert!(Shaped::<ark_bn254::Fr>::random(1, 0, &mut rng).is_err());
+    assert!(Shaped::<ark_bn254::Fr>::new(vec![], 0).is_err());
+    assert!(corpus(poseidon, false, &mut rng).is_err());
+}
+
+#[test]
+fn test_groth16() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let circuit = MerkleMembership::random(bn254_instance(2).unwrap(), 2, &mut rng).unwrap();
+    let public_inputs = circuit.public_inputs();
+    let (pk, vk) = Groth16::<Bn254>::circuit_specific_setup(circuit.clone(), &mut rng).unwrap();
+    let proof = Groth16::<Bn254>::prove(&pk, circuit, &mut rng).unwrap();
+    assert!(Groth16::<Bn254>::verify(&vk, &public_inputs, &proof).unwrap());
+    assert!(!Groth16::<Bn254>::verify(&vk, &[public_inputs[0].double()], &proof).unwrap());
+}
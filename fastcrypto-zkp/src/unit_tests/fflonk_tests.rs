@@ -0,0 +1,323 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::fflonk::{
+    coset, process_vk_special, verify_with_processed_vk, Proof, Transcript, VerifyingKey,
+};
+use crate::plonk_circuit::{constant, sum, Circuit, Srs};
+use crate::polynomial::{Polynomial, Radix2EvaluationDomain};
+use ark_ec::pairing::Pairing;
+use ark_ec::short_weierstrass::{Affine, SWCurveConfig};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{FftField, Field, One, PrimeField, UniformRand, Zero};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+
+/// The polynomial `sum_i X^i p_i(X^k)` where `k` is the number of polynomials.
+fn combine<F: FftField>(polynomials: &[&Polynomial<F>]) -> Polynomial<F> {
+    let k = polynomials.len();
+    let length = polynomials
+        .iter()
+        .map(|p| p.coefficients().len())
+        .max()
+        .unwrap();
+    let mut coefficients = vec![F::zero(); k * length];
+    for (i, p) in polynomials.iter().enumerate() {
+        for (j, c) in p.coefficients().iter().enumerate() {
+            coefficients[j * k + i] = *c;
+        }
+    }
+    Polynomial::new(coefficients)
+}
+
+/// Divide `p` by `divisor` and check that the remainder is zero.
+fn divide<F: FftField>(p: &Polynomial<F>, divisor: &Polynomial<F>) -> Polynomial<F> {
+    let (quotient, remainder) = p.div_rem(divisor).unwrap();
+    assert!(remainder.is_zero());
+    quotient
+}
+
+/// The roots w3, w4, w8 and wr of the verifying key.
+fn roots<F: PrimeField>(circuit: &Circuit<F>) -> [F; 4] {
+    // w3 = (sqrt(-3) - 1) / 2 is a root of X^2 + X + 1, and ω^e is a cube root of ω for
+    // 3e = 1 mod n.
+    let w3 = ((-F::from(3u64)).sqrt().unwrap() - F::one()) / F::from(2u64);
+    let w4 = Radix2EvaluationDomain::<F>::new(4).unwrap().generator();
+    let w8 = Radix2EvaluationDomain::<F>::new(8).unwrap().generator();
+    let n = circuit.domain.size();
+    let e = (0..n).find(|e| 3 * e % n == 1).unwrap();
+    [w3, w4, w8, circuit.domain.element(e)]
+}
+
+/// The combined preprocessed polynomial C_0.
+fn c0<F: FftField>(circuit: &Circuit<F>) -> Polynomial<F> {
+    let s = &circuit.selectors;
+    let sigmas = &circuit.sigmas;
+    combine(&[
+        &s[1], &s[2], &s[3], &s[0], &s[4], &sigmas[0], &sigmas[1], &sigmas[2],
+    ])
+}
+
+fn setup<E: Pairing>(circuit: &Circuit<E::ScalarField>, srs: &Srs<E>) -> VerifyingKey<E> {
+    let [w3, w4, w8, wr] = roots(circuit);
+    VerifyingKey {
+        power: circuit.domain.size().trailing_zeros(),
+        num_public_inputs: 1,
+        k1: circuit.k[1],
+        k2: circuit.k[2],
+        w: circuit.domain.generator(),
+        w3,
+        w4,
+        w8,
+        wr,
+        c0: srs.commit(&c0(circuit)),
+        x_g2: srs.x_g2(),
+    }
+}
+
+/// Prove knowledge of `x` and `y` with `x * y = out`.
+fn prove<E, P>(
+    circuit: &Circuit<E::ScalarField>,
+    vk: &VerifyingKey<E>,
+    srs: &Srs<E>,
+    x: E::ScalarField,
+    y: E::ScalarField,
+) -> Proof<E>
+where
+    E: Pairing<G1Affine = Affine<P>>,
+    P: SWCurveConfig,
+    P::BaseField: PrimeField,
+{
+    type F<E> = <E as Pairing>::ScalarField;
+    let omega = circuit.domain.generator();
+    let out = x * y;
+    let vanishing = circuit.vanishing();
+    let s = &circuit.selectors;
+    let (s1, s2, s3) = (&circuit.sigmas[0], &circuit.sigmas[1], &circuit.sigmas[2]);
+
+    let mut transcript = Transcript::default();
+    transcript.append_point(&vk.c0);
+    transcript.append_field(&out);
+
+    // Round 1: the wires and the quotient of the gate.
+    let wire_values = circuit.wire_values(x, y);
+    let wires = wire_values
+        .iter()
+        .map(|values| circuit.interpolate(values))
+        .collect::<Vec<_>>();
+    let (a, b, c) = (&wires[0], &wires[1], &wires[2]);
+    let pi = circuit.interpolate(&[-out]);
+    let gate = sum(&[
+        &(a * b) * &s[0],
+        a * &s[1],
+        b * &s[2],
+        c * &s[3],
+        s[4].clone(),
+        pi,
+    ]);
+    let t0 = divide(&gate, &vanishing);
+    let c1 = combine(&[a, b, c, &t0]);
+    let c1_commitment = srs.commit(&c1);
+    transcript.append_point(&c1_commitment);
+    let beta: F<E> = transcript.challenge();
+    let gamma: F<E> = transcript.challenge();
+
+    // Round 2: the permutation polynomial and the quotients of the permutation argument.
+    let z = circuit.permutation(&wire_values, beta, gamma);
+    let (identity, permuted) = circuit.permutation_products(&wires, &z, beta, gamma);
+    let t1 = divide(
+        &(&(&z - &constant(F::<E>::one())) * &circuit.l_1()),
+        &vanishing,
+    );
+    let t2 = divide(&(&identity - &permuted), &vanishing);
+    let c2 = combine(&[&z, &t1, &t2]);
+    let c2_commitment = srs.commit(&c2);
+    transcript.append_point(&c2_commitment);
+    let xi_seed: F<E> = transcript.challenge();
+
+    // Round 3: the evaluations.
+    let [w3, w4, w8, wr] = roots(circuit);
+    let h0 = xi_seed.pow([3]);
+    let h1 = xi_seed.pow([6]);
+    let h2 = xi_seed.pow([8]);
+    let xi = xi_seed.pow([24]);
+    let proof_evals = [
+        s[1].evaluate(&xi),
+        s[2].evaluate(&xi),
+        s[0].evaluate(&xi),
+        s[3].evaluate(&xi),
+        s[4].evaluate(&xi),
+        s1.evaluate(&xi),
+        s2.evaluate(&xi),
+        s3.evaluate(&xi),
+        a.evaluate(&xi),
+        b.evaluate(&xi),
+        c.evaluate(&xi),
+        z.evaluate(&xi),
+        z.evaluate(&(xi * omega)),
+        t1.evaluate(&(xi * omega)),
+        t2.evaluate(&(xi * omega)),
+    ];
+    proof_evals.iter().for_each(|x| transcript.append_field(x));
+    let alpha: F<E> = transcript.challenge();
+
+    // Round 4: the first opening proof.
+    let c0 = c0(circuit);
+    let openings = [
+        (c0, coset(h0, w8, 8)),
+        (c1, coset(h1, w4, 4)),
+        (c2, [coset(h2, w3, 3), coset(h2 * wr, w3, 3)].concat()),
+    ];
+    let remainders = openings
+        .iter()
+        .map(|(p, points)| {
+            let values = points.iter().map(|h| p.evaluate(h)).collect::<Vec<_>>();
+            Polynomial::interpolate(points, &values).unwrap()
+        })
+        .collect::<Vec<_>>();
+    let alpha_powers = [F::<E>::one(), alpha, alpha.square()];
+    let w1 = sum(&openings
+        .iter()
+        .zip(&remainders)
+        .zip(&alpha_powers)
+        .map(|(((p, points), r), alpha_power)| {
+            divide(&(p - r), &Polynomial::from_roots(points)).scale(alpha_power)
+        })
+        .collect::<Vec<_>>());
+    let w1_commitment = srs.commit(&w1);
+    transcript.append_point(&w1_commitment);
+    let y: F<E> = transcript.challenge();
+
+    // Round 5: the second opening proof of
+    // L(X) = sum_i α^i Z_Si(y)^-1 Z_S0(y) (C_i(X) - r_i(y)) - Z_S0(y) W_1(X), which vanishes at y.
+    let z_s = openings
+        .iter()
+        .map(|(_, points)| points.iter().map(|h| y - h).product::<F<E>>())
+        .collect::<Vec<_>>();
+    let l = &sum(&openings
+        .iter()
+        .zip(&remainders)
+        .zip(&alpha_powers)
+        .zip(&z_s)
+        .map(|((((p, _), r), alpha_power), z_si)| {
+            (p - &constant(r.evaluate(&y))).scale(&(*alpha_power * z_s[0] / z_si))
+        })
+        .collect::<Vec<_>>())
+        - &w1.scale(&z_s[0]);
+    let w2 = divide(&l, &Polynomial::from_roots(&[y]));
+
+    Proof {
+        c1: c1_commitment,
+        c2: c2_commitment,
+        w1: w1_commitment,
+        w2: srs.commit(&w2),
+        q_l_eval: proof_evals[0],
+        q_r_eval: proof_evals[1],
+        q_m_eval: proof_evals[2],
+        q_o_eval: proof_evals[3],
+        q_c_eval: proof_evals[4],
+        sigma_1_eval: proof_evals[5],
+        sigma_2_eval: proof_evals[6],
+        sigma_3_eval: proof_evals[7],
+        a_eval: proof_evals[8],
+        b_eval: proof_evals[9],
+        c_eval: proof_evals[10],
+        z_eval: proof_evals[11],
+        z_omega_eval: proof_evals[12],
+        t1_omega_eval: proof_evals[13],
+        t2_omega_eval: proof_evals[14],
+    }
+}
+
+fn test_verify<E, P>()
+where
+    E: Pairing<G1Affine = Affine<P>>,
+    P: SWCurveConfig,
+    P::BaseField: PrimeField,
+{
+    let rng = &mut StdRng::from_seed([0; 32]);
+    let circuit = Circuit::<E::ScalarField>::new();
+    let srs = Srs::<E>::random(rng);
+    let vk = setup(&circuit, &srs);
+    let pvk = process_vk_special(&vk).unwrap();
+
+    let x = E::ScalarField::rand(rng);
+    let y = E::ScalarField::rand(rng);
+    let proof = prove(&circuit, &vk, &srs, x, y);
+    assert!(verify_with_processed_vk(&pvk, &[x * y], &proof).unwrap());
+
+    // Other public inputs.
+    assert!(!verify_with_processed_vk(&pvk, &[x * y + E::ScalarField::one()], &proof).unwrap());
+    assert!(verify_with_processed_vk(&pvk, &[], &proof).is_err());
+    assert!(verify_with_processed_vk(&pvk, &[x, y], &proof).is_err());
+
+    // Modified evaluations or commitments.
+    let mut modified = proof.clone();
+    modified.t2_omega_eval += E::ScalarField::one();
+    assert!(!verify_with_processed_vk(&pvk, &[x * y], &modified).unwrap());
+    let mut modified = proof.clone();
+    modified.q_m_eval = modified.q_o_eval;
+    assert!(!verify_with_processed_vk(&pvk, &[x * y], &modified).unwrap());
+    let mut modified = proof.clone();
+    modified.w2 = (modified.w2.into_group() + E::G1Affine::generator()).into_affine();
+    assert!(!verify_with_processed_vk(&pvk, &[x * y], &modified).unwrap());
+    let mut modified = proof.clone();
+    std::mem::swap(&mut modified.c1, &mut modified.c2);
+    assert!(!verify_with_processed_vk(&pvk, &[x * y], &modified).unwrap());
+
+    // Another verifying key.
+    let other_vk = setup::<E>(&circuit, &Srs::random(rng));
+    let other_pvk = process_vk_special(&other_vk).unwrap();
+    assert!(!verify_with_processed_vk(&other_pvk, &[x * y], &proof).unwrap());
+
+    // The roots of unity must have the right orders.
+    let mut invalid_vk = vk.clone();
+    invalid_vk.power = 0;
+    assert!(process_vk_special(&invalid_vk).is_err());
+    let mut invalid_vk = vk.clone();
+    invalid_vk.w = invalid_vk.w.square();
+    assert!(process_vk_special(&invalid_vk).is_err());
+    let mut invalid_vk = vk.clone();
+    invalid_vk.w3 = E::ScalarField::one();
+    assert!(process_vk_special(&invalid_vk).is_err());
+    let mut invalid_vk = vk.clone();
+    invalid_vk.w8 = invalid_vk.w4;
+    assert!(process_vk_special(&invalid_vk).is_err());
+    let mut invalid_vk = vk;
+    invalid_vk.wr *= invalid_vk.w;
+    assert!(process_vk_special(&invalid_vk).is_err());
+}
+
+#[test]
+fn test_verify_bn254() {
+    test_verify::<ark_bn254::Bn254, _>();
+}
+
+#[test]
+fn test_verify_bls12381() {
+    test_verify::<ark_bls12_381::Bls12_381, _>();
+}
+
+#[test]
+fn test_transcript() {
+    // Challenges are reduced Keccak-256 digests, and each challenge starts the next transcript.
+    let mut transcript = Transcript::default();
+    transcript.append_field(&ark_bn254::Fr::from(1u64));
+    let first: ark_bn254::Fr = transcript.challenge();
+    let second: ark_bn254::Fr = transcript.challenge();
+
+    let mut expected = Transcript::default();
+    expected.append_field(&first);
+    assert_eq!(expected.challenge::<ark_bn254::Fr>(), second);
+    assert_ne!(first, second);
+
+    // The point at infinity has zero coordinates.
+    let mut infinity = Transcript::default();
+    infinity.append_point(&ark_bn254::G1Affine::zero());
+    let mut zeros = Transcript::default();
+    zeros.append_field(&ark_bn254::Fq::zero());
+    zeros.append_field(&ark_bn254::Fq::zero());
+    assert_eq!(
+        infinity.challenge::<ark_bn254::Fr>(),
+        zeros.challenge::<ark_bn254::Fr>()
+    );
+}
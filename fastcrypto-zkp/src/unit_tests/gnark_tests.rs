@@ -0,0 +1,273 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::dummy_circuits::DummyCircuit;
+use crate::gnark::*;
+use crate::{bls12381, bn254};
+use ark_crypto_primitives::snark::SNARK;
+use ark_ec::pairing::Pairing;
+use ark_ec::short_weierstrass::{Affine, SWCurveConfig};
+use ark_ec::AffineRepr;
+use ark_ff::{Field, PrimeField, QuadExtConfig, QuadExtField};
+use ark_groth16::Groth16;
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use ark_std::UniformRand;
+use num_bigint::BigUint;
+use std::ops::{Mul, Neg};
+
+/// The flags for (uncompressed, compressed smallest, compressed largest, compressed infinity).
+const BN254_FLAGS: [u8; 4] = [0x00, 0x80, 0xc0, 0x40];
+const BLS12381_FLAGS: [u8; 4] = [0x00, 0x80, 0xa0, 0xc0];
+
+fn fq_bytes<F: PrimeField>(x: &F, size: usize) -> Vec<u8> {
+    let bytes = BigUint::from(x.into_bigint()).to_bytes_be();
+    let mut result = vec![0u8; size - bytes.len()];
+    result.extend_from_slice(&bytes);
+    result
+}
+
+/// Encode a point as gnark-crypto does, using the given encoding of its coordinates.
+fn point_bytes<P: SWCurveConfig>(
+    p: &Affine<P>,
+    compressed: bool,
+    flags: [u8; 4],
+    size: usize,
+    coordinate: impl Fn(&P::BaseField) -> Vec<u8>,
+) -> Vec<u8> {
+    let mut bytes = match p.xy() {
+        None if compressed => {
+            let mut bytes = vec![0u8; size];
+            bytes[0] = flags[3];
+            return bytes;
+        }
+        None => vec![0u8; 2 * size],
+        Some((x, y)) if compressed => {
+            let mut bytes = coordinate(x);
+            bytes[0] |= if *y > y.neg() { flags[2] } else { flags[1] };
+            bytes
+        }
+        Some((x, y)) => [coordinate(x), coordinate(y)].concat(),
+    };
+    bytes[0] |= flags[0];
+    bytes
+}
+
+fn g1_bytes<P>(p: &Affine<P>, compressed: bool, flags: [u8; 4], size: usize) -> Vec<u8>
+where
+    P: SWCurveConfig,
+    P::BaseField: PrimeField,
+{
+    point_bytes(p, compressed, flags, size, |x| fq_bytes(x, size))
+}
+
+fn g2_bytes<P, Q>(p: &Affine<P>, compressed: bool, flags: [u8; 4], size: usize) -> Vec<u8>
+where
+    P: SWCurveConfig<BaseField = QuadExtField<Q>>,
+    Q: QuadExtConfig,
+    Q::BaseField: PrimeField,
+{
+    point_bytes(p, compressed, flags, 2 * size, |x| {
+        [fq_bytes(&x.c1, size), fq_bytes(&x.c0, size)].concat()
+    })
+}
+
+/// A proof and verifying key in gnark's encoding, and the public input of the proof.
+struct Artifacts<E: Pairing> {
+    proof: Vec<u8>,
+    vk: Vec<u8>,
+    public_input: E::ScalarField,
+}
+
+fn artifacts<E, P1, P2, Q>(compressed: bool, flags: [u8; 4], size: usize) -> Artifacts<E>
+where
+    E: Pairing<G1Affine = Affine<P1>, G2Affine = Affine<P2>>,
+    P1: SWCurveConfig,
+    P1::BaseField: PrimeField,
+    P2: SWCurveConfig<BaseField = QuadExtField<Q>>,
+    Q: QuadExtConfig,
+    Q::BaseField: PrimeField,
+{
+    let rng = &mut StdRng::from_seed([0; 32]);
+    let c = DummyCircuit::<E::ScalarField> {
+        a: Some(E::ScalarField::rand(rng)),
+        b: Some(E::ScalarField::rand(rng)),
+        num_variables: 4,
+        num_constraints: 10,
+    };
+    let (pk, vk) = Groth16::<E>::circuit_specific_setup(c, rng).unwrap();
+    let proof = Groth16::<E>::prove(&pk, c, rng).unwrap();
+
+    let g1 = |p: &E::G1Affine| g1_bytes(p, compressed, flags, size);
+    let g2 = |p: &E::G2Affine| g2_bytes(p, compressed, flags, size);
+    let proof = [g1(&proof.a), g2(&proof.b), g1(&proof.c)].concat();
+    let mut vk_bytes = [
+        g1(&vk.alpha_g1),
+        g1(&pk.beta_g1),
+        g2(&vk.beta_g2),
+        g2(&vk.gamma_g2),
+        g1(&pk.delta_g1),
+        g2(&vk.delta_g2),
+        (vk.gamma_abc_g1.len() as u32).to_be_bytes().to_vec(),
+    ]
+    .concat();
+    for p in &vk.gamma_abc_g1 {
+        vk_bytes.extend(g1(p));
+    }
+    Artifacts {
+        proof,
+        vk: vk_bytes,
+        public_input: c.a.unwrap().mul(c.b.unwrap()),
+    }
+}
+
+#[test]
+fn test_bn254_generators() {
+    let mut g1 = [0u8; 32];
+    g1[0] = 0x80;
+    g1[31] = 1;
+    let mut proof = g1.to_vec();
+    proof.extend(g2_bytes(
+        &ark_bn254::G2Affine::generator(),
+        true,
+        BN254_FLAGS,
+        32,
+    ));
+    proof.extend(g1);
+    let parsed = bn254_proof_from_gnark_bytes(&proof).unwrap();
+    assert_eq!(parsed.0.a, ark_bn254::G1Affine::generator());
+    assert_eq!(parsed.0.b, ark_bn254::G2Affine::generator());
+    assert_eq!(parsed.0.c, ark_bn254::G1Affine::generator());
+
+    // The negated generator has the largest y-coordinate.
+    proof[0] = 0xc0;
+    let parsed = bn254_proof_from_gnark_bytes(&proof).unwrap();
+    assert_eq!(parsed.0.a, ark_bn254::G1Affine::generator().neg());
+
+    // Uncompressed generator.
+    let mut raw = [0u8; 64];
+    raw[31] = 1;
+    raw[63] = 2;
+    proof.splice(..32, raw);
+    let parsed = bn254_proof_from_gnark_bytes(&proof).unwrap();
+    assert_eq!(parsed.0.a, ark_bn254::G1Affine::generator());
+
+    // Not on the curve.
+    proof[63] = 3;
+    assert!(bn254_proof_from_gnark_bytes(&proof).is_err());
+}
+
+#[test]
+fn test_bls12381_generators() {
+    let g1 = hex::decode(
+        "97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb",
+    )
+    .unwrap();
+    let g2 = hex::decode(
+        "93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e\
+         024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8",
+    )
+    .unwrap();
+    let proof = [g1.clone(), g2, g1].concat();
+    let parsed = bls12381_proof_from_gnark_bytes(&proof).unwrap();
+    assert_eq!(parsed.0.a, ark_bls12_381::G1Affine::generator());
+    assert_eq!(parsed.0.b, ark_bls12_381::G2Affine::generator());
+    assert_eq!(parsed.0.c, ark_bls12_381::G1Affine::generator());
+
+    // Not a BN254 proof.
+    assert!(bn254_proof_from_gnark_bytes(&proof).is_err());
+}
+
+#[test]
+fn test_invalid_points() {
+    // The point at infinity must be encoded with zeros.
+    let mut proof = [0u8; 128];
+    proof[0] = 0x40;
+    proof[32] = 0x40;
+    proof[96] = 0x40;
+    assert!(bn254_proof_from_gnark_bytes(&proof).is_ok());
+    proof[1] = 1;
+    assert!(bn254_proof_from_gnark_bytes(&proof).is_err());
+
+    // Coordinates must be reduced.
+    let mut proof = [0u8; 128];
+    let modulus = fq_bytes(&-ark_bn254::Fq::ONE, 32);
+    proof[..32].copy_from_slice(&modulus);
+    proof[31] += 1;
+    proof[0] |= 0x80;
+    proof[32] = 0x40;
+    proof[96] = 0x40;
+    assert!(bn254_proof_from_gnark_bytes(&proof).is_err());
+
+    // A point on the twist which is not in the prime order subgroup of G2.
+    let mut x = ark_bn254::Fq2::ONE;
+    let point = loop {
+        if let Some(point) = ark_bn254::G2Affine::get_point_from_x_unchecked(x, false) {
+            if !point.is_in_correct_subgroup_assuming_on_curve() {
+                break point;
+            }
+        }
+        x += ark_bn254::Fq2::ONE;
+    };
+    let mut proof = vec![0x40];
+    proof.extend([0u8; 31]);
+    proof.extend(g2_bytes(&point, true, BN254_FLAGS, 32));
+    proof.push(0x40);
+    proof.extend([0u8; 31]);
+    assert!(bn254_proof_from_gnark_bytes(&proof).is_err());
+}
+
+#[test]
+fn test_bn254_artifacts() {
+    for compressed in [true, false] {
+        let artifacts = artifacts::<ark_bn254::Bn254, _, _, _>(compressed, BN254_FLAGS, 32);
+        let vk = bn254_verifying_key_from_gnark_bytes(&artifacts.vk).unwrap();
+        let proof = bn254_proof_from_gnark_bytes(&artifacts.proof).unwrap();
+        let pvk = bn254::verifier::process_vk_special(&vk);
+        let inputs = [bn254::FieldElement(artifacts.public_input)];
+        assert!(bn254::verifier::verify_with_processed_vk(&pvk, &inputs, &proof).unwrap());
+
+        // Trailing data of newer gnark versions without commitments.
+        let mut vk_bytes = artifacts.vk.clone();
+        vk_bytes.extend([0u8; 8]);
+        assert_eq!(bn254_verifying_key_from_gnark_bytes(&vk_bytes).unwrap(), vk);
+        let mut proof_bytes = artifacts.proof.clone();
+        proof_bytes.extend([0u8; 4]);
+        proof_bytes.extend(g1_bytes(
+            &ark_bn254::G1Affine::zero(),
+            compressed,
+            BN254_FLAGS,
+            32,
+        ));
+        assert_eq!(bn254_proof_from_gnark_bytes(&proof_bytes).unwrap(), proof);
+
+        // Commitments are not supported.
+        vk_bytes[artifacts.vk.len() + 3] = 1;
+        assert!(bn254_verifying_key_from_gnark_bytes(&vk_bytes).is_err());
+        proof_bytes[artifacts.proof.len() + 3] = 1;
+        assert!(bn254_proof_from_gnark_bytes(&proof_bytes).is_err());
+
+        // Truncated or extended encodings.
+        assert!(bn254_verifying_key_from_gnark_bytes(&artifacts.vk[1..]).is_err());
+        assert!(
+            bn254_verifying_key_from_gnark_bytes(&artifacts.vk[..artifacts.vk.len() - 1]).is_err()
+        );
+        assert!(
+            bn254_proof_from_gnark_bytes(&[artifacts.proof.as_slice(), &[0]].concat()).is_err()
+        );
+    }
+}
+
+#[test]
+fn test_bls12381_artifacts() {
+    for compressed in [true, false] {
+        let artifacts =
+            artifacts::<ark_bls12_381::Bls12_381, _, _, _>(compressed, BLS12381_FLAGS, 48);
+        let vk = bls12381_verifying_key_from_gnark_bytes(&artifacts.vk).unwrap();
+        let proof = bls12381_proof_from_gnark_bytes(&artifacts.proof).unwrap();
+        let pvk = bls12381::verifier::process_vk_special(&vk);
+        let inputs = [bls12381::FieldElement(artifacts.public_input)];
+        assert!(bls12381::verifier::verify_with_processed_vk(&pvk, &inputs, &proof).unwrap());
+
+        assert!(bn254_verifying_key_from_gnark_bytes(&artifacts.vk).is_err());
+    }
+}
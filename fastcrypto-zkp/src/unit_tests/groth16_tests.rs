@@ -0,0 +1,55 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::dummy_circuits::DummyCircuit;
+use crate::groth16::{
+    multipairing_with_processed_vk, process_vk_special, verify_with_processed_vk, Arkworks, Blst,
+    Groth16Verifier,
+};
+use ark_crypto_primitives::snark::SNARK;
+use ark_ec::pairing::Pairing;
+use ark_groth16::Groth16;
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use ark_std::UniformRand;
+use std::ops::Mul;
+
+fn check_verifier<V: Groth16Verifier>() {
+    let rng = &mut StdRng::from_seed([0; 32]);
+    let c = DummyCircuit::<<V::Engine as Pairing>::ScalarField> {
+        a: Some(UniformRand::rand(rng)),
+        b: Some(UniformRand::rand(rng)),
+        num_variables: 4,
+        num_constraints: 10,
+    };
+    let (pk, vk) = Groth16::<V::Engine>::circuit_specific_setup(c, rng).unwrap();
+    let proof = Groth16::<V::Engine>::prove(&pk, c, rng).unwrap();
+    let inputs = [c.a.unwrap().mul(c.b.unwrap())];
+
+    let pvk = process_vk_special::<V>(&vk);
+    assert!(verify_with_processed_vk::<V>(&pvk, &inputs, &proof).unwrap());
+    assert!(
+        &multipairing_with_processed_vk::<V>(&pvk, &inputs, &proof).unwrap()
+            == V::alpha_g1_beta_g2(&pvk)
+    );
+
+    // Wrong public inputs.
+    assert!(!verify_with_processed_vk::<V>(&pvk, &[c.a.unwrap()], &proof).unwrap());
+    assert!(verify_with_processed_vk::<V>(&pvk, &[], &proof).is_err());
+    assert!(verify_with_processed_vk::<V>(&pvk, &[inputs[0], inputs[0]], &proof).is_err());
+
+    // A proof for another circuit.
+    let other_proof = Groth16::<V::Engine>::prove(&pk, DummyCircuit { a: c.b, ..c }, rng).unwrap();
+    assert!(!verify_with_processed_vk::<V>(&pvk, &inputs, &other_proof).unwrap());
+}
+
+#[test]
+fn test_blst() {
+    check_verifier::<Blst>();
+}
+
+#[test]
+fn test_arkworks() {
+    check_verifier::<Arkworks<ark_bls12_381::Bls12_381>>();
+    check_verifier::<Arkworks<ark_bn254::Bn254>>();
+    check_verifier::<Arkworks<ark_bls12_377::Bls12_377>>();
+}
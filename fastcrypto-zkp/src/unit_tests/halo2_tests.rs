@@ -0,0 +1,489 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::halo2::*;
+use crate::halo2::{
+    g1_from_compressed, g2_from_compressed, interpolate, rotation_sets, vanishing_polynomial,
+    Blake2bState,
+};
+use ark_bn254::{Bn254, Fq, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::{AffineRepr, CurveGroup, Group};
+use ark_ff::{BigInteger, Field, One, PrimeField, UniformRand, Zero};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use fastcrypto::error::FastCryptoError;
+use std::str::FromStr;
+
+/// A prover's view of a commitment: the committed polynomial, given by its coefficients.
+type Polynomial = Vec<Fr>;
+
+fn evaluate(polynomial: &Polynomial, x: &Fr) -> Fr {
+    polynomial
+        .iter()
+        .rev()
+        .fold(Fr::zero(), |acc, c| acc * x + c)
+}
+
+fn compress_g1(point: &G1Affine) -> Vec<u8> {
+    match point.xy() {
+        None => vec![0; 32],
+        Some((x, y)) => {
+            let mut bytes = x.into_bigint().to_bytes_le();
+            if y.into_bigint().is_odd() {
+                bytes[31] |= 0x80;
+            }
+            bytes
+        }
+    }
+}
+
+fn compress_g2(point: &G2Affine) -> Vec<u8> {
+    let (x, y) = point.xy().unwrap();
+    let mut bytes = [
+        x.c0.into_bigint().to_bytes_le(),
+        x.c1.into_bigint().to_bytes_le(),
+    ]
+    .concat();
+    if y.c0.into_bigint().is_odd() {
+        bytes[63] |= 0x80;
+    }
+    bytes
+}
+
+fn raw_fq(x: &Fq) -> Vec<u8> {
+    x.0 .0.iter().flat_map(|limb| limb.to_le_bytes()).collect()
+}
+
+fn raw_g1(point: &G1Affine) -> Vec<u8> {
+    [raw_fq(&point.x), raw_fq(&point.y)].concat()
+}
+
+fn raw_g2(point: &G2Affine) -> Vec<u8> {
+    [
+        raw_fq(&point.x.c0),
+        raw_fq(&point.x.c1),
+        raw_fq(&point.y.c0),
+        raw_fq(&point.y.c1),
+    ]
+    .concat()
+}
+
+/// A writer of proofs for [Blake2bTranscript], `Blake2bWrite` in halo2.
+struct TranscriptWriter {
+    proof: Vec<u8>,
+    transcript: Blake2bTranscript<'static>,
+}
+
+impl TranscriptWriter {
+    fn new() -> Self {
+        Self {
+            proof: Vec::new(),
+            transcript: Blake2bTranscript::new(&[]),
+        }
+    }
+
+    fn write_point(&mut self, point: G1Projective) {
+        let point = point.into_affine();
+        self.proof.extend(compress_g1(&point));
+        self.transcript.common_point(&point).unwrap();
+    }
+
+    fn squeeze_challenge(&mut self) -> Fr {
+        self.transcript.squeeze_challenge()
+    }
+}
+
+/// A setup with a known secret, together with committed polynomials and queries on them.
+struct Setup {
+    tau: Fr,
+    params: Params<Bn254>,
+    polynomials: Vec<Polynomial>,
+    commitments: Vec<Msm<Bn254>>,
+    queries: Vec<VerifierQuery<Fr>>,
+}
+
+impl Setup {
+    fn new(seed: u8) -> Self {
+        let mut rng = StdRng::from_seed([seed; 32]);
+        let tau = Fr::rand(&mut rng);
+        let params = Params {
+            k: 3,
+            g: G1Affine::generator(),
+            g2: G2Affine::generator(),
+            s_g2: (G2Projective::generator() * tau).into_affine(),
+        };
+        let mut polynomials = (0..4)
+            .map(|_| (0..8).map(|_| Fr::rand(&mut rng)).collect::<Polynomial>())
+            .collect::<Vec<_>>();
+        let mut commitments = polynomials
+            .iter()
+            .map(|p| Msm::from_point((G1Projective::generator() * evaluate(p, &tau)).into_affine()))
+            .collect::<Vec<_>>();
+
+        // A commitment given as a linear combination of the others, 2 a + b.
+        let two = Fr::from(2u64);
+        polynomials.push(
+            polynomials[0]
+                .iter()
+                .zip(&polynomials[1])
+                .map(|(a, b)| two * a + b)
+                .collect(),
+        );
+        let mut msm = commitments[0].clone();
+        msm.scale(two);
+        msm.add_msm(&commitments[1]);
+        commitments.push(msm);
+
+        // The commitments 0 and 1 are queried at {x, ωx}, 2 at {ωx, ω^2 x} and 3 and 4 at {x}.
+        let x = Fr::rand(&mut rng);
+        let omega = Fr::from(7u64);
+        let points = [x, omega * x, omega * omega * x];
+        let queries = [
+            (0, 0),
+            (1, 0),
+            (0, 1),
+            (2, 1),
+            (3, 0),
+            (1, 1),
+            (2, 2),
+            (4, 0),
+        ]
+        .iter()
+        .map(|(c, p)| VerifierQuery {
+            commitment: *c,
+            point: points[*p],
+            eval: evaluate(&polynomials[*c], &points[*p]),
+        })
+        .collect();
+        Self {
+            tau,
+            params,
+            polynomials,
+            commitments,
+            queries,
+        }
+    }
+
+    /// `[f(τ)]_1` for the secret `τ`.
+    fn commit(&self, value: Fr) -> G1Projective {
+        G1Projective::generator() * value
+    }
+
+    fn prove_gwc(&self) -> Vec<u8> {
+        let mut transcript = TranscriptWriter::new();
+        let v = transcript.squeeze_challenge();
+        let mut points: Vec<Fr> = Vec::new();
+        for query in &self.queries {
+            if !points.contains(&query.point) {
+                points.push(query.point);
+            }
+        }
+        for point in &points {
+            // W = [(sum_j v^j (p_j(τ) - p_j(z))) / (τ - z)].
+            let mut numerator = Fr::zero();
+            let mut power_of_v = Fr::one();
+            for query in self.queries.iter().filter(|q| q.point == *point) {
+                let polynomial = &self.polynomials[query.commitment];
+                numerator += power_of_v * (evaluate(polynomial, &self.tau) - query.eval);
+                power_of_v *= v;
+            }
+            transcript.write_point(self.commit(numerator * (self.tau - point).inverse().unwrap()));
+        }
+        transcript.squeeze_challenge();
+        transcript.proof
+    }
+
+    fn prove_shplonk(&self) -> Vec<u8> {
+        let (sets, super_point_set) = rotation_sets(&self.queries);
+        let mut transcript = TranscriptWriter::new();
+        let y = transcript.squeeze_challenge();
+        let v = transcript.squeeze_challenge();
+
+        // h(X) = sum_i v^i sum_j y^j (p_ij(X) - r_ij(X)) / Z_{S_i}(X).
+        let mut h = Fr::zero();
+        let mut power_of_v = Fr::one();
+        for set in &sets {
+            let mut inner = Fr::zero();
+            let mut power_of_y = Fr::one();
+            for (commitment, evals) in &set.commitments {
+                let p = evaluate(&self.polynomials[*commitment], &self.tau);
+                inner += power_of_y * (p - interpolate(&set.points, evals, &self.tau));
+                power_of_y *= y;
+            }
+            h += power_of_v
+                * inner
+                * vanishing_polynomial(set.points.iter(), &self.tau)
+                    .inverse()
+                    .unwrap();
+            power_of_v *= v;
+        }
+        transcript.write_point(self.commit(h));
+        let u = transcript.squeeze_challenge();
+
+        // L(X) = sum_i v^i z_i sum_j y^j (p_ij(X) - r_ij(u)) - Z_{S_0}(u) h(X), where z_i is
+        // Z_{T \ S_i}(u) / Z_{T \ S_0}(u), vanishes at u.
+        let z_diff = |points: &[Fr]| {
+            vanishing_polynomial(super_point_set.iter().filter(|p| !points.contains(p)), &u)
+        };
+        let z_diff_0 = z_diff(&sets[0].points);
+        let mut l = -vanishing_polynomial(sets[0].points.iter(), &u) * h;
+        let mut power_of_v = Fr::one();
+        for set in &sets {
+            let mut inner = Fr::zero();
+            let mut power_of_y = Fr::one();
+            for (commitment, evals) in &set.commitments {
+                let p = evaluate(&self.polynomials[*commitment], &self.tau);
+                inner += power_of_y * (p - interpolate(&set.points, evals, &u));
+                power_of_y *= y;
+            }
+            l += power_of_v * z_diff(&set.points) * z_diff_0.inverse().unwrap() * inner;
+            power_of_v *= v;
+        }
+        transcript.write_point(self.commit(l * (self.tau - u).inverse().unwrap()));
+        transcript.proof
+    }
+}
+
+fn check_multi_open<V>(prove: fn(&Setup) -> Vec<u8>, verify: V)
+where
+    V: Fn(
+        &Params<Bn254>,
+        &mut Blake2bTranscript,
+        &[Msm<Bn254>],
+        &[VerifierQuery<Fr>],
+    ) -> Result<DualMsm<Bn254>, FastCryptoError>,
+{
+    let setup = Setup::new(0);
+    let pvk = process_params(&setup.params);
+    let proof = prove(&setup);
+    let verify_proof = |commitments: &[Msm<Bn254>], queries: &[VerifierQuery<Fr>]| {
+        let mut transcript = Blake2bTranscript::new(&proof);
+        let result = verify(&setup.params, &mut transcript, commitments, queries);
+        transcript.finish().and(result)
+    };
+    assert!(verify_proof(&setup.commitments, &setup.queries)
+        .unwrap()
+        .check(&pvk));
+
+    // Wrong evaluations, points and commitments.
+    let mut queries = setup.queries.clone();
+    queries[3].eval += Fr::one();
+    assert!(!verify_proof(&setup.commitments, &queries)
+        .unwrap()
+        .check(&pvk));
+    let mut queries = setup.queries.clone();
+    queries[4].point += Fr::one();
+    assert!(!verify_proof(&setup.commitments, &queries).map_or(false, |d| d.check(&pvk)));
+    let mut commitments = setup.commitments.clone();
+    commitments.swap(2, 3);
+    assert!(!verify_proof(&commitments, &setup.queries)
+        .unwrap()
+        .check(&pvk));
+
+    // A proof for other parameters.
+    let other = Setup::new(1);
+    assert!(!verify_proof(&other.commitments, &other.queries).map_or(false, |d| d.check(&pvk)));
+
+    // Missing queries and commitments.
+    assert!(verify_proof(&setup.commitments, &[]).is_err());
+    assert!(verify_proof(&setup.commitments[..4], &setup.queries).is_err());
+
+    // Truncated and extended proofs.
+    let mut transcript = Blake2bTranscript::new(&proof[..proof.len() - 1]);
+    assert!(verify(
+        &setup.params,
+        &mut transcript,
+        &setup.commitments,
+        &setup.queries
+    )
+    .is_err());
+    let extended = [proof.as_slice(), &[0]].concat();
+    let mut transcript = Blake2bTranscript::new(&extended);
+    assert!(verify(
+        &setup.params,
+        &mut transcript,
+        &setup.commitments,
+        &setup.queries
+    )
+    .is_ok());
+    assert!(transcript.finish().is_err());
+}
+
+#[test]
+fn test_gwc() {
+    check_multi_open(Setup::prove_gwc, |p, t, c, q| verify_gwc(p, t, c, q));
+}
+
+#[test]
+fn test_shplonk() {
+    check_multi_open(Setup::prove_shplonk, |p, t, c, q| {
+        verify_shplonk(p, t, c, q)
+    });
+}
+
+#[test]
+fn test_accumulation() {
+    let setup = Setup::new(0);
+    let pvk = process_params(&setup.params);
+    let mut rng = StdRng::from_seed([0; 32]);
+
+    let gwc_proof = setup.prove_gwc();
+    let gwc = verify_gwc(
+        &setup.params,
+        &mut Blake2bTranscript::new(&gwc_proof),
+        &setup.commitments,
+        &setup.queries,
+    )
+    .unwrap();
+    let shplonk_proof = setup.prove_shplonk();
+    let shplonk = verify_shplonk(
+        &setup.params,
+        &mut Blake2bTranscript::new(&shplonk_proof),
+        &setup.commitments,
+        &setup.queries,
+    )
+    .unwrap();
+
+    let mut accumulator = gwc.clone();
+    accumulator.scale(Fr::rand(&mut rng));
+    accumulator.add(&shplonk);
+    assert!(accumulator.check(&pvk));
+
+    // An accumulated invalid check.
+    let mut invalid = shplonk;
+    invalid.right.append_term(Fr::one(), setup.params.g);
+    let mut accumulator = gwc;
+    accumulator.scale(Fr::rand(&mut rng));
+    accumulator.add(&invalid);
+    assert!(!accumulator.check(&pvk));
+    assert!(DualMsm::<Bn254>::new().check(&pvk));
+}
+
+#[test]
+fn test_blake2b_transcript() {
+    // Test vectors computed with Python's hashlib.blake2b with the halo2 personalization.
+    let mut state = Blake2bState::new();
+    state.update(b"abc");
+    assert_eq!(
+        hex::encode(state.finalize()),
+        "1a461cab63d8b3dbc9daab1574729f55d54e844b17b69ab1fce6dae8caecb9d2\
+         4bb550cd9270aa53c8e9277d68008b3080da0b66fc6853231e15abec0b0159e5"
+    );
+    let data = (0..512).map(|i| i as u8).collect::<Vec<_>>();
+    let mut state = Blake2bState::new();
+    data.chunks(100).for_each(|chunk| state.update(chunk));
+    assert_eq!(
+        hex::encode(state.finalize()),
+        "ac3b97b2edadaf467d3aeb6afa4eb01befc007298d7770399834ccf4824d8ab1\
+         bd832bbc56b10f2ef3df33928f41bfb27308eab232be4ef9169bc3a550929796"
+    );
+
+    // Challenges are derived from the state without resetting it.
+    let mut transcript = Blake2bTranscript::new(&[]);
+    assert_eq!(
+        transcript.squeeze_challenge(),
+        Fr::from_str(
+            "6575782310972930295273932586580659450006688520153409556884839118183920138346"
+        )
+        .unwrap()
+    );
+    assert_eq!(
+        transcript.squeeze_challenge(),
+        Fr::from_str(
+            "21309581033013708913103695236891517852985199868510776481675830606684496575554"
+        )
+        .unwrap()
+    );
+
+    // Scalars are read in canonical little-endian form.
+    let mut bytes = [0u8; 32];
+    bytes[0] = 5;
+    let mut transcript = Blake2bTranscript::new(&bytes);
+    assert_eq!(transcript.read_scalar().unwrap(), Fr::from(5u64));
+    assert_eq!(
+        transcript.squeeze_challenge(),
+        Fr::from_str(
+            "11611668716558452560618708854126664784815364708838965407114884864274070827898"
+        )
+        .unwrap()
+    );
+    assert!(transcript.finish().is_ok());
+    let modulus = Fr::MODULUS.to_bytes_le();
+    assert!(Blake2bTranscript::new(&modulus).read_scalar().is_err());
+    assert!(Blake2bTranscript::new(&bytes[..31]).read_scalar().is_err());
+
+    // The point at infinity cannot be added to the transcript.
+    assert!(Blake2bTranscript::new(&[0; 32]).read_point().is_err());
+}
+
+#[test]
+fn test_compressed_points() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    for _ in 0..10 {
+        let point = G1Projective::rand(&mut rng).into_affine();
+        assert_eq!(g1_from_compressed(&compress_g1(&point)).unwrap(), point);
+        let point = G2Projective::rand(&mut rng).into_affine();
+        assert_eq!(g2_from_compressed(&compress_g2(&point)).unwrap(), point);
+    }
+    assert!(g1_from_compressed(&[0; 32]).unwrap().is_zero());
+    assert!(g2_from_compressed(&[0; 64]).unwrap().is_zero());
+
+    // Non-canonical coordinates and coordinates which are not on the curve.
+    assert!(g1_from_compressed(&[0xff; 32]).is_err());
+    let x = (1u64..)
+        .map(Fq::from)
+        .find(|x| (x.square() * x + Fq::from(3u64)).sqrt().is_none())
+        .unwrap();
+    assert!(g1_from_compressed(&x.into_bigint().to_bytes_le()).is_err());
+}
+
+#[test]
+fn test_params_from_bytes() {
+    let setup = Setup::new(0);
+    let n = 1 << setup.params.k;
+    let points = (0..2 * n)
+        .map(|i| (G1Projective::generator() * Fr::from(i as u64 + 1)).into_affine())
+        .collect::<Vec<_>>();
+
+    for (format, g1, g2) in [
+        (
+            SerdeFormat::Processed,
+            compress_g1 as fn(&G1Affine) -> Vec<u8>,
+            compress_g2 as fn(&G2Affine) -> Vec<u8>,
+        ),
+        (SerdeFormat::RawBytes, raw_g1, raw_g2),
+    ] {
+        let mut bytes = setup.params.k.to_le_bytes().to_vec();
+        points.iter().for_each(|p| bytes.extend(g1(p)));
+        bytes.extend(g2(&setup.params.g2));
+        bytes.extend(g2(&setup.params.s_g2));
+        assert_eq!(
+            bn254_params_from_bytes(&bytes, format).unwrap(),
+            setup.params
+        );
+
+        // Wrong lengths and k.
+        assert!(bn254_params_from_bytes(&bytes[..bytes.len() - 1], format).is_err());
+        assert!(bn254_params_from_bytes(&[bytes.as_slice(), &[0]].concat(), format).is_err());
+        assert!(bn254_params_from_bytes(&bytes[..3], format).is_err());
+        let mut modified = bytes.clone();
+        modified[0] = 2;
+        assert!(bn254_params_from_bytes(&modified, format).is_err());
+        let mut modified = bytes.clone();
+        modified[..4].copy_from_slice(&(MAX_K + 1).to_le_bytes());
+        assert!(bn254_params_from_bytes(&modified, format).is_err());
+
+        // An invalid [s]_2.
+        let mut modified = bytes.clone();
+        let last = modified.len() - 1;
+        modified[last - 40] ^= 1;
+        assert!(bn254_params_from_bytes(&modified, format).is_err());
+    }
+
+    // Raw coordinates must be reduced.
+    let mut bytes = setup.params.k.to_le_bytes().to_vec();
+    bytes.extend([0xff; 64]);
+    points[1..].iter().for_each(|p| bytes.extend(raw_g1(p)));
+    bytes.extend(raw_g2(&setup.params.g2));
+    bytes.extend(raw_g2(&setup.params.s_g2));
+    assert!(bn254_params_from_bytes(&bytes, SerdeFormat::RawBytes).is_err());
+}
@@ -0,0 +1,108 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::dummy_circuits::DummyCircuit;
+use crate::inspect::{inspect_proof, inspect_verifying_key, ArtifactKind, Curve};
+use ark_bls12_381::{Bls12_381, Fq, G1Affine};
+use ark_crypto_primitives::snark::SNARK;
+use ark_groth16::Groth16;
+use ark_serialize::CanonicalSerialize;
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use ark_std::UniformRand;
+use fastcrypto::hash::{HashFunction, Sha256};
+
+const PUBLIC_SIZE: usize = 4;
+
+fn serialize<T: CanonicalSerialize>(value: &T) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    value.serialize_compressed(&mut bytes).unwrap();
+    bytes
+}
+
+#[test]
+fn test_inspect_bn254() {
+    let rng = &mut StdRng::from_seed([0; 32]);
+    let c = DummyCircuit::<ark_bn254::Fr> {
+        a: Some(<ark_bn254::Fr>::rand(rng)),
+        b: Some(<ark_bn254::Fr>::rand(rng)),
+        num_variables: PUBLIC_SIZE,
+        num_constraints: 10,
+    };
+    let (pk, vk) = Groth16::<ark_bn254::Bn254>::circuit_specific_setup(c, rng).unwrap();
+    let proof = Groth16::<ark_bn254::Bn254>::prove(&pk, c, rng).unwrap();
+
+    let vk_bytes = serialize(&vk);
+    let inspection = inspect_verifying_key(Curve::Bn254, &vk_bytes).unwrap();
+    assert_eq!(inspection.curve, Curve::Bn254);
+    assert_eq!(inspection.kind, ArtifactKind::VerifyingKey);
+    assert_eq!(inspection.public_inputs, Some(vk.gamma_abc_g1.len() - 1));
+    assert!(inspection.points_valid());
+    assert_eq!(inspection.digest, Sha256::digest(&vk_bytes).digest);
+    assert_eq!(inspection, crate::bn254::VerifyingKey(vk).inspect());
+
+    let proof_bytes = serialize(&proof);
+    let inspection = inspect_proof(Curve::Bn254, &proof_bytes).unwrap();
+    assert_eq!(inspection.kind, ArtifactKind::Proof);
+    assert_eq!(inspection.public_inputs, None);
+    assert!(inspection.points_valid());
+    assert_eq!(inspection.digest, Sha256::digest(&proof_bytes).digest);
+
+    let proof = crate::bn254::Proof(proof);
+    assert_eq!(inspection, proof.inspect());
+    assert_eq!(
+        proof.to_string(),
+        format!(
+            "Curve: bn254\nKind: proof\nPoints valid: true\nDigest: {}",
+            hex::encode(inspection.digest)
+        )
+    );
+
+    // Wrong curve, truncated and trailing bytes.
+    assert!(inspect_proof(Curve::Bls12381, &proof_bytes).is_err());
+    assert!(inspect_proof(Curve::Bn254, &proof_bytes[1..]).is_err());
+    let mut extended = proof_bytes;
+    extended.push(0);
+    assert!(inspect_proof(Curve::Bn254, &extended).is_err());
+}
+
+#[test]
+fn test_inspect_bls12381_invalid_point() {
+    let rng = &mut StdRng::from_seed([0; 32]);
+    let c = DummyCircuit::<ark_bls12_381::Fr> {
+        a: Some(<ark_bls12_381::Fr>::rand(rng)),
+        b: Some(<ark_bls12_381::Fr>::rand(rng)),
+        num_variables: PUBLIC_SIZE,
+        num_constraints: 10,
+    };
+    let (_, mut vk) = Groth16::<Bls12_381>::circuit_specific_setup(c, rng).unwrap();
+
+    let inspection = inspect_verifying_key(Curve::Bls12381, &serialize(&vk)).unwrap();
+    assert!(inspection.points_valid());
+    assert!(inspection
+        .to_string()
+        .starts_with("Curve: bls12381\nKind: verifying key\nPublic inputs: "));
+
+    // Find a point on the curve which is not in the prime order subgroup.
+    let point = (1u64..)
+        .filter_map(|x| G1Affine::get_point_from_x_unchecked(Fq::from(x), false))
+        .find(|p| !p.is_in_correct_subgroup_assuming_on_curve())
+        .unwrap();
+    vk.gamma_abc_g1[1] = point;
+
+    let inspection = inspect_verifying_key(Curve::Bls12381, &serialize(&vk)).unwrap();
+    assert!(!inspection.points_valid());
+    assert_eq!(
+        inspection.invalid_points,
+        vec!["gamma_abc_g1[1]".to_string()]
+    );
+    assert!(inspection
+        .to_string()
+        .contains("Points valid: false (gamma_abc_g1[1])"));
+}
+
+#[test]
+fn test_curve_from_str() {
+    assert_eq!("bn254".parse::<Curve>().unwrap(), Curve::Bn254);
+    assert_eq!("bls12381".parse::<Curve>().unwrap(), Curve::Bls12381);
+    assert!("secp256k1".parse::<Curve>().is_err());
+}
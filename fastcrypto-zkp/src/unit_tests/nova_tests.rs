@@ -0,0 +1,193 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::nova::*;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{One, PrimeField, UniformRand, Zero};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use fastcrypto::error::FastCryptoError;
+
+/// The R1CS of `x^3 + x + 5 = y` for a public `y` with the witness variables `(x, x^2, x^3)`.
+fn cubic<F: PrimeField>() -> R1cs<F> {
+    let (x, x2, x3, u, y) = (0, 1, 2, 3, 4);
+    R1cs::new(
+        3,
+        3,
+        1,
+        vec![
+            (0, x, F::one()),
+            (1, x2, F::one()),
+            (2, x3, F::one()),
+            (2, x, F::one()),
+            (2, u, F::from(5u64)),
+        ],
+        vec![(0, x, F::one()), (1, x, F::one()), (2, u, F::one())],
+        vec![(0, x2, F::one()), (1, x3, F::one()), (2, y, F::one())],
+    )
+    .unwrap()
+}
+
+fn cubic_witness<F: PrimeField>(x: u64) -> (Vec<F>, F) {
+    let x = F::from(x);
+    (vec![x, x * x, x * x * x], x * x * x + x + F::from(5u64))
+}
+
+fn verifier_key<G: CurveGroup>(rng: &mut StdRng) -> VerifierKey<G> {
+    let generators = (0..3).map(|_| G::rand(rng).into_affine()).collect();
+    process_vk(cubic(), CommitmentKey::new(generators)).unwrap()
+}
+
+/// Fold the instances with the given witnesses into the running instance.
+fn prove<G: CurveGroup>(
+    vk: &VerifierKey<G>,
+    running: &RelaxedR1csInstance<G>,
+    running_witness: &RelaxedR1csWitness<G::ScalarField>,
+    instances: &[(R1csInstance<G>, Vec<G::ScalarField>)],
+) -> FoldingProof<G> {
+    let mut running = running.clone();
+    let mut witness = running_witness.clone();
+    let mut cross_terms = Vec::new();
+    for (instance, w) in instances {
+        let (a1, b1, c1) = vk
+            .r1cs
+            .multiply(&witness.w, &running.u, &running.public_inputs);
+        let (a2, b2, c2) = vk
+            .r1cs
+            .multiply(w, &G::ScalarField::one(), &instance.public_inputs);
+        let t = (0..vk.r1cs.num_constraints())
+            .map(|i| a1[i] * b2[i] + a2[i] * b1[i] - running.u * c2[i] - c1[i])
+            .collect::<Vec<_>>();
+        let comm_t = vk.ck.commit(&t).unwrap();
+        let r = challenge(vk, &running, instance, &comm_t);
+        running = running.fold(instance, &comm_t, &r);
+        witness
+            .w
+            .iter_mut()
+            .zip(w)
+            .for_each(|(w1, w2)| *w1 += r * w2);
+        witness.e.iter_mut().zip(&t).for_each(|(e, t)| *e += r * t);
+        cross_terms.push(comm_t);
+    }
+    FoldingProof {
+        cross_terms,
+        witness,
+    }
+}
+
+fn instance<G: CurveGroup>(vk: &VerifierKey<G>, x: u64) -> (R1csInstance<G>, Vec<G::ScalarField>) {
+    let (w, y) = cubic_witness(x);
+    (
+        R1csInstance {
+            comm_w: vk.ck.commit(&w).unwrap(),
+            public_inputs: vec![y],
+        },
+        w,
+    )
+}
+
+fn test_folding<G: CurveGroup>() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let vk = verifier_key::<G>(&mut rng);
+
+    let (first, first_w) = instance(&vk, 3);
+    let running = RelaxedR1csInstance::from(first);
+    let running_witness = RelaxedR1csWitness {
+        w: first_w,
+        e: vec![G::ScalarField::zero(); 3],
+    };
+    assert!(decide(&vk, &running, &running_witness).unwrap());
+
+    let instances = [instance(&vk, 4), instance(&vk, 5), instance(&vk, 6)];
+    let plain = instances.iter().map(|(i, _)| i.clone()).collect::<Vec<_>>();
+    let proof = prove(&vk, &running, &running_witness, &instances);
+    assert!(verify(&vk, &running, &plain, &proof).unwrap());
+
+    // Proofs do not verify for other instances.
+    assert!(!verify(
+        &vk,
+        &running,
+        &[plain[1].clone(), plain[0].clone(), plain[2].clone()],
+        &proof
+    )
+    .unwrap());
+    let mut other = plain.clone();
+    other[1].public_inputs[0] += G::ScalarField::one();
+    assert!(!verify(&vk, &running, &other, &proof).unwrap());
+
+    // Or for modified proofs.
+    let mut modified = proof.clone();
+    modified.cross_terms[0] = (modified.cross_terms[0].into_group()
+        + vk.ck.commit(&[G::ScalarField::one()]).unwrap())
+    .into_affine();
+    assert!(!verify(&vk, &running, &plain, &modified).unwrap());
+    let mut modified = proof.clone();
+    modified.witness.e[2] += G::ScalarField::one();
+    assert!(!verify(&vk, &running, &plain, &modified).unwrap());
+
+    // An unsatisfied instance cannot be folded.
+    let (mut wrong, wrong_w) = instance(&vk, 7);
+    wrong.public_inputs[0] += G::ScalarField::one();
+    let instances = [instance(&vk, 4), (wrong, wrong_w)];
+    let plain = instances.iter().map(|(i, _)| i.clone()).collect::<Vec<_>>();
+    let proof = prove(&vk, &running, &running_witness, &instances);
+    assert!(!verify(&vk, &running, &plain, &proof).unwrap());
+
+    // A random witness does not satisfy a running instance.
+    let witness = RelaxedR1csWitness {
+        w: (0..3).map(|_| G::ScalarField::rand(&mut rng)).collect(),
+        e: (0..3).map(|_| G::ScalarField::rand(&mut rng)).collect(),
+    };
+    let random = RelaxedR1csInstance {
+        comm_w: vk.ck.commit(&witness.w).unwrap(),
+        comm_e: vk.ck.commit(&witness.e).unwrap(),
+        u: G::ScalarField::rand(&mut rng),
+        public_inputs: vec![G::ScalarField::rand(&mut rng)],
+    };
+    assert!(!decide(&vk, &random, &witness).unwrap());
+}
+
+#[test]
+fn test_bn254_folding() {
+    test_folding::<ark_bn254::G1Projective>();
+}
+
+#[test]
+fn test_bls12381_folding() {
+    test_folding::<ark_bls12_381::G1Projective>();
+}
+
+#[test]
+fn test_invalid_inputs() {
+    type G = ark_bn254::G1Projective;
+    type F = ark_bn254::Fr;
+    let mut rng = StdRng::from_seed([0; 32]);
+
+    assert!(R1cs::<F>::new(1, 1, 1, vec![(1, 0, F::one())], vec![], vec![]).is_err());
+    assert!(R1cs::<F>::new(1, 1, 1, vec![(0, 3, F::one())], vec![], vec![]).is_err());
+    let short_key = CommitmentKey::<G>::new(vec![G::rand(&mut rng).into_affine(); 2]);
+    assert!(process_vk(cubic(), short_key.clone()).is_err());
+    assert_eq!(
+        short_key.commit(&[F::one(); 3]),
+        Err(FastCryptoError::InputTooLong(2))
+    );
+
+    let vk = verifier_key::<G>(&mut rng);
+    let (first, first_w) = instance(&vk, 3);
+    let running = RelaxedR1csInstance::from(first);
+    let running_witness = RelaxedR1csWitness {
+        w: first_w,
+        e: vec![F::zero(); 3],
+    };
+    let instances = [instance(&vk, 4)];
+    let plain = instances.iter().map(|(i, _)| i.clone()).collect::<Vec<_>>();
+    let proof = prove(&vk, &running, &running_witness, &instances);
+    assert!(verify(&vk, &running, &[], &proof).is_err());
+
+    let mut long = plain.clone();
+    long[0].public_inputs.push(F::one());
+    assert!(verify(&vk, &running, &long, &proof).is_err());
+
+    let mut short = proof.clone();
+    short.witness.e.pop();
+    assert!(verify(&vk, &running, &plain, &short).is_err());
+}
@@ -0,0 +1,200 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A PLONK circuit with its permutation argument and a simulated trusted setup, shared by the tests
+//! of the PLONK and fflonk verifiers which only differ in how the polynomials are committed to and
+//! opened.
+
+use crate::polynomial::{Polynomial, Radix2EvaluationDomain};
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{FftField, Field, One, UniformRand, Zero};
+use ark_std::rand::Rng;
+
+/// A circuit on four rows proving knowledge of `x` and `y` with `x * y = out` for a public `out`.
+/// Each row is given by its selectors `(q_M, q_L, q_R, q_O, q_C)` and the variables of its wires.
+pub(crate) const ROWS: [([i64; 5], [usize; 3]); 4] = [
+    // The public input out.
+    ([0, 1, 0, 0, 0], [1, 0, 0]),
+    // p = x * y.
+    ([1, 0, 0, -1, 0], [2, 3, 4]),
+    // p = out.
+    ([0, 1, -1, 0, 0], [4, 1, 0]),
+    // Padding.
+    ([0, 0, 0, 0, 0], [0, 0, 0]),
+];
+
+/// The preprocessed polynomials of the circuit.
+pub(crate) struct Circuit<F: FftField> {
+    pub domain: Radix2EvaluationDomain<F>,
+    pub k: [F; 3],
+    pub selectors: Vec<Polynomial<F>>,
+    pub sigmas: Vec<Polynomial<F>>,
+}
+
+pub(crate) fn constant<F: FftField>(c: F) -> Polynomial<F> {
+    Polynomial::new(vec![c])
+}
+
+pub(crate) fn sum<F: FftField>(polynomials: &[Polynomial<F>]) -> Polynomial<F> {
+    polynomials
+        .iter()
+        .fold(Polynomial::zero(), |acc, p| &acc + p)
+}
+
+fn field<F: FftField>(x: i64) -> F {
+    match x < 0 {
+        true => -F::from(x.unsigned_abs()),
+        false => F::from(x as u64),
+    }
+}
+
+impl<F: FftField> Circuit<F> {
+    pub fn new() -> Self {
+        let domain = Radix2EvaluationDomain::new(ROWS.len()).unwrap();
+        let k = [F::one(), F::GENERATOR, F::GENERATOR.square()];
+        let selectors = (0..5)
+            .map(|j| {
+                let values = ROWS.iter().map(|(q, _)| field(q[j])).collect::<Vec<F>>();
+                Polynomial::new(domain.ifft(&values).unwrap())
+            })
+            .collect();
+
+        // The wire at position (column, row) is labeled k_column * ω^row, and σ maps each position
+        // to the next position with the same variable.
+        let positions = (0..3)
+            .flat_map(|column| (0..ROWS.len()).map(move |row| (column, row)))
+            .collect::<Vec<_>>();
+        let variable = |(column, row): (usize, usize)| ROWS[row].1[column];
+        let mut sigma_values = vec![vec![F::zero(); ROWS.len()]; 3];
+        for (i, position) in positions.iter().enumerate() {
+            let next = (1..=positions.len())
+                .map(|j| positions[(i + j) % positions.len()])
+                .find(|p| variable(*p) == variable(*position))
+                .unwrap();
+            sigma_values[position.0][position.1] = k[next.0] * domain.element(next.1);
+        }
+        let sigmas = sigma_values
+            .iter()
+            .map(|values| Polynomial::new(domain.ifft(values).unwrap()))
+            .collect();
+        Self {
+            domain,
+            k,
+            selectors,
+            sigmas,
+        }
+    }
+
+    /// The polynomial taking the given values on the domain.
+    pub fn interpolate(&self, values: &[F]) -> Polynomial<F> {
+        Polynomial::new(self.domain.ifft(values).unwrap())
+    }
+
+    /// The values of the three wires on each row for the witness `x`, `y`.
+    pub fn wire_values(&self, x: F, y: F) -> Vec<Vec<F>> {
+        let out = x * y;
+        let assignment = [F::zero(), out, x, y, out];
+        (0..3)
+            .map(|column| {
+                ROWS.iter()
+                    .map(|(_, wires)| assignment[wires[column]])
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The vanishing polynomial `X^n - 1` of the domain.
+    pub fn vanishing(&self) -> Polynomial<F> {
+        let n = self.domain.size();
+        let mut coefficients = vec![F::zero(); n + 1];
+        coefficients[0] = -F::one();
+        coefficients[n] = F::one();
+        Polynomial::new(coefficients)
+    }
+
+    /// The first Lagrange polynomial of the domain.
+    pub fn l_1(&self) -> Polynomial<F> {
+        let mut values = vec![F::zero(); self.domain.size()];
+        values[0] = F::one();
+        self.interpolate(&values)
+    }
+
+    /// The polynomial `p(ωX)`.
+    pub fn shift(&self, p: &Polynomial<F>) -> Polynomial<F> {
+        let omega = self.domain.generator();
+        Polynomial::new(
+            p.coefficients()
+                .iter()
+                .enumerate()
+                .map(|(i, c)| *c * omega.pow([i as u64]))
+                .collect(),
+        )
+    }
+
+    /// The permutation polynomial `z` of the wires with the challenges `beta` and `gamma`.
+    pub fn permutation(&self, wire_values: &[Vec<F>], beta: F, gamma: F) -> Polynomial<F> {
+        let domain = &self.domain;
+        let sigma_values = self
+            .sigmas
+            .iter()
+            .map(|s| domain.fft(s.coefficients()).unwrap())
+            .collect::<Vec<_>>();
+        let mut z_values = vec![F::one()];
+        for i in 0..domain.size() - 1 {
+            let mut numerator = F::one();
+            let mut denominator = F::one();
+            for j in 0..3 {
+                numerator *= wire_values[j][i] + beta * self.k[j] * domain.element(i) + gamma;
+                denominator *= wire_values[j][i] + beta * sigma_values[j][i] + gamma;
+            }
+            z_values.push(z_values[i] * numerator / denominator);
+        }
+        self.interpolate(&z_values)
+    }
+
+    /// The two products of the permutation argument, using the identity permutation and σ, whose
+    /// difference vanishes on the domain if `z` is the permutation polynomial of the wires.
+    pub fn permutation_products(
+        &self,
+        wires: &[Polynomial<F>],
+        z: &Polynomial<F>,
+        beta: F,
+        gamma: F,
+    ) -> (Polynomial<F>, Polynomial<F>) {
+        let (a, b, c) = (&wires[0], &wires[1], &wires[2]);
+        let (s1, s2, s3) = (&self.sigmas[0], &self.sigmas[1], &self.sigmas[2]);
+        let x = Polynomial::new(vec![F::zero(), F::one()]);
+        let linear = |w: &Polynomial<F>, p: &Polynomial<F>| {
+            sum(&[w.clone(), p.scale(&beta), constant(gamma)])
+        };
+        let identity = &(&(&linear(a, &x) * &linear(b, &x.scale(&self.k[1])))
+            * &linear(c, &x.scale(&self.k[2])))
+            * z;
+        let permuted = &(&(&linear(a, s1) * &linear(b, s2)) * &linear(c, s3)) * &self.shift(z);
+        (identity, permuted)
+    }
+}
+
+/// A simulated trusted setup with a known secret `τ`.
+pub(crate) struct Srs<E: Pairing> {
+    tau: E::ScalarField,
+}
+
+impl<E: Pairing> Srs<E> {
+    pub fn random<R: Rng>(rng: &mut R) -> Self {
+        Self {
+            tau: E::ScalarField::rand(rng),
+        }
+    }
+
+    /// Commit to `p` as `p(τ) * G`.
+    pub fn commit(&self, p: &Polynomial<E::ScalarField>) -> E::G1Affine {
+        (E::G1Affine::generator() * p.evaluate(&self.tau)).into_affine()
+    }
+
+    /// The element `τ * H` of G2 used by the verifying keys.
+    pub fn x_g2(&self) -> E::G2Affine {
+        (E::G2Affine::generator() * self.tau).into_affine()
+    }
+}
@@ -0,0 +1,245 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::plonk::{
+    process_vk_special, verify_with_processed_vk, vk_digest, Proof, Transcript, VerifyingKey,
+};
+use crate::plonk_circuit::{constant, sum, Circuit, Srs};
+use crate::polynomial::Polynomial;
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{FftField, Field, One, UniformRand, Zero};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+
+fn setup<E: Pairing>(circuit: &Circuit<E::ScalarField>, srs: &Srs<E>) -> VerifyingKey<E> {
+    let s = &circuit.selectors;
+    VerifyingKey {
+        domain_size: circuit.domain.size(),
+        num_public_inputs: 1,
+        k1: circuit.k[1],
+        k2: circuit.k[2],
+        q_m: srs.commit(&s[0]),
+        q_l: srs.commit(&s[1]),
+        q_r: srs.commit(&s[2]),
+        q_o: srs.commit(&s[3]),
+        q_c: srs.commit(&s[4]),
+        sigma_1: srs.commit(&circuit.sigmas[0]),
+        sigma_2: srs.commit(&circuit.sigmas[1]),
+        sigma_3: srs.commit(&circuit.sigmas[2]),
+        x_g2: srs.x_g2(),
+    }
+}
+
+/// Divide by `X - x` and check that the remainder is zero.
+fn divide_by_linear<F: FftField>(p: &Polynomial<F>, x: F) -> Polynomial<F> {
+    let (quotient, remainder) = p.div_rem(&Polynomial::new(vec![-x, F::one()])).unwrap();
+    assert!(remainder.is_zero());
+    quotient
+}
+
+/// Prove knowledge of `x` and `y` with `x * y = out`.
+fn prove<E: Pairing>(
+    circuit: &Circuit<E::ScalarField>,
+    vk: &VerifyingKey<E>,
+    srs: &Srs<E>,
+    x: E::ScalarField,
+    y: E::ScalarField,
+) -> Proof<E> {
+    type F<E> = <E as Pairing>::ScalarField;
+    let domain = &circuit.domain;
+    let n = domain.size();
+    let out = x * y;
+    let public_inputs = [out];
+
+    let mut transcript = Transcript::new(&vk_digest(vk), &public_inputs);
+
+    // Round 1: the wires.
+    let wire_values = circuit.wire_values(x, y);
+    let wires = wire_values
+        .iter()
+        .map(|values| circuit.interpolate(values))
+        .collect::<Vec<_>>();
+    let (a, b, c) = (&wires[0], &wires[1], &wires[2]);
+    let wire_commitments = wires.iter().map(|p| srs.commit(p)).collect::<Vec<_>>();
+    wire_commitments.iter().for_each(|p| transcript.append(p));
+    let beta: F<E> = transcript.challenge();
+    let gamma: F<E> = transcript.challenge();
+
+    // Round 2: the permutation polynomial.
+    let z = circuit.permutation(&wire_values, beta, gamma);
+    let z_commitment = srs.commit(&z);
+    transcript.append(&z_commitment);
+    let alpha: F<E> = transcript.challenge();
+
+    // Round 3: the quotient polynomial.
+    let omega = domain.generator();
+    let l_1 = circuit.l_1();
+    let pi = circuit.interpolate(&[-out]);
+    let s = &circuit.selectors;
+    let (s1, s2, s3) = (&circuit.sigmas[0], &circuit.sigmas[1], &circuit.sigmas[2]);
+
+    let gate = sum(&[
+        &(a * b) * &s[0],
+        a * &s[1],
+        b * &s[2],
+        c * &s[3],
+        s[4].clone(),
+        pi.clone(),
+    ]);
+    let (identity, permuted) = circuit.permutation_products(&wires, &z, beta, gamma);
+    let first = &(&z - &constant(F::<E>::one())) * &l_1;
+    let numerator = sum(&[
+        gate,
+        (&identity - &permuted).scale(&alpha),
+        first.scale(&alpha.square()),
+    ]);
+    let (t, remainder) = numerator.div_rem(&circuit.vanishing()).unwrap();
+    assert!(remainder.is_zero());
+    let mut t_coefficients = t.coefficients().to_vec();
+    assert!(t_coefficients.len() <= 3 * n);
+    t_coefficients.resize(3 * n, F::<E>::zero());
+    let t_parts = t_coefficients
+        .chunks(n)
+        .map(|c| Polynomial::new(c.to_vec()))
+        .collect::<Vec<_>>();
+    let t_commitments = t_parts.iter().map(|p| srs.commit(p)).collect::<Vec<_>>();
+    t_commitments.iter().for_each(|p| transcript.append(p));
+    let zeta: F<E> = transcript.challenge();
+
+    // Round 4: the evaluations.
+    let a_eval = a.evaluate(&zeta);
+    let b_eval = b.evaluate(&zeta);
+    let c_eval = c.evaluate(&zeta);
+    let sigma_1_eval = s1.evaluate(&zeta);
+    let sigma_2_eval = s2.evaluate(&zeta);
+    let z_omega_eval = z.evaluate(&(zeta * omega));
+    [
+        a_eval,
+        b_eval,
+        c_eval,
+        sigma_1_eval,
+        sigma_2_eval,
+        z_omega_eval,
+    ]
+    .iter()
+    .for_each(|x| transcript.append(x));
+    let v: F<E> = transcript.challenge();
+
+    // Round 5: the linearisation polynomial and the opening proofs.
+    let zeta_n = zeta.pow([n as u64]);
+    let z_h = zeta_n - F::<E>::one();
+    let gate = sum(&[
+        s[0].scale(&(a_eval * b_eval)),
+        s[1].scale(&a_eval),
+        s[2].scale(&b_eval),
+        s[3].scale(&c_eval),
+        s[4].clone(),
+        constant(pi.evaluate(&zeta)),
+    ]);
+    let identity = z.scale(
+        &((a_eval + beta * zeta + gamma)
+            * (b_eval + beta * circuit.k[1] * zeta + gamma)
+            * (c_eval + beta * circuit.k[2] * zeta + gamma)),
+    );
+    let permuted = sum(&[constant(c_eval), s3.scale(&beta), constant(gamma)]).scale(
+        &((a_eval + beta * sigma_1_eval + gamma)
+            * (b_eval + beta * sigma_2_eval + gamma)
+            * z_omega_eval),
+    );
+    let first = (&z - &constant(F::<E>::one())).scale(&l_1.evaluate(&zeta));
+    let quotient = sum(&[
+        t_parts[0].clone(),
+        t_parts[1].scale(&zeta_n),
+        t_parts[2].scale(&zeta_n.square()),
+    ]);
+    let r = sum(&[
+        gate,
+        (&identity - &permuted).scale(&alpha),
+        first.scale(&alpha.square()),
+        quotient.scale(&-z_h),
+    ]);
+    assert!(r.evaluate(&zeta).is_zero());
+
+    let mut opened = r;
+    let mut v_power = F::<E>::one();
+    for (p, eval) in [
+        (a, a_eval),
+        (b, b_eval),
+        (c, c_eval),
+        (s1, sigma_1_eval),
+        (s2, sigma_2_eval),
+    ] {
+        v_power *= v;
+        opened = &opened + &(p - &constant(eval)).scale(&v_power);
+    }
+    let w_zeta = divide_by_linear(&opened, zeta);
+    let w_zeta_omega = divide_by_linear(&(&z - &constant(z_omega_eval)), zeta * omega);
+
+    Proof {
+        a: wire_commitments[0],
+        b: wire_commitments[1],
+        c: wire_commitments[2],
+        z: z_commitment,
+        t_lo: t_commitments[0],
+        t_mid: t_commitments[1],
+        t_hi: t_commitments[2],
+        w_zeta: srs.commit(&w_zeta),
+        w_zeta_omega: srs.commit(&w_zeta_omega),
+        a_eval,
+        b_eval,
+        c_eval,
+        sigma_1_eval,
+        sigma_2_eval,
+        z_omega_eval,
+    }
+}
+
+fn test_verify<E: Pairing>() {
+    let rng = &mut StdRng::from_seed([0; 32]);
+    let circuit = Circuit::<E::ScalarField>::new();
+    let srs = Srs::<E>::random(rng);
+    let vk = setup(&circuit, &srs);
+    let pvk = process_vk_special(&vk).unwrap();
+
+    let x = E::ScalarField::rand(rng);
+    let y = E::ScalarField::rand(rng);
+    let proof = prove(&circuit, &vk, &srs, x, y);
+    assert!(verify_with_processed_vk(&pvk, &[x * y], &proof).unwrap());
+
+    // Other public inputs.
+    assert!(!verify_with_processed_vk(&pvk, &[x * y + E::ScalarField::one()], &proof).unwrap());
+    assert!(verify_with_processed_vk(&pvk, &[], &proof).is_err());
+    assert!(verify_with_processed_vk(&pvk, &[x, y], &proof).is_err());
+
+    // Modified evaluations or commitments.
+    let mut modified = proof.clone();
+    modified.z_omega_eval += E::ScalarField::one();
+    assert!(!verify_with_processed_vk(&pvk, &[x * y], &modified).unwrap());
+    let mut modified = proof.clone();
+    modified.t_hi = (modified.t_hi.into_group() + E::G1Affine::generator()).into_affine();
+    assert!(!verify_with_processed_vk(&pvk, &[x * y], &modified).unwrap());
+    let mut modified = proof.clone();
+    std::mem::swap(&mut modified.a, &mut modified.b);
+    std::mem::swap(&mut modified.a_eval, &mut modified.b_eval);
+    assert!(!verify_with_processed_vk(&pvk, &[x * y], &modified).unwrap());
+
+    // Another verifying key.
+    let other_vk = setup::<E>(&circuit, &Srs::random(rng));
+    let other_pvk = process_vk_special(&other_vk).unwrap();
+    assert!(!verify_with_processed_vk(&other_pvk, &[x * y], &proof).unwrap());
+
+    // The domain size must be a power of two.
+    let mut invalid_vk = vk;
+    invalid_vk.domain_size = 6;
+    assert!(process_vk_special(&invalid_vk).is_err());
+}
+
+#[test]
+fn test_verify_bls12381() {
+    test_verify::<ark_bls12_381::Bls12_381>();
+}
+
+#[test]
+fn test_verify_bn254() {
+    test_verify::<ark_bn254::Bn254>();
+}
@@ -0,0 +1,119 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::polynomial::{Polynomial, Radix2EvaluationDomain};
+use ark_ff::{FftField, One, UniformRand, Zero};
+use ark_std::test_rng;
+
+fn random_polynomial<F: FftField>(coefficients: usize) -> Polynomial<F> {
+    let mut rng = test_rng();
+    Polynomial::new((0..coefficients).map(|_| F::rand(&mut rng)).collect())
+}
+
+fn check_fft<F: FftField>() {
+    for size in [1, 2, 5, 16, 33] {
+        let domain = Radix2EvaluationDomain::<F>::new(size).unwrap();
+        assert_eq!(domain.size(), size.next_power_of_two());
+        assert_eq!(domain.element(domain.size()), F::one());
+
+        let p = random_polynomial::<F>(size);
+        let evaluations = domain.fft(p.coefficients()).unwrap();
+        for (e, x) in evaluations.iter().zip(domain.elements()) {
+            assert_eq!(*e, p.evaluate(&x));
+        }
+        assert_eq!(Polynomial::new(domain.ifft(&evaluations).unwrap()), p);
+    }
+
+    let domain = Radix2EvaluationDomain::<F>::new(4).unwrap();
+    assert!(domain.fft(&[F::one(); 5]).is_err());
+    assert!(domain.ifft(&[F::one(); 5]).is_err());
+    assert!(Radix2EvaluationDomain::<F>::new(0).is_err());
+    assert!(Radix2EvaluationDomain::<F>::new(1 << (F::TWO_ADICITY + 1)).is_err());
+}
+
+fn check_arithmetic<F: FftField>() {
+    let a = random_polynomial::<F>(70);
+    let b = random_polynomial::<F>(100);
+    let x = F::from(12345u64);
+
+    assert_eq!((&a + &b).evaluate(&x), a.evaluate(&x) + b.evaluate(&x));
+    assert_eq!((&a - &b).evaluate(&x), a.evaluate(&x) - b.evaluate(&x));
+    assert!((&a - &a).is_zero());
+
+    // Large products use FFTs, small ones schoolbook multiplication.
+    let product = &a * &b;
+    assert_eq!(product.degree(), a.degree() + b.degree());
+    assert_eq!(product.evaluate(&x), a.evaluate(&x) * b.evaluate(&x));
+    let small = random_polynomial::<F>(3);
+    assert_eq!(
+        (&a * &small).evaluate(&x),
+        a.evaluate(&x) * small.evaluate(&x)
+    );
+    assert!((&a * &Polynomial::zero()).is_zero());
+    assert_eq!(a.scale(&F::from(2u64)), &a + &a);
+
+    // Division with remainder.
+    let (q, r) = product.div_rem(&a).unwrap();
+    assert_eq!(q, b);
+    assert!(r.is_zero());
+    let (q, r) = b.div_rem(&small).unwrap();
+    assert!(r.degree() < small.degree());
+    assert_eq!(&(&q * &small) + &r, b);
+    assert!(a.div_rem(&Polynomial::zero()).is_err());
+}
+
+fn check_interpolation<F: FftField>() {
+    let p = random_polynomial::<F>(10);
+    let points = (0..10u64).map(|i| F::from(i * i + 1)).collect::<Vec<_>>();
+    let values = points.iter().map(|x| p.evaluate(x)).collect::<Vec<_>>();
+    assert_eq!(Polynomial::interpolate(&points, &values).unwrap(), p);
+
+    let z = Polynomial::from_roots(&points);
+    assert_eq!(z.degree(), points.len());
+    assert!(points.iter().all(|x| z.evaluate(x).is_zero()));
+
+    assert!(Polynomial::interpolate(&points[..2], &values).is_err());
+    assert!(Polynomial::interpolate(&[F::one(), F::one()], &values[..2]).is_err());
+    assert!(Polynomial::<F>::interpolate(&[], &[]).unwrap().is_zero());
+}
+
+fn check_lagrange_coefficients<F: FftField>() {
+    let domain = Radix2EvaluationDomain::<F>::new(8).unwrap();
+    let p = random_polynomial::<F>(8);
+    let evaluations = domain.fft(p.coefficients()).unwrap();
+    for x in [F::from(7u64), domain.element(3)] {
+        let coefficients = domain.lagrange_coefficients(&x);
+        let value = coefficients
+            .iter()
+            .zip(evaluations.iter())
+            .fold(F::zero(), |acc, (l, e)| acc + *l * e);
+        assert_eq!(value, p.evaluate(&x));
+    }
+    assert!(domain
+        .evaluate_vanishing_polynomial(&domain.element(5))
+        .is_zero());
+}
+
+#[test]
+fn test_fft() {
+    check_fft::<ark_bls12_381::Fr>();
+    check_fft::<ark_bn254::Fr>();
+}
+
+#[test]
+fn test_arithmetic() {
+    check_arithmetic::<ark_bls12_381::Fr>();
+    check_arithmetic::<ark_bn254::Fr>();
+}
+
+#[test]
+fn test_interpolation() {
+    check_interpolation::<ark_bls12_381::Fr>();
+    check_interpolation::<ark_bn254::Fr>();
+}
+
+#[test]
+fn test_lagrange_coefficients() {
+    check_lagrange_coefficients::<ark_bls12_381::Fr>();
+    check_lagrange_coefficients::<ark_bn254::Fr>();
+}
@@ -0,0 +1,159 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::poseidon::*;
+use ark_ff::PrimeField;
+use fastcrypto::error::FastCryptoError;
+use std::str::FromStr;
+
+fn from_hex<F: PrimeField>(encoded: &str) -> F {
+    F::from_be_bytes_mod_order(&hex::decode(encoded).unwrap())
+}
+
+fn elements<F: PrimeField>(values: std::ops::RangeInclusive<u64>) -> Vec<F> {
+    values.map(F::from).collect()
+}
+
+#[test]
+fn test_circomlib_vectors() {
+    // The expected digests are computed by circomlibjs.
+    for (inputs, expected) in [
+        (
+            elements(0..=0),
+            "19014214495641488759237505126948346942972912379615652741039992445865937985820",
+        ),
+        (
+            elements(1..=1),
+            "18586133768512220936620570745912940619677854269274689475585506675881198879027",
+        ),
+        (
+            elements(1..=2),
+            "7853200120776062878684798364095072458815029376092732009249414926327459813530",
+        ),
+        (
+            elements(1..=4),
+            "18821383157269793795438455681495246036402687001665670618754263018637548127333",
+        ),
+        (
+            elements(1..=16),
+            "9989051620750914585850546081941653841776809718687451684622678807385399211877",
+        ),
+    ] {
+        assert_eq!(
+            poseidon_bn254(&inputs).unwrap(),
+            ark_bn254::Fr::from_str(expected).unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_bls12381_vectors() {
+    // The permutation of [0, 1, 2] is the test vector poseidonperm_x5_255_3 of the reference
+    // implementation.
+    let mut state = elements::<ark_bls12_381::Fr>(0..=2);
+    bls12381_instance(2).unwrap().permute(&mut state).unwrap();
+    assert_eq!(
+        state,
+        [
+            "28ce19420fc246a05553ad1e8c98f5c9d67166be2c18e9e4cb4b4e317dd2a78a",
+            "51f3e312c95343a896cfd8945ea82ba956c1118ce9b9859b6ea56637b4b1ddc4",
+            "3b2b69139b235626a0bfb56c9527ae66a7bf486ad8c11c14d1da0c69bbe0f79a",
+        ]
+        .map(from_hex::<ark_bls12_381::Fr>)
+    );
+
+    let mut state = elements::<ark_bls12_381::Fr>(0..=4);
+    bls12381_instance(4).unwrap().permute(&mut state).unwrap();
+    assert_eq!(
+        state,
+        [
+            "2a918b9c9f9bd7bb509331c81e297b5707f6fc7393dcee1b13901a0b22202e18",
+            "65ebf8671739eeb11fb217f2d5c5bf4a0c3f210e3f3cd3b08b5db75675d797f7",
+            "2cc176fc26bc70737a696a9dfd1b636ce360ee76926d182390cdb7459cf585ce",
+            "4dc4e29d283afd2a491fe6aef122b9a968e74eff05341f3cc23fda1781dcb566",
+            "03ff622da276830b9451b88b85e6184fd6ae15c8ab3ee25a5667be8592cce3b1",
+        ]
+        .map(from_hex::<ark_bls12_381::Fr>)
+    );
+
+    for (inputs, expected) in [
+        (
+            elements(1..=1),
+            "33312903538086167554741214005086116725441315171650202128840830167854170336490",
+        ),
+        (
+            elements(1..=2),
+            "18456658763349757341014058622209659766100673761449600566550821987295786346378",
+        ),
+        (
+            elements(1..=16),
+            "41425418011113161672201583330064092125957629137402677559294124825112281440318",
+        ),
+    ] {
+        assert_eq!(
+            poseidon_bls12381(&inputs).unwrap(),
+            ark_bls12_381::Fr::from_str(expected).unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_constants() {
+    // The first and last round constants and some MDS entries of circomlib's Poseidon(2).
+    let instance = bn254_instance(2).unwrap();
+    assert_eq!(instance.width(), 3);
+    assert_eq!(instance.round_constants.len(), 3 * (8 + 57));
+    assert_eq!(
+        instance.round_constants[0],
+        from_hex("0ee9a592ba9a9518d05986d656f40c2114c4993c11bb29938d21d47304cd8e6e")
+    );
+    assert_eq!(
+        instance.round_constants[3 * (8 + 57) - 1],
+        from_hex("1da55cc900f0d21f4a3e694391918a1b3c23b2ac773c6b3ef88e2e4228325161")
+    );
+    assert_eq!(
+        instance.mds[0][0],
+        from_hex("109b7f411ba0e4c9b2b70caf5c36a7b194be7c11ad24378bfedb68592ba8118b")
+    );
+    assert_eq!(
+        instance.mds[2][1],
+        from_hex("176cc029695ad02582a70eff08a6fd99d057e12e58e7d7b6b16cdfabc8ee2911")
+    );
+
+    let instance = bls12381_instance(4).unwrap();
+    assert_eq!(
+        instance.round_constants[0],
+        from_hex("5ee52b2f39e240a4006e97a15a7609dce42fa9aa510d11586a56db98fa925158")
+    );
+    assert_eq!(
+        instance.mds[4][4],
+        from_hex("2f7126ddc54648ab6d02493dbe9907f29f4ef3967ad8cd609f0d9467e1694607")
+    );
+}
+
+#[test]
+fn test_invalid_inputs() {
+    assert_eq!(poseidon_bn254(&[]), Err(FastCryptoError::InvalidInput));
+    assert_eq!(
+        poseidon_bn254(&elements(1..=17)),
+        Err(FastCryptoError::InputTooLong(MAX_INPUTS))
+    );
+    assert!(poseidon_bls12381(&[]).is_err());
+    assert!(bls12381_instance(MAX_INPUTS + 1).is_err());
+
+    // Instances only hash and permute inputs of their width.
+    let instance = bn254_instance(2).unwrap();
+    assert_eq!(
+        instance.hash(&elements(1..=3)),
+        Err(FastCryptoError::InputLengthWrong(2))
+    );
+    assert_eq!(
+        instance.permute(&mut elements(1..=2)),
+        Err(FastCryptoError::InputLengthWrong(3))
+    );
+
+    // The capacity element is zero.
+    let mut state = elements(0..=2);
+    instance.permute(&mut state).unwrap();
+    assert_eq!(instance.hash(&elements(1..=2)).unwrap(), state[0]);
+}
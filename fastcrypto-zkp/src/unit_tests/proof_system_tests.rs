@@ -0,0 +1,30 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::proof_system::*;
+use crate::test_helpers::TestVector;
+use ark_ec::pairing::Pairing;
+
+fn test_proof_system<P: ProofSystem, E: Pairing>() {
+    let vector = TestVector::<E>::generate(1, 10, 0).unwrap();
+    let (vk, proof, public_inputs) = (
+        vector.vk_bytes(),
+        vector.proof_bytes(),
+        vector.public_inputs_bytes(),
+    );
+    assert!(P::verify(&vk, &proof, &public_inputs).unwrap());
+
+    // A malformed verifying key is rejected before verifying.
+    assert!(P::verify(&vk[1..], &proof, &public_inputs).is_err());
+    assert!(P::verify(&[], &proof, &public_inputs).is_err());
+}
+
+#[test]
+fn test_bn254_groth16() {
+    test_proof_system::<Bn254Groth16, ark_bn254::Bn254>();
+}
+
+#[test]
+fn test_bls12381_groth16() {
+    test_proof_system::<Bls12381Groth16, ark_bls12_381::Bls12_381>();
+}
@@ -0,0 +1,162 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::public_inputs::{
+    field_element_from_bytes, field_element_from_str, field_element_size,
+    field_elements_from_bytes, field_elements_from_strs, Endianness, MAX_INPUT_LENGTH,
+};
+use crate::{bls12381, bn254};
+use ark_ff::{Field, PrimeField, Zero};
+use num_bigint::BigUint;
+
+fn check_parsing<F: PrimeField>() {
+    assert_eq!(field_element_from_str::<F>("0").unwrap(), F::zero());
+    assert_eq!(field_element_from_str::<F>("0x0").unwrap(), F::zero());
+    assert_eq!(
+        field_element_from_str::<F>("1234").unwrap(),
+        F::from(1234u64)
+    );
+    assert_eq!(
+        field_element_from_str::<F>("0x4d2").unwrap(),
+        F::from(1234u64)
+    );
+    assert_eq!(
+        field_element_from_str::<F>("0X4D2").unwrap(),
+        F::from(1234u64)
+    );
+    assert_eq!(
+        field_element_from_str::<F>("001234").unwrap(),
+        F::from(1234u64)
+    );
+
+    // The largest element is accepted and the modulus is rejected in both bases.
+    let modulus: BigUint = F::MODULUS.into();
+    let max = &modulus - 1u8;
+    assert_eq!(
+        field_element_from_str::<F>(&max.to_string()).unwrap(),
+        -F::from(1u64)
+    );
+    assert_eq!(
+        field_element_from_str::<F>(&format!("0x{:x}", max)).unwrap(),
+        -F::from(1u64)
+    );
+    assert!(field_element_from_str::<F>(&modulus.to_string()).is_err());
+    assert!(field_element_from_str::<F>(&format!("0x{:x}", modulus)).is_err());
+
+    for invalid in [
+        "", "0x", "-1", "+1", " 1", "1 ", "1_000", "12a", "0xg", "0x-1", "1e3", "0b1",
+    ] {
+        assert!(field_element_from_str::<F>(invalid).is_err(), "{}", invalid);
+    }
+    assert!(field_element_from_str::<F>(&"0".repeat(MAX_INPUT_LENGTH)).is_ok());
+    assert!(field_element_from_str::<F>(&"0".repeat(MAX_INPUT_LENGTH + 1)).is_err());
+
+    assert_eq!(
+        field_elements_from_strs::<F, _>(&["1", "0x2"]).unwrap(),
+        vec![F::from(1u64), F::from(2u64)]
+    );
+    assert!(field_elements_from_strs::<F, _>(&["1", "x"]).is_err());
+}
+
+#[test]
+fn test_parsing() {
+    check_parsing::<ark_bn254::Fr>();
+    check_parsing::<ark_bls12_381::Fr>();
+}
+
+fn check_bytes<F: PrimeField>() {
+    let size = field_element_size::<F>();
+    assert_eq!(size, 32);
+
+    let mut bytes = vec![0u8; size];
+    bytes[0] = 1;
+    assert_eq!(
+        field_element_from_bytes::<F>(&bytes, Endianness::Little).unwrap(),
+        F::from(1u64)
+    );
+    assert_eq!(
+        field_element_from_bytes::<F>(&bytes, Endianness::Big).unwrap(),
+        F::from(2u64).pow([248])
+    );
+
+    // The largest element is accepted and the modulus is rejected in both byte orders.
+    let modulus: BigUint = F::MODULUS.into();
+    for (value, valid) in [(&modulus - 1u8, true), (modulus.clone(), false)] {
+        let mut le = value.to_bytes_le();
+        le.resize(size, 0);
+        let mut be = le.clone();
+        be.reverse();
+        assert_eq!(
+            field_element_from_bytes::<F>(&le, Endianness::Little).is_ok(),
+            valid
+        );
+        assert_eq!(
+            field_element_from_bytes::<F>(&be, Endianness::Big).is_ok(),
+            valid
+        );
+    }
+
+    // Wrong lengths.
+    assert!(field_element_from_bytes::<F>(&bytes[1..], Endianness::Little).is_err());
+    assert!(
+        field_element_from_bytes::<F>(&[bytes.as_slice(), &[0]].concat(), Endianness::Little)
+            .is_err()
+    );
+
+    let bytes = [bytes.clone(), vec![0u8; size]].concat();
+    assert_eq!(
+        field_elements_from_bytes::<F>(&bytes, Endianness::Little).unwrap(),
+        vec![F::from(1u64), F::zero()]
+    );
+    assert!(field_elements_from_bytes::<F>(&[], Endianness::Little)
+        .unwrap()
+        .is_empty());
+    assert!(field_elements_from_bytes::<F>(&bytes[1..], Endianness::Little).is_err());
+    let invalid = [bytes.as_slice(), &[0xff; 32]].concat();
+    assert!(field_elements_from_bytes::<F>(&invalid, Endianness::Little).is_err());
+}
+
+#[test]
+fn test_bytes() {
+    check_bytes::<ark_bn254::Fr>();
+    check_bytes::<ark_bls12_381::Fr>();
+
+    let mut bytes = [0u8; 64];
+    bytes[31] = 7;
+    let inputs = bn254::FieldElement::vec_from_bytes(&bytes, Endianness::Big).unwrap();
+    assert_eq!(inputs[0].0, ark_bn254::Fr::from(7u64));
+    assert_eq!(inputs[1].0, ark_bn254::Fr::zero());
+    let input = bls12381::FieldElement::from_bytes(&bytes[..32], Endianness::Big).unwrap();
+    assert_eq!(input.0, ark_bls12_381::Fr::from(7u64));
+    assert!(bls12381::FieldElement::from_bytes(&bytes, Endianness::Big).is_err());
+}
+
+#[test]
+fn test_limits() {
+    use fastcrypto::error::FastCryptoError;
+    use fastcrypto::limits::Limit;
+
+    let maximum = Limit::PublicInputs.default_maximum();
+    let bytes = vec![0u8; 32 * maximum];
+    assert_eq!(
+        field_elements_from_bytes::<ark_bn254::Fr>(&bytes, Endianness::Little)
+            .unwrap()
+            .len(),
+        maximum
+    );
+    let bytes = vec![0u8; 32 * (maximum + 1)];
+    assert_eq!(
+        field_elements_from_bytes::<ark_bn254::Fr>(&bytes, Endianness::Little),
+        Err(FastCryptoError::InputTooLong(maximum))
+    );
+    assert_eq!(
+        field_elements_from_strs::<ark_bn254::Fr, _>(&vec!["1"; maximum + 1]),
+        Err(FastCryptoError::InputTooLong(maximum))
+    );
+    assert_eq!(
+        bls12381::Proof::from_bytes(&vec![0; Limit::ProofBytes.default_maximum() + 1]),
+        Err(FastCryptoError::InputTooLong(
+            Limit::ProofBytes.default_maximum()
+        ))
+    );
+}
@@ -0,0 +1,85 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::pvk_cache::*;
+use crate::test_helpers::TestVector;
+use crate::{bls12381, bn254};
+use std::sync::Arc;
+
+fn bls12381_vk(seed: u64) -> bls12381::VerifyingKey {
+    TestVector::<ark_bls12_381::Bls12_381>::dummy(seed)
+        .unwrap()
+        .verifying_key
+        .into()
+}
+
+#[test]
+fn test_get_or_prepare() {
+    let cache = Bls12381PvkCache::new(2).unwrap();
+    assert_eq!(cache.capacity(), 2);
+    assert!(cache.is_empty());
+
+    let vk = bls12381_vk(0);
+    assert!(cache.get(&vk.digest()).is_none());
+    let pvk = cache.get_or_prepare(&vk);
+    assert_eq!(*pvk, bls12381::verifier::process_vk_special(&vk));
+    assert!(Arc::ptr_eq(&pvk, &cache.get_or_prepare(&vk)));
+    assert!(Arc::ptr_eq(&pvk, &cache.get(&vk.digest()).unwrap()));
+    assert_eq!(cache.len(), 1);
+
+    assert!(Arc::ptr_eq(&pvk, &cache.remove(&vk.digest()).unwrap()));
+    assert!(cache.remove(&vk.digest()).is_none());
+    assert!(!Arc::ptr_eq(&pvk, &cache.get_or_prepare(&vk)));
+    cache.clear();
+    assert!(cache.is_empty());
+
+    assert!(Bls12381PvkCache::new(0).is_err());
+}
+
+#[test]
+fn test_eviction() {
+    let cache = Bls12381PvkCache::new(2).unwrap();
+    let vks = (0..3).map(bls12381_vk).collect::<Vec<_>>();
+    cache.get_or_prepare(&vks[0]);
+    cache.get_or_prepare(&vks[1]);
+
+    // The first key was used more recently than the second, so the second is evicted.
+    cache.get(&vks[0].digest());
+    cache.get_or_prepare(&vks[2]);
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get(&vks[0].digest()).is_some());
+    assert!(cache.get(&vks[1].digest()).is_none());
+    assert!(cache.get(&vks[2].digest()).is_some());
+
+    // Now the first key is the least recently used.
+    cache.get_or_prepare(&vks[1]);
+    assert!(cache.get(&vks[0].digest()).is_none());
+    assert_eq!(cache.len(), 2);
+}
+
+#[test]
+fn test_concurrent_use() {
+    let vk: bn254::VerifyingKey = TestVector::<ark_bn254::Bn254>::dummy(0)
+        .unwrap()
+        .verifying_key
+        .into();
+    let cache = Arc::new(Bn254PvkCache::new(4).unwrap());
+
+    let handles = (0..8)
+        .map(|_| {
+            let cache = cache.clone();
+            let vk = vk.clone();
+            std::thread::spawn(move || cache.get_or_prepare(&vk))
+        })
+        .collect::<Vec<_>>();
+    let pvks = handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect::<Vec<_>>();
+
+    // All threads end up with the key in the cache, which is added only once.
+    assert_eq!(cache.len(), 1);
+    let cached = cache.get(&vk.digest()).unwrap();
+    assert!(pvks.iter().all(|pvk| **pvk == *cached));
+    assert_eq!(*cached, bn254::verifier::process_vk_special(&vk));
+}
@@ -0,0 +1,138 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::serialization::VERSION;
+use crate::test_helpers::TestVector;
+use crate::{bls12381, bn254};
+
+#[test]
+fn test_serialization_bls12381() {
+    use ark_bls12_381::Bls12_381;
+    use bls12381::verifier::{process_vk_special, verify_with_processed_vk, PreparedVerifyingKey};
+    use bls12381::{Proof, VerifyingKey};
+
+    let vector = TestVector::<Bls12_381>::dummy(0).unwrap();
+    let proof: Proof = vector.proof.into();
+    let vk: VerifyingKey = vector.verifying_key.into();
+    let pvk = process_vk_special(&vk);
+
+    let bytes = proof.to_bytes();
+    assert_eq!(bytes.len(), 1 + 48 + 96 + 48);
+    assert_eq!(bytes[0], VERSION);
+    assert_eq!(proof, Proof::from_bytes(&bytes).unwrap());
+
+    let bytes = vk.to_bytes();
+    assert_eq!(bytes.len(), 1 + 48 + 3 * 96 + 2 * 48);
+    assert_eq!(vk, VerifyingKey::from_bytes(&bytes).unwrap());
+
+    let bytes = pvk.to_bytes();
+    assert_eq!(bytes.len(), 1 + 576 + 2 * 96 + 2 * 48);
+    let reloaded = PreparedVerifyingKey::from_bytes(&bytes).unwrap();
+    assert_eq!(pvk, reloaded);
+    let v = vector.public_inputs[0];
+    assert!(verify_with_processed_vk(&reloaded, &[v.into()], &proof).unwrap());
+
+    // Unknown versions, trailing bytes and truncated encodings are rejected.
+    let mut bytes = proof.to_bytes();
+    bytes[0] = VERSION + 1;
+    assert!(Proof::from_bytes(&bytes).is_err());
+    let mut bytes = proof.to_bytes();
+    bytes.push(0);
+    assert!(Proof::from_bytes(&bytes).is_err());
+    assert!(Proof::from_bytes(&bytes[..bytes.len() - 2]).is_err());
+    assert!(Proof::from_bytes(&[]).is_err());
+    let bytes = pvk.to_bytes();
+    assert!(PreparedVerifyingKey::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    let bytes = vk.to_bytes();
+    assert!(VerifyingKey::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+    // Serde uses the same encodings.
+    let serialized = bincode::serialize(&pvk).unwrap();
+    assert_eq!(
+        pvk,
+        bincode::deserialize::<PreparedVerifyingKey>(&serialized).unwrap()
+    );
+    let serialized = serde_json::to_string(&proof).unwrap();
+    assert_eq!(proof, serde_json::from_str::<Proof>(&serialized).unwrap());
+    let serialized = serde_json::to_string(&vk).unwrap();
+    assert_eq!(
+        vk,
+        serde_json::from_str::<VerifyingKey>(&serialized).unwrap()
+    );
+}
+
+#[test]
+fn test_serialization_bn254() {
+    use ark_bn254::Bn254;
+    use bn254::verifier::{process_vk_special, verify_with_processed_vk, PreparedVerifyingKey};
+    use bn254::{Proof, VerifyingKey};
+
+    let vector = TestVector::<Bn254>::dummy(0).unwrap();
+    let proof: Proof = vector.proof.into();
+    let vk: VerifyingKey = vector.verifying_key.into();
+    let pvk = process_vk_special(&vk);
+
+    let bytes = proof.to_bytes();
+    assert_eq!(bytes.len(), 1 + 32 + 64 + 32);
+    assert_eq!(proof, Proof::from_bytes(&bytes).unwrap());
+
+    let bytes = vk.to_bytes();
+    assert_eq!(bytes.len(), 1 + 32 + 3 * 64 + 2 * 32);
+    assert_eq!(vk, VerifyingKey::from_bytes(&bytes).unwrap());
+
+    let bytes = pvk.to_bytes();
+    assert_eq!(bytes.len(), 1 + 384 + 2 * 64 + 2 * 32);
+    let reloaded = PreparedVerifyingKey::from_bytes(&bytes).unwrap();
+    assert_eq!(pvk, reloaded);
+    let v = vector.public_inputs[0];
+    assert!(verify_with_processed_vk(&reloaded, &[v.into()], &proof).unwrap());
+
+    let mut bytes = pvk.to_bytes();
+    bytes[0] = 0;
+    assert!(PreparedVerifyingKey::from_bytes(&bytes).is_err());
+
+    let serialized = bincode::serialize(&proof).unwrap();
+    assert_eq!(proof, bincode::deserialize::<Proof>(&serialized).unwrap());
+    let serialized = serde_json::to_string(&pvk).unwrap();
+    assert_eq!(
+        pvk,
+        serde_json::from_str::<PreparedVerifyingKey>(&serialized).unwrap()
+    );
+}
+
+#[test]
+fn test_verifying_key_digest() {
+    use fastcrypto::hash::{HashFunction, Sha256};
+
+    let bls12381_vk = |seed: u64| -> bls12381::VerifyingKey {
+        TestVector::<ark_bls12_381::Bls12_381>::dummy(seed)
+            .unwrap()
+            .verifying_key
+            .into()
+    };
+    let bn254_vk = |seed: u64| -> bn254::VerifyingKey {
+        TestVector::<ark_bn254::Bn254>::dummy(seed)
+            .unwrap()
+            .verifying_key
+            .into()
+    };
+
+    // The digest only depends on the key and is domain separated.
+    let vk = bls12381_vk(0);
+    let digest = vk.digest();
+    assert_eq!(digest, bls12381_vk(0).digest());
+    assert_eq!(
+        digest,
+        bls12381::VerifyingKey::from_bytes(&vk.to_bytes())
+            .unwrap()
+            .digest()
+    );
+    assert_ne!(digest, bls12381_vk(1).digest());
+    assert_ne!(digest, Sha256::digest(vk.to_bytes()).digest);
+    assert_ne!(digest, vk.inspect().digest);
+
+    let vk = bn254_vk(0);
+    assert_eq!(vk.digest(), bn254_vk(0).digest());
+    assert_ne!(vk.digest(), bn254_vk(1).digest());
+    assert_ne!(vk.digest(), digest);
+}
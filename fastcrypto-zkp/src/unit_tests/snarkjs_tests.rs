@@ -0,0 +1,252 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::dummy_circuits::DummyCircuit;
+use crate::snarkjs::*;
+use crate::{bls12381, bn254, fflonk};
+use ark_crypto_primitives::snark::SNARK;
+use ark_ec::pairing::Pairing;
+use ark_ec::short_weierstrass::{Affine, SWCurveConfig};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{PrimeField, QuadExtConfig, QuadExtField};
+use ark_groth16::Groth16;
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use ark_std::UniformRand;
+use num_bigint::BigUint;
+use serde_json::{json, Value};
+use std::ops::Mul;
+
+fn decimal<F: PrimeField>(x: &F) -> String {
+    BigUint::from(x.into_bigint()).to_string()
+}
+
+/// Encode a point as snarkjs does.
+fn g1_json<P: SWCurveConfig>(p: &Affine<P>) -> Value
+where
+    P::BaseField: PrimeField,
+{
+    match p.xy() {
+        Some((x, y)) => json!([decimal(x), decimal(y), "1"]),
+        None => json!(["0", "1", "0"]),
+    }
+}
+
+fn g2_json<P, Q>(p: &Affine<P>) -> Value
+where
+    P: SWCurveConfig<BaseField = QuadExtField<Q>>,
+    Q: QuadExtConfig,
+    Q::BaseField: PrimeField,
+{
+    let fq2 = |x: &QuadExtField<Q>| json!([decimal(&x.c0), decimal(&x.c1)]);
+    match p.xy() {
+        Some((x, y)) => json!([fq2(x), fq2(y), ["1", "0"]]),
+        None => json!([["0", "0"], ["1", "0"], ["0", "0"]]),
+    }
+}
+
+fn artifacts<E, P1, P2, Q>(curve: &str) -> (String, String, String)
+where
+    E: Pairing<G1Affine = Affine<P1>, G2Affine = Affine<P2>>,
+    P1: SWCurveConfig,
+    P1::BaseField: PrimeField,
+    P2: SWCurveConfig<BaseField = QuadExtField<Q>>,
+    Q: QuadExtConfig,
+    Q::BaseField: PrimeField,
+{
+    let rng = &mut StdRng::from_seed([0; 32]);
+    let c = DummyCircuit::<E::ScalarField> {
+        a: Some(E::ScalarField::rand(rng)),
+        b: Some(E::ScalarField::rand(rng)),
+        num_variables: 4,
+        num_constraints: 10,
+    };
+    let (pk, vk) = Groth16::<E>::circuit_specific_setup(c, rng).unwrap();
+    let proof = Groth16::<E>::prove(&pk, c, rng).unwrap();
+    let vk_json = json!({
+        "protocol": "groth16",
+        "curve": curve,
+        "nPublic": vk.gamma_abc_g1.len() - 1,
+        "vk_alpha_1": g1_json(&vk.alpha_g1),
+        "vk_beta_2": g2_json(&vk.beta_g2),
+        "vk_gamma_2": g2_json(&vk.gamma_g2),
+        "vk_delta_2": g2_json(&vk.delta_g2),
+        "vk_alphabeta_12": [],
+        "IC": vk.gamma_abc_g1.iter().map(g1_json).collect::<Vec<_>>(),
+    });
+    let proof_json = json!({
+        "pi_a": g1_json(&proof.a),
+        "pi_b": g2_json(&proof.b),
+        "pi_c": g1_json(&proof.c),
+        "protocol": "groth16",
+        "curve": curve,
+    });
+    let public_json = json!([decimal(&c.a.unwrap().mul(c.b.unwrap()))]);
+    (
+        vk_json.to_string(),
+        proof_json.to_string(),
+        public_json.to_string(),
+    )
+}
+
+#[test]
+fn test_bn254_generators() {
+    let g1 = r#"["1", "2", "1"]"#;
+    let g2 = r#"[
+        ["10857046999023057135944570762232829481370756359578518086990519993285655852781",
+         "11559732032986387107991004021392285783925812861821192530917403151452391805634"],
+        ["8495653923123431417604973247489272438418190587263600148770280649306958101930",
+         "4082367875863433681332203403145435568316851327593401208105741076214120093531"],
+        ["1", "0"]
+    ]"#;
+    let json = format!(
+        r#"{{"protocol": "groth16", "curve": "bn128", "nPublic": 1, "vk_alpha_1": {g1},
+        "vk_beta_2": {g2}, "vk_gamma_2": {g2}, "vk_delta_2": {g2}, "IC": [{g1}, ["0", "1", "0"]]}}"#
+    );
+    let vk = bn254_verifying_key_from_json(&json).unwrap();
+    assert_eq!(vk.0.alpha_g1, ark_bn254::G1Affine::generator());
+    assert_eq!(vk.0.beta_g2, ark_bn254::G2Affine::generator());
+    assert_eq!(
+        vk.0.gamma_abc_g1,
+        vec![
+            ark_bn254::G1Affine::generator(),
+            ark_bn254::G1Affine::zero()
+        ]
+    );
+
+    // Invalid metadata or points.
+    assert!(bn254_verifying_key_from_json(&json.replace("bn128", "bls12381")).is_err());
+    assert!(bn254_verifying_key_from_json(&json.replace("groth16", "plonk")).is_err());
+    assert!(
+        bn254_verifying_key_from_json(&json.replace("\"nPublic\": 1", "\"nPublic\": 2")).is_err()
+    );
+    assert!(bn254_verifying_key_from_json(
+        &json.replace(r#"["1", "2", "1"]"#, r#"["1", "3", "1"]"#)
+    )
+    .is_err());
+    assert!(bn254_verifying_key_from_json(
+        &json.replace(r#"["1", "2", "1"]"#, r#"["1", "2", "2"]"#)
+    )
+    .is_err());
+    assert!(
+        bn254_verifying_key_from_json(&json.replace(r#"["1", "0"]"#, r#"["0", "1"]"#)).is_err()
+    );
+    assert!(bn254_verifying_key_from_json("{}").is_err());
+}
+
+#[test]
+fn test_bn254_artifacts() {
+    let (vk_json, proof_json, public_json) = artifacts::<ark_bn254::Bn254, _, _, _>("bn128");
+    let vk = bn254_verifying_key_from_json(&vk_json).unwrap();
+    let proof = bn254_proof_from_json(&proof_json).unwrap();
+    let inputs = bn254_public_inputs_from_json(&public_json).unwrap();
+    let pvk = bn254::verifier::process_vk_special(&vk);
+    assert!(bn254::verifier::verify_with_processed_vk(&pvk, &inputs, &proof).unwrap());
+
+    assert!(bn254_proof_from_json(&proof_json.replace("bn128", "bls12381")).is_err());
+    assert!(bls12381_verifying_key_from_json(&vk_json).is_err());
+    assert!(bn254_public_inputs_from_json(r#"["0x10", "abc"]"#).is_err());
+}
+
+#[test]
+fn test_bls12381_artifacts() {
+    let (vk_json, proof_json, public_json) =
+        artifacts::<ark_bls12_381::Bls12_381, _, _, _>("bls12381");
+    let vk = bls12381_verifying_key_from_json(&vk_json).unwrap();
+    let proof = bls12381_proof_from_json(&proof_json).unwrap();
+    let inputs = bls12381_public_inputs_from_json(&public_json).unwrap();
+    let pvk = bls12381::verifier::process_vk_special(&vk);
+    assert!(bls12381::verifier::verify_with_processed_vk(&pvk, &inputs, &proof).unwrap());
+
+    assert!(bn254_proof_from_json(&proof_json).is_err());
+}
+
+#[test]
+fn test_bn254_fflonk_artifacts() {
+    let rng = &mut StdRng::from_seed([0; 32]);
+    let mut scalar = || ark_bn254::Fr::rand(rng);
+    let g1 = ark_bn254::G1Affine::generator();
+    let vk = fflonk::VerifyingKey::<ark_bn254::Bn254> {
+        power: 3,
+        num_public_inputs: 2,
+        k1: scalar(),
+        k2: scalar(),
+        w: scalar(),
+        w3: scalar(),
+        w4: scalar(),
+        w8: scalar(),
+        wr: scalar(),
+        c0: g1.mul(scalar()).into_affine(),
+        x_g2: ark_bn254::G2Affine::generator().mul(scalar()).into_affine(),
+    };
+    let vk_json = json!({
+        "protocol": "fflonk",
+        "curve": "bn128",
+        "nPublic": 2,
+        "power": 3,
+        "k1": decimal(&vk.k1),
+        "k2": decimal(&vk.k2),
+        "w": decimal(&vk.w),
+        "w3": decimal(&vk.w3),
+        "w4": decimal(&vk.w4),
+        "w8": decimal(&vk.w8),
+        "wr": decimal(&vk.wr),
+        "X_2": g2_json(&vk.x_g2),
+        "C0": g1_json(&vk.c0),
+    })
+    .to_string();
+    assert_eq!(bn254_fflonk_verifying_key_from_json(&vk_json).unwrap(), vk);
+    assert!(bn254_fflonk_verifying_key_from_json(&vk_json.replace("fflonk", "plonk")).is_err());
+    assert!(bn254_verifying_key_from_json(&vk_json).is_err());
+
+    let evaluations = (0..15).map(|_| scalar()).collect::<Vec<_>>();
+    let proof = fflonk::Proof::<ark_bn254::Bn254> {
+        c1: g1.mul(scalar()).into_affine(),
+        c2: g1.mul(scalar()).into_affine(),
+        w1: g1.mul(scalar()).into_affine(),
+        w2: ark_bn254::G1Affine::zero(),
+        q_l_eval: evaluations[0],
+        q_r_eval: evaluations[1],
+        q_m_eval: evaluations[2],
+        q_o_eval: evaluations[3],
+        q_c_eval: evaluations[4],
+        sigma_1_eval: evaluations[5],
+        sigma_2_eval: evaluations[6],
+        sigma_3_eval: evaluations[7],
+        a_eval: evaluations[8],
+        b_eval: evaluations[9],
+        c_eval: evaluations[10],
+        z_eval: evaluations[11],
+        z_omega_eval: evaluations[12],
+        t1_omega_eval: evaluations[13],
+        t2_omega_eval: evaluations[14],
+    };
+    let names = [
+        "ql", "qr", "qm", "qo", "qc", "s1", "s2", "s3", "a", "b", "c", "z", "zw", "t1w", "t2w",
+    ];
+    let mut evaluations_json = names
+        .iter()
+        .zip(&evaluations)
+        .map(|(name, x)| (name.to_string(), json!(decimal(x))))
+        .collect::<serde_json::Map<_, _>>();
+    evaluations_json.insert("inv".to_string(), json!(decimal(&scalar())));
+    let proof_json = json!({
+        "polynomials": {
+            "C1": g1_json(&proof.c1),
+            "C2": g1_json(&proof.c2),
+            "W1": g1_json(&proof.w1),
+            "W2": g1_json(&proof.w2),
+        },
+        "evaluations": evaluations_json,
+        "protocol": "fflonk",
+        "curve": "bn128",
+    })
+    .to_string();
+    assert_eq!(bn254_fflonk_proof_from_json(&proof_json).unwrap(), proof);
+    assert!(bn254_fflonk_proof_from_json(&proof_json.replace("bn128", "bls12381")).is_err());
+    assert!(bn254_fflonk_proof_from_json(&proof_json.replace("\"zw\"", "\"zx\"")).is_err());
+
+    assert_eq!(
+        bn254_fflonk_public_inputs_from_json(r#"["1", "33"]"#).unwrap(),
+        vec![ark_bn254::Fr::from(1u64), ark_bn254::Fr::from(33u64)]
+    );
+}
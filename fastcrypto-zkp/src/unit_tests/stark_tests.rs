@@ -0,0 +1,183 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::proof_system::ProofSystem;
+use crate::stark::{verify, Winterfell};
+use winterfell::crypto::hashers::Blake3_256;
+use winterfell::crypto::DefaultRandomCoin;
+use winterfell::math::fields::f128::BaseElement;
+use winterfell::math::{FieldElement, ToElements};
+use winterfell::matrix::ColMatrix;
+use winterfell::{
+    Air, AirContext, Assertion, AuxTraceRandElements, ByteReader, ByteWriter,
+    ConstraintCompositionCoefficients, DefaultConstraintEvaluator, DefaultTraceLde, Deserializable,
+    DeserializationError, EvaluationFrame, FieldExtension, ProofOptions, Prover, Serializable,
+    StarkDomain, Trace, TraceInfo, TracePolyTable, TraceTable, TransitionConstraintDegree,
+};
+
+/// The computation `x_{i+1} = x_i^3 + 42` from the README of Winterfell, with the public inputs
+/// `x_0` and `x_{n-1}`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct PublicInputs {
+    start: BaseElement,
+    result: BaseElement,
+}
+
+impl ToElements<BaseElement> for PublicInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        vec![self.start, self.result]
+    }
+}
+
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.start.write_into(target);
+        self.result.write_into(target);
+    }
+}
+
+impl Deserializable for PublicInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            start: BaseElement::read_from(source)?,
+            result: BaseElement::read_from(source)?,
+        })
+    }
+}
+
+struct WorkAir {
+    context: AirContext<BaseElement>,
+    start: BaseElement,
+    result: BaseElement,
+}
+
+impl Air for WorkAir {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(3)];
+        Self {
+            context: AirContext::new(trace_info, degrees, 2, options),
+            start: pub_inputs.start,
+            result: pub_inputs.result,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current()[0];
+        result[0] = frame.next()[0] - (current.exp(3u32.into()) + E::from(42u32));
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, self.start),
+            Assertion::single(0, last_step, self.result),
+        ]
+    }
+}
+
+struct WorkProver {
+    options: ProofOptions,
+}
+
+impl Prover for WorkProver {
+    type BaseField = BaseElement;
+    type Air = WorkAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3_256<BaseElement>;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintEvaluator<'a, Self::Air, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
+        PublicInputs {
+            start: trace.get(0, 0),
+            result: trace.get(0, trace.length() - 1),
+        }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_trace_lde<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
+        DefaultTraceLde::new(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: AuxTraceRandElements<E>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E> {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+}
+
+fn options(num_queries: usize) -> ProofOptions {
+    ProofOptions::new(num_queries, 8, 0, FieldExtension::None, 8, 31)
+}
+
+/// The serialized options, proof and public inputs of a computation with 1024 steps.
+fn serialized_proof(options: ProofOptions) -> (Vec<u8>, Vec<u8>, PublicInputs) {
+    let mut trace = TraceTable::new(1, 1024);
+    trace.fill(
+        |state| state[0] = BaseElement::new(3),
+        |_, state| state[0] = state[0].exp(3u32.into()) + BaseElement::new(42),
+    );
+    let prover = WorkProver { options };
+    let public_inputs = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+    (prover.options.to_bytes(), proof.to_bytes(), public_inputs)
+}
+
+type WorkStark = Winterfell<WorkAir, Blake3_256<BaseElement>>;
+
+#[test]
+fn test_verify() {
+    let (vk, proof, public_inputs) = serialized_proof(options(32));
+    assert!(WorkStark::verify(&vk, &proof, &public_inputs.to_bytes()).unwrap());
+    assert!(
+        verify::<WorkAir, Blake3_256<BaseElement>>(&vk, &proof, &public_inputs.to_bytes()).unwrap()
+    );
+
+    // Other public inputs.
+    let other = PublicInputs {
+        result: public_inputs.result + BaseElement::ONE,
+        ..public_inputs
+    };
+    assert!(!WorkStark::verify(&vk, &proof, &other.to_bytes()).unwrap());
+
+    // Proofs with other options than the accepted ones are rejected.
+    assert!(
+        !WorkStark::verify(&options(40).to_bytes(), &proof, &public_inputs.to_bytes()).unwrap()
+    );
+    let (_, weak_proof, _) = serialized_proof(options(16));
+    assert!(!WorkStark::verify(&vk, &weak_proof, &public_inputs.to_bytes()).unwrap());
+}
+
+#[test]
+fn test_malformed_inputs() {
+    let (vk, proof, public_inputs) = serialized_proof(options(32));
+    let public_inputs = public_inputs.to_bytes();
+    assert!(WorkStark::verify(&vk[1..], &proof, &public_inputs).is_err());
+    assert!(WorkStark::verify(&vk, &proof[..proof.len() / 2], &public_inputs).is_err());
+    assert!(WorkStark::verify(&vk, &proof, &public_inputs[1..]).is_err());
+}
@@ -0,0 +1,166 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::bench_circuits::Shaped;
+use crate::error::ZkError;
+use crate::inspect::Curve;
+use crate::proof_system::{Bls12381Groth16, Bn254Groth16, ProofSystem};
+use crate::test_helpers::*;
+use crate::{bls12381, bn254};
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_groth16::Proof;
+use ark_serialize::CanonicalSerialize;
+use std::ops::Neg;
+
+fn test_generated_vectors<P: ProofSystem, E: Pairing>() {
+    for num_public_inputs in [0, 1, 4] {
+        let vector = TestVector::<E>::generate(num_public_inputs, 10, 7).unwrap();
+        assert_eq!(vector.public_inputs.len(), num_public_inputs);
+        assert_eq!(vector.public_inputs_bytes().len(), 32 * num_public_inputs);
+        assert!(P::verify(
+            &vector.vk_bytes(),
+            &vector.proof_bytes(),
+            &vector.public_inputs_bytes()
+        )
+        .unwrap());
+
+        assert!(!P::verify(
+            &vector.vk_bytes(),
+            &bytes(&vector.invalid_proof()),
+            &vector.public_inputs_bytes()
+        )
+        .unwrap());
+    }
+
+    assert!(TestVector::<E>::generate(1, 0, 7).is_err());
+
+    // Vectors are deterministic.
+    let vector = TestVector::<E>::generate(2, 10, 1).unwrap();
+    let same = TestVector::<E>::generate(2, 10, 1).unwrap();
+    let other = TestVector::<E>::generate(2, 10, 2).unwrap();
+    assert_eq!(vector.vk_bytes(), same.vk_bytes());
+    assert_eq!(vector.proof_bytes(), same.proof_bytes());
+    assert_eq!(vector.public_inputs, same.public_inputs);
+    assert_ne!(vector.vk_bytes(), other.vk_bytes());
+    assert_ne!(vector.public_inputs, other.public_inputs);
+
+    // The vector is generated with keypair and prove from the same seed.
+    let circuit = Shaped::new(vector.public_inputs.clone(), 10).unwrap();
+    assert_eq!(
+        prove(&vector.proving_key, circuit.clone(), 1).unwrap(),
+        vector.proof
+    );
+    let (_, verifying_key) = keypair::<E, _>(circuit, 1).unwrap();
+    assert_eq!(verifying_key, vector.verifying_key);
+
+    // Vectors of the dummy circuit are valid and deterministic too.
+    let vector = TestVector::<E>::dummy(3).unwrap();
+    assert_eq!(vector.public_inputs.len(), 1);
+    assert!(P::verify(
+        &vector.vk_bytes(),
+        &vector.proof_bytes(),
+        &vector.public_inputs_bytes()
+    )
+    .unwrap());
+    let same = TestVector::<E>::dummy(3).unwrap();
+    assert_eq!(vector.proof_bytes(), same.proof_bytes());
+    assert_ne!(
+        vector.vk_bytes(),
+        TestVector::<E>::dummy(4).unwrap().vk_bytes()
+    );
+}
+
+#[test]
+fn test_bn254_generated_vectors() {
+    test_generated_vectors::<Bn254Groth16, ark_bn254::Bn254>();
+}
+
+#[test]
+fn test_bls12381_generated_vectors() {
+    test_generated_vectors::<Bls12381Groth16, ark_bls12_381::Bls12_381>();
+}
+
+fn bytes<T: CanonicalSerialize>(value: &T) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    value.serialize_compressed(&mut bytes).unwrap();
+    bytes
+}
+
+fn neg<A: AffineRepr>(p: A) -> A {
+    p.into_group().neg().into_affine()
+}
+
+/// The golden vectors encode the verifying key and proofs given in their documentation.
+fn test_golden_encodings<E: Pairing>(curve: Curve) {
+    let g = E::G1Affine::generator();
+    let h = E::G2Affine::generator();
+    let vk = ark_groth16::VerifyingKey::<E> {
+        alpha_g1: g,
+        beta_g2: h,
+        gamma_g2: h,
+        delta_g2: h,
+        gamma_abc_g1: vec![neg(g), g],
+    };
+    let proof = |c| bytes(&Proof::<E> { a: g, b: h, c });
+    let input = |x: u64| bytes(&E::ScalarField::from(x));
+
+    let vectors = golden_vectors(curve);
+    assert_eq!(vectors.len(), 3);
+    assert!(vectors.iter().all(|v| v.vk == bytes(&vk)));
+    assert_eq!(vectors[0].proof, proof(neg(g)));
+    assert_eq!(vectors[0].public_inputs, input(2));
+    assert_eq!(vectors[1].proof, proof(neg(g)));
+    assert_eq!(vectors[1].public_inputs, input(3));
+    assert_eq!(vectors[2].proof, proof(g));
+    assert_eq!(vectors[2].public_inputs, input(2));
+}
+
+#[test]
+fn test_golden_vectors() {
+    test_golden_encodings::<ark_bn254::Bn254>(Curve::Bn254);
+    test_golden_encodings::<ark_bls12_381::Bls12_381>(Curve::Bls12381);
+
+    for vector in golden_vectors(Curve::Bn254) {
+        assert_eq!(
+            Bn254Groth16::verify(&vector.vk, &vector.proof, &vector.public_inputs).unwrap(),
+            vector.valid,
+            "{}",
+            vector.description
+        );
+        let pvk = bn254::api::prepare_pvk_bytes(&vector.vk).unwrap();
+        let result = bn254::api::verify_groth16_in_bytes_checked(
+            &pvk[0],
+            &pvk[1],
+            &pvk[2],
+            &pvk[3],
+            &vector.public_inputs,
+            &vector.proof,
+        );
+        assert_eq!(result.is_ok(), vector.valid);
+        assert!(vector.valid || result == Err(ZkError::VerificationFailed));
+    }
+    for vector in golden_vectors(Curve::Bls12381) {
+        assert_eq!(
+            Bls12381Groth16::verify(&vector.vk, &vector.proof, &vector.public_inputs).unwrap(),
+            vector.valid,
+            "{}",
+            vector.description
+        );
+        let pvk = bls12381::api::prepare_pvk_bytes(&vector.vk).unwrap();
+        let result = bls12381::api::verify_groth16_in_bytes_checked(
+            &pvk[0],
+            &pvk[1],
+            &pvk[2],
+            &pvk[3],
+            &vector.public_inputs,
+            &vector.proof,
+        );
+        assert_eq!(result.is_ok(), vector.valid);
+        assert!(vector.valid || result == Err(ZkError::VerificationFailed));
+    }
+
+    // The vectors of the two curves are not interchangeable.
+    let vector = &golden_vectors(Curve::Bn254)[0];
+    assert!(Bls12381Groth16::verify(&vector.vk, &vector.proof, &vector.public_inputs).is_err());
+}
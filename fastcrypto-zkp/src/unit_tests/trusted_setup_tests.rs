@@ -0,0 +1,553 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::bls12381::conversions::{
+    bls_g1_affine_to_blst_g1_affine, bls_g2_affine_to_blst_g2_affine,
+};
+use crate::trusted_setup::*;
+use ark_bls12_381::{Fq, Fr, G1Affine, G2Affine};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, Field, PrimeField, UniformRand};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use blst::{
+    blst_p1_affine_compress, blst_p1_affine_serialize, blst_p2_affine_compress,
+    blst_p2_affine_serialize,
+};
+
+const POWER: u32 = 2;
+
+/// The secrets of a ceremony.
+#[derive(Clone, Copy)]
+struct Secrets {
+    tau: Fr,
+    alpha: Fr,
+    beta: Fr,
+}
+
+impl Secrets {
+    fn one() -> Self {
+        Self {
+            tau: Fr::from(1u64),
+            alpha: Fr::from(1u64),
+            beta: Fr::from(1u64),
+        }
+    }
+
+    fn random(rng: &mut StdRng) -> Self {
+        Self {
+            tau: Fr::rand(rng),
+            alpha: Fr::rand(rng),
+            beta: Fr::rand(rng),
+        }
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        Self {
+            tau: self.tau * other.tau,
+            alpha: self.alpha * other.alpha,
+            beta: self.beta * other.beta,
+        }
+    }
+
+    fn accumulator(&self, power: u32) -> Accumulator {
+        let n = 1usize << power;
+        let powers = (0..2 * n - 1)
+            .map(|i| self.tau.pow([i as u64]))
+            .collect::<Vec<_>>();
+        let g1 = |s: Fr| (G1Affine::generator() * s).into_affine();
+        let g2 = |s: Fr| (G2Affine::generator() * s).into_affine();
+        Accumulator {
+            tau_g1: powers.iter().map(|p| g1(*p)).collect(),
+            tau_g2: powers[..n].iter().map(|p| g2(*p)).collect(),
+            alpha_tau_g1: powers[..n].iter().map(|p| g1(self.alpha * p)).collect(),
+            beta_tau_g1: powers[..n].iter().map(|p| g1(self.beta * p)).collect(),
+            beta_g2: g2(self.beta),
+        }
+    }
+}
+
+fn key(x: &Fr, rng: &mut StdRng) -> ContributionKey {
+    let s = G1Affine::generator() * Fr::rand(rng);
+    let sp = G2Affine::generator() * Fr::rand(rng);
+    ContributionKey {
+        g1_s: s.into_affine(),
+        g1_sx: (s * x).into_affine(),
+        g2_spx: (sp * x).into_affine(),
+    }
+}
+
+fn public_key(contribution: &Secrets, rng: &mut StdRng) -> PublicKey {
+    PublicKey {
+        tau: key(&contribution.tau, rng),
+        alpha: key(&contribution.alpha, rng),
+        beta: key(&contribution.beta, rng),
+    }
+}
+
+/// A ceremony with the given number of honest contributions.
+fn ceremony(contributions: usize, rng: &mut StdRng) -> Ptau {
+    let mut secrets = Secrets::one();
+    let mut ptau_contributions = vec![];
+    for i in 0..contributions {
+        let contribution = Secrets::random(rng);
+        secrets = secrets.mul(&contribution);
+        ptau_contributions.push(PtauContribution {
+            after: secrets.accumulator(1).head().unwrap(),
+            key: public_key(&contribution, rng),
+            next_challenge: [i as u8; 64],
+            contribution_type: 0,
+        });
+    }
+    Ptau {
+        power: POWER,
+        ceremony_power: POWER,
+        accumulator: secrets.accumulator(POWER),
+        contributions: ptau_contributions,
+    }
+}
+
+fn write_fq(bytes: &mut Vec<u8>, f: &Fq) {
+    // The Montgomery form of the element, as used by snarkjs.
+    bytes.extend(f.0.to_bytes_le());
+}
+
+fn write_g1(bytes: &mut Vec<u8>, p: &G1Affine) {
+    match p.xy() {
+        Some((x, y)) => {
+            write_fq(bytes, x);
+            write_fq(bytes, y);
+        }
+        None => bytes.extend([0u8; 96]),
+    }
+}
+
+fn write_g2(bytes: &mut Vec<u8>, p: &G2Affine) {
+    match p.xy() {
+        Some((x, y)) => {
+            for c in [x.c0, x.c1, y.c0, y.c1] {
+                write_fq(bytes, &c);
+            }
+        }
+        None => bytes.extend([0u8; 192]),
+    }
+}
+
+fn write_modulus<F: PrimeField>(bytes: &mut Vec<u8>) {
+    let modulus = F::MODULUS.to_bytes_le();
+    bytes.extend((modulus.len() as u32).to_le_bytes());
+    bytes.extend(modulus);
+}
+
+fn write_sections(magic: &[u8; 4], sections: &[(u32, Vec<u8>)]) -> Vec<u8> {
+    let mut bytes = magic.to_vec();
+    bytes.extend(1u32.to_le_bytes());
+    bytes.extend((sections.len() as u32).to_le_bytes());
+    for (section_type, data) in sections {
+        bytes.extend(section_type.to_le_bytes());
+        bytes.extend((data.len() as u64).to_le_bytes());
+        bytes.extend(data);
+    }
+    bytes
+}
+
+fn ptau_sections(ptau: &Ptau) -> Vec<(u32, Vec<u8>)> {
+    let g1s = |points: &[G1Affine]| {
+        let mut bytes = vec![];
+        points.iter().for_each(|p| write_g1(&mut bytes, p));
+        bytes
+    };
+    let mut header = vec![];
+    write_modulus::<Fq>(&mut header);
+    header.extend(ptau.power.to_le_bytes());
+    header.extend(ptau.ceremony_power.to_le_bytes());
+
+    let mut tau_g2 = vec![];
+    ptau.accumulator
+        .tau_g2
+        .iter()
+        .for_each(|p| write_g2(&mut tau_g2, p));
+    let mut beta_g2 = vec![];
+    write_g2(&mut beta_g2, &ptau.accumulator.beta_g2);
+
+    let mut contributions = (ptau.contributions.len() as u32).to_le_bytes().to_vec();
+    for c in &ptau.contributions {
+        write_g1(&mut contributions, &c.after.tau_g1);
+        write_g2(&mut contributions, &c.after.tau_g2);
+        write_g1(&mut contributions, &c.after.alpha_g1);
+        write_g1(&mut contributions, &c.after.beta_g1);
+        write_g2(&mut contributions, &c.after.beta_g2);
+        for k in [&c.key.tau, &c.key.alpha, &c.key.beta] {
+            write_g1(&mut contributions, &k.g1_s);
+            write_g1(&mut contributions, &k.g1_sx);
+        }
+        for k in [&c.key.tau, &c.key.alpha, &c.key.beta] {
+            write_g2(&mut contributions, &k.g2_spx);
+        }
+        contributions.extend([0u8; 216]);
+        contributions.extend(c.next_challenge);
+        contributions.extend(c.contribution_type.to_le_bytes());
+        // A name as the only parameter.
+        contributions.extend(6u32.to_le_bytes());
+        contributions.extend([1, 4, b'n', b'a', b'm', b'e']);
+    }
+
+    vec![
+        (1, header),
+        (2, g1s(&ptau.accumulator.tau_g1)),
+        (3, tau_g2),
+        (4, g1s(&ptau.accumulator.alpha_tau_g1)),
+        (5, g1s(&ptau.accumulator.beta_tau_g1)),
+        (6, beta_g2),
+        (7, contributions),
+    ]
+}
+
+#[test]
+fn test_ptau() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let ptau = ceremony(3, &mut rng);
+    let bytes = write_sections(b"ptau", &ptau_sections(&ptau));
+    let parsed = Ptau::from_bytes(&bytes).unwrap();
+    assert_eq!(parsed, ptau);
+    assert_eq!(parsed.accumulator.power().unwrap(), POWER);
+    assert!(parsed.verify().unwrap());
+
+    // A ceremony without contributions.
+    assert!(ceremony(0, &mut rng).verify().unwrap());
+
+    // Sections may be in any order and unknown sections are ignored.
+    let mut sections = ptau_sections(&ptau);
+    sections.reverse();
+    sections.push((12, vec![1, 2, 3]));
+    assert_eq!(
+        Ptau::from_bytes(&write_sections(b"ptau", &sections)).unwrap(),
+        ptau
+    );
+}
+
+#[test]
+fn test_invalid_accumulator() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let accumulator = Secrets::random(&mut rng).accumulator(POWER);
+    assert!(accumulator.verify().unwrap());
+
+    let other = Secrets::random(&mut rng).accumulator(POWER);
+    let mut modified = accumulator.clone();
+    modified.tau_g1[5] = other.tau_g1[5];
+    assert!(!modified.verify().unwrap());
+
+    let mut modified = accumulator.clone();
+    modified.tau_g2[3] = other.tau_g2[3];
+    assert!(!modified.verify().unwrap());
+
+    let mut modified = accumulator.clone();
+    modified.alpha_tau_g1[2] = other.alpha_tau_g1[2];
+    assert!(!modified.verify().unwrap());
+
+    let mut modified = accumulator.clone();
+    modified.beta_tau_g1[1] = other.beta_tau_g1[1];
+    assert!(!modified.verify().unwrap());
+
+    let mut modified = accumulator.clone();
+    modified.beta_g2 = other.beta_g2;
+    assert!(!modified.verify().unwrap());
+
+    // Tau is zero.
+    let mut secrets = Secrets::random(&mut rng);
+    secrets.tau = Fr::from(0u64);
+    assert!(!secrets.accumulator(POWER).verify().unwrap());
+
+    let mut modified = accumulator.clone();
+    modified.tau_g1.pop();
+    assert!(modified.verify().is_err());
+    assert!(modified.head().is_err());
+}
+
+#[test]
+fn test_invalid_contributions() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let ptau = ceremony(2, &mut rng);
+    assert!(ptau.verify().unwrap());
+
+    // A key which does not prove knowledge of the contribution.
+    let mut modified = ptau.clone();
+    modified.contributions[1].key.tau = key(&Fr::rand(&mut rng), &mut rng);
+    assert!(!modified.verify().unwrap());
+    let mut modified = ptau.clone();
+    modified.contributions[0].key.beta = key(&Fr::rand(&mut rng), &mut rng);
+    assert!(!modified.verify().unwrap());
+
+    // A contribution which updates the points inconsistently.
+    let mut modified = ptau.clone();
+    modified.contributions[0].after.tau_g1 = G1Affine::generator();
+    assert!(!modified.verify().unwrap());
+
+    // The accumulator is not the result of the last contribution.
+    let mut modified = ptau.clone();
+    modified.accumulator = Secrets::random(&mut rng).accumulator(POWER);
+    assert!(!modified.verify().unwrap());
+    let mut modified = ptau.clone();
+    modified.contributions.pop();
+    assert!(!modified.verify().unwrap());
+}
+
+#[test]
+fn test_invalid_ptau_files() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let ptau = ceremony(1, &mut rng);
+    let sections = ptau_sections(&ptau);
+    let bytes = write_sections(b"ptau", &sections);
+    assert!(Ptau::from_bytes(&bytes).is_ok());
+
+    assert!(Ptau::from_bytes(&write_sections(b"zkey", &sections)).is_err());
+    assert!(Ptau::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    let mut longer = bytes.clone();
+    longer.push(0);
+    assert!(Ptau::from_bytes(&longer).is_err());
+
+    // A missing or duplicated section.
+    assert!(Ptau::from_bytes(&write_sections(b"ptau", &sections[..6])).is_err());
+    let mut duplicated = sections.clone();
+    duplicated.push(sections[6].clone());
+    assert!(Ptau::from_bytes(&write_sections(b"ptau", &duplicated)).is_err());
+
+    // Another curve.
+    let mut modified = sections.clone();
+    modified[0].1 = vec![];
+    write_modulus::<ark_bn254::Fq>(&mut modified[0].1);
+    modified[0].1.extend(POWER.to_le_bytes());
+    modified[0].1.extend(POWER.to_le_bytes());
+    assert!(Ptau::from_bytes(&write_sections(b"ptau", &modified)).is_err());
+
+    // A coordinate which is not reduced.
+    let mut modified = sections.clone();
+    modified[1].1[48..96].copy_from_slice(&Fq::MODULUS.to_bytes_le());
+    assert!(Ptau::from_bytes(&write_sections(b"ptau", &modified)).is_err());
+
+    // A point which is not on the curve.
+    let mut modified = sections.clone();
+    modified[1].1[96] ^= 1;
+    assert!(Ptau::from_bytes(&write_sections(b"ptau", &modified)).is_err());
+
+    // A point which is on the curve but not in the subgroup.
+    let mut modified = sections.clone();
+    let mut x = Fq::from(1u64);
+    let point = loop {
+        if let Some(p) = G1Affine::get_point_from_x_unchecked(x, false) {
+            if !p.is_in_correct_subgroup_assuming_on_curve() {
+                break p;
+            }
+        }
+        x += Fq::from(1u64);
+    };
+    let mut encoded = vec![];
+    write_g1(&mut encoded, &point);
+    modified[1].1[96..192].copy_from_slice(&encoded);
+    assert!(Ptau::from_bytes(&write_sections(b"ptau", &modified)).is_err());
+}
+
+/// Encode an accumulator in the zcash encoding of the powers of tau ceremony.
+fn write_zcash_accumulator(bytes: &mut Vec<u8>, accumulator: &Accumulator, compressed: bool) {
+    let g1 = |bytes: &mut Vec<u8>, p: &G1Affine| {
+        let p = bls_g1_affine_to_blst_g1_affine(p);
+        let mut out = [0u8; 96];
+        unsafe {
+            if compressed {
+                blst_p1_affine_compress(out.as_mut_ptr(), &p);
+            } else {
+                blst_p1_affine_serialize(out.as_mut_ptr(), &p);
+            }
+        }
+        bytes.extend(&out[..if compressed { 48 } else { 96 }]);
+    };
+    let g2 = |bytes: &mut Vec<u8>, p: &G2Affine| {
+        let p = bls_g2_affine_to_blst_g2_affine(p);
+        let mut out = [0u8; 192];
+        unsafe {
+            if compressed {
+                blst_p2_affine_compress(out.as_mut_ptr(), &p);
+            } else {
+                blst_p2_affine_serialize(out.as_mut_ptr(), &p);
+            }
+        }
+        bytes.extend(&out[..if compressed { 96 } else { 192 }]);
+    };
+    accumulator.tau_g1.iter().for_each(|p| g1(bytes, p));
+    accumulator.tau_g2.iter().for_each(|p| g2(bytes, p));
+    accumulator.alpha_tau_g1.iter().for_each(|p| g1(bytes, p));
+    accumulator.beta_tau_g1.iter().for_each(|p| g1(bytes, p));
+    g2(bytes, &accumulator.beta_g2);
+}
+
+#[test]
+fn test_ppot() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let before = Secrets::random(&mut rng);
+    let contribution = Secrets::random(&mut rng);
+    let after = before.mul(&contribution);
+    let key = public_key(&contribution, &mut rng);
+
+    let mut challenge = [1u8; 64].to_vec();
+    write_zcash_accumulator(&mut challenge, &before.accumulator(POWER), false);
+    let (hash, challenge_accumulator) =
+        Accumulator::from_ppot_challenge(&challenge, POWER).unwrap();
+    assert_eq!(hash, [1u8; 64]);
+    assert_eq!(challenge_accumulator, before.accumulator(POWER));
+    assert!(Accumulator::from_ppot_challenge(&challenge, POWER + 1).is_err());
+    assert!(Accumulator::from_ppot_challenge(&challenge[..challenge.len() - 1], POWER).is_err());
+
+    let mut response = [2u8; 64].to_vec();
+    write_zcash_accumulator(&mut response, &after.accumulator(POWER), true);
+    let mut key_bytes = vec![];
+    for k in [&key.tau, &key.alpha, &key.beta] {
+        write_zcash_points(&mut key_bytes, &[k.g1_s, k.g1_sx], &[]);
+    }
+    for k in [&key.tau, &key.alpha, &key.beta] {
+        write_zcash_points(&mut key_bytes, &[], &[k.g2_spx]);
+    }
+    response.extend(&key_bytes);
+    let (hash, response_accumulator, response_key) =
+        Accumulator::from_ppot_response(&response, POWER).unwrap();
+    assert_eq!(hash, [2u8; 64]);
+    assert_eq!(response_accumulator, after.accumulator(POWER));
+    assert_eq!(response_key, key);
+
+    assert!(response_accumulator.verify().unwrap());
+    assert!(verify_transform(
+        &challenge_accumulator.head().unwrap(),
+        &response_accumulator.head().unwrap(),
+        &response_key
+    ));
+    assert!(!verify_transform(
+        &challenge_accumulator.head().unwrap(),
+        &Secrets::random(&mut rng).accumulator(POWER).head().unwrap(),
+        &response_key
+    ));
+
+    // An uncompressed point in a response.
+    let mut modified = response.clone();
+    modified[64] &= 0x7f;
+    assert!(Accumulator::from_ppot_response(&modified, POWER).is_err());
+    // An invalid encoding of a point.
+    let mut modified = challenge.clone();
+    modified[64 + 96..64 + 192].copy_from_slice(&[0xff; 96]);
+    assert!(Accumulator::from_ppot_challenge(&modified, POWER).is_err());
+}
+
+/// Encode points in the uncompressed zcash encoding.
+fn write_zcash_points(bytes: &mut Vec<u8>, g1: &[G1Affine], g2: &[G2Affine]) {
+    for p in g1 {
+        let mut out = [0u8; 96];
+        unsafe { blst_p1_affine_serialize(out.as_mut_ptr(), &bls_g1_affine_to_blst_g1_affine(p)) };
+        bytes.extend(out);
+    }
+    for p in g2 {
+        let mut out = [0u8; 192];
+        unsafe { blst_p2_affine_serialize(out.as_mut_ptr(), &bls_g2_affine_to_blst_g2_affine(p)) };
+        bytes.extend(out);
+    }
+}
+
+/// The hash to G2 used by the tests instead of the one of snarkjs.
+fn g2_sp(transcript: &[u8; 64]) -> G2Affine {
+    (G2Affine::generator() * Fr::from_le_bytes_mod_order(transcript)).into_affine()
+}
+
+fn zkey_bytes(num_public_inputs: u32, deltas: &[Fr], rng: &mut StdRng) -> (Vec<u8>, Vec<G1Affine>) {
+    let g1 = |s: Fr| (G1Affine::generator() * s).into_affine();
+    let g2 = |s: Fr| (G2Affine::generator() * s).into_affine();
+    let delta = deltas.iter().product::<Fr>();
+
+    let mut header = vec![];
+    write_modulus::<Fq>(&mut header);
+    write_modulus::<Fr>(&mut header);
+    header.extend(10u32.to_le_bytes());
+    header.extend(num_public_inputs.to_le_bytes());
+    header.extend(16u32.to_le_bytes());
+    let (alpha, beta, gamma) = (Fr::rand(rng), Fr::rand(rng), Fr::rand(rng));
+    write_g1(&mut header, &g1(alpha));
+    write_g1(&mut header, &g1(beta));
+    write_g2(&mut header, &g2(beta));
+    write_g2(&mut header, &g2(gamma));
+    write_g1(&mut header, &g1(delta));
+    write_g2(&mut header, &g2(delta));
+
+    let ic = (0..=num_public_inputs)
+        .map(|_| g1(Fr::rand(rng)))
+        .collect::<Vec<_>>();
+    let mut ic_bytes = vec![];
+    ic.iter().for_each(|p| write_g1(&mut ic_bytes, p));
+
+    let mut contributions = [3u8; 64].to_vec();
+    contributions.extend((deltas.len() as u32).to_le_bytes());
+    let mut current = Fr::from(1u64);
+    for (i, x) in deltas.iter().enumerate() {
+        current *= x;
+        let transcript = [i as u8 + 1; 64];
+        let s = g1(Fr::rand(rng));
+        write_g1(&mut contributions, &g1(current));
+        write_g1(&mut contributions, &s);
+        write_g1(&mut contributions, &(s * x).into_affine());
+        write_g2(&mut contributions, &(g2_sp(&transcript) * x).into_affine());
+        contributions.extend(transcript);
+        contributions.extend(0u32.to_le_bytes());
+        contributions.extend(0u32.to_le_bytes());
+    }
+
+    let bytes = write_sections(
+        b"zkey",
+        &[
+            (1, 1u32.to_le_bytes().to_vec()),
+            (2, header),
+            (3, ic_bytes),
+            // The proving key, which is ignored.
+            (4, vec![0; 12]),
+            (10, contributions),
+        ],
+    );
+    (bytes, ic)
+}
+
+#[test]
+fn test_zkey() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let deltas = [Fr::rand(&mut rng), Fr::rand(&mut rng)];
+    let (bytes, ic) = zkey_bytes(2, &deltas, &mut rng);
+    let zkey = Zkey::from_bytes(&bytes).unwrap();
+    assert_eq!(zkey.contributions.len(), 2);
+    assert_eq!(zkey.circuit_hash, [3u8; 64]);
+    assert_eq!(zkey.verifying_key.0.gamma_abc_g1, ic);
+    assert_eq!(
+        zkey.delta_g1,
+        (G1Affine::generator() * deltas[0] * deltas[1]).into_affine()
+    );
+    assert!(zkey.verify_contributions(g2_sp));
+
+    // Another hash to G2.
+    assert!(!zkey.verify_contributions(|_| G2Affine::generator()));
+
+    // A contribution which does not match its proof of knowledge.
+    let mut modified = zkey.clone();
+    modified.contributions[0].key.g1_sx = modified.contributions[0].key.g1_s;
+    assert!(!modified.verify_contributions(g2_sp));
+    let mut modified = zkey.clone();
+    modified.contributions[1].delta_after = G1Affine::generator();
+    assert!(!modified.verify_contributions(g2_sp));
+
+    // The keys are not the result of the last contribution.
+    let mut modified = zkey.clone();
+    modified.contributions.pop();
+    assert!(!modified.verify_contributions(g2_sp));
+    let mut modified = zkey.clone();
+    modified.verifying_key.0.delta_g2 = G2Affine::generator();
+    assert!(!modified.verify_contributions(g2_sp));
+
+    // No contributions.
+    let (bytes, _) = zkey_bytes(1, &[], &mut rng);
+    assert!(Zkey::from_bytes(&bytes)
+        .unwrap()
+        .verify_contributions(g2_sp));
+
+    assert!(Zkey::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    assert!(Ptau::from_bytes(&bytes).is_err());
+}
@@ -0,0 +1,54 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::verification_hook::{
+    clear_verification_failure_hook, set_verification_failure_hook, VerificationFailure,
+};
+use fastcrypto::encoding::{Encoding, Hex};
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn test_verification_failure_hook() {
+    let reports = Arc::new(Mutex::new(Vec::<VerificationFailure>::new()));
+    let reports_clone = reports.clone();
+    set_verification_failure_hook(move |failure| {
+        reports_clone.lock().unwrap().push(failure.clone())
+    });
+
+    // The public inputs must be a multiple of 32 bytes.
+    let secret_looking_inputs = [7u8; 33];
+    assert!(crate::bn254::api::verify_groth16_in_bytes(
+        b"vk_gamma_abc_g1",
+        b"alpha_g1_beta_g2",
+        b"gamma_g2_neg_pc",
+        b"delta_g2_neg_pc",
+        &secret_looking_inputs,
+        b"proof",
+    )
+    .is_err());
+    assert!(crate::bls12381::api::verify_groth16_in_bytes(
+        b"vk_gamma_abc_g1",
+        b"alpha_g1_beta_g2",
+        b"gamma_g2_neg_pc",
+        b"delta_g2_neg_pc",
+        &secret_looking_inputs,
+        b"proof",
+    )
+    .is_err());
+    clear_verification_failure_hook();
+
+    // Other tests may run concurrently, so only look for the reports of the calls above.
+    let reports = reports.lock().unwrap();
+    let ours = reports
+        .iter()
+        .filter(|r| r.public_inputs_length == secret_looking_inputs.len())
+        .collect::<Vec<_>>();
+    assert!(ours.iter().any(|r| r.scheme == "groth16-bn254"));
+    assert!(ours.iter().any(|r| r.scheme == "groth16-bls12381"));
+    // The same key gives the same digest, and nothing but the length of the inputs is reported.
+    assert!(ours.iter().all(|r| r.vk_digest == ours[0].vk_digest));
+    let inputs_hex = Hex::encode(secret_looking_inputs);
+    assert!(ours
+        .iter()
+        .all(|r| !r.to_string().contains(&inputs_hex) && r.to_string().contains("33 bytes")));
+}
@@ -0,0 +1,88 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::pvk_cache::Bn254PvkCache;
+use crate::test_helpers::TestVector;
+use crate::watchable_pvk::*;
+use crate::{bls12381, bn254};
+use std::sync::Arc;
+
+fn bn254_vector(seed: u64) -> (bn254::VerifyingKey, Vec<bn254::FieldElement>, bn254::Proof) {
+    let vector = TestVector::<ark_bn254::Bn254>::dummy(seed).unwrap();
+    let inputs = vector.public_inputs.into_iter().map(Into::into).collect();
+    (vector.verifying_key.into(), inputs, vector.proof.into())
+}
+
+#[test]
+fn test_replace() {
+    let (vk1, inputs1, proof1) = bn254_vector(1);
+    let (vk2, inputs2, proof2) = bn254_vector(2);
+
+    let pvk = Bn254WatchablePvk::new(&vk1);
+    assert_eq!(pvk.version(), 0);
+    assert_eq!(pvk.digest(), vk1.digest());
+    assert!(pvk.verify(&inputs1, &proof1).unwrap());
+    assert!(!pvk.verify(&inputs2, &proof2).unwrap());
+
+    // The current key is not replaced.
+    assert!(pvk.replace(&vk1).is_none());
+    assert_eq!(pvk.version(), 0);
+
+    // A verification in flight holds on to the version it loaded.
+    let in_flight = pvk.load();
+    let previous = pvk.replace(&vk2).unwrap();
+    assert!(Arc::ptr_eq(&previous.pvk, &in_flight.pvk));
+    assert_eq!(pvk.version(), 1);
+    assert_eq!(pvk.digest(), vk2.digest());
+    assert!(bn254::verifier::verify_with_processed_vk(&in_flight.pvk, &inputs1, &proof1).unwrap());
+    assert!(pvk.verify(&inputs2, &proof2).unwrap());
+    assert!(!pvk.verify(&inputs1, &proof1).unwrap());
+
+    // Keys prepared by a cache are swapped in without being prepared again.
+    let cache = Bn254PvkCache::new(2).unwrap();
+    let cached = cache.get_or_prepare(&vk1);
+    assert!(pvk.replace_prepared(vk2.digest(), cached.clone()).is_none());
+    assert_eq!(
+        pvk.replace_prepared(vk1.digest(), cached.clone())
+            .unwrap()
+            .version,
+        1
+    );
+    assert!(Arc::ptr_eq(&pvk.pvk(), &cached));
+    assert_eq!(pvk.version(), 2);
+}
+
+#[test]
+fn test_bls12381() {
+    let vector = TestVector::<ark_bls12_381::Bls12_381>::dummy(0).unwrap();
+    let proof = vector.proof.into();
+    let vk: bls12381::VerifyingKey = vector.verifying_key.into();
+    let pvk = Bls12381WatchablePvk::new(&vk);
+    assert_eq!(*pvk.pvk(), bls12381::verifier::process_vk_special(&vk));
+    assert!(pvk
+        .verify(&[vector.public_inputs[0].into()], &proof)
+        .unwrap());
+}
+
+#[test]
+fn test_concurrent_replace() {
+    let keys = (0..5).map(|seed| bn254_vector(seed).0).collect::<Vec<_>>();
+    let pvk = Arc::new(Bn254WatchablePvk::new(&keys[0]));
+    let handles = keys[1..]
+        .iter()
+        .cloned()
+        .map(|vk| {
+            let pvk = pvk.clone();
+            std::thread::spawn(move || {
+                // Readers and writers do not block each other.
+                let version = pvk.version();
+                assert!(pvk.replace(&vk).unwrap().version >= version);
+            })
+        })
+        .collect::<Vec<_>>();
+    handles.into_iter().for_each(|h| h.join().unwrap());
+
+    // Every replacement got its own version.
+    assert_eq!(pvk.version(), 4);
+    assert!(keys[1..].iter().any(|vk| vk.digest() == pvk.digest()));
+}
@@ -0,0 +1,217 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::bls12381::prover::{prove, ProvingKey};
+use crate::bls12381::verifier::{process_vk_special, PreparedVerifyingKey};
+use crate::dummy_circuits::DummyCircuit;
+use crate::zklogin::*;
+use ark_bls12_381::{Bls12_381, Fr};
+use ark_crypto_primitives::snark::SNARK;
+use ark_ff::One;
+use ark_groth16::Groth16;
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use base64ct::{Base64UrlUnpadded, Encoding};
+use fastcrypto::error::FastCryptoError;
+use std::str::FromStr;
+
+const ISS: &str = "https://accounts.example.com";
+const HEADER: &str = r#"{"alg":"RS256","kid":"key-1","typ":"JWT"}"#;
+const EPHEMERAL_PUBLIC_KEY: [u8; 32] = [7; 32];
+const MAX_EPOCH: u64 = 10;
+
+fn jwk() -> Jwk {
+    let json = format!(
+        r#"{{"kty":"RSA","alg":"RS256","use":"sig","kid":"key-1","e":"AQAB","n":"{}"}}"#,
+        Base64UrlUnpadded::encode_string(&[0xab; 256])
+    );
+    Jwk::from_json(&json, ISS).unwrap()
+}
+
+fn encode_jwt(header: &str, payload: &str) -> String {
+    format!(
+        "{}.{}.{}",
+        Base64UrlUnpadded::encode_string(header.as_bytes()),
+        Base64UrlUnpadded::encode_string(payload.as_bytes()),
+        Base64UrlUnpadded::encode_string(b"signature")
+    )
+}
+
+fn payload(nonce: &str) -> String {
+    format!(
+        r#"{{"iss":"{}","aud":"client","sub":"1234","email":"user@example.com","nonce":"{}","iat":1700000000}}"#,
+        ISS, nonce
+    )
+}
+
+fn setup(rng: &mut StdRng) -> (ProvingKey, PreparedVerifyingKey) {
+    let circuit = circuit(Fr::one());
+    let (pk, vk) = Groth16::<Bls12_381>::circuit_specific_setup(circuit, rng).unwrap();
+    (pk.into(), process_vk_special(&vk.into()))
+}
+
+/// A circuit whose only public input is the product of its two witnesses, which proves any public
+/// input `x` with the witnesses `(x, 1)`.
+fn circuit(public_input: Fr) -> DummyCircuit<Fr> {
+    DummyCircuit {
+        a: Some(public_input),
+        b: Some(Fr::one()),
+        num_variables: 3,
+        num_constraints: 2,
+    }
+}
+
+#[test]
+fn test_verify_zklogin() {
+    let rng = &mut StdRng::from_seed([0; 32]);
+    let (pk, pvk) = setup(rng);
+    let nonce = zklogin_nonce(&EPHEMERAL_PUBLIC_KEY, MAX_EPOCH, &Fr::from(1234u64)).unwrap();
+    let token = encode_jwt(HEADER, &payload(&nonce));
+    let jwt = Jwt::parse(&token).unwrap();
+    let inputs = ZkLoginInputs {
+        ephemeral_public_key: EPHEMERAL_PUBLIC_KEY.to_vec(),
+        max_epoch: MAX_EPOCH,
+        address_seed: address_seed(&jwt, "sub", &Fr::from(5678u64)).unwrap(),
+    };
+    let public_input = inputs.public_inputs_hash(&jwt, &jwk()).unwrap();
+    let proof = prove(&pk, circuit(public_input), rng).unwrap();
+
+    for epoch in [0, MAX_EPOCH] {
+        assert!(verify_zklogin(&pvk, &token, &jwk(), &inputs, epoch, &proof).is_ok());
+    }
+    assert_eq!(
+        verify_zklogin(&pvk, &token, &jwk(), &inputs, MAX_EPOCH + 1, &proof),
+        Err(FastCryptoError::InvalidInput)
+    );
+
+    // Other public values and JWTs.
+    let mut other = inputs.clone();
+    other.max_epoch += 1;
+    assert_eq!(
+        verify_zklogin(&pvk, &token, &jwk(), &other, 0, &proof),
+        Err(FastCryptoError::InvalidProof)
+    );
+    let mut other = inputs.clone();
+    other.ephemeral_public_key[0] ^= 1;
+    assert!(verify_zklogin(&pvk, &token, &jwk(), &other, 0, &proof).is_err());
+    let mut other = inputs.clone();
+    other.address_seed = address_seed(&jwt, "email", &Fr::from(5678u64)).unwrap();
+    assert!(verify_zklogin(&pvk, &token, &jwk(), &other, 0, &proof).is_err());
+    let other_nonce = zklogin_nonce(&EPHEMERAL_PUBLIC_KEY, MAX_EPOCH, &Fr::from(1u64)).unwrap();
+    let other_token = encode_jwt(HEADER, &payload(&other_nonce));
+    assert!(verify_zklogin(&pvk, &other_token, &jwk(), &inputs, 0, &proof).is_err());
+    let mut other_jwk = jwk();
+    other_jwk.modulus[0] ^= 1;
+    assert!(verify_zklogin(&pvk, &token, &other_jwk, &inputs, 0, &proof).is_err());
+
+    // JWTs for other keys and issuers, or with other algorithms.
+    let mut other_jwk = jwk();
+    other_jwk.kid = "key-2".to_string();
+    assert!(verify_zklogin(&pvk, &token, &other_jwk, &inputs, 0, &proof).is_err());
+    let mut other_jwk = jwk();
+    other_jwk.iss = "https://other.example.com".to_string();
+    assert!(verify_zklogin(&pvk, &token, &other_jwk, &inputs, 0, &proof).is_err());
+    let other_token = encode_jwt(&HEADER.replace("RS256", "HS256"), &payload(&nonce));
+    assert_eq!(
+        verify_zklogin(&pvk, &other_token, &jwk(), &inputs, 0, &proof),
+        Err(FastCryptoError::InvalidInput)
+    );
+}
+
+#[test]
+fn test_jwt_parsing() {
+    let token = encode_jwt(HEADER, &payload("nonce"));
+    let jwt = Jwt::parse(&token).unwrap();
+    assert_eq!(jwt, Jwt::from_parts(HEADER, &payload("nonce")).unwrap());
+    assert_eq!(jwt.kid().unwrap(), "key-1");
+    assert_eq!(jwt.iss().unwrap(), ISS);
+    assert_eq!(jwt.aud().unwrap(), "client");
+    assert_eq!(jwt.nonce().unwrap(), "nonce");
+    assert_eq!(jwt.claim("email"), Some("user@example.com"));
+    assert_eq!(jwt.claim("iat"), None);
+    assert_eq!(jwt.claim("name"), None);
+
+    // An audience must be a string or an array with a single string.
+    let with_aud =
+        |aud: &str| Jwt::from_parts(HEADER, &payload("nonce").replace(r#""client""#, aud)).unwrap();
+    assert_eq!(with_aud(r#"["client"]"#).aud().unwrap(), "client");
+    assert!(with_aud(r#"["client","other"]"#).aud().is_err());
+    assert!(with_aud("[]").aud().is_err());
+    assert!(with_aud("7").aud().is_err());
+
+    // Missing parts, invalid base64 and JSON which is not an object.
+    let parts = token.split('.').collect::<Vec<_>>();
+    assert!(Jwt::parse(&parts[..2].join(".")).is_err());
+    assert!(Jwt::parse(&format!("{}.", parts[..2].join("."))).is_err());
+    assert!(Jwt::parse(&format!("{}.x", token)).is_err());
+    assert!(Jwt::parse(&token.replacen('e', "+", 1)).is_err());
+    assert!(Jwt::parse(&encode_jwt("[]", &payload("nonce"))).is_err());
+    assert!(Jwt::from_parts(HEADER, "{").is_err());
+}
+
+#[test]
+fn test_jwk() {
+    let jwk = jwk();
+    assert_eq!(jwk.kid, "key-1");
+    assert_eq!(jwk.iss, ISS);
+    assert_eq!(jwk.modulus, vec![0xab; 256]);
+
+    // Only RSA keys with exponent 65537.
+    assert!(Jwk::from_json(r#"{"kty":"EC","kid":"key-1","e":"AQAB","n":"AQ"}"#, ISS).is_err());
+    assert!(Jwk::from_json(r#"{"kty":"RSA","kid":"key-1","e":"Aw","n":"AQ"}"#, ISS).is_err());
+    assert!(Jwk::from_json(r#"{"kty":"RSA","kid":"key-1","e":"AQAB","n":"*"}"#, ISS).is_err());
+    assert!(Jwk::from_json(r#"{"kty":"RSA","e":"AQAB","n":"AQ"}"#, ISS).is_err());
+}
+
+#[test]
+fn test_hashing() {
+    // Test vectors computed with an independent implementation.
+    for (bytes, expected) in [
+        (
+            vec![],
+            "2811068068091031911201269074038037779542827974520177560187358960284013358662",
+        ),
+        (
+            b"abc".to_vec(),
+            "19825253267556527310284684095664386571526412151506272542460200860008061170650",
+        ),
+        (
+            (0..40).collect(),
+            "28652108117486872887578456668074815849883615298727085803787459314830420541419",
+        ),
+    ] {
+        assert_eq!(
+            hash_bytes_to_field(&bytes).unwrap(),
+            Fr::from_str(expected).unwrap()
+        );
+    }
+    assert_eq!(
+        zklogin_nonce(&EPHEMERAL_PUBLIC_KEY, MAX_EPOCH, &Fr::from(1234u64)).unwrap(),
+        "NJkhDJl2c7zLZGeqmNLOYb7UH48LSzLJVnJDexOPLCw"
+    );
+
+    // The length is hashed, so trailing zeros change the hash.
+    assert_ne!(
+        hash_bytes_to_field(b"abc").unwrap(),
+        hash_bytes_to_field(b"abc\0").unwrap()
+    );
+    assert!(hash_bytes_to_field(&[1; MAX_HASHED_LENGTH]).is_ok());
+    assert_eq!(
+        hash_bytes_to_field(&[1; MAX_HASHED_LENGTH + 1]),
+        Err(FastCryptoError::InputTooLong(MAX_HASHED_LENGTH))
+    );
+
+    // Nonces must encode canonical field elements.
+    let inputs = ZkLoginInputs {
+        ephemeral_public_key: EPHEMERAL_PUBLIC_KEY.to_vec(),
+        max_epoch: MAX_EPOCH,
+        address_seed: Fr::one(),
+    };
+    for nonce in [
+        Base64UrlUnpadded::encode_string(&[0xff; 32]),
+        Base64UrlUnpadded::encode_string(&[0x01; 31]),
+        "nonce".to_string(),
+    ] {
+        let jwt = Jwt::from_parts(HEADER, &payload(&nonce)).unwrap();
+        assert!(inputs.public_inputs_hash(&jwt, &jwk()).is_err());
+    }
+}
@@ -0,0 +1,100 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! An optional process-wide hook which is called whenever a Groth16 verification through the
+//! binary APIs ([`crate::bn254::api::verify_groth16_in_bytes`] and
+//! [`crate::bls12381::api::verify_groth16_in_bytes`]) fails, e.g. to log rejected proofs.
+//!
+//! A report identifies the verifying key only by a digest and contains neither the proof nor the
+//! public inputs, so it can be logged without leaking any data of the prover.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto_zkp::verification_hook::*;
+//! set_verification_failure_hook(|failure| {
+//!     println!("{} verification failed: {}", failure.scheme, failure.reason);
+//! });
+//! clear_verification_failure_hook();
+//! ```
+
+use crate::error::ZkResult;
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::hash::{HashFunction, Sha256};
+use std::fmt::{Display, Formatter};
+use std::sync::RwLock;
+
+#[cfg(test)]
+#[path = "unit_tests/verification_hook_tests.rs"]
+mod verification_hook_tests;
+
+type Hook = Box<dyn Fn(&VerificationFailure) + Send + Sync>;
+
+static HOOK: RwLock<Option<Hook>> = RwLock::new(None);
+
+/// A report of a failed verification.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerificationFailure {
+    /// The proof system and curve, e.g. `groth16-bn254`.
+    pub scheme: &'static str,
+    /// The SHA-256 digest of the serialized prepared verifying key.
+    pub vk_digest: [u8; 32],
+    /// The length in bytes of the serialized public inputs.
+    pub public_inputs_length: usize,
+    /// Why the verification failed.
+    pub reason: String,
+}
+
+impl Display for VerificationFailure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} verification failed for vk {} with {} bytes of public inputs: {}",
+            self.scheme,
+            Hex::encode(self.vk_digest),
+            self.public_inputs_length,
+            self.reason
+        )
+    }
+}
+
+/// Set the hook which is called on every failed verification, replacing any previous hook. The hook
+/// is called synchronously by the verifying thread, so it should be fast.
+pub fn set_verification_failure_hook<F>(hook: F)
+where
+    F: Fn(&VerificationFailure) + Send + Sync + 'static,
+{
+    *HOOK.write().expect("Lock is not poisoned") = Some(Box::new(hook));
+}
+
+/// Remove the hook set by [set_verification_failure_hook].
+pub fn clear_verification_failure_hook() {
+    *HOOK.write().expect("Lock is not poisoned") = None;
+}
+
+/// Call the hook if the result of a verification is a failure. The verifying key is given as the
+/// serialized components of a prepared verifying key.
+pub(crate) fn report_failure(
+    scheme: &'static str,
+    vk_components: &[&[u8]],
+    public_inputs_length: usize,
+    result: &ZkResult<()>,
+) {
+    let error = match result {
+        Ok(()) => return,
+        Err(e) => e,
+    };
+    let hook = HOOK.read().expect("Lock is not poisoned");
+    if let Some(hook) = hook.as_ref() {
+        let mut hash = Sha256::new();
+        for component in vk_components {
+            hash.update((component.len() as u64).to_le_bytes());
+            hash.update(*component);
+        }
+        hook(&VerificationFailure {
+            scheme,
+            vk_digest: hash.finalize().digest,
+            public_inputs_length,
+            reason: error.to_string(),
+        });
+    }
+}
@@ -0,0 +1,157 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A handle to the prepared verifying key of a circuit which can be replaced while the service
+//! using it keeps verifying proofs, e.g., after an upgrade of the circuit.
+//!
+//! A [WatchablePvk] holds the current [PvkVersion], which is loaded without taking a lock.
+//! Verifications hold on to the version they loaded, so verifications in flight when a new key is
+//! swapped in complete against the old key, and the old key is dropped after the last of them. New
+//! keys are prepared before they are swapped in, so the swap itself is a single atomic operation.
+//! Each swap increments the version number, which services can watch to detect upgrades.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto_zkp::{bn254::VerifyingKey, dummy_circuits::DummyCircuit, watchable_pvk::*};
+//! # use ark_bn254::{Bn254, Fr};
+//! # use ark_crypto_primitives::snark::SNARK;
+//! # use ark_ff::One;
+//! # use ark_groth16::Groth16;
+//! # use ark_std::rand::thread_rng;
+//! let circuit = DummyCircuit::<Fr> {
+//!     a: Some(Fr::one()),
+//!     b: Some(Fr::one()),
+//!     num_variables: 4,
+//!     num_constraints: 10,
+//! };
+//! let (pk, vk) = Groth16::<Bn254>::circuit_specific_setup(circuit, &mut thread_rng()).unwrap();
+//! let proof = Groth16::<Bn254>::prove(&pk, circuit, &mut thread_rng()).unwrap().into();
+//!
+//! let pvk = Bn254WatchablePvk::new(&VerifyingKey::from(vk));
+//! assert!(pvk.verify(&[Fr::one().into()], &proof).unwrap());
+//!
+//! // An upgrade of the circuit.
+//! let (_, upgraded) = Groth16::<Bn254>::circuit_specific_setup(circuit, &mut thread_rng()).unwrap();
+//! let previous = pvk.replace(&VerifyingKey::from(upgraded)).unwrap();
+//! assert_eq!((previous.version, pvk.version()), (0, 1));
+//! assert!(!pvk.verify(&[Fr::one().into()], &proof).unwrap());
+//! ```
+
+use crate::pvk_cache::PreparableVerifyingKey;
+use crate::{bls12381, bn254};
+use arc_swap::ArcSwap;
+use fastcrypto::error::FastCryptoError;
+use std::sync::Arc;
+
+#[cfg(test)]
+#[path = "unit_tests/watchable_pvk_tests.rs"]
+mod watchable_pvk_tests;
+
+/// A prepared verifying key together with the digest of the verifying key and its version.
+#[derive(Debug)]
+pub struct PvkVersion<P> {
+    /// The number of keys swapped in before this one.
+    pub version: u64,
+    /// The digest of the verifying key, see [PreparableVerifyingKey::digest].
+    pub digest: [u8; 32],
+    pub pvk: Arc<P>,
+}
+
+/// A prepared verifying key over BLS12-381 which can be replaced.
+pub type Bls12381WatchablePvk = WatchablePvk<bls12381::VerifyingKey>;
+
+/// A prepared verifying key over BN254 which can be replaced.
+pub type Bn254WatchablePvk = WatchablePvk<bn254::VerifyingKey>;
+
+/// A prepared verifying key which can be atomically replaced, see the module documentation.
+#[derive(Debug)]
+pub struct WatchablePvk<K: PreparableVerifyingKey> {
+    current: ArcSwap<PvkVersion<K::Prepared>>,
+}
+
+impl<K: PreparableVerifyingKey> WatchablePvk<K> {
+    /// A handle to the prepared key of `vk` with version zero.
+    pub fn new(vk: &K) -> Self {
+        Self {
+            current: ArcSwap::from_pointee(PvkVersion {
+                version: 0,
+                digest: vk.digest(),
+                pvk: Arc::new(vk.prepare()),
+            }),
+        }
+    }
+
+    /// The current version, which stays valid after newer keys have been swapped in.
+    pub fn load(&self) -> Arc<PvkVersion<K::Prepared>> {
+        self.current.load_full()
+    }
+
+    /// The current prepared key.
+    pub fn pvk(&self) -> Arc<K::Prepared> {
+        self.current.load().pvk.clone()
+    }
+
+    /// The number of the current version.
+    pub fn version(&self) -> u64 {
+        self.current.load().version
+    }
+
+    /// The digest of the current verifying key.
+    pub fn digest(&self) -> [u8; 32] {
+        self.current.load().digest
+    }
+
+    /// Prepare `vk` and swap it in, returning the previous version. Returns `None` and keeps the
+    /// current version if `vk` is the current verifying key.
+    pub fn replace(&self, vk: &K) -> Option<Arc<PvkVersion<K::Prepared>>> {
+        let digest = vk.digest();
+        if self.digest() == digest {
+            return None;
+        }
+        self.replace_prepared(digest, Arc::new(vk.prepare()))
+    }
+
+    /// Swap in a key which has already been prepared, e.g., by a [crate::pvk_cache::PvkCache],
+    /// returning the previous version. `digest` must be the digest of the verifying key of `pvk`.
+    /// Returns `None` and keeps the current version if the digest is the current one.
+    pub fn replace_prepared(
+        &self,
+        digest: [u8; 32],
+        pvk: Arc<K::Prepared>,
+    ) -> Option<Arc<PvkVersion<K::Prepared>>> {
+        // The closure is called again if another key was swapped in concurrently.
+        let previous = self.current.rcu(|current| {
+            if current.digest == digest {
+                return current.clone();
+            }
+            Arc::new(PvkVersion {
+                version: current.version + 1,
+                digest,
+                pvk: pvk.clone(),
+            })
+        });
+        (previous.digest != digest).then_some(previous)
+    }
+}
+
+impl Bn254WatchablePvk {
+    /// Verify a proof with the current key, see [bn254::verifier::verify_with_processed_vk].
+    pub fn verify(
+        &self,
+        public_inputs: &[bn254::FieldElement],
+        proof: &bn254::Proof,
+    ) -> Result<bool, FastCryptoError> {
+        bn254::verifier::verify_with_processed_vk(&self.pvk(), public_inputs, proof)
+    }
+}
+
+impl Bls12381WatchablePvk {
+    /// Verify a proof with the current key, see [bls12381::verifier::verify_with_processed_vk].
+    pub fn verify(
+        &self,
+        public_inputs: &[bls12381::FieldElement],
+        proof: &bls12381::Proof,
+    ) -> Result<bool, FastCryptoError> {
+        bls12381::verifier::verify_with_processed_vk(&self.pvk(), public_inputs, proof)
+    }
+}
@@ -0,0 +1,282 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verification of zkLogin-style proofs, which show that the holder of an ephemeral key was
+//! authenticated by an OpenID provider without revealing the secret salt linking the OpenID account
+//! to an on-chain address.
+//!
+//! A user creates an ephemeral key pair, valid until `max_epoch`, and asks the provider for a JWT
+//! whose `nonce` claim is [zklogin_nonce] of the ephemeral public key, `max_epoch` and secret
+//! randomness. A Groth16 proof over BLS12-381 then shows that
+//! * the JWT is signed by the provider key [Jwk] with the given `kid` and contains the `iss` and
+//!   `nonce` claims hashed into the public input,
+//! * the nonce commits to the ephemeral public key and `max_epoch`, and
+//! * the address seed is [address_seed] of the key claim of the JWT (e.g. `sub`), the `aud` claim
+//!   and the salt of the user.
+//!
+//! The circuit has a single public input, [ZkLoginInputs::public_inputs_hash], which is the
+//! Poseidon hash of all values listed above as derived from the JWT. Strings and byte arrays are
+//! hashed to field elements with [hash_bytes_to_field]. The signature of the JWT is only verified
+//! by the circuit, so [verify_zklogin] does not check it.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto_zkp::zklogin::*;
+//! # use ark_bls12_381::Fr;
+//! let ephemeral_public_key = [7u8; 32];
+//! let nonce = zklogin_nonce(&ephemeral_public_key, 10, &Fr::from(1234u64)).unwrap();
+//! assert_eq!(nonce.len(), 43);
+//!
+//! let payload = format!(
+//!     r#"{{"iss":"https://accounts.example.com","aud":"app","sub":"42","nonce":"{}"}}"#,
+//!     nonce
+//! );
+//! let jwt = Jwt::from_parts(r#"{"alg":"RS256","kid":"key-1"}"#, &payload).unwrap();
+//! assert_eq!(jwt.claim("sub"), Some("42"));
+//!
+//! // The address seed depends on the salt, which is only known to the user.
+//! let seed = address_seed(&jwt, "sub", &Fr::from(5678u64)).unwrap();
+//! assert_ne!(seed, address_seed(&jwt, "sub", &Fr::from(5679u64)).unwrap());
+//! ```
+
+use crate::bls12381::verifier::{verify_with_processed_vk, PreparedVerifyingKey};
+use crate::bls12381::{FieldElement, Proof};
+use crate::poseidon::poseidon_bls12381;
+use ark_bls12_381::Fr;
+use ark_ff::{BigInteger, PrimeField};
+use base64ct::{Base64UrlUnpadded, Encoding};
+use fastcrypto::error::FastCryptoError;
+use serde_json::{Map, Value};
+
+#[cfg(test)]
+#[path = "unit_tests/zklogin_tests.rs"]
+mod zklogin_tests;
+
+/// The number of bytes packed into each field element by [hash_bytes_to_field].
+const BYTES_PER_ELEMENT: usize = 31;
+
+/// The maximal length of the inputs of [hash_bytes_to_field], which is the number of bytes fitting
+/// into the 15 field elements hashed after the length.
+pub const MAX_HASHED_LENGTH: usize = 15 * BYTES_PER_ELEMENT;
+
+/// The only supported signature algorithm of JWTs.
+pub const JWT_ALGORITHM: &str = "RS256";
+
+/// An RSA key of an OpenID provider from its JSON Web Key Set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Jwk {
+    pub kid: String,
+    /// The issuer of the JWTs signed by this key, i.e., the `iss` claim.
+    pub iss: String,
+    /// The big-endian modulus of the key.
+    pub modulus: Vec<u8>,
+}
+
+impl Jwk {
+    /// Parse a single RSA key of a JSON Web Key Set, e.g. as served by a provider at its `jwks_uri`.
+    /// Only the public exponent 65537 is supported.
+    pub fn from_json(json: &str, iss: &str) -> Result<Self, FastCryptoError> {
+        let key = parse_object(json.as_bytes())?;
+        if string_member(&key, "kty")? != "RSA" || string_member(&key, "e")? != "AQAB" {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(Self {
+            kid: string_member(&key, "kid")?.to_string(),
+            iss: iss.to_string(),
+            modulus: decode_base64url(string_member(&key, "n")?)?,
+        })
+    }
+}
+
+/// The decoded header and claims of a JWT.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Jwt {
+    header: Map<String, Value>,
+    claims: Map<String, Value>,
+}
+
+impl Jwt {
+    /// Parse a JWT in the compact serialization `header.payload.signature`. The signature is not
+    /// decoded or verified.
+    pub fn parse(token: &str) -> Result<Self, FastCryptoError> {
+        let parts = token.split('.').collect::<Vec<_>>();
+        match parts.as_slice() {
+            [header, payload, signature] if !signature.is_empty() => Ok(Self {
+                header: parse_object(&decode_base64url(header)?)?,
+                claims: parse_object(&decode_base64url(payload)?)?,
+            }),
+            _ => Err(FastCryptoError::InvalidInput),
+        }
+    }
+
+    /// Create a JWT from its decoded header and payload given as JSON objects.
+    pub fn from_parts(header: &str, payload: &str) -> Result<Self, FastCryptoError> {
+        Ok(Self {
+            header: parse_object(header.as_bytes())?,
+            claims: parse_object(payload.as_bytes())?,
+        })
+    }
+
+    /// The string claim with the given name, if any.
+    pub fn claim(&self, name: &str) -> Option<&str> {
+        self.claims.get(name).and_then(Value::as_str)
+    }
+
+    pub fn kid(&self) -> Result<&str, FastCryptoError> {
+        string_member(&self.header, "kid")
+    }
+
+    pub fn iss(&self) -> Result<&str, FastCryptoError> {
+        string_member(&self.claims, "iss")
+    }
+
+    /// The audience of this JWT. An array is only accepted if it has a single element.
+    pub fn aud(&self) -> Result<&str, FastCryptoError> {
+        match self.claims.get("aud") {
+            Some(Value::String(aud)) => Ok(aud),
+            Some(Value::Array(values)) if values.len() == 1 => {
+                values[0].as_str().ok_or(FastCryptoError::InvalidInput)
+            }
+            _ => Err(FastCryptoError::InvalidInput),
+        }
+    }
+
+    pub fn nonce(&self) -> Result<&str, FastCryptoError> {
+        string_member(&self.claims, "nonce")
+    }
+}
+
+/// The public values of a zkLogin proof which are not part of the JWT.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ZkLoginInputs {
+    pub ephemeral_public_key: Vec<u8>,
+    /// The last epoch in which the ephemeral key is valid.
+    pub max_epoch: u64,
+    /// The address seed of the user, see [address_seed].
+    pub address_seed: Fr,
+}
+
+impl ZkLoginInputs {
+    /// The public input of the circuit for the given JWT and provider key. Fails if the JWT does
+    /// not have the required claims, if it is not signed with RS256, or if it was not issued for
+    /// the given key.
+    pub fn public_inputs_hash(&self, jwt: &Jwt, jwk: &Jwk) -> Result<Fr, FastCryptoError> {
+        if string_member(&jwt.header, "alg")? != JWT_ALGORITHM
+            || jwt.kid()? != jwk.kid
+            || jwt.iss()? != jwk.iss
+        {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        poseidon_bls12381(&[
+            hash_bytes_to_field(&self.ephemeral_public_key)?,
+            Fr::from(self.max_epoch),
+            nonce_to_field(jwt.nonce()?)?,
+            self.address_seed,
+            hash_bytes_to_field(jwk.iss.as_bytes())?,
+            hash_bytes_to_field(jwk.kid.as_bytes())?,
+            hash_bytes_to_field(&jwk.modulus)?,
+        ])
+    }
+}
+
+/// Verify a zkLogin proof for the given JWT, provider key and public values in the given epoch.
+/// Fails with [FastCryptoError::InvalidProof] if the proof is invalid, and with
+/// [FastCryptoError::InvalidInput] if the ephemeral key has expired or if the public input can not
+/// be derived, see [ZkLoginInputs::public_inputs_hash].
+pub fn verify_zklogin(
+    pvk: &PreparedVerifyingKey,
+    jwt: &str,
+    jwk: &Jwk,
+    inputs: &ZkLoginInputs,
+    current_epoch: u64,
+    proof: &Proof,
+) -> Result<(), FastCryptoError> {
+    if current_epoch > inputs.max_epoch {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    let public_input = inputs.public_inputs_hash(&Jwt::parse(jwt)?, jwk)?;
+    if !verify_with_processed_vk(pvk, &[FieldElement(public_input)], proof)? {
+        return Err(FastCryptoError::InvalidProof);
+    }
+    Ok(())
+}
+
+/// The nonce to request from the provider for an ephemeral key valid until `max_epoch`, i.e., the
+/// unpadded base64url encoding of the 32 byte big-endian encoding of the Poseidon hash of the key,
+/// `max_epoch` and the randomness.
+pub fn zklogin_nonce(
+    ephemeral_public_key: &[u8],
+    max_epoch: u64,
+    randomness: &Fr,
+) -> Result<String, FastCryptoError> {
+    let nonce = poseidon_bls12381(&[
+        hash_bytes_to_field(ephemeral_public_key)?,
+        Fr::from(max_epoch),
+        *randomness,
+    ])?;
+    Ok(Base64UrlUnpadded::encode_string(
+        &nonce.into_bigint().to_bytes_be(),
+    ))
+}
+
+/// The address seed of a user given by the value of the key claim with the given name, e.g. `sub`
+/// or `email`, the audience of the JWT and the salt of the user. This is
+/// `Poseidon(H(name), H(value), H(aud), Poseidon(salt))` where `H` is [hash_bytes_to_field].
+pub fn address_seed(jwt: &Jwt, key_claim_name: &str, salt: &Fr) -> Result<Fr, FastCryptoError> {
+    let value = jwt
+        .claim(key_claim_name)
+        .ok_or(FastCryptoError::InvalidInput)?;
+    poseidon_bls12381(&[
+        hash_bytes_to_field(key_claim_name.as_bytes())?,
+        hash_bytes_to_field(value.as_bytes())?,
+        hash_bytes_to_field(jwt.aud()?.as_bytes())?,
+        poseidon_bls12381(&[*salt])?,
+    ])
+}
+
+/// Hash at most [MAX_HASHED_LENGTH] bytes to a field element as the Poseidon hash of the length
+/// followed by the bytes packed into big-endian field elements of 31 bytes each. The last element
+/// is padded with zeros at the end.
+pub fn hash_bytes_to_field(bytes: &[u8]) -> Result<Fr, FastCryptoError> {
+    if bytes.len() > MAX_HASHED_LENGTH {
+        return Err(FastCryptoError::InputTooLong(MAX_HASHED_LENGTH));
+    }
+    let mut elements = vec![Fr::from(bytes.len() as u64)];
+    elements.extend(bytes.chunks(BYTES_PER_ELEMENT).map(|chunk| {
+        let mut padded = [0u8; BYTES_PER_ELEMENT];
+        padded[..chunk.len()].copy_from_slice(chunk);
+        Fr::from_be_bytes_mod_order(&padded)
+    }));
+    poseidon_bls12381(&elements)
+}
+
+/// Decode a nonce created by [zklogin_nonce]. Fails unless it encodes a canonical field element.
+fn nonce_to_field(nonce: &str) -> Result<Fr, FastCryptoError> {
+    let bytes = decode_base64url(nonce)?;
+    let element = Fr::from_be_bytes_mod_order(&bytes);
+    if bytes.len() != 32 || element.into_bigint().to_bytes_be() != bytes {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    Ok(element)
+}
+
+fn decode_base64url(encoded: &str) -> Result<Vec<u8>, FastCryptoError> {
+    Base64UrlUnpadded::decode_vec(encoded).map_err(|_| FastCryptoError::InvalidInput)
+}
+
+fn parse_object(json: &[u8]) -> Result<Map<String, Value>, FastCryptoError> {
+    match serde_json::from_slice(json) {
+        Ok(Value::Object(object)) => Ok(object),
+        _ => Err(FastCryptoError::InvalidInput),
+    }
+}
+
+fn string_member<'a>(
+    object: &'a Map<String, Value>,
+    name: &str,
+) -> Result<&'a str, FastCryptoError> {
+    object
+        .get(name)
+        .and_then(Value::as_str)
+        .ok_or(FastCryptoError::InvalidInput)
+}
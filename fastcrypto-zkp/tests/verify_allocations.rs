@@ -0,0 +1,86 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Checks the heap allocations of Groth16 verification over BLS12-381, which matter for embedders
+//! with custom allocators or strict latency requirements. This is an integration test because it
+//! replaces the global allocator with one counting the allocations of each thread.
+//!
+//! The pairing context of blst is allocated for every verification, and seven or more public
+//! inputs need buffers for the multi-scalar multiplication, so we check that the number of
+//! allocations does not depend on the number of public inputs otherwise.
+
+use ark_bls12_381::{Bls12_381, Fr};
+use ark_groth16::Groth16;
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use fastcrypto_zkp::bench_circuits::Shaped;
+use fastcrypto_zkp::bls12381::verifier::{process_vk_special, verify_with_processed_vk};
+use fastcrypto_zkp::bls12381::FieldElement;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Run `f` and return its result and the number of allocations made by this thread while running
+/// it.
+fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(|count| count.get());
+    let result = f();
+    let after = ALLOCATIONS.with(|count| count.get());
+    (result, after - before)
+}
+
+/// The number of allocations of verifying a valid proof with the given number of public inputs.
+fn verify_allocations(num_public_inputs: usize) -> usize {
+    let rng = &mut StdRng::from_seed([0; 32]);
+    let circuit = Shaped::<Fr>::random(num_public_inputs, 64, rng).unwrap();
+    let public_inputs = circuit
+        .public_inputs()
+        .into_iter()
+        .map(FieldElement::from)
+        .collect::<Vec<_>>();
+    let params =
+        Groth16::<Bls12_381>::generate_random_parameters_with_reduction(circuit.clone(), rng)
+            .unwrap();
+    let proof = Groth16::<Bls12_381>::create_random_proof_with_reduction(circuit, &params, rng)
+        .unwrap()
+        .into();
+    let pvk = process_vk_special(&params.vk.into());
+
+    // The first verification may initialize lazy statics.
+    assert!(verify_with_processed_vk(&pvk, &public_inputs, &proof).unwrap());
+
+    let (result, allocations) =
+        count_allocations(|| verify_with_processed_vk(&pvk, &public_inputs, &proof));
+    assert!(result.unwrap());
+    allocations
+}
+
+#[test]
+fn test_bls12381_groth16_verify_allocations_do_not_grow() {
+    // Fewer than seven public inputs only allocate the pairing context.
+    assert_eq!(verify_allocations(1), verify_allocations(6));
+
+    // The multi-scalar multiplication allocates the same number of buffers for any number of
+    // public inputs below the threshold for parallel multi-scalar multiplication.
+    let many = verify_allocations(7);
+    assert_eq!(many, verify_allocations(32));
+    assert!(many > verify_allocations(1));
+}
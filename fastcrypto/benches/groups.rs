@@ -7,7 +7,9 @@ mod group_benches {
     use criterion::{measurement, BenchmarkGroup, Criterion};
     use fastcrypto::groups::bls12381::{G1Element, G2Element, GTElement};
     use fastcrypto::groups::ristretto255::RistrettoPoint;
-    use fastcrypto::groups::{GroupElement, HashToGroupElement, Pairing, Scalar};
+    use fastcrypto::groups::{
+        FixedBaseMultiplication, GroupElement, HashToGroupElement, Pairing, Scalar,
+    };
     use rand::thread_rng;
 
     fn add_single<G: GroupElement, M: measurement::Measurement>(
@@ -44,6 +46,23 @@ mod group_benches {
         scale_single::<RistrettoPoint, _>("Ristretto255", &mut group);
     }
 
+    fn mul_generator_single<G: FixedBaseMultiplication, M: measurement::Measurement>(
+        name: &str,
+        c: &mut BenchmarkGroup<M>,
+    ) {
+        let x = G::ScalarType::rand(&mut thread_rng());
+        c.bench_function(&(name.to_string()), move |b| {
+            b.iter(|| G::mul_generator(&x))
+        });
+    }
+
+    fn mul_generator(c: &mut Criterion) {
+        let mut group: BenchmarkGroup<_> = c.benchmark_group("Generator Multiplication");
+        mul_generator_single::<G1Element, _>("BLS12381-G1", &mut group);
+        mul_generator_single::<G2Element, _>("BLS12381-G2", &mut group);
+        mul_generator_single::<RistrettoPoint, _>("Ristretto255", &mut group);
+    }
+
     fn hash_to_group_single<G: GroupElement + HashToGroupElement, M: measurement::Measurement>(
         name: &str,
         c: &mut BenchmarkGroup<M>,
@@ -82,6 +101,7 @@ mod group_benches {
         targets =
             add,
             scale,
+            mul_generator,
             hash_to_group,
             pairing,
     }
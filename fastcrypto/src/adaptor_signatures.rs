@@ -0,0 +1,356 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Adaptor signatures over secp256k1.
+//!
+//! An adaptor signature (or pre-signature) is created by a signer for a message and an _adaptor
+//! point_ `T = tG`. Anyone can verify the pre-signature against the public key, message and adaptor
+//! point, but it is not a valid signature by itself. Whoever knows the adaptor secret `t` can
+//! _adapt_ the pre-signature into a valid signature, and from the pre-signature and the adapted
+//! signature anyone can _extract_ `t`. This is the building block for atomic swaps and payment
+//! channels: publishing the completed signature reveals the secret to the counterparty.
+//!
+//! Two schemes are provided, both using [crate::secp256k1::Secp256k1KeyPair] keys:
+//! * [schnorr]: Schnorr signatures over secp256k1 (not BIP-340 encoded).
+//! * [ecdsa]: ECDSA following the construction of Aumayr et al.,
+//!   ["Generalized Channels from Limited Blockchain Scripts and Adaptor Signatures"](https://eprint.iacr.org/2020/476).
+//!   The adapted signature is a regular [crate::secp256k1::Secp256k1Signature] which verifies
+//!   with the public key of the signer.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::adaptor_signatures::ecdsa::EcdsaPreSignature;
+//! # use fastcrypto::groups::{GroupElement, Scalar};
+//! # use fastcrypto::groups::secp256k1::{Secp256k1Point, Secp256k1Scalar};
+//! # use fastcrypto::secp256k1::Secp256k1KeyPair;
+//! # use fastcrypto::traits::{KeyPair, VerifyingKey};
+//! # use rand::thread_rng;
+//! let kp = Secp256k1KeyPair::generate(&mut thread_rng());
+//! let t = Secp256k1Scalar::rand(&mut thread_rng());
+//! let adaptor_point = Secp256k1Point::generator() * t;
+//!
+//! let pre_signature = EcdsaPreSignature::new(&kp, b"Hello, world!", &adaptor_point, &mut thread_rng());
+//! assert!(pre_signature.verify(kp.public(), b"Hello, world!", &adaptor_point).is_ok());
+//!
+//! let signature = pre_signature.adapt(&t).unwrap();
+//! assert!(kp.public().verify(b"Hello, world!", &signature).is_ok());
+//! assert_eq!(pre_signature.extract(&signature, &adaptor_point).unwrap(), t);
+//! ```
+
+use crate::groups::secp256k1::{Secp256k1Point, Secp256k1Scalar};
+use crate::secp256k1::{Secp256k1PrivateKey, Secp256k1PublicKey};
+use crate::serde_helpers::ToFromByteArray;
+
+/// Domain separation tag used for the Fiat-Shamir challenges.
+const DST: &[u8] = b"FASTCRYPTO_ADAPTOR_SIGNATURES_SECP256K1_V1";
+
+fn public_key_to_point(public_key: &Secp256k1PublicKey) -> Secp256k1Point {
+    let bytes = public_key
+        .as_ref()
+        .try_into()
+        .expect("Public keys are compressed points");
+    Secp256k1Point::from_byte_array(bytes).expect("Public keys are valid points")
+}
+
+fn private_key_to_scalar(private_key: &Secp256k1PrivateKey) -> Secp256k1Scalar {
+    let bytes = private_key
+        .as_ref()
+        .try_into()
+        .expect("Private keys are 32 bytes");
+    Secp256k1Scalar::from_byte_array(bytes).expect("Private keys are valid scalars")
+}
+
+/// Hash a tag and a sequence of points to a scalar.
+fn challenge(tag: &[u8], points: &[&Secp256k1Point], message: &[u8]) -> Secp256k1Scalar {
+    use crate::groups::HashToGroupElement;
+    let mut input = DST.to_vec();
+    input.extend_from_slice(tag);
+    points
+        .iter()
+        .for_each(|p| input.extend_from_slice(&p.to_byte_array()));
+    input.extend_from_slice(message);
+    Secp256k1Scalar::hash_to_group_element(&input)
+}
+
+/// Adaptor signatures for Schnorr signatures over secp256k1.
+pub mod schnorr {
+    use super::{challenge, private_key_to_scalar, public_key_to_point};
+    use crate::error::FastCryptoError;
+    use crate::groups::secp256k1::{Secp256k1Point, Secp256k1Scalar};
+    use crate::groups::{GroupElement, Scalar};
+    use crate::secp256k1::{Secp256k1KeyPair, Secp256k1PublicKey};
+    use crate::traits::{AllowedRng, KeyPair};
+    use serde::{Deserialize, Serialize};
+
+    const CHALLENGE_TAG: &[u8] = b"schnorr";
+
+    /// A Schnorr signature `(R, s)` satisfying `sG = R + H(R, P, m) P`.
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct SchnorrSignature {
+        r: Secp256k1Point,
+        s: Secp256k1Scalar,
+    }
+
+    /// A Schnorr pre-signature `(R', s')` for an adaptor point `T` satisfying
+    /// `s'G = R' + H(R' + T, P, m) P`.
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct SchnorrPreSignature {
+        r: Secp256k1Point,
+        s: Secp256k1Scalar,
+    }
+
+    impl SchnorrSignature {
+        /// Create a signature on a message.
+        pub fn new<R: AllowedRng>(
+            key_pair: &Secp256k1KeyPair,
+            message: &[u8],
+            rng: &mut R,
+        ) -> Self {
+            let pre_signature =
+                SchnorrPreSignature::new(key_pair, message, &Secp256k1Point::zero(), rng);
+            pre_signature.adapt(&Secp256k1Scalar::zero())
+        }
+
+        /// Verify this signature on a message.
+        pub fn verify(
+            &self,
+            public_key: &Secp256k1PublicKey,
+            message: &[u8],
+        ) -> Result<(), FastCryptoError> {
+            let p = public_key_to_point(public_key);
+            let c = challenge(CHALLENGE_TAG, &[&self.r, &p], message);
+            if Secp256k1Point::generator() * self.s != self.r + p * c {
+                return Err(FastCryptoError::InvalidSignature);
+            }
+            Ok(())
+        }
+    }
+
+    impl SchnorrPreSignature {
+        /// Create a pre-signature on a message for the given adaptor point.
+        pub fn new<R: AllowedRng>(
+            key_pair: &Secp256k1KeyPair,
+            message: &[u8],
+            adaptor_point: &Secp256k1Point,
+            rng: &mut R,
+        ) -> Self {
+            let x = private_key_to_scalar(&key_pair.secret);
+            let p = public_key_to_point(key_pair.public());
+            let k = Secp256k1Scalar::rand(rng);
+            let r = Secp256k1Point::generator() * k;
+            let c = challenge(CHALLENGE_TAG, &[&(r + adaptor_point), &p], message);
+            Self { r, s: k + c * x }
+        }
+
+        /// Verify this pre-signature on a message for the given adaptor point.
+        pub fn verify(
+            &self,
+            public_key: &Secp256k1PublicKey,
+            message: &[u8],
+            adaptor_point: &Secp256k1Point,
+        ) -> Result<(), FastCryptoError> {
+            let p = public_key_to_point(public_key);
+            let c = challenge(CHALLENGE_TAG, &[&(self.r + adaptor_point), &p], message);
+            if Secp256k1Point::generator() * self.s != self.r + p * c {
+                return Err(FastCryptoError::InvalidSignature);
+            }
+            Ok(())
+        }
+
+        /// Complete this pre-signature using the discrete log of the adaptor point. If the
+        /// pre-signature is valid and the adaptor secret is correct, the result is a valid signature.
+        pub fn adapt(&self, adaptor_secret: &Secp256k1Scalar) -> SchnorrSignature {
+            SchnorrSignature {
+                r: self.r + Secp256k1Point::generator() * adaptor_secret,
+                s: self.s + adaptor_secret,
+            }
+        }
+
+        /// Extract the discrete log of the adaptor point from this pre-signature and the adapted
+        /// signature. Returns an error if the signature was not adapted from this pre-signature.
+        pub fn extract(
+            &self,
+            signature: &SchnorrSignature,
+            adaptor_point: &Secp256k1Point,
+        ) -> Result<Secp256k1Scalar, FastCryptoError> {
+            if signature.r != self.r + adaptor_point {
+                return Err(FastCryptoError::InvalidInput);
+            }
+            let adaptor_secret = signature.s - self.s;
+            if Secp256k1Point::generator() * adaptor_secret != *adaptor_point {
+                return Err(FastCryptoError::InvalidInput);
+            }
+            Ok(adaptor_secret)
+        }
+    }
+}
+
+/// Adaptor signatures for ECDSA signatures over secp256k1.
+pub mod ecdsa {
+    use super::{challenge, private_key_to_scalar, public_key_to_point};
+    use crate::error::FastCryptoError;
+    use crate::groups::secp256k1::{Secp256k1Point, Secp256k1Scalar};
+    use crate::groups::{GroupElement, Scalar};
+    use crate::hash::HashFunction;
+    use crate::secp256k1::{
+        DefaultHash, Secp256k1KeyPair, Secp256k1PublicKey, Secp256k1Signature,
+        SECP256K1_SIGNATURE_LENGTH,
+    };
+    use crate::serde_helpers::ToFromByteArray;
+    use crate::traits::{AllowedRng, KeyPair, ToFromBytes};
+    use serde::{Deserialize, Serialize};
+
+    const DLEQ_TAG: &[u8] = b"ecdsa-dleq";
+
+    /// A non-interactive proof that `log_G R' = log_Y R`.
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct DleqProof {
+        challenge: Secp256k1Scalar,
+        response: Secp256k1Scalar,
+    }
+
+    /// An ECDSA pre-signature for an adaptor point `Y`. It consists of `R = kY` and `R' = kG` with a
+    /// proof that these have the same discrete log, and `s' = k^{-1}(H(m) + rx)` where `r` is the
+    /// x-coordinate of `R`.
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct EcdsaPreSignature {
+        r: Secp256k1Point,
+        r_prime: Secp256k1Point,
+        s: Secp256k1Scalar,
+        proof: DleqProof,
+    }
+
+    fn hash_message(message: &[u8]) -> Secp256k1Scalar {
+        Secp256k1Scalar::from_bytes_mod_order(&DefaultHash::digest(message).digest)
+    }
+
+    impl DleqProof {
+        fn prove<R: AllowedRng>(
+            k: &Secp256k1Scalar,
+            y: &Secp256k1Point,
+            r_prime: &Secp256k1Point,
+            r: &Secp256k1Point,
+            rng: &mut R,
+        ) -> Self {
+            let a = Secp256k1Scalar::rand(rng);
+            let a_g = Secp256k1Point::generator() * a;
+            let a_y = *y * a;
+            let challenge = challenge(DLEQ_TAG, &[y, r_prime, r, &a_g, &a_y], &[]);
+            Self {
+                challenge,
+                response: a + challenge * k,
+            }
+        }
+
+        fn verify(
+            &self,
+            y: &Secp256k1Point,
+            r_prime: &Secp256k1Point,
+            r: &Secp256k1Point,
+        ) -> Result<(), FastCryptoError> {
+            let a_g = Secp256k1Point::generator() * self.response - *r_prime * self.challenge;
+            let a_y = *y * self.response - *r * self.challenge;
+            if challenge(DLEQ_TAG, &[y, r_prime, r, &a_g, &a_y], &[]) != self.challenge {
+                return Err(FastCryptoError::InvalidProof);
+            }
+            Ok(())
+        }
+    }
+
+    impl EcdsaPreSignature {
+        /// Create a pre-signature on a message for the given adaptor point. As with
+        /// [crate::traits::VerifyingKey::verify] for [Secp256k1PublicKey], the message is
+        /// hashed using Sha256.
+        pub fn new<R: AllowedRng>(
+            key_pair: &Secp256k1KeyPair,
+            message: &[u8],
+            adaptor_point: &Secp256k1Point,
+            rng: &mut R,
+        ) -> Self {
+            let x = private_key_to_scalar(&key_pair.secret);
+            let h = hash_message(message);
+            loop {
+                let k = Secp256k1Scalar::rand(rng);
+                let r = *adaptor_point * k;
+                let r_prime = Secp256k1Point::generator() * k;
+                let r_x = match r.x_as_scalar() {
+                    Ok(r_x) if r_x != Secp256k1Scalar::zero() => r_x,
+                    _ => continue,
+                };
+                let s = match (h + r_x * x) / k {
+                    Ok(s) if s != Secp256k1Scalar::zero() => s,
+                    _ => continue,
+                };
+                let proof = DleqProof::prove(&k, adaptor_point, &r_prime, &r, rng);
+                return Self {
+                    r,
+                    r_prime,
+                    s,
+                    proof,
+                };
+            }
+        }
+
+        /// Verify this pre-signature on a message for the given adaptor point.
+        pub fn verify(
+            &self,
+            public_key: &Secp256k1PublicKey,
+            message: &[u8],
+            adaptor_point: &Secp256k1Point,
+        ) -> Result<(), FastCryptoError> {
+            self.proof.verify(adaptor_point, &self.r_prime, &self.r)?;
+            let r_x = self.r.x_as_scalar()?;
+            let u = (hash_message(message) / self.s)?;
+            let v = (r_x / self.s)?;
+            if Secp256k1Point::generator() * u + public_key_to_point(public_key) * v != self.r_prime
+            {
+                return Err(FastCryptoError::InvalidSignature);
+            }
+            Ok(())
+        }
+
+        /// Complete this pre-signature using the discrete log of the adaptor point. If the
+        /// pre-signature is valid and the adaptor secret is correct, the result is a valid (low-s
+        /// normalized) ECDSA signature.
+        pub fn adapt(
+            &self,
+            adaptor_secret: &Secp256k1Scalar,
+        ) -> Result<Secp256k1Signature, FastCryptoError> {
+            let mut s = (self.s / adaptor_secret)?;
+            if s.is_high() {
+                s = -s;
+            }
+            let mut bytes = [0u8; SECP256K1_SIGNATURE_LENGTH];
+            bytes[..32].copy_from_slice(&self.r.x_as_scalar()?.to_byte_array());
+            bytes[32..].copy_from_slice(&s.to_byte_array());
+            Secp256k1Signature::from_bytes(&bytes)
+        }
+
+        /// Extract the discrete log of the adaptor point from this pre-signature and the adapted
+        /// signature. Returns an error if the signature was not adapted from this pre-signature.
+        pub fn extract(
+            &self,
+            signature: &Secp256k1Signature,
+            adaptor_point: &Secp256k1Point,
+        ) -> Result<Secp256k1Scalar, FastCryptoError> {
+            let bytes = signature.as_ref();
+            let r =
+                Secp256k1Scalar::from_byte_array(bytes[..32].try_into().expect("Length is 64"))?;
+            let s =
+                Secp256k1Scalar::from_byte_array(bytes[32..].try_into().expect("Length is 64"))?;
+            if r != self.r.x_as_scalar()? {
+                return Err(FastCryptoError::InvalidInput);
+            }
+
+            // The adapted signature may have been normalized, so both signs of s are tried.
+            let adaptor_secret = (self.s / s)?;
+            if Secp256k1Point::generator() * adaptor_secret == *adaptor_point {
+                Ok(adaptor_secret)
+            } else if Secp256k1Point::generator() * -adaptor_secret == *adaptor_point {
+                Ok(-adaptor_secret)
+            } else {
+                Err(FastCryptoError::InvalidInput)
+            }
+        }
+    }
+}
@@ -16,6 +16,23 @@
 //! let decrypted = cipher.decrypt(&iv, &ciphertext).unwrap();
 //! assert_eq!(decrypted, plaintext);
 //! ```
+//!
+//! Large messages can be encrypted chunk by chunk using the STREAM construction:
+//! ```
+//! # use fastcrypto::aes::*;
+//! # use crate::fastcrypto::traits::Generate;
+//! # use typenum::U12;
+//! use rand::thread_rng;
+//! let key = AesKey::generate(&mut thread_rng());
+//! let prefix = StreamNoncePrefix::generate(&mut thread_rng());
+//! let mut encryptor = StreamEncryptor::new(Aes256Gcm::<U12>::new(key.clone()), &prefix);
+//! let c1 = encryptor.encrypt_next(b"", b"Hello, ").unwrap();
+//! let c2 = encryptor.encrypt_last(b"", b"world!").unwrap();
+//!
+//! let mut decryptor = StreamDecryptor::new(Aes256Gcm::<U12>::new(key), &prefix);
+//! assert_eq!(decryptor.decrypt_next(b"", &c1).unwrap(), b"Hello, ");
+//! assert_eq!(decryptor.decrypt_last(b"", &c2).unwrap(), b"world!");
+//! ```
 
 use crate::{
     error::FastCryptoError,
@@ -28,11 +45,13 @@ use aes::cipher::{
 use aes_gcm::AeadInPlace;
 use fastcrypto_derive::{SilentDebug, SilentDisplay};
 use generic_array::{ArrayLength, GenericArray};
+use hkdf::hmac::{Hmac, Mac};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 use std::marker::PhantomData;
-use typenum::{U16, U24, U32};
+use subtle::ConstantTimeEq;
+use typenum::{U12, U16, U24, U32, U7};
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
 /// Trait impl'd by encryption keys in symmetric cryptography
@@ -347,3 +366,263 @@ pub type Aes128Gcm<NonceSize> = AesGcm<U16, aes::Aes128, NonceSize>;
 
 /// AES256 in GCM-mode (authenticated) using the given nonce size.
 pub type Aes256Gcm<NonceSize> = AesGcm<U32, aes::Aes256, NonceSize>;
+
+/// AES in GCM-SIV mode (authenticated and nonce-misuse resistant) as specified in
+/// [RFC 8452](https://www.rfc-editor.org/rfc/rfc8452). Reusing a nonce only reveals whether the same
+/// message was encrypted twice with the same nonce and associated data.
+pub struct AesGcmSiv<KeySize: ArrayLength<u8>, Aes> {
+    key: AesKey<KeySize>,
+    algorithm: PhantomData<Aes>,
+}
+
+impl<KeySize: ArrayLength<u8>, Aes> AesGcmSiv<KeySize, Aes> {
+    pub fn new(key: AesKey<KeySize>) -> Self {
+        Self {
+            key,
+            algorithm: PhantomData,
+        }
+    }
+}
+
+impl<KeySize: ArrayLength<u8>, Aes> AuthenticatedCipher for AesGcmSiv<KeySize, Aes>
+where
+    Aes: KeySizeUser<KeySize = KeySize>
+        + KeyInit
+        + BlockCipher
+        + BlockSizeUser<BlockSize = U16>
+        + BlockEncrypt,
+{
+    type IVType = InitializationVector<U12>;
+
+    fn encrypt_authenticated(&self, iv: &Self::IVType, aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        let cipher = aes_gcm_siv::AesGcmSiv::<Aes>::new(&self.key.bytes);
+        let mut buffer: Vec<u8> = plaintext.to_vec();
+        cipher
+            .encrypt_in_place(iv.as_bytes().into(), aad, &mut buffer)
+            .unwrap();
+        buffer
+    }
+
+    fn decrypt_authenticated(
+        &self,
+        iv: &Self::IVType,
+        aad: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, FastCryptoError> {
+        let cipher = aes_gcm_siv::AesGcmSiv::<Aes>::new(&self.key.bytes);
+        let mut buffer: Vec<u8> = ciphertext.to_vec();
+        cipher
+            .decrypt_in_place(iv.as_bytes().into(), aad, &mut buffer)
+            .map_err(|_| FastCryptoError::GeneralOpaqueError)?;
+        Ok(buffer)
+    }
+}
+
+impl<KeySize: ArrayLength<u8>, Aes> Cipher for AesGcmSiv<KeySize, Aes>
+where
+    Aes: KeySizeUser<KeySize = KeySize>
+        + KeyInit
+        + BlockCipher
+        + BlockSizeUser<BlockSize = U16>
+        + BlockEncrypt,
+{
+    type IVType = InitializationVector<U12>;
+
+    fn encrypt(&self, iv: &Self::IVType, plaintext: &[u8]) -> Vec<u8> {
+        self.encrypt_authenticated(iv, b"", plaintext)
+    }
+
+    fn decrypt(&self, iv: &Self::IVType, ciphertext: &[u8]) -> Result<Vec<u8>, FastCryptoError> {
+        self.decrypt_authenticated(iv, b"", ciphertext)
+    }
+}
+
+/// AES256 in GCM-SIV-mode (authenticated and nonce-misuse resistant).
+pub type Aes256GcmSiv = AesGcmSiv<U32, aes::Aes256>;
+
+/// Domain separation tag for deriving the commitment and encryption key in [KeyCommittingAesGcm].
+const KEY_COMMITMENT_DST: &[u8] = b"fastcrypto-aead-key-commitment";
+
+/// Length of the key commitment prepended to ciphertexts of [KeyCommittingAesGcm].
+pub const KEY_COMMITMENT_LENGTH: usize = 32;
+
+/// AES-GCM made key-committing as described in [Albertini et al.](https://www.usenix.org/system/files/sec22-albertini.pdf):
+/// the commitment `HMAC-SHA256(K, dst || 0x01)` is prepended to each ciphertext and the message is
+/// encrypted under the derived key `HMAC-SHA256(K, dst || 0x02)`. A ciphertext can thus only be
+/// decrypted under the key used to create it, which prevents partitioning oracle attacks when the
+/// ciphertexts come from untrusted parties.
+pub struct KeyCommittingAesGcm<KeySize: ArrayLength<u8>, Aes, NonceSize> {
+    cipher: AesGcm<KeySize, Aes, NonceSize>,
+    commitment: [u8; KEY_COMMITMENT_LENGTH],
+}
+
+impl<KeySize: ArrayLength<u8> + Debug, Aes, NonceSize>
+    KeyCommittingAesGcm<KeySize, Aes, NonceSize>
+{
+    pub fn new(key: AesKey<KeySize>) -> Self {
+        let derive = |i: u8| {
+            let mut mac = Hmac::<sha2::Sha256>::new_from_slice(key.as_bytes())
+                .expect("HMAC can take key of any size");
+            mac.update(KEY_COMMITMENT_DST);
+            mac.update(&[i]);
+            let output: [u8; KEY_COMMITMENT_LENGTH] = mac.finalize().into_bytes().into();
+            output
+        };
+        let commitment = derive(1);
+        let mut encryption_key = derive(2);
+        let cipher = AesGcm::new(
+            AesKey::from_bytes(&encryption_key[..KeySize::USIZE]).expect("Key size is at most 32"),
+        );
+        encryption_key.zeroize();
+        Self { cipher, commitment }
+    }
+}
+
+impl<KeySize: ArrayLength<u8>, Aes, NonceSize> AuthenticatedCipher
+    for KeyCommittingAesGcm<KeySize, Aes, NonceSize>
+where
+    AesGcm<KeySize, Aes, NonceSize>: AuthenticatedCipher<IVType = InitializationVector<NonceSize>>,
+    NonceSize: ArrayLength<u8> + Debug,
+{
+    type IVType = InitializationVector<NonceSize>;
+
+    fn encrypt_authenticated(&self, iv: &Self::IVType, aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        let mut result = self.commitment.to_vec();
+        result.extend(self.cipher.encrypt_authenticated(iv, aad, plaintext));
+        result
+    }
+
+    fn decrypt_authenticated(
+        &self,
+        iv: &Self::IVType,
+        aad: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, FastCryptoError> {
+        if ciphertext.len() < KEY_COMMITMENT_LENGTH {
+            return Err(FastCryptoError::InputTooShort(KEY_COMMITMENT_LENGTH));
+        }
+        let (commitment, ciphertext) = ciphertext.split_at(KEY_COMMITMENT_LENGTH);
+        if !bool::from(commitment.ct_eq(&self.commitment)) {
+            return Err(FastCryptoError::GeneralOpaqueError);
+        }
+        self.cipher.decrypt_authenticated(iv, aad, ciphertext)
+    }
+}
+
+/// Key-committing AES128 in GCM-mode using the given nonce size.
+pub type KeyCommittingAes128Gcm<NonceSize> = KeyCommittingAesGcm<U16, aes::Aes128, NonceSize>;
+
+/// Key-committing AES256 in GCM-mode using the given nonce size.
+pub type KeyCommittingAes256Gcm<NonceSize> = KeyCommittingAesGcm<U32, aes::Aes256, NonceSize>;
+
+/// The nonce prefix used by [StreamEncryptor] and [StreamDecryptor]. It must be unique for each
+/// stream encrypted with the same key.
+pub type StreamNoncePrefix = GenericByteArray<U7>;
+
+/// Compute the nonce `prefix || counter || last_chunk_flag` of a chunk in the STREAM construction.
+fn stream_nonce(prefix: &StreamNoncePrefix, counter: u32, last: bool) -> InitializationVector<U12> {
+    let mut nonce = GenericArray::<u8, U12>::default();
+    nonce[..7].copy_from_slice(prefix.as_bytes());
+    nonce[7..11].copy_from_slice(&counter.to_be_bytes());
+    nonce[11] = last as u8;
+    InitializationVector { bytes: nonce }
+}
+
+/// State shared by encryptors and decryptors in the STREAM construction.
+struct StreamState<C> {
+    cipher: C,
+    prefix: StreamNoncePrefix,
+    counter: u32,
+}
+
+impl<C> StreamState<C> {
+    fn new(cipher: C, prefix: &StreamNoncePrefix) -> Self {
+        Self {
+            cipher,
+            prefix: prefix.clone(),
+            counter: 0,
+        }
+    }
+
+    /// Return the nonce for the next chunk and advance the counter.
+    fn next_nonce(&mut self, last: bool) -> Result<InitializationVector<U12>, FastCryptoError> {
+        let nonce = stream_nonce(&self.prefix, self.counter, last);
+        if !last {
+            self.counter = self
+                .counter
+                .checked_add(1)
+                .ok_or_else(|| FastCryptoError::GeneralError("Too many chunks".to_string()))?;
+        }
+        Ok(nonce)
+    }
+}
+
+/// Encryptor for the [STREAM construction](https://eprint.iacr.org/2015/189.pdf) which encrypts
+/// a message as a sequence of chunks, each authenticated with its position in the sequence and
+/// whether it is the last chunk. A decryptor thus detects reordered, dropped and truncated chunks
+/// while only holding a single chunk in memory.
+pub struct StreamEncryptor<C> {
+    state: StreamState<C>,
+}
+
+impl<C: AuthenticatedCipher<IVType = InitializationVector<U12>>> StreamEncryptor<C> {
+    /// Create a new encryptor. The prefix must not be reused for another stream under the same key.
+    pub fn new(cipher: C, prefix: &StreamNoncePrefix) -> Self {
+        Self {
+            state: StreamState::new(cipher, prefix),
+        }
+    }
+
+    /// Encrypt the next chunk of the stream. Fails if there have already been `2^32 - 1` chunks.
+    pub fn encrypt_next(&mut self, aad: &[u8], chunk: &[u8]) -> Result<Vec<u8>, FastCryptoError> {
+        let nonce = self.state.next_nonce(false)?;
+        Ok(self.state.cipher.encrypt_authenticated(&nonce, aad, chunk))
+    }
+
+    /// Encrypt the last chunk of the stream.
+    pub fn encrypt_last(mut self, aad: &[u8], chunk: &[u8]) -> Result<Vec<u8>, FastCryptoError> {
+        let nonce = self.state.next_nonce(true)?;
+        Ok(self.state.cipher.encrypt_authenticated(&nonce, aad, chunk))
+    }
+}
+
+/// Decryptor for the STREAM construction. See [StreamEncryptor].
+pub struct StreamDecryptor<C> {
+    state: StreamState<C>,
+}
+
+impl<C: AuthenticatedCipher<IVType = InitializationVector<U12>>> StreamDecryptor<C> {
+    /// Create a new decryptor for a stream encrypted with the given prefix.
+    pub fn new(cipher: C, prefix: &StreamNoncePrefix) -> Self {
+        Self {
+            state: StreamState::new(cipher, prefix),
+        }
+    }
+
+    /// Decrypt the next chunk of the stream. Fails if the chunk is not authentic, if it is not the
+    /// next chunk in the stream or if it is the last chunk. The stream should be discarded after
+    /// an error.
+    pub fn decrypt_next(
+        &mut self,
+        aad: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, FastCryptoError> {
+        let nonce = self.state.next_nonce(false)?;
+        self.state
+            .cipher
+            .decrypt_authenticated(&nonce, aad, ciphertext)
+    }
+
+    /// Decrypt the last chunk of the stream. Fails if the chunk is not authentic or if it is not the
+    /// last chunk of the stream, so a truncated stream is detected.
+    pub fn decrypt_last(
+        mut self,
+        aad: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, FastCryptoError> {
+        let nonce = self.state.next_nonce(true)?;
+        self.state
+            .cipher
+            .decrypt_authenticated(&nonce, aad, ciphertext)
+    }
+}
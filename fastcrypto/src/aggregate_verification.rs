@@ -0,0 +1,108 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verification of many signatures on the same message, e.g. votes of a large validator set, via
+//! aggregation, which identifies an invalid signature if the aggregate is invalid.
+//!
+//! [verify_in_chunks] first verifies the aggregate of all signatures. If that fails, the signatures
+//! are shuffled and split into chunks whose aggregates are verified one by one. The first invalid
+//! chunk is then split in halves recursively until a single invalid signature is found, which costs
+//! a logarithmic number of verifications in the chunk size. The random order prevents an adversary
+//! from choosing signatures which only cancel out within a chunk.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::aggregate_verification::*;
+//! # use fastcrypto::bls12381::min_sig::{BLS12381AggregateSignature, BLS12381KeyPair};
+//! # use fastcrypto::traits::{KeyPair, Signer};
+//! # use rand::thread_rng;
+//! let key_pairs = (0..10).map(|_| BLS12381KeyPair::generate(&mut thread_rng())).collect::<Vec<_>>();
+//! let public_keys = key_pairs.iter().map(|kp| kp.public().clone()).collect::<Vec<_>>();
+//! let mut signatures = key_pairs.iter().map(|kp| kp.sign(b"block")).collect::<Vec<_>>();
+//! signatures[7] = key_pairs[7].sign(b"other block");
+//!
+//! let result = verify_in_chunks::<BLS12381AggregateSignature, _>(
+//!     &public_keys, b"block", &signatures, 4, &mut thread_rng()).unwrap();
+//! match result {
+//!     ChunkedVerification::Invalid { index, .. } => assert_eq!(index, 7),
+//!     ChunkedVerification::Valid => panic!(),
+//! }
+//! ```
+
+use crate::error::FastCryptoError;
+use crate::limits::{enforce, Limit};
+use crate::traits::{AggregateAuthenticator, AllowedRng};
+use rand::seq::SliceRandom;
+
+/// The outcome of [verify_in_chunks].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChunkedVerification {
+    /// All signatures are valid.
+    Valid,
+    /// The signature at `index` is invalid. `chunk` contains the indices of the signatures whose
+    /// aggregate was found to be invalid first, and includes `index`.
+    Invalid { index: usize, chunk: Vec<usize> },
+}
+
+/// Verify that `signatures[i]` is a valid signature on `message` by `public_keys[i]` for all `i`.
+/// If not, an invalid signature is identified using verifications of aggregates of chunks of
+/// `chunk_size` signatures. Fails if the inputs have different lengths, if `chunk_size` is zero or
+/// if there are more signatures than allowed by [Limit::AggregateParticipants].
+pub fn verify_in_chunks<A: AggregateAuthenticator, R: AllowedRng>(
+    public_keys: &[A::PubKey],
+    message: &[u8],
+    signatures: &[A::Sig],
+    chunk_size: usize,
+    rng: &mut R,
+) -> Result<ChunkedVerification, FastCryptoError> {
+    if public_keys.len() != signatures.len() || chunk_size == 0 {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    enforce(Limit::AggregateParticipants, signatures.len())?;
+    let all = (0..signatures.len()).collect::<Vec<_>>();
+    if is_valid::<A>(public_keys, message, signatures, &all)? {
+        return Ok(ChunkedVerification::Valid);
+    }
+
+    let mut order = all;
+    order.shuffle(rng);
+    for chunk in order.chunks(chunk_size) {
+        if is_valid::<A>(public_keys, message, signatures, chunk)? {
+            continue;
+        }
+        // If the aggregates of both halves were valid, so would their sum be, so one of the halves
+        // must be invalid.
+        let mut subset = chunk;
+        while subset.len() > 1 {
+            let (left, right) = subset.split_at(subset.len() / 2);
+            subset = match is_valid::<A>(public_keys, message, signatures, left)? {
+                true => right,
+                false => left,
+            };
+        }
+        return Ok(ChunkedVerification::Invalid {
+            index: subset[0],
+            chunk: chunk.to_vec(),
+        });
+    }
+
+    // The chunks partition all signatures, so if all chunks were valid, their sum would be too.
+    Err(FastCryptoError::GeneralError(
+        "Aggregate is invalid but all chunks are valid".to_string(),
+    ))
+}
+
+/// Whether the aggregate of the signatures with the given indices is valid.
+fn is_valid<A: AggregateAuthenticator>(
+    public_keys: &[A::PubKey],
+    message: &[u8],
+    signatures: &[A::Sig],
+    indices: &[usize],
+) -> Result<bool, FastCryptoError> {
+    let aggregate = A::aggregate(indices.iter().map(|i| &signatures[*i]))?;
+    let public_keys = indices
+        .iter()
+        .map(|i| public_keys[*i].clone())
+        .collect::<Vec<_>>();
+    Ok(aggregate.verify(&public_keys, message).is_ok())
+}
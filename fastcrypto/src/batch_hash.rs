@@ -0,0 +1,195 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Hashing of many small messages at once, e.g. the leaves of a Merkle tree or the transactions of
+//! a block.
+//!
+//! [sha256_many] hashes groups of [SHA256_LANES] messages simultaneously. The state of each message
+//! is kept in its own lane of arrays of 32-bit words, so the compression function is vectorized by
+//! the compiler, e.g. using eight lanes with AVX2 or two times four lanes with NEON. All messages
+//! of a group take as long as the longest of them, so the throughput is best if messages of about
+//! the same length are hashed together.
+//!
+//! [blake3_many] hashes the messages on all available cores.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::batch_hash::*;
+//! # use fastcrypto::hash::{Blake3, HashFunction, Sha256};
+//! let messages = (0..100u32).map(|i| i.to_be_bytes()).collect::<Vec<_>>();
+//! let digests = sha256_many(&messages);
+//! assert_eq!(digests[7], Sha256::digest(messages[7]));
+//! let digests = blake3_many(&messages);
+//! assert_eq!(digests[7], Blake3::digest(messages[7]));
+//! ```
+
+use crate::hash::{Blake3, Digest, HashFunction};
+
+/// The number of messages hashed simultaneously by [sha256_many].
+pub const SHA256_LANES: usize = 8;
+
+/// The number of messages below which [blake3_many] does not spawn any threads.
+pub const BLAKE3_PARALLEL_THRESHOLD: usize = 256;
+
+const BLOCK_LENGTH: usize = 64;
+
+/// A word of the state of each of the messages hashed simultaneously.
+type Lanes = [u32; SHA256_LANES];
+
+const INITIAL_STATE: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const ROUND_CONSTANTS: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// Compute the SHA-256 digests of the given messages. The result is equal to hashing each message
+/// with [crate::hash::Sha256].
+pub fn sha256_many<M: AsRef<[u8]>>(messages: &[M]) -> Vec<Digest<32>> {
+    messages
+        .chunks(SHA256_LANES)
+        .flat_map(sha256_group)
+        .collect()
+}
+
+/// Compute the Blake3 digests of the given messages, using multiple threads if there are at least
+/// [BLAKE3_PARALLEL_THRESHOLD] messages. The result is equal to hashing each message with
+/// [Blake3].
+pub fn blake3_many<M: AsRef<[u8]> + Sync>(messages: &[M]) -> Vec<Digest<32>> {
+    let threads = std::thread::available_parallelism().map_or(1, |t| t.get());
+    if threads == 1 || messages.len() < BLAKE3_PARALLEL_THRESHOLD {
+        return messages.iter().map(Blake3::digest).collect();
+    }
+    let chunk_size = (messages.len() + threads - 1) / threads;
+    std::thread::scope(|scope| {
+        let handles = messages
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(Blake3::digest).collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("Hashing should not panic"))
+            .collect()
+    })
+}
+
+/// Hash at most [SHA256_LANES] messages simultaneously.
+fn sha256_group<M: AsRef<[u8]>>(messages: &[M]) -> Vec<Digest<32>> {
+    let blocks = messages
+        .iter()
+        .map(|m| padded_length(m.as_ref().len()) / BLOCK_LENGTH)
+        .collect::<Vec<_>>();
+    let mut state: [Lanes; 8] = INITIAL_STATE.map(|word| [word; SHA256_LANES]);
+    let mut block = [0u8; BLOCK_LENGTH];
+
+    for index in 0..blocks.iter().copied().max().unwrap_or(0) {
+        // Lanes of messages which have no more blocks are compressed with a zero block, but the
+        // result is discarded.
+        let mut schedule = [[0u32; SHA256_LANES]; 16];
+        for (lane, message) in messages.iter().enumerate() {
+            if index < blocks[lane] {
+                padded_block(message.as_ref(), index, &mut block);
+                for (word, bytes) in schedule.iter_mut().zip(block.chunks_exact(4)) {
+                    word[lane] = u32::from_be_bytes(bytes.try_into().expect("Length is 4"));
+                }
+            }
+        }
+        let updated = compress(&state, schedule);
+        for (lane, _) in blocks.iter().enumerate().filter(|(_, b)| index < **b) {
+            for (word, updated) in state.iter_mut().zip(updated.iter()) {
+                word[lane] = updated[lane];
+            }
+        }
+    }
+
+    (0..messages.len())
+        .map(|lane| {
+            let mut digest = [0u8; 32];
+            for (bytes, word) in digest.chunks_exact_mut(4).zip(state.iter()) {
+                bytes.copy_from_slice(&word[lane].to_be_bytes());
+            }
+            Digest::new(digest)
+        })
+        .collect()
+}
+
+/// The length of a message of the given length after padding.
+fn padded_length(length: usize) -> usize {
+    // The padding is a single one bit followed by zeros and the length in bits as a 64-bit integer.
+    (length + 9 + BLOCK_LENGTH - 1) / BLOCK_LENGTH * BLOCK_LENGTH
+}
+
+/// Write the block with the given index of the padded message into `block`.
+fn padded_block(message: &[u8], index: usize, block: &mut [u8; BLOCK_LENGTH]) {
+    let start = index * BLOCK_LENGTH;
+    block.fill(0);
+    if start < message.len() {
+        let end = message.len().min(start + BLOCK_LENGTH);
+        block[..end - start].copy_from_slice(&message[start..end]);
+    }
+    if (start..start + BLOCK_LENGTH).contains(&message.len()) {
+        block[message.len() - start] = 0x80;
+    }
+    if start + BLOCK_LENGTH == padded_length(message.len()) {
+        block[BLOCK_LENGTH - 8..].copy_from_slice(&((message.len() as u64) * 8).to_be_bytes());
+    }
+}
+
+/// Apply the compression function to all lanes. Each operation is written lane by lane to allow
+/// the compiler to use SIMD instructions.
+fn compress(state: &[Lanes; 8], mut w: [Lanes; 16]) -> [Lanes; 8] {
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+
+    for (t, k) in ROUND_CONSTANTS.iter().enumerate() {
+        if t >= 16 {
+            // Indices modulo 16 of t - 16, t - 15, t - 7 and t - 2.
+            let (w16, w15, w7, w2) = (
+                w[t % 16],
+                w[(t + 1) % 16],
+                w[(t + 9) % 16],
+                w[(t + 14) % 16],
+            );
+            w[t % 16] = lanes(|i| {
+                let s0 = w15[i].rotate_right(7) ^ w15[i].rotate_right(18) ^ (w15[i] >> 3);
+                let s1 = w2[i].rotate_right(17) ^ w2[i].rotate_right(19) ^ (w2[i] >> 10);
+                w16[i].wrapping_add(s0).wrapping_add(w7[i]).wrapping_add(s1)
+            });
+        }
+        let t1 = lanes(|i| {
+            let s1 = e[i].rotate_right(6) ^ e[i].rotate_right(11) ^ e[i].rotate_right(25);
+            let ch = (e[i] & f[i]) ^ (!e[i] & g[i]);
+            h[i].wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(*k)
+                .wrapping_add(w[t % 16][i])
+        });
+        let t2 = lanes(|i| {
+            let s0 = a[i].rotate_right(2) ^ a[i].rotate_right(13) ^ a[i].rotate_right(22);
+            let maj = (a[i] & b[i]) ^ (a[i] & c[i]) ^ (b[i] & c[i]);
+            s0.wrapping_add(maj)
+        });
+        h = g;
+        g = f;
+        f = e;
+        e = lanes(|i| d[i].wrapping_add(t1[i]));
+        d = c;
+        c = b;
+        b = a;
+        a = lanes(|i| t1[i].wrapping_add(t2[i]));
+    }
+
+    let result = [a, b, c, d, e, f, g, h];
+    std::array::from_fn(|j| lanes(|i| state[j][i].wrapping_add(result[j][i])))
+}
+
+fn lanes(f: impl FnMut(usize) -> u32) -> Lanes {
+    std::array::from_fn(f)
+}
@@ -0,0 +1,382 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! BBS+ signatures over BLS12-381, which sign a vector of messages at once and allow the holder of
+//! a signature to prove knowledge of it while revealing only some of the messages, e.g. a subset of
+//! the attributes of a credential.
+//!
+//! The construction follows [Camenisch, Drijvers and Lehmann](https://eprint.iacr.org/2016/663.pdf):
+//! * A signature on messages `m_1, ..., m_L` is `(A, e, s)` with `A = B * 1/(e + x)` where
+//!   `B = g_1 + s * h_0 + sum m_i * h_i` and `x` is the private key. It is verified by checking
+//!   `e(A, w + e * g_2) = e(B, g_2)` where `w = x * g_2` is the public key.
+//! * A [Presentation] randomizes `A` and is a non-interactive zero-knowledge proof of knowledge of
+//!   a signature and the undisclosed messages, bound to a nonce chosen by the verifier.
+//!
+//! The generators `h_0, ..., h_L` are derived by hashing to G1, so nobody knows their discrete
+//! logarithms. Messages are scalars, and byte strings can be mapped to messages with [message].
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::bbs_plus::*;
+//! # use rand::thread_rng;
+//! let parameters = PublicParameters::new(3);
+//! let (private_key, public_key) = generate_keypair(&mut thread_rng());
+//! let messages = [message(b"alice"), message(b"1990-01-01"), message(b"switzerland")];
+//! let signature = private_key.sign(&parameters, &messages, &mut thread_rng()).unwrap();
+//! assert!(public_key.verify(&parameters, &messages, &signature).is_ok());
+//!
+//! // Only reveal the country.
+//! let presentation = signature
+//!     .present(&parameters, &public_key, &messages, &[2], b"nonce", &mut thread_rng())
+//!     .unwrap();
+//! assert!(presentation
+//!     .verify(&parameters, &public_key, &[(2, messages[2])], b"nonce")
+//!     .is_ok());
+//! ```
+
+use crate::error::FastCryptoError;
+use crate::groups::bls12381::{G1Element, G2Element, GTElement, Scalar};
+use crate::groups::{GroupElement, HashToGroupElement, Scalar as ScalarTrait};
+use crate::hash::{HashFunction, Sha256};
+use crate::pairing_product::PairingProductEquation;
+use crate::serde_helpers::ToFromByteArray;
+use crate::traits::AllowedRng;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+/// Domain separation tag used when deriving the generators.
+const GENERATORS_DST: &[u8] = b"FASTCRYPTO_BBS_PLUS_BLS12381_GENERATORS_V1";
+
+/// Domain separation tag used when computing the challenge of a presentation.
+const CHALLENGE_DST: &[u8] = b"FASTCRYPTO_BBS_PLUS_BLS12381_CHALLENGE_V1";
+
+/// Domain separation tag used when mapping byte strings to messages.
+const MESSAGE_DST: &[u8] = b"FASTCRYPTO_BBS_PLUS_BLS12381_MESSAGE_V1";
+
+/// The generators `h_0, ..., h_L` for signatures on `L` messages.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublicParameters {
+    h_0: G1Element,
+    h: Vec<G1Element>,
+}
+
+/// A private key `x`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrivateKey(Scalar);
+
+/// A public key `w = x * g_2`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublicKey(G2Element);
+
+/// A signature `(A, e, s)` on a vector of messages.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Signature {
+    a: G1Element,
+    e: Scalar,
+    s: Scalar,
+}
+
+/// A proof of knowledge of a signature which reveals only some of the messages.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Presentation {
+    a_prime: G1Element,
+    a_bar: G1Element,
+    d: G1Element,
+    challenge: Scalar,
+    e_hat: Scalar,
+    r2_hat: Scalar,
+    r3_hat: Scalar,
+    s_hat: Scalar,
+    /// The responses for the undisclosed messages in increasing order of their indices.
+    m_hat: Vec<Scalar>,
+}
+
+/// Map a byte string to a message.
+pub fn message(bytes: &[u8]) -> Scalar {
+    hash_to_scalar(&[MESSAGE_DST, bytes])
+}
+
+/// Generate a new key pair.
+pub fn generate_keypair<R: AllowedRng>(rng: &mut R) -> (PrivateKey, PublicKey) {
+    let x = Scalar::rand(rng);
+    (PrivateKey(x), PublicKey(G2Element::generator() * x))
+}
+
+fn hash_to_scalar(inputs: &[&[u8]]) -> Scalar {
+    let mut hash = Sha256::default();
+    for input in inputs {
+        hash.update((input.len() as u64).to_be_bytes());
+        hash.update(input);
+    }
+    Scalar::rand(&mut StdRng::from_seed(hash.finalize().digest))
+}
+
+impl PublicParameters {
+    /// Derive the generators for signatures on `message_count` messages.
+    pub fn new(message_count: usize) -> Self {
+        let generator = |i: u64| {
+            let mut input = GENERATORS_DST.to_vec();
+            input.extend_from_slice(&i.to_be_bytes());
+            G1Element::hash_to_group_element(&input)
+        };
+        Self {
+            h_0: generator(0),
+            h: (1..=message_count as u64).map(generator).collect(),
+        }
+    }
+
+    /// The number of messages in a signature.
+    pub fn message_count(&self) -> usize {
+        self.h.len()
+    }
+
+    /// Compute `B = g_1 + s * h_0 + sum m_i * h_i`.
+    fn commit(&self, messages: &[Scalar], s: &Scalar) -> Result<G1Element, FastCryptoError> {
+        if messages.len() != self.h.len() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(self
+            .h
+            .iter()
+            .zip(messages)
+            .fold(G1Element::generator() + self.h_0 * s, |b, (h, m)| {
+                b + *h * m
+            }))
+    }
+}
+
+impl PrivateKey {
+    /// Sign a vector of messages, which must have the length given by the parameters.
+    pub fn sign<R: AllowedRng>(
+        &self,
+        parameters: &PublicParameters,
+        messages: &[Scalar],
+        rng: &mut R,
+    ) -> Result<Signature, FastCryptoError> {
+        let s = Scalar::rand(rng);
+        let b = parameters.commit(messages, &s)?;
+        loop {
+            let e = Scalar::rand(rng);
+            // Fails only if e = -x, which happens with negligible probability.
+            if let Ok(inverse) = Scalar::generator() / (e + self.0) {
+                return Ok(Signature {
+                    a: b * inverse,
+                    e,
+                    s,
+                });
+            }
+        }
+    }
+}
+
+impl From<&PrivateKey> for PublicKey {
+    fn from(private_key: &PrivateKey) -> Self {
+        PublicKey(G2Element::generator() * private_key.0)
+    }
+}
+
+impl PublicKey {
+    /// Verify a signature on a vector of messages.
+    pub fn verify(
+        &self,
+        parameters: &PublicParameters,
+        messages: &[Scalar],
+        signature: &Signature,
+    ) -> Result<(), FastCryptoError> {
+        let b = parameters.commit(messages, &signature.s)?;
+        if signature.a == G1Element::zero() {
+            return Err(FastCryptoError::InvalidSignature);
+        }
+        PairingProductEquation::<G1Element>::new(
+            vec![
+                (signature.a, self.0 + G2Element::generator() * signature.e),
+                (-b, G2Element::generator()),
+            ],
+            GTElement::zero(),
+        )
+        .verify()
+        .map_err(|_| FastCryptoError::InvalidSignature)
+    }
+}
+
+impl Signature {
+    /// Create a presentation revealing the messages with the given indices, which must be
+    /// increasing, for a verifier who chose `nonce`.
+    pub fn present<R: AllowedRng>(
+        &self,
+        parameters: &PublicParameters,
+        public_key: &PublicKey,
+        messages: &[Scalar],
+        disclosed: &[usize],
+        nonce: &[u8],
+        rng: &mut R,
+    ) -> Result<Presentation, FastCryptoError> {
+        check_disclosed(disclosed, parameters.message_count())?;
+        let b = parameters.commit(messages, &self.s)?;
+
+        // Randomize the signature.
+        let r1 = loop {
+            let r1 = Scalar::rand(rng);
+            if r1 != Scalar::zero() {
+                break r1;
+            }
+        };
+        let r2 = Scalar::rand(rng);
+        let r3 = (Scalar::generator() / r1)?;
+        let a_prime = self.a * r1;
+        let a_bar = b * r1 - a_prime * self.e;
+        let d = b * r1 - parameters.h_0 * r2;
+        let s_prime = self.s - r2 * r3;
+
+        // Prove knowledge of (e, r2) with a_bar - d = -e * a_prime + r2 * h_0 and of
+        // (r3, s', m_i for i not disclosed) with g_1 + sum_{i disclosed} m_i * h_i =
+        // r3 * d - s' * h_0 - sum_{i not disclosed} m_i * h_i.
+        let hidden = hidden_indices(disclosed, parameters.message_count());
+        let e_tilde = Scalar::rand(rng);
+        let r2_tilde = Scalar::rand(rng);
+        let r3_tilde = Scalar::rand(rng);
+        let s_tilde = Scalar::rand(rng);
+        let m_tilde = hidden.iter().map(|_| Scalar::rand(rng)).collect::<Vec<_>>();
+
+        let t1 = parameters.h_0 * r2_tilde - a_prime * e_tilde;
+        let t2 = hidden
+            .iter()
+            .zip(&m_tilde)
+            .fold(d * r3_tilde - parameters.h_0 * s_tilde, |t, (i, m)| {
+                t - parameters.h[*i] * m
+            });
+        let disclosed_messages = disclosed
+            .iter()
+            .map(|i| (*i, messages[*i]))
+            .collect::<Vec<_>>();
+        let challenge = challenge(
+            public_key,
+            &a_prime,
+            &a_bar,
+            &d,
+            &t1,
+            &t2,
+            &disclosed_messages,
+            nonce,
+        );
+
+        Ok(Presentation {
+            a_prime,
+            a_bar,
+            d,
+            challenge,
+            e_hat: e_tilde + challenge * self.e,
+            r2_hat: r2_tilde + challenge * r2,
+            r3_hat: r3_tilde + challenge * r3,
+            s_hat: s_tilde + challenge * s_prime,
+            m_hat: hidden
+                .iter()
+                .zip(m_tilde)
+                .map(|(i, m)| m + challenge * messages[*i])
+                .collect(),
+        })
+    }
+}
+
+impl Presentation {
+    /// Verify a presentation revealing the given messages, given as pairs of indices and messages
+    /// in increasing order of the indices.
+    pub fn verify(
+        &self,
+        parameters: &PublicParameters,
+        public_key: &PublicKey,
+        disclosed_messages: &[(usize, Scalar)],
+        nonce: &[u8],
+    ) -> Result<(), FastCryptoError> {
+        let disclosed = disclosed_messages
+            .iter()
+            .map(|(i, _)| *i)
+            .collect::<Vec<_>>();
+        check_disclosed(&disclosed, parameters.message_count())?;
+        let hidden = hidden_indices(&disclosed, parameters.message_count());
+        if self.m_hat.len() != hidden.len() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+
+        // The randomized signature must be valid for the public key, i.e. a_bar = x * a_prime.
+        if self.a_prime == G1Element::zero() {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        PairingProductEquation::<G1Element>::new(
+            vec![
+                (self.a_prime, public_key.0),
+                (-self.a_bar, G2Element::generator()),
+            ],
+            GTElement::zero(),
+        )
+        .verify()?;
+
+        let t1 = parameters.h_0 * self.r2_hat
+            - self.a_prime * self.e_hat
+            - (self.a_bar - self.d) * self.challenge;
+        let disclosed_commitment = disclosed_messages
+            .iter()
+            .fold(G1Element::generator(), |c, (i, m)| c + parameters.h[*i] * m);
+        let t2 = hidden.iter().zip(&self.m_hat).fold(
+            self.d * self.r3_hat
+                - parameters.h_0 * self.s_hat
+                - disclosed_commitment * self.challenge,
+            |t, (i, m)| t - parameters.h[*i] * m,
+        );
+
+        if challenge(
+            public_key,
+            &self.a_prime,
+            &self.a_bar,
+            &self.d,
+            &t1,
+            &t2,
+            disclosed_messages,
+            nonce,
+        ) != self.challenge
+        {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        Ok(())
+    }
+}
+
+/// Check that the disclosed indices are increasing and smaller than `message_count`.
+fn check_disclosed(disclosed: &[usize], message_count: usize) -> Result<(), FastCryptoError> {
+    if disclosed.windows(2).any(|w| w[0] >= w[1])
+        || disclosed.last().map_or(false, |i| *i >= message_count)
+    {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    Ok(())
+}
+
+fn hidden_indices(disclosed: &[usize], message_count: usize) -> Vec<usize> {
+    (0..message_count)
+        .filter(|i| disclosed.binary_search(i).is_err())
+        .collect()
+}
+
+#[allow(clippy::too_many_arguments)]
+fn challenge(
+    public_key: &PublicKey,
+    a_prime: &G1Element,
+    a_bar: &G1Element,
+    d: &G1Element,
+    t1: &G1Element,
+    t2: &G1Element,
+    disclosed_messages: &[(usize, Scalar)],
+    nonce: &[u8],
+) -> Scalar {
+    let mut points = public_key.0.to_byte_array().to_vec();
+    for point in [a_prime, a_bar, d, t1, t2] {
+        points.extend_from_slice(&point.to_byte_array());
+    }
+    let mut disclosed = Vec::new();
+    for (i, m) in disclosed_messages {
+        disclosed.extend_from_slice(&(*i as u64).to_be_bytes());
+        disclosed.extend_from_slice(&m.to_byte_array());
+    }
+    hash_to_scalar(&[CHALLENGE_DST, &points, &disclosed, nonce])
+}
@@ -0,0 +1,271 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Blind signatures allow a user to obtain a signature on a message from a signer without the
+//! signer learning the message or being able to link the resulting signature to the signing
+//! session. This is the main building block for privacy-preserving token issuance.
+//!
+//! Two schemes are provided:
+//! * [bls]: Blind BLS signatures (Boldyreva, 2003) over BLS12-381. Signing is a single round, and
+//!   the unblinded signature is a regular [crate::bls12381::min_sig::BLS12381Signature] which
+//!   verifies with the public key of the signer.
+//! * [schnorr]: Blind Schnorr signatures over Ristretto255. Signing takes three moves.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::blind_signatures::bls::{blind, blind_sign};
+//! # use fastcrypto::bls12381::min_sig::BLS12381KeyPair;
+//! # use fastcrypto::traits::{KeyPair, VerifyingKey};
+//! # use rand::thread_rng;
+//! let kp = BLS12381KeyPair::generate(&mut thread_rng());
+//! let public_key = kp.public().clone();
+//! let message: &[u8] = b"Hello, world!";
+//!
+//! // The user blinds the message and sends the blinded message to the signer.
+//! let (blinded_message, blinding_factor) = blind(message, &mut thread_rng());
+//!
+//! // The signer signs the blinded message and returns the blind signature.
+//! let blind_signature = blind_sign(&kp.private(), &blinded_message);
+//!
+//! // The user unblinds the signature and gets a regular BLS signature on the message.
+//! let signature = blind_signature.unblind(&blinding_factor).unwrap();
+//! assert!(public_key.verify(message, &signature).is_ok());
+//! ```
+
+/// Blind BLS signatures over BLS12-381, compatible with [crate::bls12381::min_sig].
+pub mod bls {
+    use crate::bls12381::min_sig::{BLS12381PrivateKey, BLS12381PublicKey, BLS12381Signature};
+    use crate::error::FastCryptoError;
+    use crate::groups::bls12381::{G1Element, G2Element, Scalar};
+    use crate::groups::{GroupElement, HashToGroupElement, Pairing, Scalar as ScalarTrait};
+    use crate::serde_helpers::ToFromByteArray;
+    use crate::traits::{AllowedRng, ToFromBytes};
+    use serde::{Deserialize, Serialize};
+
+    /// The secret factor used by the user to blind a message and later unblind the signature.
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct BlindingFactor(Scalar);
+
+    /// A blinded message which is sent to the signer.
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct BlindedMessage(G1Element);
+
+    /// A signature on a blinded message which is returned to the user.
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct BlindSignature(G1Element);
+
+    /// Blind a message. Returns the blinded message, which should be sent to the signer, and the
+    /// blinding factor, which should be kept secret by the user.
+    pub fn blind<R: AllowedRng>(message: &[u8], rng: &mut R) -> (BlindedMessage, BlindingFactor) {
+        let r = Scalar::rand(rng);
+        (
+            BlindedMessage(G1Element::hash_to_group_element(message) * r),
+            BlindingFactor(r),
+        )
+    }
+
+    /// Sign a blinded message.
+    pub fn blind_sign(
+        private_key: &BLS12381PrivateKey,
+        blinded_message: &BlindedMessage,
+    ) -> BlindSignature {
+        let bytes = private_key
+            .as_ref()
+            .try_into()
+            .expect("Private keys are 32 bytes");
+        let sk = Scalar::from_byte_array(bytes).expect("Private keys are valid scalars");
+        BlindSignature(blinded_message.0 * sk)
+    }
+
+    impl BlindSignature {
+        /// Verify that this is a valid signature on the blinded message. This allows the user to
+        /// check the response of the signer before unblinding.
+        pub fn verify(
+            &self,
+            public_key: &BLS12381PublicKey,
+            blinded_message: &BlindedMessage,
+        ) -> Result<(), FastCryptoError> {
+            let pk = G2Element::from_byte_array(
+                public_key
+                    .as_ref()
+                    .try_into()
+                    .map_err(|_| FastCryptoError::InvalidInput)?,
+            )?;
+            if self.0.pairing(&G2Element::generator()) != blinded_message.0.pairing(&pk) {
+                return Err(FastCryptoError::InvalidSignature);
+            }
+            Ok(())
+        }
+
+        /// Unblind this signature. If the signer signed the blinded message honestly, the result is
+        /// a valid signature on the original message.
+        pub fn unblind(
+            &self,
+            blinding_factor: &BlindingFactor,
+        ) -> Result<BLS12381Signature, FastCryptoError> {
+            let r_inverse = (Scalar::generator() / blinding_factor.0)?;
+            BLS12381Signature::from_bytes(&(self.0 * r_inverse).to_byte_array())
+        }
+    }
+}
+
+/// Blind Schnorr signatures over Ristretto255.
+///
+/// The protocol is a three-move protocol between a signer holding a [PrivateKey] and a user:
+/// 1. The signer creates a [SigningNonce] and sends the corresponding [Commitment] to the user.
+/// 2. The user blinds the commitment and its message, keeps the [UnblindingState] and sends the
+///    [BlindedChallenge] to the signer.
+/// 3. The signer responds with a [BlindResponse] which the user unblinds into a [Signature].
+///
+/// Note that the signer must not run many signing sessions concurrently, since that allows the
+/// ROS attack (Benhamouda et al., 2020) where the user obtains more signatures than the number of
+/// sessions. Sessions should be run sequentially.
+pub mod schnorr {
+    use crate::error::FastCryptoError;
+    use crate::groups::ristretto255::{RistrettoPoint, RistrettoScalar};
+    use crate::groups::{FixedBaseMultiplication, HashToGroupElement, Scalar};
+    use crate::serde_helpers::ToFromByteArray;
+    use crate::traits::AllowedRng;
+    use serde::{Deserialize, Serialize};
+
+    /// Domain separation tag used when computing the challenge.
+    const DST: &[u8] = b"FASTCRYPTO_BLIND_SCHNORR_RISTRETTO255_V1";
+
+    /// A private key for blind Schnorr signatures.
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct PrivateKey(RistrettoScalar);
+
+    /// A public key for blind Schnorr signatures.
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct PublicKey(RistrettoPoint);
+
+    /// A Schnorr signature `(R, s)` satisfying `sG = R + H(R, P, m) P`.
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct Signature {
+        r: RistrettoPoint,
+        s: RistrettoScalar,
+    }
+
+    /// The secret nonce of the signer for a single signing session.
+    pub struct SigningNonce {
+        k: RistrettoScalar,
+    }
+
+    /// The commitment `R = kG` sent by the signer to the user.
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct Commitment(RistrettoPoint);
+
+    /// The blinded challenge sent by the user to the signer.
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct BlindedChallenge(RistrettoScalar);
+
+    /// The response sent by the signer to the user.
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct BlindResponse(RistrettoScalar);
+
+    /// The secret state of the user needed to unblind the response of the signer.
+    pub struct UnblindingState {
+        commitment: RistrettoPoint,
+        blinded_commitment: RistrettoPoint,
+        alpha: RistrettoScalar,
+        challenge: RistrettoScalar,
+    }
+
+    fn challenge(
+        r: &RistrettoPoint,
+        public_key: &RistrettoPoint,
+        message: &[u8],
+    ) -> RistrettoScalar {
+        let mut input = DST.to_vec();
+        input.extend_from_slice(&r.to_byte_array());
+        input.extend_from_slice(&public_key.to_byte_array());
+        input.extend_from_slice(message);
+        RistrettoScalar::hash_to_group_element(&input)
+    }
+
+    impl PrivateKey {
+        /// Generate a new private key.
+        pub fn generate<R: AllowedRng>(rng: &mut R) -> Self {
+            PrivateKey(RistrettoScalar::rand(rng))
+        }
+    }
+
+    impl From<&PrivateKey> for PublicKey {
+        fn from(private_key: &PrivateKey) -> Self {
+            PublicKey(RistrettoPoint::mul_generator(&private_key.0))
+        }
+    }
+
+    impl SigningNonce {
+        /// Start a signing session. Returns the secret nonce of the signer and the commitment which
+        /// should be sent to the user.
+        pub fn new<R: AllowedRng>(rng: &mut R) -> (Self, Commitment) {
+            let k = RistrettoScalar::rand(rng);
+            (Self { k }, Commitment(RistrettoPoint::mul_generator(&k)))
+        }
+
+        /// Respond to a blinded challenge from the user. The nonce is consumed since reusing it for
+        /// another challenge reveals the private key.
+        pub fn sign(self, private_key: &PrivateKey, challenge: &BlindedChallenge) -> BlindResponse {
+            BlindResponse(self.k + challenge.0 * private_key.0)
+        }
+    }
+
+    impl UnblindingState {
+        /// Blind the commitment of the signer and a message. Returns the secret state of the user
+        /// and the blinded challenge which should be sent to the signer.
+        pub fn new<R: AllowedRng>(
+            public_key: &PublicKey,
+            commitment: &Commitment,
+            message: &[u8],
+            rng: &mut R,
+        ) -> (Self, BlindedChallenge) {
+            let alpha = RistrettoScalar::rand(rng);
+            let beta = RistrettoScalar::rand(rng);
+            let blinded_commitment =
+                commitment.0 + RistrettoPoint::mul_generator(&alpha) + public_key.0 * beta;
+            let c = challenge(&blinded_commitment, &public_key.0, message);
+            (
+                Self {
+                    commitment: commitment.0,
+                    blinded_commitment,
+                    alpha,
+                    challenge: c + beta,
+                },
+                BlindedChallenge(c + beta),
+            )
+        }
+
+        /// Verify the response of the signer and unblind it into a signature on the message.
+        pub fn unblind(
+            self,
+            public_key: &PublicKey,
+            response: &BlindResponse,
+        ) -> Result<Signature, FastCryptoError> {
+            if RistrettoPoint::mul_generator(&response.0)
+                != self.commitment + public_key.0 * self.challenge
+            {
+                return Err(FastCryptoError::InvalidSignature);
+            }
+            Ok(Signature {
+                r: self.blinded_commitment,
+                s: response.0 + self.alpha,
+            })
+        }
+    }
+
+    impl Signature {
+        /// Verify this signature on a message.
+        pub fn verify(
+            &self,
+            public_key: &PublicKey,
+            message: &[u8],
+        ) -> Result<(), FastCryptoError> {
+            let c = challenge(&self.r, &public_key.0, message);
+            if RistrettoPoint::mul_generator(&self.s) != self.r + public_key.0 * c {
+                return Err(FastCryptoError::InvalidSignature);
+            }
+            Ok(())
+        }
+    }
+}
@@ -13,10 +13,12 @@
 //! assert!(kp.public().verify(message, &signature).is_ok());
 //! ```
 
+#[cfg(any(test, feature = "experimental"))]
+use crate::hash::{HashFunction, Sha256};
 use crate::serde_helpers::BytesRepresentation;
 use crate::traits::{
-    AggregateAuthenticator, AllowedRng, Authenticator, EncodeDecodeBase64, InsecureDefault,
-    KeyPair, Signer, SigningKey, ToFromBytes, VerifyingKey,
+    AggregateAuthenticator, AllowedRng, Authenticator, BatchSigner, EncodeDecodeBase64,
+    InsecureDefault, KeyPair, Signer, SigningKey, ToFromBytes, VerifyingKey,
 };
 use crate::{
     encoding::Base64, encoding::Encoding, error::FastCryptoError,
@@ -273,22 +275,53 @@ fn get_random_scalar<Rng: AllowedRng>(rng: &mut Rng) -> blst_scalar {
         64 <= BLS_BATCH_RANDOM_SCALAR_LENGTH && BLS_BATCH_RANDOM_SCALAR_LENGTH <= 128
     );
 
-    let mut vals = [0u64; 4];
     loop {
-        vals[0] = rng.next_u64();
-        vals[1] = rng.next_u64();
-
-        // Reject zero as it is used for multiplication.
-        let vals1_lsb = vals[1] & ((1 << (BLS_BATCH_RANDOM_SCALAR_LENGTH - 64)) - 1);
-        if vals[0] | vals1_lsb != 0 {
-            break;
+        if let Some(scalar) = scalar_from_u64s(rng.next_u64(), rng.next_u64()) {
+            return scalar;
         }
     }
+}
+
+/// Create a scalar from the lowest BLS_BATCH_RANDOM_SCALAR_LENGTH bits of the given limbs, or None
+/// if these bits are all zero.
+fn scalar_from_u64s(low: u64, high: u64) -> Option<blst_scalar> {
+    // Reject zero as it is used for multiplication.
+    let high_lsb = high & ((1 << (BLS_BATCH_RANDOM_SCALAR_LENGTH - 64)) - 1);
+    if low | high_lsb == 0 {
+        return None;
+    }
+    let vals = [low, high, 0, 0];
     let mut rand_i = MaybeUninit::<blst_scalar>::uninit();
     unsafe {
         blst_scalar_from_uint64(rand_i.as_mut_ptr(), vals.as_ptr());
-        return rand_i.assume_init();
+        Some(rand_i.assume_init())
+    }
+}
+
+/// Derive the coefficients of a batch verification from a seed, such that the verification can be
+/// repeated by another party. The first coefficient is one as in [get_random_scalars].
+#[cfg(any(test, feature = "experimental"))]
+fn derive_random_scalars(seed: &[u8; 32], n: usize) -> Vec<blst_scalar> {
+    let mut rands: Vec<blst_scalar> = Vec::with_capacity(n);
+    rands.push(get_one());
+    for i in 1..n {
+        let mut counter = 0u64;
+        loop {
+            let mut hash = Sha256::new();
+            hash.update(seed);
+            hash.update((i as u64).to_le_bytes());
+            hash.update(counter.to_le_bytes());
+            let digest = hash.finalize().digest;
+            let low = u64::from_le_bytes(digest[0..8].try_into().expect("Length is 8"));
+            let high = u64::from_le_bytes(digest[8..16].try_into().expect("Length is 8"));
+            if let Some(scalar) = scalar_from_u64s(low, high) {
+                rands.push(scalar);
+                break;
+            }
+            counter += 1;
+        }
     }
+    rands
 }
 
 fn get_one() -> blst_scalar {
@@ -379,6 +412,42 @@ impl Signer<BLS12381Signature> for BLS12381PrivateKey {
     }
 }
 
+#[cfg(any(test, feature = "experimental"))]
+impl BLS12381PrivateKey {
+    /// Derive a master secret key from a seed of at least 32 bytes as specified in
+    /// [EIP-2333](https://eips.ethereum.org/EIPS/eip-2333).
+    pub fn derive_master_eip2333(seed: &[u8]) -> Result<Self, FastCryptoError> {
+        if seed.len() < EIP2333_MIN_SEED_LENGTH {
+            return Err(FastCryptoError::InputTooShort(EIP2333_MIN_SEED_LENGTH));
+        }
+        let privkey =
+            blst::SecretKey::derive_master_eip2333(seed).map_err(|_| FastCryptoError::InvalidInput)?;
+        Ok(BLS12381PrivateKey {
+            privkey,
+            bytes: OnceCell::new(),
+        })
+    }
+
+    /// Derive the child of this secret key with the given index as specified in
+    /// [EIP-2333](https://eips.ethereum.org/EIPS/eip-2333).
+    pub fn derive_child_eip2333(&self, index: u32) -> Self {
+        BLS12381PrivateKey {
+            privkey: self.privkey.derive_child_eip2333(index),
+            bytes: OnceCell::new(),
+        }
+    }
+
+    /// Derive a secret key from a seed and a path of the form `m/12381/3600/0/0/0` as specified in
+    /// [EIP-2334](https://eips.ethereum.org/EIPS/eip-2334).
+    pub fn derive_eip2333_path(seed: &[u8], path: &str) -> Result<Self, FastCryptoError> {
+        parse_eip2334_path(path)?
+            .into_iter()
+            .try_fold(Self::derive_master_eip2333(seed)?, |key, index| {
+                Ok(key.derive_child_eip2333(index))
+            })
+    }
+}
+
 //
 // Boilerplate code for [BLS12381Signature].
 //
@@ -521,6 +590,8 @@ impl Signer<BLS12381Signature> for BLS12381KeyPair {
     }
 }
 
+impl BatchSigner<BLS12381Signature> for BLS12381KeyPair {}
+
 impl FromStr for BLS12381KeyPair {
     type Err = eyre::Report;
 
@@ -631,15 +702,25 @@ impl AggregateAuthenticator for BLS12381AggregateSignature {
         pks: &[<Self::Sig as Authenticator>::PubKey],
         message: &[u8],
     ) -> Result<(), FastCryptoError> {
+        // Aggregate the keys one by one instead of using fast_aggregate_verify to avoid collecting
+        // them. This is equivalent since fast_aggregate_verify does not validate the keys either.
+        let (first, rest) = pks.split_first().ok_or(FastCryptoError::InvalidSignature)?;
+        let mut agg_pk = blst::AggregatePublicKey::from_public_key(&first.pubkey);
+        for pk in rest {
+            agg_pk
+                .add_public_key(&pk.pubkey, false)
+                .map_err(|_| FastCryptoError::InvalidSignature)?;
+        }
+
         // Validate signatures but not public keys which the user must validate before calling this.
-        let result = self
-            .sig
-            .fast_aggregate_verify(
-                true,
-                message,
-                $dst_string,
-                &pks.iter().map(|x| &x.pubkey).collect::<Vec<_>>()[..],
-            );
+        let result = self.sig.verify(
+            true,
+            message,
+            $dst_string,
+            &[],
+            &agg_pk.to_public_key(),
+            false,
+        );
         if result != BLST_ERROR::BLST_SUCCESS {
             return Err(FastCryptoError::InvalidSignature);
         }
@@ -667,11 +748,17 @@ impl AggregateAuthenticator for BLS12381AggregateSignature {
         Ok(())
     }
 
-    fn batch_verify<'a>(
+    fn batch_verify<'a, P, I>(
         signatures: &[&Self],
-        pks: Vec<impl Iterator<Item = &'a Self::PubKey>>,
+        pks: P,
         messages: &[&[u8]],
-    ) -> Result<(), FastCryptoError> {
+    ) -> Result<(), FastCryptoError>
+    where
+        P: IntoIterator<Item = I>,
+        P::IntoIter: ExactSizeIterator,
+        I: ExactSizeIterator<Item = &'a Self::PubKey>,
+    {
+        let pks = pks.into_iter();
         if signatures.len() != pks.len() || signatures.len() != messages.len() {
             return Err(FastCryptoError::InputLengthWrong(signatures.len()));
         }
@@ -682,11 +769,19 @@ impl AggregateAuthenticator for BLS12381AggregateSignature {
         }
 
         let mut agg_pks: Vec<blst::PublicKey> = Vec::with_capacity(signatures.len());
-        for keys in pks {
-             let keys_as_vec = keys.map(|x| x.pubkey.borrow()).collect::<Vec<_>>();
-             agg_pks.push(blst::AggregatePublicKey::aggregate(&keys_as_vec, false).unwrap().to_public_key()
-             );
-         }
+        for mut keys in pks {
+            // Aggregate the keys one by one to avoid collecting them.
+            let mut agg_pk = match keys.next() {
+                Some(pk) => blst::AggregatePublicKey::from_public_key(&pk.pubkey),
+                None => return Err(FastCryptoError::InvalidInput),
+            };
+            for pk in keys {
+                agg_pk
+                    .add_public_key(&pk.pubkey, false)
+                    .map_err(|_| FastCryptoError::InvalidInput)?;
+            }
+            agg_pks.push(agg_pk.to_public_key());
+        }
 
         // Validate signatures but not public keys which the user must validate before calling this.
         let result = blst::Signature::verify_multiple_aggregate_signatures(
@@ -707,6 +802,100 @@ impl AggregateAuthenticator for BLS12381AggregateSignature {
     }
 }
 
+#[cfg(any(test, feature = "experimental"))]
+impl BLS12381AggregateSignature {
+    /// Verify a batch of aggregate signatures like [AggregateAuthenticator::batch_verify], but
+    /// derive the random coefficients from a random seed and return a [VerificationTranscript]
+    /// with the seed, a digest of the inputs and the result. Another party can re-verify the
+    /// decision using [Self::check_transcript]. A single aggregate signature is a batch of size one.
+    ///
+    /// Returns an error if the inputs have different lengths or if a public key list is empty.
+    pub fn batch_verify_with_transcript<R: AllowedRng>(
+        signatures: &[&Self],
+        pks: &[&[BLS12381PublicKey]],
+        messages: &[&[u8]],
+        rng: &mut R,
+    ) -> Result<VerificationTranscript, FastCryptoError> {
+        let mut seed = [0u8; 32];
+        rng.fill_bytes(&mut seed);
+        Self::batch_verify_with_seed(signatures, pks, messages, seed)
+    }
+
+    /// Check that the transcript was created by [Self::batch_verify_with_transcript] for the given
+    /// inputs, and that verifying them with the same coefficients gives the same result.
+    pub fn check_transcript(
+        transcript: &VerificationTranscript,
+        signatures: &[&Self],
+        pks: &[&[BLS12381PublicKey]],
+        messages: &[&[u8]],
+    ) -> Result<(), FastCryptoError> {
+        let recomputed =
+            Self::batch_verify_with_seed(signatures, pks, messages, transcript.randomizer_seed)?;
+        if recomputed.inputs_digest != transcript.inputs_digest {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        if recomputed.valid != transcript.valid {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        Ok(())
+    }
+
+    fn batch_verify_with_seed(
+        signatures: &[&Self],
+        pks: &[&[BLS12381PublicKey]],
+        messages: &[&[u8]],
+        seed: [u8; 32],
+    ) -> Result<VerificationTranscript, FastCryptoError> {
+        if signatures.len() != pks.len() || signatures.len() != messages.len() {
+            return Err(FastCryptoError::InputLengthWrong(signatures.len()));
+        }
+        if signatures.is_empty() || pks.iter().any(|keys| keys.is_empty()) {
+            return Err(FastCryptoError::InvalidInput);
+        }
+
+        // All lengths are included so the encoding of the inputs is unambiguous.
+        let mut hash = Sha256::new();
+        hash.update($dst_string);
+        hash.update((signatures.len() as u64).to_le_bytes());
+        for ((signature, keys), message) in signatures.iter().zip(pks).zip(messages) {
+            hash.update(signature.as_ref());
+            hash.update((keys.len() as u64).to_le_bytes());
+            keys.iter().for_each(|pk| hash.update(pk.as_ref()));
+            hash.update((message.len() as u64).to_le_bytes());
+            hash.update(message);
+        }
+        let inputs_digest = hash.finalize().digest;
+
+        let mut agg_pks: Vec<blst::PublicKey> = Vec::with_capacity(signatures.len());
+        for keys in pks {
+            let mut agg_pk = blst::AggregatePublicKey::from_public_key(&keys[0].pubkey);
+            for pk in &keys[1..] {
+                agg_pk
+                    .add_public_key(&pk.pubkey, false)
+                    .map_err(|_| FastCryptoError::InvalidInput)?;
+            }
+            agg_pks.push(agg_pk.to_public_key());
+        }
+
+        // Validate signatures but not public keys which the user must validate before calling this.
+        let result = blst::Signature::verify_multiple_aggregate_signatures(
+            messages,
+            $dst_string,
+            &agg_pks.iter().collect::<Vec<_>>(),
+            false,
+            &signatures.iter().map(|agg_sig| &agg_sig.sig).collect::<Vec<_>>(),
+            true,
+            &derive_random_scalars(&seed, signatures.len()),
+            BLS_BATCH_RANDOM_SCALAR_LENGTH,
+        );
+        Ok(VerificationTranscript {
+            inputs_digest,
+            randomizer_seed: seed,
+            valid: result == BLST_ERROR::BLST_SUCCESS,
+        })
+    }
+}
+
 };
 
 } // macro_rules! define_bls12381.
@@ -714,6 +903,40 @@ impl AggregateAuthenticator for BLS12381AggregateSignature {
 /// The length of a private key in bytes.
 pub const BLS_PRIVATE_KEY_LENGTH: usize = 32;
 
+/// A compact record of a batch verification which allows another party to repeat it and check the
+/// decision, e.g. for dispute resolution. It is created by `batch_verify_with_transcript` of
+/// [min_sig::BLS12381AggregateSignature] or [min_pk::BLS12381AggregateSignature].
+#[cfg(any(test, feature = "experimental"))]
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct VerificationTranscript {
+    /// SHA-256 digest of the scheme, the signatures, the public keys and the messages.
+    pub inputs_digest: [u8; 32],
+    /// The seed from which the random coefficients of the batch verification are derived.
+    pub randomizer_seed: [u8; 32],
+    /// Whether the batch was valid.
+    pub valid: bool,
+}
+
+/// The minimum length of a seed used for EIP-2333 key derivation.
+#[cfg(any(test, feature = "experimental"))]
+const EIP2333_MIN_SEED_LENGTH: usize = 32;
+
+/// Parse a path of the form `m/12381/3600/0/0/0` into its indices.
+#[cfg(any(test, feature = "experimental"))]
+fn parse_eip2334_path(path: &str) -> Result<Vec<u32>, FastCryptoError> {
+    let mut parts = path.split('/');
+    if parts.next() != Some("m") {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    parts
+        .map(|index| {
+            index
+                .parse::<u32>()
+                .map_err(|_| FastCryptoError::InvalidInput)
+        })
+        .collect()
+}
+
 /// The length of public keys when using the [min_pk] module and the length of signatures when using the [min_sig] module.
 pub const BLS_G1_LENGTH: usize = 48;
 
@@ -15,6 +15,18 @@
 //!    BulletproofsRangeProof::prove_bit_length(value, blinding, upper_bound, b"MY_DOMAIN").unwrap();
 //! assert!(range_proof.verify_bit_length(&commitment, upper_bound, b"MY_DOMAIN").is_ok());
 //! ```
+//!
+//! Range proofs for several values can be aggregated into a single proof, whose size only grows
+//! logarithmically in the number of values:
+//! ```rust
+//! # use fastcrypto::bulletproofs::*;
+//! let values = [1, 2, 3, 300];
+//! let blindings = [[1u8; 32], [2u8; 32], [3u8; 32], [4u8; 32]];
+//! let (commitments, range_proof) = BulletproofsRangeProof::prove_bit_length_aggregated(
+//!     &values, &blindings, 16, b"MY_DOMAIN").unwrap();
+//! assert_eq!(commitments.len(), 4);
+//! assert!(range_proof.verify_bit_length_aggregated(&commitments, 16, b"MY_DOMAIN").is_ok());
+//! ```
 use std::ops;
 
 use bulletproofs::{BulletproofGens, PedersenGens, RangeProof};
@@ -25,7 +37,9 @@ use curve25519_dalek_ng::{
 use merlin::Transcript;
 use once_cell::sync::OnceCell;
 use serde::{de, Deserialize, Serialize};
+use zeroize::Zeroizing;
 
+use crate::limits::{enforce, Limit};
 use crate::{error::FastCryptoError, traits::ToFromBytes};
 
 //
@@ -165,23 +179,19 @@ impl BulletproofsRangeProof {
         bits: usize,
         domain: &'static [u8],
     ) -> Result<(PedersenCommitment, Self), FastCryptoError> {
-        // Although this is also checked in the bulletproofs library, we check again
-        // to avoid unexpected behaviour in the case of library updates
-        if !(bits == 8 || bits == 16 || bits == 32 || bits == 64) {
-            return Err(FastCryptoError::InvalidInput);
-        }
+        check_bit_length(bits)?;
 
         let pc_gens = PedersenGens::default();
         let bp_gens = BulletproofGens::new(bits, 1);
         let mut prover_transcript = Transcript::new(domain);
-        let blinding = Scalar::from_bits(blinding);
+        let blinding = Zeroizing::new(Scalar::from_bits(blinding));
 
         let (proof, commitment) = RangeProof::prove_single(
             &bp_gens,
             &pc_gens,
             &mut prover_transcript,
             value,
-            &blinding,
+            &*blinding,
             bits,
         )
         .map_err(|_| signature::Error::new())?;
@@ -208,11 +218,7 @@ impl BulletproofsRangeProof {
         bits: usize,
         domain: &'static [u8],
     ) -> Result<(), FastCryptoError> {
-        // Although this is also checked in the bulletproofs library, we check again
-        // to avoid unexpected behaviour in the case of library updates
-        if !(bits == 8 || bits == 16 || bits == 32 || bits == 64) {
-            return Err(FastCryptoError::InvalidInput);
-        }
+        check_bit_length(bits)?;
 
         let pc_gens = PedersenGens::default();
         let bp_gens = BulletproofGens::new(bits, 1);
@@ -228,6 +234,110 @@ impl BulletproofsRangeProof {
             )
             .map_err(|_| FastCryptoError::GeneralError("Failed to verify proof".to_string()))
     }
+
+    /// Prove with a single proof that each of the values is an unsigned integer with bit length
+    /// `bits`, where `blindings[i]` is the blinding factor of the commitment to `values[i]`. The
+    /// number of values must be a power of two and, like for
+    /// [BulletproofsRangeProof::prove_bit_length], `bits` must be 8, 16, 32 or 64.
+    pub fn prove_bit_length_aggregated(
+        values: &[u64],
+        blindings: &[[u8; 32]],
+        bits: usize,
+        domain: &'static [u8],
+    ) -> Result<(Vec<PedersenCommitment>, Self), FastCryptoError> {
+        check_bit_length(bits)?;
+        check_aggregation_size(values.len())?;
+        if blindings.len() != values.len() {
+            return Err(FastCryptoError::InputLengthWrong(values.len()));
+        }
+
+        let pc_gens = PedersenGens::default();
+        let bp_gens = BulletproofGens::new(bits, values.len());
+        let mut prover_transcript = Transcript::new(domain);
+        let blindings = Zeroizing::new(
+            blindings
+                .iter()
+                .map(|blinding| Scalar::from_bits(*blinding))
+                .collect::<Vec<_>>(),
+        );
+
+        let (proof, commitments) = RangeProof::prove_multiple(
+            &bp_gens,
+            &pc_gens,
+            &mut prover_transcript,
+            values,
+            blindings.as_slice(),
+            bits,
+        )
+        .map_err(|_| signature::Error::new())?;
+
+        let commitments = commitments
+            .iter()
+            .map(|commitment| {
+                Ok(PedersenCommitment {
+                    point: commitment.decompress().ok_or_else(signature::Error::new)?,
+                    bytes: OnceCell::new(),
+                })
+            })
+            .collect::<Result<Vec<_>, FastCryptoError>>()?;
+        Ok((
+            commitments,
+            BulletproofsRangeProof {
+                proof,
+                bytes: OnceCell::new(),
+            },
+        ))
+    }
+
+    /// Verifies that each of the commitments is a Pedersen commitment of some value with an
+    /// unsigned bit length `bits`, given in the order they were returned by
+    /// [BulletproofsRangeProof::prove_bit_length_aggregated]. Fails if the number of commitments is
+    /// not a power of two or exceeds [Limit::AggregateParticipants].
+    pub fn verify_bit_length_aggregated(
+        &self,
+        commitments: &[PedersenCommitment],
+        bits: usize,
+        domain: &'static [u8],
+    ) -> Result<(), FastCryptoError> {
+        check_bit_length(bits)?;
+        check_aggregation_size(commitments.len())?;
+        enforce(Limit::AggregateParticipants, commitments.len())?;
+
+        let pc_gens = PedersenGens::default();
+        let bp_gens = BulletproofGens::new(bits, commitments.len());
+        let mut verifier_transcript = Transcript::new(domain);
+        let commitments = commitments
+            .iter()
+            .map(|commitment| CompressedRistretto::from_slice(commitment.as_bytes()))
+            .collect::<Vec<_>>();
+
+        self.proof
+            .verify_multiple(
+                &bp_gens,
+                &pc_gens,
+                &mut verifier_transcript,
+                &commitments,
+                bits,
+            )
+            .map_err(|_| FastCryptoError::GeneralError("Failed to verify proof".to_string()))
+    }
+}
+
+/// Although this is also checked in the bulletproofs library, we check again to avoid unexpected
+/// behaviour in the case of library updates.
+fn check_bit_length(bits: usize) -> Result<(), FastCryptoError> {
+    if !(bits == 8 || bits == 16 || bits == 32 || bits == 64) {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    Ok(())
+}
+
+/// Aggregated proofs are only supported for a non-zero power of two number of values.
+fn check_aggregation_size(size: usize) -> Result<(), FastCryptoError> {
+    if !size.is_power_of_two() {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    Ok(())
 }
 
 impl AsRef<[u8]> for BulletproofsRangeProof {
@@ -245,3 +355,22 @@ impl ToFromBytes for BulletproofsRangeProof {
         })
     }
 }
+
+impl Serialize for BulletproofsRangeProof {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_bytes(self.as_ref())
+    }
+}
+
+impl<'de> Deserialize<'de> for BulletproofsRangeProof {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let bytes = Vec::deserialize(deserializer)?;
+        BulletproofsRangeProof::from_bytes(&bytes[..]).map_err(|e| de::Error::custom(e.to_string()))
+    }
+}
@@ -0,0 +1,273 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A compact binary encoding of quorum certificates, i.e. a constant-size aggregate signature (e.g.
+//! BLS) together with the set of committee members who signed.
+//!
+//! A certificate is encoded as the aggregate signature followed by the signers in one of two
+//! containers, chosen like in a roaring bitmap:
+//! - An array: a tag `0`, the number of signers and the differences between consecutive indices
+//!   (minus one), all as LEB128 varints. This is small if few members signed.
+//! - A bitmap: a tag `1` and a bit per committee member, least significant bit first. This is
+//!   small if most members signed.
+//!
+//! The shorter container is used, preferring the array if both have the same length. Parsing is
+//! strict, so every certificate has exactly one encoding: the other container, non-minimal varints,
+//! indices outside the committee, unused bits which are set and trailing bytes are all rejected.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::certificate_codec::*;
+//! # use fastcrypto::bls12381::min_sig::{BLS12381AggregateSignature, BLS12381KeyPair};
+//! # use fastcrypto::traits::{KeyPair, Signer};
+//! # use rand::thread_rng;
+//! let committee = (0..10).map(|_| BLS12381KeyPair::generate(&mut thread_rng())).collect::<Vec<_>>();
+//! let public_keys = committee.iter().map(|kp| kp.public().clone()).collect::<Vec<_>>();
+//! let votes = [1, 4, 5, 8].iter().map(|i| (*i, committee[*i].sign(b"block"))).collect::<Vec<_>>();
+//!
+//! let certificate = Certificate::<BLS12381AggregateSignature>::from_signatures(10, &votes).unwrap();
+//! let bytes = certificate.to_bytes();
+//! let parsed = Certificate::<BLS12381AggregateSignature>::from_bytes(&bytes, 10).unwrap();
+//! assert!(parsed.verify(&public_keys, b"block").is_ok());
+//! ```
+
+use crate::error::FastCryptoError;
+use crate::traits::{AggregateAuthenticator, Authenticator, ToFromBytes};
+
+const ARRAY_TAG: u8 = 0;
+const BITMAP_TAG: u8 = 1;
+
+/// The indices of the committee members who signed a certificate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignerSet {
+    committee_size: usize,
+    /// Sorted and without duplicates.
+    signers: Vec<usize>,
+}
+
+impl SignerSet {
+    /// Create a set from signer indices in any order. Fails if an index is not smaller than
+    /// `committee_size` or appears twice.
+    pub fn new(committee_size: usize, signers: &[usize]) -> Result<Self, FastCryptoError> {
+        let mut signers = signers.to_vec();
+        signers.sort_unstable();
+        if signers.windows(2).any(|w| w[0] == w[1])
+            || signers.last().map_or(false, |i| *i >= committee_size)
+        {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(Self {
+            committee_size,
+            signers,
+        })
+    }
+
+    pub fn committee_size(&self) -> usize {
+        self.committee_size
+    }
+
+    /// The signer indices in increasing order.
+    pub fn signers(&self) -> &[usize] {
+        &self.signers
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        self.signers.binary_search(&index).is_ok()
+    }
+
+    pub fn len(&self) -> usize {
+        self.signers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.signers.is_empty()
+    }
+
+    fn encode_array(&self) -> Vec<u8> {
+        let mut bytes = vec![ARRAY_TAG];
+        write_varint(self.signers.len() as u64, &mut bytes);
+        let mut next = 0;
+        for index in &self.signers {
+            write_varint((index - next) as u64, &mut bytes);
+            next = index + 1;
+        }
+        bytes
+    }
+
+    fn encode_bitmap(&self) -> Vec<u8> {
+        let mut bytes = vec![0u8; 1 + bitmap_length(self.committee_size)];
+        bytes[0] = BITMAP_TAG;
+        for index in &self.signers {
+            bytes[1 + index / 8] |= 1 << (index % 8);
+        }
+        bytes
+    }
+
+    /// The encoding of this set, using the shorter container.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let array = self.encode_array();
+        // The bitmap length only depends on the committee size.
+        match array.len() <= 1 + bitmap_length(self.committee_size) {
+            true => array,
+            false => self.encode_bitmap(),
+        }
+    }
+
+    /// Parse a set encoded with [SignerSet::to_bytes] for a committee of the given size.
+    pub fn from_bytes(bytes: &[u8], committee_size: usize) -> Result<Self, FastCryptoError> {
+        let (tag, mut rest) = bytes.split_first().ok_or(FastCryptoError::InvalidInput)?;
+        let mut signers = Vec::new();
+        match *tag {
+            ARRAY_TAG => {
+                let count = read_varint(&mut rest)?;
+                if count > committee_size as u64 {
+                    return Err(FastCryptoError::InvalidInput);
+                }
+                let mut next = 0u64;
+                for _ in 0..count {
+                    let index = next
+                        .checked_add(read_varint(&mut rest)?)
+                        .filter(|i| *i < committee_size as u64)
+                        .ok_or(FastCryptoError::InvalidInput)?;
+                    signers.push(index as usize);
+                    next = index + 1;
+                }
+            }
+            BITMAP_TAG => {
+                if rest.len() != bitmap_length(committee_size) {
+                    return Err(FastCryptoError::InputLengthWrong(
+                        1 + bitmap_length(committee_size),
+                    ));
+                }
+                for (i, byte) in rest.iter().enumerate() {
+                    for bit in 0..8 {
+                        if byte & (1 << bit) == 0 {
+                            continue;
+                        }
+                        let index = 8 * i + bit;
+                        if index >= committee_size {
+                            return Err(FastCryptoError::InvalidInput);
+                        }
+                        signers.push(index);
+                    }
+                }
+                rest = &[];
+            }
+            _ => return Err(FastCryptoError::InvalidInput),
+        }
+        if !rest.is_empty() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+
+        let set = Self {
+            committee_size,
+            signers,
+        };
+        // Only the canonical container is accepted.
+        if set.to_bytes() != bytes {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(set)
+    }
+}
+
+/// An aggregate signature by a set of committee members.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Certificate<A: AggregateAuthenticator> {
+    pub signature: A,
+    pub signers: SignerSet,
+}
+
+impl<A: AggregateAuthenticator + ToFromBytes> Certificate<A> {
+    /// Create a certificate. Fails if the encoding of the signature does not have the length of a
+    /// single signature, e.g. for schemes whose aggregates are not constant-size.
+    pub fn new(signature: A, signers: SignerSet) -> Result<Self, FastCryptoError> {
+        if signature.as_ref().len() != <A::Sig as Authenticator>::LENGTH {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(Self { signature, signers })
+    }
+
+    /// Aggregate the signatures of the given committee members.
+    pub fn from_signatures(
+        committee_size: usize,
+        signatures: &[(usize, A::Sig)],
+    ) -> Result<Self, FastCryptoError> {
+        let indices = signatures.iter().map(|(i, _)| *i).collect::<Vec<_>>();
+        let signers = SignerSet::new(committee_size, &indices)?;
+        let signature = A::aggregate(signatures.iter().map(|(_, s)| s))?;
+        Self::new(signature, signers)
+    }
+
+    /// Verify the certificate on `message` against the public keys of the whole committee.
+    pub fn verify(
+        &self,
+        committee: &[<A::Sig as Authenticator>::PubKey],
+        message: &[u8],
+    ) -> Result<(), FastCryptoError> {
+        if committee.len() != self.signers.committee_size {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let public_keys = self
+            .signers
+            .signers
+            .iter()
+            .map(|i| committee[*i].clone())
+            .collect::<Vec<_>>();
+        self.signature.verify(&public_keys, message)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.signature.as_ref().to_vec();
+        bytes.extend(self.signers.to_bytes());
+        bytes
+    }
+
+    /// Parse a certificate encoded with [Certificate::to_bytes] for a committee of the given size.
+    pub fn from_bytes(bytes: &[u8], committee_size: usize) -> Result<Self, FastCryptoError> {
+        let length = <A::Sig as Authenticator>::LENGTH;
+        if bytes.len() <= length {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let (signature, signers) = bytes.split_at(length);
+        Self::new(
+            A::from_bytes(signature)?,
+            SignerSet::from_bytes(signers, committee_size)?,
+        )
+    }
+}
+
+fn bitmap_length(committee_size: usize) -> usize {
+    committee_size / 8 + usize::from(committee_size % 8 != 0)
+}
+
+fn write_varint(mut value: u64, bytes: &mut Vec<u8>) {
+    while value >= 0x80 {
+        bytes.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+/// Read a minimally encoded LEB128 varint.
+fn read_varint(bytes: &mut &[u8]) -> Result<u64, FastCryptoError> {
+    let mut value = 0u64;
+    for i in 0..10 {
+        let (byte, rest) = bytes.split_first().ok_or(FastCryptoError::InvalidInput)?;
+        *bytes = rest;
+        let bits = (*byte & 0x7f) as u64;
+        // The tenth byte may only hold the highest bit of a u64.
+        if i == 9 && bits > 1 {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        value |= bits << (7 * i);
+        if byte & 0x80 == 0 {
+            // A trailing zero byte would be a non-minimal encoding.
+            if i > 0 && *byte == 0 {
+                return Err(FastCryptoError::InvalidInput);
+            }
+            return Ok(value);
+        }
+    }
+    Err(FastCryptoError::InvalidInput)
+}
@@ -0,0 +1,288 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Linearly homomorphic encryption over class groups following the CL framework of
+//! [Castagnos and Laguillaumie](https://eprint.iacr.org/2015/047.pdf), in the variant based on
+//! the hard subgroup membership assumption of [CCL+19](https://eprint.iacr.org/2019/503.pdf).
+//!
+//! The plaintext space is `Z/qZ` for a prime `q` chosen by the user, typically the order of the
+//! group used by a threshold ECDSA protocol, so the MtA share conversion requires no range proofs
+//! and no RSA modulus with a trusted setup:
+//! * The fundamental discriminant is `D_K = -q * q'` for a random prime `q'`, and the class group
+//!   of the non-maximal order of discriminant `D_q = q^2 * D_K` contains a subgroup `F` of order
+//!   `q` generated by `f = (q^2, q, *)` in which discrete logarithms are easy.
+//! * `g_q` is a random `q`-th power in the class group, and a key pair is `(x, h = g_q^x)`.
+//! * A message `m` is encrypted as `(g_q^r, f^m * h^r)` for a random `r`.
+//!
+//! All parameters can be generated publicly from the random prime `q'`. For 128-bit security the
+//! fundamental discriminant should have at least 1827 bits.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::cl_encryption::*;
+//! # use num_bigint::BigUint;
+//! # use rand::thread_rng;
+//! let q = BigUint::from(1_000_000_007u64);
+//! let parameters = ClParameters::generate(&q, 128, &mut thread_rng()).unwrap();
+//! let sk = ClPrivateKey::generate(&parameters, &mut thread_rng());
+//! let pk = sk.public_key();
+//! let (c1, _) = pk.encrypt(&BigUint::from(3u32), &mut thread_rng()).unwrap();
+//! let (c2, _) = pk.encrypt(&BigUint::from(4u32), &mut thread_rng()).unwrap();
+//! let sum = pk.add(&c1, &pk.mul(&c2, &BigUint::from(5u32)));
+//! assert_eq!(sk.decrypt(&sum).unwrap(), BigUint::from(23u32));
+//! ```
+
+use crate::class_group::QuadraticForm;
+use crate::error::FastCryptoError;
+use crate::number_theory::{
+    generate_prime, is_probable_prime, mod_inverse, random_below, STATISTICAL_SECURITY,
+};
+use crate::traits::AllowedRng;
+use num_bigint::{BigInt, BigUint, Sign};
+use serde::{Deserialize, Serialize};
+
+/// Public parameters of the encryption scheme. They may be shared by all users.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClParameters {
+    q: BigUint,
+    discriminant: BigInt,
+    generator: QuadraticForm,
+    exponent_bound: BigUint,
+}
+
+/// A public key `h = g_q^x`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClPublicKey {
+    parameters: ClParameters,
+    h: QuadraticForm,
+}
+
+/// A private key.
+#[derive(Clone, Debug)]
+pub struct ClPrivateKey {
+    public_key: ClPublicKey,
+    x: BigUint,
+}
+
+/// A ciphertext `(g_q^r, f^m * h^r)`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClCiphertext {
+    c1: QuadraticForm,
+    c2: QuadraticForm,
+}
+
+impl ClParameters {
+    /// Generate parameters for the message space `Z/qZ` with a fundamental discriminant of
+    /// `discriminant_bits` bits.
+    pub fn generate<R: AllowedRng>(
+        q: &BigUint,
+        discriminant_bits: u64,
+        rng: &mut R,
+    ) -> Result<Self, FastCryptoError> {
+        // Decryption requires that f^m is reduced, which holds if q' > 4q.
+        if q.bits() < 2
+            || discriminant_bits < 2 * q.bits() + 3
+            || !is_probable_prime(q, rng)
+            || !q.bit(0)
+        {
+            return Err(FastCryptoError::InvalidInput);
+        }
+
+        // Find a prime q' such that q * q' = 3 mod 4 and (q / q') = -1.
+        let q_tilde_bits = discriminant_bits - q.bits();
+        let q_tilde = loop {
+            let candidate = generate_prime(q_tilde_bits, rng);
+            let exponent = (&candidate - 1u8) >> 1;
+            if (q * &candidate) % 4u8 == BigUint::from(3u8)
+                && q.modpow(&exponent, &candidate) == &candidate - 1u8
+            {
+                break candidate;
+            }
+        };
+        let q_int = BigInt::from(q.clone());
+        let fundamental_discriminant = -(&q_int * BigInt::from(q_tilde));
+        let discriminant = &fundamental_discriminant * &q_int * &q_int;
+
+        let generator = Self::sample_generator(q, &fundamental_discriminant)?;
+        let exponent_bound =
+            BigUint::from(1u8) << (fundamental_discriminant.bits() / 2 + STATISTICAL_SECURITY);
+
+        Ok(Self {
+            q: q.clone(),
+            discriminant,
+            generator,
+            exponent_bound,
+        })
+    }
+
+    /// Compute the generator `g_q` of a subgroup of `q`-th powers in the class group of
+    /// discriminant `q^2 * D_K` as `phi^{-1}(r^2)^q`, where `r` is the class of a prime ideal of small
+    /// norm in the class group of discriminant `D_K` and `phi^{-1}` lifts a class to the
+    /// non-maximal order.
+    fn sample_generator(
+        q: &BigUint,
+        fundamental_discriminant: &BigInt,
+    ) -> Result<QuadraticForm, FastCryptoError> {
+        let q_int = BigInt::from(q.clone());
+        let discriminant = fundamental_discriminant * &q_int * &q_int;
+        let identity = QuadraticForm::identity(&discriminant)?;
+
+        for r in (3u32..).step_by(2).filter(|&r| is_small_prime(r)) {
+            let r_int = BigInt::from(r);
+            if r_int == q_int {
+                continue;
+            }
+            // The form (r, b, *) exists if D_K is a non-zero square modulo r.
+            let d = fundamental_discriminant % &r_int;
+            let d = if d < BigInt::from(0u8) { d + &r_int } else { d };
+            if d == BigInt::from(0u8) {
+                continue;
+            }
+            let b = match (1..r).find(|b| BigInt::from(b * b) % &r_int == d) {
+                Some(b) if b % 2 == 1 => b,
+                Some(b) => r - b,
+                None => continue,
+            };
+            let ideal = QuadraticForm::from_a_b(r_int, BigInt::from(b), fundamental_discriminant)?;
+            let squared = ideal.compose(&ideal);
+            let (a, b, c) = squared.coefficients();
+            if a % &q_int == BigInt::from(0u8) {
+                continue;
+            }
+            let lifted = QuadraticForm::new(a.clone(), b * &q_int, c * &q_int * &q_int)?;
+            let generator = lifted.pow(q);
+            if generator != identity {
+                return Ok(generator);
+            }
+        }
+        unreachable!("There are infinitely many primes which split")
+    }
+
+    /// The order `q` of the message space.
+    pub fn message_modulus(&self) -> &BigUint {
+        &self.q
+    }
+
+    /// Compute `f^m` in the subgroup with easy discrete logarithms. For `m != 0` this is
+    /// `(q^2, L(m) * q, *)` where `L(m)` is the odd representative of `m^{-1} mod q` in `[-q, q]`.
+    fn power_of_f(&self, m: &BigUint) -> QuadraticForm {
+        let m = m % &self.q;
+        let q = BigInt::from(self.q.clone());
+        let inverse = match mod_inverse(&m, &self.q) {
+            Some(inverse) => BigInt::from(inverse),
+            None => {
+                return QuadraticForm::identity(&self.discriminant).expect("Valid discriminant")
+            }
+        };
+        let l = if inverse.bit(0) {
+            inverse
+        } else {
+            inverse - &q
+        };
+        QuadraticForm::from_a_b(&q * &q, l * &q, &self.discriminant)
+            .expect("Coefficients are valid")
+    }
+
+    /// Solve the discrete logarithm of an element of the subgroup generated by `f`.
+    fn discrete_log_of_f(&self, form: &QuadraticForm) -> Result<BigUint, FastCryptoError> {
+        if form == &QuadraticForm::identity(&self.discriminant)? {
+            return Ok(BigUint::from(0u8));
+        }
+        let q = BigInt::from(self.q.clone());
+        let (a, b, _) = form.coefficients();
+        if a != &(&q * &q) || b % &q != BigInt::from(0u8) {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let l = (b / &q) % &q;
+        let l = if l.sign() == Sign::Minus { l + &q } else { l };
+        mod_inverse(&l.to_biguint().expect("l is non-negative"), &self.q)
+            .ok_or(FastCryptoError::InvalidInput)
+    }
+
+    fn is_valid(&self, form: &QuadraticForm) -> bool {
+        form.discriminant() == self.discriminant
+    }
+}
+
+impl ClPrivateKey {
+    /// Generate a new key pair.
+    pub fn generate<R: AllowedRng>(parameters: &ClParameters, rng: &mut R) -> Self {
+        let x = random_below(&parameters.exponent_bound, rng);
+        let h = parameters.generator.pow(&x);
+        Self {
+            public_key: ClPublicKey {
+                parameters: parameters.clone(),
+                h,
+            },
+            x,
+        }
+    }
+
+    /// The public key of this private key.
+    pub fn public_key(&self) -> &ClPublicKey {
+        &self.public_key
+    }
+
+    /// Decrypt a ciphertext.
+    pub fn decrypt(&self, ciphertext: &ClCiphertext) -> Result<BigUint, FastCryptoError> {
+        let parameters = &self.public_key.parameters;
+        if !parameters.is_valid(&ciphertext.c1) || !parameters.is_valid(&ciphertext.c2) {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let f_m = ciphertext.c2.compose(&ciphertext.c1.pow(&self.x).inverse());
+        parameters.discrete_log_of_f(&f_m)
+    }
+}
+
+impl ClPublicKey {
+    /// The parameters of this key.
+    pub fn parameters(&self) -> &ClParameters {
+        &self.parameters
+    }
+
+    /// Encrypt a message `m < q` using the randomness `r`.
+    pub fn encrypt_with_randomness(
+        &self,
+        m: &BigUint,
+        r: &BigUint,
+    ) -> Result<ClCiphertext, FastCryptoError> {
+        if m >= &self.parameters.q {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(ClCiphertext {
+            c1: self.parameters.generator.pow(r),
+            c2: self.parameters.power_of_f(m).compose(&self.h.pow(r)),
+        })
+    }
+
+    /// Encrypt a message `m < q`. Returns the ciphertext and the randomness used.
+    pub fn encrypt<R: AllowedRng>(
+        &self,
+        m: &BigUint,
+        rng: &mut R,
+    ) -> Result<(ClCiphertext, BigUint), FastCryptoError> {
+        let r = random_below(&self.parameters.exponent_bound, rng);
+        Ok((self.encrypt_with_randomness(m, &r)?, r))
+    }
+
+    /// Homomorphic addition: the result decrypts to the sum of the plaintexts modulo `q`.
+    pub fn add(&self, a: &ClCiphertext, b: &ClCiphertext) -> ClCiphertext {
+        ClCiphertext {
+            c1: a.c1.compose(&b.c1),
+            c2: a.c2.compose(&b.c2),
+        }
+    }
+
+    /// Homomorphic scalar multiplication: the result decrypts to `k` times the plaintext modulo
+    /// `q`.
+    pub fn mul(&self, c: &ClCiphertext, k: &BigUint) -> ClCiphertext {
+        ClCiphertext {
+            c1: c.c1.pow(k),
+            c2: c.c2.pow(k),
+        }
+    }
+}
+
+fn is_small_prime(n: u32) -> bool {
+    n >= 2 && (2..).take_while(|d| d * d <= n).all(|d| n % d != 0)
+}
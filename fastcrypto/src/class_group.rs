@@ -0,0 +1,167 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Arithmetic in class groups of imaginary quadratic orders, represented by reduced positive
+//! definite binary quadratic forms `ax^2 + bxy + cy^2` of a fixed negative discriminant
+//! `b^2 - 4ac`.
+//!
+//! Class groups are groups of unknown order which can be generated without a trusted setup from a
+//! random discriminant. Composition uses Dirichlet's formulas (Cohen, "A Course in Computational
+//! Algebraic Number Theory", section 5.4) followed by reduction, so every form returned by this
+//! module is reduced and two forms represent the same class if and only if they are equal.
+//!
+//! The arithmetic is not constant time.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::class_group::*;
+//! # use num_bigint::{BigInt, BigUint};
+//! let discriminant = BigInt::from(-199);
+//! let f = QuadraticForm::from_a_b(BigInt::from(2), BigInt::from(1), &discriminant).unwrap();
+//! let g = f.compose(&f.inverse());
+//! assert_eq!(g, QuadraticForm::identity(&discriminant).unwrap());
+//! assert_eq!(f.pow(&BigUint::from(9u8)), QuadraticForm::identity(&discriminant).unwrap());
+//! ```
+
+use crate::error::FastCryptoError;
+use crate::number_theory::{div_floor, xgcd};
+use num_bigint::{BigInt, BigUint};
+use serde::{Deserialize, Serialize};
+use std::mem::swap;
+
+/// A reduced positive definite binary quadratic form `(a, b, c)`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuadraticForm {
+    a: BigInt,
+    b: BigInt,
+    c: BigInt,
+}
+
+impl QuadraticForm {
+    /// Create the reduced form equivalent to `(a, b, c)`. The form must be positive definite and
+    /// its discriminant must be congruent to 1 modulo 4.
+    pub fn new(a: BigInt, b: BigInt, c: BigInt) -> Result<Self, FastCryptoError> {
+        let form = Self { a, b, c };
+        let discriminant = form.discriminant();
+        let zero = BigInt::from(0u8);
+        if form.a <= zero || !is_valid_discriminant(&discriminant) {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(form.reduce())
+    }
+
+    /// Create the reduced form equivalent to `(a, b, c)` of the given discriminant, where `c` is
+    /// computed from `a`, `b` and the discriminant.
+    pub fn from_a_b(a: BigInt, b: BigInt, discriminant: &BigInt) -> Result<Self, FastCryptoError> {
+        let zero = BigInt::from(0u8);
+        if a <= zero {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let numerator = &b * &b - discriminant;
+        let denominator = BigInt::from(4u8) * &a;
+        if &numerator % &denominator != zero {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Self::new(a, b, numerator / denominator)
+    }
+
+    /// The identity element `(1, 1, (1 - D) / 4)` of the class group of discriminant `D`.
+    pub fn identity(discriminant: &BigInt) -> Result<Self, FastCryptoError> {
+        if !is_valid_discriminant(discriminant) {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(Self {
+            a: BigInt::from(1u8),
+            b: BigInt::from(1u8),
+            c: (BigInt::from(1u8) - discriminant) / 4,
+        })
+    }
+
+    /// The coefficients `(a, b, c)` of this form.
+    pub fn coefficients(&self) -> (&BigInt, &BigInt, &BigInt) {
+        (&self.a, &self.b, &self.c)
+    }
+
+    /// The discriminant `b^2 - 4ac` of this form.
+    pub fn discriminant(&self) -> BigInt {
+        &self.b * &self.b - BigInt::from(4u8) * &self.a * &self.c
+    }
+
+    /// Compose this form with another form of the same discriminant.
+    pub fn compose(&self, other: &Self) -> Self {
+        let discriminant = self.discriminant();
+        debug_assert_eq!(discriminant, other.discriminant());
+        let (a1, b1) = (&self.a, &self.b);
+        let (a2, b2) = (&other.a, &other.b);
+
+        // Find mu, nu and omega such that mu * a1 + nu * a2 + omega * (b1 + b2) / 2 = e where e is
+        // the gcd of the three.
+        let beta: BigInt = (b1 + b2) / 2;
+        let (g, u1, v1) = xgcd(a1, a2);
+        let (e, u2, omega) = xgcd(&g, &beta);
+        let mu = &u2 * u1;
+        let nu = &u2 * v1;
+
+        let a3 = a1 * a2 / (&e * &e);
+        let b3 = (&mu * a1 * b2 + &nu * a2 * b1 + &omega * ((b1 * b2 + &discriminant) / 2)) / &e;
+
+        // Reduce b3 modulo 2 * a3 to keep the coefficients small before computing c3.
+        let two_a3 = BigInt::from(2u8) * &a3;
+        let b3 = &b3 - div_floor(&b3, &two_a3) * &two_a3;
+        let c3 = (&b3 * &b3 - &discriminant) / (BigInt::from(4u8) * &a3);
+        Self {
+            a: a3,
+            b: b3,
+            c: c3,
+        }
+        .reduce()
+    }
+
+    /// The inverse of this form, `(a, -b, c)`.
+    pub fn inverse(&self) -> Self {
+        Self {
+            a: self.a.clone(),
+            b: -&self.b,
+            c: self.c.clone(),
+        }
+        .reduce()
+    }
+
+    /// Compute this form raised to the power `exponent`.
+    pub fn pow(&self, exponent: &BigUint) -> Self {
+        let mut result = Self::identity(&self.discriminant()).expect("Discriminant is valid");
+        for i in (0..exponent.bits()).rev() {
+            result = result.compose(&result);
+            if exponent.bit(i) {
+                result = result.compose(self);
+            }
+        }
+        result
+    }
+
+    /// Replace `b` by the representative of `b` modulo `2a` in `(-a, a]`.
+    fn normalize(&mut self) {
+        let two_a = BigInt::from(2u8) * &self.a;
+        let t = div_floor(&(&self.a - &self.b), &two_a);
+        self.c = &self.c + &t * (&self.b + &self.a * &t);
+        self.b = &self.b + &two_a * &t;
+    }
+
+    fn reduce(mut self) -> Self {
+        self.normalize();
+        while self.a > self.c {
+            swap(&mut self.a, &mut self.c);
+            self.b = -&self.b;
+            self.normalize();
+        }
+        if self.a == self.c && self.b < BigInt::from(0u8) {
+            self.b = -&self.b;
+        }
+        self
+    }
+}
+
+fn is_valid_discriminant(discriminant: &BigInt) -> bool {
+    discriminant < &BigInt::from(0u8)
+        && (discriminant - BigInt::from(1u8)) % BigInt::from(4u8) == BigInt::from(0u8)
+}
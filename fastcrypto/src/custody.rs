@@ -0,0 +1,363 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Custody attestations for erasure-coded data, a building block for data availability sampling.
+//!
+//! The chunks of a blob, typically the output of an erasure code so that the blob can be recovered
+//! from a fraction of them, are committed to by [CommittedData::new] as the root of a binary Merkle
+//! tree over the chunks. A custodian storing the data signs a [CustodyAttestation] for a chunk
+//! with its BLS key, binding the key to the commitment, the index and the digest of the chunk.
+//!
+//! To check that a custodian still holds the data, a verifier derives pseudo-random indices from
+//! a fresh public seed, e.g. a randomness beacon, with [sample_indices]. The custodian answers with
+//! a [CustodyResponse] for each index which contains the chunk, its Merkle proof and its
+//! attestation, and [verify_samples] checks all of them with one aggregated signature check.
+//!
+//! The Merkle tree uses SHA-256 with distinct prefixes for leaves and inner nodes. A lone last node
+//! of a level is moved up to the next level unchanged, so the number of chunks is part of the
+//! commitment.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::custody::*;
+//! # use fastcrypto::bls12381::min_sig::BLS12381KeyPair;
+//! # use fastcrypto::traits::KeyPair;
+//! # use rand::thread_rng;
+//! let chunks = (0..10u8).map(|i| vec![i; 64]).collect::<Vec<_>>();
+//! let data = CommittedData::new(chunks).unwrap();
+//! let commitment = data.commitment();
+//!
+//! let custodian = BLS12381KeyPair::generate(&mut thread_rng());
+//! let indices = sample_indices(&commitment, b"beacon round 7", 3).unwrap();
+//! let responses = indices
+//!     .iter()
+//!     .map(|i| data.respond(&custodian, *i).unwrap())
+//!     .collect::<Vec<_>>();
+//! assert!(verify_samples(custodian.public(), &commitment, b"beacon round 7", &responses).is_ok());
+//! ```
+
+use crate::bls12381::min_sig::{
+    BLS12381AggregateSignature, BLS12381KeyPair, BLS12381PublicKey, BLS12381Signature,
+};
+use crate::error::FastCryptoError;
+use crate::hash::{Digest, HashFunction, Sha256};
+use crate::limits::{enforce, Limit};
+use crate::traits::{AggregateAuthenticator, Signer, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+/// The domain separation tag of the messages signed by custody attestations.
+pub const CUSTODY_ATTESTATION_DST: &[u8] = b"FASTCRYPTO-CUSTODY-ATTESTATION-V1";
+
+/// The domain separation tag of the derivation of sampled indices.
+pub const SAMPLING_DST: &[u8] = b"FASTCRYPTO-CUSTODY-SAMPLING-V1";
+
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+
+/// A commitment to the chunks of a blob.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DataCommitment {
+    pub root: Digest<32>,
+    pub num_chunks: u32,
+}
+
+/// A proof that a chunk is at a given index of a commitment, i.e., the siblings of the nodes on the
+/// path from the leaf of the chunk to the root, starting at the leaf. Lone nodes have no sibling.
+///
+/// Deserialization fails if the proof has more siblings than allowed by [Limit::MerkleProofDepth].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "Vec<Digest<32>>")]
+pub struct MerkleProof(pub Vec<Digest<32>>);
+
+impl TryFrom<Vec<Digest<32>>> for MerkleProof {
+    type Error = FastCryptoError;
+
+    fn try_from(siblings: Vec<Digest<32>>) -> Result<Self, Self::Error> {
+        enforce(Limit::MerkleProofDepth, siblings.len())?;
+        Ok(Self(siblings))
+    }
+}
+
+/// A signed statement by a custodian that it stores the chunk with the given digest at the given
+/// index of a commitment.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustodyAttestation {
+    pub commitment: DataCommitment,
+    pub index: u32,
+    /// The Merkle tree leaf of the chunk.
+    pub chunk_digest: Digest<32>,
+    pub signature: BLS12381Signature,
+}
+
+/// The answer of a custodian to a challenge for a single index.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustodyResponse {
+    pub chunk: Vec<u8>,
+    pub proof: MerkleProof,
+    pub attestation: CustodyAttestation,
+}
+
+/// The chunks of a blob together with their Merkle tree.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommittedData {
+    chunks: Vec<Vec<u8>>,
+    /// The levels of the tree, starting with the leaves and ending with the root.
+    levels: Vec<Vec<Digest<32>>>,
+}
+
+impl CommittedData {
+    /// Commit to the given chunks. Fails if there are no chunks or more than `u32::MAX`.
+    pub fn new(chunks: Vec<Vec<u8>>) -> Result<Self, FastCryptoError> {
+        if chunks.is_empty() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        if chunks.len() > u32::MAX as usize {
+            return Err(FastCryptoError::InputTooLong(u32::MAX as usize));
+        }
+        let mut levels = vec![chunks.iter().map(|c| leaf_hash(c)).collect::<Vec<_>>()];
+        while levels.last().expect("Not empty").len() > 1 {
+            let next = levels
+                .last()
+                .expect("Not empty")
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => node_hash(left, right),
+                    [lone] => *lone,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+        Ok(Self { chunks, levels })
+    }
+
+    pub fn commitment(&self) -> DataCommitment {
+        DataCommitment {
+            root: self.levels.last().expect("Not empty")[0],
+            num_chunks: self.chunks.len() as u32,
+        }
+    }
+
+    pub fn chunks(&self) -> &[Vec<u8>] {
+        &self.chunks
+    }
+
+    /// The Merkle proof for the chunk at the given index. Fails if the index is out of range.
+    pub fn prove(&self, index: u32) -> Result<MerkleProof, FastCryptoError> {
+        self.check_index(index)?;
+        let mut position = index as usize;
+        let mut siblings = Vec::new();
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(position ^ 1) {
+                siblings.push(*sibling);
+            }
+            position /= 2;
+        }
+        Ok(MerkleProof(siblings))
+    }
+
+    /// Sign an attestation for the chunk at the given index. Fails if the index is out of range.
+    pub fn attest(
+        &self,
+        key_pair: &BLS12381KeyPair,
+        index: u32,
+    ) -> Result<CustodyAttestation, FastCryptoError> {
+        self.check_index(index)?;
+        let commitment = self.commitment();
+        let chunk_digest = self.levels[0][index as usize];
+        let signature = key_pair.sign(&attestation_message(&commitment, index, &chunk_digest));
+        Ok(CustodyAttestation {
+            commitment,
+            index,
+            chunk_digest,
+            signature,
+        })
+    }
+
+    /// The response to a challenge for the chunk at the given index. Fails if the index is out of
+    /// range.
+    pub fn respond(
+        &self,
+        key_pair: &BLS12381KeyPair,
+        index: u32,
+    ) -> Result<CustodyResponse, FastCryptoError> {
+        Ok(CustodyResponse {
+            chunk: self.chunks[self.check_index(index)?].clone(),
+            proof: self.prove(index)?,
+            attestation: self.attest(key_pair, index)?,
+        })
+    }
+
+    fn check_index(&self, index: u32) -> Result<usize, FastCryptoError> {
+        if index as usize >= self.chunks.len() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(index as usize)
+    }
+}
+
+impl DataCommitment {
+    /// Verify that `chunk` is at the given index of this commitment.
+    pub fn verify_chunk(
+        &self,
+        index: u32,
+        chunk: &[u8],
+        proof: &MerkleProof,
+    ) -> Result<(), FastCryptoError> {
+        if index >= self.num_chunks {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        enforce(Limit::MerkleProofDepth, proof.0.len())?;
+        let mut siblings = proof.0.iter();
+        let mut node = leaf_hash(chunk);
+        let mut position = index;
+        let mut level_size = self.num_chunks;
+        while level_size > 1 {
+            // The last node of a level of odd size has no sibling.
+            if position % 2 == 1 || position + 1 < level_size {
+                let sibling = siblings.next().ok_or(FastCryptoError::InvalidProof)?;
+                node = if position % 2 == 0 {
+                    node_hash(&node, sibling)
+                } else {
+                    node_hash(sibling, &node)
+                };
+            }
+            position /= 2;
+            level_size = (level_size + 1) / 2;
+        }
+        if siblings.next().is_some() || node != self.root {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        Ok(())
+    }
+}
+
+impl CustodyAttestation {
+    /// The message signed by this attestation.
+    pub fn message(&self) -> Vec<u8> {
+        attestation_message(&self.commitment, self.index, &self.chunk_digest)
+    }
+
+    /// Verify the signature of this attestation by the given custodian.
+    pub fn verify(&self, public_key: &BLS12381PublicKey) -> Result<(), FastCryptoError> {
+        public_key.verify(&self.message(), &self.signature)
+    }
+}
+
+impl CustodyResponse {
+    /// Verify that this response is a valid answer by the given custodian to a challenge for the
+    /// given index of a commitment, including the signature of the attestation.
+    pub fn verify(
+        &self,
+        public_key: &BLS12381PublicKey,
+        commitment: &DataCommitment,
+        index: u32,
+    ) -> Result<(), FastCryptoError> {
+        self.verify_without_signature(commitment, index)?;
+        self.attestation.verify(public_key)
+    }
+
+    fn verify_without_signature(
+        &self,
+        commitment: &DataCommitment,
+        index: u32,
+    ) -> Result<(), FastCryptoError> {
+        if self.attestation.commitment != *commitment
+            || self.attestation.index != index
+            || self.attestation.chunk_digest != leaf_hash(&self.chunk)
+        {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        commitment.verify_chunk(index, &self.chunk, &self.proof)
+    }
+}
+
+/// Derive `num_samples` distinct pseudo-random indices of the chunks of a commitment from a seed.
+/// Fails if `num_samples` is zero or larger than the number of chunks.
+pub fn sample_indices(
+    commitment: &DataCommitment,
+    seed: &[u8],
+    num_samples: usize,
+) -> Result<Vec<u32>, FastCryptoError> {
+    if num_samples == 0 || num_samples > commitment.num_chunks as usize {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    // Sample by rejection to avoid a modulo bias.
+    let num_chunks = commitment.num_chunks as u64;
+    let limit = u64::MAX - u64::MAX % num_chunks;
+    let mut indices = Vec::with_capacity(num_samples);
+    let mut counter = 0u64;
+    while indices.len() < num_samples {
+        let mut hash = Sha256::new();
+        hash.update(SAMPLING_DST);
+        hash.update((seed.len() as u64).to_be_bytes());
+        hash.update(seed);
+        hash.update(commitment.root.digest);
+        hash.update(commitment.num_chunks.to_be_bytes());
+        hash.update(counter.to_be_bytes());
+        counter += 1;
+        for bytes in hash.finalize().digest.chunks_exact(8) {
+            let value = u64::from_be_bytes(bytes.try_into().expect("Length is 8"));
+            let index = (value % num_chunks) as u32;
+            if value < limit && !indices.contains(&index) && indices.len() < num_samples {
+                indices.push(index);
+            }
+        }
+    }
+    Ok(indices)
+}
+
+/// Verify the responses of a custodian to the challenge given by the seed, i.e., that there is a
+/// valid response for each of the indices sampled by [sample_indices] in order. The signatures of
+/// the attestations are verified together as an aggregate signature.
+pub fn verify_samples(
+    public_key: &BLS12381PublicKey,
+    commitment: &DataCommitment,
+    seed: &[u8],
+    responses: &[CustodyResponse],
+) -> Result<(), FastCryptoError> {
+    let indices = sample_indices(commitment, seed, responses.len())?;
+    for (response, index) in responses.iter().zip(&indices) {
+        response.verify_without_signature(commitment, *index)?;
+    }
+    let messages = responses
+        .iter()
+        .map(|r| r.attestation.message())
+        .collect::<Vec<_>>();
+    let aggregate =
+        BLS12381AggregateSignature::aggregate(responses.iter().map(|r| &r.attestation.signature))?;
+    aggregate.verify_different_msg(
+        &vec![public_key.clone(); responses.len()],
+        &messages.iter().map(|m| m.as_slice()).collect::<Vec<_>>(),
+    )
+}
+
+fn attestation_message(
+    commitment: &DataCommitment,
+    index: u32,
+    chunk_digest: &Digest<32>,
+) -> Vec<u8> {
+    [
+        CUSTODY_ATTESTATION_DST,
+        &commitment.root.digest,
+        &commitment.num_chunks.to_be_bytes(),
+        &index.to_be_bytes(),
+        &chunk_digest.digest,
+    ]
+    .concat()
+}
+
+fn leaf_hash(chunk: &[u8]) -> Digest<32> {
+    let mut hash = Sha256::new();
+    hash.update([LEAF_PREFIX]);
+    hash.update(chunk);
+    hash.finalize()
+}
+
+fn node_hash(left: &Digest<32>, right: &Digest<32>) -> Digest<32> {
+    let mut hash = Sha256::new();
+    hash.update([NODE_PREFIX]);
+    hash.update(left.digest);
+    hash.update(right.digest);
+    hash.finalize()
+}
@@ -0,0 +1,451 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The [Double Ratchet](https://signal.org/docs/specifications/doubleratchet/) algorithm for
+//! encrypted sessions between two parties, with optional header encryption.
+//!
+//! Both parties start from a shared secret, e.g., from a Noise handshake, and the initiator must
+//! also know an X25519 public key of the responder. Each message is encrypted with AES-256-GCM
+//! using its own key from a [KeyRatchet]. The chains of the ratchets are replaced by a DH ratchet
+//! step whenever a party receives a message with a new X25519 public key of the other party, which
+//! each party changes once per round trip. A compromise of the state of a session thus neither
+//! reveals the keys of earlier messages nor, once a DH ratchet step has been done, of later ones.
+//!
+//! Messages may arrive out of order. The keys of skipped messages are stored for the current and
+//! up to [MAX_PREVIOUS_CHAINS] previous receiving chains, and at most `max_skip` keys are skipped
+//! in a chain, see [KeyRatchet].
+//!
+//! With header encryption, the [Header] of a message, which contains the public key of the sender
+//! and the index of the message, is encrypted using AES-256-GCM with header keys which are updated
+//! in each DH ratchet step, so an observer cannot link the messages of a session.
+//!
+//! The state of a session can be serialized, e.g., using bcs, to persist it. The serialized state
+//! contains secret keys and must be protected like them.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::double_ratchet::*;
+//! # use fastcrypto::key_ratchet::DEFAULT_MAX_SKIP;
+//! # use fastcrypto::noise::NoiseKeyPair;
+//! # use rand::thread_rng;
+//! let secret = [7u8; 32];
+//! let bob_key = NoiseKeyPair::generate(&mut thread_rng());
+//! let mut alice =
+//!     DoubleRatchet::initiator(&secret, bob_key.public(), true, DEFAULT_MAX_SKIP, &mut thread_rng())
+//!         .unwrap();
+//! let mut bob = DoubleRatchet::responder(&secret, bob_key, true, DEFAULT_MAX_SKIP).unwrap();
+//!
+//! let message = alice.encrypt(b"Hello, Bob!", b"", &mut thread_rng()).unwrap();
+//! assert_eq!(bob.decrypt(&message, b"", &mut thread_rng()).unwrap(), b"Hello, Bob!");
+//! let reply = bob.encrypt(b"Hello, Alice!", b"", &mut thread_rng()).unwrap();
+//! assert_eq!(alice.decrypt(&reply, b"", &mut thread_rng()).unwrap(), b"Hello, Alice!");
+//!
+//! // A message cannot be decrypted twice.
+//! assert!(alice.decrypt(&reply, b"", &mut thread_rng()).is_err());
+//! ```
+
+use crate::aes::{Aes256Gcm, AesKey, AuthenticatedCipher, InitializationVector};
+use crate::error::{FastCryptoError, FastCryptoResult};
+use crate::hmac::{hkdf_sha3_256, HkdfIkm};
+use crate::key_ratchet::{KeyRatchet, MessageKey};
+use crate::noise::{NoiseKeyPair, NoisePrivateKey, NoisePublicKey};
+use crate::traits::{AllowedRng, ToFromBytes};
+use fastcrypto_derive::{SilentDebug, SilentDisplay};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use typenum::U12;
+use zeroize::Zeroize;
+
+/// The number of previous receiving chains for which the keys of skipped messages are kept.
+pub const MAX_PREVIOUS_CHAINS: usize = 8;
+
+/// The length of an encoded [Header].
+pub const HEADER_LENGTH: usize = KEY_LENGTH + 16;
+
+const KEY_LENGTH: usize = 32;
+const IV_LENGTH: usize = 12;
+const INITIAL_KEYS_DST: &[u8] = b"fastcrypto-double-ratchet-initial-keys-v1";
+const ROOT_DST: &[u8] = b"fastcrypto-double-ratchet-root-v1";
+
+type Key = [u8; KEY_LENGTH];
+
+/// The header of a message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Header {
+    /// The current X25519 public key of the sender.
+    pub dh: NoisePublicKey,
+    /// The number of messages in the previous sending chain of the sender.
+    pub previous_chain_length: u64,
+    /// The index of the message in the current sending chain of the sender.
+    pub index: u64,
+}
+
+impl Header {
+    /// The public key followed by the lengths as little-endian integers.
+    pub fn to_bytes(&self) -> [u8; HEADER_LENGTH] {
+        let mut bytes = [0u8; HEADER_LENGTH];
+        bytes[..KEY_LENGTH].copy_from_slice(self.dh.as_ref());
+        bytes[KEY_LENGTH..KEY_LENGTH + 8]
+            .copy_from_slice(&self.previous_chain_length.to_le_bytes());
+        bytes[KEY_LENGTH + 8..].copy_from_slice(&self.index.to_le_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> FastCryptoResult<Self> {
+        if bytes.len() != HEADER_LENGTH {
+            return Err(FastCryptoError::InputLengthWrong(HEADER_LENGTH));
+        }
+        let u64_at =
+            |i: usize| u64::from_le_bytes(bytes[i..i + 8].try_into().expect("Length is 8"));
+        Ok(Self {
+            dh: NoisePublicKey::from_bytes(&bytes[..KEY_LENGTH])?,
+            previous_chain_length: u64_at(KEY_LENGTH),
+            index: u64_at(KEY_LENGTH + 8),
+        })
+    }
+}
+
+/// An encrypted message. The header is encrypted if the session uses header encryption.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Message {
+    pub header: Vec<u8>,
+    pub ciphertext: Vec<u8>,
+}
+
+/// The keys used to encrypt headers and the keys which will be used after the next DH ratchet step.
+#[derive(Clone, Serialize, Deserialize, Zeroize)]
+struct HeaderKeys {
+    sending: Option<Key>,
+    receiving: Option<Key>,
+    next_sending: Key,
+    next_receiving: Key,
+}
+
+/// A receiving chain replaced by a DH ratchet step, which holds the keys of skipped messages. It is
+/// identified by the public key of the sender or, with header encryption, by its header key.
+#[derive(Clone, Serialize, Deserialize)]
+struct PreviousChain {
+    id: Key,
+    chain: KeyRatchet,
+}
+
+/// The state of one party of a session, see the module documentation.
+#[derive(Clone, SilentDebug, SilentDisplay, Serialize, Deserialize)]
+pub struct DoubleRatchet {
+    dh_private: Key,
+    dh_public: NoisePublicKey,
+    remote: Option<NoisePublicKey>,
+    root_key: Key,
+    sending: Option<KeyRatchet>,
+    receiving: Option<KeyRatchet>,
+    previous_sending_length: u64,
+    previous: VecDeque<PreviousChain>,
+    header_keys: Option<HeaderKeys>,
+    max_skip: u64,
+}
+
+impl DoubleRatchet {
+    /// The state of the party sending the first message, which knows the public key `remote` of the
+    /// other party.
+    pub fn initiator<R: AllowedRng>(
+        secret: &[u8],
+        remote: &NoisePublicKey,
+        header_encryption: bool,
+        max_skip: u64,
+        rng: &mut R,
+    ) -> FastCryptoResult<Self> {
+        let (root_key, header_key, next_header_key) = initial_keys(secret)?;
+        let key_pair = NoiseKeyPair::generate(rng);
+        let (root_key, sending, next_sending) = kdf_rk(&root_key, &key_pair.dh(remote)?)?;
+        Ok(Self {
+            dh_private: *key_pair.private().as_bytes(),
+            dh_public: key_pair.public().clone(),
+            remote: Some(remote.clone()),
+            root_key,
+            sending: Some(KeyRatchet::from_chain_key(sending, max_skip)),
+            receiving: None,
+            previous_sending_length: 0,
+            previous: VecDeque::new(),
+            header_keys: header_encryption.then_some(HeaderKeys {
+                sending: Some(header_key),
+                receiving: None,
+                next_sending,
+                next_receiving: next_header_key,
+            }),
+            max_skip,
+        })
+    }
+
+    /// The state of the party receiving the first message, whose key pair is `key_pair`. It can
+    /// only send messages after receiving one.
+    pub fn responder(
+        secret: &[u8],
+        key_pair: NoiseKeyPair,
+        header_encryption: bool,
+        max_skip: u64,
+    ) -> FastCryptoResult<Self> {
+        let (root_key, header_key, next_header_key) = initial_keys(secret)?;
+        Ok(Self {
+            dh_private: *key_pair.private().as_bytes(),
+            dh_public: key_pair.public().clone(),
+            remote: None,
+            root_key,
+            sending: None,
+            receiving: None,
+            previous_sending_length: 0,
+            previous: VecDeque::new(),
+            header_keys: header_encryption.then_some(HeaderKeys {
+                sending: None,
+                receiving: None,
+                next_sending: next_header_key,
+                next_receiving: header_key,
+            }),
+            max_skip,
+        })
+    }
+
+    /// The current X25519 public key of this party.
+    pub fn public_key(&self) -> &NoisePublicKey {
+        &self.dh_public
+    }
+
+    /// The current X25519 public key of the other party, if known.
+    pub fn remote_public_key(&self) -> Option<&NoisePublicKey> {
+        self.remote.as_ref()
+    }
+
+    /// Encrypt a message, authenticating also `associated_data`. Fails for a responder which has
+    /// not received a message yet.
+    pub fn encrypt<R: AllowedRng>(
+        &mut self,
+        plaintext: &[u8],
+        associated_data: &[u8],
+        rng: &mut R,
+    ) -> FastCryptoResult<Message> {
+        let sending = self.sending.as_mut().ok_or_else(|| {
+            FastCryptoError::GeneralError("No message has been received yet".to_string())
+        })?;
+        let key = sending.next_key();
+        let header = Header {
+            dh: self.dh_public.clone(),
+            previous_chain_length: self.previous_sending_length,
+            index: key.index(),
+        }
+        .to_bytes();
+        let header = match &self.header_keys {
+            Some(HeaderKeys {
+                sending: Some(header_key),
+                ..
+            }) => encrypt_header(header_key, &header, rng),
+            Some(_) => unreachable!("Header keys are set with the sending chain"),
+            None => header.to_vec(),
+        };
+        let ciphertext = key.encrypt(&aad(associated_data, &header), plaintext);
+        Ok(Message { header, ciphertext })
+    }
+
+    /// Decrypt a message, which must have been encrypted with the same `associated_data`. If the
+    /// message has a new public key of the other party, this does a DH ratchet step. The state is
+    /// unchanged if decryption fails.
+    pub fn decrypt<R: AllowedRng>(
+        &mut self,
+        message: &Message,
+        associated_data: &[u8],
+        rng: &mut R,
+    ) -> FastCryptoResult<Vec<u8>> {
+        let mut state = self.clone();
+        let key = state.message_key(&message.header, rng)?;
+        let plaintext = key.decrypt(&aad(associated_data, &message.header), &message.ciphertext)?;
+        *self = state;
+        Ok(plaintext)
+    }
+
+    /// The key of a message with the given header, which may require a DH ratchet step.
+    fn message_key<R: AllowedRng>(
+        &mut self,
+        header: &[u8],
+        rng: &mut R,
+    ) -> FastCryptoResult<MessageKey> {
+        let header = match &self.header_keys {
+            None => {
+                let header = Header::from_bytes(header)?;
+                let id = key_from_slice(header.dh.as_ref())?;
+                if let Some(previous) = self.previous.iter_mut().find(|p| p.id == id) {
+                    return previous_key(&mut previous.chain, header.index);
+                }
+                if self.remote.as_ref() != Some(&header.dh) {
+                    self.dh_ratchet(&header, rng)?;
+                }
+                header
+            }
+            Some(keys) => {
+                for previous in self.previous.iter_mut() {
+                    if let Ok(header) = decrypt_header(&previous.id, header) {
+                        return previous_key(&mut previous.chain, header.index);
+                    }
+                }
+                match keys
+                    .receiving
+                    .as_ref()
+                    .and_then(|key| decrypt_header(key, header).ok())
+                {
+                    Some(header) => header,
+                    None => {
+                        let header = decrypt_header(&keys.next_receiving, header)?;
+                        self.dh_ratchet(&header, rng)?;
+                        header
+                    }
+                }
+            }
+        };
+        self.receiving
+            .as_mut()
+            .expect("The receiving chain is set by the DH ratchet")
+            .key_for(header.index)
+    }
+
+    /// Replace the chains after receiving a new public key of the other party.
+    fn dh_ratchet<R: AllowedRng>(&mut self, header: &Header, rng: &mut R) -> FastCryptoResult<()> {
+        // Keep the keys of the messages in the current receiving chain which have not arrived.
+        if let Some(mut receiving) = self.receiving.take() {
+            receiving.skip_to(header.previous_chain_length)?;
+            let id = match &self.header_keys {
+                Some(keys) => keys
+                    .receiving
+                    .expect("Header keys are set with the receiving chain"),
+                None => key_from_slice(
+                    self.remote
+                        .as_ref()
+                        .expect("The remote key is set with the receiving chain")
+                        .as_ref(),
+                )?,
+            };
+            if receiving.skipped() > 0 {
+                self.previous.push_back(PreviousChain {
+                    id,
+                    chain: receiving,
+                });
+                if self.previous.len() > MAX_PREVIOUS_CHAINS {
+                    self.previous.pop_front();
+                }
+            }
+        }
+
+        let key_pair = NoiseKeyPair::from(NoisePrivateKey::from_bytes(&self.dh_private)?);
+        let (root_key, receiving, next_receiving) =
+            kdf_rk(&self.root_key, &key_pair.dh(&header.dh)?)?;
+        let key_pair = NoiseKeyPair::generate(rng);
+        let (root_key, sending, next_sending) = kdf_rk(&root_key, &key_pair.dh(&header.dh)?)?;
+
+        self.previous_sending_length = self.sending.as_ref().map_or(0, |chain| chain.index());
+        self.remote = Some(header.dh.clone());
+        self.dh_private.zeroize();
+        self.dh_private = *key_pair.private().as_bytes();
+        self.dh_public = key_pair.public().clone();
+        self.root_key.zeroize();
+        self.root_key = root_key;
+        self.receiving = Some(KeyRatchet::from_chain_key(receiving, self.max_skip));
+        self.sending = Some(KeyRatchet::from_chain_key(sending, self.max_skip));
+        if let Some(keys) = &mut self.header_keys {
+            keys.sending = Some(keys.next_sending);
+            keys.receiving = Some(keys.next_receiving);
+            keys.next_sending = next_sending;
+            keys.next_receiving = next_receiving;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for DoubleRatchet {
+    fn drop(&mut self) {
+        self.dh_private.zeroize();
+        self.root_key.zeroize();
+        if let Some(keys) = &mut self.header_keys {
+            keys.zeroize();
+        }
+        self.previous.iter_mut().for_each(|p| p.id.zeroize());
+    }
+}
+
+/// The key of a skipped message in a previous chain, which does not have keys of later messages.
+fn previous_key(chain: &mut KeyRatchet, index: u64) -> FastCryptoResult<MessageKey> {
+    if index >= chain.index() {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    chain.key_for(index)
+}
+
+/// The initial root key and the two initial header keys derived from the shared secret.
+fn initial_keys(secret: &[u8]) -> FastCryptoResult<(Key, Key, Key)> {
+    let okm = hkdf_sha3_256(
+        &HkdfIkm::from_bytes(secret)?,
+        INITIAL_KEYS_DST,
+        &[],
+        3 * KEY_LENGTH,
+    )?;
+    split_keys(&okm)
+}
+
+/// The KDF of the root chain, which outputs the next root key, a chain key and a header key.
+fn kdf_rk(root_key: &Key, dh_output: &Key) -> FastCryptoResult<(Key, Key, Key)> {
+    let okm = hkdf_sha3_256(
+        &HkdfIkm::from_bytes(dh_output)?,
+        root_key,
+        ROOT_DST,
+        3 * KEY_LENGTH,
+    )?;
+    split_keys(&okm)
+}
+
+fn split_keys(okm: &[u8]) -> FastCryptoResult<(Key, Key, Key)> {
+    Ok((
+        key_from_slice(&okm[..KEY_LENGTH])?,
+        key_from_slice(&okm[KEY_LENGTH..2 * KEY_LENGTH])?,
+        key_from_slice(&okm[2 * KEY_LENGTH..])?,
+    ))
+}
+
+fn key_from_slice(bytes: &[u8]) -> FastCryptoResult<Key> {
+    bytes
+        .try_into()
+        .map_err(|_| FastCryptoError::InputLengthWrong(KEY_LENGTH))
+}
+
+/// The associated data of a message, which also authenticates the (encrypted) header.
+fn aad(associated_data: &[u8], header: &[u8]) -> Vec<u8> {
+    [
+        &(associated_data.len() as u64).to_le_bytes(),
+        associated_data,
+        header,
+    ]
+    .concat()
+}
+
+fn header_cipher(header_key: &Key) -> Aes256Gcm<U12> {
+    Aes256Gcm::new(AesKey::from_bytes(header_key).expect("Length is correct"))
+}
+
+/// Encrypt a header with a random nonce, which is prepended to the ciphertext.
+fn encrypt_header<R: AllowedRng>(header_key: &Key, header: &[u8], rng: &mut R) -> Vec<u8> {
+    let mut iv = [0u8; IV_LENGTH];
+    rng.fill_bytes(&mut iv);
+    let ciphertext = header_cipher(header_key).encrypt_authenticated(
+        &InitializationVector::from_bytes(&iv).expect("Length is correct"),
+        &[],
+        header,
+    );
+    [&iv[..], &ciphertext].concat()
+}
+
+fn decrypt_header(header_key: &Key, bytes: &[u8]) -> FastCryptoResult<Header> {
+    if bytes.len() < IV_LENGTH {
+        return Err(FastCryptoError::InputTooShort(IV_LENGTH));
+    }
+    let (iv, ciphertext) = bytes.split_at(IV_LENGTH);
+    let header = header_cipher(header_key).decrypt_authenticated(
+        &InitializationVector::from_bytes(iv)?,
+        &[],
+        ciphertext,
+    )?;
+    Header::from_bytes(&header)
+}
@@ -0,0 +1,211 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verification of beacons from the [drand](https://drand.love) randomness network.
+//!
+//! A drand beacon for round `r` is a BLS signature on `SHA-256(m)` by the threshold key of the
+//! network, and its randomness is the SHA-256 hash of the signature. The message `m` depends on
+//! the scheme of the network:
+//! * `pedersen-bls-chained`: The signature of the previous round followed by `r` as an 8 byte
+//!   big-endian integer. Signatures are in G2 and the public key in G1.
+//! * `pedersen-bls-unchained`: `r` as an 8 byte big-endian integer. Signatures are in G2 and the
+//!   public key in G1.
+//! * `bls-unchained-on-g1`: As above, but signatures are in G1 and the public key in G2. This
+//!   scheme hashes to G1 using the DST of G2, and is superseded by `bls-unchained-g1-rfc9380`.
+//! * `bls-unchained-g1-rfc9380`: As above, but hashing to G1 uses the DST of G1 (used by the
+//!   quicknet network).
+//!
+//! Beacons can be deserialized from the JSON returned by the drand HTTP API.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::drand::*;
+//! # use fastcrypto::bls12381::min_pk::BLS12381KeyPair;
+//! # use fastcrypto::traits::{KeyPair, Signer, ToFromBytes};
+//! # use fastcrypto::hash::{HashFunction, Sha256};
+//! # use rand::thread_rng;
+//! let kp = BLS12381KeyPair::generate(&mut thread_rng());
+//! let chain = DrandChain::new(DrandScheme::PedersenBlsUnchained, kp.public().as_bytes()).unwrap();
+//!
+//! let signature = kp.sign(&Sha256::digest(7u64.to_be_bytes()).digest);
+//! let beacon = DrandBeacon {
+//!     round: 7,
+//!     signature: signature.as_bytes().to_vec(),
+//!     previous_signature: None,
+//! };
+//! assert!(chain.verify(&beacon).is_ok());
+//! assert_eq!(beacon.randomness(), Sha256::digest(signature.as_bytes()).digest);
+//! ```
+
+use crate::bls12381::min_pk::DST_G2;
+use crate::bls12381::min_sig::DST_G1;
+use crate::bls12381::{min_pk, min_sig};
+use crate::encoding::Hex;
+use crate::error::FastCryptoError;
+use crate::hash::{HashFunction, Sha256};
+use crate::traits::ToFromBytes;
+use blst::BLST_ERROR;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+/// The schemes used by drand networks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DrandScheme {
+    PedersenBlsChained,
+    PedersenBlsUnchained,
+    BlsUnchainedOnG1,
+    BlsUnchainedG1Rfc9380,
+}
+
+impl DrandScheme {
+    /// The identifier of the scheme as given in the chain info of a drand network.
+    pub fn id(&self) -> &'static str {
+        match self {
+            DrandScheme::PedersenBlsChained => "pedersen-bls-chained",
+            DrandScheme::PedersenBlsUnchained => "pedersen-bls-unchained",
+            DrandScheme::BlsUnchainedOnG1 => "bls-unchained-on-g1",
+            DrandScheme::BlsUnchainedG1Rfc9380 => "bls-unchained-g1-rfc9380",
+        }
+    }
+
+    /// Parse the identifier of a scheme.
+    pub fn from_id(id: &str) -> Result<Self, FastCryptoError> {
+        [
+            DrandScheme::PedersenBlsChained,
+            DrandScheme::PedersenBlsUnchained,
+            DrandScheme::BlsUnchainedOnG1,
+            DrandScheme::BlsUnchainedG1Rfc9380,
+        ]
+        .into_iter()
+        .find(|scheme| scheme.id() == id)
+        .ok_or(FastCryptoError::InvalidInput)
+    }
+
+    /// True if the message of a round depends on the signature of the previous round.
+    pub fn is_chained(&self) -> bool {
+        *self == DrandScheme::PedersenBlsChained
+    }
+
+    fn signatures_on_g1(&self) -> bool {
+        matches!(
+            self,
+            DrandScheme::BlsUnchainedOnG1 | DrandScheme::BlsUnchainedG1Rfc9380
+        )
+    }
+
+    fn dst(&self) -> &'static [u8] {
+        match self {
+            DrandScheme::BlsUnchainedG1Rfc9380 => DST_G1,
+            _ => DST_G2,
+        }
+    }
+}
+
+/// A single round of a drand network.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DrandBeacon {
+    pub round: u64,
+    #[serde_as(as = "Hex")]
+    pub signature: Vec<u8>,
+    /// The signature of the previous round. Only used by chained schemes.
+    #[serde_as(as = "Option<Hex>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_signature: Option<Vec<u8>>,
+}
+
+impl DrandBeacon {
+    /// The randomness of this round. Only meaningful once the beacon has been verified.
+    pub fn randomness(&self) -> [u8; 32] {
+        Sha256::digest(&self.signature).digest
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum DrandPublicKey {
+    G1(min_pk::BLS12381PublicKey),
+    G2(min_sig::BLS12381PublicKey),
+}
+
+/// The scheme and public key of a drand network, used to verify its beacons.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DrandChain {
+    scheme: DrandScheme,
+    public_key: DrandPublicKey,
+}
+
+impl DrandChain {
+    /// Create a chain from its scheme and the compressed encoding of its public key. Fails if the
+    /// public key is not a valid group element of the group used by the scheme.
+    pub fn new(scheme: DrandScheme, public_key: &[u8]) -> Result<Self, FastCryptoError> {
+        let public_key = if scheme.signatures_on_g1() {
+            let pk = min_sig::BLS12381PublicKey::from_bytes(public_key)?;
+            pk.validate()?;
+            DrandPublicKey::G2(pk)
+        } else {
+            let pk = min_pk::BLS12381PublicKey::from_bytes(public_key)?;
+            pk.validate()?;
+            DrandPublicKey::G1(pk)
+        };
+        Ok(Self { scheme, public_key })
+    }
+
+    pub fn scheme(&self) -> DrandScheme {
+        self.scheme
+    }
+
+    /// Verify a single beacon. Beacons of chained schemes must include the previous signature,
+    /// which is not checked against the previous round. Use [DrandChain::verify_chain] to also
+    /// check the link between rounds.
+    pub fn verify(&self, beacon: &DrandBeacon) -> Result<(), FastCryptoError> {
+        let message = self.message(beacon)?;
+        let dst = self.scheme.dst();
+        let result = match &self.public_key {
+            DrandPublicKey::G1(pk) => min_pk::BLS12381Signature::from_bytes(&beacon.signature)?
+                .sig
+                .verify(true, &message, dst, &[], &pk.pubkey, false),
+            DrandPublicKey::G2(pk) => min_sig::BLS12381Signature::from_bytes(&beacon.signature)?
+                .sig
+                .verify(true, &message, dst, &[], &pk.pubkey, false),
+        };
+        if result == BLST_ERROR::BLST_SUCCESS {
+            Ok(())
+        } else {
+            Err(FastCryptoError::InvalidSignature)
+        }
+    }
+
+    /// Verify a sequence of beacons of consecutive rounds. For chained schemes, the previous
+    /// signature of each beacon except the first must also equal the signature of the beacon
+    /// before it.
+    pub fn verify_chain(&self, beacons: &[DrandBeacon]) -> Result<(), FastCryptoError> {
+        for pair in beacons.windows(2) {
+            if pair[0].round.checked_add(1) != Some(pair[1].round)
+                || (self.scheme.is_chained()
+                    && pair[1].previous_signature.as_ref() != Some(&pair[0].signature))
+            {
+                return Err(FastCryptoError::InvalidInput);
+            }
+        }
+        beacons.iter().try_for_each(|beacon| self.verify(beacon))
+    }
+
+    /// The message signed in the given round.
+    fn message(&self, beacon: &DrandBeacon) -> Result<[u8; 32], FastCryptoError> {
+        // Round zero is the genesis of the network and has no signature.
+        if beacon.round == 0 {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let mut hash = Sha256::default();
+        if self.scheme.is_chained() {
+            hash.update(
+                beacon
+                    .previous_signature
+                    .as_ref()
+                    .ok_or(FastCryptoError::InvalidInput)?,
+            );
+        }
+        hash.update(beacon.round.to_be_bytes());
+        Ok(hash.finalize().digest)
+    }
+}
@@ -13,8 +13,9 @@
 //! let signature = kp.sign(message);
 //! assert!(kp.public().verify(message, &signature).is_ok());
 //! ```
+use crate::hash::{HashFunction, Sha512};
 use crate::serde_helpers::{to_custom_error, BytesRepresentation};
-use crate::traits::{InsecureDefault, Signer};
+use crate::traits::{BatchSigner, InsecureDefault, Signer};
 use crate::{
     encoding::Base64,
     error::FastCryptoError,
@@ -29,6 +30,9 @@ use crate::{
     traits,
 };
 use base64ct::Encoding as _;
+use curve25519_dalek_ng::constants::ED25519_BASEPOINT_TABLE;
+use curve25519_dalek_ng::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek_ng::scalar::Scalar as ExternalScalar;
 use derive_more::AsRef;
 use fastcrypto_derive::{SilentDebug, SilentDisplay};
 use once_cell::sync::OnceCell;
@@ -61,6 +65,16 @@ pub const ED25519_PUBLIC_KEY_LENGTH: usize = 32;
 /// The length of a signature in bytes.
 pub const ED25519_SIGNATURE_LENGTH: usize = 64;
 
+/// The length of an expanded secret key in bytes.
+pub const ED25519_EXPANDED_SECRET_LENGTH: usize = 64;
+
+/// The maximal length of the context of an Ed25519ph signature in bytes.
+pub const ED25519PH_MAX_CONTEXT_LENGTH: usize = 255;
+
+/// The prefix of the domain separation of Ed25519ph, see
+/// [RFC 8032](https://www.rfc-editor.org/rfc/rfc8032#section-2).
+const ED25519PH_DOMAIN: &[u8] = b"SigEd25519 no Ed25519 collisions";
+
 /// The key pair bytes length is the same as the private key length. This enforces deserialization to always derive the public key from the private key.
 pub const ED25519_KEYPAIR_LENGTH: usize = ED25519_PRIVATE_KEY_LENGTH;
 
@@ -81,6 +95,15 @@ pub struct Ed25519KeyPair {
     private: Ed25519PrivateKey,
 }
 
+/// Ed25519 key pair holding the expanded secret key, so signing does not hash the seed again. This
+/// also allows using keys which are only available in expanded form.
+#[derive(SilentDebug)]
+pub struct Ed25519ExpandedKeyPair {
+    public: Ed25519PublicKey,
+    scalar: ExternalScalar,
+    prefix: [u8; 32],
+}
+
 /// Ed25519 signature.
 #[derive(Debug, Clone)]
 pub struct Ed25519Signature {
@@ -89,6 +112,15 @@ pub struct Ed25519Signature {
     pub bytes: OnceCell<[u8; ED25519_SIGNATURE_LENGTH]>,
 }
 
+/// A borrowed view of a single entry in a batch of serialized Ed25519 signatures. See
+/// [Ed25519PublicKey::verify_batch_from_bytes].
+#[derive(Debug, Clone, Copy)]
+pub struct Ed25519BatchItemRef<'a> {
+    pub public_key: &'a [u8; ED25519_PUBLIC_KEY_LENGTH],
+    pub signature: &'a [u8; ED25519_SIGNATURE_LENGTH],
+    pub message: &'a [u8],
+}
+
 /// Aggregation of multiple Ed25519 signatures.
 #[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -204,6 +236,8 @@ impl From<ed25519_consensus::SigningKey> for Ed25519KeyPair {
     }
 }
 
+impl BatchSigner<Ed25519Signature> for Ed25519KeyPair {}
+
 impl Signer<Ed25519Signature> for Ed25519KeyPair {
     fn sign(&self, msg: &[u8]) -> Ed25519Signature {
         Ed25519Signature {
@@ -213,6 +247,95 @@ impl Signer<Ed25519Signature> for Ed25519KeyPair {
     }
 }
 
+//
+// Implementation of [Ed25519ExpandedKeyPair].
+//
+
+impl Ed25519ExpandedKeyPair {
+    /// Construct a key pair from an expanded secret key, which is the SHA-512 hash of the 32 byte
+    /// seed with the first half clamped as described in
+    /// [RFC 8032](https://www.rfc-editor.org/rfc/rfc8032#section-5.1.5). Returns an error if the
+    /// scalar is not clamped.
+    pub fn from_expanded_secret(
+        expanded: &[u8; ED25519_EXPANDED_SECRET_LENGTH],
+    ) -> Result<Self, FastCryptoError> {
+        let mut scalar_bytes = [0u8; 32];
+        scalar_bytes.copy_from_slice(&expanded[..32]);
+        if scalar_bytes[0] & 7 != 0 || scalar_bytes[31] & 0xc0 != 0x40 {
+            scalar_bytes.zeroize();
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let scalar = ExternalScalar::from_bits(scalar_bytes);
+        scalar_bytes.zeroize();
+        let mut prefix = [0u8; 32];
+        prefix.copy_from_slice(&expanded[32..]);
+
+        let public_bytes = (&scalar * &ED25519_BASEPOINT_TABLE).compress().to_bytes();
+        Ok(Self {
+            public: Ed25519PublicKey::from_bytes(&public_bytes)?,
+            scalar,
+            prefix,
+        })
+    }
+
+    /// Get the public key.
+    pub fn public(&self) -> &Ed25519PublicKey {
+        &self.public
+    }
+}
+
+impl From<&Ed25519PrivateKey> for Ed25519ExpandedKeyPair {
+    fn from(private: &Ed25519PrivateKey) -> Self {
+        let mut expanded = Sha512::digest(private.as_ref()).digest;
+        expanded[0] &= 248;
+        expanded[31] &= 127;
+        expanded[31] |= 64;
+        let result = Self::from_expanded_secret(&expanded).expect("The scalar is clamped");
+        expanded.zeroize();
+        result
+    }
+}
+
+impl From<&Ed25519KeyPair> for Ed25519ExpandedKeyPair {
+    fn from(kp: &Ed25519KeyPair) -> Self {
+        Self::from(&kp.private)
+    }
+}
+
+impl Signer<Ed25519Signature> for Ed25519ExpandedKeyPair {
+    fn sign(&self, msg: &[u8]) -> Ed25519Signature {
+        let mut hash = Sha512::new();
+        hash.update(self.prefix);
+        hash.update(msg);
+        let r = ExternalScalar::from_bytes_mod_order_wide(&hash.finalize().digest);
+        let big_r = (&r * &ED25519_BASEPOINT_TABLE).compress().to_bytes();
+
+        let mut hash = Sha512::new();
+        hash.update(big_r);
+        hash.update(self.public.as_ref());
+        hash.update(msg);
+        let k = ExternalScalar::from_bytes_mod_order_wide(&hash.finalize().digest);
+        let s = k * self.scalar + r;
+
+        let mut bytes = [0u8; ED25519_SIGNATURE_LENGTH];
+        bytes[..32].copy_from_slice(&big_r);
+        bytes[32..].copy_from_slice(s.as_bytes());
+        Ed25519Signature {
+            sig: ed25519_consensus::Signature::from(bytes),
+            bytes: OnceCell::new(),
+        }
+    }
+}
+
+impl BatchSigner<Ed25519Signature> for Ed25519ExpandedKeyPair {}
+
+impl Drop for Ed25519ExpandedKeyPair {
+    fn drop(&mut self) {
+        self.scalar.zeroize();
+        self.prefix.zeroize();
+    }
+}
+
 //
 // Implementation of [Ed25519Signature].
 //
@@ -322,6 +445,73 @@ generate_bytes_representation!(
     ED25519_PUBLIC_KEY_LENGTH,
     Ed25519PublicKeyAsBytes
 );
+impl Ed25519PublicKey {
+    /// Verify a batch of signatures given as serialized public keys and signatures, e.g. borrowed
+    /// directly from a network message, without deserializing them into owned types first. Returns
+    /// an error if the batch is empty or if any signature is invalid.
+    #[cfg(any(test, feature = "experimental"))]
+    pub fn verify_batch_from_bytes<'a>(
+        items: impl IntoIterator<Item = Ed25519BatchItemRef<'a>>,
+    ) -> Result<(), FastCryptoError> {
+        let mut batch = batch::Verifier::new();
+        let mut is_empty = true;
+        for item in items {
+            is_empty = false;
+            batch.queue((
+                VerificationKeyBytes::from(*item.public_key),
+                ed25519_consensus::Signature::from(*item.signature),
+                item.message,
+            ));
+        }
+        if is_empty {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        batch
+            .verify(OsRng)
+            .map_err(|_| FastCryptoError::InvalidSignature)
+    }
+
+    /// Verify an Ed25519ph signature as defined in
+    /// [RFC 8032](https://www.rfc-editor.org/rfc/rfc8032#section-5.1), where `prehash` is the
+    /// SHA-512 digest of the message and `context` is at most 255 bytes. Note that Ed25519ph
+    /// signatures are not valid Ed25519 signatures of the same message and vice versa.
+    pub fn verify_prehashed(
+        &self,
+        prehash: &[u8; 64],
+        context: &[u8],
+        signature: &Ed25519Signature,
+    ) -> Result<(), FastCryptoError> {
+        if context.len() > ED25519PH_MAX_CONTEXT_LENGTH {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let signature_bytes = signature.as_ref();
+        let big_r = &signature_bytes[..32];
+        let s = ExternalScalar::from_canonical_bytes(
+            signature_bytes[32..].try_into().expect("Length is 32"),
+        )
+        .ok_or(FastCryptoError::InvalidSignature)?;
+        let a = CompressedEdwardsY(*self.0.as_bytes())
+            .decompress()
+            .ok_or(FastCryptoError::InvalidSignature)?;
+
+        let mut hash = Sha512::new();
+        hash.update(ED25519PH_DOMAIN);
+        hash.update([1, context.len() as u8]);
+        hash.update(context);
+        hash.update(big_r);
+        hash.update(self.0.as_bytes());
+        hash.update(prehash);
+        let k = ExternalScalar::from_bytes_mod_order_wide(&hash.finalize().digest);
+
+        // Check that R = [S]B - [k]A.
+        let expected_r = EdwardsPoint::vartime_double_scalar_mul_basepoint(&k, &-a, &s);
+        if expected_r.compress().as_bytes() != big_r {
+            return Err(FastCryptoError::InvalidSignature);
+        }
+        Ok(())
+    }
+}
+
 impl VerifyingKey for Ed25519PublicKey {
     type PrivKey = Ed25519PrivateKey;
     type Sig = Ed25519Signature;
@@ -514,25 +704,28 @@ impl AggregateAuthenticator for Ed25519AggregateSignature {
             .map_err(|_| FastCryptoError::GeneralOpaqueError)
     }
 
-    fn batch_verify<'a>(
+    fn batch_verify<'a, P, I>(
         sigs: &[&Self],
-        pks: Vec<impl ExactSizeIterator<Item = &'a Self::PubKey>>,
+        pks: P,
         messages: &[&[u8]],
-    ) -> Result<(), FastCryptoError> {
+    ) -> Result<(), FastCryptoError>
+    where
+        P: IntoIterator<Item = I>,
+        P::IntoIter: ExactSizeIterator,
+        I: ExactSizeIterator<Item = &'a Self::PubKey>,
+    {
+        let pks = pks.into_iter();
         if pks.len() != messages.len() || messages.len() != sigs.len() {
             return Err(FastCryptoError::InputLengthWrong(sigs.len()));
         }
         let mut batch = batch::Verifier::new();
 
-        let mut pk_iter = pks.into_iter();
-        for i in 0..sigs.len() {
-            let pk_list = &pk_iter.next().unwrap().map(|x| &x.0).collect::<Vec<_>>()[..];
-            if pk_list.len() != sigs[i].sigs.len() {
+        for ((pk_list, sig), msg) in pks.zip(sigs).zip(messages) {
+            if pk_list.len() != sig.sigs.len() {
                 return Err(FastCryptoError::InvalidInput);
             }
-            for (&pk, sig) in pk_list.iter().zip(&sigs[i].sigs) {
-                let vk_bytes = VerificationKeyBytes::from(*pk);
-                batch.queue((vk_bytes, *sig, messages[i]));
+            for (pk, sig) in pk_list.zip(&sig.sigs) {
+                batch.queue((VerificationKeyBytes::from(pk.0), *sig, *msg));
             }
         }
         batch
@@ -0,0 +1,260 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Standard envelopes for exchanging signed blobs between services.
+//!
+//! A [DetachedSignature] holds a signature and the public key of the signer, and is transmitted
+//! separately from the message. An [AttachedSignature] also carries the message. The encodings are
+//! ```text
+//! detached: version || scheme flag || public key || signature
+//! attached: version || scheme flag || public key || signature || message length || message
+//! ```
+//! where the version is [ENVELOPE_VERSION], the scheme flag is a [SignatureScheme] and the message
+//! length is a big-endian `u64`. Public keys and signatures have the fixed lengths of the scheme.
+//!
+//! Parsing is strict: unknown versions, a flag of another scheme, non-canonical encodings of keys
+//! and signatures, wrong lengths and trailing bytes are all rejected, so every envelope has exactly
+//! one encoding. Use [scheme_of] to find the scheme of an envelope before parsing it.
+//!
+//! Verifying an envelope only shows that the message was signed by the public key in the envelope.
+//! Callers must check that this key is trusted, e.g. with [DetachedSignature::public_key].
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::ed25519::{Ed25519KeyPair, Ed25519PublicKey};
+//! # use fastcrypto::envelope::*;
+//! # use fastcrypto::traits::KeyPair;
+//! # use rand::thread_rng;
+//! let kp = Ed25519KeyPair::generate(&mut thread_rng());
+//! let envelope = AttachedSignature::sign(&kp, b"blob".to_vec());
+//! let bytes = envelope.to_bytes();
+//!
+//! assert_eq!(scheme_of(&bytes).unwrap(), SignatureScheme::Ed25519);
+//! let parsed = AttachedSignature::<Ed25519PublicKey>::from_bytes(&bytes).unwrap();
+//! assert!(parsed.verify().is_ok());
+//! assert_eq!(parsed.message(), b"blob");
+//! assert_eq!(parsed.public_key(), kp.public());
+//! ```
+
+use crate::ed25519::Ed25519PublicKey;
+use crate::error::{FastCryptoError, FastCryptoResult};
+use crate::traits::{Authenticator, KeyPair, Signer, ToFromBytes, VerifyingKey};
+
+/// The version of the encodings of envelopes.
+pub const ENVELOPE_VERSION: u8 = 1;
+
+/// The length of the version and the scheme flag.
+const HEADER_LENGTH: usize = 2;
+
+/// The length of the message length of an attached signature.
+const MESSAGE_LENGTH_LENGTH: usize = 8;
+
+/// The signature schemes supported in envelopes, and their flags.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SignatureScheme {
+    Ed25519 = 0x00,
+    Secp256k1 = 0x01,
+    Secp256r1 = 0x02,
+    BLS12381MinSig = 0x03,
+    BLS12381MinPk = 0x04,
+}
+
+impl SignatureScheme {
+    /// The flag of this scheme.
+    pub fn flag(&self) -> u8 {
+        *self as u8
+    }
+
+    /// The scheme with the given flag.
+    pub fn from_flag(flag: u8) -> FastCryptoResult<Self> {
+        match flag {
+            0x00 => Ok(SignatureScheme::Ed25519),
+            0x01 => Ok(SignatureScheme::Secp256k1),
+            0x02 => Ok(SignatureScheme::Secp256r1),
+            0x03 => Ok(SignatureScheme::BLS12381MinSig),
+            0x04 => Ok(SignatureScheme::BLS12381MinPk),
+            _ => Err(FastCryptoError::InvalidInput),
+        }
+    }
+}
+
+/// A public key of a scheme which can be used in envelopes.
+pub trait EnvelopeScheme: VerifyingKey {
+    const SCHEME: SignatureScheme;
+}
+
+impl EnvelopeScheme for Ed25519PublicKey {
+    const SCHEME: SignatureScheme = SignatureScheme::Ed25519;
+}
+
+#[cfg(feature = "secp256k1")]
+impl EnvelopeScheme for crate::secp256k1::Secp256k1PublicKey {
+    const SCHEME: SignatureScheme = SignatureScheme::Secp256k1;
+}
+
+#[cfg(feature = "secp256r1")]
+impl EnvelopeScheme for crate::secp256r1::Secp256r1PublicKey {
+    const SCHEME: SignatureScheme = SignatureScheme::Secp256r1;
+}
+
+#[cfg(feature = "bls12381")]
+impl EnvelopeScheme for crate::bls12381::min_sig::BLS12381PublicKey {
+    const SCHEME: SignatureScheme = SignatureScheme::BLS12381MinSig;
+}
+
+#[cfg(feature = "bls12381")]
+impl EnvelopeScheme for crate::bls12381::min_pk::BLS12381PublicKey {
+    const SCHEME: SignatureScheme = SignatureScheme::BLS12381MinPk;
+}
+
+/// The scheme of an encoded envelope of either type. Fails if the version or the flag is unknown.
+pub fn scheme_of(bytes: &[u8]) -> FastCryptoResult<SignatureScheme> {
+    match bytes {
+        [ENVELOPE_VERSION, flag, ..] => SignatureScheme::from_flag(*flag),
+        [_, _, ..] => Err(FastCryptoError::InvalidInput),
+        _ => Err(FastCryptoError::InputTooShort(HEADER_LENGTH)),
+    }
+}
+
+/// A signature which is transmitted separately from the message, together with the public key of
+/// the signer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DetachedSignature<K: EnvelopeScheme> {
+    public_key: K,
+    signature: K::Sig,
+}
+
+impl<K: EnvelopeScheme> DetachedSignature<K> {
+    /// The length of the encoding of a detached signature of this scheme.
+    pub const LENGTH: usize = HEADER_LENGTH + K::LENGTH + <K::Sig as Authenticator>::LENGTH;
+
+    pub fn new(public_key: K, signature: K::Sig) -> Self {
+        Self {
+            public_key,
+            signature,
+        }
+    }
+
+    /// Sign a message with the given key pair.
+    pub fn sign<KP: KeyPair<PubKey = K, Sig = K::Sig>>(key_pair: &KP, message: &[u8]) -> Self {
+        Self::new(key_pair.public().clone(), key_pair.sign(message))
+    }
+
+    pub fn public_key(&self) -> &K {
+        &self.public_key
+    }
+
+    pub fn signature(&self) -> &K::Sig {
+        &self.signature
+    }
+
+    /// Verify the signature over the given message with the public key of this envelope.
+    pub fn verify(&self, message: &[u8]) -> FastCryptoResult<()> {
+        self.public_key.verify(message, &self.signature)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::LENGTH);
+        bytes.extend_from_slice(&[ENVELOPE_VERSION, K::SCHEME.flag()]);
+        bytes.extend_from_slice(self.public_key.as_ref());
+        bytes.extend_from_slice(self.signature.as_ref());
+        bytes
+    }
+
+    /// Parse a detached signature encoded with [DetachedSignature::to_bytes].
+    pub fn from_bytes(bytes: &[u8]) -> FastCryptoResult<Self> {
+        if bytes.len() != Self::LENGTH {
+            return Err(FastCryptoError::InputLengthWrong(Self::LENGTH));
+        }
+        Self::parse(bytes)
+    }
+
+    /// Parse the prefix of an encoding of length [DetachedSignature::LENGTH].
+    fn parse(bytes: &[u8]) -> FastCryptoResult<Self> {
+        if scheme_of(bytes)? != K::SCHEME {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let (public_key, signature) = bytes[HEADER_LENGTH..Self::LENGTH].split_at(K::LENGTH);
+        Ok(Self::new(
+            canonical_from_bytes(public_key)?,
+            canonical_from_bytes(signature)?,
+        ))
+    }
+}
+
+/// A signature together with the signed message and the public key of the signer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AttachedSignature<K: EnvelopeScheme> {
+    detached: DetachedSignature<K>,
+    message: Vec<u8>,
+}
+
+impl<K: EnvelopeScheme> AttachedSignature<K> {
+    pub fn new(detached: DetachedSignature<K>, message: Vec<u8>) -> Self {
+        Self { detached, message }
+    }
+
+    /// Sign a message with the given key pair.
+    pub fn sign<KP: KeyPair<PubKey = K, Sig = K::Sig>>(key_pair: &KP, message: Vec<u8>) -> Self {
+        Self::new(DetachedSignature::sign(key_pair, &message), message)
+    }
+
+    pub fn public_key(&self) -> &K {
+        self.detached.public_key()
+    }
+
+    pub fn signature(&self) -> &K::Sig {
+        self.detached.signature()
+    }
+
+    pub fn message(&self) -> &[u8] {
+        &self.message
+    }
+
+    /// Split this envelope into the detached signature and the message.
+    pub fn into_parts(self) -> (DetachedSignature<K>, Vec<u8>) {
+        (self.detached, self.message)
+    }
+
+    /// Verify the signature over the attached message with the public key of this envelope.
+    pub fn verify(&self) -> FastCryptoResult<()> {
+        self.detached.verify(&self.message)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.detached.to_bytes();
+        bytes.extend_from_slice(&(self.message.len() as u64).to_be_bytes());
+        bytes.extend_from_slice(&self.message);
+        bytes
+    }
+
+    /// Parse an attached signature encoded with [AttachedSignature::to_bytes].
+    pub fn from_bytes(bytes: &[u8]) -> FastCryptoResult<Self> {
+        let message_start = DetachedSignature::<K>::LENGTH + MESSAGE_LENGTH_LENGTH;
+        if bytes.len() < message_start {
+            return Err(FastCryptoError::InputTooShort(message_start));
+        }
+        let detached = DetachedSignature::parse(bytes)?;
+        let message_length = u64::from_be_bytes(
+            bytes[DetachedSignature::<K>::LENGTH..message_start]
+                .try_into()
+                .expect("Length is 8"),
+        );
+        let message = &bytes[message_start..];
+        if message_length != message.len() as u64 {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(Self::new(detached, message.to_vec()))
+    }
+}
+
+/// Parse a key or signature, rejecting encodings which are not the canonical encoding of the
+/// parsed value.
+fn canonical_from_bytes<T: ToFromBytes>(bytes: &[u8]) -> FastCryptoResult<T> {
+    let value = T::from_bytes(bytes)?;
+    if value.as_ref() != bytes {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    Ok(value)
+}
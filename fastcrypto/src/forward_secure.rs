@@ -0,0 +1,270 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A forward-secure signature scheme based on Ed25519 using the sum composition of
+//! [Malkin, Micciancio and Miner](https://cseweb.ucsd.edu/~daniele/papers/MMM.pdf).
+//!
+//! The lifetime of a key is divided into `2^depth` periods (e.g. epochs). Each period has its own
+//! Ed25519 key pair, and the key pairs are the leaves of a binary Merkle tree whose root is the
+//! public key. The key pairs are derived from a seed, where the seed of a node is expanded into the
+//! seeds of its children. The signing key of a period holds the leaf key pair of the period and the
+//! seeds of the right siblings along the path from the leaf to the root, which are needed for later
+//! periods. Evolving the key to the next period deletes the current leaf key pair and all seeds
+//! which are no longer needed, so a compromise of the signing key does not allow forging signatures
+//! for past periods.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::forward_secure::*;
+//! # use rand::thread_rng;
+//! let mut sk = ForwardSecureSigningKey::generate(4, &mut thread_rng()).unwrap();
+//! let pk = sk.public_key().clone();
+//! let signature = sk.sign(b"message in period 0");
+//! sk.evolve().unwrap();
+//! assert_eq!(sk.period(), 1);
+//! assert!(pk.verify(b"message in period 0", &signature).is_ok());
+//! assert_eq!(signature.period(), 0);
+//! ```
+
+use crate::ed25519::{Ed25519KeyPair, Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature};
+use crate::error::FastCryptoError;
+use crate::hash::{HashFunction, Sha256};
+use crate::traits::{AllowedRng, KeyPair, Signer, ToFromBytes, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+/// The maximal depth of the tree, which allows up to ~one million periods.
+pub const MAX_DEPTH: u8 = 20;
+
+const SEED_LENGTH: usize = 32;
+const NODE_DST: &[u8] = b"fastcrypto-forward-secure-node";
+const SEED_DST: &[u8] = b"fastcrypto-forward-secure-seed";
+
+type Seed = [u8; SEED_LENGTH];
+type Node = [u8; 32];
+
+/// A public key, which is valid for `2^depth` periods.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForwardSecurePublicKey {
+    depth: u8,
+    root: Node,
+}
+
+/// A signature for a given period.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForwardSecureSignature {
+    period: u32,
+    signature: Ed25519Signature,
+    leaf: Ed25519PublicKey,
+    /// The siblings of the nodes on the path from the leaf to the root, starting at the leaf.
+    authentication_path: Vec<Node>,
+}
+
+/// The signing key of a given period.
+pub struct ForwardSecureSigningKey {
+    public_key: ForwardSecurePublicKey,
+    period: u32,
+    leaf: Ed25519KeyPair,
+    /// For each level of the tree, starting at the root, the children of the node on the path to
+    /// the current leaf.
+    path: Vec<(Node, Node)>,
+    /// For each level of the tree, starting at the root, the seed of the right child if the path to
+    /// the current leaf goes to the left child.
+    right_seeds: Vec<Option<Seed>>,
+}
+
+impl ForwardSecureSigningKey {
+    /// Generate a new key for `2^depth` periods. The depth must be between 1 and [MAX_DEPTH].
+    pub fn generate<R: AllowedRng>(depth: u8, rng: &mut R) -> Result<Self, FastCryptoError> {
+        let mut seed = [0u8; SEED_LENGTH];
+        rng.fill_bytes(&mut seed);
+        Self::from_seed(depth, seed)
+    }
+
+    /// Derive a key for `2^depth` periods from a seed. The depth must be between 1 and
+    /// [MAX_DEPTH].
+    pub fn from_seed(depth: u8, mut seed: Seed) -> Result<Self, FastCryptoError> {
+        if depth == 0 || depth > MAX_DEPTH {
+            seed.zeroize();
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let root = subtree_root(&seed, depth as usize);
+        let mut key = Self {
+            public_key: ForwardSecurePublicKey { depth, root },
+            period: 0,
+            leaf: leaf_key_pair(&[0u8; SEED_LENGTH]),
+            path: vec![([0u8; 32], [0u8; 32]); depth as usize],
+            right_seeds: vec![None; depth as usize],
+        };
+        key.descend(seed, 0);
+        Ok(key)
+    }
+
+    /// The public key, which is the same for all periods.
+    pub fn public_key(&self) -> &ForwardSecurePublicKey {
+        &self.public_key
+    }
+
+    /// The current period.
+    pub fn period(&self) -> u32 {
+        self.period
+    }
+
+    /// Sign a message in the current period.
+    pub fn sign(&self, msg: &[u8]) -> ForwardSecureSignature {
+        let depth = self.path.len();
+        let authentication_path = (0..depth)
+            .rev()
+            .map(|level| {
+                let (left, right) = self.path[level];
+                match bit(self.period, depth, level) {
+                    false => right,
+                    true => left,
+                }
+            })
+            .collect();
+        ForwardSecureSignature {
+            period: self.period,
+            signature: self.leaf.sign(msg),
+            leaf: self.leaf.public().clone(),
+            authentication_path,
+        }
+    }
+
+    /// Evolve the key to the next period, deleting the key material of the current period. Fails
+    /// if this is the last period.
+    pub fn evolve(&mut self) -> Result<(), FastCryptoError> {
+        // The deepest level where the path goes left is where the path to the next leaf diverges.
+        let level = self
+            .right_seeds
+            .iter()
+            .rposition(|seed| seed.is_some())
+            .ok_or(FastCryptoError::InvalidInput)?;
+        let seed = self.right_seeds[level].take().expect("Seed is present");
+        self.descend(seed, level + 1);
+        self.period += 1;
+        Ok(())
+    }
+
+    /// Evolve the key to the given future period. Fails if the period is not after the current
+    /// period or is out of range.
+    pub fn evolve_to(&mut self, period: u32) -> Result<(), FastCryptoError> {
+        if period <= self.period || period as u64 >= 1 << self.public_key.depth {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        while self.period < period {
+            self.evolve()?;
+        }
+        Ok(())
+    }
+
+    /// Take the leftmost path down from the node at the given level with the given seed, updating
+    /// the path and seeds below it and setting the leaf key pair.
+    fn descend(&mut self, mut seed: Seed, start: usize) {
+        let depth = self.path.len();
+        for level in start..depth {
+            let (mut left, right) = expand_seed(&seed);
+            let height = depth - level - 1;
+            self.path[level] = (subtree_root(&left, height), subtree_root(&right, height));
+            if let Some(mut old) = self.right_seeds[level].replace(right) {
+                old.zeroize();
+            }
+            seed.copy_from_slice(&left);
+            left.zeroize();
+        }
+        self.leaf = leaf_key_pair(&seed);
+        seed.zeroize();
+    }
+}
+
+impl Drop for ForwardSecureSigningKey {
+    fn drop(&mut self) {
+        self.right_seeds
+            .iter_mut()
+            .flatten()
+            .for_each(|s| s.zeroize());
+    }
+}
+
+impl ForwardSecurePublicKey {
+    /// The number of periods this key is valid for.
+    pub fn number_of_periods(&self) -> u64 {
+        1 << self.depth
+    }
+
+    /// Verify a signature. The period of the signature is given by [ForwardSecureSignature::period].
+    pub fn verify(
+        &self,
+        msg: &[u8],
+        signature: &ForwardSecureSignature,
+    ) -> Result<(), FastCryptoError> {
+        let depth = self.depth as usize;
+        if signature.authentication_path.len() != depth
+            || signature.period as u64 >= self.number_of_periods()
+        {
+            return Err(FastCryptoError::InvalidSignature);
+        }
+        signature.leaf.verify(msg, &signature.signature)?;
+
+        let mut node = leaf_node(&signature.leaf);
+        for (sibling, level) in signature.authentication_path.iter().zip((0..depth).rev()) {
+            node = match bit(signature.period, depth, level) {
+                false => node_hash(&node, sibling),
+                true => node_hash(sibling, &node),
+            };
+        }
+        if node != self.root {
+            return Err(FastCryptoError::InvalidSignature);
+        }
+        Ok(())
+    }
+}
+
+impl ForwardSecureSignature {
+    /// The period in which this signature was created.
+    pub fn period(&self) -> u32 {
+        self.period
+    }
+}
+
+/// Whether the path to the leaf of the given period goes to the right child at the given level.
+fn bit(period: u32, depth: usize, level: usize) -> bool {
+    (period >> (depth - level - 1)) & 1 == 1
+}
+
+fn expand_seed(seed: &Seed) -> (Seed, Seed) {
+    let expand = |i: u8| {
+        let parts: [&[u8]; 3] = [SEED_DST, &[i], seed];
+        Sha256::digest_iterator(parts.into_iter()).digest
+    };
+    (expand(0), expand(1))
+}
+
+fn leaf_key_pair(seed: &Seed) -> Ed25519KeyPair {
+    Ed25519KeyPair::from(Ed25519PrivateKey::from_bytes(seed).expect("Seed has the right length"))
+}
+
+fn leaf_node(pk: &Ed25519PublicKey) -> Node {
+    let parts: [&[u8]; 3] = [NODE_DST, &[0], pk.as_ref()];
+    Sha256::digest_iterator(parts.into_iter()).digest
+}
+
+fn node_hash(left: &Node, right: &Node) -> Node {
+    let parts: [&[u8]; 4] = [NODE_DST, &[1], left, right];
+    Sha256::digest_iterator(parts.into_iter()).digest
+}
+
+/// Compute the root of the subtree of the given height with the given seed.
+fn subtree_root(seed: &Seed, height: usize) -> Node {
+    if height == 0 {
+        return leaf_node(leaf_key_pair(seed).public());
+    }
+    let (mut left, mut right) = expand_seed(seed);
+    let root = node_hash(
+        &subtree_root(&left, height - 1),
+        &subtree_root(&right, height - 1),
+    );
+    left.zeroize();
+    right.zeroize();
+    root
+}
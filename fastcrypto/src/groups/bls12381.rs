@@ -4,25 +4,30 @@
 use crate::bls12381::min_pk::DST_G2;
 use crate::bls12381::min_sig::DST_G1;
 use crate::error::FastCryptoError;
-use crate::groups::{GroupElement, HashToGroupElement, Pairing, Scalar as ScalarType};
+use crate::groups::fixed_base::{ConditionallyAssignable, FixedBaseTable};
+use crate::groups::{
+    FixedBaseMultiplication, GroupElement, HashToGroupElement, Pairing, Scalar as ScalarType,
+};
 use crate::serde_helpers::BytesRepresentation;
 use crate::serde_helpers::ToFromByteArray;
 use crate::traits::AllowedRng;
 use crate::utils::log2_byte;
 use crate::{generate_bytes_representation, serialize_deserialize_with_to_from_byte_array};
 use blst::{
-    blst_bendian_from_scalar, blst_final_exp, blst_fp12, blst_fp12_inverse, blst_fp12_mul,
-    blst_fp12_one, blst_fp12_sqr, blst_fr, blst_fr_add, blst_fr_cneg, blst_fr_from_scalar,
-    blst_fr_inverse, blst_fr_mul, blst_fr_rshift, blst_fr_sub, blst_hash_to_g1, blst_hash_to_g2,
-    blst_lendian_from_scalar, blst_miller_loop, blst_p1, blst_p1_add_or_double, blst_p1_affine,
-    blst_p1_cneg, blst_p1_compress, blst_p1_deserialize, blst_p1_from_affine, blst_p1_in_g1,
-    blst_p1_mult, blst_p1_to_affine, blst_p2, blst_p2_add_or_double, blst_p2_affine, blst_p2_cneg,
-    blst_p2_compress, blst_p2_deserialize, blst_p2_from_affine, blst_p2_in_g2, blst_p2_mult,
-    blst_p2_to_affine, blst_scalar, blst_scalar_from_bendian, blst_scalar_from_fr,
-    blst_scalar_from_lendian, Pairing as BlstPairing, BLS12_381_G1, BLS12_381_G2, BLST_ERROR,
+    blst_bendian_from_scalar, blst_final_exp, blst_fp, blst_fp12, blst_fp12_inverse, blst_fp12_mul,
+    blst_fp12_one, blst_fp12_sqr, blst_fp2, blst_fr, blst_fr_add, blst_fr_cneg,
+    blst_fr_from_scalar, blst_fr_inverse, blst_fr_mul, blst_fr_rshift, blst_fr_sub,
+    blst_hash_to_g1, blst_hash_to_g2, blst_lendian_from_scalar, blst_miller_loop, blst_p1,
+    blst_p1_add_or_double, blst_p1_affine, blst_p1_cneg, blst_p1_compress, blst_p1_deserialize,
+    blst_p1_from_affine, blst_p1_in_g1, blst_p1_is_inf, blst_p1_mult, blst_p1_to_affine, blst_p2,
+    blst_p2_add_or_double, blst_p2_affine, blst_p2_cneg, blst_p2_compress, blst_p2_deserialize,
+    blst_p2_from_affine, blst_p2_in_g2, blst_p2_is_inf, blst_p2_mult, blst_p2_to_affine,
+    blst_scalar, blst_scalar_from_bendian, blst_scalar_from_fr, blst_scalar_from_lendian, limb_t,
+    Pairing as BlstPairing, BLS12_381_G1, BLS12_381_G2, BLST_ERROR,
 };
 use derive_more::From;
 use fastcrypto_derive::GroupOpsExtend;
+use once_cell::sync::Lazy;
 use serde::{de, Deserialize};
 use std::ops::{Add, Div, Mul, Neg, Sub};
 use std::ptr;
@@ -147,6 +152,23 @@ impl GroupElement for G1Element {
     }
 }
 
+static G1_GENERATOR_TABLE: Lazy<FixedBaseTable<G1Element>> =
+    Lazy::new(|| FixedBaseTable::new(G1Element::generator()));
+
+impl FixedBaseMultiplication for G1Element {
+    fn mul_generator(scalar: &Scalar) -> Self {
+        G1_GENERATOR_TABLE.mul(&scalar.to_little_endian_bytes())
+    }
+}
+
+impl ConditionallyAssignable for G1Element {
+    fn conditional_assign(&mut self, other: &Self, choice: u64) {
+        conditional_assign_fp(&mut self.0.x, &other.0.x, choice);
+        conditional_assign_fp(&mut self.0.y, &other.0.y, choice);
+        conditional_assign_fp(&mut self.0.z, &other.0.z, choice);
+    }
+}
+
 impl Pairing for G1Element {
     type Other = G2Element;
     type Output = GTElement;
@@ -163,6 +185,39 @@ impl Pairing for G1Element {
         }
         <Self as Pairing>::Output::from(res)
     }
+
+    /// Compute the Miller loops of all pairs and a single final exponentiation.
+    fn multi_pairing(
+        points_g1: &[Self],
+        points_g2: &[Self::Other],
+    ) -> Result<<Self as Pairing>::Output, FastCryptoError> {
+        if points_g1.len() != points_g2.len() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let dst = [0u8; 3];
+        let mut pairing_blst = BlstPairing::new(false, &dst);
+        let mut is_empty = true;
+        for (a, b) in points_g1.iter().zip(points_g2) {
+            // Pairs with the identity contribute nothing.
+            if unsafe { blst_p1_is_inf(&a.0) || blst_p2_is_inf(&b.0) } {
+                continue;
+            }
+            is_empty = false;
+            let mut a_affine = blst_p1_affine::default();
+            let mut b_affine = blst_p2_affine::default();
+            unsafe {
+                blst_p1_to_affine(&mut a_affine, &a.0);
+                blst_p2_to_affine(&mut b_affine, &b.0);
+            }
+            pairing_blst.raw_aggregate(&b_affine, &a_affine);
+        }
+        if is_empty {
+            return Ok(GTElement::zero());
+        }
+        Ok(<Self as Pairing>::Output::from(
+            pairing_blst.as_fp12().final_exp(),
+        ))
+    }
 }
 
 impl HashToGroupElement for G1Element {
@@ -295,6 +350,23 @@ impl GroupElement for G2Element {
     }
 }
 
+static G2_GENERATOR_TABLE: Lazy<FixedBaseTable<G2Element>> =
+    Lazy::new(|| FixedBaseTable::new(G2Element::generator()));
+
+impl FixedBaseMultiplication for G2Element {
+    fn mul_generator(scalar: &Scalar) -> Self {
+        G2_GENERATOR_TABLE.mul(&scalar.to_little_endian_bytes())
+    }
+}
+
+impl ConditionallyAssignable for G2Element {
+    fn conditional_assign(&mut self, other: &Self, choice: u64) {
+        conditional_assign_fp2(&mut self.0.x, &other.0.x, choice);
+        conditional_assign_fp2(&mut self.0.y, &other.0.y, choice);
+        conditional_assign_fp2(&mut self.0.z, &other.0.z, choice);
+    }
+}
+
 impl HashToGroupElement for G2Element {
     fn hash_to_group_element(msg: &[u8]) -> Self {
         let mut res = blst_p2::default();
@@ -557,6 +629,33 @@ impl ToFromByteArray<SCALAR_LENGTH> for Scalar {
 }
 serialize_deserialize_with_to_from_byte_array!(Scalar);
 
+impl Scalar {
+    /// The canonical little-endian representation of this scalar.
+    fn to_little_endian_bytes(self) -> [u8; SCALAR_LENGTH] {
+        let mut bytes = [0u8; SCALAR_LENGTH];
+        unsafe {
+            let mut scalar = blst_scalar::default();
+            blst_scalar_from_fr(&mut scalar, &self.0);
+            blst_lendian_from_scalar(bytes.as_mut_ptr(), &scalar);
+        }
+        bytes
+    }
+}
+
+/// Set `a` to `b` in constant time if `choice == 1`.
+fn conditional_assign_fp(a: &mut blst_fp, b: &blst_fp, choice: u64) {
+    let mask = limb_t::from(choice == 1).wrapping_neg();
+    a.l.iter_mut()
+        .zip(b.l.iter())
+        .for_each(|(a, b)| *a ^= mask & (*a ^ *b));
+}
+
+fn conditional_assign_fp2(a: &mut blst_fp2, b: &blst_fp2, choice: u64) {
+    a.fp.iter_mut()
+        .zip(b.fp.iter())
+        .for_each(|(a, b)| conditional_assign_fp(a, b, choice));
+}
+
 pub(crate) fn is_odd(value: &blst_fr) -> bool {
     let odd: bool;
     unsafe {
@@ -569,7 +668,9 @@ pub(crate) fn is_odd(value: &blst_fr) -> bool {
     odd
 }
 
-/// This represents the multiplicative unit scalar
+/// This represents the multiplicative unit scalar. Scalars are stored in Montgomery form using
+/// 64-bit limbs on 64-bit targets and 32-bit limbs otherwise.
+#[cfg(target_pointer_width = "64")]
 const BLST_FR_ONE: blst_fr = blst_fr {
     l: [
         8589934590,
@@ -578,3 +679,10 @@ const BLST_FR_ONE: blst_fr = blst_fr {
         1739710354780652911,
     ],
 };
+
+#[cfg(not(target_pointer_width = "64"))]
+const BLST_FR_ONE: blst_fr = blst_fr {
+    l: [
+        4294967294, 1, 215042, 1485092858, 3971764213, 2576109551, 2898593135, 405057881,
+    ],
+};
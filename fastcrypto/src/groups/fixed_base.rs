@@ -0,0 +1,87 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Precomputed tables for fast multiplication of a fixed base point.
+//!
+//! A scalar `k < 2^255` is written in signed radix 16 as `k = sum_i k_i 16^i` with
+//! `-8 <= k_i <= 8`, and the table holds `j * 16^i * B` for `1 <= j <= 8` and all 64 windows `i`.
+//! The product `k * B` is then the sum of one table entry per window, which requires 64 additions
+//! and no doublings. Entries are selected in constant time by scanning all entries of a window.
+
+use crate::groups::GroupElement;
+
+/// Number of 4-bit windows in a 256-bit scalar.
+const WINDOWS: usize = 64;
+
+/// Number of precomputed multiples per window.
+const ENTRIES: usize = 8;
+
+/// Trait impl'd by group elements that can be conditionally assigned in constant time.
+pub(crate) trait ConditionallyAssignable {
+    /// Set `self` to `other` if `choice == 1` and leave it unchanged if `choice == 0`.
+    fn conditional_assign(&mut self, other: &Self, choice: u64);
+}
+
+/// A table of multiples of a fixed base point.
+pub(crate) struct FixedBaseTable<G>(Vec<[G; ENTRIES]>);
+
+impl<G: GroupElement + ConditionallyAssignable> FixedBaseTable<G> {
+    /// Precompute the table for the given base point.
+    pub(crate) fn new(base: G) -> Self {
+        let mut table = Vec::with_capacity(WINDOWS);
+        let mut window_base = base;
+        for _ in 0..WINDOWS {
+            let mut row = [window_base; ENTRIES];
+            let mut multiple = window_base;
+            for entry in row.iter_mut().skip(1) {
+                multiple = multiple + window_base;
+                *entry = multiple;
+            }
+            window_base = multiple + window_base;
+            table.push(row);
+        }
+        Self(table)
+    }
+
+    /// Compute `k * base` where `k < 2^255` is given as little-endian bytes.
+    pub(crate) fn mul(&self, scalar: &[u8; 32]) -> G {
+        let digits = signed_radix_16(scalar);
+        self.0
+            .iter()
+            .zip(digits.iter())
+            .fold(G::zero(), |acc, (row, digit)| acc + select(row, *digit))
+    }
+}
+
+/// Return `digit * row[0]` in constant time, for `-8 <= digit <= 8`.
+fn select<G: GroupElement + ConditionallyAssignable>(row: &[G; ENTRIES], digit: i8) -> G {
+    let negative = (digit as u8) >> 7;
+    let absolute = ((digit as i16) * (1 - 2 * negative as i16)) as u8;
+
+    let mut result = G::zero();
+    for (j, entry) in row.iter().enumerate() {
+        // The difference is zero if and only if this is the entry we need.
+        let difference = (absolute ^ (j as u8 + 1)) as u64;
+        result.conditional_assign(entry, difference.wrapping_sub(1) >> 63);
+    }
+    let negated = -result;
+    result.conditional_assign(&negated, negative as u64);
+    result
+}
+
+/// Write `k = sum_i k_i 16^i` with `-8 <= k_i < 8` for `i < 63` and `0 <= k_63 <= 8`. Requires that
+/// `k < 2^255`.
+fn signed_radix_16(bytes: &[u8; 32]) -> [i8; WINDOWS] {
+    debug_assert!(bytes[31] < 128);
+    let mut digits = [0i8; WINDOWS];
+    for (i, byte) in bytes.iter().enumerate() {
+        digits[2 * i] = (byte & 15) as i8;
+        digits[2 * i + 1] = (byte >> 4) as i8;
+    }
+    for i in 0..WINDOWS - 1 {
+        let carry = (digits[i] + 8) >> 4;
+        digits[i] -= carry << 4;
+        digits[i + 1] += carry;
+    }
+    digits
+}
@@ -7,11 +7,24 @@ use core::ops::{Add, Div, Mul, Neg, Sub};
 use std::fmt::Debug;
 use std::ops::{AddAssign, SubAssign};
 
-#[cfg(any(test, feature = "experimental"))]
+#[cfg(all(feature = "bls12381", any(test, feature = "experimental")))]
 pub mod bls12381;
 
+#[cfg(all(feature = "bls12381", any(test, feature = "experimental")))]
+pub(crate) mod fixed_base;
+
+#[cfg(any(test, feature = "experimental"))]
+pub mod pedersen;
+
+#[cfg(not(feature = "fips"))]
 pub mod ristretto255;
 
+#[cfg(all(feature = "secp256k1", any(test, feature = "experimental")))]
+pub mod secp256k1;
+
+#[cfg(all(feature = "secp256r1", any(test, feature = "experimental")))]
+pub mod secp256r1;
+
 /// Trait impl'd by elements of an additive cyclic group.
 pub trait GroupElement:
     Copy
@@ -51,11 +64,36 @@ pub trait Scalar:
     fn rand<R: AllowedRng>(rng: &mut R) -> Self;
 }
 
+/// Trait impl'd by groups with a faster method of multiplying the generator than
+/// `Self::generator() * scalar`, typically using a precomputed table of multiples of the generator.
+pub trait FixedBaseMultiplication: GroupElement {
+    /// Compute `Self::generator() * scalar`. The running time does not depend on the scalar.
+    fn mul_generator(scalar: &Self::ScalarType) -> Self;
+}
+
 pub trait Pairing: GroupElement {
     type Other: GroupElement;
-    type Output;
+    type Output: GroupElement;
 
     fn pairing(&self, other: &Self::Other) -> <Self as Pairing>::Output;
+
+    /// Compute the sum of the pairings `e(points_g1[i], points_g2[i])`. Implementations may share
+    /// work between the pairings, e.g. the final exponentiation. Fails if the inputs have different
+    /// lengths.
+    fn multi_pairing(
+        points_g1: &[Self],
+        points_g2: &[Self::Other],
+    ) -> Result<<Self as Pairing>::Output, FastCryptoError> {
+        if points_g1.len() != points_g2.len() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(points_g1
+            .iter()
+            .zip(points_g2)
+            .fold(<Self as Pairing>::Output::zero(), |acc, (a, b)| {
+                acc + a.pairing(b)
+            }))
+    }
 }
 
 /// Trait for groups that have a standardized "hash_to_point"/"hash_to_curve" function (see
@@ -0,0 +1,143 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Pedersen commitments `C = v G + r H` to scalars `v` over any group with a hash-to-group
+//! function, e.g. Ristretto255 and BLS12-381 G1. `G` is the generator of the group, and the
+//! blinding generator `H` is derived by hashing a domain, so nobody knows the discrete logarithm of
+//! `H` with respect to `G`.
+//!
+//! The commitments are perfectly hiding, binding under the discrete logarithm assumption, and
+//! additively homomorphic: The sum of commitments to `v_1` and `v_2` with blindings `r_1` and `r_2`
+//! is a commitment to `v_1 + v_2` with blinding `r_1 + r_2`.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::groups::pedersen::*;
+//! # use fastcrypto::groups::ristretto255::{RistrettoPoint, RistrettoScalar};
+//! # use rand::thread_rng;
+//! let parameters = PedersenParameters::<RistrettoPoint>::new(b"my-domain");
+//! let (c1, r1) =
+//!     PedersenCommitment::commit_random(&parameters, &RistrettoScalar::from(3), &mut thread_rng());
+//! let (c2, r2) =
+//!     PedersenCommitment::commit_random(&parameters, &RistrettoScalar::from(5), &mut thread_rng());
+//! assert!(c1.open(&parameters, &RistrettoScalar::from(3), &r1).is_ok());
+//! assert!((c1 + c2)
+//!     .open(&parameters, &RistrettoScalar::from(8), &(r1 + r2))
+//!     .is_ok());
+//! ```
+
+use crate::error::{FastCryptoError, FastCryptoResult};
+use crate::groups::{GroupElement, HashToGroupElement, Scalar};
+use crate::traits::AllowedRng;
+use serde::{Deserialize, Serialize};
+use std::ops::{Add, Neg, Sub};
+
+/// Domain separation tag for the derivation of blinding generators.
+const BLINDING_GENERATOR_DST: &[u8] = b"fastcrypto-pedersen-blinding-generator-v1";
+
+/// The generators `G` and `H` of Pedersen commitments.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PedersenParameters<G> {
+    generator: G,
+    blinding_generator: G,
+}
+
+/// A Pedersen commitment to a scalar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PedersenCommitment<G>(G);
+
+impl<G: GroupElement + HashToGroupElement> PedersenParameters<G> {
+    /// The parameters whose blinding generator is derived from the given domain. Commitments with
+    /// parameters from different domains are unrelated.
+    pub fn new(domain: &[u8]) -> Self {
+        Self {
+            generator: G::generator(),
+            blinding_generator: G::hash_to_group_element(
+                &[BLINDING_GENERATOR_DST, domain].concat(),
+            ),
+        }
+    }
+}
+
+impl<G: GroupElement> PedersenParameters<G> {
+    /// The generator `G` of the group, which is multiplied by the committed value.
+    pub fn generator(&self) -> &G {
+        &self.generator
+    }
+
+    /// The generator `H`, which is multiplied by the blinding factor.
+    pub fn blinding_generator(&self) -> &G {
+        &self.blinding_generator
+    }
+}
+
+impl<G: GroupElement> PedersenCommitment<G> {
+    /// Commit to a value with the given blinding factor, which must be uniformly random and secret
+    /// for the commitment to be hiding.
+    pub fn commit(
+        parameters: &PedersenParameters<G>,
+        value: &G::ScalarType,
+        blinding: &G::ScalarType,
+    ) -> Self {
+        Self(parameters.generator * value + parameters.blinding_generator * blinding)
+    }
+
+    /// Commit to a value with a random blinding factor, which is returned with the commitment.
+    pub fn commit_random<R: AllowedRng>(
+        parameters: &PedersenParameters<G>,
+        value: &G::ScalarType,
+        rng: &mut R,
+    ) -> (Self, G::ScalarType) {
+        let blinding = G::ScalarType::rand(rng);
+        (Self::commit(parameters, value, &blinding), blinding)
+    }
+
+    /// Check that this commitment opens to the given value with the given blinding factor. Fails
+    /// with [FastCryptoError::InvalidInput] otherwise.
+    pub fn open(
+        &self,
+        parameters: &PedersenParameters<G>,
+        value: &G::ScalarType,
+        blinding: &G::ScalarType,
+    ) -> FastCryptoResult<()> {
+        if *self != Self::commit(parameters, value, blinding) {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(())
+    }
+
+    /// The group element `v G + r H`.
+    pub fn point(&self) -> &G {
+        &self.0
+    }
+}
+
+impl<G: GroupElement> From<G> for PedersenCommitment<G> {
+    fn from(point: G) -> Self {
+        Self(point)
+    }
+}
+
+impl<G: GroupElement> Add for PedersenCommitment<G> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl<G: GroupElement> Sub for PedersenCommitment<G> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl<G: GroupElement> Neg for PedersenCommitment<G> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(-self.0)
+    }
+}
@@ -4,7 +4,7 @@
 //! Implementations of the [ristretto255 group](https://www.ietf.org/archive/id/draft-irtf-cfrg-ristretto255-decaf448-03.html) which is a group of
 //! prime order 2^{252} + 27742317777372353535851937790883648493 built over Curve25519.
 
-use crate::groups::{GroupElement, HashToGroupElement, Scalar};
+use crate::groups::{FixedBaseMultiplication, GroupElement, HashToGroupElement, Scalar};
 use crate::hash::Sha512;
 use crate::serde_helpers::ToFromByteArray;
 use crate::traits::AllowedRng;
@@ -12,7 +12,9 @@ use crate::{
     error::FastCryptoError, hash::HashFunction, serialize_deserialize_with_to_from_byte_array,
 };
 use curve25519_dalek_ng;
-use curve25519_dalek_ng::constants::{BASEPOINT_ORDER, RISTRETTO_BASEPOINT_POINT};
+use curve25519_dalek_ng::constants::{
+    BASEPOINT_ORDER, RISTRETTO_BASEPOINT_POINT, RISTRETTO_BASEPOINT_TABLE,
+};
 use curve25519_dalek_ng::ristretto::CompressedRistretto as ExternalCompressedRistrettoPoint;
 use curve25519_dalek_ng::ristretto::RistrettoPoint as ExternalRistrettoPoint;
 use curve25519_dalek_ng::scalar::Scalar as ExternalRistrettoScalar;
@@ -96,6 +98,12 @@ impl GroupElement for RistrettoPoint {
     }
 }
 
+impl FixedBaseMultiplication for RistrettoPoint {
+    fn mul_generator(scalar: &RistrettoScalar) -> Self {
+        RistrettoPoint::from(&RISTRETTO_BASEPOINT_TABLE * &scalar.0)
+    }
+}
+
 impl TryFrom<&[u8]> for RistrettoPoint {
     type Error = FastCryptoError;
 
@@ -0,0 +1,194 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Implementation of the [secp256k1](https://www.secg.org/sec2-v2.pdf) group which is a group of
+//! prime order 2^{256} - 432420386565659656852420866394968145599. This is the group used by the
+//! ECDSA signatures in [crate::secp256k1], and the implementation here exposes its arithmetic so
+//! that protocols built on top of those signatures (e.g. adaptor signatures) can be implemented.
+
+use crate::error::FastCryptoError;
+use crate::groups::{GroupElement, HashToGroupElement, Scalar};
+use crate::hash::{HashFunction, Sha512};
+use crate::serde_helpers::ToFromByteArray;
+use crate::serialize_deserialize_with_to_from_byte_array;
+use crate::traits::AllowedRng;
+use derive_more::{Add, From, Neg, Sub};
+use fastcrypto_derive::GroupOpsExtend;
+use k256::elliptic_curve::bigint::U256;
+use k256::elliptic_curve::group::GroupEncoding;
+use k256::elliptic_curve::ops::Reduce;
+use k256::elliptic_curve::scalar::IsHigh;
+use k256::elliptic_curve::{AffineXCoordinate, Field, PrimeField};
+use k256::{
+    CompressedPoint, FieldBytes, ProjectivePoint as ExternalPoint, Scalar as ExternalScalar,
+};
+use serde::{de, Deserialize};
+use std::ops::{Div, Mul};
+
+/// Length of a compressed secp256k1 point.
+pub const SECP256K1_POINT_BYTE_LENGTH: usize = 33;
+
+/// Length of a secp256k1 scalar.
+pub const SECP256K1_SCALAR_BYTE_LENGTH: usize = 32;
+
+/// Represents a point on the secp256k1 curve.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, From, Add, Sub, Neg, GroupOpsExtend)]
+pub struct Secp256k1Point(pub(crate) ExternalPoint);
+
+impl Secp256k1Point {
+    /// Return the x-coordinate of this point reduced modulo the group order, as used by ECDSA to
+    /// compute the `r` part of a signature. Returns an error if this is the point at infinity.
+    pub fn x_as_scalar(&self) -> Result<Secp256k1Scalar, FastCryptoError> {
+        if self.0 == ExternalPoint::IDENTITY {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(Secp256k1Scalar(
+            <ExternalScalar as Reduce<U256>>::from_be_bytes_reduced(self.0.to_affine().x()),
+        ))
+    }
+}
+
+impl Mul<Secp256k1Scalar> for Secp256k1Point {
+    type Output = Secp256k1Point;
+
+    fn mul(self, rhs: Secp256k1Scalar) -> Secp256k1Point {
+        Secp256k1Point::from(self.0 * rhs.0)
+    }
+}
+
+impl GroupElement for Secp256k1Point {
+    type ScalarType = Secp256k1Scalar;
+
+    fn zero() -> Self {
+        Secp256k1Point::from(ExternalPoint::IDENTITY)
+    }
+
+    fn generator() -> Self {
+        Secp256k1Point::from(ExternalPoint::GENERATOR)
+    }
+}
+
+impl ToFromByteArray<SECP256K1_POINT_BYTE_LENGTH> for Secp256k1Point {
+    /// Decode a point in SEC1 compressed form. The point at infinity is encoded as all zeros.
+    fn from_byte_array(bytes: &[u8; SECP256K1_POINT_BYTE_LENGTH]) -> Result<Self, FastCryptoError> {
+        if bytes.iter().all(|b| *b == 0) {
+            return Ok(Self::zero());
+        }
+        let point: Option<ExternalPoint> =
+            ExternalPoint::from_bytes(&CompressedPoint::clone_from_slice(bytes)).into();
+        point
+            .map(Secp256k1Point)
+            .ok_or(FastCryptoError::InvalidInput)
+    }
+
+    /// Encode this point in SEC1 compressed form. The point at infinity is encoded as all zeros.
+    fn to_byte_array(&self) -> [u8; SECP256K1_POINT_BYTE_LENGTH] {
+        let mut bytes = [0u8; SECP256K1_POINT_BYTE_LENGTH];
+        if self.0 != ExternalPoint::IDENTITY {
+            bytes.copy_from_slice(self.0.to_bytes().as_slice());
+        }
+        bytes
+    }
+}
+
+serialize_deserialize_with_to_from_byte_array!(Secp256k1Point);
+
+/// Represents a scalar modulo the order of the secp256k1 group.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, From, Add, Sub, Neg, GroupOpsExtend)]
+pub struct Secp256k1Scalar(pub(crate) ExternalScalar);
+
+impl Secp256k1Scalar {
+    /// Construct a [Secp256k1Scalar] by reducing a 32-byte big-endian integer modulo the group order.
+    pub fn from_bytes_mod_order(bytes: &[u8; 32]) -> Self {
+        Secp256k1Scalar(<ExternalScalar as Reduce<U256>>::from_be_bytes_reduced(
+            FieldBytes::from(*bytes),
+        ))
+    }
+
+    /// Construct a [Secp256k1Scalar] by reducing a 64-byte big-endian integer modulo the group order.
+    pub fn from_bytes_mod_order_wide(bytes: &[u8; 64]) -> Self {
+        let (high, low) = bytes.split_at(32);
+        // 2^256 mod n, computed as (2^32)^8.
+        let mut shift = ExternalScalar::from(1u64 << 32);
+        for _ in 0..3 {
+            shift = shift.square();
+        }
+        let high = Self::from_bytes_mod_order(high.try_into().expect("Length is 32"));
+        let low = Self::from_bytes_mod_order(low.try_into().expect("Length is 32"));
+        Secp256k1Scalar(high.0 * shift + low.0)
+    }
+
+    /// Returns true if this scalar is larger than half the group order. Used to normalize ECDSA
+    /// signatures.
+    pub fn is_high(&self) -> bool {
+        self.0.is_high().into()
+    }
+}
+
+impl From<u64> for Secp256k1Scalar {
+    fn from(value: u64) -> Secp256k1Scalar {
+        Secp256k1Scalar(ExternalScalar::from(value))
+    }
+}
+
+impl Mul<Secp256k1Scalar> for Secp256k1Scalar {
+    type Output = Secp256k1Scalar;
+
+    fn mul(self, rhs: Secp256k1Scalar) -> Secp256k1Scalar {
+        Secp256k1Scalar::from(self.0 * rhs.0)
+    }
+}
+
+impl Div<Secp256k1Scalar> for Secp256k1Scalar {
+    type Output = Result<Secp256k1Scalar, FastCryptoError>;
+
+    fn div(self, rhs: Secp256k1Scalar) -> Result<Secp256k1Scalar, FastCryptoError> {
+        let inverse: Option<ExternalScalar> = rhs.0.invert().into();
+        inverse
+            .map(|inverse| Secp256k1Scalar(self.0 * inverse))
+            .ok_or(FastCryptoError::InvalidInput)
+    }
+}
+
+impl GroupElement for Secp256k1Scalar {
+    type ScalarType = Self;
+
+    fn zero() -> Self {
+        Secp256k1Scalar(ExternalScalar::ZERO)
+    }
+
+    fn generator() -> Self {
+        Secp256k1Scalar(ExternalScalar::ONE)
+    }
+}
+
+impl Scalar for Secp256k1Scalar {
+    fn rand<R: AllowedRng>(rng: &mut R) -> Self {
+        Secp256k1Scalar(ExternalScalar::random(rng))
+    }
+}
+
+impl HashToGroupElement for Secp256k1Scalar {
+    fn hash_to_group_element(bytes: &[u8]) -> Self {
+        Self::from_bytes_mod_order_wide(&Sha512::digest(bytes).digest)
+    }
+}
+
+impl ToFromByteArray<SECP256K1_SCALAR_BYTE_LENGTH> for Secp256k1Scalar {
+    /// Decode a scalar from its canonical 32-byte big-endian representation.
+    fn from_byte_array(
+        bytes: &[u8; SECP256K1_SCALAR_BYTE_LENGTH],
+    ) -> Result<Self, FastCryptoError> {
+        let scalar: Option<ExternalScalar> =
+            ExternalScalar::from_repr(FieldBytes::from(*bytes)).into();
+        scalar
+            .map(Secp256k1Scalar)
+            .ok_or(FastCryptoError::InvalidInput)
+    }
+
+    fn to_byte_array(&self) -> [u8; SECP256K1_SCALAR_BYTE_LENGTH] {
+        self.0.to_repr().into()
+    }
+}
+
+serialize_deserialize_with_to_from_byte_array!(Secp256k1Scalar);
@@ -0,0 +1,178 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Implementation of the [secp256r1](https://www.secg.org/sec2-v2.pdf) group, also known as NIST
+//! P-256, which is a group of prime order
+//! 2^{256} - 2^{224} + 2^{192} - 89188191075325690597107910205041859247. This is the group used by
+//! the ECDSA signatures in [crate::secp256r1], and the implementation here exposes its arithmetic
+//! and hash-to-curve so that protocols over this group (e.g. the P256-SHA256 ciphersuite of
+//! [crate::oprf]) can be implemented.
+
+use crate::error::FastCryptoError;
+use crate::groups::{FixedBaseMultiplication, GroupElement, Scalar};
+use crate::serde_helpers::ToFromByteArray;
+use crate::serialize_deserialize_with_to_from_byte_array;
+use crate::traits::AllowedRng;
+use derive_more::{Add, From, Neg, Sub};
+use fastcrypto_derive::GroupOpsExtend;
+use p256::elliptic_curve::group::GroupEncoding;
+use p256::elliptic_curve::hash2curve::{ExpandMsgXmd, GroupDigest};
+use p256::elliptic_curve::{Field, PrimeField};
+use p256::{
+    CompressedPoint, FieldBytes, NistP256, ProjectivePoint as ExternalPoint,
+    Scalar as ExternalScalar,
+};
+use serde::{de, Deserialize};
+use std::ops::{Div, Mul};
+
+/// Length of a compressed secp256r1 point.
+pub const SECP256R1_POINT_BYTE_LENGTH: usize = 33;
+
+/// Length of a secp256r1 scalar.
+pub const SECP256R1_SCALAR_BYTE_LENGTH: usize = 32;
+
+/// Represents a point on the secp256r1 curve.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, From, Add, Sub, Neg, GroupOpsExtend)]
+pub struct Secp256r1Point(pub(crate) ExternalPoint);
+
+impl Secp256r1Point {
+    /// Hash a message to a point using the P256_XMD:SHA-256_SSWU_RO_ suite from section 8.2 of
+    /// [RFC 9380](https://www.rfc-editor.org/rfc/rfc9380.html). The message and the domain
+    /// separation tag are the concatenations of `msg` and `dst` respectively. Fails if
+    /// expand_message_xmd rejects the domain separation tag.
+    pub fn hash_to_curve(msg: &[&[u8]], dst: &[&[u8]]) -> Result<Self, FastCryptoError> {
+        NistP256::hash_from_bytes::<ExpandMsgXmd<sha2::Sha256>>(msg, dst)
+            .map(Secp256r1Point)
+            .map_err(|_| FastCryptoError::InvalidInput)
+    }
+}
+
+impl Mul<Secp256r1Scalar> for Secp256r1Point {
+    type Output = Secp256r1Point;
+
+    fn mul(self, rhs: Secp256r1Scalar) -> Secp256r1Point {
+        Secp256r1Point::from(self.0 * rhs.0)
+    }
+}
+
+impl GroupElement for Secp256r1Point {
+    type ScalarType = Secp256r1Scalar;
+
+    fn zero() -> Self {
+        Secp256r1Point::from(ExternalPoint::IDENTITY)
+    }
+
+    fn generator() -> Self {
+        Secp256r1Point::from(ExternalPoint::GENERATOR)
+    }
+}
+
+impl FixedBaseMultiplication for Secp256r1Point {
+    fn mul_generator(scalar: &Secp256r1Scalar) -> Self {
+        Secp256r1Point::from(ExternalPoint::GENERATOR * scalar.0)
+    }
+}
+
+impl ToFromByteArray<SECP256R1_POINT_BYTE_LENGTH> for Secp256r1Point {
+    /// Decode a point in SEC1 compressed form. The point at infinity is encoded as all zeros.
+    fn from_byte_array(bytes: &[u8; SECP256R1_POINT_BYTE_LENGTH]) -> Result<Self, FastCryptoError> {
+        if bytes.iter().all(|b| *b == 0) {
+            return Ok(Self::zero());
+        }
+        let point: Option<ExternalPoint> =
+            ExternalPoint::from_bytes(&CompressedPoint::clone_from_slice(bytes)).into();
+        point
+            .map(Secp256r1Point)
+            .ok_or(FastCryptoError::InvalidInput)
+    }
+
+    /// Encode this point in SEC1 compressed form. The point at infinity is encoded as all zeros.
+    fn to_byte_array(&self) -> [u8; SECP256R1_POINT_BYTE_LENGTH] {
+        let mut bytes = [0u8; SECP256R1_POINT_BYTE_LENGTH];
+        if self.0 != ExternalPoint::IDENTITY {
+            bytes.copy_from_slice(self.0.to_bytes().as_slice());
+        }
+        bytes
+    }
+}
+
+serialize_deserialize_with_to_from_byte_array!(Secp256r1Point);
+
+/// Represents a scalar modulo the order of the secp256r1 group.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, From, Add, Sub, Neg, GroupOpsExtend)]
+pub struct Secp256r1Scalar(pub(crate) ExternalScalar);
+
+impl Secp256r1Scalar {
+    /// Hash a message to a scalar using hash_to_field from section 5.2 of
+    /// [RFC 9380](https://www.rfc-editor.org/rfc/rfc9380.html) with expand_message_xmd using
+    /// SHA-256 and 48 bytes per element. The message and the domain separation tag are the
+    /// concatenations of `msg` and `dst` respectively. Fails if expand_message_xmd rejects the
+    /// domain separation tag.
+    pub fn hash_to_field(msg: &[&[u8]], dst: &[&[u8]]) -> Result<Self, FastCryptoError> {
+        NistP256::hash_to_scalar::<ExpandMsgXmd<sha2::Sha256>>(msg, dst)
+            .map(Secp256r1Scalar)
+            .map_err(|_| FastCryptoError::InvalidInput)
+    }
+}
+
+impl From<u64> for Secp256r1Scalar {
+    fn from(value: u64) -> Secp256r1Scalar {
+        Secp256r1Scalar(ExternalScalar::from(value))
+    }
+}
+
+impl Mul<Secp256r1Scalar> for Secp256r1Scalar {
+    type Output = Secp256r1Scalar;
+
+    fn mul(self, rhs: Secp256r1Scalar) -> Secp256r1Scalar {
+        Secp256r1Scalar::from(self.0 * rhs.0)
+    }
+}
+
+impl Div<Secp256r1Scalar> for Secp256r1Scalar {
+    type Output = Result<Secp256r1Scalar, FastCryptoError>;
+
+    fn div(self, rhs: Secp256r1Scalar) -> Result<Secp256r1Scalar, FastCryptoError> {
+        let inverse: Option<ExternalScalar> = rhs.0.invert().into();
+        inverse
+            .map(|inverse| Secp256r1Scalar(self.0 * inverse))
+            .ok_or(FastCryptoError::InvalidInput)
+    }
+}
+
+impl GroupElement for Secp256r1Scalar {
+    type ScalarType = Self;
+
+    fn zero() -> Self {
+        Secp256r1Scalar(ExternalScalar::ZERO)
+    }
+
+    fn generator() -> Self {
+        Secp256r1Scalar(ExternalScalar::ONE)
+    }
+}
+
+impl Scalar for Secp256r1Scalar {
+    fn rand<R: AllowedRng>(rng: &mut R) -> Self {
+        Secp256r1Scalar(ExternalScalar::random(rng))
+    }
+}
+
+impl ToFromByteArray<SECP256R1_SCALAR_BYTE_LENGTH> for Secp256r1Scalar {
+    /// Decode a scalar from its canonical 32-byte big-endian representation.
+    fn from_byte_array(
+        bytes: &[u8; SECP256R1_SCALAR_BYTE_LENGTH],
+    ) -> Result<Self, FastCryptoError> {
+        let scalar: Option<ExternalScalar> =
+            ExternalScalar::from_repr(FieldBytes::from(*bytes)).into();
+        scalar
+            .map(Secp256r1Scalar)
+            .ok_or(FastCryptoError::InvalidInput)
+    }
+
+    fn to_byte_array(&self) -> [u8; SECP256R1_SCALAR_BYTE_LENGTH] {
+        self.0.to_repr().into()
+    }
+}
+
+serialize_deserialize_with_to_from_byte_array!(Secp256r1Scalar);
@@ -21,9 +21,14 @@ use digest::OutputSizeUser;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use std::fmt;
+use std::marker::PhantomData;
+use std::str::FromStr;
 
-use crate::encoding::{Base64, Encoding};
+use crate::encoding::{Base64, Encoding, Hex};
+use crate::error::FastCryptoError;
+#[cfg(not(feature = "fips"))]
 use crate::groups::ristretto255::RistrettoPoint;
+#[cfg(not(feature = "fips"))]
 use crate::groups::HashToGroupElement;
 
 /// Represents a digest of `DIGEST_LEN` bytes.
@@ -114,6 +119,147 @@ pub trait Hash<const DIGEST_LEN: usize> {
     fn digest(&self) -> Self::TypedDigest;
 }
 
+/// A domain of objects whose digests should never be confused with digests of other objects, e.g.
+/// transactions or checkpoints. Digests of objects from different domains have different types and
+/// are computed with different prefixes, see [TaggedDigest].
+pub trait DigestDomain {
+    /// The name of this domain. It should be unique.
+    const NAME: &'static str;
+}
+
+/// A content-addressed digest of an object of the domain `D`. It is computed as the digest of
+/// `len(NAME) || NAME || content`, where the length of the domain name is encoded as an 8 byte
+/// big-endian integer. Since the name can be parsed from the input of the hash function, digests of
+/// different domains are digests of different inputs, even if the content is the same. It is
+/// displayed and parsed as hex and serialized as the underlying [Digest].
+///
+/// # Example
+/// ```
+/// # use fastcrypto::hash::*;
+/// struct Transactions;
+/// impl DigestDomain for Transactions {
+///     const NAME: &'static str = "Transaction";
+/// }
+/// type TransactionDigest = TaggedDigest<Transactions, 32>;
+///
+/// let digest = TransactionDigest::compute::<Sha256, _>(b"transaction");
+/// let input = [11u64.to_be_bytes().as_slice(), b"Transaction", b"transaction"].concat();
+/// assert_eq!(digest.inner(), &Sha256::digest(input));
+/// assert_eq!(digest.to_string().parse::<TransactionDigest>().unwrap(), digest);
+/// ```
+#[derive(Serialize, Deserialize)]
+#[serde(transparent, bound = "")]
+pub struct TaggedDigest<D: DigestDomain, const DIGEST_LEN: usize> {
+    digest: Digest<DIGEST_LEN>,
+    #[serde(skip)]
+    domain: PhantomData<D>,
+}
+
+impl<D: DigestDomain, const DIGEST_LEN: usize> TaggedDigest<D, DIGEST_LEN> {
+    /// Wrap an existing digest.
+    pub fn new(digest: Digest<DIGEST_LEN>) -> Self {
+        Self {
+            digest,
+            domain: PhantomData,
+        }
+    }
+
+    /// Compute the digest of the given content using the hash function `H`.
+    pub fn compute<H: HashFunction<DIGEST_LEN>, Data: AsRef<[u8]>>(data: Data) -> Self {
+        Self::compute_iterator::<H, _, _>(std::iter::once(data))
+    }
+
+    /// Compute the digest of the concatenation of all slices in the iterator using the hash
+    /// function `H`. See [HashFunction::digest_iterator].
+    pub fn compute_iterator<H: HashFunction<DIGEST_LEN>, K: AsRef<[u8]>, I: Iterator<Item = K>>(
+        iter: I,
+    ) -> Self {
+        let mut h = H::default();
+        h.update((D::NAME.len() as u64).to_be_bytes());
+        h.update(D::NAME);
+        iter.for_each(|chunk| h.update(chunk));
+        Self::new(h.finalize())
+    }
+
+    /// The underlying untyped digest.
+    pub fn inner(&self) -> &Digest<DIGEST_LEN> {
+        &self.digest
+    }
+}
+
+// The traits are implemented manually because deriving them would require `D` to implement them.
+impl<D: DigestDomain, const DIGEST_LEN: usize> Clone for TaggedDigest<D, DIGEST_LEN> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<D: DigestDomain, const DIGEST_LEN: usize> Copy for TaggedDigest<D, DIGEST_LEN> {}
+
+impl<D: DigestDomain, const DIGEST_LEN: usize> PartialEq for TaggedDigest<D, DIGEST_LEN> {
+    fn eq(&self, other: &Self) -> bool {
+        self.digest == other.digest
+    }
+}
+
+impl<D: DigestDomain, const DIGEST_LEN: usize> Eq for TaggedDigest<D, DIGEST_LEN> {}
+
+impl<D: DigestDomain, const DIGEST_LEN: usize> PartialOrd for TaggedDigest<D, DIGEST_LEN> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<D: DigestDomain, const DIGEST_LEN: usize> Ord for TaggedDigest<D, DIGEST_LEN> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.digest.cmp(&other.digest)
+    }
+}
+
+impl<D: DigestDomain, const DIGEST_LEN: usize> std::hash::Hash for TaggedDigest<D, DIGEST_LEN> {
+    fn hash<S: std::hash::Hasher>(&self, state: &mut S) {
+        std::hash::Hash::hash(&self.digest, state)
+    }
+}
+
+impl<D: DigestDomain, const DIGEST_LEN: usize> fmt::Debug for TaggedDigest<D, DIGEST_LEN> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}({})", D::NAME, Hex::encode(self.digest))
+    }
+}
+
+impl<D: DigestDomain, const DIGEST_LEN: usize> fmt::Display for TaggedDigest<D, DIGEST_LEN> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}", Hex::encode(self.digest))
+    }
+}
+
+impl<D: DigestDomain, const DIGEST_LEN: usize> FromStr for TaggedDigest<D, DIGEST_LEN> {
+    type Err = FastCryptoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = Hex::decode(s).map_err(|_| FastCryptoError::InvalidInput)?;
+        let digest = bytes
+            .try_into()
+            .map_err(|_| FastCryptoError::InputLengthWrong(DIGEST_LEN))?;
+        Ok(Self::new(Digest::new(digest)))
+    }
+}
+
+impl<D: DigestDomain, const DIGEST_LEN: usize> From<TaggedDigest<D, DIGEST_LEN>>
+    for Digest<DIGEST_LEN>
+{
+    fn from(digest: TaggedDigest<D, DIGEST_LEN>) -> Self {
+        digest.digest
+    }
+}
+
+impl<D: DigestDomain, const DIGEST_LEN: usize> AsRef<[u8]> for TaggedDigest<D, DIGEST_LEN> {
+    fn as_ref(&self) -> &[u8] {
+        self.digest.as_ref()
+    }
+}
+
 /// This wraps a [digest::Digest] as a [HashFunction].
 #[derive(Default)]
 pub struct HashFunctionWrapper<Variant: digest::Digest + 'static, const DIGEST_LEN: usize>(Variant);
@@ -172,17 +318,25 @@ pub type Sha512 = HashFunctionWrapper<sha2::Sha512, 64>;
 pub type Sha3_512 = HashFunctionWrapper<sha3::Sha3_512, 64>;
 
 /// The [KECCAK](https://keccak.team/files/Keccak-reference-3.0.pdf) hash function with 256 bit digests.
+#[cfg(not(feature = "fips"))]
 pub type Keccak256 = HashFunctionWrapper<sha3::Keccak256, 32>;
 
 /// The [BLAKE2-256](https://en.wikipedia.org/wiki/BLAKE_(hash_function)#BLAKE2) hash function with 256 bit digests.
+#[cfg(not(feature = "fips"))]
 pub type Blake2b256 = HashFunctionWrapper<blake2::Blake2b<typenum::U32>, 32>;
 
+/// The [BLAKE2-512](https://en.wikipedia.org/wiki/BLAKE_(hash_function)#BLAKE2) hash function with 512 bit digests.
+#[cfg(not(feature = "fips"))]
+pub type Blake2b512 = HashFunctionWrapper<blake2::Blake2b<typenum::U64>, 64>;
+
 /// The [BLAKE3](https://en.wikipedia.org/wiki/BLAKE_(hash_function)#BLAKE3) hash function with 256 bit digests.
+#[cfg(not(feature = "fips"))]
 #[derive(Default)]
 pub struct Blake3 {
     instance: blake3::Hasher,
 }
 
+#[cfg(not(feature = "fips"))]
 impl HashFunction<32> for Blake3 {
     fn update<Data: AsRef<[u8]>>(&mut self, data: Data) {
         self.instance.update(data.as_ref());
@@ -208,6 +362,8 @@ impl HashFunction<32> for Blake3 {
 ///
 /// # Example
 /// ```
+/// # #[cfg(not(feature = "fips"))]
+/// # {
 /// use fastcrypto::hash::{EllipticCurveMultisetHash, MultisetHash};
 ///
 /// let mut hash1 = EllipticCurveMultisetHash::default();
@@ -220,6 +376,7 @@ impl HashFunction<32> for Blake3 {
 ///
 /// assert_eq!(hash1, hash2);
 /// assert_eq!(hash1.digest(), hash2.digest());
+/// # }
 /// ```
 pub trait MultisetHash<const DIGEST_LENGTH: usize>: Eq {
     /// Insert an item into this hash function.
@@ -270,19 +427,23 @@ pub trait MultisetHash<const DIGEST_LENGTH: usize>: Eq {
 /// Under the hood, it uses an Ristretto-flavoured Elligator 2 map to map a Sha512 hash of the provided
 /// data into points in the Ristretto group, and Sha256 to construct a digest from a serialization of
 /// the resulting RistrettoPoint, so digests are 32 bytes long.
+#[cfg(not(feature = "fips"))]
 #[derive(Default, Clone, Serialize, Deserialize)]
 pub struct EllipticCurveMultisetHash {
     accumulator: RistrettoPoint,
 }
 
+#[cfg(not(feature = "fips"))]
 impl PartialEq for EllipticCurveMultisetHash {
     fn eq(&self, other: &Self) -> bool {
         self.accumulator == other.accumulator
     }
 }
 
+#[cfg(not(feature = "fips"))]
 impl Eq for EllipticCurveMultisetHash {}
 
+#[cfg(not(feature = "fips"))]
 impl MultisetHash<32> for EllipticCurveMultisetHash {
     fn insert<Data: AsRef<[u8]>>(&mut self, item: Data) {
         self.accumulator += Self::hash_to_point(item);
@@ -322,6 +483,7 @@ impl MultisetHash<32> for EllipticCurveMultisetHash {
     }
 }
 
+#[cfg(not(feature = "fips"))]
 impl EllipticCurveMultisetHash {
     /// Hash the given item into a RistrettoPoint to be used by the insert and remove methods.
     fn hash_to_point<Data: AsRef<[u8]>>(item: Data) -> RistrettoPoint {
@@ -329,6 +491,7 @@ impl EllipticCurveMultisetHash {
     }
 }
 
+#[cfg(not(feature = "fips"))]
 impl Debug for EllipticCurveMultisetHash {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Accumulator").finish()
@@ -0,0 +1,80 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A salted hash commitment scheme based on HMAC-SHA3-256.
+//!
+//! A commitment to a message `m` in a domain `d` is `HMAC(r, len(d) || d || m)` where `r` is a random
+//! 32 byte salt and `len(d)` is the length of `d` as an 8 byte big-endian integer. The commitment
+//! is hiding since the salt is secret until the commitment is opened, and binding since HMAC-SHA3-256
+//! is collision resistant. The encoding of the domain is prefix-free, so a commitment in one domain
+//! can not be opened in another domain, and since the salt has a fixed length there is no other
+//! way to split an opening between the salt and the message.
+//!
+//! A commitment is opened by revealing the message and the [Opening], and the opening is checked in
+//! constant time.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::hash_commitment::*;
+//! # use rand::thread_rng;
+//! let (commitment, opening) = commit(b"my protocol", b"my bid", &mut thread_rng());
+//! assert!(commitment.verify(b"my protocol", b"my bid", &opening).is_ok());
+//! assert!(commitment.verify(b"my protocol", b"another bid", &opening).is_err());
+//! assert!(commitment.verify(b"another protocol", b"my bid", &opening).is_err());
+//! ```
+
+use crate::error::FastCryptoError;
+use crate::hmac::{hmac_sha3_256, HmacKey};
+use crate::traits::{AllowedRng, ToFromBytes};
+use serde::{Deserialize, Serialize};
+use subtle::ConstantTimeEq;
+
+/// The length of salts and commitments in bytes.
+pub const LENGTH: usize = 32;
+
+/// A commitment to a message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Commitment(pub [u8; LENGTH]);
+
+/// The secret salt of a commitment, which is revealed together with the message to open it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Opening(pub [u8; LENGTH]);
+
+/// Commit to a message in a domain using a random salt.
+pub fn commit<R: AllowedRng>(domain: &[u8], message: &[u8], rng: &mut R) -> (Commitment, Opening) {
+    let mut salt = [0u8; LENGTH];
+    rng.fill_bytes(&mut salt);
+    let opening = Opening(salt);
+    (commit_with_opening(domain, message, &opening), opening)
+}
+
+/// Compute the commitment to a message in a domain with the given salt.
+pub fn commit_with_opening(domain: &[u8], message: &[u8], opening: &Opening) -> Commitment {
+    let key = HmacKey::from_bytes(&opening.0).expect("Any length is allowed");
+    let input = [
+        (domain.len() as u64).to_be_bytes().as_slice(),
+        domain,
+        message,
+    ]
+    .concat();
+    Commitment(hmac_sha3_256(&key, &input).digest)
+}
+
+impl Commitment {
+    /// Check that this commitment opens to the given message in the given domain.
+    pub fn verify(
+        &self,
+        domain: &[u8],
+        message: &[u8],
+        opening: &Opening,
+    ) -> Result<(), FastCryptoError> {
+        if !bool::from(
+            commit_with_opening(domain, message, opening)
+                .0
+                .ct_eq(&self.0),
+        ) {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,128 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Challenge-response proofs of key ownership for any signature scheme, e.g. for handshakes
+//! between nodes or when registering a key.
+//!
+//! The verifier sends a [KeyOwnershipChallenge] consisting of a domain, which names the protocol
+//! or flow, and a random nonce. The prover answers with a [KeyOwnershipProof] which signs the
+//! domain, the nonce and the public key. Since the public key is signed, a proof cannot be
+//! replayed for another key, and since the nonce is chosen by the verifier, it cannot be replayed
+//! to another verifier. The proof consists of the nonce and the signature only, while the domain
+//! and the public key are given by the context.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::key_ownership::*;
+//! # use fastcrypto::ed25519::Ed25519KeyPair;
+//! # use fastcrypto::traits::KeyPair;
+//! # use rand::thread_rng;
+//! let kp = Ed25519KeyPair::generate(&mut thread_rng());
+//! let challenge = KeyOwnershipChallenge::new(b"my-handshake", &mut thread_rng()).unwrap();
+//! let proof = KeyOwnershipProof::new(&kp, &challenge);
+//! assert!(proof.verify(kp.public(), &challenge).is_ok());
+//! ```
+
+use crate::error::FastCryptoError;
+use crate::traits::{AllowedRng, Authenticator, KeyPair, Signer, ToFromBytes, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+/// The length of the nonce of a challenge in bytes.
+pub const NONCE_LENGTH: usize = 32;
+
+/// The maximal length of a domain in bytes.
+pub const MAX_DOMAIN_LENGTH: usize = 255;
+
+const DST: &[u8] = b"fastcrypto-key-ownership";
+
+/// A challenge created by the verifier.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyOwnershipChallenge {
+    domain: Vec<u8>,
+    nonce: [u8; NONCE_LENGTH],
+}
+
+/// A response to a [KeyOwnershipChallenge] proving ownership of the private key of a given public
+/// key.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyOwnershipProof<S> {
+    nonce: [u8; NONCE_LENGTH],
+    signature: S,
+}
+
+impl KeyOwnershipChallenge {
+    /// Create a challenge with a random nonce. Fails if the domain is empty or longer than
+    /// [MAX_DOMAIN_LENGTH].
+    pub fn new<R: AllowedRng>(domain: &[u8], rng: &mut R) -> Result<Self, FastCryptoError> {
+        let mut nonce = [0u8; NONCE_LENGTH];
+        rng.fill_bytes(&mut nonce);
+        Self::from_nonce(domain, nonce)
+    }
+
+    /// Create a challenge with a given nonce, e.g. one received from the verifier. Fails if the
+    /// domain is empty or longer than [MAX_DOMAIN_LENGTH].
+    pub fn from_nonce(domain: &[u8], nonce: [u8; NONCE_LENGTH]) -> Result<Self, FastCryptoError> {
+        if domain.is_empty() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        if domain.len() > MAX_DOMAIN_LENGTH {
+            return Err(FastCryptoError::InputTooLong(MAX_DOMAIN_LENGTH));
+        }
+        Ok(Self {
+            domain: domain.to_vec(),
+            nonce,
+        })
+    }
+
+    /// The domain of this challenge.
+    pub fn domain(&self) -> &[u8] {
+        &self.domain
+    }
+
+    /// The nonce of this challenge.
+    pub fn nonce(&self) -> &[u8; NONCE_LENGTH] {
+        &self.nonce
+    }
+
+    /// The message which is signed to answer this challenge for the given public key.
+    fn message<P: VerifyingKey>(&self, public_key: &P) -> Vec<u8> {
+        let public_key = public_key.as_bytes();
+        let mut message =
+            Vec::with_capacity(DST.len() + 1 + self.domain.len() + NONCE_LENGTH + public_key.len());
+        message.extend_from_slice(DST);
+        message.push(self.domain.len() as u8);
+        message.extend_from_slice(&self.domain);
+        message.extend_from_slice(&self.nonce);
+        message.extend_from_slice(public_key);
+        message
+    }
+}
+
+impl<S: Authenticator> KeyOwnershipProof<S> {
+    /// Answer a challenge using the given key pair.
+    pub fn new<K: KeyPair<Sig = S>>(key_pair: &K, challenge: &KeyOwnershipChallenge) -> Self {
+        Self {
+            nonce: challenge.nonce,
+            signature: key_pair.sign(&challenge.message(key_pair.public())),
+        }
+    }
+
+    /// The nonce of the challenge this proof answers.
+    pub fn nonce(&self) -> &[u8; NONCE_LENGTH] {
+        &self.nonce
+    }
+
+    /// Verify that this proof answers the given challenge for the given public key.
+    pub fn verify(
+        &self,
+        public_key: &S::PubKey,
+        challenge: &KeyOwnershipChallenge,
+    ) -> Result<(), FastCryptoError> {
+        if self.nonce != challenge.nonce {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        public_key
+            .verify(&challenge.message(public_key), &self.signature)
+            .map_err(|_| FastCryptoError::InvalidProof)
+    }
+}
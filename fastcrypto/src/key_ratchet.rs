@@ -0,0 +1,221 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A symmetric key ratchet giving forward secrecy to the messages of a session.
+//!
+//! Both parties of a session start a [KeyRatchet] from a shared secret, e.g., the output of a
+//! key exchange, and each message is encrypted with its own [MessageKey]. The ratchet is a
+//! one-way chain of keys, where the message key and the next chain key are derived from the
+//! current chain key using HMAC-SHA3-256 as in the symmetric-key ratchet of
+//! [Signal](https://signal.org/docs/specifications/doubleratchet/#symmetric-key-ratchet). Since a
+//! chain key is deleted once the next one is derived, a compromise of the ratchet does not reveal
+//! the keys of earlier messages.
+//!
+//! A receiver may get messages out of order or not at all. When a message with a later index than
+//! expected arrives, the keys of the skipped messages are stored so the messages can be decrypted
+//! if they arrive later. At most `max_skip` keys are skipped at once or stored, which bounds the
+//! work and memory a peer can cause by sending large indices.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::key_ratchet::*;
+//! let mut sender = KeyRatchet::new(&[7u8; 32], b"my-session", DEFAULT_MAX_SKIP).unwrap();
+//! let mut receiver = KeyRatchet::new(&[7u8; 32], b"my-session", DEFAULT_MAX_SKIP).unwrap();
+//!
+//! let first = sender.next_key();
+//! let second = sender.next_key();
+//! let ciphertext = second.encrypt(b"header", b"Hello, world!");
+//!
+//! // The second message arrives first.
+//! let key = receiver.key_for(second.index()).unwrap();
+//! assert_eq!(key.decrypt(b"header", &ciphertext).unwrap(), b"Hello, world!");
+//! assert_eq!(receiver.key_for(first.index()).unwrap(), first);
+//!
+//! // Each key can only be used once.
+//! assert!(receiver.key_for(first.index()).is_err());
+//! ```
+
+use crate::aes::{Aes256Gcm, AesKey, AuthenticatedCipher, InitializationVector};
+use crate::error::{FastCryptoError, FastCryptoResult};
+use crate::hmac::{hkdf_sha3_256, hmac_sha3_256, HkdfIkm, HmacKey};
+use crate::traits::ToFromBytes;
+use fastcrypto_derive::{SilentDebug, SilentDisplay};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use typenum::U12;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// The default bound on the number of skipped message keys.
+pub const DEFAULT_MAX_SKIP: u64 = 1000;
+
+const KEY_LENGTH: usize = 32;
+const IV_LENGTH: usize = 12;
+const CHAIN_DST: &[u8] = b"fastcrypto-key-ratchet-chain-v1";
+const CIPHER_DST: &[u8] = b"fastcrypto-key-ratchet-cipher-v1";
+const MESSAGE_KEY_CONSTANT: &[u8] = &[0x01];
+const CHAIN_KEY_CONSTANT: &[u8] = &[0x02];
+
+/// The key of a single message in a chain.
+#[derive(Clone, PartialEq, Eq, SilentDebug, SilentDisplay, Zeroize, ZeroizeOnDrop)]
+pub struct MessageKey {
+    index: u64,
+    key: [u8; KEY_LENGTH],
+}
+
+impl MessageKey {
+    /// The index of the message in the chain.
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+
+    /// The raw key, e.g., for use with another cipher.
+    pub fn as_bytes(&self) -> &[u8; KEY_LENGTH] {
+        &self.key
+    }
+
+    /// The AES-256-GCM cipher and nonce of this key. A message key is used for a single message,
+    /// so the nonce is derived from the key together with the cipher key.
+    pub fn cipher(&self) -> (Aes256Gcm<U12>, InitializationVector<U12>) {
+        let ikm = HkdfIkm::from_bytes(&self.key).expect("Any length is allowed");
+        let okm = hkdf_sha3_256(&ikm, CIPHER_DST, &[], KEY_LENGTH + IV_LENGTH)
+            .expect("Output length is smaller than the maximum");
+        (
+            Aes256Gcm::new(AesKey::from_bytes(&okm[..KEY_LENGTH]).expect("Length is correct")),
+            InitializationVector::from_bytes(&okm[KEY_LENGTH..]).expect("Length is correct"),
+        )
+    }
+
+    /// Encrypt a message with this key, authenticating also `aad`.
+    pub fn encrypt(&self, aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        let (cipher, iv) = self.cipher();
+        cipher.encrypt_authenticated(&iv, aad, plaintext)
+    }
+
+    /// Decrypt a message encrypted with [MessageKey::encrypt].
+    pub fn decrypt(&self, aad: &[u8], ciphertext: &[u8]) -> FastCryptoResult<Vec<u8>> {
+        let (cipher, iv) = self.cipher();
+        cipher.decrypt_authenticated(&iv, aad, ciphertext)
+    }
+}
+
+/// A chain of message keys with storage for the keys of skipped messages, see the module
+/// documentation.
+///
+/// The state can be serialized to persist a session. The serialized state contains the current
+/// chain key and the stored message keys, and must be protected like them.
+#[derive(Clone, SilentDebug, SilentDisplay, Serialize, Deserialize)]
+pub struct KeyRatchet {
+    chain_key: [u8; KEY_LENGTH],
+    index: u64,
+    skipped: BTreeMap<u64, [u8; KEY_LENGTH]>,
+    max_skip: u64,
+}
+
+impl KeyRatchet {
+    /// A ratchet derived from a shared secret. The `info` binds the chain to its use, e.g., to a
+    /// session and direction, so the two directions of a session must use different values.
+    pub fn new(secret: &[u8], info: &[u8], max_skip: u64) -> FastCryptoResult<Self> {
+        let ikm = HkdfIkm::from_bytes(secret)?;
+        let okm = hkdf_sha3_256(&ikm, CHAIN_DST, info, KEY_LENGTH)?;
+        Ok(Self::from_chain_key(
+            okm.try_into().expect("Length is correct"),
+            max_skip,
+        ))
+    }
+
+    /// A ratchet starting at the given chain key, e.g., one derived by a DH ratchet.
+    pub fn from_chain_key(chain_key: [u8; KEY_LENGTH], max_skip: u64) -> Self {
+        Self {
+            chain_key,
+            index: 0,
+            skipped: BTreeMap::new(),
+            max_skip,
+        }
+    }
+
+    /// The index of the next message key of the chain.
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+
+    /// The number of stored keys of skipped messages.
+    pub fn skipped(&self) -> usize {
+        self.skipped.len()
+    }
+
+    /// The key of the next message to send. This advances the chain.
+    pub fn next_key(&mut self) -> MessageKey {
+        let index = self.index;
+        let key = self.advance();
+        MessageKey { index, key }
+    }
+
+    /// The key of a received message with the given index. The keys of the messages between the
+    /// current index and `index` are stored for later, and each key is only returned once.
+    ///
+    /// Returns [FastCryptoError::InvalidInput] if the key has already been returned or was dropped
+    /// from the stored keys, and [FastCryptoError::InputTooLong] if more than `max_skip` keys would
+    /// be skipped. In both cases the state is unchanged.
+    pub fn key_for(&mut self, index: u64) -> FastCryptoResult<MessageKey> {
+        if index < self.index {
+            return self
+                .skipped
+                .remove(&index)
+                .map(|key| MessageKey { index, key })
+                .ok_or(FastCryptoError::InvalidInput);
+        }
+        self.skip_to(index)?;
+        Ok(self.next_key())
+    }
+
+    /// Store the keys of all messages before `index`, so the chain continues at `index`. Used when
+    /// a chain ends, e.g., when a DH ratchet step replaces it, to keep the keys of messages which
+    /// have not arrived yet.
+    pub fn skip_to(&mut self, index: u64) -> FastCryptoResult<()> {
+        if index <= self.index {
+            return Ok(());
+        }
+        if index - self.index > self.max_skip {
+            return Err(FastCryptoError::InputTooLong(self.max_skip as usize));
+        }
+        while self.index < index {
+            let skipped_index = self.index;
+            let key = self.advance();
+            self.skipped.insert(skipped_index, key);
+        }
+        // Drop the oldest keys which are least likely to be used.
+        while self.skipped.len() as u64 > self.max_skip {
+            let oldest = *self.skipped.keys().next().expect("Not empty");
+            if let Some(mut key) = self.skipped.remove(&oldest) {
+                key.zeroize();
+            }
+        }
+        Ok(())
+    }
+
+    /// Take the stored keys of skipped messages, e.g., to move them to a store shared by several
+    /// chains.
+    pub fn take_skipped(&mut self) -> Vec<MessageKey> {
+        std::mem::take(&mut self.skipped)
+            .into_iter()
+            .map(|(index, key)| MessageKey { index, key })
+            .collect()
+    }
+
+    /// Replace the chain key with the next one and return the current message key.
+    fn advance(&mut self) -> [u8; KEY_LENGTH] {
+        let chain_key = HmacKey::from_bytes(&self.chain_key).expect("Any length is allowed");
+        let message_key = hmac_sha3_256(&chain_key, MESSAGE_KEY_CONSTANT).digest;
+        self.chain_key.zeroize();
+        self.chain_key = hmac_sha3_256(&chain_key, CHAIN_KEY_CONSTANT).digest;
+        self.index += 1;
+        message_key
+    }
+}
+
+impl Drop for KeyRatchet {
+    fn drop(&mut self) {
+        self.chain_key.zeroize();
+        self.skipped.values_mut().for_each(|key| key.zeroize());
+    }
+}
@@ -0,0 +1,347 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! [KZG](https://www.iacr.org/archive/asiacrypt2010/6477178/6477178.pdf) polynomial commitments
+//! over BLS12-381.
+//!
+//! A polynomial `p` of degree at most `d` is committed to as `C = [p(τ)]_1`, where the powers
+//! `[τ^i]_1` for `i <= d` and `[τ]_2` come from a trusted setup. An opening of `C` at a point `z`
+//! is the value `y = p(z)` and the proof `π = [q(τ)]_1` with `q = (p - y) / (X - z)`, which the
+//! verifier checks with `e(C - [y]_1 + z π, [1]_2) = e(π, [τ]_2)`. Several polynomials can be
+//! opened at the same point with a single proof of a random linear combination of them.
+//!
+//! The parameters can be loaded from the output of the common powers-of-tau ceremonies:
+//! * [KzgParameters::from_ceremony] loads the `powersOfTau` of the
+//!   [Ethereum KZG ceremony](https://github.com/ethereum/kzg-ceremony-specs) used by EIP-4844.
+//! * [KzgParameters::from_powers_of_tau_response] loads a response file of the
+//!   [Zcash powers of tau](https://github.com/ebfull/powersoftau) ceremony.
+//!
+//! Loaded powers are checked to be consistent, i.e., to be powers of the same `τ`.
+//! [KzgParameters::generate] samples `τ` locally and is only suitable for testing.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::kzg::*;
+//! # use fastcrypto::groups::bls12381::Scalar;
+//! # use rand::thread_rng;
+//! let parameters = KzgParameters::generate(7, &mut thread_rng()).unwrap();
+//! let polynomial = (1..=8u64).map(Scalar::from).collect::<Vec<_>>();
+//! let commitment = parameters.commit(&polynomial).unwrap();
+//!
+//! let point = Scalar::from(42);
+//! let (value, proof) = parameters.open(&polynomial, &point).unwrap();
+//! assert!(parameters.verify(&commitment, &point, &value, &proof).is_ok());
+//! ```
+
+use crate::error::FastCryptoError;
+use crate::groups::bls12381::{
+    G1Element, G2Element, GTElement, Scalar, G1_ELEMENT_BYTE_LENGTH, G2_ELEMENT_BYTE_LENGTH,
+};
+use crate::groups::{FixedBaseMultiplication, GroupElement, Pairing, Scalar as _};
+use crate::hash::{HashFunction, Sha256};
+use crate::polynomial::{divide_by_linear, powers};
+use crate::serde_helpers::ToFromByteArray;
+use crate::traits::AllowedRng;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+/// Domain separation tag of the challenge for batch openings.
+const BATCH_DST: &[u8] = b"fastcrypto-kzg-batch-v1";
+
+/// Domain separation tag of the challenge used to check the consistency of loaded powers.
+const CONSISTENCY_DST: &[u8] = b"fastcrypto-kzg-consistency-v1";
+
+/// The length of the hash of the previous contribution at the start of a Zcash powers of tau
+/// response file.
+const RESPONSE_HASH_LENGTH: usize = 64;
+
+/// The public parameters for committing to polynomials of degree at most `d`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KzgParameters {
+    /// `[τ^i]_1` for `0 <= i <= d`.
+    powers_g1: Vec<G1Element>,
+    /// `[τ]_2`.
+    tau_g2: G2Element,
+}
+
+/// A commitment to a polynomial.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Commitment(pub G1Element);
+
+/// A proof that a commitment opens to a value at a point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpeningProof(pub G1Element);
+
+/// The powers of the Ethereum KZG ceremony, i.e., the `powersOfTau` field of its transcript as
+/// hex encoded compressed points.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CeremonyPowers {
+    #[serde(rename = "G1Powers")]
+    pub g1_powers: Vec<String>,
+    #[serde(rename = "G2Powers")]
+    pub g2_powers: Vec<String>,
+}
+
+impl KzgParameters {
+    /// Generate parameters for polynomials of degree at most `max_degree` from a random trapdoor
+    /// which is discarded afterwards.
+    pub fn generate<R: AllowedRng>(
+        max_degree: usize,
+        rng: &mut R,
+    ) -> Result<Self, FastCryptoError> {
+        let tau = Scalar::rand(rng);
+        let powers_g1 = powers(&tau, max_degree + 1)
+            .iter()
+            .map(G1Element::mul_generator)
+            .collect();
+        Ok(Self {
+            powers_g1,
+            tau_g2: G2Element::mul_generator(&tau),
+        })
+    }
+
+    /// Create parameters from the powers `[τ^i]_1` for `0 <= i <= d` and `[τ^i]_2` for
+    /// `0 <= i < k`, where `d >= 1` and `k >= 2`. Fails if the powers do not start with the
+    /// generators or are not powers of the same `τ`.
+    pub fn from_powers(
+        powers_g1: Vec<G1Element>,
+        powers_g2: &[G2Element],
+    ) -> Result<Self, FastCryptoError> {
+        if powers_g1.len() < 2
+            || powers_g2.len() < 2
+            || powers_g1[0] != G1Element::generator()
+            || powers_g2[0] != G2Element::generator()
+        {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let tau_g2 = powers_g2[1];
+
+        // Check e([τ^i]_1, [τ]_2) = e([τ^(i+1)]_1, [1]_2) and e([τ]_1, [τ^i]_2) = e([1]_1,
+        // [τ^(i+1)]_2) for all i, with a random linear combination of all equations. The
+        // equations in G2 use higher powers of the challenge, so they cannot cancel those in G1.
+        let mut transcript = vec![CONSISTENCY_DST.to_vec()];
+        transcript.extend(powers_g1.iter().map(|p| p.to_byte_array().to_vec()));
+        transcript.extend(powers_g2.iter().map(|p| p.to_byte_array().to_vec()));
+        let offset = powers_g1.len();
+        let r = powers(&hash_to_scalar(&transcript), offset + powers_g2.len());
+        let n = powers_g1.len() - 1;
+        let k = powers_g2.len() - 1;
+        let lhs = G1Element::multi_pairing(
+            &[
+                msm(&powers_g1[..n], &r),
+                -msm(&powers_g1[1..], &r),
+                powers_g1[1],
+                -G1Element::generator(),
+            ],
+            &[
+                tau_g2,
+                G2Element::generator(),
+                msm(&powers_g2[..k], &r[offset..]),
+                msm(&powers_g2[1..], &r[offset..]),
+            ],
+        )?;
+        if lhs != GTElement::zero() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(Self { powers_g1, tau_g2 })
+    }
+
+    /// Load the parameters for polynomials of degree at most `max_degree` from the powers of the
+    /// Ethereum KZG ceremony. Fails if there are not enough powers or they are not valid.
+    pub fn from_ceremony(
+        powers: &CeremonyPowers,
+        max_degree: usize,
+    ) -> Result<Self, FastCryptoError> {
+        if powers.g1_powers.len() <= max_degree || powers.g2_powers.len() < 2 {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let powers_g1 = powers.g1_powers[..=max_degree]
+            .iter()
+            .map(|p| point_from_hex::<G1Element, G1_ELEMENT_BYTE_LENGTH>(p))
+            .collect::<Result<Vec<_>, _>>()?;
+        let powers_g2 = powers
+            .g2_powers
+            .iter()
+            .map(|p| point_from_hex::<G2Element, G2_ELEMENT_BYTE_LENGTH>(p))
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::from_powers(powers_g1, &powers_g2)
+    }
+
+    /// Load the parameters for polynomials of degree at most `max_degree` from a response file of
+    /// a Zcash powers of tau ceremony with `2^tau_powers_log` powers, i.e., with `TAU_POWERS_LENGTH`
+    /// equal to `2^tau_powers_log`. The file consists of the hash of the previous contribution
+    /// followed by the compressed powers `[τ^i]_1` for `i < 2^(tau_powers_log + 1) - 1` and
+    /// `[τ^i]_2` for `i < 2^tau_powers_log`.
+    pub fn from_powers_of_tau_response(
+        bytes: &[u8],
+        tau_powers_log: u32,
+        max_degree: usize,
+    ) -> Result<Self, FastCryptoError> {
+        let tau_powers_length = 1usize
+            .checked_shl(tau_powers_log)
+            .ok_or(FastCryptoError::InvalidInput)?;
+        let g1_powers_length = 2 * tau_powers_length - 1;
+        if max_degree >= g1_powers_length || tau_powers_length < 2 {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let g2_start = RESPONSE_HASH_LENGTH + g1_powers_length * G1_ELEMENT_BYTE_LENGTH;
+        let g2_end = g2_start + 2 * G2_ELEMENT_BYTE_LENGTH;
+        if bytes.len() < g2_end {
+            return Err(FastCryptoError::InputTooShort(g2_end));
+        }
+        let powers_g1 = bytes[RESPONSE_HASH_LENGTH
+            ..RESPONSE_HASH_LENGTH + (max_degree + 1) * G1_ELEMENT_BYTE_LENGTH]
+            .chunks_exact(G1_ELEMENT_BYTE_LENGTH)
+            .map(|p| G1Element::from_byte_array(p.try_into().expect("Length is correct")))
+            .collect::<Result<Vec<_>, _>>()?;
+        let powers_g2 = bytes[g2_start..g2_end]
+            .chunks_exact(G2_ELEMENT_BYTE_LENGTH)
+            .map(|p| G2Element::from_byte_array(p.try_into().expect("Length is correct")))
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::from_powers(powers_g1, &powers_g2)
+    }
+
+    /// The maximal degree of polynomials which can be committed to with these parameters.
+    pub fn max_degree(&self) -> usize {
+        self.powers_g1.len() - 1
+    }
+
+    /// Commit to a polynomial given by its coefficients, starting from the constant term.
+    pub fn commit(&self, polynomial: &[Scalar]) -> Result<Commitment, FastCryptoError> {
+        self.check_degree(polynomial)?;
+        Ok(Commitment(msm(&self.powers_g1, polynomial)))
+    }
+
+    /// Evaluate a polynomial at a point and compute a proof of the evaluation.
+    pub fn open(
+        &self,
+        polynomial: &[Scalar],
+        point: &Scalar,
+    ) -> Result<(Scalar, OpeningProof), FastCryptoError> {
+        self.check_degree(polynomial)?;
+        let (quotient, value) = divide_by_linear(polynomial, point);
+        Ok((value, OpeningProof(msm(&self.powers_g1, &quotient))))
+    }
+
+    /// Verify that a commitment opens to `value` at `point`.
+    pub fn verify(
+        &self,
+        commitment: &Commitment,
+        point: &Scalar,
+        value: &Scalar,
+        proof: &OpeningProof,
+    ) -> Result<(), FastCryptoError> {
+        // e(C - [y]_1 + z π, [1]_2) - e(π, [τ]_2) = 0.
+        let lhs = G1Element::multi_pairing(
+            &[
+                commitment.0 - G1Element::mul_generator(value) + proof.0 * point,
+                -proof.0,
+            ],
+            &[G2Element::generator(), self.tau_g2],
+        )?;
+        if lhs != GTElement::zero() {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        Ok(())
+    }
+
+    /// Evaluate several polynomials at the same point and compute a single proof of all
+    /// evaluations. Returns the values in the order of the polynomials.
+    pub fn batch_open(
+        &self,
+        polynomials: &[Vec<Scalar>],
+        point: &Scalar,
+    ) -> Result<(Vec<Scalar>, OpeningProof), FastCryptoError> {
+        if polynomials.is_empty() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let commitments = polynomials
+            .iter()
+            .map(|p| self.commit(p))
+            .collect::<Result<Vec<_>, _>>()?;
+        let values = polynomials
+            .iter()
+            .map(|p| divide_by_linear(p, point).1)
+            .collect::<Vec<_>>();
+        let gamma = powers(
+            &batch_challenge(&commitments, point, &values),
+            polynomials.len(),
+        );
+
+        let mut combined = vec![Scalar::zero(); self.max_degree() + 1];
+        for (p, g) in polynomials.iter().zip(&gamma) {
+            combined.iter_mut().zip(p).for_each(|(c, x)| *c += *x * g);
+        }
+        let (_, proof) = self.open(&combined, point)?;
+        Ok((values, proof))
+    }
+
+    /// Verify that the commitments open to the respective values at the same point with a proof
+    /// from [KzgParameters::batch_open].
+    pub fn batch_verify(
+        &self,
+        commitments: &[Commitment],
+        point: &Scalar,
+        values: &[Scalar],
+        proof: &OpeningProof,
+    ) -> Result<(), FastCryptoError> {
+        if commitments.is_empty() || commitments.len() != values.len() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let gamma = powers(
+            &batch_challenge(commitments, point, values),
+            commitments.len(),
+        );
+        let commitment = Commitment(msm(
+            &commitments.iter().map(|c| c.0).collect::<Vec<_>>(),
+            &gamma,
+        ));
+        let value = values
+            .iter()
+            .zip(&gamma)
+            .fold(Scalar::zero(), |acc, (v, g)| acc + *v * g);
+        self.verify(&commitment, point, &value, proof)
+    }
+
+    fn check_degree(&self, polynomial: &[Scalar]) -> Result<(), FastCryptoError> {
+        if polynomial.len() > self.powers_g1.len() {
+            return Err(FastCryptoError::InputTooLong(self.powers_g1.len()));
+        }
+        Ok(())
+    }
+}
+
+fn batch_challenge(commitments: &[Commitment], point: &Scalar, values: &[Scalar]) -> Scalar {
+    let mut transcript = vec![BATCH_DST.to_vec(), point.to_byte_array().to_vec()];
+    transcript.extend(commitments.iter().map(|c| c.0.to_byte_array().to_vec()));
+    transcript.extend(values.iter().map(|v| v.to_byte_array().to_vec()));
+    hash_to_scalar(&transcript)
+}
+
+fn hash_to_scalar(inputs: &[Vec<u8>]) -> Scalar {
+    let mut hash = Sha256::default();
+    for input in inputs {
+        hash.update((input.len() as u64).to_be_bytes());
+        hash.update(input);
+    }
+    Scalar::rand(&mut StdRng::from_seed(hash.finalize().digest))
+}
+
+fn point_from_hex<G: ToFromByteArray<N>, const N: usize>(hex: &str) -> Result<G, FastCryptoError> {
+    let bytes = hex::decode(hex.strip_prefix("0x").unwrap_or(hex))
+        .map_err(|_| FastCryptoError::InvalidInput)?;
+    G::from_byte_array(
+        &bytes
+            .try_into()
+            .map_err(|_| FastCryptoError::InputLengthWrong(N))?,
+    )
+}
+
+/// Compute `sum_j coefficients[j] * bases[j]`.
+fn msm<G: GroupElement<ScalarType = Scalar>>(bases: &[G], coefficients: &[Scalar]) -> G {
+    bases
+        .iter()
+        .zip(coefficients.iter())
+        .fold(G::zero(), |acc, (b, c)| acc + *b * c)
+}
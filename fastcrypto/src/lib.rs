@@ -8,26 +8,48 @@
     rust_2021_compatibility
 )]
 
+// The fips feature removes the algorithms which are not approved by FIPS 140-3 from the crate, so
+// using them is a compile-time error. Features which only contain such algorithms are rejected.
+#[cfg(all(
+    feature = "fips",
+    any(
+        feature = "bls12381",
+        feature = "secp256k1",
+        feature = "experimental",
+        feature = "unsecure_schemes",
+        feature = "paillier"
+    )
+))]
+compile_error!(
+    "The fips feature can only be combined with the secp256r1, rsa and copy_key features. \
+     Disable the default features of fastcrypto to use it."
+);
+
 #[cfg(test)]
 #[path = "tests/ed25519_tests.rs"]
 pub mod ed25519_tests;
 
+#[cfg(feature = "secp256k1")]
 #[cfg(test)]
 #[path = "tests/secp256k1_tests.rs"]
 pub mod secp256k1_tests;
 
+#[cfg(feature = "secp256k1")]
 #[cfg(test)]
 #[path = "tests/secp256k1_recoverable_tests.rs"]
 pub mod secp256k1_recoverable_tests;
 
+#[cfg(all(feature = "secp256k1", feature = "secp256r1"))]
 #[cfg(test)]
 #[path = "tests/secp256r1_tests.rs"]
 pub mod secp256r1_tests;
 
+#[cfg(all(feature = "secp256k1", feature = "secp256r1"))]
 #[cfg(test)]
 #[path = "tests/secp256r1_recoverable_tests.rs"]
 pub mod secp256r1_recoverable_tests;
 
+#[cfg(feature = "bls12381")]
 #[cfg(test)]
 #[path = "tests/bls12381_tests.rs"]
 pub mod bls12381_tests;
@@ -57,22 +79,236 @@ pub mod encoding_tests;
 #[path = "tests/mskr_tests.rs"]
 pub mod mskr_tests;
 
+#[cfg(not(feature = "fips"))]
 #[cfg(test)]
 #[path = "tests/ristretto255_tests.rs"]
 pub mod ristretto255_tests;
 
+#[cfg(feature = "bls12381")]
 #[cfg(test)]
 #[path = "tests/bls12381_group_tests.rs"]
 pub mod bls12381_group_tests;
 
+#[cfg(feature = "bls12381")]
+#[cfg(test)]
+#[path = "tests/pedersen_tests.rs"]
+pub mod pedersen_tests;
+
+#[cfg(feature = "secp256k1")]
+#[cfg(test)]
+#[path = "tests/secp256k1_group_tests.rs"]
+pub mod secp256k1_group_tests;
+
+#[cfg(feature = "secp256r1")]
+#[cfg(test)]
+#[path = "tests/secp256r1_group_tests.rs"]
+pub mod secp256r1_group_tests;
+
+#[cfg(feature = "secp256k1")]
+#[cfg(test)]
+#[path = "tests/adaptor_signatures_tests.rs"]
+pub mod adaptor_signatures_tests;
+
+#[cfg(feature = "bls12381")]
+#[cfg(test)]
+#[path = "tests/blind_signatures_tests.rs"]
+pub mod blind_signatures_tests;
+
+#[cfg(not(feature = "fips"))]
+#[cfg(test)]
+#[path = "tests/linkable_ring_signatures_tests.rs"]
+pub mod linkable_ring_signatures_tests;
+
+#[cfg(not(feature = "fips"))]
+#[cfg(test)]
+#[path = "tests/oprf_tests.rs"]
+pub mod oprf_tests;
+
+#[cfg(not(feature = "fips"))]
+#[cfg(test)]
+#[path = "tests/opaque_tests.rs"]
+pub mod opaque_tests;
+
+#[cfg(test)]
+#[path = "tests/srp_tests.rs"]
+pub mod srp_tests;
+
+#[cfg(test)]
+#[path = "tests/noise_tests.rs"]
+pub mod noise_tests;
+
+#[cfg(test)]
+#[path = "tests/paillier_tests.rs"]
+pub mod paillier_tests;
+
+#[cfg(test)]
+#[path = "tests/class_group_tests.rs"]
+pub mod class_group_tests;
+
+#[cfg(test)]
+#[path = "tests/cl_encryption_tests.rs"]
+pub mod cl_encryption_tests;
+
+#[cfg(test)]
+#[path = "tests/rsa_accumulator_tests.rs"]
+pub mod rsa_accumulator_tests;
+
+#[cfg(feature = "bls12381")]
+#[cfg(test)]
+#[path = "tests/vector_commitment_tests.rs"]
+pub mod vector_commitment_tests;
+
+#[cfg(test)]
+#[path = "tests/forward_secure_tests.rs"]
+pub mod forward_secure_tests;
+
+#[cfg(feature = "secp256k1")]
+#[cfg(test)]
+#[path = "tests/one_time_signer_tests.rs"]
+pub mod one_time_signer_tests;
+
+#[cfg(test)]
+#[path = "tests/vdf_tests.rs"]
+pub mod vdf_tests;
+
+#[cfg(all(feature = "bls12381", feature = "secp256k1", feature = "secp256r1"))]
+#[cfg(test)]
+#[path = "tests/key_ownership_tests.rs"]
+pub mod key_ownership_tests;
+
+#[cfg(feature = "bls12381")]
+#[cfg(test)]
+#[path = "tests/aggregate_verification_tests.rs"]
+pub mod aggregate_verification_tests;
+
+#[cfg(feature = "bls12381")]
+#[cfg(test)]
+#[path = "tests/certificate_codec_tests.rs"]
+pub mod certificate_codec_tests;
+
+#[cfg(test)]
+#[path = "tests/revocation_registry_tests.rs"]
+pub mod revocation_registry_tests;
+
+#[cfg(feature = "bls12381")]
+#[cfg(test)]
+#[path = "tests/bbs_plus_tests.rs"]
+pub mod bbs_plus_tests;
+
+#[cfg(feature = "bls12381")]
+#[cfg(test)]
+#[path = "tests/pairing_product_tests.rs"]
+pub mod pairing_product_tests;
+
+#[cfg(test)]
+#[path = "tests/policy_tests.rs"]
+pub mod policy_tests;
+
+#[cfg(feature = "bls12381")]
+#[cfg(test)]
+#[path = "tests/polynomial_tests.rs"]
+pub mod polynomial_tests;
+
+#[cfg(test)]
+#[path = "tests/limits_tests.rs"]
+pub mod limits_tests;
+
+#[cfg(test)]
+#[path = "tests/hash_commitment_tests.rs"]
+pub mod hash_commitment_tests;
+
+#[cfg(test)]
+#[path = "tests/sortition_tests.rs"]
+pub mod sortition_tests;
+
+#[cfg(test)]
+#[path = "tests/time_lock_tests.rs"]
+pub mod time_lock_tests;
+
+#[cfg(feature = "bls12381")]
+#[cfg(test)]
+#[path = "tests/drand_tests.rs"]
+pub mod drand_tests;
+
+#[cfg(not(feature = "fips"))]
+#[cfg(test)]
+#[path = "tests/batch_hash_tests.rs"]
+pub mod batch_hash_tests;
+
+#[cfg(all(test, feature = "secp256k1", feature = "secp256r1"))]
+#[path = "tests/verify_stream_tests.rs"]
+pub mod verify_stream_tests;
+
+#[cfg(all(
+    test,
+    feature = "bls12381",
+    feature = "secp256k1",
+    feature = "secp256r1"
+))]
+#[path = "tests/envelope_tests.rs"]
+pub mod envelope_tests;
+
+#[cfg(feature = "bls12381")]
+#[cfg(test)]
+#[path = "tests/kzg_tests.rs"]
+pub mod kzg_tests;
+
+#[cfg(all(test, feature = "secp256r1"))]
+#[path = "tests/sshsig_tests.rs"]
+pub mod sshsig_tests;
+
+#[cfg(not(feature = "fips"))]
+#[cfg(test)]
+#[path = "tests/minisign_tests.rs"]
+pub mod minisign_tests;
+
+#[cfg(feature = "bls12381")]
+#[cfg(test)]
+#[path = "tests/custody_tests.rs"]
+pub mod custody_tests;
+
+#[cfg(feature = "bls12381")]
+#[cfg(test)]
+#[path = "tests/reed_solomon_tests.rs"]
+pub mod reed_solomon_tests;
+
+#[cfg(test)]
+#[path = "tests/key_ratchet_tests.rs"]
+pub mod key_ratchet_tests;
+
+#[cfg(test)]
+#[path = "tests/double_ratchet_tests.rs"]
+pub mod double_ratchet_tests;
+
+#[cfg(feature = "bls12381")]
+#[cfg(test)]
+#[path = "tests/sampled_certificate_tests.rs"]
+pub mod sampled_certificate_tests;
+
+#[cfg(not(feature = "fips"))]
 #[cfg(test)]
 #[path = "tests/vrf_tests.rs"]
 pub mod vrf_tests;
 
+#[cfg(test)]
+#[path = "tests/self_test_tests.rs"]
+pub mod self_test_tests;
+
+#[cfg(feature = "bls12381")]
 #[cfg(test)]
 #[path = "tests/signature_service_tests.rs"]
 pub mod signature_service_tests;
 
+#[cfg(feature = "bls12381")]
+#[cfg(test)]
+#[path = "tests/verification_service_tests.rs"]
+pub mod verification_service_tests;
+
+#[cfg(not(feature = "fips"))]
+#[cfg(test)]
+#[path = "tests/verifiable_encryption_tests.rs"]
+pub mod verifiable_encryption_tests;
+
 #[cfg(test)]
 #[path = "tests/test_helpers.rs"]
 pub mod test_helpers;
@@ -83,24 +319,111 @@ pub mod utils_tests;
 
 pub mod traits;
 
+#[cfg(all(feature = "secp256k1", any(test, feature = "experimental")))]
+pub mod adaptor_signatures;
 #[cfg(any(test, feature = "experimental"))]
 pub mod aes;
+#[cfg(any(test, feature = "experimental"))]
+pub mod aggregate_verification;
+#[cfg(all(not(feature = "fips"), any(test, feature = "experimental")))]
+pub mod batch_hash;
+#[cfg(all(feature = "bls12381", any(test, feature = "experimental")))]
+pub mod bbs_plus;
+#[cfg(all(feature = "bls12381", any(test, feature = "experimental")))]
+pub mod blind_signatures;
+#[cfg(feature = "bls12381")]
 pub mod bls12381;
 #[cfg(any(test, feature = "experimental"))]
 pub mod bulletproofs;
+#[cfg(any(test, feature = "experimental"))]
+pub mod certificate_codec;
+#[cfg(any(test, feature = "experimental"))]
+pub mod cl_encryption;
+#[cfg(any(test, feature = "experimental"))]
+pub mod class_group;
+#[cfg(all(feature = "bls12381", any(test, feature = "experimental")))]
+pub mod custody;
+#[cfg(any(test, feature = "experimental"))]
+pub mod double_ratchet;
+#[cfg(all(feature = "bls12381", any(test, feature = "experimental")))]
+pub mod drand;
 pub mod ed25519;
 pub mod encoding;
+#[cfg(any(test, feature = "experimental"))]
+pub mod envelope;
 pub mod error;
+#[cfg(any(test, feature = "experimental"))]
+pub mod forward_secure;
 pub mod groups;
 pub mod hash;
+#[cfg(any(test, feature = "experimental"))]
+pub mod hash_commitment;
 pub mod hmac;
+#[cfg(any(test, feature = "experimental"))]
+pub mod key_ownership;
+#[cfg(any(test, feature = "experimental"))]
+pub mod key_ratchet;
+#[cfg(all(feature = "bls12381", any(test, feature = "experimental")))]
+pub mod kzg;
+pub mod limits;
+#[cfg(all(not(feature = "fips"), any(test, feature = "experimental")))]
+pub mod linkable_ring_signatures;
+#[cfg(all(not(feature = "fips"), any(test, feature = "experimental")))]
+pub mod minisign;
+#[cfg(any(test, feature = "experimental"))]
+pub mod noise;
+#[cfg(any(test, feature = "experimental", feature = "paillier"))]
+pub(crate) mod number_theory;
+#[cfg(any(test, feature = "experimental"))]
+pub mod one_time_signer;
+#[cfg(all(not(feature = "fips"), any(test, feature = "experimental")))]
+pub mod opaque;
+#[cfg(all(not(feature = "fips"), any(test, feature = "experimental")))]
+pub mod oprf;
+#[cfg(any(test, feature = "paillier"))]
+pub mod paillier;
+#[cfg(any(test, feature = "experimental"))]
+pub mod pairing_product;
+pub mod policy;
+#[cfg(any(test, feature = "experimental"))]
+pub mod polynomial;
 pub mod private_seed;
+#[cfg(all(feature = "bls12381", any(test, feature = "experimental")))]
+pub mod reed_solomon;
+#[cfg(any(test, feature = "experimental"))]
+pub mod revocation_registry;
+#[cfg(feature = "rsa")]
 pub mod rsa;
+#[cfg(any(test, feature = "experimental"))]
+pub mod rsa_accumulator;
+#[cfg(all(not(feature = "fips"), any(test, feature = "experimental")))]
+pub mod sampled_certificate;
+#[cfg(feature = "secp256k1")]
 pub mod secp256k1;
+#[cfg(feature = "secp256r1")]
 pub mod secp256r1;
+pub mod self_test;
 pub mod serde_helpers;
 pub mod signature_service;
+#[cfg(any(test, feature = "experimental"))]
+pub mod sortition;
+#[cfg(any(test, feature = "experimental"))]
+pub mod srp;
+#[cfg(any(test, feature = "experimental"))]
+pub mod sshsig;
+#[cfg(any(test, feature = "experimental"))]
+pub mod time_lock;
 pub mod utils;
+#[cfg(any(test, feature = "experimental"))]
+pub mod vdf;
+#[cfg(all(feature = "bls12381", any(test, feature = "experimental")))]
+pub mod vector_commitment;
+#[cfg(all(not(feature = "fips"), any(test, feature = "experimental")))]
+pub mod verifiable_encryption;
+pub mod verification_service;
+#[cfg(any(test, feature = "experimental"))]
+pub mod verify_stream;
+#[cfg(not(feature = "fips"))]
 pub mod vrf;
 
 /// This module contains unsecure cryptographic primitives. The purpose of this library is to allow seamless
@@ -0,0 +1,149 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Process-wide limits on the sizes of untrusted inputs, so that parsing or verifying an input
+//! cannot trigger an unbounded allocation or computation.
+//!
+//! The limits are checked before anything is allocated for an input, and exceeding a limit fails
+//! with [FastCryptoError::InputTooLong] of the maximum. They currently cover:
+//! * [Limit::PublicInputs]: The public inputs of proofs parsed by `fastcrypto-zkp`.
+//! * [Limit::AggregateParticipants]: The signatures verified by
+//!   [verify_in_chunks](crate::aggregate_verification::verify_in_chunks), the commitments of
+//!   aggregated [range proofs](crate::bulletproofs) and the proofs of SnarkPack aggregates in
+//!   `fastcrypto-zkp`.
+//! * [Limit::ProofBytes]: Serialized proofs parsed by `fastcrypto-zkp`.
+//! * [Limit::MerkleProofDepth]: The siblings of Merkle proofs of [crate::custody] (requires the
+//!   `experimental` feature).
+//!
+//! The defaults are generous for honest inputs, see [Limit::default_maximum].
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::limits::*;
+//! # use fastcrypto::error::FastCryptoError;
+//! let limits = Limits::default().with_maximum(Limit::PublicInputs, 16);
+//! assert!(limits.check(Limit::PublicInputs, 16).is_ok());
+//! assert_eq!(
+//!     limits.check(Limit::PublicInputs, 17),
+//!     Err(FastCryptoError::InputTooLong(16))
+//! );
+//! install(limits);
+//! ```
+
+use crate::error::{FastCryptoError, FastCryptoResult};
+use once_cell::sync::Lazy;
+use std::fmt::{Display, Formatter};
+use std::sync::RwLock;
+
+/// The sizes which are limited.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Limit {
+    /// The number of public inputs of a proof.
+    PublicInputs,
+    /// The number of signatures or proofs in an aggregate.
+    AggregateParticipants,
+    /// The length in bytes of a serialized proof.
+    ProofBytes,
+    /// The number of siblings in a Merkle proof.
+    MerkleProofDepth,
+}
+
+impl Limit {
+    /// The maximum of this limit unless another one is installed.
+    pub fn default_maximum(&self) -> usize {
+        match self {
+            Limit::PublicInputs => 1 << 10,
+            Limit::AggregateParticipants => 1 << 16,
+            Limit::ProofBytes => 1 << 20,
+            Limit::MerkleProofDepth => 64,
+        }
+    }
+}
+
+impl Display for Limit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Limit::PublicInputs => write!(f, "public inputs"),
+            Limit::AggregateParticipants => write!(f, "aggregate participants"),
+            Limit::ProofBytes => write!(f, "proof bytes"),
+            Limit::MerkleProofDepth => write!(f, "Merkle proof depth"),
+        }
+    }
+}
+
+/// The maxima of all limits.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Limits {
+    public_inputs: usize,
+    aggregate_participants: usize,
+    proof_bytes: usize,
+    merkle_proof_depth: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            public_inputs: Limit::PublicInputs.default_maximum(),
+            aggregate_participants: Limit::AggregateParticipants.default_maximum(),
+            proof_bytes: Limit::ProofBytes.default_maximum(),
+            merkle_proof_depth: Limit::MerkleProofDepth.default_maximum(),
+        }
+    }
+}
+
+impl Limits {
+    /// Set the maximum of the given limit.
+    pub fn with_maximum(mut self, limit: Limit, maximum: usize) -> Self {
+        *self.maximum_mut(limit) = maximum;
+        self
+    }
+
+    pub fn maximum(&self, limit: Limit) -> usize {
+        match limit {
+            Limit::PublicInputs => self.public_inputs,
+            Limit::AggregateParticipants => self.aggregate_participants,
+            Limit::ProofBytes => self.proof_bytes,
+            Limit::MerkleProofDepth => self.merkle_proof_depth,
+        }
+    }
+
+    /// Check whether the given size is within the given limit.
+    pub fn check(&self, limit: Limit, size: usize) -> FastCryptoResult<()> {
+        let maximum = self.maximum(limit);
+        if size > maximum {
+            return Err(FastCryptoError::InputTooLong(maximum));
+        }
+        Ok(())
+    }
+
+    fn maximum_mut(&mut self, limit: Limit) -> &mut usize {
+        match limit {
+            Limit::PublicInputs => &mut self.public_inputs,
+            Limit::AggregateParticipants => &mut self.aggregate_participants,
+            Limit::ProofBytes => &mut self.proof_bytes,
+            Limit::MerkleProofDepth => &mut self.merkle_proof_depth,
+        }
+    }
+}
+
+static LIMITS: Lazy<RwLock<Limits>> = Lazy::new(|| RwLock::new(Limits::default()));
+
+/// Replace the limits of this process.
+pub fn install(limits: Limits) {
+    *LIMITS.write().expect("Lock is not poisoned") = limits;
+}
+
+/// The limits of this process.
+pub fn current() -> Limits {
+    LIMITS.read().expect("Lock is not poisoned").clone()
+}
+
+/// Check a size against the limits of this process. This is called by the parsers of this crate
+/// and of `fastcrypto-zkp`.
+pub fn enforce(limit: Limit, size: usize) -> FastCryptoResult<()> {
+    LIMITS
+        .read()
+        .expect("Lock is not poisoned")
+        .check(limit, size)
+}
@@ -0,0 +1,203 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Linkable ring signatures over Ristretto255.
+//!
+//! A ring signature proves that the signer holds the private key of one of the public keys in a
+//! ring without revealing which one. The scheme implemented here is LSAG (Liu, Wei and Wong,
+//! ["Linkable Spontaneous Anonymous Group Signature for Ad Hoc Groups"](https://eprint.iacr.org/2004/027)),
+//! where every signature contains a [KeyImage] which is determined by the private key of the signer.
+//! Two signatures created with the same private key have the same key image, even if the rings they
+//! were created for differ, which allows detecting double-signing, e.g. in anonymous voting.
+//!
+//! # Batch verification
+//! This module does not support batch verification. LSAG signatures are verified by recomputing a
+//! chain of challenges around the ring where each challenge is the hash of group elements computed
+//! from the previous one, so the checks of several signatures cannot be combined into a single
+//! multi-scalar multiplication the way Schnorr-style signatures can. Callers verifying several
+//! signatures must call [RingSignature::verify] on each of them, which is no slower than a batch
+//! API built on top of it would be. Batch verification would need another ring signature scheme.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::linkable_ring_signatures::{PrivateKey, PublicKey, RingSignature};
+//! # use rand::thread_rng;
+//! let private_keys = (0..4).map(|_| PrivateKey::generate(&mut thread_rng())).collect::<Vec<_>>();
+//! let ring = private_keys.iter().map(PublicKey::from).collect::<Vec<_>>();
+//!
+//! let signature = RingSignature::sign(b"Hello, world!", &ring, &private_keys[2], &mut thread_rng()).unwrap();
+//! assert!(signature.verify(b"Hello, world!", &ring).is_ok());
+//!
+//! let other = RingSignature::sign(b"Goodbye!", &ring, &private_keys[2], &mut thread_rng()).unwrap();
+//! assert_eq!(signature.key_image(), other.key_image());
+//! ```
+
+use crate::error::FastCryptoError;
+use crate::groups::ristretto255::{RistrettoPoint, RistrettoScalar};
+use crate::groups::{FixedBaseMultiplication, GroupElement, HashToGroupElement, Scalar};
+use crate::serde_helpers::ToFromByteArray;
+use crate::traits::AllowedRng;
+use serde::{Deserialize, Serialize};
+
+/// Domain separation tag used when computing challenges.
+const CHALLENGE_DST: &[u8] = b"FASTCRYPTO_LSAG_RISTRETTO255_CHALLENGE_V1";
+
+/// Domain separation tag used when hashing public keys to points.
+const KEY_IMAGE_DST: &[u8] = b"FASTCRYPTO_LSAG_RISTRETTO255_KEY_IMAGE_V1";
+
+/// A private key for linkable ring signatures.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrivateKey(RistrettoScalar);
+
+/// A public key for linkable ring signatures.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublicKey(RistrettoPoint);
+
+/// The key image `I = x H_p(P)` of a signer with private key `x` and public key `P`. Signatures with
+/// the same key image are created by the same signer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct KeyImage([u8; 32]);
+
+/// A linkable ring signature. It consists of a single challenge, one response for each member of
+/// the ring and the key image of the signer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RingSignature {
+    challenge: RistrettoScalar,
+    responses: Vec<RistrettoScalar>,
+    key_image: KeyImage,
+}
+
+impl PrivateKey {
+    /// Generate a new private key.
+    pub fn generate<R: AllowedRng>(rng: &mut R) -> Self {
+        PrivateKey(RistrettoScalar::rand(rng))
+    }
+}
+
+impl From<&PrivateKey> for PublicKey {
+    fn from(private_key: &PrivateKey) -> Self {
+        PublicKey(RistrettoPoint::mul_generator(&private_key.0))
+    }
+}
+
+fn hash_to_point(public_key: &PublicKey) -> RistrettoPoint {
+    let mut input = KEY_IMAGE_DST.to_vec();
+    input.extend_from_slice(&public_key.0.to_byte_array());
+    RistrettoPoint::hash_to_group_element(&input)
+}
+
+/// The part of the challenge input which is the same for all members of the ring.
+fn challenge_prefix(message: &[u8], ring: &[PublicKey], key_image: &KeyImage) -> Vec<u8> {
+    let mut prefix = CHALLENGE_DST.to_vec();
+    prefix.extend_from_slice(&(ring.len() as u64).to_le_bytes());
+    ring.iter()
+        .for_each(|pk| prefix.extend_from_slice(&pk.0.to_byte_array()));
+    prefix.extend_from_slice(&key_image.0);
+    prefix.extend_from_slice(&(message.len() as u64).to_le_bytes());
+    prefix.extend_from_slice(message);
+    prefix
+}
+
+fn challenge(prefix: &[u8], l: &RistrettoPoint, r: &RistrettoPoint) -> RistrettoScalar {
+    let mut input = prefix.to_vec();
+    input.extend_from_slice(&l.to_byte_array());
+    input.extend_from_slice(&r.to_byte_array());
+    RistrettoScalar::hash_to_group_element(&input)
+}
+
+impl RingSignature {
+    /// Sign a message with the given private key. The public key of the signer must be in the ring.
+    pub fn sign<R: AllowedRng>(
+        message: &[u8],
+        ring: &[PublicKey],
+        private_key: &PrivateKey,
+        rng: &mut R,
+    ) -> Result<Self, FastCryptoError> {
+        let public_key = PublicKey::from(private_key);
+        let index = ring
+            .iter()
+            .position(|pk| *pk == public_key)
+            .ok_or(FastCryptoError::InvalidInput)?;
+        let n = ring.len();
+
+        let h = hash_to_point(&public_key);
+        let key_image_point = h * private_key.0;
+        let key_image = KeyImage(key_image_point.to_byte_array());
+        let prefix = challenge_prefix(message, ring, &key_image);
+
+        let mut challenges = vec![RistrettoScalar::zero(); n];
+        let mut responses = (0..n)
+            .map(|_| RistrettoScalar::rand(rng))
+            .collect::<Vec<_>>();
+
+        // Start the chain at the signer using a random nonce and go around the ring.
+        let alpha = RistrettoScalar::rand(rng);
+        challenges[(index + 1) % n] = challenge(
+            &prefix,
+            &(RistrettoPoint::mul_generator(&alpha)),
+            &(h * alpha),
+        );
+        for offset in 1..n {
+            let i = (index + offset) % n;
+            let (l, r) =
+                Self::commitments(&ring[i], &key_image_point, &challenges[i], &responses[i]);
+            challenges[(i + 1) % n] = challenge(&prefix, &l, &r);
+        }
+
+        // Close the ring.
+        responses[index] = alpha - challenges[index] * private_key.0;
+
+        Ok(Self {
+            challenge: challenges[0],
+            responses,
+            key_image,
+        })
+    }
+
+    /// Verify this signature on a message for the given ring. See the module documentation for why
+    /// there is no batch verification.
+    pub fn verify(&self, message: &[u8], ring: &[PublicKey]) -> Result<(), FastCryptoError> {
+        if ring.is_empty() || ring.len() != self.responses.len() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let key_image_point = RistrettoPoint::from_byte_array(&self.key_image.0)
+            .map_err(|_| FastCryptoError::InvalidSignature)?;
+        if key_image_point == RistrettoPoint::zero() {
+            return Err(FastCryptoError::InvalidSignature);
+        }
+
+        let prefix = challenge_prefix(message, ring, &self.key_image);
+        let mut c = self.challenge;
+        for (public_key, response) in ring.iter().zip(self.responses.iter()) {
+            let (l, r) = Self::commitments(public_key, &key_image_point, &c, response);
+            c = challenge(&prefix, &l, &r);
+        }
+        if c != self.challenge {
+            return Err(FastCryptoError::InvalidSignature);
+        }
+        Ok(())
+    }
+
+    /// The key image of the signer.
+    pub fn key_image(&self) -> &KeyImage {
+        &self.key_image
+    }
+
+    /// Returns true if this signature and the other signature were created with the same private
+    /// key. Both signatures should be verified before calling this.
+    pub fn is_linked(&self, other: &RingSignature) -> bool {
+        self.key_image == other.key_image
+    }
+
+    /// Compute the commitments `L = sG + cP` and `R = sH_p(P) + cI` for a member of the ring.
+    fn commitments(
+        public_key: &PublicKey,
+        key_image: &RistrettoPoint,
+        challenge: &RistrettoScalar,
+        response: &RistrettoScalar,
+    ) -> (RistrettoPoint, RistrettoPoint) {
+        let l = RistrettoPoint::mul_generator(response) + public_key.0 * challenge;
+        let r = hash_to_point(public_key) * response + *key_image * challenge;
+        (l, r)
+    }
+}
@@ -0,0 +1,370 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Ed25519 signatures in the [minisign](https://jedisct1.github.io/minisign/) format, which is
+//! used to sign release artifacts and can also be verified by `minisign -V` and `rsign`.
+//!
+//! A signature file has four lines:
+//! ```text
+//! untrusted comment: <comment>
+//! <base64 of the algorithm, the key id and the signature of the message>
+//! trusted comment: <comment>
+//! <base64 of the global signature of the signature and the trusted comment>
+//! ```
+//! The trusted comment, typically a timestamp and a file name, is signed together with the
+//! signature, whereas the untrusted comment can be changed by anyone. New signatures sign the
+//! BLAKE2b-512 digest of the message, but legacy signatures over the message itself are also
+//! verified.
+//!
+//! Only the public key format is supported for keys. Secret key files, which are encrypted with a
+//! password, must be decrypted by other tools and given as an [Ed25519KeyPair] and a key id.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::minisign::*;
+//! # use rand::thread_rng;
+//! let key_pair = MinisignKeyPair::generate(&mut thread_rng());
+//! let public_key = MinisignPublicKey::decode(&key_pair.public().encode()).unwrap();
+//!
+//! let message = b"release-1.0.tar.gz";
+//! let signature = key_pair
+//!     .sign(message, "timestamp:1700000000\tfile:release-1.0.tar.gz\thashed")
+//!     .unwrap();
+//! let signature = MinisignSignature::decode(&signature.encode()).unwrap();
+//! assert!(signature.verify(&public_key, message).is_ok());
+//! assert_eq!(
+//!     signature.trusted_comment(),
+//!     "timestamp:1700000000\tfile:release-1.0.tar.gz\thashed"
+//! );
+//! ```
+
+use crate::ed25519::{
+    Ed25519KeyPair, Ed25519PublicKey, Ed25519Signature, ED25519_PUBLIC_KEY_LENGTH,
+    ED25519_SIGNATURE_LENGTH,
+};
+use crate::error::{FastCryptoError, FastCryptoResult};
+use crate::hash::Blake2b512;
+use crate::traits::{AllowedRng, KeyPair, Signer, ToFromBytes, VerifyingKey};
+use crate::verify_stream::hash_reader;
+use base64ct::{Base64, Encoding};
+use std::io::Read;
+
+/// The length of the key ids which identify the key of a signature.
+pub const MINISIGN_KEY_ID_LENGTH: usize = 8;
+
+/// The identifier of the signature scheme of public keys.
+const KEY_ALGORITHM: &[u8; 2] = b"Ed";
+
+const UNTRUSTED_COMMENT_PREFIX: &str = "untrusted comment: ";
+const TRUSTED_COMMENT_PREFIX: &str = "trusted comment: ";
+const DEFAULT_SIGNATURE_COMMENT: &str = "signature from minisign secret key";
+
+/// The key id of a key, which is an arbitrary identifier chosen when the key is generated.
+pub type MinisignKeyId = [u8; MINISIGN_KEY_ID_LENGTH];
+
+/// A minisign public key, i.e., an Ed25519 public key with a key id.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MinisignPublicKey {
+    key_id: MinisignKeyId,
+    public_key: Ed25519PublicKey,
+}
+
+impl MinisignPublicKey {
+    pub fn new(key_id: MinisignKeyId, public_key: Ed25519PublicKey) -> Self {
+        Self { key_id, public_key }
+    }
+
+    pub fn key_id(&self) -> &MinisignKeyId {
+        &self.key_id
+    }
+
+    pub fn public_key(&self) -> &Ed25519PublicKey {
+        &self.public_key
+    }
+
+    /// Parse a public key given as a base64 encoded line, as given to `minisign -P`.
+    pub fn from_base64(encoded: &str) -> FastCryptoResult<Self> {
+        let bytes = decode_line(
+            encoded,
+            2 + MINISIGN_KEY_ID_LENGTH + ED25519_PUBLIC_KEY_LENGTH,
+        )?;
+        let (algorithm, rest) = bytes.split_at(2);
+        let (key_id, public_key) = rest.split_at(MINISIGN_KEY_ID_LENGTH);
+        if algorithm != KEY_ALGORITHM {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(Self {
+            key_id: key_id.try_into().expect("Length is 8"),
+            public_key: Ed25519PublicKey::from_bytes(public_key)?,
+        })
+    }
+
+    /// The base64 encoded line of this public key.
+    pub fn to_base64(&self) -> String {
+        Base64::encode_string(
+            &[
+                &KEY_ALGORITHM[..],
+                &self.key_id[..],
+                self.public_key.as_ref(),
+            ]
+            .concat(),
+        )
+    }
+
+    /// Parse the contents of a public key file, i.e., an untrusted comment and the base64 encoded
+    /// line. A single line without comment is also accepted.
+    pub fn decode(contents: &str) -> FastCryptoResult<Self> {
+        match contents.lines().collect::<Vec<_>>().as_slice() {
+            [encoded] => Self::from_base64(encoded),
+            [comment, encoded] if comment.starts_with(UNTRUSTED_COMMENT_PREFIX) => {
+                Self::from_base64(encoded)
+            }
+            _ => Err(FastCryptoError::InvalidInput),
+        }
+    }
+
+    /// The contents of a public key file for this key, with the comment written by minisign.
+    pub fn encode(&self) -> String {
+        format!(
+            "{}minisign public key {}\n{}\n",
+            UNTRUSTED_COMMENT_PREFIX,
+            key_id_to_hex(&self.key_id),
+            self.to_base64()
+        )
+    }
+}
+
+/// An Ed25519 key pair with a key id, which creates signatures in the minisign format.
+pub struct MinisignKeyPair {
+    key_id: MinisignKeyId,
+    key_pair: Ed25519KeyPair,
+}
+
+impl MinisignKeyPair {
+    pub fn new(key_id: MinisignKeyId, key_pair: Ed25519KeyPair) -> Self {
+        Self { key_id, key_pair }
+    }
+
+    /// Generate a new key pair with a random key id.
+    pub fn generate<R: AllowedRng>(rng: &mut R) -> Self {
+        let mut key_id = [0u8; MINISIGN_KEY_ID_LENGTH];
+        rng.fill_bytes(&mut key_id);
+        Self::new(key_id, Ed25519KeyPair::generate(rng))
+    }
+
+    pub fn public(&self) -> MinisignPublicKey {
+        MinisignPublicKey::new(self.key_id, self.key_pair.public().clone())
+    }
+
+    /// Sign the BLAKE2b-512 digest of `message` together with the given trusted comment. Fails if
+    /// the comment has more than one line.
+    pub fn sign(
+        &self,
+        message: &[u8],
+        trusted_comment: &str,
+    ) -> FastCryptoResult<MinisignSignature> {
+        self.sign_reader(message, trusted_comment)
+    }
+
+    /// Sign the BLAKE2b-512 digest of the message read from `reader` together with the given
+    /// trusted comment. Fails if reading fails or if the comment has more than one line.
+    pub fn sign_reader<R: Read>(
+        &self,
+        reader: R,
+        trusted_comment: &str,
+    ) -> FastCryptoResult<MinisignSignature> {
+        check_comment(trusted_comment)?;
+        let digest = hash_reader::<Blake2b512, 64, R>(reader)?;
+        let signature = self.key_pair.sign(&digest);
+        let global_signature = self
+            .key_pair
+            .sign(&global_data(&signature, trusted_comment));
+        Ok(MinisignSignature {
+            algorithm: MinisignAlgorithm::Prehashed,
+            key_id: self.key_id,
+            signature,
+            untrusted_comment: DEFAULT_SIGNATURE_COMMENT.to_string(),
+            trusted_comment: trusted_comment.to_string(),
+            global_signature,
+        })
+    }
+}
+
+/// The message which is signed by a signature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MinisignAlgorithm {
+    /// The message itself, as created by minisign before version 0.10.
+    Legacy,
+    /// The BLAKE2b-512 digest of the message.
+    Prehashed,
+}
+
+impl MinisignAlgorithm {
+    fn identifier(&self) -> &'static [u8; 2] {
+        match self {
+            MinisignAlgorithm::Legacy => b"Ed",
+            MinisignAlgorithm::Prehashed => b"ED",
+        }
+    }
+
+    fn from_identifier(identifier: &[u8]) -> FastCryptoResult<Self> {
+        match identifier {
+            b"Ed" => Ok(MinisignAlgorithm::Legacy),
+            b"ED" => Ok(MinisignAlgorithm::Prehashed),
+            _ => Err(FastCryptoError::InvalidInput),
+        }
+    }
+}
+
+/// A signature in the minisign format.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MinisignSignature {
+    algorithm: MinisignAlgorithm,
+    key_id: MinisignKeyId,
+    signature: Ed25519Signature,
+    untrusted_comment: String,
+    trusted_comment: String,
+    global_signature: Ed25519Signature,
+}
+
+impl MinisignSignature {
+    /// Parse the contents of a signature file, e.g. a `.minisig` file.
+    pub fn decode(contents: &str) -> FastCryptoResult<Self> {
+        let (untrusted_comment, encoded, trusted_comment, encoded_global) =
+            match contents.lines().collect::<Vec<_>>().as_slice() {
+                [a, b, c, d] => (
+                    a.strip_prefix(UNTRUSTED_COMMENT_PREFIX)
+                        .ok_or(FastCryptoError::InvalidInput)?,
+                    *b,
+                    c.strip_prefix(TRUSTED_COMMENT_PREFIX)
+                        .ok_or(FastCryptoError::InvalidInput)?,
+                    *d,
+                ),
+                _ => return Err(FastCryptoError::InvalidInput),
+            };
+        let bytes = decode_line(
+            encoded,
+            2 + MINISIGN_KEY_ID_LENGTH + ED25519_SIGNATURE_LENGTH,
+        )?;
+        let (algorithm, rest) = bytes.split_at(2);
+        let (key_id, signature) = rest.split_at(MINISIGN_KEY_ID_LENGTH);
+        Ok(Self {
+            algorithm: MinisignAlgorithm::from_identifier(algorithm)?,
+            key_id: key_id.try_into().expect("Length is 8"),
+            signature: Ed25519Signature::from_bytes(signature)?,
+            untrusted_comment: untrusted_comment.to_string(),
+            trusted_comment: trusted_comment.to_string(),
+            global_signature: Ed25519Signature::from_bytes(&decode_line(
+                encoded_global,
+                ED25519_SIGNATURE_LENGTH,
+            )?)?,
+        })
+    }
+
+    /// The contents of a signature file for this signature.
+    pub fn encode(&self) -> String {
+        format!(
+            "{}{}\n{}\n{}{}\n{}\n",
+            UNTRUSTED_COMMENT_PREFIX,
+            self.untrusted_comment,
+            Base64::encode_string(
+                &[
+                    &self.algorithm.identifier()[..],
+                    &self.key_id[..],
+                    self.signature.as_ref()
+                ]
+                .concat()
+            ),
+            TRUSTED_COMMENT_PREFIX,
+            self.trusted_comment,
+            Base64::encode_string(self.global_signature.as_ref())
+        )
+    }
+
+    pub fn algorithm(&self) -> MinisignAlgorithm {
+        self.algorithm
+    }
+
+    /// The key id of the signer, as given in the signature.
+    pub fn key_id(&self) -> &MinisignKeyId {
+        &self.key_id
+    }
+
+    /// The trusted comment. It is only authenticated once the signature has been verified.
+    pub fn trusted_comment(&self) -> &str {
+        &self.trusted_comment
+    }
+
+    pub fn untrusted_comment(&self) -> &str {
+        &self.untrusted_comment
+    }
+
+    /// Replace the untrusted comment, which does not invalidate the signature. Fails if the comment
+    /// has more than one line.
+    pub fn set_untrusted_comment(&mut self, comment: &str) -> FastCryptoResult<()> {
+        check_comment(comment)?;
+        self.untrusted_comment = comment.to_string();
+        Ok(())
+    }
+
+    /// Verify that this is a signature by `public_key` over `message` and the trusted comment.
+    pub fn verify(&self, public_key: &MinisignPublicKey, message: &[u8]) -> FastCryptoResult<()> {
+        self.verify_reader(public_key, message)
+    }
+
+    /// Verify that this is a signature by `public_key` over the message read from `reader` and the
+    /// trusted comment. Fails if reading fails. Legacy signatures require the entire message to be
+    /// read into memory.
+    pub fn verify_reader<R: Read>(
+        &self,
+        public_key: &MinisignPublicKey,
+        mut reader: R,
+    ) -> FastCryptoResult<()> {
+        if public_key.key_id != self.key_id {
+            return Err(FastCryptoError::InvalidSignature);
+        }
+        let signed_data = match self.algorithm {
+            MinisignAlgorithm::Prehashed => hash_reader::<Blake2b512, 64, R>(reader)?.to_vec(),
+            MinisignAlgorithm::Legacy => {
+                let mut message = Vec::new();
+                reader
+                    .read_to_end(&mut message)
+                    .map_err(|e| FastCryptoError::GeneralError(e.to_string()))?;
+                message
+            }
+        };
+        public_key
+            .public_key
+            .verify(&signed_data, &self.signature)?;
+        public_key.public_key.verify(
+            &global_data(&self.signature, &self.trusted_comment),
+            &self.global_signature,
+        )
+    }
+}
+
+/// The data signed by the global signature.
+fn global_data(signature: &Ed25519Signature, trusted_comment: &str) -> Vec<u8> {
+    [signature.as_ref(), trusted_comment.as_bytes()].concat()
+}
+
+/// Decode a base64 encoded line of the given decoded length.
+fn decode_line(encoded: &str, length: usize) -> FastCryptoResult<Vec<u8>> {
+    let bytes = Base64::decode_vec(encoded.trim()).map_err(|_| FastCryptoError::InvalidInput)?;
+    if bytes.len() != length {
+        return Err(FastCryptoError::InputLengthWrong(length));
+    }
+    Ok(bytes)
+}
+
+fn check_comment(comment: &str) -> FastCryptoResult<()> {
+    if comment.contains(|c| c == '\n' || c == '\r') {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    Ok(())
+}
+
+/// The key id as displayed by minisign, i.e., as a little-endian integer in upper case hex.
+fn key_id_to_hex(key_id: &MinisignKeyId) -> String {
+    format!("{:016X}", u64::from_le_bytes(*key_id))
+}
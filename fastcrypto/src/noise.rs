@@ -0,0 +1,589 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Handshakes from the [Noise protocol framework](https://noiseprotocol.org/noise.html) for
+//! establishing encrypted channels between peers.
+//!
+//! The supported protocols are `Noise_XX_25519_AESGCM_SHA256` and `Noise_IK_25519_AESGCM_SHA256`:
+//! * [HandshakePattern::XX] is used when the peers do not know each other's static keys in advance.
+//!   Both static keys are transmitted encrypted during the handshake, which takes three messages.
+//! * [HandshakePattern::IK] is used when the initiator already knows the static key of the
+//!   responder. The handshake takes two messages, and the first message can carry an encrypted
+//!   payload.
+//!
+//! After the handshake, both parties call [HandshakeState::into_transport] to get a
+//! [TransportState] used to encrypt and decrypt messages. The static key of the peer is available
+//! from [HandshakeState::remote_static] and must be checked by the application.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::noise::*;
+//! # use rand::thread_rng;
+//! let initiator_key = NoiseKeyPair::generate(&mut thread_rng());
+//! let responder_key = NoiseKeyPair::generate(&mut thread_rng());
+//!
+//! let mut initiator = HandshakeState::initiator(HandshakePattern::XX, b"", initiator_key, None, &mut thread_rng()).unwrap();
+//! let mut responder = HandshakeState::responder(HandshakePattern::XX, b"", responder_key, &mut thread_rng()).unwrap();
+//!
+//! // -> e
+//! let message = initiator.write_message(b"").unwrap();
+//! responder.read_message(&message).unwrap();
+//! // <- e, ee, s, es
+//! let message = responder.write_message(b"").unwrap();
+//! initiator.read_message(&message).unwrap();
+//! // -> s, se
+//! let message = initiator.write_message(b"").unwrap();
+//! responder.read_message(&message).unwrap();
+//!
+//! let mut initiator = initiator.into_transport().unwrap();
+//! let mut responder = responder.into_transport().unwrap();
+//! let ciphertext = initiator.write_message(b"Hello, world!").unwrap();
+//! assert_eq!(responder.read_message(&ciphertext).unwrap(), b"Hello, world!");
+//! ```
+
+use crate::aes::{Aes256Gcm, AesKey, AuthenticatedCipher, InitializationVector};
+use crate::error::FastCryptoError;
+use crate::hash::{HashFunction, Sha256};
+use crate::traits::{AllowedRng, ToFromBytes};
+use curve25519_dalek_ng::constants::X25519_BASEPOINT;
+use curve25519_dalek_ng::montgomery::MontgomeryPoint;
+use curve25519_dalek_ng::scalar::Scalar;
+use fastcrypto_derive::{SilentDebug, SilentDisplay};
+use hkdf::hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use typenum::U12;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// The length of public keys and DH outputs.
+const DH_LENGTH: usize = 32;
+
+/// The length of the outputs of the hash function.
+const HASH_LENGTH: usize = 32;
+
+/// The length of the authentication tags of the AEAD.
+const TAG_LENGTH: usize = 16;
+
+/// The maximum length of a Noise message.
+pub const MAX_MESSAGE_LENGTH: usize = 65535;
+
+/// An X25519 public key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NoisePublicKey([u8; DH_LENGTH]);
+
+/// An X25519 private key.
+#[derive(SilentDebug, SilentDisplay, Zeroize, ZeroizeOnDrop)]
+pub struct NoisePrivateKey([u8; DH_LENGTH]);
+
+/// A static or ephemeral X25519 key pair.
+#[derive(Debug)]
+pub struct NoiseKeyPair {
+    private: NoisePrivateKey,
+    public: NoisePublicKey,
+}
+
+/// The handshake patterns which are supported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HandshakePattern {
+    XX,
+    IK,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Token {
+    E,
+    S,
+    EE,
+    ES,
+    SE,
+    SS,
+}
+
+impl HandshakePattern {
+    fn protocol_name(&self) -> &'static [u8] {
+        match self {
+            HandshakePattern::XX => b"Noise_XX_25519_AESGCM_SHA256",
+            HandshakePattern::IK => b"Noise_IK_25519_AESGCM_SHA256",
+        }
+    }
+
+    /// The message patterns. Messages with an even index are sent by the initiator.
+    fn messages(&self) -> &'static [&'static [Token]] {
+        use Token::*;
+        match self {
+            HandshakePattern::XX => &[&[E], &[E, EE, S, ES], &[S, SE]],
+            HandshakePattern::IK => &[&[E, ES, S, SS], &[E, EE, SE]],
+        }
+    }
+}
+
+impl NoisePublicKey {
+    /// Create a public key from its 32 byte encoding.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        Ok(Self(bytes.try_into().map_err(|_| {
+            FastCryptoError::InputLengthWrong(DH_LENGTH)
+        })?))
+    }
+}
+
+impl AsRef<[u8]> for NoisePublicKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl NoiseKeyPair {
+    /// Generate a new key pair.
+    pub fn generate<R: AllowedRng>(rng: &mut R) -> Self {
+        let mut bytes = [0u8; DH_LENGTH];
+        rng.fill_bytes(&mut bytes);
+        Self::from(NoisePrivateKey(bytes))
+    }
+
+    /// The public key of this key pair.
+    pub fn public(&self) -> &NoisePublicKey {
+        &self.public
+    }
+
+    pub(crate) fn private(&self) -> &NoisePrivateKey {
+        &self.private
+    }
+
+    /// X25519 with the public key of the peer. Returns an error if the output is all zeros, which
+    /// happens if the public key of the peer has small order.
+    pub(crate) fn dh(
+        &self,
+        public_key: &NoisePublicKey,
+    ) -> Result<[u8; DH_LENGTH], FastCryptoError> {
+        let shared = (MontgomeryPoint(public_key.0) * self.private.scalar()).to_bytes();
+        if shared == [0u8; DH_LENGTH] {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(shared)
+    }
+}
+
+impl From<NoisePrivateKey> for NoiseKeyPair {
+    fn from(private: NoisePrivateKey) -> Self {
+        let public = NoisePublicKey((X25519_BASEPOINT * private.scalar()).to_bytes());
+        Self { private, public }
+    }
+}
+
+impl NoisePrivateKey {
+    /// Create a private key from 32 bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        Ok(Self(bytes.try_into().map_err(|_| {
+            FastCryptoError::InputLengthWrong(DH_LENGTH)
+        })?))
+    }
+
+    pub(crate) fn as_bytes(&self) -> &[u8; DH_LENGTH] {
+        &self.0
+    }
+
+    /// The clamped scalar as specified in RFC 7748.
+    fn scalar(&self) -> Scalar {
+        let mut bytes = self.0;
+        bytes[0] &= 248;
+        bytes[31] &= 127;
+        bytes[31] |= 64;
+        Scalar::from_bits(bytes)
+    }
+}
+
+fn hmac_sha256(key: &[u8], parts: &[&[u8]]) -> [u8; HASH_LENGTH] {
+    let mut mac = Hmac::<sha2::Sha256>::new_from_slice(key).expect("HMAC can take key of any size");
+    parts.iter().for_each(|part| mac.update(part));
+    mac.finalize().into_bytes().into()
+}
+
+/// The HKDF function from section 4.3 of the Noise specification with two outputs.
+fn hkdf(chaining_key: &[u8], input_key_material: &[u8]) -> ([u8; HASH_LENGTH], [u8; HASH_LENGTH]) {
+    let temp_key = hmac_sha256(chaining_key, &[input_key_material]);
+    let output1 = hmac_sha256(&temp_key, &[&[1u8]]);
+    let output2 = hmac_sha256(&temp_key, &[&output1, &[2u8]]);
+    (output1, output2)
+}
+
+/// A CipherState from section 5.1 of the Noise specification.
+#[derive(Default)]
+struct CipherState {
+    key: Option<[u8; HASH_LENGTH]>,
+    nonce: u64,
+}
+
+impl CipherState {
+    fn new(key: [u8; HASH_LENGTH]) -> Self {
+        Self {
+            key: Some(key),
+            nonce: 0,
+        }
+    }
+
+    fn has_key(&self) -> bool {
+        self.key.is_some()
+    }
+
+    /// The cipher and the IV for the current nonce. Returns `None` if no key is set.
+    fn cipher(
+        &mut self,
+    ) -> Result<Option<(Aes256Gcm<U12>, InitializationVector<U12>)>, FastCryptoError> {
+        let key = match self.key {
+            Some(key) => key,
+            None => return Ok(None),
+        };
+        // The nonce 2^64 - 1 is reserved.
+        if self.nonce == u64::MAX {
+            return Err(FastCryptoError::GeneralError(
+                "Nonces are exhausted".to_string(),
+            ));
+        }
+        let mut iv = [0u8; 12];
+        iv[4..].copy_from_slice(&self.nonce.to_be_bytes());
+        self.nonce += 1;
+        Ok(Some((
+            Aes256Gcm::<U12>::new(AesKey::from_bytes(&key)?),
+            InitializationVector::<U12>::from_bytes(&iv)?,
+        )))
+    }
+
+    fn encrypt_with_ad(&mut self, ad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, FastCryptoError> {
+        Ok(match self.cipher()? {
+            Some((cipher, iv)) => cipher.encrypt_authenticated(&iv, ad, plaintext),
+            None => plaintext.to_vec(),
+        })
+    }
+
+    fn decrypt_with_ad(
+        &mut self,
+        ad: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, FastCryptoError> {
+        // The nonce is only incremented if decryption succeeds.
+        let nonce = self.nonce;
+        match self.cipher()? {
+            Some((cipher, iv)) => cipher
+                .decrypt_authenticated(&iv, ad, ciphertext)
+                .map_err(|e| {
+                    self.nonce = nonce;
+                    e
+                }),
+            None => Ok(ciphertext.to_vec()),
+        }
+    }
+}
+
+/// A SymmetricState from section 5.2 of the Noise specification.
+struct SymmetricState {
+    cipher_state: CipherState,
+    chaining_key: [u8; HASH_LENGTH],
+    h: [u8; HASH_LENGTH],
+}
+
+impl SymmetricState {
+    fn new(protocol_name: &[u8]) -> Self {
+        let mut h = [0u8; HASH_LENGTH];
+        if protocol_name.len() <= HASH_LENGTH {
+            h[..protocol_name.len()].copy_from_slice(protocol_name);
+        } else {
+            h = Sha256::digest(protocol_name).digest;
+        }
+        Self {
+            cipher_state: CipherState::default(),
+            chaining_key: h,
+            h,
+        }
+    }
+
+    fn mix_key(&mut self, input_key_material: &[u8]) {
+        let (chaining_key, key) = hkdf(&self.chaining_key, input_key_material);
+        self.chaining_key = chaining_key;
+        self.cipher_state = CipherState::new(key);
+    }
+
+    fn mix_hash(&mut self, data: &[u8]) {
+        let mut hash = Sha256::default();
+        hash.update(self.h);
+        hash.update(data);
+        self.h = hash.finalize().digest;
+    }
+
+    fn encrypt_and_hash(&mut self, plaintext: &[u8]) -> Result<Vec<u8>, FastCryptoError> {
+        let ciphertext = self.cipher_state.encrypt_with_ad(&self.h, plaintext)?;
+        self.mix_hash(&ciphertext);
+        Ok(ciphertext)
+    }
+
+    fn decrypt_and_hash(&mut self, ciphertext: &[u8]) -> Result<Vec<u8>, FastCryptoError> {
+        let plaintext = self.cipher_state.decrypt_with_ad(&self.h, ciphertext)?;
+        self.mix_hash(ciphertext);
+        Ok(plaintext)
+    }
+
+    fn split(&self) -> (CipherState, CipherState) {
+        let (key1, key2) = hkdf(&self.chaining_key, &[]);
+        (CipherState::new(key1), CipherState::new(key2))
+    }
+}
+
+/// The state of a party during a handshake.
+pub struct HandshakeState {
+    pattern: HandshakePattern,
+    initiator: bool,
+    symmetric_state: SymmetricState,
+    s: NoiseKeyPair,
+    e: NoiseKeyPair,
+    rs: Option<NoisePublicKey>,
+    re: Option<NoisePublicKey>,
+    message_index: usize,
+}
+
+/// The state of a party after a successful handshake, used to encrypt and decrypt messages.
+pub struct TransportState {
+    send: CipherState,
+    receive: CipherState,
+    handshake_hash: [u8; HASH_LENGTH],
+    remote_static: NoisePublicKey,
+}
+
+impl HandshakeState {
+    /// Start a handshake as the initiator using the static key pair `s`. For [HandshakePattern::IK],
+    /// the static key of the responder must be given as `remote_static`. The prologue must be the
+    /// same for both parties.
+    pub fn initiator<R: AllowedRng>(
+        pattern: HandshakePattern,
+        prologue: &[u8],
+        s: NoiseKeyPair,
+        remote_static: Option<NoisePublicKey>,
+        rng: &mut R,
+    ) -> Result<Self, FastCryptoError> {
+        match (pattern, remote_static) {
+            (HandshakePattern::XX, None) | (HandshakePattern::IK, Some(_)) => (),
+            _ => return Err(FastCryptoError::InvalidInput),
+        }
+        Ok(Self::new(pattern, true, prologue, s, remote_static, rng))
+    }
+
+    /// Start a handshake as the responder using the static key pair `s`. The prologue must be the
+    /// same for both parties.
+    pub fn responder<R: AllowedRng>(
+        pattern: HandshakePattern,
+        prologue: &[u8],
+        s: NoiseKeyPair,
+        rng: &mut R,
+    ) -> Result<Self, FastCryptoError> {
+        Ok(Self::new(pattern, false, prologue, s, None, rng))
+    }
+
+    fn new<R: AllowedRng>(
+        pattern: HandshakePattern,
+        initiator: bool,
+        prologue: &[u8],
+        s: NoiseKeyPair,
+        rs: Option<NoisePublicKey>,
+        rng: &mut R,
+    ) -> Self {
+        let mut symmetric_state = SymmetricState::new(pattern.protocol_name());
+        symmetric_state.mix_hash(prologue);
+
+        // The only pre-message is the static key of the responder in IK.
+        if pattern == HandshakePattern::IK {
+            match &rs {
+                Some(rs) => symmetric_state.mix_hash(&rs.0),
+                None => symmetric_state.mix_hash(&s.public.0),
+            }
+        }
+
+        Self {
+            pattern,
+            initiator,
+            symmetric_state,
+            s,
+            e: NoiseKeyPair::generate(rng),
+            rs,
+            re: None,
+            message_index: 0,
+        }
+    }
+
+    /// Returns true if all handshake messages have been sent and received.
+    pub fn is_finished(&self) -> bool {
+        self.message_index == self.pattern.messages().len()
+    }
+
+    /// Returns true if it is this party's turn to write a message.
+    pub fn is_my_turn(&self) -> bool {
+        !self.is_finished() && (self.message_index % 2 == 0) == self.initiator
+    }
+
+    /// The static key of the peer, if it has been received.
+    pub fn remote_static(&self) -> Option<&NoisePublicKey> {
+        self.rs.as_ref()
+    }
+
+    /// The handshake hash which uniquely identifies the handshake so far.
+    pub fn handshake_hash(&self) -> &[u8] {
+        &self.symmetric_state.h
+    }
+
+    fn dh(&self, token: Token) -> Result<[u8; DH_LENGTH], FastCryptoError> {
+        let missing = || FastCryptoError::GeneralError("Missing remote key".to_string());
+        let re = self.re.as_ref().ok_or_else(missing);
+        let rs = self.rs.as_ref().ok_or_else(missing);
+        match (token, self.initiator) {
+            (Token::EE, _) => self.e.dh(re?),
+            (Token::SS, _) => self.s.dh(rs?),
+            (Token::ES, true) | (Token::SE, false) => self.e.dh(rs?),
+            (Token::ES, false) | (Token::SE, true) => self.s.dh(re?),
+            (Token::E, _) | (Token::S, _) => unreachable!("Not a DH token"),
+        }
+    }
+
+    /// Write the next handshake message with the given payload. Note that the payload of the first
+    /// message in [HandshakePattern::XX] is sent in plaintext.
+    pub fn write_message(&mut self, payload: &[u8]) -> Result<Vec<u8>, FastCryptoError> {
+        if !self.is_my_turn() {
+            return Err(FastCryptoError::GeneralError("Not our turn".to_string()));
+        }
+        // Fail before the state is modified, so that the handshake can continue with a shorter
+        // payload.
+        if self.message_length(payload.len()) > MAX_MESSAGE_LENGTH {
+            return Err(FastCryptoError::InputTooLong(MAX_MESSAGE_LENGTH));
+        }
+        let mut message = Vec::new();
+        for token in self.pattern.messages()[self.message_index] {
+            match token {
+                Token::E => {
+                    let e = self.e.public.0;
+                    message.extend_from_slice(&e);
+                    self.symmetric_state.mix_hash(&e);
+                }
+                Token::S => {
+                    let s = self.s.public.0;
+                    message.extend(self.symmetric_state.encrypt_and_hash(&s)?);
+                }
+                _ => {
+                    let shared = self.dh(*token)?;
+                    self.symmetric_state.mix_key(&shared);
+                }
+            }
+        }
+        message.extend(self.symmetric_state.encrypt_and_hash(payload)?);
+        self.message_index += 1;
+        Ok(message)
+    }
+
+    /// The length of the next message written with a payload of the given length. Static keys and
+    /// payloads are followed by a tag once a key has been mixed in by a DH token.
+    fn message_length(&self, payload_length: usize) -> usize {
+        let tag_length = |has_key: bool| if has_key { TAG_LENGTH } else { 0 };
+        let mut has_key = self.symmetric_state.cipher_state.key.is_some();
+        let mut length = 0;
+        for token in self.pattern.messages()[self.message_index] {
+            match token {
+                Token::E => length += DH_LENGTH,
+                Token::S => length += DH_LENGTH + tag_length(has_key),
+                _ => has_key = true,
+            }
+        }
+        length.saturating_add(payload_length) + tag_length(has_key)
+    }
+
+    /// Read the next handshake message from the peer and return its payload.
+    pub fn read_message(&mut self, message: &[u8]) -> Result<Vec<u8>, FastCryptoError> {
+        if self.is_finished() || self.is_my_turn() {
+            return Err(FastCryptoError::GeneralError(
+                "Not the peer's turn".to_string(),
+            ));
+        }
+        if message.len() > MAX_MESSAGE_LENGTH {
+            return Err(FastCryptoError::InputTooLong(MAX_MESSAGE_LENGTH));
+        }
+        let mut remaining = message;
+        for token in self.pattern.messages()[self.message_index] {
+            match token {
+                Token::E => {
+                    let (re, rest) = split_at(remaining, DH_LENGTH)?;
+                    self.symmetric_state.mix_hash(re);
+                    self.re = Some(NoisePublicKey::from_bytes(re)?);
+                    remaining = rest;
+                }
+                Token::S => {
+                    let length = match self.symmetric_state.cipher_state.has_key() {
+                        true => DH_LENGTH + TAG_LENGTH,
+                        false => DH_LENGTH,
+                    };
+                    let (rs, rest) = split_at(remaining, length)?;
+                    let rs = self.symmetric_state.decrypt_and_hash(rs)?;
+                    self.rs = Some(NoisePublicKey::from_bytes(&rs)?);
+                    remaining = rest;
+                }
+                _ => {
+                    let shared = self.dh(*token)?;
+                    self.symmetric_state.mix_key(&shared);
+                }
+            }
+        }
+        let payload = self.symmetric_state.decrypt_and_hash(remaining)?;
+        self.message_index += 1;
+        Ok(payload)
+    }
+
+    /// Finish the handshake and return the transport state. Returns an error if the handshake has
+    /// not finished.
+    pub fn into_transport(self) -> Result<TransportState, FastCryptoError> {
+        if !self.is_finished() {
+            return Err(FastCryptoError::GeneralError(
+                "Handshake is not finished".to_string(),
+            ));
+        }
+        let (c1, c2) = self.symmetric_state.split();
+        let (send, receive) = match self.initiator {
+            true => (c1, c2),
+            false => (c2, c1),
+        };
+        Ok(TransportState {
+            send,
+            receive,
+            handshake_hash: self.symmetric_state.h,
+            remote_static: self.rs.expect("All patterns transmit the static keys"),
+        })
+    }
+}
+
+fn split_at(bytes: &[u8], mid: usize) -> Result<(&[u8], &[u8]), FastCryptoError> {
+    if bytes.len() < mid {
+        return Err(FastCryptoError::InputTooShort(mid));
+    }
+    Ok(bytes.split_at(mid))
+}
+
+impl TransportState {
+    /// Encrypt a message to the peer.
+    pub fn write_message(&mut self, payload: &[u8]) -> Result<Vec<u8>, FastCryptoError> {
+        if payload.len() + TAG_LENGTH > MAX_MESSAGE_LENGTH {
+            return Err(FastCryptoError::InputTooLong(
+                MAX_MESSAGE_LENGTH - TAG_LENGTH,
+            ));
+        }
+        self.send.encrypt_with_ad(&[], payload)
+    }
+
+    /// Decrypt a message from the peer. Messages must be decrypted in the order they were sent.
+    pub fn read_message(&mut self, message: &[u8]) -> Result<Vec<u8>, FastCryptoError> {
+        if message.len() > MAX_MESSAGE_LENGTH {
+            return Err(FastCryptoError::InputTooLong(MAX_MESSAGE_LENGTH));
+        }
+        self.receive.decrypt_with_ad(&[], message)
+    }
+
+    /// The handshake hash which may be used for channel binding.
+    pub fn handshake_hash(&self) -> &[u8] {
+        &self.handshake_hash
+    }
+
+    /// The static key of the peer which was authenticated in the handshake.
+    pub fn remote_static(&self) -> &NoisePublicKey {
+        &self.remote_static
+    }
+}
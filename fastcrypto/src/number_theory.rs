@@ -0,0 +1,163 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Number theoretic helper functions over arbitrary precision integers, shared by the schemes
+//! whose security is based on RSA moduli or class groups.
+
+use crate::traits::AllowedRng;
+use num_bigint::{BigInt, BigUint};
+
+/// The number of Miller-Rabin rounds used when generating primes.
+const MILLER_RABIN_ROUNDS: usize = 40;
+
+/// Statistical security parameter in bits used when sampling values which must be uniform modulo
+/// a bound.
+pub(crate) const STATISTICAL_SECURITY: u64 = 128;
+
+const SMALL_PRIMES: [u32; 25] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+];
+
+/// Sample a uniformly random value in `[0, bound)`, up to a statistically negligible bias.
+pub(crate) fn random_below<R: AllowedRng>(bound: &BigUint, rng: &mut R) -> BigUint {
+    let bytes = ((bound.bits() + STATISTICAL_SECURITY + 7) / 8) as usize;
+    let mut buffer = vec![0u8; bytes];
+    rng.fill_bytes(&mut buffer);
+    BigUint::from_bytes_be(&buffer) % bound
+}
+
+/// Sample a uniformly random element of the multiplicative group modulo `modulus`.
+pub(crate) fn random_unit<R: AllowedRng>(modulus: &BigUint, rng: &mut R) -> BigUint {
+    loop {
+        let r = random_below(modulus, rng);
+        if gcd(&r, modulus) == BigUint::from(1u8) {
+            return r;
+        }
+    }
+}
+
+pub(crate) fn gcd(a: &BigUint, b: &BigUint) -> BigUint {
+    let (mut a, mut b) = (a.clone(), b.clone());
+    while b != BigUint::from(0u8) {
+        (a, b) = (b.clone(), a % b);
+    }
+    a
+}
+
+/// Compute `(d, x, y)` such that `d = gcd(a, b) = x * a + y * b` and `d >= 0`.
+pub(crate) fn xgcd(a: &BigInt, b: &BigInt) -> (BigInt, BigInt, BigInt) {
+    let zero = BigInt::from(0u8);
+    let (mut old_r, mut r) = (a.clone(), b.clone());
+    let (mut old_s, mut s) = (BigInt::from(1u8), zero.clone());
+    let (mut old_t, mut t) = (zero.clone(), BigInt::from(1u8));
+    while r != zero {
+        let quotient = &old_r / &r;
+        (old_r, r) = (r.clone(), old_r - &quotient * &r);
+        (old_s, s) = (s.clone(), old_s - &quotient * &s);
+        (old_t, t) = (t.clone(), old_t - &quotient * &t);
+    }
+    if old_r < zero {
+        (-old_r, -old_s, -old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+/// Compute `floor(a / b)`.
+pub(crate) fn div_floor(a: &BigInt, b: &BigInt) -> BigInt {
+    let quotient = a / b;
+    let zero = BigInt::from(0u8);
+    if (a % b != zero) && ((a < &zero) != (b < &zero)) {
+        quotient - 1
+    } else {
+        quotient
+    }
+}
+
+/// Compute the inverse of `a` modulo `modulus` if it exists.
+pub(crate) fn mod_inverse(a: &BigUint, modulus: &BigUint) -> Option<BigUint> {
+    let m = BigInt::from(modulus.clone());
+    let (d, x, _) = xgcd(&BigInt::from(a % modulus), &m);
+    if d != BigInt::from(1u8) {
+        return None;
+    }
+    (((x % &m) + &m) % &m).to_biguint()
+}
+
+pub(crate) fn is_probable_prime<R: AllowedRng>(n: &BigUint, rng: &mut R) -> bool {
+    let one = BigUint::from(1u8);
+    let two = BigUint::from(2u8);
+    if n < &two {
+        return false;
+    }
+    for p in SMALL_PRIMES.iter().map(|&p| BigUint::from(p)) {
+        if n == &p {
+            return true;
+        }
+        if (n % &p) == BigUint::from(0u8) {
+            return false;
+        }
+    }
+
+    // Write n - 1 = d * 2^s with d odd.
+    let n_minus_one = n - &one;
+    let s = n_minus_one
+        .trailing_zeros()
+        .expect("n - 1 is non-zero since n > 2");
+    let d = &n_minus_one >> s;
+
+    let base_bound = n - 3u8;
+    'witness: for _ in 0..MILLER_RABIN_ROUNDS {
+        let a = random_below(&base_bound, rng) + &two;
+        let mut x = a.modpow(&d, n);
+        if x == one || x == n_minus_one {
+            continue;
+        }
+        for _ in 1..s {
+            x = x.modpow(&two, n);
+            if x == n_minus_one {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// Generate a random prime with exactly `bits` bits whose two most significant bits are set, so
+/// the product of two such primes has exactly `2 * bits` bits.
+pub(crate) fn generate_prime<R: AllowedRng>(bits: u64, rng: &mut R) -> BigUint {
+    let bytes = ((bits + 7) / 8) as usize;
+    let excess = bytes as u64 * 8 - bits;
+    loop {
+        let mut buffer = vec![0u8; bytes];
+        rng.fill_bytes(&mut buffer);
+        let mut candidate = BigUint::from_bytes_be(&buffer) >> excess;
+        candidate.set_bit(bits - 1, true);
+        candidate.set_bit(bits - 2, true);
+        candidate.set_bit(0, true);
+        if is_probable_prime(&candidate, rng) {
+            return candidate;
+        }
+    }
+}
+
+/// Deterministic primality test by trial division, which is only feasible for small `n`.
+pub(crate) fn is_prime_by_trial_division(n: u64) -> bool {
+    n >= 2 && (2u64..).take_while(|d| d * d <= n).all(|d| n % d != 0)
+}
+
+/// Check Pocklington's criterion for `n = 2 * h * q + 1` where `q` is a known prime with
+/// `(q + 1)^2 > n`: If `a^(n-1) = 1 mod n` and `gcd(a^(2h) - 1, n) = 1`, then `n` is prime.
+pub(crate) fn pocklington_criterion(n: &BigUint, q: &BigUint, h: &BigUint, a: u32) -> bool {
+    let one = BigUint::from(1u8);
+    let a = BigUint::from(a);
+    if n != &(BigUint::from(2u8) * h * q + &one) || (q + &one) * (q + &one) <= *n {
+        return false;
+    }
+    if a.modpow(&(n - &one), n) != one {
+        return false;
+    }
+    let t = a.modpow(&(BigUint::from(2u8) * h), n);
+    t != BigUint::from(0u8) && gcd(&(t - &one), n) == one
+}
@@ -0,0 +1,111 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Wrappers around key pairs which limit how many signatures can be created with a key, e.g. for
+//! ephemeral keys in a DKG or for schemes where signing a second message leaks the key.
+//!
+//! [OneTimeSigner] is consumed when it signs, so reuse is prevented at compile time.
+//! [BoundedUseSigner] allows a fixed number of signatures and deletes the key once they are used.
+//! Neither wrapper implements [Clone] or the [Signer] trait, since that would allow bypassing the
+//! limit.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::one_time_signer::*;
+//! # use fastcrypto::ed25519::Ed25519KeyPair;
+//! # use fastcrypto::traits::VerifyingKey;
+//! # use rand::thread_rng;
+//! let signer = OneTimeSigner::<Ed25519KeyPair>::generate(&mut thread_rng());
+//! let pk = signer.public().clone();
+//! let signature = signer.sign(b"Hello, world!");
+//! assert!(pk.verify(b"Hello, world!", &signature).is_ok());
+//!
+//! let mut signer = BoundedUseSigner::<Ed25519KeyPair>::generate(2, &mut thread_rng()).unwrap();
+//! assert!(signer.sign(b"first").is_ok());
+//! assert!(signer.sign(b"second").is_ok());
+//! assert!(signer.sign(b"third").is_err());
+//! ```
+
+use crate::error::FastCryptoError;
+use crate::traits::{AllowedRng, KeyPair, Signer};
+
+/// A key pair which can create exactly one signature.
+pub struct OneTimeSigner<K: KeyPair> {
+    key_pair: K,
+}
+
+impl<K: KeyPair> OneTimeSigner<K> {
+    /// Wrap a key pair. The caller must not keep a copy of the key pair.
+    pub fn new(key_pair: K) -> Self {
+        Self { key_pair }
+    }
+
+    /// Generate a new key pair using the given RNG.
+    pub fn generate<R: AllowedRng>(rng: &mut R) -> Self {
+        Self::new(K::generate(rng))
+    }
+
+    /// Get the public key.
+    pub fn public(&self) -> &K::PubKey {
+        self.key_pair.public()
+    }
+
+    /// Sign a message, consuming the key pair.
+    pub fn sign(self, msg: &[u8]) -> K::Sig {
+        self.key_pair.sign(msg)
+    }
+}
+
+/// A key pair which can create a bounded number of signatures. The key pair is dropped after the
+/// last signature.
+pub struct BoundedUseSigner<K: KeyPair> {
+    key_pair: Option<K>,
+    public: K::PubKey,
+    remaining: u64,
+}
+
+impl<K: KeyPair> BoundedUseSigner<K> {
+    /// Wrap a key pair which may be used for at most `max_uses` signatures. The caller must not keep
+    /// a copy of the key pair. Fails if `max_uses` is zero.
+    pub fn new(key_pair: K, max_uses: u64) -> Result<Self, FastCryptoError> {
+        if max_uses == 0 {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(Self {
+            public: key_pair.public().clone(),
+            key_pair: Some(key_pair),
+            remaining: max_uses,
+        })
+    }
+
+    /// Generate a new key pair using the given RNG which may be used for at most `max_uses`
+    /// signatures.
+    pub fn generate<R: AllowedRng>(max_uses: u64, rng: &mut R) -> Result<Self, FastCryptoError> {
+        Self::new(K::generate(rng), max_uses)
+    }
+
+    /// Get the public key.
+    pub fn public(&self) -> &K::PubKey {
+        &self.public
+    }
+
+    /// The number of signatures which can still be created.
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// Sign a message. Fails if all uses of the key have been spent.
+    pub fn sign(&mut self, msg: &[u8]) -> Result<K::Sig, FastCryptoError> {
+        let key_pair = self
+            .key_pair
+            .as_ref()
+            .ok_or_else(|| FastCryptoError::GeneralError("Signing key is used up".to_string()))?;
+        let signature = key_pair.sign(msg);
+        self.remaining -= 1;
+        if self.remaining == 0 {
+            // Dropping the key pair zeroizes the private key.
+            self.key_pair = None;
+        }
+        Ok(signature)
+    }
+}
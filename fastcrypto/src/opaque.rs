@@ -0,0 +1,520 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! An implementation of the OPAQUE asymmetric password-authenticated key exchange following the
+//! structure of [RFC 9807](https://www.rfc-editor.org/rfc/rfc9807.html). The server never sees the
+//! password of the client, neither during registration nor during login, and an attacker who
+//! compromises the server cannot run an offline dictionary attack without first compromising the
+//! OPRF key.
+//!
+//! The OPRF is the ristretto255-SHA512 OPRF from [crate::oprf], the authenticated key exchange is
+//! 3DH over ristretto255, and key derivation and MACs use HKDF and HMAC with SHA3-256 from
+//! [crate::hmac]. Since the KDF and MAC differ from the ciphersuites in the RFC, this is not
+//! interoperable with other implementations. Passwords are not stretched, so applications should
+//! pass the output of a memory-hard function (e.g. Argon2) on the password if the OPRF key may be
+//! compromised.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::opaque::*;
+//! # use rand::thread_rng;
+//! let server = ServerSetup::new(&mut thread_rng());
+//! let password = b"correct horse battery staple";
+//! let credential_identifier = b"alice@example.com";
+//!
+//! // Registration
+//! let (client_state, request) = ClientRegistration::start(password, &mut thread_rng()).unwrap();
+//! let response = server.registration_response(&request, credential_identifier).unwrap();
+//! let (record, export_key) = client_state.finish(&response, &mut thread_rng()).unwrap();
+//!
+//! // Login
+//! let (client_state, ke1) = ClientLogin::start(password, &mut thread_rng()).unwrap();
+//! let (server_state, ke2) = server.login_start(&record, credential_identifier, &ke1, &mut thread_rng()).unwrap();
+//! let (ke3, client_session_key, client_export_key) = client_state.finish(&ke2).unwrap();
+//! let server_session_key = server_state.finish(&ke3).unwrap();
+//!
+//! assert_eq!(client_session_key, server_session_key);
+//! assert_eq!(client_export_key, export_key);
+//! ```
+
+use crate::error::FastCryptoError;
+use crate::groups::ristretto255::{RistrettoPoint, RistrettoScalar};
+use crate::groups::{FixedBaseMultiplication, Scalar};
+use crate::hash::{HashFunction, Sha3_256};
+use crate::hmac::{hkdf_sha3_256, hmac_sha3_256, HkdfIkm, HmacKey};
+use crate::oprf::{blind, finalize, Blind, BlindedElement, EvaluatedElement, Mode, OprfPrivateKey};
+use crate::serde_helpers::ToFromByteArray;
+use crate::traits::{AllowedRng, ToFromBytes};
+use serde::{Deserialize, Serialize};
+use subtle::ConstantTimeEq;
+
+/// Length of nonces, keys and MACs.
+const LENGTH: usize = 32;
+
+/// Length of a serialized envelope.
+const ENVELOPE_LENGTH: usize = 2 * LENGTH;
+
+/// Length of the masked part of a credential response: the server public key and the envelope.
+const MASKED_RESPONSE_LENGTH: usize = LENGTH + ENVELOPE_LENGTH;
+
+/// Context used in the transcript of the key exchange.
+const CONTEXT: &[u8] = b"FASTCRYPTO_OPAQUE_3DH_RISTRETTO255_V1";
+
+/// A shared session key established by a successful login.
+pub type SessionKey = [u8; LENGTH];
+
+/// A key known only to the client which is the same for every login. It can be used by the client
+/// to encrypt application data stored on the server.
+pub type ExportKey = [u8; LENGTH];
+
+/// The long-term secrets of the server.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServerSetup {
+    oprf_seed: [u8; LENGTH],
+    private_key: RistrettoScalar,
+    public_key: RistrettoPoint,
+}
+
+/// The envelope stored on the server which allows the client to recover its private key.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct Envelope {
+    nonce: [u8; LENGTH],
+    auth_tag: [u8; LENGTH],
+}
+
+/// The first message of the registration flow sent from the client to the server.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistrationRequest {
+    blinded_element: BlindedElement,
+}
+
+/// The response of the server to a [RegistrationRequest].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistrationResponse {
+    evaluated_element: EvaluatedElement,
+    server_public_key: RistrettoPoint,
+}
+
+/// The record created by the client at the end of the registration flow which should be stored by
+/// the server for the given credential identifier.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistrationRecord {
+    client_public_key: RistrettoPoint,
+    masking_key: [u8; LENGTH],
+    envelope: Envelope,
+}
+
+/// Entry point for the client in the registration flow.
+pub struct ClientRegistration;
+
+/// The state of the client between sending a [RegistrationRequest] and receiving the
+/// [RegistrationResponse].
+pub struct ClientRegistrationState {
+    password: Vec<u8>,
+    blind: Blind,
+}
+
+/// The first message of the login flow sent from the client to the server.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KE1 {
+    blinded_element: BlindedElement,
+    client_nonce: [u8; LENGTH],
+    client_public_keyshare: RistrettoPoint,
+}
+
+/// The response of the server to a [KE1] message.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KE2 {
+    evaluated_element: EvaluatedElement,
+    masking_nonce: [u8; LENGTH],
+    masked_response: Vec<u8>,
+    server_nonce: [u8; LENGTH],
+    server_public_keyshare: RistrettoPoint,
+    server_mac: [u8; LENGTH],
+}
+
+/// The final message of the login flow sent from the client to the server.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KE3 {
+    client_mac: [u8; LENGTH],
+}
+
+/// Entry point for the client in the login flow.
+pub struct ClientLogin;
+
+/// The state of the client between sending [KE1] and receiving [KE2].
+pub struct ClientLoginState {
+    password: Vec<u8>,
+    blind: Blind,
+    client_secret_keyshare: RistrettoScalar,
+    ke1: KE1,
+}
+
+/// The state of the server between sending [KE2] and receiving [KE3].
+pub struct ServerLoginState {
+    expected_client_mac: [u8; LENGTH],
+    session_key: SessionKey,
+}
+
+/// HKDF with an empty salt producing a fixed length output.
+fn kdf<const N: usize>(ikm: &[u8], info: &[&[u8]]) -> [u8; N] {
+    let ikm = HkdfIkm::from_bytes(ikm).expect("Any length is allowed");
+    hkdf_sha3_256(&ikm, &[], &info.concat(), N)
+        .expect("Output length is valid")
+        .try_into()
+        .expect("Output has length N")
+}
+
+fn mac(key: &[u8], message: &[&[u8]]) -> [u8; LENGTH] {
+    let key = HmacKey::from_bytes(key).expect("Any length is allowed");
+    hmac_sha3_256(&key, &message.concat()).digest
+}
+
+fn random_nonce<R: AllowedRng>(rng: &mut R) -> [u8; LENGTH] {
+    let mut nonce = [0u8; LENGTH];
+    rng.fill_bytes(&mut nonce);
+    nonce
+}
+
+/// The keys derived from the OPRF output on the password.
+struct PasswordKeys {
+    randomized_password: [u8; LENGTH],
+    masking_key: [u8; LENGTH],
+}
+
+impl PasswordKeys {
+    fn new(
+        password: &[u8],
+        blind: &Blind,
+        evaluated_element: &EvaluatedElement,
+    ) -> Result<Self, FastCryptoError> {
+        let oprf_output = finalize(password, blind, evaluated_element)?;
+        let randomized_password = kdf(&oprf_output, &[b"RandomizedPassword"]);
+        let masking_key = kdf(&randomized_password, &[b"MaskingKey"]);
+        Ok(Self {
+            randomized_password,
+            masking_key,
+        })
+    }
+
+    /// Derive the envelope keys and the key pair of the client for the given envelope nonce.
+    /// Returns the auth key, the export key and the private key of the client.
+    fn envelope_keys(&self, nonce: &[u8; LENGTH]) -> ([u8; LENGTH], ExportKey, RistrettoScalar) {
+        let auth_key = kdf(&self.randomized_password, &[nonce, b"AuthKey"]);
+        let export_key = kdf(&self.randomized_password, &[nonce, b"ExportKey"]);
+        let seed: [u8; 64] = kdf(&self.randomized_password, &[nonce, b"PrivateKey"]);
+        (
+            auth_key,
+            export_key,
+            RistrettoScalar::from_bytes_mod_order_wide(&seed),
+        )
+    }
+}
+
+impl Envelope {
+    fn auth_tag(
+        auth_key: &[u8; LENGTH],
+        nonce: &[u8; LENGTH],
+        server_public_key: &RistrettoPoint,
+        client_public_key: &RistrettoPoint,
+    ) -> [u8; LENGTH] {
+        mac(
+            auth_key,
+            &[
+                nonce,
+                &server_public_key.to_byte_array(),
+                &client_public_key.to_byte_array(),
+            ],
+        )
+    }
+
+    fn to_bytes(&self) -> [u8; ENVELOPE_LENGTH] {
+        let mut bytes = [0u8; ENVELOPE_LENGTH];
+        bytes[..LENGTH].copy_from_slice(&self.nonce);
+        bytes[LENGTH..].copy_from_slice(&self.auth_tag);
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            nonce: bytes[..LENGTH].try_into().expect("Length is checked"),
+            auth_tag: bytes[LENGTH..].try_into().expect("Length is checked"),
+        }
+    }
+}
+
+/// The keys derived in the key exchange.
+struct KeySchedule {
+    server_mac: [u8; LENGTH],
+    client_mac: [u8; LENGTH],
+    session_key: SessionKey,
+}
+
+impl KeySchedule {
+    fn new(dh: [RistrettoPoint; 3], ke1: &KE1, ke2: &KE2) -> Self {
+        let ikm = dh
+            .iter()
+            .flat_map(|p| p.to_byte_array())
+            .collect::<Vec<_>>();
+
+        let mut transcript = Sha3_256::default();
+        transcript.update(CONTEXT);
+        transcript.update(ke1.blinded_element_bytes());
+        transcript.update(ke1.client_nonce);
+        transcript.update(ke1.client_public_keyshare.to_byte_array());
+        transcript.update(ke2.evaluated_element_bytes());
+        transcript.update(ke2.masking_nonce);
+        transcript.update(&ke2.masked_response);
+        transcript.update(ke2.server_nonce);
+        transcript.update(ke2.server_public_keyshare.to_byte_array());
+        let transcript_hash = transcript.finalize().digest;
+
+        let handshake_secret: [u8; LENGTH] = kdf(&ikm, &[b"HandshakeSecret", &transcript_hash]);
+        let session_key = kdf(&ikm, &[b"SessionKey", &transcript_hash]);
+        let server_mac_key: [u8; LENGTH] = kdf(&handshake_secret, &[b"ServerMAC"]);
+        let client_mac_key: [u8; LENGTH] = kdf(&handshake_secret, &[b"ClientMAC"]);
+        let server_mac = mac(&server_mac_key, &[&transcript_hash]);
+        let client_mac = mac(&client_mac_key, &[&transcript_hash, &server_mac]);
+        Self {
+            server_mac,
+            client_mac,
+            session_key,
+        }
+    }
+}
+
+impl KE1 {
+    fn blinded_element_bytes(&self) -> Vec<u8> {
+        bincode::serialize(&self.blinded_element).expect("Serialization does not fail")
+    }
+}
+
+impl KE2 {
+    fn evaluated_element_bytes(&self) -> Vec<u8> {
+        bincode::serialize(&self.evaluated_element).expect("Serialization does not fail")
+    }
+}
+
+impl ServerSetup {
+    /// Generate new long-term secrets for a server.
+    pub fn new<R: AllowedRng>(rng: &mut R) -> Self {
+        let private_key = RistrettoScalar::rand(rng);
+        Self {
+            oprf_seed: random_nonce(rng),
+            private_key,
+            public_key: RistrettoPoint::mul_generator(&private_key),
+        }
+    }
+
+    /// The OPRF key used for the given credential identifier.
+    fn oprf_key(&self, credential_identifier: &[u8]) -> Result<OprfPrivateKey, FastCryptoError> {
+        let seed = kdf(&self.oprf_seed, &[credential_identifier, b"OprfKey"]);
+        OprfPrivateKey::derive(Mode::Oprf, &seed, b"OPAQUE-DeriveKeyPair")
+    }
+
+    /// Respond to a registration request from a client with the given credential identifier.
+    pub fn registration_response(
+        &self,
+        request: &RegistrationRequest,
+        credential_identifier: &[u8],
+    ) -> Result<RegistrationResponse, FastCryptoError> {
+        Ok(RegistrationResponse {
+            evaluated_element: self
+                .oprf_key(credential_identifier)?
+                .blind_evaluate(&request.blinded_element),
+            server_public_key: self.public_key,
+        })
+    }
+
+    /// Respond to a login request from the client with the given credential identifier and record.
+    pub fn login_start<R: AllowedRng>(
+        &self,
+        record: &RegistrationRecord,
+        credential_identifier: &[u8],
+        ke1: &KE1,
+        rng: &mut R,
+    ) -> Result<(ServerLoginState, KE2), FastCryptoError> {
+        let evaluated_element = self
+            .oprf_key(credential_identifier)?
+            .blind_evaluate(&ke1.blinded_element);
+
+        let masking_nonce = random_nonce(rng);
+        let pad: [u8; MASKED_RESPONSE_LENGTH] = kdf(
+            &record.masking_key,
+            &[&masking_nonce, b"CredentialResponsePad"],
+        );
+        let masked_response = self
+            .public_key
+            .to_byte_array()
+            .iter()
+            .chain(record.envelope.to_bytes().iter())
+            .zip(pad.iter())
+            .map(|(a, b)| a ^ b)
+            .collect();
+
+        let server_secret_keyshare = RistrettoScalar::rand(rng);
+        let mut ke2 = KE2 {
+            evaluated_element,
+            masking_nonce,
+            masked_response,
+            server_nonce: random_nonce(rng),
+            server_public_keyshare: RistrettoPoint::mul_generator(&server_secret_keyshare),
+            server_mac: [0u8; LENGTH],
+        };
+
+        let dh = [
+            ke1.client_public_keyshare * server_secret_keyshare,
+            ke1.client_public_keyshare * self.private_key,
+            record.client_public_key * server_secret_keyshare,
+        ];
+        let keys = KeySchedule::new(dh, ke1, &ke2);
+        ke2.server_mac = keys.server_mac;
+        Ok((
+            ServerLoginState {
+                expected_client_mac: keys.client_mac,
+                session_key: keys.session_key,
+            },
+            ke2,
+        ))
+    }
+}
+
+impl ClientRegistration {
+    /// Start the registration of a password.
+    pub fn start<R: AllowedRng>(
+        password: &[u8],
+        rng: &mut R,
+    ) -> Result<(ClientRegistrationState, RegistrationRequest), FastCryptoError> {
+        let (blind, blinded_element) = blind(Mode::Oprf, password, rng)?;
+        Ok((
+            ClientRegistrationState {
+                password: password.to_vec(),
+                blind,
+            },
+            RegistrationRequest { blinded_element },
+        ))
+    }
+}
+
+impl ClientRegistrationState {
+    /// Finish the registration using the response from the server. Returns the record which should
+    /// be sent to the server and the export key.
+    pub fn finish<R: AllowedRng>(
+        self,
+        response: &RegistrationResponse,
+        rng: &mut R,
+    ) -> Result<(RegistrationRecord, ExportKey), FastCryptoError> {
+        let keys = PasswordKeys::new(&self.password, &self.blind, &response.evaluated_element)?;
+        let nonce = random_nonce(rng);
+        let (auth_key, export_key, client_private_key) = keys.envelope_keys(&nonce);
+        let client_public_key = RistrettoPoint::mul_generator(&client_private_key);
+        let auth_tag = Envelope::auth_tag(
+            &auth_key,
+            &nonce,
+            &response.server_public_key,
+            &client_public_key,
+        );
+        Ok((
+            RegistrationRecord {
+                client_public_key,
+                masking_key: keys.masking_key,
+                envelope: Envelope { nonce, auth_tag },
+            },
+            export_key,
+        ))
+    }
+}
+
+impl ClientLogin {
+    /// Start a login with the given password.
+    pub fn start<R: AllowedRng>(
+        password: &[u8],
+        rng: &mut R,
+    ) -> Result<(ClientLoginState, KE1), FastCryptoError> {
+        let (blind, blinded_element) = blind(Mode::Oprf, password, rng)?;
+        let client_secret_keyshare = RistrettoScalar::rand(rng);
+        let ke1 = KE1 {
+            blinded_element,
+            client_nonce: random_nonce(rng),
+            client_public_keyshare: RistrettoPoint::mul_generator(&client_secret_keyshare),
+        };
+        Ok((
+            ClientLoginState {
+                password: password.to_vec(),
+                blind,
+                client_secret_keyshare,
+                ke1: ke1.clone(),
+            },
+            ke1,
+        ))
+    }
+}
+
+impl ClientLoginState {
+    /// Finish the login using the response from the server. Returns the final message which should
+    /// be sent to the server, the session key and the export key. Fails if the password is wrong or
+    /// if the server could not authenticate itself.
+    pub fn finish(self, ke2: &KE2) -> Result<(KE3, SessionKey, ExportKey), FastCryptoError> {
+        if ke2.masked_response.len() != MASKED_RESPONSE_LENGTH {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let keys = PasswordKeys::new(&self.password, &self.blind, &ke2.evaluated_element)?;
+
+        // Unmask the server public key and the envelope.
+        let pad: [u8; MASKED_RESPONSE_LENGTH] = kdf(
+            &keys.masking_key,
+            &[&ke2.masking_nonce, b"CredentialResponsePad"],
+        );
+        let unmasked = ke2
+            .masked_response
+            .iter()
+            .zip(pad.iter())
+            .map(|(a, b)| a ^ b)
+            .collect::<Vec<_>>();
+        let server_public_key = RistrettoPoint::from_byte_array(
+            unmasked[..LENGTH].try_into().expect("Length is checked"),
+        )
+        .map_err(|_| FastCryptoError::GeneralOpaqueError)?;
+        let envelope = Envelope::from_bytes(&unmasked[LENGTH..]);
+
+        // Recover the key pair of the client from the envelope.
+        let (auth_key, export_key, client_private_key) = keys.envelope_keys(&envelope.nonce);
+        let client_public_key = RistrettoPoint::mul_generator(&client_private_key);
+        let expected_auth_tag = Envelope::auth_tag(
+            &auth_key,
+            &envelope.nonce,
+            &server_public_key,
+            &client_public_key,
+        );
+        if !bool::from(expected_auth_tag.ct_eq(&envelope.auth_tag)) {
+            return Err(FastCryptoError::GeneralOpaqueError);
+        }
+
+        let dh = [
+            ke2.server_public_keyshare * self.client_secret_keyshare,
+            server_public_key * self.client_secret_keyshare,
+            ke2.server_public_keyshare * client_private_key,
+        ];
+        let keys = KeySchedule::new(dh, &self.ke1, ke2);
+        if !bool::from(keys.server_mac.ct_eq(&ke2.server_mac)) {
+            return Err(FastCryptoError::GeneralOpaqueError);
+        }
+        Ok((
+            KE3 {
+                client_mac: keys.client_mac,
+            },
+            keys.session_key,
+            export_key,
+        ))
+    }
+}
+
+impl ServerLoginState {
+    /// Finish the login by verifying the final message of the client. Returns the session key.
+    pub fn finish(self, ke3: &KE3) -> Result<SessionKey, FastCryptoError> {
+        if !bool::from(self.expected_client_mac.ct_eq(&ke3.client_mac)) {
+            return Err(FastCryptoError::GeneralOpaqueError);
+        }
+        Ok(self.session_key)
+    }
+}
@@ -0,0 +1,442 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! An implementation of the oblivious pseudorandom function (OPRF) and the verifiable OPRF (VOPRF)
+//! from [RFC 9497](https://www.rfc-editor.org/rfc/rfc9497.html). The ciphersuite is given by a type
+//! implementing [OprfSuite] and defaults to ristretto255-SHA512. The P256-SHA256 ciphersuite is
+//! also available if the `secp256r1` feature is enabled.
+//!
+//! In an OPRF protocol, a client learns the output of a PRF keyed by the server on an input of its
+//! choice, without the server learning the input or the output. In the verifiable mode, the server
+//! also proves that the output was computed using the private key corresponding to its public key.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::oprf::{blind, finalize_verifiable, Mode, OprfPrivateKey, OprfPublicKey, Ristretto255Sha512};
+//! # use rand::thread_rng;
+//! let private_key = OprfPrivateKey::<Ristretto255Sha512>::generate(&mut thread_rng());
+//! let public_key = OprfPublicKey::from(&private_key);
+//! let input: &[u8] = b"Hello, world!";
+//!
+//! // The client blinds its input and sends the blinded element to the server.
+//! let (blind_scalar, blinded_element) = blind(Mode::Voprf, input, &mut thread_rng()).unwrap();
+//!
+//! // The server evaluates the PRF on the blinded element and proves that it did so correctly.
+//! let (evaluated_element, proof) = private_key.blind_evaluate_verifiable(&blinded_element, &mut thread_rng());
+//!
+//! // The client verifies the proof and computes the output.
+//! let output = finalize_verifiable(input, &blind_scalar, &blinded_element, &evaluated_element, &proof, &public_key).unwrap();
+//! assert_eq!(output, private_key.evaluate(Mode::Voprf, input).unwrap());
+//! ```
+
+use crate::error::FastCryptoError;
+use crate::groups::ristretto255::{RistrettoPoint, RistrettoScalar};
+#[cfg(feature = "secp256r1")]
+use crate::groups::secp256r1::{Secp256r1Point, Secp256r1Scalar};
+use crate::groups::{FixedBaseMultiplication, GroupElement, Scalar};
+#[cfg(feature = "secp256r1")]
+use crate::hash::Sha256;
+use crate::hash::{HashFunction, Sha512};
+#[cfg(feature = "secp256r1")]
+use crate::serde_helpers::ToFromByteArray;
+use crate::traits::AllowedRng;
+use elliptic_curve::hash2curve::{ExpandMsg, ExpandMsgXmd, Expander};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+/// A ciphersuite of the (V)OPRF, consisting of a prime-order group and a hash function, see
+/// section 4 of RFC 9497. Implementations are marker types.
+pub trait OprfSuite: Clone + Debug + PartialEq + Eq {
+    /// Identifier of the ciphersuite, used in the context string.
+    const IDENTIFIER: &'static [u8];
+
+    /// Elements of the group.
+    type Element: GroupElement<ScalarType = Self::Scalar>
+        + FixedBaseMultiplication
+        + Serialize
+        + DeserializeOwned;
+
+    /// Scalars of the group.
+    type Scalar: Scalar + Serialize + DeserializeOwned;
+
+    /// Output of the hash function, which is also the output of the (V)OPRF.
+    type Output: AsRef<[u8]> + Copy + Debug + Eq;
+
+    /// The HashToGroup function of the ciphersuite with the given DST.
+    fn hash_to_group(input: &[u8], dst: &[&[u8]]) -> Self::Element;
+
+    /// The HashToScalar function of the ciphersuite with the given DST.
+    fn hash_to_scalar(input: &[&[u8]], dst: &[&[u8]]) -> Self::Scalar;
+
+    /// The SerializeElement function of the ciphersuite.
+    fn serialize_element(element: &Self::Element) -> Vec<u8>;
+
+    /// Hash the concatenation of the given byte strings.
+    fn hash(input: &[&[u8]]) -> Self::Output;
+}
+
+/// The ristretto255-SHA512 ciphersuite from section 4.1 of RFC 9497.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ristretto255Sha512;
+
+impl OprfSuite for Ristretto255Sha512 {
+    const IDENTIFIER: &'static [u8] = b"ristretto255-SHA512";
+    type Element = RistrettoPoint;
+    type Scalar = RistrettoScalar;
+    type Output = [u8; 64];
+
+    fn hash_to_group(input: &[u8], dst: &[&[u8]]) -> RistrettoPoint {
+        RistrettoPoint::from_uniform_bytes(&expand_message(&[input], dst))
+    }
+
+    fn hash_to_scalar(input: &[&[u8]], dst: &[&[u8]]) -> RistrettoScalar {
+        RistrettoScalar::from_bytes_mod_order_wide(&expand_message(input, dst))
+    }
+
+    fn serialize_element(element: &RistrettoPoint) -> Vec<u8> {
+        element.compress().to_vec()
+    }
+
+    fn hash(input: &[&[u8]]) -> [u8; 64] {
+        Sha512::digest_iterator(input.iter()).digest
+    }
+}
+
+/// The P256-SHA256 ciphersuite from section 4.3 of RFC 9497.
+#[cfg(feature = "secp256r1")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct P256Sha256;
+
+#[cfg(feature = "secp256r1")]
+impl OprfSuite for P256Sha256 {
+    const IDENTIFIER: &'static [u8] = b"P256-SHA256";
+    type Element = Secp256r1Point;
+    type Scalar = Secp256r1Scalar;
+    type Output = [u8; 32];
+
+    fn hash_to_group(input: &[u8], dst: &[&[u8]]) -> Secp256r1Point {
+        // This only fails if the DST is rejected which is not the case for the DSTs used here.
+        Secp256r1Point::hash_to_curve(&[input], dst).unwrap()
+    }
+
+    fn hash_to_scalar(input: &[&[u8]], dst: &[&[u8]]) -> Secp256r1Scalar {
+        // This only fails if the DST is rejected which is not the case for the DSTs used here.
+        Secp256r1Scalar::hash_to_field(input, dst).unwrap()
+    }
+
+    fn serialize_element(element: &Secp256r1Point) -> Vec<u8> {
+        element.to_byte_array().to_vec()
+    }
+
+    fn hash(input: &[&[u8]]) -> [u8; 32] {
+        Sha256::digest_iterator(input.iter()).digest
+    }
+}
+
+/// The modes of the protocol. Inputs blinded in one mode can only be finalized in the same mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// The base mode where the client cannot verify the response of the server.
+    Oprf,
+    /// The verifiable mode where the server proves that it used the private key corresponding to its
+    /// public key.
+    Voprf,
+}
+
+impl Mode {
+    fn context_string<S: OprfSuite>(&self) -> Vec<u8> {
+        let mode = match self {
+            Mode::Oprf => 0x00,
+            Mode::Voprf => 0x01,
+        };
+        let parts: [&[u8]; 4] = [b"OPRFV1-", &[mode], b"-", S::IDENTIFIER];
+        parts.concat()
+    }
+}
+
+/// A private key of the server.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct OprfPrivateKey<S: OprfSuite = Ristretto255Sha512>(S::Scalar);
+
+/// A public key of the server.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct OprfPublicKey<S: OprfSuite = Ristretto255Sha512>(S::Element);
+
+/// The secret scalar used by the client to blind its input.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct Blind<S: OprfSuite = Ristretto255Sha512>(S::Scalar);
+
+/// A blinded input sent from the client to the server.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct BlindedElement<S: OprfSuite = Ristretto255Sha512>(S::Element);
+
+/// The evaluation of the PRF on a blinded input sent from the server to the client.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct EvaluatedElement<S: OprfSuite = Ristretto255Sha512>(S::Element);
+
+/// A proof that a batch of evaluated elements were computed using the private key corresponding to
+/// a given public key.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct Proof<S: OprfSuite = Ristretto255Sha512> {
+    c: S::Scalar,
+    s: S::Scalar,
+}
+
+/// Compute expand_message_xmd using SHA-512 with a 64 byte output.
+fn expand_message(messages: &[&[u8]], dst: &[&[u8]]) -> [u8; 64] {
+    // This only fails if the output length or DST is out of bounds which is not the case here.
+    let mut expander = ExpandMsgXmd::<sha2::Sha512>::expand_message(messages, dst, 64).unwrap();
+    let mut bytes = [0u8; 64];
+    expander.fill_bytes(&mut bytes);
+    bytes
+}
+
+/// The HashToGroup function from section 4 of RFC 9497.
+fn hash_to_group<S: OprfSuite>(mode: Mode, input: &[u8]) -> S::Element {
+    let context = mode.context_string::<S>();
+    S::hash_to_group(input, &[b"HashToGroup-", context.as_slice()])
+}
+
+/// The HashToScalar function from section 4 of RFC 9497 with the given DST prefix.
+fn hash_to_scalar<S: OprfSuite>(mode: Mode, input: &[&[u8]], dst_prefix: &[u8]) -> S::Scalar {
+    let context = mode.context_string::<S>();
+    S::hash_to_scalar(input, &[dst_prefix, context.as_slice()])
+}
+
+/// Encode a byte string prefixed by its length as a two byte big-endian integer.
+fn length_prefixed(bytes: &[u8]) -> Vec<u8> {
+    let parts: [&[u8]; 2] = [&(bytes.len() as u16).to_be_bytes(), bytes];
+    parts.concat()
+}
+
+impl<S: OprfSuite> OprfPrivateKey<S> {
+    /// Generate a new random private key.
+    pub fn generate<R: AllowedRng>(rng: &mut R) -> Self {
+        OprfPrivateKey(S::Scalar::rand(rng))
+    }
+
+    /// Deterministically derive a private key from a seed and an info string, as described in
+    /// section 3.2.1 of RFC 9497.
+    pub fn derive(mode: Mode, seed: &[u8; 32], info: &[u8]) -> Result<Self, FastCryptoError> {
+        if info.len() > u16::MAX as usize {
+            return Err(FastCryptoError::InputTooLong(u16::MAX as usize));
+        }
+        let derive_input = [seed.as_slice(), length_prefixed(info).as_slice()].concat();
+        for counter in 0..=255u8 {
+            let sk = hash_to_scalar::<S>(
+                mode,
+                &[derive_input.as_slice(), &[counter]],
+                b"DeriveKeyPair",
+            );
+            if sk != S::Scalar::zero() {
+                return Ok(OprfPrivateKey(sk));
+            }
+        }
+        Err(FastCryptoError::GeneralOpaqueError)
+    }
+
+    /// Evaluate the PRF on a blinded element without a proof. This is the BlindEvaluate function
+    /// of the base OPRF mode.
+    pub fn blind_evaluate(&self, blinded_element: &BlindedElement<S>) -> EvaluatedElement<S> {
+        EvaluatedElement(blinded_element.0 * self.0)
+    }
+
+    /// Evaluate the PRF on a blinded element and prove that it was done correctly. This is the
+    /// BlindEvaluate function of the VOPRF mode.
+    pub fn blind_evaluate_verifiable<R: AllowedRng>(
+        &self,
+        blinded_element: &BlindedElement<S>,
+        rng: &mut R,
+    ) -> (EvaluatedElement<S>, Proof<S>) {
+        self.blind_evaluate_verifiable_with_scalar(blinded_element, &S::Scalar::rand(rng))
+    }
+
+    pub(crate) fn blind_evaluate_verifiable_with_scalar(
+        &self,
+        blinded_element: &BlindedElement<S>,
+        r: &S::Scalar,
+    ) -> (EvaluatedElement<S>, Proof<S>) {
+        let evaluated_element = self.blind_evaluate(blinded_element);
+        let proof = Proof::generate(
+            &self.0,
+            &OprfPublicKey::<S>::from(self).0,
+            &[blinded_element.0],
+            &[evaluated_element.0],
+            r,
+        );
+        (evaluated_element, proof)
+    }
+
+    /// Compute the output of the PRF on an input directly. This gives the same result as running
+    /// the protocol with a client in the given mode.
+    pub fn evaluate(&self, mode: Mode, input: &[u8]) -> Result<S::Output, FastCryptoError> {
+        let input_element = hash_to_group::<S>(mode, input);
+        if input_element == S::Element::zero() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        finalize_element::<S>(input, &(input_element * self.0))
+    }
+}
+
+impl<S: OprfSuite> From<&OprfPrivateKey<S>> for OprfPublicKey<S> {
+    fn from(private_key: &OprfPrivateKey<S>) -> Self {
+        OprfPublicKey(S::Element::mul_generator(&private_key.0))
+    }
+}
+
+/// Blind an input using a random scalar. Returns the blind, which should be kept secret by the
+/// client, and the blinded element which should be sent to the server.
+pub fn blind<S: OprfSuite, R: AllowedRng>(
+    mode: Mode,
+    input: &[u8],
+    rng: &mut R,
+) -> Result<(Blind<S>, BlindedElement<S>), FastCryptoError> {
+    blind_with_scalar(mode, input, S::Scalar::rand(rng))
+}
+
+pub(crate) fn blind_with_scalar<S: OprfSuite>(
+    mode: Mode,
+    input: &[u8],
+    blind: S::Scalar,
+) -> Result<(Blind<S>, BlindedElement<S>), FastCryptoError> {
+    let input_element = hash_to_group::<S>(mode, input);
+    if input_element == S::Element::zero() {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    Ok((Blind(blind), BlindedElement(input_element * blind)))
+}
+
+/// Compute the output of the PRF from an evaluated element returned by a server in the base OPRF
+/// mode.
+pub fn finalize<S: OprfSuite>(
+    input: &[u8],
+    blind: &Blind<S>,
+    evaluated_element: &EvaluatedElement<S>,
+) -> Result<S::Output, FastCryptoError> {
+    let unblinded_element = evaluated_element.0 * (S::Scalar::generator() / blind.0)?;
+    finalize_element::<S>(input, &unblinded_element)
+}
+
+/// Verify the proof from the server and compute the output of the PRF from an evaluated element
+/// returned in the VOPRF mode.
+pub fn finalize_verifiable<S: OprfSuite>(
+    input: &[u8],
+    blind: &Blind<S>,
+    blinded_element: &BlindedElement<S>,
+    evaluated_element: &EvaluatedElement<S>,
+    proof: &Proof<S>,
+    public_key: &OprfPublicKey<S>,
+) -> Result<S::Output, FastCryptoError> {
+    proof.verify(&public_key.0, &[blinded_element.0], &[evaluated_element.0])?;
+    finalize(input, blind, evaluated_element)
+}
+
+/// The final hash of the Finalize function in section 3.3 of RFC 9497.
+fn finalize_element<S: OprfSuite>(
+    input: &[u8],
+    unblinded_element: &S::Element,
+) -> Result<S::Output, FastCryptoError> {
+    if input.len() > u16::MAX as usize {
+        return Err(FastCryptoError::InputTooLong(u16::MAX as usize));
+    }
+    Ok(S::hash(&[
+        &length_prefixed(input),
+        &length_prefixed(&S::serialize_element(unblinded_element)),
+        b"Finalize",
+    ]))
+}
+
+/// The ComputeComposites function from section 2.2.1 of RFC 9497. If the private key is given, the
+/// second composite is computed using that instead (ComputeCompositesFast).
+fn compute_composites<S: OprfSuite>(
+    private_key: Option<&S::Scalar>,
+    public_key: &S::Element,
+    c: &[S::Element],
+    d: &[S::Element],
+) -> (S::Element, S::Element) {
+    let mode = Mode::Voprf;
+    let seed_dst = [b"Seed-".to_vec(), mode.context_string::<S>()].concat();
+    let seed = S::hash(&[
+        &length_prefixed(&S::serialize_element(public_key)),
+        &length_prefixed(&seed_dst),
+    ]);
+
+    let mut m = S::Element::zero();
+    let mut z = S::Element::zero();
+    for (i, (c_i, d_i)) in c.iter().zip(d.iter()).enumerate() {
+        let composite_transcript = [
+            length_prefixed(seed.as_ref()),
+            (i as u16).to_be_bytes().to_vec(),
+            length_prefixed(&S::serialize_element(c_i)),
+            length_prefixed(&S::serialize_element(d_i)),
+            b"Composite".to_vec(),
+        ]
+        .concat();
+        let d_i_scalar =
+            hash_to_scalar::<S>(mode, &[composite_transcript.as_slice()], b"HashToScalar-");
+        m += *c_i * d_i_scalar;
+        if private_key.is_none() {
+            z += *d_i * d_i_scalar;
+        }
+    }
+    if let Some(k) = private_key {
+        z = m * k;
+    }
+    (m, z)
+}
+
+/// The challenge computed in GenerateProof and VerifyProof in section 2.2 of RFC 9497.
+fn challenge<S: OprfSuite>(points: &[&S::Element; 5]) -> S::Scalar {
+    let mut transcript = points
+        .iter()
+        .flat_map(|p| length_prefixed(&S::serialize_element(p)))
+        .collect::<Vec<_>>();
+    transcript.extend_from_slice(b"Challenge");
+    hash_to_scalar::<S>(Mode::Voprf, &[transcript.as_slice()], b"HashToScalar-")
+}
+
+impl<S: OprfSuite> Proof<S> {
+    /// The GenerateProof function from section 2.2.1 of RFC 9497 with A the generator.
+    fn generate(
+        private_key: &S::Scalar,
+        public_key: &S::Element,
+        c: &[S::Element],
+        d: &[S::Element],
+        r: &S::Scalar,
+    ) -> Self {
+        let (m, z) = compute_composites::<S>(Some(private_key), public_key, c, d);
+        let t2 = S::Element::mul_generator(r);
+        let t3 = m * r;
+        let c = challenge::<S>(&[public_key, &m, &z, &t2, &t3]);
+        Proof {
+            c,
+            s: *r - c * private_key,
+        }
+    }
+
+    /// The VerifyProof function from section 2.2.2 of RFC 9497 with A the generator.
+    fn verify(
+        &self,
+        public_key: &S::Element,
+        c: &[S::Element],
+        d: &[S::Element],
+    ) -> Result<(), FastCryptoError> {
+        if c.len() != d.len() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let (m, z) = compute_composites::<S>(None, public_key, c, d);
+        let t2 = S::Element::mul_generator(&self.s) + *public_key * self.c;
+        let t3 = m * self.s + z * self.c;
+        if challenge::<S>(&[public_key, &m, &z, &t2, &t3]) != self.c {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        Ok(())
+    }
+}
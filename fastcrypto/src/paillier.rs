@@ -0,0 +1,511 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The Paillier additively homomorphic encryption scheme together with the zero-knowledge proofs
+//! used by the multiplicative-to-additive (MtA) share conversion in threshold ECDSA protocols such
+//! as [GG18](https://eprint.iacr.org/2019/114.pdf).
+//!
+//! The scheme uses the generator `g = N + 1`, so an encryption of `m` with nonce `r` is
+//! `(1 + mN) * r^N mod N^2`. The proofs follow appendix A of GG18 and are made non-interactive
+//! using Fiat-Shamir with SHA-256:
+//! * [RangeProof] shows that a ciphertext encrypts a value `m` in `[0, q^3)` where `q` is the order
+//!   of the signature group.
+//! * [AffineProof] shows that a ciphertext `c2 = c1^x * Enc(y)` was computed correctly from a
+//!   ciphertext `c1` for values `x` in `[0, q^3)` and `y` in `[0, q^7)`, optionally also proving
+//!   that `x` is the discrete log of a public group element.
+//!
+//! Both proofs are relative to [RingPedersenParameters] generated by the verifier. The parameters
+//! must be generated honestly (or be accompanied by a proof that `h1` and `h2` generate the same
+//! group which is not provided here), and the Paillier modulus should be at least 2048 bits and
+//! larger than `q^8` to avoid overflows in the MtA protocol.
+//!
+//! The big integer arithmetic is not constant time.
+//!
+//! This module is only available when the `paillier` feature is enabled.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::paillier::*;
+//! # use num_bigint::BigUint;
+//! # use rand::thread_rng;
+//! let sk = PaillierPrivateKey::generate(512, &mut thread_rng()).unwrap();
+//! let pk = sk.public_key();
+//! let (c1, _) = pk.encrypt(&BigUint::from(3u32), &mut thread_rng()).unwrap();
+//! let (c2, _) = pk.encrypt(&BigUint::from(4u32), &mut thread_rng()).unwrap();
+//! let sum = pk.add(&c1, &pk.mul(&c2, &BigUint::from(5u32)));
+//! assert_eq!(sk.decrypt(&sum).unwrap(), BigUint::from(23u32));
+//! ```
+
+use crate::error::FastCryptoError;
+use crate::hash::{HashFunction, Sha256};
+use crate::number_theory::{gcd, generate_prime, mod_inverse, random_below, random_unit};
+use crate::traits::AllowedRng;
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+
+/// The smallest supported bit length of a Paillier modulus.
+pub const MIN_MODULUS_BITS: u64 = 128;
+
+const RANGE_PROOF_DST: &[u8] = b"fastcrypto-paillier-range-proof-v1";
+const AFFINE_PROOF_DST: &[u8] = b"fastcrypto-paillier-affine-proof-v1";
+
+/// A Paillier public key. It is serialized as the modulus `N`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "BigUint", into = "BigUint")]
+pub struct PaillierPublicKey {
+    n: BigUint,
+    n_squared: BigUint,
+}
+
+/// A Paillier private key.
+#[derive(Clone, Debug)]
+pub struct PaillierPrivateKey {
+    public_key: PaillierPublicKey,
+    phi: BigUint,
+    phi_inverse: BigUint,
+}
+
+/// A Paillier ciphertext, an element of the multiplicative group modulo `N^2`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ciphertext(BigUint);
+
+/// Parameters of a Pedersen commitment over an RSA modulus `Ñ`, `h1^x * h2^r mod Ñ`, generated by
+/// the verifier of the proofs.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RingPedersenParameters {
+    n_tilde: BigUint,
+    h1: BigUint,
+    h2: BigUint,
+}
+
+/// Proof that a ciphertext encrypts a value in `[0, q^3)` (GG18, section A.1).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RangeProof {
+    z: BigUint,
+    u: BigUint,
+    w: BigUint,
+    s: BigUint,
+    s1: BigUint,
+    s2: BigUint,
+}
+
+/// Proof that `c2 = c1^x * (1 + yN) * r^N mod N^2` for `x` in `[0, q^3)` and `y` in `[0, q^7)`
+/// (GG18, section A.2 and A.3).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AffineProof {
+    z: BigUint,
+    z_prime: BigUint,
+    t: BigUint,
+    v: BigUint,
+    w: BigUint,
+    s: BigUint,
+    s1: BigUint,
+    s2: BigUint,
+    t1: BigUint,
+    t2: BigUint,
+}
+
+/// Generate two distinct primes whose product has exactly `bits` bits.
+fn generate_prime_pair<R: AllowedRng>(
+    bits: u64,
+    rng: &mut R,
+) -> Result<(BigUint, BigUint), FastCryptoError> {
+    if bits < MIN_MODULUS_BITS || bits % 2 != 0 {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    let p = generate_prime(bits / 2, rng);
+    loop {
+        let q = generate_prime(bits / 2, rng);
+        if q != p {
+            return Ok((p, q));
+        }
+    }
+}
+
+/// Fiat-Shamir challenge in `[0, q)` computed from a list of integers.
+fn challenge(dst: &[u8], q: &BigUint, values: &[&BigUint]) -> BigUint {
+    let mut hash = Sha256::default();
+    hash.update(dst);
+    for value in values {
+        let bytes = value.to_bytes_be();
+        hash.update((bytes.len() as u64).to_be_bytes());
+        hash.update(bytes);
+    }
+    BigUint::from_bytes_be(&hash.finalize().digest) % q
+}
+
+impl PaillierPublicKey {
+    /// Create a public key from a modulus `N`.
+    pub fn new(n: BigUint) -> Result<Self, FastCryptoError> {
+        if n.bits() < MIN_MODULUS_BITS || !n.bit(0) {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(Self {
+            n_squared: &n * &n,
+            n,
+        })
+    }
+
+    /// The modulus `N`.
+    pub fn modulus(&self) -> &BigUint {
+        &self.n
+    }
+
+    /// Check that `c` is an element of the multiplicative group modulo `N^2`.
+    fn is_valid(&self, c: &BigUint) -> bool {
+        c < &self.n_squared && gcd(c, &self.n) == BigUint::from(1u8)
+    }
+
+    /// Encrypt a message `m < N` using the given nonce `r`, which must be a unit modulo `N`.
+    pub fn encrypt_with_nonce(
+        &self,
+        m: &BigUint,
+        r: &BigUint,
+    ) -> Result<Ciphertext, FastCryptoError> {
+        if m >= &self.n || r >= &self.n || gcd(r, &self.n) != BigUint::from(1u8) {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(Ciphertext(self.raw_encrypt(m, r)))
+    }
+
+    /// Encrypt a message `m < N`. Returns the ciphertext and the nonce used.
+    pub fn encrypt<R: AllowedRng>(
+        &self,
+        m: &BigUint,
+        rng: &mut R,
+    ) -> Result<(Ciphertext, BigUint), FastCryptoError> {
+        let r = random_unit(&self.n, rng);
+        Ok((self.encrypt_with_nonce(m, &r)?, r))
+    }
+
+    /// Compute `(1 + mN) * r^N mod N^2` for arbitrary non-negative `m`.
+    fn raw_encrypt(&self, m: &BigUint, r: &BigUint) -> BigUint {
+        let g_m = (BigUint::from(1u8) + (m % &self.n) * &self.n) % &self.n_squared;
+        g_m * r.modpow(&self.n, &self.n_squared) % &self.n_squared
+    }
+
+    /// Homomorphic addition: the result decrypts to the sum of the plaintexts modulo `N`.
+    pub fn add(&self, c1: &Ciphertext, c2: &Ciphertext) -> Ciphertext {
+        Ciphertext(&c1.0 * &c2.0 % &self.n_squared)
+    }
+
+    /// Homomorphic scalar multiplication: the result decrypts to `k` times the plaintext modulo
+    /// `N`.
+    pub fn mul(&self, c: &Ciphertext, k: &BigUint) -> Ciphertext {
+        Ciphertext(c.0.modpow(k, &self.n_squared))
+    }
+}
+
+impl TryFrom<BigUint> for PaillierPublicKey {
+    type Error = FastCryptoError;
+
+    fn try_from(n: BigUint) -> Result<Self, Self::Error> {
+        Self::new(n)
+    }
+}
+
+impl From<PaillierPublicKey> for BigUint {
+    fn from(pk: PaillierPublicKey) -> Self {
+        pk.n
+    }
+}
+
+impl PaillierPrivateKey {
+    /// Generate a new key pair with a modulus of `bits` bits.
+    pub fn generate<R: AllowedRng>(bits: u64, rng: &mut R) -> Result<Self, FastCryptoError> {
+        let (p, q) = generate_prime_pair(bits, rng)?;
+        Self::from_primes(&p, &q)
+    }
+
+    /// Create a private key from the prime factors of the modulus. The primes must have the same
+    /// bit length.
+    pub fn from_primes(p: &BigUint, q: &BigUint) -> Result<Self, FastCryptoError> {
+        if p == q || p.bits() != q.bits() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let public_key = PaillierPublicKey::new(p * q)?;
+        let one = BigUint::from(1u8);
+        let phi = (p - &one) * (q - &one);
+        let phi_inverse = mod_inverse(&phi, &public_key.n).ok_or(FastCryptoError::InvalidInput)?;
+        Ok(Self {
+            public_key,
+            phi,
+            phi_inverse,
+        })
+    }
+
+    /// The public key of this private key.
+    pub fn public_key(&self) -> &PaillierPublicKey {
+        &self.public_key
+    }
+
+    /// Decrypt a ciphertext.
+    pub fn decrypt(&self, c: &Ciphertext) -> Result<BigUint, FastCryptoError> {
+        let pk = &self.public_key;
+        if !pk.is_valid(&c.0) {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        // L(c^phi mod N^2) * phi^{-1} mod N where L(x) = (x - 1) / N.
+        let l = (c.0.modpow(&self.phi, &pk.n_squared) - 1u8) / &pk.n;
+        Ok(l * &self.phi_inverse % &pk.n)
+    }
+}
+
+impl RingPedersenParameters {
+    /// Generate new parameters with a modulus of `bits` bits.
+    pub fn generate<R: AllowedRng>(bits: u64, rng: &mut R) -> Result<Self, FastCryptoError> {
+        let (p, q) = generate_prime_pair(bits, rng)?;
+        let n_tilde = &p * &q;
+        let one = BigUint::from(1u8);
+        let phi = (&p - &one) * (&q - &one);
+
+        // h2 is a random quadratic residue and h1 a random power of it.
+        let r = random_unit(&n_tilde, rng);
+        let h2 = r.modpow(&BigUint::from(2u8), &n_tilde);
+        let alpha = random_below(&phi, rng);
+        let h1 = h2.modpow(&alpha, &n_tilde);
+        Self::new(n_tilde, h1, h2)
+    }
+
+    /// Create parameters from a modulus `Ñ` and two elements of the multiplicative group modulo
+    /// `Ñ`.
+    pub fn new(n_tilde: BigUint, h1: BigUint, h2: BigUint) -> Result<Self, FastCryptoError> {
+        let one = BigUint::from(1u8);
+        if n_tilde.bits() < MIN_MODULUS_BITS
+            || [&h1, &h2]
+                .iter()
+                .any(|h| **h <= one || **h >= n_tilde || gcd(h, &n_tilde) != one)
+            || h1 == h2
+        {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(Self { n_tilde, h1, h2 })
+    }
+
+    /// Compute `h1^x * h2^r mod Ñ`.
+    fn commit(&self, x: &BigUint, r: &BigUint) -> BigUint {
+        self.h1.modpow(x, &self.n_tilde) * self.h2.modpow(r, &self.n_tilde) % &self.n_tilde
+    }
+
+    fn is_valid(&self, x: &BigUint) -> bool {
+        x < &self.n_tilde && gcd(x, &self.n_tilde) == BigUint::from(1u8)
+    }
+}
+
+impl RangeProof {
+    /// Prove that `c = Enc(m, r)` encrypts `m` in `[0, q^3)`. The statement is only proven for
+    /// `m < q`, which is the case used in the MtA protocol.
+    pub fn prove<R: AllowedRng>(
+        pk: &PaillierPublicKey,
+        setup: &RingPedersenParameters,
+        q: &BigUint,
+        c: &Ciphertext,
+        m: &BigUint,
+        r: &BigUint,
+        rng: &mut R,
+    ) -> Result<Self, FastCryptoError> {
+        if m >= q {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let q3 = q.pow(3);
+        let alpha = random_below(&q3, rng);
+        let beta = random_unit(&pk.n, rng);
+        let gamma = random_below(&(&q3 * &setup.n_tilde), rng);
+        let rho = random_below(&(q * &setup.n_tilde), rng);
+
+        let z = setup.commit(m, &rho);
+        let u = pk.raw_encrypt(&alpha, &beta);
+        let w = setup.commit(&alpha, &gamma);
+
+        let e = Self::challenge(pk, setup, q, c, &z, &u, &w);
+        Ok(Self {
+            s: r.modpow(&e, &pk.n) * beta % &pk.n,
+            s1: &e * m + alpha,
+            s2: &e * rho + gamma,
+            z,
+            u,
+            w,
+        })
+    }
+
+    /// Verify that `c` encrypts a value in `[0, q^3)`.
+    pub fn verify(
+        &self,
+        pk: &PaillierPublicKey,
+        setup: &RingPedersenParameters,
+        q: &BigUint,
+        c: &Ciphertext,
+    ) -> Result<(), FastCryptoError> {
+        if !pk.is_valid(&c.0)
+            || !pk.is_valid(&self.u)
+            || !setup.is_valid(&self.z)
+            || !setup.is_valid(&self.w)
+            || self.s1 >= q.pow(3)
+            || self.s >= pk.n
+        {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        let e = Self::challenge(pk, setup, q, c, &self.z, &self.u, &self.w);
+
+        // u * c^e = Enc(s1, s) mod N^2
+        let lhs = &self.u * c.0.modpow(&e, &pk.n_squared) % &pk.n_squared;
+        if lhs != pk.raw_encrypt(&self.s1, &self.s) {
+            return Err(FastCryptoError::InvalidProof);
+        }
+
+        // w * z^e = h1^s1 * h2^s2 mod Ñ
+        let lhs = &self.w * self.z.modpow(&e, &setup.n_tilde) % &setup.n_tilde;
+        if lhs != setup.commit(&self.s1, &self.s2) {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        Ok(())
+    }
+
+    fn challenge(
+        pk: &PaillierPublicKey,
+        setup: &RingPedersenParameters,
+        q: &BigUint,
+        c: &Ciphertext,
+        z: &BigUint,
+        u: &BigUint,
+        w: &BigUint,
+    ) -> BigUint {
+        challenge(
+            RANGE_PROOF_DST,
+            q,
+            &[&pk.n, &setup.n_tilde, &setup.h1, &setup.h2, &c.0, z, u, w],
+        )
+    }
+}
+
+impl AffineProof {
+    /// Prove that `c2 = c1^x * Enc(y, r)` for `x < q` and `y < q^5`, where `c1` and `c2` are
+    /// encrypted under `pk`. The `statement` is an optional encoding of additional public values
+    /// which are bound to the proof, e.g., the public group element `X = x * G` which the verifier
+    /// should check against `x` in the "MtAwc" variant of the protocol.
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove<R: AllowedRng>(
+        pk: &PaillierPublicKey,
+        setup: &RingPedersenParameters,
+        q: &BigUint,
+        c1: &Ciphertext,
+        c2: &Ciphertext,
+        x: &BigUint,
+        y: &BigUint,
+        r: &BigUint,
+        statement: &[u8],
+        rng: &mut R,
+    ) -> Result<Self, FastCryptoError> {
+        if x >= q || y >= &q.pow(5) {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let q_n_tilde = q * &setup.n_tilde;
+        let q3 = q.pow(3);
+        let q3_n_tilde = &q3 * &setup.n_tilde;
+
+        let alpha = random_below(&q3, rng);
+        let rho = random_below(&q_n_tilde, rng);
+        let rho_prime = random_below(&q3_n_tilde, rng);
+        let sigma = random_below(&q_n_tilde, rng);
+        let beta = random_unit(&pk.n, rng);
+        let gamma = random_below(&q.pow(7), rng);
+        let tau = random_below(&q3_n_tilde, rng);
+
+        let z = setup.commit(x, &rho);
+        let z_prime = setup.commit(&alpha, &rho_prime);
+        let t = setup.commit(y, &sigma);
+        let v = c1.0.modpow(&alpha, &pk.n_squared) * pk.raw_encrypt(&gamma, &beta) % &pk.n_squared;
+        let w = setup.commit(&gamma, &tau);
+
+        let e = Self::challenge(pk, setup, q, c1, c2, statement, [&z, &z_prime, &t, &v, &w]);
+        Ok(Self {
+            s: r.modpow(&e, &pk.n) * beta % &pk.n,
+            s1: &e * x + alpha,
+            s2: &e * rho + rho_prime,
+            t1: &e * y + gamma,
+            t2: &e * sigma + tau,
+            z,
+            z_prime,
+            t,
+            v,
+            w,
+        })
+    }
+
+    /// Verify that `c2` was computed correctly from `c1`.
+    pub fn verify(
+        &self,
+        pk: &PaillierPublicKey,
+        setup: &RingPedersenParameters,
+        q: &BigUint,
+        c1: &Ciphertext,
+        c2: &Ciphertext,
+        statement: &[u8],
+    ) -> Result<(), FastCryptoError> {
+        if !pk.is_valid(&c1.0)
+            || !pk.is_valid(&c2.0)
+            || !pk.is_valid(&self.v)
+            || [&self.z, &self.z_prime, &self.t, &self.w]
+                .iter()
+                .any(|x| !setup.is_valid(x))
+            || self.s1 >= q.pow(3)
+            || self.t1 >= q.pow(7)
+            || self.s >= pk.n
+        {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        let e = Self::challenge(
+            pk,
+            setup,
+            q,
+            c1,
+            c2,
+            statement,
+            [&self.z, &self.z_prime, &self.t, &self.v, &self.w],
+        );
+
+        // z^e * z' = h1^s1 * h2^s2 mod Ñ
+        let lhs = self.z.modpow(&e, &setup.n_tilde) * &self.z_prime % &setup.n_tilde;
+        if lhs != setup.commit(&self.s1, &self.s2) {
+            return Err(FastCryptoError::InvalidProof);
+        }
+
+        // t^e * w = h1^t1 * h2^t2 mod Ñ
+        let lhs = self.t.modpow(&e, &setup.n_tilde) * &self.w % &setup.n_tilde;
+        if lhs != setup.commit(&self.t1, &self.t2) {
+            return Err(FastCryptoError::InvalidProof);
+        }
+
+        // c2^e * v = c1^s1 * Enc(t1, s) mod N^2
+        let lhs = c2.0.modpow(&e, &pk.n_squared) * &self.v % &pk.n_squared;
+        let rhs = c1.0.modpow(&self.s1, &pk.n_squared) * pk.raw_encrypt(&self.t1, &self.s)
+            % &pk.n_squared;
+        if lhs != rhs {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        Ok(())
+    }
+
+    fn challenge(
+        pk: &PaillierPublicKey,
+        setup: &RingPedersenParameters,
+        q: &BigUint,
+        c1: &Ciphertext,
+        c2: &Ciphertext,
+        statement: &[u8],
+        commitments: [&BigUint; 5],
+    ) -> BigUint {
+        let statement = BigUint::from_bytes_be(&[&[1u8], statement].concat());
+        let mut values = vec![
+            &pk.n,
+            &setup.n_tilde,
+            &setup.h1,
+            &setup.h2,
+            &c1.0,
+            &c2.0,
+            &statement,
+        ];
+        values.extend(commitments);
+        challenge(AFFINE_PROOF_DST, q, &values)
+    }
+}
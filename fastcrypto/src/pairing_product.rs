@@ -0,0 +1,156 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verification of pairing product equations `e(a_1, b_1) + ... + e(a_n, b_n) = t`, written
+//! additively in the target group, which are the verification equations of many pairing-based
+//! schemes, e.g. [crate::bbs_plus].
+//!
+//! A single equation is verified with [Pairing::multi_pairing], which shares the final
+//! exponentiation between the pairings. Many equations are verified at once with [verify_batch],
+//! which checks a random linear combination of the equations. Pairs of different equations with the
+//! same point in the second group are merged, so e.g. all pairs with the generator of G2 cost a
+//! single Miller loop.
+//!
+//! Equations can also be written term by term with [PairingCheck], which takes terms
+//! `s * e(a, b)` with scalar exponents and compiles them into a single equation, applying the
+//! exponents in the first group and merging terms with the same point in the second group.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::pairing_product::*;
+//! # use fastcrypto::groups::bls12381::{G1Element, G2Element, Scalar};
+//! # use fastcrypto::groups::{GroupElement, Scalar as _};
+//! # use rand::thread_rng;
+//! // e(x * g_1, g_2) - e(g_1, x * g_2) = 0
+//! let x = Scalar::rand(&mut thread_rng());
+//! let equation = PairingProductEquation::<G1Element>::new(
+//!     vec![
+//!         (G1Element::generator() * x, G2Element::generator()),
+//!         (-G1Element::generator(), G2Element::generator() * x),
+//!     ],
+//!     GroupElement::zero(),
+//! );
+//! assert!(equation.verify().is_ok());
+//! assert!(verify_batch(&[equation.clone(), equation], &mut thread_rng()).is_ok());
+//!
+//! // The same equation as a pairing check: x * e(g_1, g_2) = e(g_1, x * g_2).
+//! assert!(PairingCheck::<G1Element>::new()
+//!     .pair_scaled(x, G1Element::generator(), G2Element::generator())
+//!     .pair_negated(G1Element::generator(), G2Element::generator() * x)
+//!     .verify()
+//!     .is_ok());
+//! ```
+
+use crate::error::FastCryptoError;
+use crate::groups::{GroupElement, Pairing, Scalar};
+use crate::traits::AllowedRng;
+
+/// An equation `sum e(a_i, b_i) = target`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PairingProductEquation<G: Pairing> {
+    pub pairs: Vec<(G, G::Other)>,
+    pub target: G::Output,
+}
+
+impl<G: Pairing> PairingProductEquation<G> {
+    pub fn new(pairs: Vec<(G, G::Other)>, target: G::Output) -> Self {
+        Self { pairs, target }
+    }
+
+    /// Check whether the equation holds.
+    pub fn verify(&self) -> Result<(), FastCryptoError> {
+        let (a, b): (Vec<G>, Vec<G::Other>) = self.pairs.iter().cloned().unzip();
+        if G::multi_pairing(&a, &b)? != self.target {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        Ok(())
+    }
+}
+
+/// A builder of a [PairingProductEquation] from a sum of pairings with scalar exponents.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PairingCheck<G: Pairing> {
+    pairs: Vec<(G, G::Other)>,
+    target: G::Output,
+}
+
+impl<G: Pairing> Default for PairingCheck<G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<G: Pairing> PairingCheck<G> {
+    /// The check `0 = 0`, to which terms are added.
+    pub fn new() -> Self {
+        Self {
+            pairs: Vec::new(),
+            target: G::Output::zero(),
+        }
+    }
+
+    /// Add the term `e(a, b)`.
+    pub fn pair(mut self, a: G, b: G::Other) -> Self {
+        self.pairs.push((a, b));
+        self
+    }
+
+    /// Add the term `exponent * e(a, b)`, which is computed as `e(exponent * a, b)`.
+    pub fn pair_scaled(self, exponent: G::ScalarType, a: G, b: G::Other) -> Self {
+        self.pair(a * exponent, b)
+    }
+
+    /// Subtract the term `e(a, b)`.
+    pub fn pair_negated(self, a: G, b: G::Other) -> Self {
+        self.pair(-a, b)
+    }
+
+    /// Set the right hand side of the equation, which is zero by default.
+    pub fn equals(mut self, target: G::Output) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Compile the terms into an equation with one pair per distinct point in the second group,
+    /// leaving out pairs with the identity.
+    pub fn build(self) -> PairingProductEquation<G> {
+        let mut pairs: Vec<(G, G::Other)> = Vec::with_capacity(self.pairs.len());
+        for (a, b) in self.pairs {
+            match pairs.iter().position(|(_, b_j)| *b_j == b) {
+                Some(j) => pairs[j].0 += a,
+                None => pairs.push((a, b)),
+            }
+        }
+        pairs.retain(|(a, b)| *a != G::zero() && *b != G::Other::zero());
+        PairingProductEquation::new(pairs, self.target)
+    }
+
+    /// Check whether the equation holds with a single multi-pairing.
+    pub fn verify(self) -> Result<(), FastCryptoError> {
+        self.build().verify()
+    }
+}
+
+/// Check whether all equations hold, except with probability `1 / r` where `r` is the order of the
+/// groups, using a single multi-pairing.
+pub fn verify_batch<G, R>(
+    equations: &[PairingProductEquation<G>],
+    rng: &mut R,
+) -> Result<(), FastCryptoError>
+where
+    G: Pairing,
+    G::Output: GroupElement<ScalarType = G::ScalarType>,
+    R: AllowedRng,
+{
+    // Multiply each equation by a random scalar and add them.
+    let mut check = PairingCheck::new();
+    let mut target = G::Output::zero();
+    for equation in equations {
+        let r = G::ScalarType::rand(rng);
+        for (a_i, b_i) in &equation.pairs {
+            check = check.pair_scaled(r, *a_i, *b_i);
+        }
+        target += equation.target * r;
+    }
+    check.equals(target).verify()
+}
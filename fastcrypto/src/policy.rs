@@ -0,0 +1,130 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A process-wide policy forbidding algorithms or parameters below a given security level, for
+//! deployments which must comply with e.g. NIST SP 800-131A.
+//!
+//! The policy is enforced when keys or parameters of variable strength are imported and again
+//! when they are used for verification, so installing a stricter policy also affects keys imported
+//! before. The policy currently covers:
+//! * [Algorithm::RsaSignature]: Import of [RSAPublicKey](crate::rsa::RSAPublicKey) and RSA
+//!   signature verification (requires the `rsa` feature).
+//! * [Algorithm::RsaAccumulator]: Import of the parameters of an RSA accumulator.
+//!
+//! The security levels of factoring based algorithms are taken from Table 2 of NIST SP 800-57
+//! Part 1, see [factoring_security_bits]. The default policy allows everything.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::policy::*;
+//! # #[cfg(feature = "rsa")] {
+//! # use fastcrypto::rsa::RSAPublicKey;
+//! install(Policy::default().with_min_security_bits(112));
+//! assert!(RSAPublicKey::from_raw_components(&[0xff; 128], &[1, 0, 1]).is_err());
+//! assert!(RSAPublicKey::from_raw_components(&[0xff; 256], &[1, 0, 1]).is_ok());
+//!
+//! install(Policy::default().forbid(Algorithm::RsaSignature));
+//! assert!(RSAPublicKey::from_raw_components(&[0xff; 256], &[1, 0, 1]).is_err());
+//! # }
+//! ```
+
+use crate::error::{FastCryptoError, FastCryptoResult};
+use once_cell::sync::Lazy;
+use std::fmt::{Display, Formatter};
+use std::sync::RwLock;
+
+/// The algorithms covered by the policy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Algorithm {
+    RsaSignature,
+    RsaAccumulator,
+}
+
+impl Display for Algorithm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Algorithm::RsaSignature => write!(f, "RSA signature"),
+            Algorithm::RsaAccumulator => write!(f, "RSA accumulator"),
+        }
+    }
+}
+
+/// The minimal security level and the forbidden algorithms.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Policy {
+    min_security_bits: u32,
+    forbidden: Vec<Algorithm>,
+}
+
+impl Policy {
+    /// Forbid all instances of algorithms with fewer than the given bits of security.
+    pub fn with_min_security_bits(mut self, bits: u32) -> Self {
+        self.min_security_bits = bits;
+        self
+    }
+
+    /// Forbid the given algorithm regardless of its parameters.
+    pub fn forbid(mut self, algorithm: Algorithm) -> Self {
+        if !self.forbidden.contains(&algorithm) {
+            self.forbidden.push(algorithm);
+        }
+        self
+    }
+
+    pub fn min_security_bits(&self) -> u32 {
+        self.min_security_bits
+    }
+
+    /// Check whether this policy allows an instance of the given algorithm with the given bits of
+    /// security.
+    pub fn check(&self, algorithm: Algorithm, security_bits: u32) -> FastCryptoResult<()> {
+        if self.forbidden.contains(&algorithm) {
+            return Err(FastCryptoError::GeneralError(format!(
+                "{} is forbidden by the policy",
+                algorithm
+            )));
+        }
+        if security_bits < self.min_security_bits {
+            return Err(FastCryptoError::GeneralError(format!(
+                "{} with {} bits of security is below the minimum of {} bits",
+                algorithm, security_bits, self.min_security_bits
+            )));
+        }
+        Ok(())
+    }
+}
+
+static POLICY: Lazy<RwLock<Policy>> = Lazy::new(|| RwLock::new(Policy::default()));
+
+/// Replace the policy of this process.
+pub fn install(policy: Policy) {
+    *POLICY.write().expect("Lock is not poisoned") = policy;
+}
+
+/// The policy of this process.
+pub fn current() -> Policy {
+    POLICY.read().expect("Lock is not poisoned").clone()
+}
+
+/// Check an instance of an algorithm against the policy of this process.
+pub(crate) fn enforce(algorithm: Algorithm, security_bits: u32) -> FastCryptoResult<()> {
+    POLICY
+        .read()
+        .expect("Lock is not poisoned")
+        .check(algorithm, security_bits)
+}
+
+/// The bits of security of an algorithm based on the hardness of factoring a modulus of the given
+/// size, e.g. RSA, rounded down to the nearest level of NIST SP 800-57. Moduli of fewer than 1024
+/// bits are considered to have no security.
+pub fn factoring_security_bits(modulus_bits: u64) -> u32 {
+    match modulus_bits {
+        15360.. => 256,
+        7680.. => 192,
+        3072.. => 128,
+        2048.. => 112,
+        1024.. => 80,
+        _ => 0,
+    }
+}
@@ -0,0 +1,231 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Operations on univariate polynomials over the scalars of a group, e.g. for Reed-Solomon codes,
+//! polynomial commitments and secret sharing.
+//!
+//! Polynomials are given by their coefficients, starting with the constant term. Besides
+//! evaluation, division and interpolation over arbitrary points, the module has radix-2 FFTs which
+//! evaluate and interpolate over a multiplicative subgroup of order `2^k` in `O(n log n)`, given a
+//! generator of the subgroup. The scalar [GroupElement::generator] is used as the multiplicative
+//! identity.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::groups::bls12381::Scalar;
+//! # use fastcrypto::polynomial::*;
+//! let points = [Scalar::from(1), Scalar::from(2), Scalar::from(3)];
+//! let values = [Scalar::from(2), Scalar::from(5), Scalar::from(10)];
+//! // The polynomial 1 + X^2.
+//! let polynomial = interpolate(&points, &values).unwrap();
+//! assert_eq!(polynomial, vec![Scalar::from(1), Scalar::from(0), Scalar::from(1)]);
+//! assert_eq!(evaluate(&polynomial, &Scalar::from(4)), Scalar::from(17));
+//! ```
+
+use crate::error::FastCryptoError;
+use crate::groups::{GroupElement, Scalar};
+
+/// The powers `1, x, ..., x^(n-1)`.
+pub fn powers<S: Scalar>(x: &S, n: usize) -> Vec<S> {
+    (0..n)
+        .scan(S::generator(), |power, _| {
+            let current = *power;
+            *power = *power * x;
+            Some(current)
+        })
+        .collect()
+}
+
+/// Compute `base^exponent` by square-and-multiply.
+pub fn pow<S: Scalar>(base: S, exponent: u64) -> S {
+    let mut result = S::generator();
+    for i in (0..u64::BITS - exponent.leading_zeros()).rev() {
+        result = result * result;
+        if (exponent >> i) & 1 == 1 {
+            result = result * base;
+        }
+    }
+    result
+}
+
+/// Evaluate the polynomial with the given coefficients at `x` using Horner's method. The
+/// coefficients may also be group elements, in which case the polynomial is evaluated in the
+/// exponent.
+pub fn evaluate<G: GroupElement>(coefficients: &[G], x: &G::ScalarType) -> G {
+    coefficients
+        .iter()
+        .rev()
+        .fold(G::zero(), |acc, c| acc * x + c)
+}
+
+/// Divide a polynomial by `X - point`, returning the quotient and the remainder, which is the
+/// evaluation of the polynomial at the point.
+pub fn divide_by_linear<S: Scalar>(polynomial: &[S], point: &S) -> (Vec<S>, S) {
+    let mut quotient = vec![S::zero(); polynomial.len().saturating_sub(1)];
+    let mut remainder = S::zero();
+    for (i, c) in polynomial.iter().enumerate().rev() {
+        remainder = remainder * point + c;
+        if i > 0 {
+            quotient[i - 1] = remainder;
+        }
+    }
+    (quotient, remainder)
+}
+
+/// Divide `a` by a monic polynomial `b`, returning the quotient and the remainder.
+pub fn divide_by_monic<S: Scalar>(a: &[S], b: &[S]) -> (Vec<S>, Vec<S>) {
+    let degree = b.len() - 1;
+    if a.len() <= degree {
+        return (vec![], a.to_vec());
+    }
+    let mut remainder = a.to_vec();
+    let mut quotient = vec![S::zero(); a.len() - degree];
+    for i in (0..quotient.len()).rev() {
+        let c = remainder[i + degree];
+        quotient[i] = c;
+        for (j, b_j) in b.iter().enumerate() {
+            remainder[i + j] -= c * b_j;
+        }
+    }
+    remainder.truncate(degree);
+    (quotient, remainder)
+}
+
+/// The polynomial `prod_i (X - points[i])`.
+pub fn vanishing_polynomial<S: Scalar>(points: &[S]) -> Vec<S> {
+    points.iter().fold(vec![S::generator()], |z, x| {
+        // Multiply by (X - x).
+        let mut result = vec![S::zero(); z.len() + 1];
+        for (j, c) in z.iter().enumerate() {
+            result[j + 1] += *c;
+            result[j] -= *c * x;
+        }
+        result
+    })
+}
+
+/// Compute the coefficients of the polynomial of degree less than `points.len()` which takes the
+/// value `values[i]` at `points[i]` for all `i`, as `sum_i values[i] (Z / (X - points[i])) /
+/// Z'(points[i])`, where `Z` is the vanishing polynomial of the points. Fails if the lengths
+/// differ or if the points are not distinct.
+pub fn interpolate<S: Scalar>(points: &[S], values: &[S]) -> Result<Vec<S>, FastCryptoError> {
+    if points.len() != values.len() {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    let z = vanishing_polynomial(points);
+    let quotients = points
+        .iter()
+        .map(|p| divide_by_linear(&z, p).0)
+        .collect::<Vec<_>>();
+    let mut derivatives = points
+        .iter()
+        .zip(&quotients)
+        .map(|(p, q)| evaluate(q, p))
+        .collect::<Vec<_>>();
+    batch_inverse(&mut derivatives)?;
+
+    let mut coefficients = vec![S::zero(); points.len()];
+    for ((quotient, v), d) in quotients.into_iter().zip(values).zip(derivatives) {
+        let scale = *v * d;
+        coefficients
+            .iter_mut()
+            .zip(quotient)
+            .for_each(|(c, q)| *c += q * scale);
+    }
+    Ok(coefficients)
+}
+
+/// Computes the Lagrange coefficients `l_i(x) = prod_{j != i} (x - x_j) / (x_i - x_j)` for the
+/// given points, such that `p(x) = sum_i l_i(x) p(x_i)` for any polynomial `p` of degree less than
+/// `points.len()`. Fails if the points are not distinct.
+///
+/// The sequence of operations depends only on the number of points, and all denominators are
+/// inverted using a single field inversion.
+pub fn lagrange_coefficients<S: Scalar>(x: &S, points: &[S]) -> Result<Vec<S>, FastCryptoError> {
+    let mut numerators = Vec::with_capacity(points.len());
+    let mut denominators = Vec::with_capacity(points.len());
+    for (i, x_i) in points.iter().enumerate() {
+        let (numerator, denominator) = points.iter().enumerate().filter(|(j, _)| *j != i).fold(
+            (S::generator(), S::generator()),
+            |(numerator, denominator), (_, x_j)| {
+                (numerator * (*x - x_j), denominator * (*x_i - x_j))
+            },
+        );
+        numerators.push(numerator);
+        denominators.push(denominator);
+    }
+
+    batch_inverse(&mut denominators)?;
+    Ok(numerators
+        .into_iter()
+        .zip(denominators)
+        .map(|(numerator, inverse)| numerator * inverse)
+        .collect())
+}
+
+/// Replaces each element by its inverse using Montgomery's trick. Fails if any element is zero.
+pub fn batch_inverse<S: Scalar>(values: &mut [S]) -> Result<(), FastCryptoError> {
+    let mut prefix = Vec::with_capacity(values.len());
+    let mut acc = S::generator();
+    for v in values.iter() {
+        prefix.push(acc);
+        acc = acc * v;
+    }
+    let mut inverse = (S::generator() / acc)?;
+    for (v, p) in values.iter_mut().zip(prefix).rev() {
+        let next = inverse * *v;
+        *v = inverse * p;
+        inverse = next;
+    }
+    Ok(())
+}
+
+/// Iterative radix-2 FFT overwriting `values` with `(sum_j values[j] root^(ij))_i`. The length of
+/// `values` must be a power of two and `root` must have that order.
+pub fn fft<S: Scalar>(values: &mut [S], root: S) {
+    let n = values.len();
+    debug_assert!(n.is_power_of_two());
+    bit_reverse_permutation(values);
+    let mut size = 2;
+    while size <= n {
+        let step = pow(root, (n / size) as u64);
+        for start in (0..n).step_by(size) {
+            let mut twiddle = S::generator();
+            for i in start..start + size / 2 {
+                let t = values[i + size / 2] * twiddle;
+                values[i + size / 2] = values[i] - t;
+                values[i] = values[i] + t;
+                twiddle = twiddle * step;
+            }
+        }
+        size *= 2;
+    }
+}
+
+/// The inverse of [fft], overwriting evaluations at the powers of `root` with the coefficients of
+/// the polynomial of degree less than `values.len()` taking these values.
+pub fn inverse_fft<S: Scalar>(values: &mut [S], root: S) -> Result<(), FastCryptoError> {
+    fft(values, (S::generator() / root)?);
+    let scale = (S::generator() / S::from(values.len() as u64))?;
+    values.iter_mut().for_each(|v| *v = *v * scale);
+    Ok(())
+}
+
+/// Reverse the bits of an index below `size`, which must be a power of two.
+pub fn reverse_bits(index: usize, size: usize) -> usize {
+    match size.trailing_zeros() {
+        0 => 0,
+        bits => index.reverse_bits() >> (usize::BITS - bits),
+    }
+}
+
+/// Reorder the values so that the value at index `i` moves to index [reverse_bits] of `i`. The
+/// length of `values` must be a power of two.
+pub fn bit_reverse_permutation<T>(values: &mut [T]) {
+    for i in 0..values.len() {
+        let j = reverse_bits(i, values.len());
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+}
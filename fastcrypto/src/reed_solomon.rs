@@ -0,0 +1,393 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Systematic Reed–Solomon erasure codes whose chunks fit the commitments of this crate, so that
+//! data availability layers can commit to, sample and recover encoded data consistently.
+//!
+//! * [ReedSolomon] encodes bytes over GF(2^8). The data is split into `k` chunks of equal length
+//!   and `n - k` parity chunks are added, such that the data can be recovered from any `k` of the
+//!   `n` chunks. Chunk `i` holds, at each byte position, the evaluation at `i` of the polynomial of
+//!   degree less than `k` through the data bytes at that position. [ReedSolomon::commit] commits to
+//!   the chunks with the Merkle tree of [crate::custody], whose chunk indices are the same.
+//! * [ScalarReedSolomon] encodes `k` scalars of BLS12-381 as the evaluations of the polynomial of
+//!   degree less than `k` through them on a multiplicative subgroup of order `n`, computed with
+//!   FFTs. The evaluations are in bit-reversed order, so the first `k` of them are the data. The
+//!   polynomial is committed to with [crate::kzg], and chunk `i` is opened at
+//!   [ScalarReedSolomon::point] of `i`.
+//!
+//! Decoding trusts its inputs, so chunks should be verified against their commitment first.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::reed_solomon::*;
+//! let code = ReedSolomon::new(3, 5).unwrap();
+//! let data = b"hello world";
+//! let committed = code.commit(data).unwrap();
+//! let commitment = committed.commitment();
+//!
+//! // Recover the data from chunks 1, 3 and 4 after checking them against the commitment.
+//! let mut chunks = vec![None; 5];
+//! for index in [1, 3, 4] {
+//!     let chunk = &committed.chunks()[index];
+//!     let proof = committed.prove(index as u32).unwrap();
+//!     assert!(commitment.verify_chunk(index as u32, chunk, &proof).is_ok());
+//!     chunks[index] = Some(chunk.clone());
+//! }
+//! assert_eq!(code.decode(&chunks, data.len()).unwrap(), data);
+//! ```
+
+use crate::custody::CommittedData;
+use crate::error::FastCryptoError;
+use crate::groups::bls12381::Scalar;
+use crate::groups::GroupElement;
+use crate::kzg::{Commitment, KzgParameters, OpeningProof};
+use crate::polynomial::{
+    bit_reverse_permutation, fft, interpolate, inverse_fft, pow, reverse_bits,
+};
+use crate::serde_helpers::ToFromByteArray;
+
+/// The largest number of chunks of a [ReedSolomon] code, which is the size of GF(2^8).
+pub const MAX_BYTE_CHUNKS: usize = 256;
+
+/// The base 2 logarithm of the largest number of chunks of a [ScalarReedSolomon] code, which is the
+/// 2-adicity of the scalar field of BLS12-381.
+pub const MAX_SCALAR_CHUNKS_LOG: u32 = 32;
+
+/// A primitive root of unity of order 2^32 in the scalar field of BLS12-381, i.e., `7^((r - 1) /
+/// 2^32)` in big-endian.
+const ROOT_OF_UNITY: [u8; 32] = [
+    0x16, 0xa2, 0xa1, 0x9e, 0xdf, 0xe8, 0x1f, 0x20, 0xd0, 0x9b, 0x68, 0x19, 0x22, 0xc8, 0x13, 0xb4,
+    0xb6, 0x36, 0x83, 0x50, 0x8c, 0x22, 0x80, 0xb9, 0x38, 0x29, 0x97, 0x1f, 0x43, 0x9f, 0x0d, 0x2b,
+];
+
+/// Exponentials and logarithms of GF(2^8) with the reduction polynomial `x^8 + x^4 + x^3 + x^2 + 1`
+/// and the generator `x`. The exponentials are repeated so that sums of two logarithms can be
+/// looked up directly.
+const GF_TABLES: ([u8; 512], [u8; 256]) = gf_tables();
+
+/// A systematic Reed–Solomon code over GF(2^8) with `k` data chunks and `n` chunks in total.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReedSolomon {
+    data_chunks: usize,
+    total_chunks: usize,
+    /// The coefficients of the data chunks in each parity chunk.
+    parity_rows: Vec<Vec<u8>>,
+}
+
+impl ReedSolomon {
+    /// A code with the given number of data chunks and chunks in total. Fails unless
+    /// `1 <= data_chunks <= total_chunks <= MAX_BYTE_CHUNKS`.
+    pub fn new(data_chunks: usize, total_chunks: usize) -> Result<Self, FastCryptoError> {
+        if data_chunks == 0 || data_chunks > total_chunks {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        if total_chunks > MAX_BYTE_CHUNKS {
+            return Err(FastCryptoError::InputTooLong(MAX_BYTE_CHUNKS));
+        }
+        let points = (0..data_chunks).map(|i| i as u8).collect::<Vec<_>>();
+        let parity_rows = (data_chunks..total_chunks)
+            .map(|j| lagrange_coefficients(&points, j as u8))
+            .collect();
+        Ok(Self {
+            data_chunks,
+            total_chunks,
+            parity_rows,
+        })
+    }
+
+    pub fn data_chunks(&self) -> usize {
+        self.data_chunks
+    }
+
+    pub fn total_chunks(&self) -> usize {
+        self.total_chunks
+    }
+
+    /// The length of the chunks encoding data of the given length.
+    pub fn chunk_length(&self, data_length: usize) -> usize {
+        (data_length + self.data_chunks - 1) / self.data_chunks
+    }
+
+    /// Encode non-empty data into [Self::total_chunks] chunks of length [Self::chunk_length]. The
+    /// first [Self::data_chunks] chunks are the data, padded with zeros at the end.
+    pub fn encode(&self, data: &[u8]) -> Result<Vec<Vec<u8>>, FastCryptoError> {
+        if data.is_empty() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let length = self.chunk_length(data.len());
+        let mut chunks = (0..self.data_chunks)
+            .map(|i| {
+                let mut chunk = data
+                    .iter()
+                    .skip(i * length)
+                    .take(length)
+                    .copied()
+                    .collect::<Vec<_>>();
+                chunk.resize(length, 0);
+                chunk
+            })
+            .collect::<Vec<_>>();
+        let parity = self
+            .parity_rows
+            .iter()
+            .map(|row| combine(row, &chunks, length))
+            .collect::<Vec<_>>();
+        chunks.extend(parity);
+        Ok(chunks)
+    }
+
+    /// Encode the data as in [Self::encode] and commit to the chunks.
+    pub fn commit(&self, data: &[u8]) -> Result<CommittedData, FastCryptoError> {
+        CommittedData::new(self.encode(data)?)
+    }
+
+    /// Recover data of the given length from the available chunks, given as a vector with
+    /// [Self::total_chunks] entries. Fails if fewer than [Self::data_chunks] chunks are given, or if
+    /// any of them does not have the length of the chunks of the data.
+    pub fn decode(
+        &self,
+        chunks: &[Option<Vec<u8>>],
+        data_length: usize,
+    ) -> Result<Vec<u8>, FastCryptoError> {
+        if chunks.len() != self.total_chunks {
+            return Err(FastCryptoError::InputLengthWrong(self.total_chunks));
+        }
+        let length = self.chunk_length(data_length);
+        if data_length == 0 || chunks.iter().flatten().any(|chunk| chunk.len() != length) {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let (points, available): (Vec<u8>, Vec<Vec<u8>>) = chunks
+            .iter()
+            .enumerate()
+            .filter_map(|(i, chunk)| chunk.clone().map(|chunk| (i as u8, chunk)))
+            .take(self.data_chunks)
+            .unzip();
+        if points.len() < self.data_chunks {
+            return Err(FastCryptoError::InvalidInput);
+        }
+
+        let mut data = Vec::with_capacity(self.data_chunks * length);
+        for (i, chunk) in chunks.iter().take(self.data_chunks).enumerate() {
+            match chunk {
+                Some(chunk) => data.extend_from_slice(chunk),
+                None => data.extend(combine(
+                    &lagrange_coefficients(&points, i as u8),
+                    &available,
+                    length,
+                )),
+            }
+        }
+        data.truncate(data_length);
+        Ok(data)
+    }
+}
+
+/// A systematic Reed–Solomon code over the scalar field of BLS12-381 with `k` data chunks and `n`
+/// chunks in total, where `k` and `n` are powers of two.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScalarReedSolomon {
+    data_chunks: usize,
+    total_chunks: usize,
+    /// Generators of the subgroups of order `k` and `n`.
+    data_root: Scalar,
+    total_root: Scalar,
+}
+
+impl ScalarReedSolomon {
+    /// A code with the given number of data chunks and chunks in total. Fails unless both are
+    /// powers of two with `data_chunks <= total_chunks <= 2^MAX_SCALAR_CHUNKS_LOG`.
+    pub fn new(data_chunks: usize, total_chunks: usize) -> Result<Self, FastCryptoError> {
+        if !data_chunks.is_power_of_two()
+            || !total_chunks.is_power_of_two()
+            || data_chunks > total_chunks
+        {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        if total_chunks.trailing_zeros() > MAX_SCALAR_CHUNKS_LOG {
+            return Err(FastCryptoError::InputTooLong(1 << MAX_SCALAR_CHUNKS_LOG));
+        }
+        Ok(Self {
+            data_chunks,
+            total_chunks,
+            data_root: root_of_unity(data_chunks),
+            total_root: root_of_unity(total_chunks),
+        })
+    }
+
+    pub fn data_chunks(&self) -> usize {
+        self.data_chunks
+    }
+
+    pub fn total_chunks(&self) -> usize {
+        self.total_chunks
+    }
+
+    /// The point at which the polynomial of the data is evaluated for the chunk with the given
+    /// index, which is `ω^rev(index)` for the generator `ω` of the subgroup of order `n` and the
+    /// bit reversal `rev` of indices below `n`.
+    pub fn point(&self, index: usize) -> Result<Scalar, FastCryptoError> {
+        if index >= self.total_chunks {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(pow(
+            self.total_root,
+            reverse_bits(index, self.total_chunks) as u64,
+        ))
+    }
+
+    /// The coefficients of the polynomial of degree less than `k` whose evaluations at the points
+    /// of the first `k` chunks are the given data.
+    pub fn interpolate(&self, data: &[Scalar]) -> Result<Vec<Scalar>, FastCryptoError> {
+        if data.len() != self.data_chunks {
+            return Err(FastCryptoError::InputLengthWrong(self.data_chunks));
+        }
+        let mut coefficients = data.to_vec();
+        bit_reverse_permutation(&mut coefficients);
+        inverse_fft(&mut coefficients, self.data_root)?;
+        Ok(coefficients)
+    }
+
+    /// Encode `k` scalars into `n` chunks, the first `k` of which are the data.
+    pub fn encode(&self, data: &[Scalar]) -> Result<Vec<Scalar>, FastCryptoError> {
+        let mut evaluations = self.interpolate(data)?;
+        evaluations.resize(self.total_chunks, Scalar::zero());
+        fft(&mut evaluations, self.total_root);
+        bit_reverse_permutation(&mut evaluations);
+        Ok(evaluations)
+    }
+
+    /// Recover the data from the available chunks, given as a vector with [Self::total_chunks]
+    /// entries. Fails if fewer than [Self::data_chunks] chunks are given. Missing chunks of the
+    /// data are recovered by Lagrange interpolation in `O(k^2)`.
+    pub fn decode(&self, chunks: &[Option<Scalar>]) -> Result<Vec<Scalar>, FastCryptoError> {
+        if chunks.len() != self.total_chunks {
+            return Err(FastCryptoError::InputLengthWrong(self.total_chunks));
+        }
+        if let Some(data) = chunks[..self.data_chunks]
+            .iter()
+            .copied()
+            .collect::<Option<Vec<_>>>()
+        {
+            return Ok(data);
+        }
+        let (points, values): (Vec<Scalar>, Vec<Scalar>) = chunks
+            .iter()
+            .enumerate()
+            .filter_map(|(i, chunk)| chunk.map(|value| (i, value)))
+            .take(self.data_chunks)
+            .map(|(i, value)| Ok((self.point(i)?, value)))
+            .collect::<Result<Vec<_>, FastCryptoError>>()?
+            .into_iter()
+            .unzip();
+        if points.len() < self.data_chunks {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let mut data = interpolate(&points, &values)?;
+        fft(&mut data, self.data_root);
+        bit_reverse_permutation(&mut data);
+        Ok(data)
+    }
+
+    /// Commit to the polynomial of the data, see [Self::interpolate].
+    pub fn commit(
+        &self,
+        parameters: &KzgParameters,
+        data: &[Scalar],
+    ) -> Result<Commitment, FastCryptoError> {
+        parameters.commit(&self.interpolate(data)?)
+    }
+
+    /// Open the commitment to the polynomial of the data at the chunk with the given index, and
+    /// return the chunk and its proof.
+    pub fn open(
+        &self,
+        parameters: &KzgParameters,
+        data: &[Scalar],
+        index: usize,
+    ) -> Result<(Scalar, OpeningProof), FastCryptoError> {
+        parameters.open(&self.interpolate(data)?, &self.point(index)?)
+    }
+
+    /// Verify that `chunk` is at the given index of the encoding of the committed data.
+    pub fn verify_chunk(
+        &self,
+        parameters: &KzgParameters,
+        commitment: &Commitment,
+        index: usize,
+        chunk: &Scalar,
+        proof: &OpeningProof,
+    ) -> Result<(), FastCryptoError> {
+        parameters.verify(commitment, &self.point(index)?, chunk, proof)
+    }
+}
+
+const fn gf_tables() -> ([u8; 512], [u8; 256]) {
+    let mut exp = [0u8; 512];
+    let mut log = [0u8; 256];
+    let mut x = 1u16;
+    let mut i = 0;
+    while i < 255 {
+        exp[i] = x as u8;
+        exp[i + 255] = x as u8;
+        log[x as usize] = i as u8;
+        x <<= 1;
+        if x & 0x100 != 0 {
+            x ^= 0x11d;
+        }
+        i += 1;
+    }
+    (exp, log)
+}
+
+fn gf_mul(a: u8, b: u8) -> u8 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    let (exp, log) = &GF_TABLES;
+    exp[log[a as usize] as usize + log[b as usize] as usize]
+}
+
+fn gf_inverse(a: u8) -> u8 {
+    let (exp, log) = &GF_TABLES;
+    exp[255 - log[a as usize] as usize]
+}
+
+/// The coefficients `l_i(x)` of the Lagrange basis polynomials for the given distinct points.
+fn lagrange_coefficients(points: &[u8], x: u8) -> Vec<u8> {
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let (numerator, denominator) = points
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .fold((1, 1), |(n, d), (_, q)| {
+                    (gf_mul(n, x ^ q), gf_mul(d, p ^ q))
+                });
+            gf_mul(numerator, gf_inverse(denominator))
+        })
+        .collect()
+}
+
+/// The linear combination of chunks of the given length with the given coefficients.
+fn combine(coefficients: &[u8], chunks: &[Vec<u8>], length: usize) -> Vec<u8> {
+    let mut result = vec![0u8; length];
+    for (c, chunk) in coefficients.iter().zip(chunks) {
+        result
+            .iter_mut()
+            .zip(chunk)
+            .for_each(|(r, b)| *r ^= gf_mul(*c, *b));
+    }
+    result
+}
+
+/// A generator of the subgroup of the given order, which must be a power of two of at most
+/// `2^MAX_SCALAR_CHUNKS_LOG`.
+fn root_of_unity(order: usize) -> Scalar {
+    let mut root = Scalar::from_byte_array(&ROOT_OF_UNITY).expect("Valid constant");
+    for _ in order.trailing_zeros()..MAX_SCALAR_CHUNKS_LOG {
+        root = root * root;
+    }
+    root
+}
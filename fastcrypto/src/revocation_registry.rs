@@ -0,0 +1,232 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A registry of public keys which can be revoked, for credential systems where a verifier must
+//! check that the key of a holder has not been revoked without downloading a revocation list.
+//!
+//! The registry manager accumulates all registered keys which have not been revoked in an
+//! [RsaAccumulator] and publishes a [RegistryState], i.e. the value of the accumulator and an
+//! epoch which is incremented on every change. A key holder proves that their key is registered
+//! and not revoked with a [NotRevokedProof], which is a membership witness for the key. After every
+//! change, the manager publishes a [RegistryUpdate] which holders apply to their proofs in order
+//! to keep them valid without knowing the other keys. Revoked keys cannot be registered again.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::revocation_registry::*;
+//! # use fastcrypto::rsa_accumulator::AccumulatorParameters;
+//! # use fastcrypto::ed25519::{Ed25519KeyPair, Ed25519PublicKey};
+//! # use fastcrypto::traits::KeyPair;
+//! # use rand::thread_rng;
+//! let parameters = AccumulatorParameters::generate(1024, &mut thread_rng()).unwrap();
+//! let mut registry = RevocationRegistry::<Ed25519PublicKey>::new(&parameters);
+//! let alice = Ed25519KeyPair::generate(&mut thread_rng()).public().clone();
+//! let bob = Ed25519KeyPair::generate(&mut thread_rng()).public().clone();
+//!
+//! let (proofs, _) = registry.register(&[alice.clone(), bob.clone()]).unwrap();
+//! let mut alice_proof = proofs[0].clone();
+//! assert!(alice_proof.verify(&parameters, &registry.state(), &alice).is_ok());
+//!
+//! // Alice updates her proof after Bob's key is revoked.
+//! let update = registry.revoke(&[bob]).unwrap();
+//! alice_proof.update(&parameters, &alice, &update).unwrap();
+//! assert!(alice_proof.verify(&parameters, &registry.state(), &alice).is_ok());
+//! ```
+
+use crate::error::FastCryptoError;
+use crate::rsa_accumulator::{AccumulatorParameters, MembershipWitness, RsaAccumulator};
+use crate::traits::VerifyingKey;
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::marker::PhantomData;
+
+/// The public state of a registry.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryState {
+    /// The number of changes made to the registry.
+    pub epoch: u64,
+    /// The value of the accumulator of all registered keys which are not revoked.
+    pub value: BigUint,
+}
+
+/// A change of a registry, which holders apply with [NotRevokedProof::update].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RegistryUpdate {
+    /// Keys were registered.
+    Registered { epoch: u64, keys: Vec<Vec<u8>> },
+    /// Keys were revoked and the accumulator now has the value `value`.
+    Revoked {
+        epoch: u64,
+        keys: Vec<Vec<u8>>,
+        value: BigUint,
+    },
+}
+
+impl RegistryUpdate {
+    /// The epoch of the registry after this update.
+    pub fn epoch(&self) -> u64 {
+        match self {
+            RegistryUpdate::Registered { epoch, .. } => *epoch,
+            RegistryUpdate::Revoked { epoch, .. } => *epoch,
+        }
+    }
+}
+
+/// A proof that a key is registered and not revoked in a given epoch.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotRevokedProof {
+    epoch: u64,
+    witness: MembershipWitness,
+}
+
+/// The state of the manager of a registry of keys of type `K`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct RevocationRegistry<K: VerifyingKey> {
+    accumulator: RsaAccumulator,
+    epoch: u64,
+    revoked: BTreeSet<Vec<u8>>,
+    _key: PhantomData<K>,
+}
+
+impl<K: VerifyingKey> RevocationRegistry<K> {
+    /// Create an empty registry.
+    pub fn new(parameters: &AccumulatorParameters) -> Self {
+        Self {
+            accumulator: RsaAccumulator::new(parameters),
+            epoch: 0,
+            revoked: BTreeSet::new(),
+            _key: PhantomData,
+        }
+    }
+
+    /// The current public state.
+    pub fn state(&self) -> RegistryState {
+        RegistryState {
+            epoch: self.epoch,
+            value: self.accumulator.value().clone(),
+        }
+    }
+
+    /// Whether `key` is registered and not revoked.
+    pub fn is_valid(&self, key: &K) -> bool {
+        self.accumulator.contains(key.as_bytes())
+    }
+
+    /// Whether `key` has been revoked.
+    pub fn is_revoked(&self, key: &K) -> bool {
+        self.revoked.contains(key.as_bytes())
+    }
+
+    /// Register a batch of new keys, returning a proof for each of them in the new epoch and the
+    /// update for the holders of existing proofs. Fails if a key is already registered, has been
+    /// revoked or appears twice.
+    pub fn register(
+        &mut self,
+        keys: &[K],
+    ) -> Result<(Vec<NotRevokedProof>, RegistryUpdate), FastCryptoError> {
+        if keys.iter().any(|k| self.is_revoked(k)) {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let elements = keys.iter().map(|k| k.as_bytes()).collect::<Vec<_>>();
+        let witnesses = self.accumulator.add(&elements)?;
+        self.epoch += 1;
+        let proofs = witnesses
+            .into_iter()
+            .map(|witness| NotRevokedProof {
+                epoch: self.epoch,
+                witness,
+            })
+            .collect();
+        let update = RegistryUpdate::Registered {
+            epoch: self.epoch,
+            keys: byte_vectors(&elements),
+        };
+        Ok((proofs, update))
+    }
+
+    /// Revoke a batch of registered keys, returning the update for the holders of the remaining
+    /// proofs. Fails if a key is not registered or appears twice.
+    pub fn revoke(&mut self, keys: &[K]) -> Result<RegistryUpdate, FastCryptoError> {
+        let elements = keys.iter().map(|k| k.as_bytes()).collect::<Vec<_>>();
+        self.accumulator.delete(&elements)?;
+        self.epoch += 1;
+        self.revoked
+            .extend(elements.iter().map(|element| element.to_vec()));
+        Ok(RegistryUpdate::Revoked {
+            epoch: self.epoch,
+            keys: byte_vectors(&elements),
+            value: self.accumulator.value().clone(),
+        })
+    }
+
+    /// Compute a fresh proof for a registered key which has not been revoked, e.g. for a holder who
+    /// missed updates.
+    pub fn prove(&self, key: &K) -> Result<NotRevokedProof, FastCryptoError> {
+        Ok(NotRevokedProof {
+            epoch: self.epoch,
+            witness: self.accumulator.membership_witness(key.as_bytes())?,
+        })
+    }
+}
+
+impl NotRevokedProof {
+    /// The epoch in which this proof is valid.
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// Verify that `key` is registered and not revoked in the given state.
+    pub fn verify<K: VerifyingKey>(
+        &self,
+        parameters: &AccumulatorParameters,
+        state: &RegistryState,
+        key: &K,
+    ) -> Result<(), FastCryptoError> {
+        if self.epoch != state.epoch {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        self.witness
+            .verify(parameters, &state.value, key.as_bytes())
+    }
+
+    /// Apply the update of the next epoch to the proof for `key`. Fails if the update is not for the
+    /// next epoch or if it revokes `key`.
+    pub fn update<K: VerifyingKey>(
+        &mut self,
+        parameters: &AccumulatorParameters,
+        key: &K,
+        update: &RegistryUpdate,
+    ) -> Result<(), FastCryptoError> {
+        if update.epoch() != self.epoch + 1 {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        match update {
+            RegistryUpdate::Registered { keys, .. } => {
+                self.witness.update_on_add(parameters, &byte_slices(keys));
+            }
+            RegistryUpdate::Revoked { keys, value, .. } => {
+                if keys.iter().any(|k| k.as_slice() == key.as_bytes()) {
+                    return Err(FastCryptoError::InvalidInput);
+                }
+                self.witness.update_on_delete(
+                    parameters,
+                    key.as_bytes(),
+                    &byte_slices(keys),
+                    value,
+                )?;
+            }
+        }
+        self.epoch += 1;
+        Ok(())
+    }
+}
+
+fn byte_vectors(elements: &[&[u8]]) -> Vec<Vec<u8>> {
+    elements.iter().map(|e| e.to_vec()).collect()
+}
+
+fn byte_slices(elements: &[Vec<u8>]) -> Vec<&[u8]> {
+    elements.iter().map(|e| e.as_slice()).collect()
+}
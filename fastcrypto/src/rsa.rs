@@ -5,10 +5,11 @@
 
 use crate::error::{FastCryptoError, FastCryptoResult};
 use crate::hash::{HashFunction, Sha256};
+use crate::policy::{self, factoring_security_bits, Algorithm};
 use rsa::pkcs1::DecodeRsaPublicKey;
 use rsa::pkcs1v15::Signature as ExternalSignature;
 use rsa::{BigUint, RsaPublicKey as ExternalPublicKey};
-use rsa::{Pkcs1v15Sign, PublicKey};
+use rsa::{Pkcs1v15Sign, PublicKey, PublicKeyParts};
 
 #[derive(Clone)]
 pub struct RSAPublicKey(pub ExternalPublicKey);
@@ -18,22 +19,35 @@ pub struct RSASignature(pub ExternalSignature);
 
 impl RSAPublicKey {
     /// Parse an `RSAPublicKey` from an ASN.1 DER (Distinguished Encoding Rules) PKCS #1 encoding.
+    /// Fails if the key is forbidden by the installed [policy](crate::policy).
     pub fn from_der(der: &[u8]) -> FastCryptoResult<Self> {
-        Ok(RSAPublicKey(
+        let key = RSAPublicKey(
             rsa::RsaPublicKey::from_pkcs1_der(der).map_err(|_| FastCryptoError::InvalidInput)?,
-        ))
+        );
+        key.check_policy()?;
+        Ok(key)
     }
 
     /// Parse an `RSAPublicKey` from its components, eg. the modulus (n) and the exponent (e) from a binary big-endian representation.
     pub fn from_raw_components(modulus: &[u8], exponent: &[u8]) -> FastCryptoResult<Self> {
         // The Base64 encodings in a JSON Web Key is big-endian encoded (see RFC 7517 and 7518), so we expect the same here.
-        Ok(RSAPublicKey(
+        let key = RSAPublicKey(
             rsa::RsaPublicKey::new(
                 BigUint::from_bytes_be(modulus),
                 BigUint::from_bytes_be(exponent),
             )
             .map_err(|_| FastCryptoError::InvalidInput)?,
-        ))
+        );
+        key.check_policy()?;
+        Ok(key)
+    }
+
+    /// Check this key against the installed [policy](crate::policy).
+    fn check_policy(&self) -> FastCryptoResult<()> {
+        policy::enforce(
+            Algorithm::RsaSignature,
+            factoring_security_bits(self.0.n().bits() as u64),
+        )
     }
 
     /// Verify a signed message. The verification uses SHA-256 for hashing.
@@ -43,6 +57,7 @@ impl RSAPublicKey {
 
     /// Verify a signed message. The message, `hashed`, must be the output of a cryptographic hash function.
     pub fn verify_prehash(&self, hashed: &[u8], signature: &RSASignature) -> FastCryptoResult<()> {
+        self.check_policy()?;
         self.0
             .verify(
                 Pkcs1v15Sign::new::<sha2::Sha256>(),
@@ -0,0 +1,346 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! An RSA accumulator, a constant-size commitment `A = g^(x_1 * ... * x_n) mod N` to a dynamic
+//! set of elements, where each element is first mapped to a 256-bit prime `x_i`.
+//!
+//! Compared to a Merkle tree, both membership and non-membership witnesses have constant size,
+//! and the membership of many elements can be proven with a single group element. The
+//! constructions follow [Boneh, Bünz and Fisch](https://eprint.iacr.org/2018/1188.pdf):
+//! * A membership witness for `x` is `w = A^(1/x)`, verified by checking `w^x = A`.
+//! * A non-membership witness for `x` is a pair `(a, d)` with `A^a * d^x = g`, which exists when
+//!   `x` is coprime to the product of the members.
+//! * Witnesses of existing members can be updated by their holders after a batch of additions or
+//!   deletions without knowing the rest of the set.
+//!
+//! The accumulator is maintained by a manager who keeps the product of all members. The modulus
+//! must be generated such that nobody knows its factorization, e.g., using the RSA-2048 challenge
+//! number, or by an honest party who erases the factors after [AccumulatorParameters::generate].
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::rsa_accumulator::*;
+//! # use rand::thread_rng;
+//! let parameters = AccumulatorParameters::generate(1024, &mut thread_rng()).unwrap();
+//! let mut accumulator = RsaAccumulator::new(&parameters);
+//! let witnesses = accumulator.add(&[b"alice", b"bob"]).unwrap();
+//! assert!(witnesses[0].verify(&parameters, accumulator.value(), b"alice").is_ok());
+//!
+//! let non_membership = accumulator.non_membership_witness(b"carol").unwrap();
+//! assert!(non_membership.verify(&parameters, accumulator.value(), b"carol").is_ok());
+//! ```
+
+use crate::error::FastCryptoError;
+use crate::hash::{HashFunction, Sha256};
+use crate::number_theory::{
+    gcd, generate_prime, is_probable_prime, mod_inverse, random_unit, xgcd,
+};
+use crate::policy::{self, factoring_security_bits, Algorithm};
+use crate::traits::AllowedRng;
+use num_bigint::{BigInt, BigUint, Sign};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+/// The smallest supported bit length of the modulus.
+pub const MIN_MODULUS_BITS: u64 = 1024;
+
+const HASH_TO_PRIME_DST: &[u8] = b"fastcrypto-rsa-accumulator-hash-to-prime-v1";
+
+/// The public parameters of an accumulator: an RSA modulus `N` of unknown factorization and a
+/// generator `g` of the group of quadratic residues modulo `N`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccumulatorParameters {
+    n: BigUint,
+    g: BigUint,
+}
+
+/// The state of the manager of an accumulator.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RsaAccumulator {
+    parameters: AccumulatorParameters,
+    value: BigUint,
+    product: BigUint,
+}
+
+/// A witness that an element, or a set of elements if the witness was aggregated, is a member
+/// of an accumulator.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MembershipWitness(BigUint);
+
+/// A witness that an element is not a member of an accumulator.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NonMembershipWitness {
+    a: BigUint,
+    d: BigUint,
+}
+
+/// Map an element to a 256-bit prime by hashing it together with a counter until the result is
+/// prime. The primality test is seeded by the candidate, so the mapping is deterministic.
+fn hash_to_prime(element: &[u8]) -> BigUint {
+    for counter in 0u64.. {
+        let mut hash = Sha256::default();
+        hash.update(HASH_TO_PRIME_DST);
+        hash.update((element.len() as u64).to_be_bytes());
+        hash.update(element);
+        hash.update(counter.to_be_bytes());
+        let digest = hash.finalize().digest;
+
+        let mut candidate = BigUint::from_bytes_be(&digest);
+        candidate.set_bit(255, true);
+        candidate.set_bit(0, true);
+        if is_probable_prime(&candidate, &mut StdRng::from_seed(digest)) {
+            return candidate;
+        }
+    }
+    unreachable!("There are infinitely many primes")
+}
+
+fn product_of_primes(elements: &[&[u8]]) -> BigUint {
+    elements
+        .iter()
+        .map(|e| hash_to_prime(e))
+        .fold(BigUint::from(1u8), |acc, x| acc * x)
+}
+
+impl AccumulatorParameters {
+    /// Generate a new modulus of `bits` bits together with a random generator. The factorization
+    /// of the modulus is erased, but the caller must trust that this function was run honestly.
+    pub fn generate<R: AllowedRng>(bits: u64, rng: &mut R) -> Result<Self, FastCryptoError> {
+        if bits < MIN_MODULUS_BITS || bits % 2 != 0 {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let p = generate_prime(bits / 2, rng);
+        let q = loop {
+            let q = generate_prime(bits / 2, rng);
+            if q != p {
+                break q;
+            }
+        };
+        let n = p * q;
+        let g = random_unit(&n, rng).modpow(&BigUint::from(2u8), &n);
+        Self::new(n, g)
+    }
+
+    /// Create parameters from a modulus `N` of unknown factorization and a generator `g`. Fails if
+    /// the size of the modulus is forbidden by the installed [policy](crate::policy).
+    pub fn new(n: BigUint, g: BigUint) -> Result<Self, FastCryptoError> {
+        let one = BigUint::from(1u8);
+        if n.bits() < MIN_MODULUS_BITS || !n.bit(0) || g <= one || g >= n || gcd(&g, &n) != one {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        policy::enforce(Algorithm::RsaAccumulator, factoring_security_bits(n.bits()))?;
+        Ok(Self { n, g })
+    }
+
+    /// Compute `base^exponent mod N` for a possibly negative exponent.
+    fn pow(&self, base: &BigUint, exponent: &BigInt) -> Result<BigUint, FastCryptoError> {
+        let magnitude = exponent.magnitude();
+        match exponent.sign() {
+            Sign::Minus => mod_inverse(base, &self.n)
+                .map(|inverse| inverse.modpow(magnitude, &self.n))
+                .ok_or(FastCryptoError::InvalidInput),
+            _ => Ok(base.modpow(magnitude, &self.n)),
+        }
+    }
+
+    /// Given `w1^x1 = A` and `w2^x2 = A` with coprime `x1` and `x2`, compute `w` with
+    /// `w^(x1 * x2) = A` (Shamir's trick).
+    fn shamir_trick(
+        &self,
+        w1: &BigUint,
+        x1: &BigUint,
+        w2: &BigUint,
+        x2: &BigUint,
+    ) -> Result<BigUint, FastCryptoError> {
+        let (d, a, b) = xgcd(&BigInt::from(x1.clone()), &BigInt::from(x2.clone()));
+        if d != BigInt::from(1u8) {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        // w = w1^b * w2^a since w^(x1 * x2) = A^(b * x2) * A^(a * x1) = A.
+        Ok(self.pow(w1, &b)? * self.pow(w2, &a)? % &self.n)
+    }
+}
+
+impl RsaAccumulator {
+    /// Create an empty accumulator.
+    pub fn new(parameters: &AccumulatorParameters) -> Self {
+        Self {
+            parameters: parameters.clone(),
+            value: parameters.g.clone(),
+            product: BigUint::from(1u8),
+        }
+    }
+
+    /// The current value of the accumulator.
+    pub fn value(&self) -> &BigUint {
+        &self.value
+    }
+
+    /// Check whether an element is a member of the accumulator.
+    pub fn contains(&self, element: &[u8]) -> bool {
+        &self.product % hash_to_prime(element) == BigUint::from(0u8)
+    }
+
+    /// Add a batch of new elements to the accumulator, returning a membership witness for each
+    /// of them with respect to the new value. Witnesses of existing members must be updated using
+    /// [MembershipWitness::update_on_add].
+    pub fn add(&mut self, elements: &[&[u8]]) -> Result<Vec<MembershipWitness>, FastCryptoError> {
+        let primes = elements
+            .iter()
+            .map(|e| hash_to_prime(e))
+            .collect::<Vec<_>>();
+        let mut product = BigUint::from(1u8);
+        for x in &primes {
+            if &self.product % x == BigUint::from(0u8) || &product % x == BigUint::from(0u8) {
+                return Err(FastCryptoError::InvalidInput);
+            }
+            product *= x;
+        }
+
+        let witnesses = primes
+            .iter()
+            .map(|x| MembershipWitness(self.value.modpow(&(&product / x), &self.parameters.n)))
+            .collect();
+        self.value = self.value.modpow(&product, &self.parameters.n);
+        self.product *= product;
+        Ok(witnesses)
+    }
+
+    /// Delete a batch of members from the accumulator. Witnesses of the remaining members must be
+    /// updated using [MembershipWitness::update_on_delete].
+    pub fn delete(&mut self, elements: &[&[u8]]) -> Result<(), FastCryptoError> {
+        let mut product = self.product.clone();
+        for x in elements.iter().map(|e| hash_to_prime(e)) {
+            if &product % &x != BigUint::from(0u8) {
+                return Err(FastCryptoError::InvalidInput);
+            }
+            product /= x;
+        }
+        self.value = self.parameters.g.modpow(&product, &self.parameters.n);
+        self.product = product;
+        Ok(())
+    }
+
+    /// Compute a membership witness for a member.
+    pub fn membership_witness(&self, element: &[u8]) -> Result<MembershipWitness, FastCryptoError> {
+        let x = hash_to_prime(element);
+        if &self.product % &x != BigUint::from(0u8) {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(MembershipWitness(
+            self.parameters
+                .g
+                .modpow(&(&self.product / x), &self.parameters.n),
+        ))
+    }
+
+    /// Compute a non-membership witness for an element which is not a member.
+    pub fn non_membership_witness(
+        &self,
+        element: &[u8],
+    ) -> Result<NonMembershipWitness, FastCryptoError> {
+        let x = BigInt::from(hash_to_prime(element));
+        let (d, a, _) = xgcd(&BigInt::from(self.product.clone()), &x);
+        if d != BigInt::from(1u8) {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        // Choose a in [0, x) and b = (1 - a * u) / x such that a * u + b * x = 1.
+        let a = ((a % &x) + &x) % &x;
+        let b = (BigInt::from(1u8) - &a * BigInt::from(self.product.clone())) / &x;
+        Ok(NonMembershipWitness {
+            a: a.to_biguint().expect("a is non-negative"),
+            d: self.parameters.pow(&self.parameters.g, &b)?,
+        })
+    }
+}
+
+impl MembershipWitness {
+    /// Verify that `element` is a member of the accumulator with the given value.
+    pub fn verify(
+        &self,
+        parameters: &AccumulatorParameters,
+        value: &BigUint,
+        element: &[u8],
+    ) -> Result<(), FastCryptoError> {
+        self.verify_batch(parameters, value, &[element])
+    }
+
+    /// Verify that all `elements` are members of the accumulator with the given value using a
+    /// witness computed with [MembershipWitness::aggregate].
+    pub fn verify_batch(
+        &self,
+        parameters: &AccumulatorParameters,
+        value: &BigUint,
+        elements: &[&[u8]],
+    ) -> Result<(), FastCryptoError> {
+        if self.0 >= parameters.n
+            || self.0.modpow(&product_of_primes(elements), &parameters.n) != *value
+        {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        Ok(())
+    }
+
+    /// Aggregate membership witnesses for distinct elements into a single witness for all of
+    /// them.
+    pub fn aggregate(
+        parameters: &AccumulatorParameters,
+        witnesses: &[(&[u8], MembershipWitness)],
+    ) -> Result<Self, FastCryptoError> {
+        let ((first_element, first), rest) = witnesses
+            .split_first()
+            .ok_or(FastCryptoError::InvalidInput)?;
+        let mut product = hash_to_prime(first_element);
+        let mut witness = first.0.clone();
+        for (element, w) in rest {
+            let x = hash_to_prime(element);
+            witness = parameters.shamir_trick(&witness, &product, &w.0, &x)?;
+            product *= x;
+        }
+        Ok(Self(witness))
+    }
+
+    /// Update the witness of `element` after `added` were added to the accumulator.
+    pub fn update_on_add(&mut self, parameters: &AccumulatorParameters, added: &[&[u8]]) {
+        self.0 = self.0.modpow(&product_of_primes(added), &parameters.n);
+    }
+
+    /// Update the witness of `element` after `deleted` were deleted from the accumulator, which
+    /// now has the value `new_value`.
+    pub fn update_on_delete(
+        &mut self,
+        parameters: &AccumulatorParameters,
+        element: &[u8],
+        deleted: &[&[u8]],
+        new_value: &BigUint,
+    ) -> Result<(), FastCryptoError> {
+        // We have w^x = A and A'^y = A, so Shamir's trick gives w' with w'^(x * y) = A and hence
+        // w'^x = A'.
+        let x = hash_to_prime(element);
+        let y = product_of_primes(deleted);
+        self.0 = parameters.shamir_trick(&self.0, &x, new_value, &y)?;
+        Ok(())
+    }
+}
+
+impl NonMembershipWitness {
+    /// Verify that `element` is not a member of the accumulator with the given value.
+    pub fn verify(
+        &self,
+        parameters: &AccumulatorParameters,
+        value: &BigUint,
+        element: &[u8],
+    ) -> Result<(), FastCryptoError> {
+        let x = hash_to_prime(element);
+        if self.a >= x || self.d >= parameters.n {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        let lhs =
+            value.modpow(&self.a, &parameters.n) * self.d.modpow(&x, &parameters.n) % &parameters.n;
+        if lhs != parameters.g {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        Ok(())
+    }
+}
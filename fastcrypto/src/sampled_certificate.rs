@@ -0,0 +1,335 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Certificates signed by a committee sampled with VRF sortition, for consensus protocols where
+//! only a random subset of the validators votes on each message.
+//!
+//! Each member of a [SampledCommittee] evaluates its VRF on the input [sortition_input] of a seed,
+//! e.g. a round number together with the output of a randomness beacon, and gets a number of seats
+//! as in Algorand: Each unit of stake is a seat with probability `p = expected_size / total_stake`,
+//! so a member with stake `w` gets `j` seats with the binomial probability
+//! `B(j; w, p) = C(w, j) p^j (1 - p)^(w - j)`. With the VRF output `y` and
+//! `x = SortitionRng::new(SAMPLING_DOMAIN, y).next_u64()`, the member gets the `j` seats for which
+//! `B(0; w, p) + ... + B(j - 1; w, p) <= x / 2^64 < B(0; w, p) + ... + B(j; w, p)`. The binomial
+//! probabilities are computed with a 64 bit mantissa in integer arithmetic, so all platforms agree
+//! on the number of seats.
+//!
+//! A [SampledCertificate] is an aggregate signature by members with seats together with their VRF
+//! proofs. It is valid if all proofs are valid, all signers have seats and the signers have at
+//! least `threshold` seats in total, instead of a quorum of the stake.
+//!
+//! Because each unit of stake is a seat independently of how the stake is split between members,
+//! the number of seats of members with a fraction `f` of the stake has the binomial distribution
+//! `B(f * total_stake, p)` with mean `f * expected_size`, regardless of the number of members. By
+//! the Chernoff bound, it exceeds `(1 + δ) f * expected_size` with probability at most
+//! `exp(-δ^2 f * expected_size / (2 + δ))`. The threshold must be chosen so that an adversary with
+//! less than a given fraction of the stake is unlikely to get `threshold` seats while honest
+//! members are likely to reach it. E.g., Algorand uses an expected size of 2000 and a threshold of
+//! 1370 seats to tolerate an adversary with less than a fifth of the stake.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::sampled_certificate::*;
+//! # use fastcrypto::bls12381::min_sig::{BLS12381AggregateSignature, BLS12381KeyPair};
+//! # use fastcrypto::traits::{KeyPair, Signer};
+//! # use fastcrypto::vrf::ecvrf::{ECVRFKeyPair, ECVRFProof};
+//! # use fastcrypto::vrf::VRFKeyPair;
+//! # use rand::thread_rng;
+//! let keys = (0..10).map(|_| BLS12381KeyPair::generate(&mut thread_rng())).collect::<Vec<_>>();
+//! let vrf_keys = (0..10).map(|_| ECVRFKeyPair::generate(&mut thread_rng())).collect::<Vec<_>>();
+//! let committee = SampledCommittee::<BLS12381AggregateSignature, ECVRFProof>::new(
+//!     keys.iter().map(|kp| kp.public().clone()).collect(),
+//!     vrf_keys.iter().map(|kp| kp.pk.clone()).collect(),
+//!     vec![1; 10],
+//!     5,
+//!     1,
+//! )
+//! .unwrap();
+//!
+//! // Every member with seats signs.
+//! let input = sortition_input(b"round 1");
+//! let mut votes = Vec::new();
+//! for i in 0..10 {
+//!     let (output, proof) = vrf_keys[i].output(&input);
+//!     if committee.seats(i, &output).unwrap() > 0 {
+//!         votes.push((i, keys[i].sign(b"block"), proof));
+//!     }
+//! }
+//! # if votes.is_empty() { return; }
+//!
+//! let certificate = SampledCertificate::from_votes(&committee, votes).unwrap();
+//! assert!(certificate.verify(&committee, b"round 1", b"block").is_ok());
+//! ```
+
+use crate::certificate_codec::{Certificate, SignerSet};
+use crate::error::FastCryptoError;
+use crate::sortition::SortitionRng;
+use crate::traits::{AggregateAuthenticator, Authenticator, ToFromBytes};
+use crate::vrf::VRFProof;
+
+/// The domain of the VRF inputs, see [sortition_input].
+pub const SORTITION_DOMAIN: &[u8] = b"fastcrypto-sampled-committee-v1";
+
+/// The domain of the [SortitionRng] which maps VRF outputs to seats.
+pub const SAMPLING_DOMAIN: &[u8] = b"fastcrypto-sampled-committee-selection-v1";
+
+/// The VRF input of the members for a seed: `len(SORTITION_DOMAIN) || SORTITION_DOMAIN || seed`,
+/// where the length is encoded as an 8 byte big-endian integer.
+pub fn sortition_input(seed: &[u8]) -> Vec<u8> {
+    let mut input = (SORTITION_DOMAIN.len() as u64).to_be_bytes().to_vec();
+    input.extend_from_slice(SORTITION_DOMAIN);
+    input.extend_from_slice(seed);
+    input
+}
+
+/// A committee whose members get a number of seats which is binomially distributed in their stake.
+pub struct SampledCommittee<A: AggregateAuthenticator, P: VRFProof<64>> {
+    public_keys: Vec<<A::Sig as Authenticator>::PubKey>,
+    vrf_public_keys: Vec<P::PublicKey>,
+    stakes: Vec<u64>,
+    total_stake: u64,
+    expected_size: u64,
+    threshold: u64,
+}
+
+impl<A: AggregateAuthenticator + ToFromBytes, P: VRFProof<64>> SampledCommittee<A, P> {
+    /// Create a committee where member `i` has the given keys and stake. On average, there are
+    /// `expected_size` seats, and certificates need signers with `threshold` seats. Fails if the
+    /// lengths of the lists differ, if all stakes are zero or their sum overflows, if the threshold
+    /// or expected size is zero, or if the expected size is larger than the total stake.
+    pub fn new(
+        public_keys: Vec<<A::Sig as Authenticator>::PubKey>,
+        vrf_public_keys: Vec<P::PublicKey>,
+        stakes: Vec<u64>,
+        expected_size: u64,
+        threshold: u64,
+    ) -> Result<Self, FastCryptoError> {
+        let total_stake = stakes
+            .iter()
+            .try_fold(0u64, |acc, w| acc.checked_add(*w))
+            .ok_or(FastCryptoError::InvalidInput)?;
+        if public_keys.len() != stakes.len()
+            || vrf_public_keys.len() != stakes.len()
+            || total_stake == 0
+            || expected_size == 0
+            || expected_size > total_stake
+            || threshold == 0
+        {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(Self {
+            public_keys,
+            vrf_public_keys,
+            stakes,
+            total_stake,
+            expected_size,
+            threshold,
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        self.stakes.len()
+    }
+
+    /// The number of seats needed for a certificate.
+    pub fn threshold(&self) -> u64 {
+        self.threshold
+    }
+
+    /// The number of seats of the member with the given index given its VRF output. The output must
+    /// be verified, e.g. with [SampledCommittee::verify_selection]. The cost is linear in the
+    /// number of seats. Fails if the index is not in the committee.
+    pub fn seats(&self, index: usize, vrf_output: &[u8; 64]) -> Result<u64, FastCryptoError> {
+        let stake = *self
+            .stakes
+            .get(index)
+            .ok_or(FastCryptoError::InvalidInput)?;
+        if self.expected_size == self.total_stake {
+            return Ok(stake);
+        }
+        let x = SortitionRng::new(SAMPLING_DOMAIN, vrf_output).next_u64();
+        let x = Float::new(x as u128, -64);
+
+        // B(j + 1; w, p) = B(j; w, p) * (w - j) / (j + 1) * p / (1 - p), starting from
+        // B(0; w, p) = (1 - p)^w.
+        let odds = Float::ratio(self.expected_size, self.total_stake - self.expected_size);
+        let mut probability =
+            Float::ratio(self.total_stake - self.expected_size, self.total_stake).pow(stake);
+        let mut cdf = probability;
+        let mut seats = 0;
+        while seats < stake && cdf <= x {
+            probability = probability
+                .mul(Float::ratio(stake - seats, seats + 1))
+                .mul(odds);
+            let next = cdf.add(probability);
+            // The remaining probabilities are too small to change the sum.
+            if next == cdf {
+                break;
+            }
+            cdf = next;
+            seats += 1;
+        }
+        Ok(seats)
+    }
+
+    /// Verify the VRF proof of the member with the given index on the input of the seed, and return
+    /// its number of seats. Fails if the member has no seats.
+    pub fn verify_selection(
+        &self,
+        index: usize,
+        seed: &[u8],
+        proof: &P,
+    ) -> Result<u64, FastCryptoError> {
+        let vrf_public_key = self
+            .vrf_public_keys
+            .get(index)
+            .ok_or(FastCryptoError::InvalidInput)?;
+        proof.verify(&sortition_input(seed), vrf_public_key)?;
+        match self.seats(index, &proof.to_hash())? {
+            0 => Err(FastCryptoError::InvalidProof),
+            seats => Ok(seats),
+        }
+    }
+}
+
+/// A non-negative number `mantissa * 2^exponent`, where the mantissa is zero or has its highest bit
+/// set. The arithmetic rounds towards zero.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Float {
+    mantissa: u64,
+    exponent: i128,
+}
+
+impl Float {
+    fn new(mantissa: u128, exponent: i128) -> Self {
+        if mantissa == 0 {
+            return Self {
+                mantissa: 0,
+                exponent: 0,
+            };
+        }
+        let bits = 128 - mantissa.leading_zeros() as i128;
+        let mantissa = match bits > 64 {
+            true => mantissa >> (bits - 64),
+            false => mantissa << (64 - bits),
+        };
+        Self {
+            mantissa: mantissa as u64,
+            exponent: exponent + bits - 64,
+        }
+    }
+
+    /// `numerator / denominator` for a non-zero denominator.
+    fn ratio(numerator: u64, denominator: u64) -> Self {
+        Self::new(((numerator as u128) << 64) / denominator as u128, -64)
+    }
+
+    fn mul(self, other: Self) -> Self {
+        Self::new(
+            self.mantissa as u128 * other.mantissa as u128,
+            self.exponent + other.exponent,
+        )
+    }
+
+    fn add(self, other: Self) -> Self {
+        if self.mantissa == 0 {
+            return other;
+        } else if other.mantissa == 0 {
+            return self;
+        }
+        let (larger, smaller) = match self.exponent >= other.exponent {
+            true => (self, other),
+            false => (other, self),
+        };
+        let shift = larger.exponent - smaller.exponent;
+        let smaller_mantissa = match shift < 63 {
+            true => (smaller.mantissa as u128) << (63 - shift),
+            false => 0,
+        };
+        Self::new(
+            ((larger.mantissa as u128) << 63) + smaller_mantissa,
+            larger.exponent - 63,
+        )
+    }
+
+    fn pow(self, mut exponent: u64) -> Self {
+        let mut result = Self::new(1, 0);
+        let mut base = self;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.mul(base);
+            }
+            base = base.mul(base);
+            exponent >>= 1;
+        }
+        result
+    }
+}
+
+impl PartialOrd for Float {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Float {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.mantissa != 0, self.exponent, self.mantissa).cmp(&(
+            other.mantissa != 0,
+            other.exponent,
+            other.mantissa,
+        ))
+    }
+}
+
+/// An aggregate signature by sampled committee members with proofs of their seats.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SampledCertificate<A: AggregateAuthenticator, P: VRFProof<64>> {
+    pub certificate: Certificate<A>,
+    /// The VRF proofs of the signers in increasing order of their indices.
+    pub proofs: Vec<P>,
+}
+
+impl<A: AggregateAuthenticator + ToFromBytes, P: VRFProof<64>> SampledCertificate<A, P> {
+    /// Aggregate the signatures of the given members, given as their index, signature and VRF
+    /// proof in any order. The seats are not verified.
+    pub fn from_votes(
+        committee: &SampledCommittee<A, P>,
+        mut votes: Vec<(usize, A::Sig, P)>,
+    ) -> Result<Self, FastCryptoError> {
+        votes.sort_unstable_by_key(|(i, _, _)| *i);
+        let indices = votes.iter().map(|(i, _, _)| *i).collect::<Vec<_>>();
+        let signers = SignerSet::new(committee.size(), &indices)?;
+        let signature = A::aggregate(votes.iter().map(|(_, s, _)| s))?;
+        Ok(Self {
+            certificate: Certificate::new(signature, signers)?,
+            proofs: votes.into_iter().map(|(_, _, p)| p).collect(),
+        })
+    }
+
+    /// Verify that members with at least [SampledCommittee::threshold] seats for the seed signed
+    /// `message` and that all signers have seats. The aggregate signature is only verified after
+    /// the cheaper checks of the VRF proofs and the number of seats.
+    pub fn verify(
+        &self,
+        committee: &SampledCommittee<A, P>,
+        seed: &[u8],
+        message: &[u8],
+    ) -> Result<(), FastCryptoError> {
+        let signers = self.certificate.signers.signers();
+        if self.certificate.signers.committee_size() != committee.size()
+            || self.proofs.len() != signers.len()
+        {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let mut seats = 0u64;
+        for (index, proof) in signers.iter().zip(&self.proofs) {
+            // The seats of each member are at most its stake, so the sum does not overflow.
+            seats += committee.verify_selection(*index, seed, proof)?;
+        }
+        if seats < committee.threshold {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        self.certificate.verify(&committee.public_keys, message)
+    }
+}
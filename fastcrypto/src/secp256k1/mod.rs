@@ -20,7 +20,7 @@ pub mod recoverable;
 use crate::hash::{HashFunction, Sha256};
 use crate::secp256k1::recoverable::Secp256k1RecoverableSignature;
 use crate::serde_helpers::BytesRepresentation;
-use crate::traits::Signer;
+use crate::traits::{BatchSigner, Signer};
 use crate::{
     encoding::{Base64, Encoding},
     error::FastCryptoError,
@@ -130,8 +130,18 @@ impl Secp256k1PublicKey {
         msg: &[u8],
         signature: &Secp256k1Signature,
     ) -> Result<(), FastCryptoError> {
-        // This fails if the output of the hash function is not 32 bytes, but that is ensured by the def of H.
-        let hashed_message = Message::from_slice(H::digest(msg).as_ref()).unwrap();
+        self.verify_prehash(&H::digest(msg).digest, signature)
+    }
+
+    /// Verify the signature over a message which has already been hashed. The digest must be the
+    /// output of a cryptographic hash function.
+    pub fn verify_prehash(
+        &self,
+        digest: &[u8; 32],
+        signature: &Secp256k1Signature,
+    ) -> Result<(), FastCryptoError> {
+        // This fails if the digest is not 32 bytes, but that is ensured by its type.
+        let hashed_message = Message::from_slice(digest).unwrap();
         signature
             .sig
             .verify(&hashed_message, &self.pubkey)
@@ -373,6 +383,8 @@ impl Secp256k1KeyPair {
     }
 }
 
+impl BatchSigner<Secp256k1Signature> for Secp256k1KeyPair {}
+
 impl Signer<Secp256k1Signature> for Secp256k1KeyPair {
     fn sign(&self, msg: &[u8]) -> Secp256k1Signature {
         // Sha256 is used by default
@@ -3,9 +3,12 @@
 
 //! This module contains conversion function between scalars (fr), field elements (fq) and elliptic curve
 //! points between the representations used by arkworks in ark-secp256r1 and RustCrypto's p256 crate.
+//!
+//! The conversions used when verifying signatures do not allocate. In particular, they avoid the
+//! `from_*_bytes_mod_order` functions of arkworks which copy their input into a `Vec`.
 
 use ark_ec::AffineRepr;
-use ark_ff::{Field, PrimeField as ArkworksPrimeField, Zero};
+use ark_ff::{BigInt, BigInteger, Field, PrimeField as ArkworksPrimeField, Zero};
 use ark_serialize::{CanonicalSerialize, CanonicalSerializeWithFlags, EmptyFlags};
 use elliptic_curve::bigint::ArrayEncoding;
 use elliptic_curve::scalar::FromUintUnchecked;
@@ -15,7 +18,7 @@ use p256::{FieldBytes, U256};
 
 /// Convert a p256 scalar to an arkworks scalar.
 pub(crate) fn fr_p256_to_arkworks(scalar: &p256::Scalar) -> ark_secp256r1::Fr {
-    ark_secp256r1::Fr::from_be_bytes_mod_order(&scalar.to_bytes())
+    reduce_once(bigint_from_be_bytes(&scalar.to_bytes()))
 }
 
 /// Convert an arkworks scalar to a p256 scalar.
@@ -43,21 +46,39 @@ pub(crate) fn affine_pt_p256_to_arkworks(point: &p256::AffinePoint) -> ark_secp2
     }
     let encoded_point = point.to_encoded_point(false);
     ark_secp256r1::Affine::new_unchecked(
-        ark_secp256r1::Fq::from_be_bytes_mod_order(encoded_point.x().unwrap()),
-        ark_secp256r1::Fq::from_be_bytes_mod_order(encoded_point.y().unwrap()),
+        reduce_once(bigint_from_be_bytes(encoded_point.x().unwrap())),
+        reduce_once(bigint_from_be_bytes(encoded_point.y().unwrap())),
     )
 }
 
 /// Reduce a big-endian integer representation modulo the subgroup order in arkworks representation.
 pub(crate) fn reduce_bytes(bytes: &[u8; 32]) -> ark_secp256r1::Fr {
-    ark_secp256r1::Fr::from_be_bytes_mod_order(bytes)
+    // Any 256-bit integer is smaller than twice the subgroup order.
+    reduce_once(bigint_from_be_bytes(bytes))
 }
 
 /// Reduce an arkworks field element (modulo field size) to a scalar (modulo subgroup order)
 pub(crate) fn arkworks_fq_to_fr(scalar: &ark_secp256r1::Fq) -> ark_secp256r1::Fr {
-    let mut bytes = [0u8; 32];
-    scalar.serialize_uncompressed(&mut bytes[..]).unwrap();
-    ark_secp256r1::Fr::from_le_bytes_mod_order(&bytes)
+    // The field size is smaller than twice the subgroup order.
+    reduce_once(scalar.into_bigint())
+}
+
+/// Interpret 32 big-endian bytes as an integer.
+fn bigint_from_be_bytes(bytes: &[u8]) -> BigInt<4> {
+    debug_assert_eq!(bytes.len(), 32);
+    let mut limbs = [0u64; 4];
+    for (limb, chunk) in limbs.iter_mut().zip(bytes.rchunks_exact(8)) {
+        *limb = u64::from_be_bytes(chunk.try_into().expect("Chunks are 8 bytes"));
+    }
+    BigInt::new(limbs)
+}
+
+/// Reduce an integer smaller than twice the modulus of the field `F`.
+fn reduce_once<F: ArkworksPrimeField<BigInt = BigInt<4>>>(mut x: BigInt<4>) -> F {
+    if x >= F::MODULUS {
+        x.sub_with_borrow(&F::MODULUS);
+    }
+    F::from_bigint(x).expect("x is smaller than the modulus")
 }
 
 /// Converts an arkworks affine point to a p256 affine point.
@@ -183,6 +204,17 @@ mod tests {
         assert_eq!(reduce_bytes(&p256_s.to_bytes().try_into().unwrap()), s_fr);
     }
 
+    #[test]
+    fn test_reduce_bytes() {
+        let modulus: [u8; 32] = ark_secp256r1::Fr::MODULUS.to_bytes_be().try_into().unwrap();
+        for bytes in [[0u8; 32], [0xff; 32], modulus, [7u8; 32]] {
+            assert_eq!(
+                fr_arkworks_to_p256(&reduce_bytes(&bytes)),
+                p256::Scalar::reduce_bytes(&FieldBytes::from(bytes))
+            );
+        }
+    }
+
     #[test]
     fn test_fq_arkworks_to_p256() {
         let arkworks_seven = ark_secp256r1::Fq::from(7u32);
@@ -48,7 +48,7 @@ use crate::secp256r1::conversion::{
     get_affine_x_coordinate, reduce_bytes,
 };
 use crate::secp256r1::recoverable::Secp256r1RecoverableSignature;
-use crate::traits::Signer;
+use crate::traits::{BatchSigner, Signer};
 use crate::{
     encoding::{Base64, Encoding},
     error::FastCryptoError,
@@ -146,6 +146,16 @@ impl Secp256r1PublicKey {
         &self,
         msg: &[u8],
         signature: &Secp256r1Signature,
+    ) -> Result<(), FastCryptoError> {
+        self.verify_prehash(&H::digest(msg).digest, signature)
+    }
+
+    /// Verify the signature over a message which has already been hashed. The digest must be the
+    /// output of a cryptographic hash function.
+    pub fn verify_prehash(
+        &self,
+        digest: &[u8; 32],
+        signature: &Secp256r1Signature,
     ) -> Result<(), FastCryptoError> {
         if signature.sig.s().is_high().into() {
             return Err(FastCryptoError::GeneralError(
@@ -159,7 +169,7 @@ impl Secp256r1PublicKey {
         // Split signature into scalars. Note that this panics if r or s are zero, which is handled
         // in Secp256r1Signature::from_bytes.
         let (r, s) = signature.sig.split_scalars();
-        let z = reduce_bytes(&H::digest(msg).digest);
+        let z = reduce_bytes(digest);
 
         // Convert scalars to arkworks representation
         let r = fr_p256_to_arkworks(&r);
@@ -466,6 +476,8 @@ impl FromStr for Secp256r1KeyPair {
     }
 }
 
+impl BatchSigner<Secp256r1Signature> for Secp256r1KeyPair {}
+
 impl Signer<Secp256r1Signature> for Secp256r1KeyPair {
     fn sign(&self, msg: &[u8]) -> Secp256r1Signature {
         self.sign_with_hash::<DefaultHash>(msg)
@@ -0,0 +1,188 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Runtime self-tests of the arithmetic backends.
+//!
+//! The BLS12-381 implementation uses blst, which selects between hand-written assembly for some
+//! targets (e.g. x86_64 and aarch64), generic C code for others (e.g. 32-bit ARM or RISC-V) and
+//! limbs of 32 or 64 bits depending on the pointer width. Applications deployed on less common
+//! targets, or built with the `portable` feature, should call [run] at startup to check that
+//! the backend computes correct results before handling any keys.
+//!
+//! The BLS12-381 checks are only included with the `bls12381` feature.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::self_test;
+//! assert!(self_test::run().is_ok());
+//! ```
+
+use crate::error::FastCryptoError;
+#[cfg(all(feature = "bls12381", any(test, feature = "experimental")))]
+use crate::groups::{
+    bls12381::{G1Element, G2Element, GTElement, Scalar},
+    GroupElement, HashToGroupElement, Pairing, Scalar as _,
+};
+use crate::hash::{HashFunction, Sha256};
+#[cfg(all(feature = "bls12381", any(test, feature = "experimental")))]
+use crate::serde_helpers::ToFromByteArray;
+#[cfg(feature = "bls12381")]
+use crate::{
+    bls12381::{min_pk, min_sig},
+    traits::{AggregateAuthenticator, KeyPair, Signer, ToFromBytes, VerifyingKey},
+};
+use once_cell::sync::OnceCell;
+
+/// The SHA-256 digest of "abc" from FIPS 180-2.
+const SHA256_ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+/// The compressed encodings of the standard generators of G1 and G2.
+#[cfg(feature = "bls12381")]
+const G1_GENERATOR: &str = "97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+#[cfg(feature = "bls12381")]
+const G2_GENERATOR: &str = "93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8";
+
+static RESULT: OnceCell<Result<(), FastCryptoError>> = OnceCell::new();
+
+/// Run all self-tests. Returns [FastCryptoError::GeneralError] naming the first failed test.
+pub fn run() -> Result<(), FastCryptoError> {
+    check("sha256", sha256)?;
+    #[cfg(feature = "bls12381")]
+    {
+        check("bls12381 min-sig signatures", || {
+            bls_signatures::<min_sig::BLS12381KeyPair, min_sig::BLS12381AggregateSignature>(
+                G2_GENERATOR,
+            )
+        })?;
+        check("bls12381 min-pk signatures", || {
+            bls_signatures::<min_pk::BLS12381KeyPair, min_pk::BLS12381AggregateSignature>(
+                G1_GENERATOR,
+            )
+        })?;
+    }
+    #[cfg(all(feature = "bls12381", any(test, feature = "experimental")))]
+    {
+        check("bls12381 scalar arithmetic", scalar_arithmetic)?;
+        check("bls12381 g1 arithmetic", g1_arithmetic)?;
+        check("bls12381 g2 arithmetic", g2_arithmetic)?;
+        check("bls12381 pairing", pairing)?;
+    }
+    Ok(())
+}
+
+/// Run all self-tests the first time this is called and return the cached result afterwards.
+pub fn run_once() -> Result<(), FastCryptoError> {
+    RESULT.get_or_init(run).clone()
+}
+
+fn check(name: &str, test: impl FnOnce() -> bool) -> Result<(), FastCryptoError> {
+    match test() {
+        true => Ok(()),
+        false => Err(FastCryptoError::GeneralError(format!(
+            "Self-test failed: {}",
+            name
+        ))),
+    }
+}
+
+fn sha256() -> bool {
+    hex::encode(Sha256::digest(b"abc").digest) == SHA256_ABC
+}
+
+#[cfg(all(feature = "bls12381", any(test, feature = "experimental")))]
+fn scalar_arithmetic() -> bool {
+    let one = Scalar::generator();
+    let a = Scalar::from(0x1234_5678_9abc_def0);
+    let b = Scalar::from(0x0fed_cba9_8765_4321);
+    let product = Scalar::from(0x0123_4567_89ab_cdef) * Scalar::from(0x1_0000_0000);
+    let quotient = match a / b {
+        Ok(q) => q,
+        Err(_) => return false,
+    };
+    // The byte encoding does not depend on the limb size, so it catches limb conversion errors.
+    let mut expected_product = [0u8; 32];
+    expected_product[20..28].copy_from_slice(&0x0123_4567_89ab_cdefu64.to_be_bytes());
+    Scalar::from(1) == one
+        && a + b == b + a
+        && (a - b) + b == a
+        && a * (b + one) == a * b + a
+        && quotient * b == a
+        && a + (-a) == Scalar::zero()
+        && product.to_byte_array() == expected_product
+        && Scalar::from_byte_array(&a.to_byte_array()) == Ok(a)
+}
+
+#[cfg(all(feature = "bls12381", any(test, feature = "experimental")))]
+fn g1_arithmetic() -> bool {
+    let g = G1Element::generator();
+    let (a, b) = (Scalar::from(7), Scalar::from(11));
+    let h = G1Element::hash_to_group_element(b"fastcrypto-self-test");
+    hex::encode(g.to_byte_array()) == G1_GENERATOR
+        && g + g == g * Scalar::from(2)
+        && g * (a + b) == g * a + g * b
+        && (g * a) * b == g * (a * b)
+        && g * -Scalar::generator() == -g
+        && g - g == G1Element::zero()
+        && G1Element::from_byte_array(&h.to_byte_array()) == Ok(h)
+        && h != G1Element::zero()
+}
+
+#[cfg(all(feature = "bls12381", any(test, feature = "experimental")))]
+fn g2_arithmetic() -> bool {
+    let g = G2Element::generator();
+    let (a, b) = (Scalar::from(7), Scalar::from(11));
+    let h = G2Element::hash_to_group_element(b"fastcrypto-self-test");
+    hex::encode(g.to_byte_array()) == G2_GENERATOR
+        && g + g == g * Scalar::from(2)
+        && g * (a + b) == g * a + g * b
+        && (g * a) * b == g * (a * b)
+        && g * -Scalar::generator() == -g
+        && g - g == G2Element::zero()
+        && G2Element::from_byte_array(&h.to_byte_array()) == Ok(h)
+        && h != G2Element::zero()
+}
+
+#[cfg(all(feature = "bls12381", any(test, feature = "experimental")))]
+fn pairing() -> bool {
+    let (a, b) = (Scalar::from(5), Scalar::from(13));
+    let base = G1Element::generator().pairing(&G2Element::generator());
+    base != GTElement::zero()
+        && (G1Element::generator() * a).pairing(&(G2Element::generator() * b)) == base * (a * b)
+        && (G1Element::generator() * a).pairing(&G2Element::generator())
+            == G1Element::generator().pairing(&(G2Element::generator() * a))
+}
+
+/// Check signing and verification, the aggregation of signatures (which adds group elements) and
+/// that the public key of the private key 1 is the generator.
+#[cfg(feature = "bls12381")]
+fn bls_signatures<K, A>(public_key_of_one: &str) -> bool
+where
+    K: KeyPair,
+    A: AggregateAuthenticator<Sig = K::Sig, PubKey = K::PubKey, PrivKey = K::PrivKey> + ToFromBytes,
+{
+    const MESSAGE: &[u8] = b"fastcrypto-self-test";
+    let key_pair = |x: u8| {
+        let mut bytes = [0u8; 32];
+        bytes[31] = x;
+        <K::PrivKey as ToFromBytes>::from_bytes(&bytes).map(K::from)
+    };
+    let (k1, k2, k3) = match (key_pair(1), key_pair(2), key_pair(3)) {
+        (Ok(k1), Ok(k2), Ok(k3)) => (k1, k2, k3),
+        _ => return false,
+    };
+    let (s1, s2, s3) = (k1.sign(MESSAGE), k2.sign(MESSAGE), k3.sign(MESSAGE));
+    let aggregate = match A::aggregate(&[s1.clone(), s2]) {
+        Ok(aggregate) => aggregate,
+        Err(_) => return false,
+    };
+    // Signatures are linear in the private key, so aggregating the signatures of 1 and 2 gives the
+    // signature of 3.
+    hex::encode(k1.public().as_ref()) == public_key_of_one
+        && k1.public().verify(MESSAGE, &s1).is_ok()
+        && k1.public().verify(b"fastcrypto-self-tesu", &s1).is_err()
+        && k2.public().verify(MESSAGE, &s1).is_err()
+        && aggregate.as_ref() == s3.as_ref()
+        && aggregate
+            .verify(&[k1.public().clone(), k2.public().clone()], MESSAGE)
+            .is_ok()
+}
@@ -268,13 +268,14 @@ impl<const N: usize> Display for BytesRepresentation<N> {
 }
 
 // This is needed in Narwhal certificates but we don't want default implementations for all BytesRepresentations.
+#[cfg(feature = "bls12381")]
 impl Default for crate::bls12381::min_sig::BLS12381AggregateSignatureAsBytes {
     fn default() -> Self {
         (&crate::bls12381::min_sig::BLS12381AggregateSignature::default()).into()
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "bls12381"))]
 mod tests {
     use super::*;
     use crate::groups::bls12381::{G1Element, G1ElementAsBytes, G1_ELEMENT_BYTE_LENGTH};
@@ -1,9 +1,14 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::error::FastCryptoError;
 use crate::hash::Digest;
 use crate::traits;
-use crate::traits::Signer;
+use crate::traits::{BatchSigner, Signer};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::error::TrySendError;
 use tokio::sync::mpsc::{channel, Sender};
 use tokio::sync::oneshot;
 
@@ -41,3 +46,200 @@ impl<Signature: traits::Authenticator, const DIGEST_LEN: usize>
             .expect("Failed to receive signature from Signature Service")
     }
 }
+
+/// Configuration of a [BatchingSignatureService].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchingConfig {
+    /// The maximal number of requests waiting to be signed. Further requests wait (or are rejected
+    /// by [BatchingSignatureService::try_request_signature]) until there is room in the queue.
+    pub queue_capacity: usize,
+    /// The maximal number of requests signed in a single batch.
+    pub max_batch_size: usize,
+}
+
+impl Default for BatchingConfig {
+    fn default() -> Self {
+        Self {
+            queue_capacity: 10_000,
+            max_batch_size: 256,
+        }
+    }
+}
+
+/// Metrics of a [BatchingSignatureService].
+#[derive(Debug, Default)]
+pub struct SignatureServiceMetrics {
+    pending: AtomicUsize,
+    signed: AtomicU64,
+    batches: AtomicU64,
+    rejected: AtomicU64,
+    total_latency_micros: AtomicU64,
+    max_latency_micros: AtomicU64,
+}
+
+impl SignatureServiceMetrics {
+    /// The number of requests which have been queued but not yet signed.
+    pub fn pending(&self) -> usize {
+        self.pending.load(Ordering::Relaxed)
+    }
+
+    /// The total number of signatures created.
+    pub fn signed(&self) -> u64 {
+        self.signed.load(Ordering::Relaxed)
+    }
+
+    /// The total number of batches signed.
+    pub fn batches(&self) -> u64 {
+        self.batches.load(Ordering::Relaxed)
+    }
+
+    /// The number of requests rejected because the queue was full.
+    pub fn rejected(&self) -> u64 {
+        self.rejected.load(Ordering::Relaxed)
+    }
+
+    /// The average time from a request being queued until it was signed.
+    pub fn average_latency(&self) -> Duration {
+        match self.signed() {
+            0 => Duration::ZERO,
+            signed => {
+                Duration::from_micros(self.total_latency_micros.load(Ordering::Relaxed) / signed)
+            }
+        }
+    }
+
+    /// The maximal time from a request being queued until it was signed.
+    pub fn max_latency(&self) -> Duration {
+        Duration::from_micros(self.max_latency_micros.load(Ordering::Relaxed))
+    }
+
+    fn record_batch(&self, queued_at: &[Instant]) {
+        let now = Instant::now();
+        for t in queued_at {
+            let latency = now.duration_since(*t).as_micros() as u64;
+            self.total_latency_micros
+                .fetch_add(latency, Ordering::Relaxed);
+            self.max_latency_micros
+                .fetch_max(latency, Ordering::Relaxed);
+        }
+        self.pending.fetch_sub(queued_at.len(), Ordering::Relaxed);
+        self.signed
+            .fetch_add(queued_at.len() as u64, Ordering::Relaxed);
+        self.batches.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+type SignatureRequest<Signature, const DIGEST_LEN: usize> =
+    (Digest<DIGEST_LEN>, Instant, oneshot::Sender<Signature>);
+
+/// A signature service which, like [SignatureService], holds the node's private key and signs
+/// digests on request, but signs all requests queued at the same time as a single batch using
+/// [BatchSigner::sign_batch]. The queue is bounded, so callers experience backpressure when
+/// requests arrive faster than they can be signed.
+#[derive(Clone)]
+pub struct BatchingSignatureService<Signature: traits::Authenticator, const DIGEST_LEN: usize> {
+    channel: Sender<SignatureRequest<Signature, DIGEST_LEN>>,
+    metrics: Arc<SignatureServiceMetrics>,
+}
+
+impl<Signature: traits::Authenticator, const DIGEST_LEN: usize>
+    BatchingSignatureService<Signature, DIGEST_LEN>
+{
+    /// Spawn a new service with the default configuration.
+    pub fn new<S>(signer: S) -> Self
+    where
+        S: BatchSigner<Signature> + Send + 'static,
+    {
+        Self::new_with_config(signer, BatchingConfig::default())
+    }
+
+    /// Spawn a new service with the given configuration.
+    pub fn new_with_config<S>(signer: S, config: BatchingConfig) -> Self
+    where
+        S: BatchSigner<Signature> + Send + 'static,
+    {
+        let (tx, mut rx) =
+            channel::<SignatureRequest<Signature, DIGEST_LEN>>(config.queue_capacity.max(1));
+        let metrics = Arc::new(SignatureServiceMetrics::default());
+        let service_metrics = metrics.clone();
+        let max_batch_size = config.max_batch_size.max(1);
+
+        tokio::spawn(async move {
+            while let Some(first) = rx.recv().await {
+                let mut batch = vec![first];
+                while batch.len() < max_batch_size {
+                    match rx.try_recv() {
+                        Ok(request) => batch.push(request),
+                        Err(_) => break,
+                    }
+                }
+
+                let digests = batch
+                    .iter()
+                    .map(|(digest, _, _)| digest.as_ref())
+                    .collect::<Vec<_>>();
+                let signatures = signer.sign_batch(&digests);
+                service_metrics.record_batch(
+                    &batch
+                        .iter()
+                        .map(|(_, queued_at, _)| *queued_at)
+                        .collect::<Vec<_>>(),
+                );
+
+                for ((_, _, sender), signature) in batch.into_iter().zip(signatures) {
+                    let _ = sender.send(signature);
+                }
+            }
+        });
+        Self {
+            channel: tx,
+            metrics,
+        }
+    }
+
+    /// Request a signature over a digest, waiting for room in the queue if it is full. The request
+    /// only counts as pending once it is queued, so dropping the future while it waits for room
+    /// leaves the metrics unchanged.
+    pub async fn request_signature(&self, digest: Digest<DIGEST_LEN>) -> Signature {
+        let (sender, receiver) = oneshot::channel();
+        let permit = match self.channel.reserve().await {
+            Ok(permit) => permit,
+            Err(e) => panic!("Failed to send message Signature Service: {e}"),
+        };
+        // Sending on a reserved permit does not yield, so the batcher cannot sign the request
+        // before it is counted.
+        self.metrics.pending.fetch_add(1, Ordering::Relaxed);
+        permit.send((digest, Instant::now(), sender));
+        receiver
+            .await
+            .expect("Failed to receive signature from Signature Service")
+    }
+
+    /// Request a signature over a digest. Returns an error immediately if the queue is full.
+    pub async fn try_request_signature(
+        &self,
+        digest: Digest<DIGEST_LEN>,
+    ) -> Result<Signature, FastCryptoError> {
+        let (sender, receiver) = oneshot::channel();
+        self.metrics.pending.fetch_add(1, Ordering::Relaxed);
+        match self.channel.try_send((digest, Instant::now(), sender)) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => {
+                self.metrics.pending.fetch_sub(1, Ordering::Relaxed);
+                self.metrics.rejected.fetch_add(1, Ordering::Relaxed);
+                return Err(FastCryptoError::GeneralError(
+                    "Signature Service queue is full".to_string(),
+                ));
+            }
+            Err(TrySendError::Closed(_)) => panic!("Signature Service has stopped"),
+        }
+        Ok(receiver
+            .await
+            .expect("Failed to receive signature from Signature Service"))
+    }
+
+    /// The metrics of this service.
+    pub fn metrics(&self) -> &SignatureServiceMetrics {
+        &self.metrics
+    }
+}
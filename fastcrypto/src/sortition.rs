@@ -0,0 +1,172 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Deterministic shuffling and weighted sampling from a random seed, e.g. the output of a VRF or a
+//! randomness beacon, so that parties with the same seed compute the same committees.
+//!
+//! All functions draw from a [SortitionRng], whose output is specified at the byte level:
+//! * The key is `SHA-256(len(domain) || domain || seed)`, where `len(domain)` is the length of the
+//!   domain as an 8 byte big-endian integer.
+//! * The `i`'th number, counting from zero, is the first 8 bytes of `SHA-256(key || i)` read as a
+//!   big-endian integer, where `i` is encoded as an 8 byte big-endian integer.
+//! * [SortitionRng::uniform] with a bound `b` draws numbers until one, `x`, is less than
+//!   `b * floor(2^64 / b)` and returns `x mod b`, so the result is exactly uniform.
+//! * [SortitionRng::shuffle] is a Fisher–Yates shuffle: For `i` from `n - 1` down to `1`, it swaps
+//!   the elements with indices `i` and `uniform(i + 1)`.
+//! * [SortitionRng::weighted_index] draws `t = uniform(w_0 + ... + w_{n-1})` and returns the
+//!   smallest `i` with `t < w_0 + ... + w_i`.
+//! * [SortitionRng::sample_without_replacement] calls `weighted_index` `k` times, setting the
+//!   weight of each selected index to zero before the next call.
+//! * [leader_schedule] calls `weighted_index` once per round on the stakes using the domain
+//!   [LEADER_SCHEDULE_DOMAIN].
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::sortition::*;
+//! let stakes = [10, 5, 0, 30, 20];
+//! let committee = SortitionRng::new(b"committee", b"vrf output")
+//!     .sample_without_replacement(&stakes, 3)
+//!     .unwrap();
+//! assert_eq!(committee.len(), 3);
+//! assert!(!committee.contains(&2));
+//! assert_eq!(
+//!     committee,
+//!     SortitionRng::new(b"committee", b"vrf output")
+//!         .sample_without_replacement(&stakes, 3)
+//!         .unwrap()
+//! );
+//! ```
+
+use crate::error::FastCryptoError;
+use crate::hash::{HashFunction, Sha256};
+
+/// The domain of the [SortitionRng] used by [leader_schedule].
+pub const LEADER_SCHEDULE_DOMAIN: &[u8] = b"fastcrypto-leader-schedule-v1";
+
+/// A deterministic stream of random numbers derived from a domain and a seed. See the module
+/// documentation for the exact output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SortitionRng {
+    key: [u8; 32],
+    counter: u64,
+}
+
+impl SortitionRng {
+    pub fn new(domain: &[u8], seed: &[u8]) -> Self {
+        let mut hash = Sha256::default();
+        hash.update((domain.len() as u64).to_be_bytes());
+        hash.update(domain);
+        hash.update(seed);
+        Self {
+            key: hash.finalize().digest,
+            counter: 0,
+        }
+    }
+
+    /// The next number of the stream.
+    pub fn next_u64(&mut self) -> u64 {
+        let mut hash = Sha256::default();
+        hash.update(self.key);
+        hash.update(self.counter.to_be_bytes());
+        self.counter += 1;
+        let digest = hash.finalize().digest;
+        u64::from_be_bytes(digest[..8].try_into().expect("Digest is 32 bytes"))
+    }
+
+    /// A uniformly random number in `0..bound`. Fails if the bound is zero.
+    pub fn uniform(&mut self, bound: u64) -> Result<u64, FastCryptoError> {
+        if bound == 0 {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        // The largest multiple of the bound which is at most 2^64.
+        let zone = u64::MAX - (u64::MAX - bound + 1) % bound;
+        loop {
+            let x = self.next_u64();
+            if x <= zone {
+                return Ok(x % bound);
+            }
+        }
+    }
+
+    /// Shuffle the given elements uniformly.
+    pub fn shuffle<T>(&mut self, elements: &mut [T]) {
+        for i in (1..elements.len()).rev() {
+            let j = self.uniform(i as u64 + 1).expect("Bound is positive") as usize;
+            elements.swap(i, j);
+        }
+    }
+
+    /// A random index where index `i` is chosen with probability proportional to `weights[i]`.
+    /// Fails if all weights are zero or if their sum overflows.
+    pub fn weighted_index(&mut self, weights: &[u64]) -> Result<usize, FastCryptoError> {
+        let total = weights
+            .iter()
+            .try_fold(0u64, |acc, w| acc.checked_add(*w))
+            .ok_or(FastCryptoError::InvalidInput)?;
+        let t = self.uniform(total)?;
+        let mut sum = 0;
+        for (i, w) in weights.iter().enumerate() {
+            sum += w;
+            if t < sum {
+                return Ok(i);
+            }
+        }
+        unreachable!("t is less than the total weight")
+    }
+
+    /// Choose `k` distinct indices, each time with probability proportional to the weights of the
+    /// indices not chosen before. The indices are returned in the order they are chosen. Fails if
+    /// fewer than `k` weights are non-zero or if the sum of the weights overflows.
+    pub fn sample_without_replacement(
+        &mut self,
+        weights: &[u64],
+        k: usize,
+    ) -> Result<Vec<usize>, FastCryptoError> {
+        if weights.iter().filter(|w| **w > 0).count() < k {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let mut weights = weights.to_vec();
+        let mut indices = Vec::with_capacity(k);
+        for _ in 0..k {
+            let i = self.weighted_index(&weights)?;
+            weights[i] = 0;
+            indices.push(i);
+        }
+        Ok(indices)
+    }
+}
+
+/// Compute the leaders of the given number of rounds from a shared randomness, e.g. the output of a
+/// randomness beacon, where the leader of each round is chosen independently with probability
+/// proportional to its stake. Fails if all stakes are zero or if their sum overflows.
+///
+/// The only bias in the schedule is that of SHA-256, since [SortitionRng::uniform] draws exactly
+/// uniform numbers. Thus, by Hoeffding's inequality, the probability that a validator with a
+/// fraction `p` of the stake leads more than `(p + ε) * rounds` or fewer than `(p - ε) * rounds`
+/// rounds is at most `2 exp(-2 ε^2 rounds)`.
+pub fn leader_schedule(
+    randomness: &[u8],
+    stakes: &[u64],
+    rounds: usize,
+) -> Result<Vec<usize>, FastCryptoError> {
+    // Equivalent to calling weighted_index for every round, but the cumulative stakes are only
+    // computed once.
+    let cumulative = stakes
+        .iter()
+        .scan(0u64, |sum, stake| {
+            *sum = sum.checked_add(*stake)?;
+            Some(*sum)
+        })
+        .collect::<Vec<_>>();
+    let total = cumulative.last().copied().unwrap_or(0);
+    if cumulative.len() != stakes.len() || total == 0 {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    let mut rng = SortitionRng::new(LEADER_SCHEDULE_DOMAIN, randomness);
+    (0..rounds)
+        .map(|_| {
+            let t = rng.uniform(total)?;
+            Ok(cumulative.partition_point(|sum| *sum <= t))
+        })
+        .collect()
+}
@@ -0,0 +1,342 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! An implementation of the SRP-6a password-authenticated key exchange as specified in
+//! [RFC 2945](https://www.rfc-editor.org/rfc/rfc2945) and [RFC 5054](https://www.rfc-editor.org/rfc/rfc5054).
+//!
+//! SRP is provided for interoperability with existing deployments, and new systems should use
+//! [crate::opaque] instead. The hash function is a parameter, and the computation of the
+//! multiplier `k`, the scrambling parameter `u`, the session key and the proofs `M1` and `M2`
+//! follow the conventions of RFC 5054 and RFC 2945:
+//! * `k = H(N | PAD(g))` and `u = H(PAD(A) | PAD(B))`,
+//! * `x = H(s | H(I | ":" | P))` and `v = g^x`,
+//! * `K = H(S)`, `M1 = H(H(N) xor H(g) | H(I) | s | A | B | K)` and `M2 = H(A | M1 | K)`.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::hash::Sha256;
+//! # use fastcrypto::srp::*;
+//! # use rand::thread_rng;
+//! let group = SrpGroup::rfc5054_2048();
+//!
+//! // Registration: the client computes a verifier which is stored by the server.
+//! let verifier = SrpVerifier::new::<Sha256, 32, _>(&group, b"alice", b"password", &mut thread_rng());
+//!
+//! // Login
+//! let (client, a) = SrpClient::new(&group, &mut thread_rng());
+//! let (server, b) = SrpServer::new::<Sha256, 32, _>(&group, &verifier, &mut thread_rng());
+//! let (client, m1) = client.process_challenge::<Sha256, 32>(b"alice", b"password", verifier.salt(), &b).unwrap();
+//! let (m2, server_key) = server.verify_client::<Sha256, 32>(b"alice", &a, &m1).unwrap();
+//! let client_key = client.verify_server(&m2).unwrap();
+//! assert_eq!(client_key, server_key);
+//! ```
+
+use crate::error::FastCryptoError;
+use crate::hash::HashFunction;
+use crate::traits::AllowedRng;
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+use subtle::ConstantTimeEq;
+
+/// The length of the salt and the secret exponents in bytes.
+const SECRET_LENGTH: usize = 32;
+
+/// The group used in the protocol. Clients and servers must agree on the group. It consists of a
+/// safe prime `N` and a generator `g` of the multiplicative group modulo `N`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SrpGroup {
+    n: BigUint,
+    g: BigUint,
+}
+
+/// The verifier for a password which is created by the client during registration and stored by
+/// the server.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SrpVerifier {
+    salt: Vec<u8>,
+    verifier: Vec<u8>,
+}
+
+/// The state of a client during login.
+pub struct SrpClient {
+    group: SrpGroup,
+    a: BigUint,
+    a_pub: BigUint,
+}
+
+/// The state of a client after it sent its proof `M1` to the server.
+pub struct SrpClientProof<const DIGEST_LEN: usize> {
+    expected_m2: [u8; DIGEST_LEN],
+    key: [u8; DIGEST_LEN],
+}
+
+/// The state of a server during login.
+pub struct SrpServer {
+    group: SrpGroup,
+    salt: Vec<u8>,
+    v: BigUint,
+    b: BigUint,
+    b_pub: BigUint,
+}
+
+fn hash<H: HashFunction<DIGEST_LEN>, const DIGEST_LEN: usize>(parts: &[&[u8]]) -> [u8; DIGEST_LEN] {
+    let mut hash = H::default();
+    parts.iter().for_each(|part| hash.update(part));
+    hash.finalize().digest
+}
+
+fn random_exponent<R: AllowedRng>(rng: &mut R) -> BigUint {
+    let mut bytes = [0u8; SECRET_LENGTH];
+    rng.fill_bytes(&mut bytes);
+    BigUint::from_bytes_be(&bytes)
+}
+
+impl SrpGroup {
+    /// Create a group from a modulus and a generator given as big-endian bytes. The modulus should be
+    /// a safe prime, which is not checked.
+    pub fn new(n: &[u8], g: &[u8]) -> Result<Self, FastCryptoError> {
+        let n = BigUint::from_bytes_be(n);
+        let g = BigUint::from_bytes_be(g);
+        if n.bits() < 1024 || g <= BigUint::from(1u8) || g >= n {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(Self { n, g })
+    }
+
+    /// The 2048-bit group from appendix A of RFC 5054.
+    pub fn rfc5054_2048() -> Self {
+        Self::from_hex(
+            "AC6BDB41324A9A9BF166DE5E1389582FAF72B6651987EE07FC3192943DB56050A37329CBB4A099ED8193E0757767A13DD52312AB4B03310DCD7F48A9DA04FD50E8083969EDB767B0CF6095179A163AB3661A05FBD5FAAAE82918A9962F0B93B855F97993EC975EEAA80D740ADBF4FF747359D041D5C33EA71D281E446B14773BCA97B43A23FB801676BD207A436C6481F1D2B9078717461A5B9D32E688F87748544523B524B0D57D5EA77A2775D2ECFA032CFBDBF52FB3786160279004E57AE6AF874E7303CE53299CCC041C7BC308D82A5698F3A8D0C38271AE35F8E9DBFBB694B5C803D89F7AE435DE236D525F54759B65E372FCD68EF20FA7111F9E4AFF73",
+            2,
+        )
+    }
+
+    /// The 3072-bit group from appendix A of RFC 5054.
+    pub fn rfc5054_3072() -> Self {
+        Self::from_hex(
+            "FFFFFFFFFFFFFFFFC90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74020BBEA63B139B22514A08798E3404DDEF9519B3CD3A431B302B0A6DF25F14374FE1356D6D51C245E485B576625E7EC6F44C42E9A637ED6B0BFF5CB6F406B7EDEE386BFB5A899FA5AE9F24117C4B1FE649286651ECE45B3DC2007CB8A163BF0598DA48361C55D39A69163FA8FD24CF5F83655D23DCA3AD961C62F356208552BB9ED529077096966D670C354E4ABC9804F1746C08CA18217C32905E462E36CE3BE39E772C180E86039B2783A2EC07A28FB5C55DF06F4C52C9DE2BCBF6955817183995497CEA956AE515D2261898FA051015728E5A8AAAC42DAD33170D04507A33A85521ABDF1CBA64ECFB850458DBEF0A8AEA71575D060C7DB3970F85A6E1E4C7ABF5AE8CDB0933D71E8C94E04A25619DCEE3D2261AD2EE6BF12FFA06D98A0864D87602733EC86A64521F2B18177B200CBBE117577A615D6C770988C0BAD946E208E24FA074E5AB3143DB5BFCE0FD108E4B82D120A93AD2CAFFFFFFFFFFFFFFFF",
+            5,
+        )
+    }
+
+    fn from_hex(n: &str, g: u8) -> Self {
+        Self {
+            n: BigUint::parse_bytes(n.as_bytes(), 16).expect("Constant is valid hex"),
+            g: BigUint::from(g),
+        }
+    }
+
+    /// The length of the modulus in bytes.
+    fn length(&self) -> usize {
+        ((self.n.bits() + 7) / 8) as usize
+    }
+
+    /// Encode a number as big-endian bytes padded to the length of the modulus.
+    fn pad(&self, x: &BigUint) -> Vec<u8> {
+        let bytes = x.to_bytes_be();
+        let mut padded = vec![0u8; self.length().saturating_sub(bytes.len())];
+        padded.extend_from_slice(&bytes);
+        padded
+    }
+
+    /// Decode a public value and check that it is non-zero modulo N.
+    fn decode_public(&self, bytes: &[u8]) -> Result<BigUint, FastCryptoError> {
+        let x = BigUint::from_bytes_be(bytes) % &self.n;
+        if x == BigUint::from(0u8) {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(x)
+    }
+
+    /// k = H(N | PAD(g))
+    fn k<H: HashFunction<DIGEST_LEN>, const DIGEST_LEN: usize>(&self) -> BigUint {
+        BigUint::from_bytes_be(&hash::<H, DIGEST_LEN>(&[
+            &self.n.to_bytes_be(),
+            &self.pad(&self.g),
+        ]))
+    }
+
+    /// u = H(PAD(A) | PAD(B))
+    fn u<H: HashFunction<DIGEST_LEN>, const DIGEST_LEN: usize>(
+        &self,
+        a_pub: &BigUint,
+        b_pub: &BigUint,
+    ) -> Result<BigUint, FastCryptoError> {
+        let u = BigUint::from_bytes_be(&hash::<H, DIGEST_LEN>(&[
+            &self.pad(a_pub),
+            &self.pad(b_pub),
+        ]));
+        if u == BigUint::from(0u8) {
+            return Err(FastCryptoError::GeneralOpaqueError);
+        }
+        Ok(u)
+    }
+
+    /// Compute the session key K and the proofs M1 and M2 from the shared secret S.
+    fn proofs<H: HashFunction<DIGEST_LEN>, const DIGEST_LEN: usize>(
+        &self,
+        identity: &[u8],
+        salt: &[u8],
+        a_pub: &BigUint,
+        b_pub: &BigUint,
+        s: &BigUint,
+    ) -> ([u8; DIGEST_LEN], [u8; DIGEST_LEN], [u8; DIGEST_LEN]) {
+        let key = hash::<H, DIGEST_LEN>(&[&s.to_bytes_be()]);
+        let h_n = hash::<H, DIGEST_LEN>(&[&self.n.to_bytes_be()]);
+        let h_g = hash::<H, DIGEST_LEN>(&[&self.g.to_bytes_be()]);
+        let h_n_xor_h_g = h_n
+            .iter()
+            .zip(h_g.iter())
+            .map(|(x, y)| x ^ y)
+            .collect::<Vec<_>>();
+        let a_bytes = a_pub.to_bytes_be();
+        let m1 = hash::<H, DIGEST_LEN>(&[
+            &h_n_xor_h_g,
+            &hash::<H, DIGEST_LEN>(&[identity]),
+            salt,
+            &a_bytes,
+            &b_pub.to_bytes_be(),
+            &key,
+        ]);
+        let m2 = hash::<H, DIGEST_LEN>(&[&a_bytes, &m1, &key]);
+        (key, m1, m2)
+    }
+}
+
+/// x = H(s | H(I | ":" | P))
+fn private_key<H: HashFunction<DIGEST_LEN>, const DIGEST_LEN: usize>(
+    identity: &[u8],
+    password: &[u8],
+    salt: &[u8],
+) -> BigUint {
+    let inner = hash::<H, DIGEST_LEN>(&[identity, b":", password]);
+    BigUint::from_bytes_be(&hash::<H, DIGEST_LEN>(&[salt, &inner]))
+}
+
+impl SrpVerifier {
+    /// Create a verifier for the given identity and password using a random salt.
+    pub fn new<H: HashFunction<DIGEST_LEN>, const DIGEST_LEN: usize, R: AllowedRng>(
+        group: &SrpGroup,
+        identity: &[u8],
+        password: &[u8],
+        rng: &mut R,
+    ) -> Self {
+        let mut salt = vec![0u8; SECRET_LENGTH];
+        rng.fill_bytes(&mut salt);
+        let x = private_key::<H, DIGEST_LEN>(identity, password, &salt);
+        Self {
+            verifier: group.pad(&group.g.modpow(&x, &group.n)),
+            salt,
+        }
+    }
+
+    /// The salt which should be sent to the client when it tries to log in.
+    pub fn salt(&self) -> &[u8] {
+        &self.salt
+    }
+}
+
+impl SrpClient {
+    /// Start a login. Returns the state of the client and its public value `A` which should be sent
+    /// to the server.
+    pub fn new<R: AllowedRng>(group: &SrpGroup, rng: &mut R) -> (Self, Vec<u8>) {
+        let a = random_exponent(rng);
+        let a_pub = group.g.modpow(&a, &group.n);
+        let a_bytes = group.pad(&a_pub);
+        (
+            Self {
+                group: group.clone(),
+                a,
+                a_pub,
+            },
+            a_bytes,
+        )
+    }
+
+    /// Process the salt and the public value `B` from the server. Returns the proof `M1` which
+    /// should be sent to the server.
+    pub fn process_challenge<H: HashFunction<DIGEST_LEN>, const DIGEST_LEN: usize>(
+        self,
+        identity: &[u8],
+        password: &[u8],
+        salt: &[u8],
+        b_pub: &[u8],
+    ) -> Result<(SrpClientProof<DIGEST_LEN>, [u8; DIGEST_LEN]), FastCryptoError> {
+        let group = &self.group;
+        let b_pub = group.decode_public(b_pub)?;
+        let u = group.u::<H, DIGEST_LEN>(&self.a_pub, &b_pub)?;
+        let k = group.k::<H, DIGEST_LEN>();
+        let x = private_key::<H, DIGEST_LEN>(identity, password, salt);
+
+        // S = (B - k g^x)^(a + u x) mod N
+        let kgx = (k * group.g.modpow(&x, &group.n)) % &group.n;
+        let base = (&b_pub + &group.n - kgx) % &group.n;
+        let s = base.modpow(&(&self.a + u * x), &group.n);
+
+        let (key, m1, expected_m2) =
+            group.proofs::<H, DIGEST_LEN>(identity, salt, &self.a_pub, &b_pub, &s);
+        Ok((SrpClientProof { expected_m2, key }, m1))
+    }
+}
+
+impl<const DIGEST_LEN: usize> SrpClientProof<DIGEST_LEN> {
+    /// Verify the proof `M2` from the server. Returns the session key.
+    pub fn verify_server(self, m2: &[u8]) -> Result<[u8; DIGEST_LEN], FastCryptoError> {
+        if !bool::from(self.expected_m2.ct_eq(m2)) {
+            return Err(FastCryptoError::GeneralOpaqueError);
+        }
+        Ok(self.key)
+    }
+}
+
+impl SrpServer {
+    /// Start a login for a client with the given verifier. Returns the state of the server and its
+    /// public value `B`, which should be sent to the client along with the salt.
+    pub fn new<H: HashFunction<DIGEST_LEN>, const DIGEST_LEN: usize, R: AllowedRng>(
+        group: &SrpGroup,
+        verifier: &SrpVerifier,
+        rng: &mut R,
+    ) -> (Self, Vec<u8>) {
+        let k = group.k::<H, DIGEST_LEN>();
+        let v = BigUint::from_bytes_be(&verifier.verifier);
+        let b = random_exponent(rng);
+
+        // B = k v + g^b mod N
+        let b_pub = (k * &v + group.g.modpow(&b, &group.n)) % &group.n;
+        let b_bytes = group.pad(&b_pub);
+        (
+            Self {
+                group: group.clone(),
+                salt: verifier.salt.clone(),
+                v,
+                b,
+                b_pub,
+            },
+            b_bytes,
+        )
+    }
+
+    /// Verify the proof `M1` and the public value `A` from the client. Returns the proof `M2` which
+    /// should be sent to the client and the session key.
+    pub fn verify_client<H: HashFunction<DIGEST_LEN>, const DIGEST_LEN: usize>(
+        self,
+        identity: &[u8],
+        a_pub: &[u8],
+        m1: &[u8],
+    ) -> Result<([u8; DIGEST_LEN], [u8; DIGEST_LEN]), FastCryptoError> {
+        let group = &self.group;
+        let a_pub = group.decode_public(a_pub)?;
+        let u = group.u::<H, DIGEST_LEN>(&a_pub, &self.b_pub)?;
+
+        // S = (A v^u)^b mod N
+        let base = (&a_pub * self.v.modpow(&u, &group.n)) % &group.n;
+        let s = base.modpow(&self.b, &group.n);
+
+        let (key, expected_m1, m2) =
+            group.proofs::<H, DIGEST_LEN>(identity, &self.salt, &a_pub, &self.b_pub, &s);
+        if !bool::from(expected_m1.ct_eq(m1)) {
+            return Err(FastCryptoError::GeneralOpaqueError);
+        }
+        Ok((m2, key))
+    }
+}
@@ -0,0 +1,349 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verification of signatures in the OpenSSH
+//! [sshsig](https://github.com/openssh/openssh-portable/blob/master/PROTOCOL.sshsig) format, which
+//! are created with `ssh-keygen -Y sign` and are used e.g. by git to sign commits and tags, and to
+//! sign release artifacts.
+//!
+//! A signature is bound to a namespace such as `git` or `file`, which the verifier must give to
+//! prevent signatures created for one purpose from being accepted for another. Supported keys are
+//! * Ed25519 (`ssh-ed25519`), and
+//! * ECDSA over NIST P-256 (`ecdsa-sha2-nistp256`, requires the `secp256r1` feature),
+//!
+//! and messages may be hashed with either SHA-256 or SHA-512.
+//!
+//! Verifying a signature only shows that the message was signed by the given public key, so the
+//! caller must decide whether this key is trusted, e.g. from a git `allowed_signers` file.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::sshsig::*;
+//! let public_key = SshPublicKey::from_openssh(
+//!     "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIIOj0BxtZ2SYqTs+itr/WqbSBnmMLujgVVEkFUMND8Vi user@host",
+//! )
+//! .unwrap();
+//! let signature = SshSignature::from_armored(
+//!     "-----BEGIN SSH SIGNATURE-----
+//! U1NIU0lHAAAAAQAAADMAAAALc3NoLWVkMjU1MTkAAAAgg6PQHG1nZJipOz6K2v9aptIGeY
+//! wu6OBVUSQVQw0PxWIAAAAEZmlsZQAAAAAAAAAGc2hhNTEyAAAAUwAAAAtzc2gtZWQyNTUx
+//! OQAAAEAXCCptSYrNZWZTh2sl+rVCcB0DevbUiI9jy/7I0rAKJInAQmf3OSwk75MzO7Ls5k
+//! 4fBxCNn3Fgl6VMa9x0csoP
+//! -----END SSH SIGNATURE-----",
+//! )
+//! .unwrap();
+//! assert!(signature
+//!     .verify(&public_key, "file", b"hello sshsig\n")
+//!     .is_ok());
+//! assert!(signature
+//!     .verify(&public_key, "git", b"hello sshsig\n")
+//!     .is_err());
+//! ```
+
+use crate::ed25519::{Ed25519PublicKey, Ed25519Signature};
+use crate::error::{FastCryptoError, FastCryptoResult};
+use crate::hash::{Sha256, Sha512};
+#[cfg(feature = "secp256r1")]
+use crate::secp256r1::{Secp256r1PublicKey, Secp256r1Signature};
+use crate::traits::{ToFromBytes, VerifyingKey};
+use crate::verify_stream::hash_reader;
+use base64ct::{Base64, Encoding};
+use std::io::Read;
+
+/// The magic preamble of signatures and of the data they sign.
+pub const SSHSIG_MAGIC: &[u8] = b"SSHSIG";
+
+/// The only version of the signature format.
+pub const SSHSIG_VERSION: u32 = 1;
+
+const ARMOR_BEGIN: &str = "-----BEGIN SSH SIGNATURE-----";
+const ARMOR_END: &str = "-----END SSH SIGNATURE-----";
+
+const ED25519_KEY_TYPE: &[u8] = b"ssh-ed25519";
+#[cfg(feature = "secp256r1")]
+const ECDSA_P256_KEY_TYPE: &[u8] = b"ecdsa-sha2-nistp256";
+#[cfg(feature = "secp256r1")]
+const ECDSA_P256_CURVE: &[u8] = b"nistp256";
+
+/// A public key of a supported type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SshPublicKey {
+    Ed25519(Ed25519PublicKey),
+    #[cfg(feature = "secp256r1")]
+    EcdsaP256(Secp256r1PublicKey),
+}
+
+impl SshPublicKey {
+    /// Parse a public key in the OpenSSH wire format.
+    pub fn from_bytes(bytes: &[u8]) -> FastCryptoResult<Self> {
+        let mut reader = WireReader(bytes);
+        let key = match reader.string()? {
+            ED25519_KEY_TYPE => {
+                SshPublicKey::Ed25519(Ed25519PublicKey::from_bytes(reader.string()?)?)
+            }
+            #[cfg(feature = "secp256r1")]
+            ECDSA_P256_KEY_TYPE => {
+                if reader.string()? != ECDSA_P256_CURVE {
+                    return Err(FastCryptoError::InvalidInput);
+                }
+                // OpenSSH always encodes the point uncompressed.
+                match reader.string()? {
+                    point @ [0x04, ..] if point.len() == 65 => {
+                        SshPublicKey::EcdsaP256(Secp256r1PublicKey::from_bytes(point)?)
+                    }
+                    _ => return Err(FastCryptoError::InvalidInput),
+                }
+            }
+            _ => return Err(FastCryptoError::InvalidInput),
+        };
+        reader.finish()?;
+        Ok(key)
+    }
+
+    /// Parse a public key in the format of `authorized_keys`, `allowed_signers` and `.pub` files,
+    /// i.e., `<key type> <base64 encoded key> [comment]`.
+    pub fn from_openssh(line: &str) -> FastCryptoResult<Self> {
+        let mut parts = line.split_whitespace();
+        let key_type = parts.next().ok_or(FastCryptoError::InvalidInput)?;
+        let encoded = parts.next().ok_or(FastCryptoError::InvalidInput)?;
+        let bytes = Base64::decode_vec(encoded).map_err(|_| FastCryptoError::InvalidInput)?;
+        if WireReader(&bytes).string()? != key_type.as_bytes() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Self::from_bytes(&bytes)
+    }
+}
+
+/// The hash functions which may be used to hash the signed message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SshHashAlgorithm {
+    Sha256,
+    Sha512,
+}
+
+impl SshHashAlgorithm {
+    /// The name of this hash function in the signature format.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SshHashAlgorithm::Sha256 => "sha256",
+            SshHashAlgorithm::Sha512 => "sha512",
+        }
+    }
+
+    fn from_name(name: &[u8]) -> FastCryptoResult<Self> {
+        match name {
+            b"sha256" => Ok(SshHashAlgorithm::Sha256),
+            b"sha512" => Ok(SshHashAlgorithm::Sha512),
+            _ => Err(FastCryptoError::InvalidInput),
+        }
+    }
+
+    fn digest_reader<R: Read>(&self, reader: R) -> FastCryptoResult<Vec<u8>> {
+        match self {
+            SshHashAlgorithm::Sha256 => hash_reader::<Sha256, 32, R>(reader).map(|d| d.to_vec()),
+            SshHashAlgorithm::Sha512 => hash_reader::<Sha512, 64, R>(reader).map(|d| d.to_vec()),
+        }
+    }
+}
+
+/// A signature by a key of the same type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum SshSignatureValue {
+    Ed25519(Ed25519Signature),
+    #[cfg(feature = "secp256r1")]
+    EcdsaP256(Secp256r1Signature),
+}
+
+/// A signature in the sshsig format.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SshSignature {
+    public_key: SshPublicKey,
+    namespace: String,
+    reserved: Vec<u8>,
+    hash_algorithm: SshHashAlgorithm,
+    signature: SshSignatureValue,
+}
+
+impl SshSignature {
+    /// Parse an armored signature as written by `ssh-keygen -Y sign`.
+    pub fn from_armored(armored: &str) -> FastCryptoResult<Self> {
+        let body = armored
+            .trim()
+            .strip_prefix(ARMOR_BEGIN)
+            .and_then(|s| s.strip_suffix(ARMOR_END))
+            .ok_or(FastCryptoError::InvalidInput)?;
+        let encoded = body.split_whitespace().collect::<String>();
+        let bytes = Base64::decode_vec(&encoded).map_err(|_| FastCryptoError::InvalidInput)?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Parse a binary signature, i.e., the decoded body of an armored signature.
+    pub fn from_bytes(bytes: &[u8]) -> FastCryptoResult<Self> {
+        let mut reader = WireReader(bytes);
+        if reader.take(SSHSIG_MAGIC.len())? != SSHSIG_MAGIC || reader.u32()? != SSHSIG_VERSION {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let public_key = SshPublicKey::from_bytes(reader.string()?)?;
+        let namespace = String::from_utf8(reader.string()?.to_vec())
+            .map_err(|_| FastCryptoError::InvalidInput)?;
+        if namespace.is_empty() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let reserved = reader.string()?.to_vec();
+        let hash_algorithm = SshHashAlgorithm::from_name(reader.string()?)?;
+        let signature = parse_signature(&public_key, reader.string()?)?;
+        reader.finish()?;
+        Ok(Self {
+            public_key,
+            namespace,
+            reserved,
+            hash_algorithm,
+            signature,
+        })
+    }
+
+    /// The public key of the signer, as given in the signature.
+    pub fn public_key(&self) -> &SshPublicKey {
+        &self.public_key
+    }
+
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    pub fn hash_algorithm(&self) -> SshHashAlgorithm {
+        self.hash_algorithm
+    }
+
+    /// Verify that this is a signature by `public_key` over `message` in the given namespace.
+    pub fn verify(
+        &self,
+        public_key: &SshPublicKey,
+        namespace: &str,
+        message: &[u8],
+    ) -> FastCryptoResult<()> {
+        self.verify_reader(public_key, namespace, message)
+    }
+
+    /// Verify that this is a signature by `public_key` over the message read from `reader` in the
+    /// given namespace. Fails if reading fails.
+    pub fn verify_reader<R: Read>(
+        &self,
+        public_key: &SshPublicKey,
+        namespace: &str,
+        reader: R,
+    ) -> FastCryptoResult<()> {
+        if *public_key != self.public_key || namespace != self.namespace {
+            return Err(FastCryptoError::InvalidSignature);
+        }
+        let digest = self.hash_algorithm.digest_reader(reader)?;
+        let signed_data = self.signed_data(&digest);
+        match (&self.public_key, &self.signature) {
+            (SshPublicKey::Ed25519(pk), SshSignatureValue::Ed25519(sig)) => {
+                pk.verify(&signed_data, sig)
+            }
+            // ECDSA over P-256 signs the SHA-256 digest of the signed data, as Secp256r1PublicKey.
+            #[cfg(feature = "secp256r1")]
+            (SshPublicKey::EcdsaP256(pk), SshSignatureValue::EcdsaP256(sig)) => {
+                pk.verify(&signed_data, sig)
+            }
+            #[cfg(feature = "secp256r1")]
+            _ => Err(FastCryptoError::InvalidSignature),
+        }
+    }
+
+    /// The data which is signed for a message with the given digest.
+    fn signed_data(&self, digest: &[u8]) -> Vec<u8> {
+        let mut data = SSHSIG_MAGIC.to_vec();
+        put_string(&mut data, self.namespace.as_bytes());
+        put_string(&mut data, &self.reserved);
+        put_string(&mut data, self.hash_algorithm.name().as_bytes());
+        put_string(&mut data, digest);
+        data
+    }
+}
+
+/// Parse the signature blob of a signature by the given key.
+fn parse_signature(public_key: &SshPublicKey, bytes: &[u8]) -> FastCryptoResult<SshSignatureValue> {
+    let mut reader = WireReader(bytes);
+    let format = reader.string()?;
+    let blob = reader.string()?;
+    reader.finish()?;
+    match (public_key, format) {
+        (SshPublicKey::Ed25519(_), ED25519_KEY_TYPE) => Ok(SshSignatureValue::Ed25519(
+            Ed25519Signature::from_bytes(blob)?,
+        )),
+        #[cfg(feature = "secp256r1")]
+        (SshPublicKey::EcdsaP256(_), ECDSA_P256_KEY_TYPE) => {
+            let mut reader = WireReader(blob);
+            let r = parse_mpint(reader.string()?)?;
+            let s = parse_mpint(reader.string()?)?;
+            reader.finish()?;
+            let signature = Secp256r1Signature::from_bytes(&[r, s].concat())?;
+            // ssh-keygen does not normalize s, but Secp256r1PublicKey only accepts low s. Both s
+            // and -s give valid signatures, so we negate a high s.
+            match signature.sig.normalize_s() {
+                Some(normalized) => Ok(SshSignatureValue::EcdsaP256(
+                    Secp256r1Signature::from_bytes(&normalized.to_bytes())?,
+                )),
+                None => Ok(SshSignatureValue::EcdsaP256(signature)),
+            }
+        }
+        _ => Err(FastCryptoError::InvalidInput),
+    }
+}
+
+/// Parse a positive, minimally encoded mpint of at most 32 bytes into a 32 byte big-endian integer.
+#[cfg(feature = "secp256r1")]
+fn parse_mpint(bytes: &[u8]) -> FastCryptoResult<[u8; 32]> {
+    // The encoding is two's complement, so a leading zero is required iff the top bit is set.
+    let value = match bytes {
+        [0, rest @ ..] if rest.first().map_or(false, |b| b & 0x80 != 0) => rest,
+        [first, ..] if *first != 0 && first & 0x80 == 0 => bytes,
+        _ => return Err(FastCryptoError::InvalidInput),
+    };
+    if value.len() > 32 {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    let mut result = [0u8; 32];
+    result[32 - value.len()..].copy_from_slice(value);
+    Ok(result)
+}
+
+fn put_string(buffer: &mut Vec<u8>, bytes: &[u8]) {
+    buffer.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+    buffer.extend_from_slice(bytes);
+}
+
+/// A reader of the SSH wire encoding from RFC 4251.
+struct WireReader<'a>(&'a [u8]);
+
+impl<'a> WireReader<'a> {
+    fn take(&mut self, length: usize) -> FastCryptoResult<&'a [u8]> {
+        if self.0.len() < length {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let (taken, rest) = self.0.split_at(length);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    fn u32(&mut self) -> FastCryptoResult<u32> {
+        Ok(u32::from_be_bytes(
+            self.take(4)?.try_into().expect("Length is 4"),
+        ))
+    }
+
+    fn string(&mut self) -> FastCryptoResult<&'a [u8]> {
+        let length = self.u32()? as usize;
+        self.take(length)
+    }
+
+    /// Fail if there are bytes left.
+    fn finish(self) -> FastCryptoResult<()> {
+        if !self.0.is_empty() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::adaptor_signatures::ecdsa::EcdsaPreSignature;
+use crate::adaptor_signatures::schnorr::{SchnorrPreSignature, SchnorrSignature};
+use crate::groups::secp256k1::{Secp256k1Point, Secp256k1Scalar};
+use crate::groups::{GroupElement, Scalar};
+use crate::secp256k1::Secp256k1KeyPair;
+use crate::test_helpers::verify_serialization;
+use crate::traits::{KeyPair, VerifyingKey};
+use rand::{rngs::StdRng, SeedableRng as _};
+
+const MSG: &[u8] = b"test message";
+
+#[test]
+fn test_schnorr_adaptor() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let kp = Secp256k1KeyPair::generate(&mut rng);
+    let t = Secp256k1Scalar::rand(&mut rng);
+    let adaptor_point = Secp256k1Point::generator() * t;
+
+    let pre_signature = SchnorrPreSignature::new(&kp, MSG, &adaptor_point, &mut rng);
+    assert!(pre_signature
+        .verify(kp.public(), MSG, &adaptor_point)
+        .is_ok());
+    assert!(pre_signature
+        .verify(kp.public(), b"other message", &adaptor_point)
+        .is_err());
+    assert!(pre_signature
+        .verify(kp.public(), MSG, &Secp256k1Point::generator())
+        .is_err());
+
+    let signature = pre_signature.adapt(&t);
+    assert!(signature.verify(kp.public(), MSG).is_ok());
+    assert_eq!(
+        pre_signature.extract(&signature, &adaptor_point).unwrap(),
+        t
+    );
+
+    // Adapting with the wrong secret gives an invalid signature.
+    let wrong = pre_signature.adapt(&(t + Secp256k1Scalar::generator()));
+    assert!(wrong.verify(kp.public(), MSG).is_err());
+    assert!(pre_signature.extract(&wrong, &adaptor_point).is_err());
+
+    let other = Secp256k1KeyPair::generate(&mut rng);
+    let signature = SchnorrSignature::new(&kp, MSG, &mut rng);
+    assert!(signature.verify(kp.public(), MSG).is_ok());
+    assert!(signature.verify(other.public(), MSG).is_err());
+
+    verify_serialization(&pre_signature, None);
+    verify_serialization(&signature, None);
+}
+
+#[test]
+fn test_ecdsa_adaptor() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let kp = Secp256k1KeyPair::generate(&mut rng);
+
+    // Repeat to exercise both the high and low s cases in adapt and extract.
+    for _ in 0..8 {
+        let t = Secp256k1Scalar::rand(&mut rng);
+        let adaptor_point = Secp256k1Point::generator() * t;
+
+        let pre_signature = EcdsaPreSignature::new(&kp, MSG, &adaptor_point, &mut rng);
+        assert!(pre_signature
+            .verify(kp.public(), MSG, &adaptor_point)
+            .is_ok());
+        assert!(pre_signature
+            .verify(kp.public(), b"other message", &adaptor_point)
+            .is_err());
+        assert!(pre_signature
+            .verify(kp.public(), MSG, &Secp256k1Point::generator())
+            .is_err());
+
+        let signature = pre_signature.adapt(&t).unwrap();
+        assert!(kp.public().verify(MSG, &signature).is_ok());
+        assert_eq!(
+            pre_signature.extract(&signature, &adaptor_point).unwrap(),
+            t
+        );
+
+        let wrong = pre_signature
+            .adapt(&(t + Secp256k1Scalar::generator()))
+            .unwrap();
+        assert!(kp.public().verify(MSG, &wrong).is_err());
+        assert!(pre_signature.extract(&wrong, &adaptor_point).is_err());
+
+        verify_serialization(&pre_signature, None);
+    }
+}
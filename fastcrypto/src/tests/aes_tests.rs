@@ -4,7 +4,8 @@ use crate::aes::{AuthenticatedCipher, Cipher};
 use crate::{
     aes::{
         Aes128CbcPkcs7, Aes128Ctr, Aes128Gcm, Aes192Ctr, Aes256CbcPkcs7, Aes256Ctr, Aes256Gcm,
-        AesKey, GenericByteArray, InitializationVector,
+        Aes256GcmSiv, AesKey, GenericByteArray, InitializationVector, KeyCommittingAes256Gcm,
+        StreamDecryptor, StreamEncryptor, StreamNoncePrefix, KEY_COMMITMENT_LENGTH,
     },
     error::FastCryptoError,
     traits::{Generate, ToFromBytes},
@@ -216,3 +217,129 @@ fn test_sk_zeroization_on_drop() {
         }
     }
 }
+
+#[test]
+fn test_stream_encryption() {
+    let mut rng = StdRng::from_seed([9; 32]);
+    let key = AesKey::<U32>::generate(&mut rng);
+    let prefix = StreamNoncePrefix::generate(&mut rng);
+    let chunks: Vec<Vec<u8>> = (0..5u8).map(|i| vec![i; 100 + i as usize]).collect();
+
+    let mut encryptor = StreamEncryptor::new(Aes256Gcm::<U12>::new(key.clone()), &prefix);
+    let mut ciphertexts = chunks[..4]
+        .iter()
+        .map(|chunk| encryptor.encrypt_next(b"header", chunk).unwrap())
+        .collect::<Vec<_>>();
+    ciphertexts.push(encryptor.encrypt_last(b"header", &chunks[4]).unwrap());
+
+    let decryptor = || StreamDecryptor::new(Aes256Gcm::<U12>::new(key.clone()), &prefix);
+
+    // Decryption in order succeeds.
+    let mut d = decryptor();
+    for (c, p) in ciphertexts[..4].iter().zip(&chunks) {
+        assert_eq!(&d.decrypt_next(b"header", c).unwrap(), p);
+    }
+    assert_eq!(
+        d.decrypt_last(b"header", &ciphertexts[4]).unwrap(),
+        chunks[4]
+    );
+
+    // Reordered chunks are rejected.
+    let mut d = decryptor();
+    assert!(d.decrypt_next(b"header", &ciphertexts[1]).is_err());
+
+    // A truncated stream is rejected.
+    let mut d = decryptor();
+    for c in &ciphertexts[..2] {
+        d.decrypt_next(b"header", c).unwrap();
+    }
+    assert!(d.decrypt_last(b"header", &ciphertexts[2]).is_err());
+
+    // The last chunk cannot be decrypted as an intermediate chunk.
+    let mut d = decryptor();
+    for c in &ciphertexts[..4] {
+        d.decrypt_next(b"header", c).unwrap();
+    }
+    assert!(d.decrypt_next(b"header", &ciphertexts[4]).is_err());
+
+    // Wrong associated data or prefix is rejected.
+    assert!(decryptor().decrypt_next(b"other", &ciphertexts[0]).is_err());
+    let other_prefix = StreamNoncePrefix::generate(&mut rng);
+    let mut d = StreamDecryptor::new(Aes256Gcm::<U12>::new(key), &other_prefix);
+    assert!(d.decrypt_next(b"header", &ciphertexts[0]).is_err());
+}
+
+#[test]
+fn test_key_committing_aes_gcm() {
+    let mut rng = StdRng::from_seed([9; 32]);
+    let key = AesKey::<U32>::generate(&mut rng);
+    let other_key = AesKey::<U32>::generate(&mut rng);
+    let iv = InitializationVector::<U12>::generate(&mut rng);
+    let cipher = KeyCommittingAes256Gcm::<U12>::new(key.clone());
+    let other_cipher = KeyCommittingAes256Gcm::<U12>::new(other_key);
+
+    let ciphertext = cipher.encrypt_authenticated(&iv, b"aad", b"Hello, world!");
+    assert_eq!(ciphertext.len(), KEY_COMMITMENT_LENGTH + 13 + 16);
+    assert_eq!(
+        cipher
+            .decrypt_authenticated(&iv, b"aad", &ciphertext)
+            .unwrap(),
+        b"Hello, world!"
+    );
+
+    // The ciphertext is not valid under another key, nor with another aad.
+    assert!(other_cipher
+        .decrypt_authenticated(&iv, b"aad", &ciphertext)
+        .is_err());
+    assert!(cipher
+        .decrypt_authenticated(&iv, b"other", &ciphertext)
+        .is_err());
+
+    // Replacing the commitment is detected.
+    let other_ciphertext = other_cipher.encrypt_authenticated(&iv, b"aad", b"Hello, world!");
+    let mut modified = other_ciphertext[..KEY_COMMITMENT_LENGTH].to_vec();
+    modified.extend_from_slice(&ciphertext[KEY_COMMITMENT_LENGTH..]);
+    assert!(cipher
+        .decrypt_authenticated(&iv, b"aad", &modified)
+        .is_err());
+
+    // The ciphertext differs from plain AES-GCM under the same key.
+    let plain = Aes256Gcm::<U12>::new(key).encrypt_authenticated(&iv, b"aad", b"Hello, world!");
+    assert_ne!(plain, ciphertext[KEY_COMMITMENT_LENGTH..]);
+
+    assert!(cipher
+        .decrypt_authenticated(&iv, b"aad", &[0u8; 10])
+        .is_err());
+}
+
+#[test]
+fn test_aes256gcmsiv_encrypt_and_decrypt() {
+    test_cipher::<U32, U12, _, _>(Aes256GcmSiv::new);
+}
+
+#[test]
+fn test_aes256gcmsiv_regression() {
+    // Test vector from RFC 8452, Appendix C.2.
+    let key = AesKey::<U32>::from_bytes(
+        &hex::decode("0100000000000000000000000000000000000000000000000000000000000000").unwrap(),
+    )
+    .unwrap();
+    let iv =
+        InitializationVector::<U12>::from_bytes(&hex::decode("030000000000000000000000").unwrap())
+            .unwrap();
+    let cipher = Aes256GcmSiv::new(key);
+    let ciphertext = cipher.encrypt_authenticated(&iv, b"", b"");
+    assert_eq!(hex::encode(&ciphertext), "07f5f4169bbf55a8400cd47ea6fd400f");
+
+    // Reusing a nonce gives the same ciphertext for the same message, but different ciphertexts
+    // for different messages, all of which decrypt correctly.
+    let c1 = cipher.encrypt_authenticated(&iv, b"aad", b"message 1");
+    let c2 = cipher.encrypt_authenticated(&iv, b"aad", b"message 2");
+    assert_eq!(c1, cipher.encrypt_authenticated(&iv, b"aad", b"message 1"));
+    assert_ne!(c1, c2);
+    assert_eq!(
+        cipher.decrypt_authenticated(&iv, b"aad", &c2).unwrap(),
+        b"message 2"
+    );
+    assert!(cipher.decrypt_authenticated(&iv, b"", &c2).is_err());
+}
@@ -0,0 +1,109 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::aggregate_verification::*;
+use crate::bls12381::{min_pk, min_sig};
+use crate::ed25519::{Ed25519AggregateSignature, Ed25519KeyPair};
+use crate::error::FastCryptoError;
+use crate::traits::{AggregateAuthenticator, KeyPair, Signer};
+use rand::{rngs::StdRng, SeedableRng};
+
+const MESSAGE: &[u8] = b"block";
+
+fn setup<K: KeyPair>(n: usize) -> (Vec<K::PubKey>, Vec<K::Sig>, Vec<K>) {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let key_pairs = (0..n).map(|_| K::generate(&mut rng)).collect::<Vec<_>>();
+    let public_keys = key_pairs.iter().map(|kp| kp.public().clone()).collect();
+    let signatures = key_pairs.iter().map(|kp| kp.sign(MESSAGE)).collect();
+    (public_keys, signatures, key_pairs)
+}
+
+fn test_verify_in_chunks<K, A>()
+where
+    K: KeyPair,
+    A: AggregateAuthenticator<Sig = K::Sig, PubKey = K::PubKey, PrivKey = K::PrivKey>,
+{
+    let mut rng = StdRng::from_seed([1; 32]);
+    let (public_keys, mut signatures, key_pairs) = setup::<K>(20);
+
+    for chunk_size in [1, 3, 20, 100] {
+        assert_eq!(
+            verify_in_chunks::<A, _>(&public_keys, MESSAGE, &signatures, chunk_size, &mut rng),
+            Ok(ChunkedVerification::Valid)
+        );
+    }
+
+    // A single invalid signature is always found.
+    signatures[13] = key_pairs[13].sign(b"other block");
+    for chunk_size in [1, 3, 20, 100] {
+        match verify_in_chunks::<A, _>(&public_keys, MESSAGE, &signatures, chunk_size, &mut rng)
+            .unwrap()
+        {
+            ChunkedVerification::Invalid { index, chunk } => {
+                assert_eq!(index, 13);
+                assert!(chunk.contains(&13));
+                assert!(chunk.len() <= chunk_size);
+            }
+            ChunkedVerification::Valid => panic!("Invalid signature not found"),
+        }
+    }
+
+    // A signature by another signer.
+    signatures[13] = key_pairs[13].sign(MESSAGE);
+    signatures[2] = key_pairs[3].sign(MESSAGE);
+    match verify_in_chunks::<A, _>(&public_keys, MESSAGE, &signatures, 4, &mut rng).unwrap() {
+        ChunkedVerification::Invalid { index, .. } => assert_eq!(index, 2),
+        ChunkedVerification::Valid => panic!("Invalid signature not found"),
+    }
+
+    // With several invalid signatures, one of them is found.
+    signatures[17] = key_pairs[17].sign(b"other block");
+    match verify_in_chunks::<A, _>(&public_keys, MESSAGE, &signatures, 4, &mut rng).unwrap() {
+        ChunkedVerification::Invalid { index, chunk } => {
+            assert!(index == 2 || index == 17);
+            assert!(chunk.contains(&index));
+        }
+        ChunkedVerification::Valid => panic!("Invalid signature not found"),
+    }
+}
+
+#[test]
+fn test_verify_in_chunks_bls12381_min_sig() {
+    test_verify_in_chunks::<min_sig::BLS12381KeyPair, min_sig::BLS12381AggregateSignature>();
+}
+
+#[test]
+fn test_verify_in_chunks_bls12381_min_pk() {
+    test_verify_in_chunks::<min_pk::BLS12381KeyPair, min_pk::BLS12381AggregateSignature>();
+}
+
+#[test]
+fn test_verify_in_chunks_ed25519() {
+    test_verify_in_chunks::<Ed25519KeyPair, Ed25519AggregateSignature>();
+}
+
+#[test]
+fn test_invalid_inputs() {
+    let mut rng = StdRng::from_seed([1; 32]);
+    let (public_keys, signatures, _) = setup::<min_sig::BLS12381KeyPair>(5);
+    assert_eq!(
+        verify_in_chunks::<min_sig::BLS12381AggregateSignature, _>(
+            &public_keys[..4],
+            MESSAGE,
+            &signatures,
+            2,
+            &mut rng
+        ),
+        Err(FastCryptoError::InvalidInput)
+    );
+    assert_eq!(
+        verify_in_chunks::<min_sig::BLS12381AggregateSignature, _>(
+            &public_keys,
+            MESSAGE,
+            &signatures,
+            0,
+            &mut rng
+        ),
+        Err(FastCryptoError::InvalidInput)
+    );
+}
@@ -0,0 +1,63 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::batch_hash::{blake3_many, sha256_many, BLAKE3_PARALLEL_THRESHOLD, SHA256_LANES};
+use crate::hash::{Blake3, HashFunction, Sha256};
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+
+fn random_messages(lengths: impl Iterator<Item = usize>) -> Vec<Vec<u8>> {
+    let mut rng = StdRng::from_seed([0; 32]);
+    lengths
+        .map(|length| {
+            let mut message = vec![0u8; length];
+            rng.fill_bytes(&mut message);
+            message
+        })
+        .collect()
+}
+
+#[test]
+fn test_sha256_many() {
+    // All lengths around the block boundaries, so groups contain messages of different lengths.
+    let messages = random_messages(0..300);
+    let digests = sha256_many(&messages);
+    assert_eq!(digests.len(), messages.len());
+    for (message, digest) in messages.iter().zip(digests) {
+        assert_eq!(Sha256::digest(message), digest);
+    }
+
+    // Groups which are not full.
+    for n in 1..=SHA256_LANES {
+        let messages = random_messages((0..n).map(|i| 100 * i));
+        assert_eq!(
+            sha256_many(&messages),
+            messages.iter().map(Sha256::digest).collect::<Vec<_>>()
+        );
+    }
+
+    assert!(sha256_many::<&[u8]>(&[]).is_empty());
+}
+
+#[test]
+fn test_sha256_many_test_vector() {
+    let digests = sha256_many(&[b"abc".as_slice(), b"".as_slice()]);
+    assert_eq!(
+        hex::encode(digests[0].digest),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_eq!(
+        hex::encode(digests[1].digest),
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+}
+
+#[test]
+fn test_blake3_many() {
+    for n in [10, 2 * BLAKE3_PARALLEL_THRESHOLD + 1] {
+        let messages = random_messages((0..n).map(|i| i % 100));
+        assert_eq!(
+            blake3_many(&messages),
+            messages.iter().map(Blake3::digest).collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,173 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::bbs_plus::*;
+use crate::groups::bls12381::Scalar;
+use rand::{rngs::StdRng, SeedableRng};
+
+fn messages() -> Vec<Scalar> {
+    [b"name".as_slice(), b"date of birth", b"country", b"expiry"]
+        .iter()
+        .map(|m| message(m))
+        .collect()
+}
+
+#[test]
+fn test_sign_and_verify() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let parameters = PublicParameters::new(4);
+    let (private_key, public_key) = generate_keypair(&mut rng);
+    assert_eq!(public_key, PublicKey::from(&private_key));
+    let messages = messages();
+
+    let signature = private_key.sign(&parameters, &messages, &mut rng).unwrap();
+    assert!(public_key
+        .verify(&parameters, &messages, &signature)
+        .is_ok());
+
+    // Other messages, another key or the wrong number of messages.
+    let mut other_messages = messages.clone();
+    other_messages.swap(0, 1);
+    assert!(public_key
+        .verify(&parameters, &other_messages, &signature)
+        .is_err());
+    let (_, other_public_key) = generate_keypair(&mut rng);
+    assert!(other_public_key
+        .verify(&parameters, &messages, &signature)
+        .is_err());
+    assert!(private_key
+        .sign(&parameters, &messages[1..], &mut rng)
+        .is_err());
+    assert!(public_key
+        .verify(&PublicParameters::new(3), &messages[1..], &signature)
+        .is_err());
+
+    let bytes = bincode::serialize(&signature).unwrap();
+    assert_eq!(signature, bincode::deserialize(&bytes).unwrap());
+}
+
+#[test]
+fn test_presentation() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let parameters = PublicParameters::new(4);
+    let (private_key, public_key) = generate_keypair(&mut rng);
+    let messages = messages();
+    let signature = private_key.sign(&parameters, &messages, &mut rng).unwrap();
+
+    for disclosed in [vec![], vec![2], vec![0, 3], vec![0, 1, 2, 3]] {
+        let presentation = signature
+            .present(
+                &parameters,
+                &public_key,
+                &messages,
+                &disclosed,
+                b"nonce",
+                &mut rng,
+            )
+            .unwrap();
+        let disclosed_messages = disclosed
+            .iter()
+            .map(|i| (*i, messages[*i]))
+            .collect::<Vec<_>>();
+        assert!(presentation
+            .verify(&parameters, &public_key, &disclosed_messages, b"nonce")
+            .is_ok());
+        assert!(presentation
+            .verify(
+                &parameters,
+                &public_key,
+                &disclosed_messages,
+                b"other nonce"
+            )
+            .is_err());
+
+        let bytes = bincode::serialize(&presentation).unwrap();
+        assert_eq!(presentation, bincode::deserialize(&bytes).unwrap());
+    }
+
+    let presentation = signature
+        .present(
+            &parameters,
+            &public_key,
+            &messages,
+            &[1, 2],
+            b"nonce",
+            &mut rng,
+        )
+        .unwrap();
+
+    // Another disclosed message, set of disclosed indices or public key.
+    assert!(presentation
+        .verify(
+            &parameters,
+            &public_key,
+            &[(1, messages[1]), (2, message(b"other country"))],
+            b"nonce"
+        )
+        .is_err());
+    assert!(presentation
+        .verify(
+            &parameters,
+            &public_key,
+            &[(1, messages[1]), (3, messages[3])],
+            b"nonce"
+        )
+        .is_err());
+    assert!(presentation
+        .verify(&parameters, &public_key, &[(1, messages[1])], b"nonce")
+        .is_err());
+    let (_, other_public_key) = generate_keypair(&mut rng);
+    assert!(presentation
+        .verify(
+            &parameters,
+            &other_public_key,
+            &[(1, messages[1]), (2, messages[2])],
+            b"nonce"
+        )
+        .is_err());
+
+    // Presentations are unlinkable, so two presentations differ.
+    let other_presentation = signature
+        .present(
+            &parameters,
+            &public_key,
+            &messages,
+            &[1, 2],
+            b"nonce",
+            &mut rng,
+        )
+        .unwrap();
+    assert_ne!(presentation, other_presentation);
+
+    // Disclosed indices must be increasing and in range.
+    assert!(signature
+        .present(
+            &parameters,
+            &public_key,
+            &messages,
+            &[2, 1],
+            b"nonce",
+            &mut rng
+        )
+        .is_err());
+    assert!(signature
+        .present(
+            &parameters,
+            &public_key,
+            &messages,
+            &[1, 1],
+            b"nonce",
+            &mut rng
+        )
+        .is_err());
+    assert!(signature
+        .present(
+            &parameters,
+            &public_key,
+            &messages,
+            &[4],
+            b"nonce",
+            &mut rng
+        )
+        .is_err());
+}
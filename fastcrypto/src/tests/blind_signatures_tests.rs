@@ -0,0 +1,66 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::blind_signatures::bls::{blind, blind_sign};
+use crate::blind_signatures::schnorr::{PrivateKey, PublicKey, SigningNonce, UnblindingState};
+use crate::bls12381::min_sig::BLS12381KeyPair;
+use crate::test_helpers::verify_serialization;
+use crate::traits::{KeyPair, VerifyingKey};
+use rand::{rngs::StdRng, SeedableRng as _};
+
+const MSG: &[u8] = b"test message";
+
+#[test]
+fn test_blind_bls() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let kp = BLS12381KeyPair::generate(&mut rng);
+    let other = BLS12381KeyPair::generate(&mut rng);
+    let public_key = kp.public().clone();
+
+    let (blinded_message, blinding_factor) = blind(MSG, &mut rng);
+    let blind_signature = blind_sign(&kp.private(), &blinded_message);
+    assert!(blind_signature
+        .verify(&public_key, &blinded_message)
+        .is_ok());
+    assert!(blind_signature
+        .verify(other.public(), &blinded_message)
+        .is_err());
+
+    let signature = blind_signature.unblind(&blinding_factor).unwrap();
+    assert!(public_key.verify(MSG, &signature).is_ok());
+    assert!(public_key.verify(b"other message", &signature).is_err());
+
+    // Unblinding with the wrong factor gives an invalid signature.
+    let (_, wrong_factor) = blind(MSG, &mut rng);
+    let wrong = blind_signature.unblind(&wrong_factor).unwrap();
+    assert!(public_key.verify(MSG, &wrong).is_err());
+
+    verify_serialization(&blinded_message, None);
+    verify_serialization(&blind_signature, None);
+}
+
+#[test]
+fn test_blind_schnorr() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let private_key = PrivateKey::generate(&mut rng);
+    let public_key = PublicKey::from(&private_key);
+
+    let (nonce, commitment) = SigningNonce::new(&mut rng);
+    let (state, challenge) = UnblindingState::new(&public_key, &commitment, MSG, &mut rng);
+    let response = nonce.sign(&private_key, &challenge);
+    let signature = state.unblind(&public_key, &response).unwrap();
+
+    assert!(signature.verify(&public_key, MSG).is_ok());
+    assert!(signature.verify(&public_key, b"other message").is_err());
+    let other = PublicKey::from(&PrivateKey::generate(&mut rng));
+    assert!(signature.verify(&other, MSG).is_err());
+
+    // A response from a different key is rejected when unblinding.
+    let (nonce, commitment) = SigningNonce::new(&mut rng);
+    let (state, challenge) = UnblindingState::new(&public_key, &commitment, MSG, &mut rng);
+    let response = nonce.sign(&PrivateKey::generate(&mut rng), &challenge);
+    assert!(state.unblind(&public_key, &response).is_err());
+
+    verify_serialization(&commitment, None);
+    verify_serialization(&signature, None);
+}
@@ -5,7 +5,9 @@ use crate::bls12381::min_pk::{BLS12381KeyPair, BLS12381Signature};
 use crate::groups::bls12381::{
     G1Element, G2Element, GTElement, Scalar, G1_ELEMENT_BYTE_LENGTH, G2_ELEMENT_BYTE_LENGTH,
 };
-use crate::groups::{GroupElement, HashToGroupElement, Pairing};
+use crate::groups::{
+    FixedBaseMultiplication, GroupElement, HashToGroupElement, Pairing, Scalar as ScalarTrait,
+};
 use crate::traits::Signer;
 use crate::traits::VerifyingKey;
 use crate::traits::{KeyPair, ToFromBytes};
@@ -43,6 +45,32 @@ fn test_g1_arithmetic() {
     assert_eq!(G1Element::zero(), g - g);
 }
 
+fn check_mul_generator<G: FixedBaseMultiplication<ScalarType = Scalar>>() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let scalars = [
+        Scalar::zero(),
+        Scalar::generator(),
+        Scalar::from(8),
+        Scalar::from(u64::MAX),
+        -Scalar::generator(),
+        Scalar::rand(&mut rng),
+        Scalar::rand(&mut rng),
+    ];
+    for s in scalars {
+        assert_eq!(G::mul_generator(&s), G::generator() * s);
+    }
+}
+
+#[test]
+fn test_g1_mul_generator() {
+    check_mul_generator::<G1Element>();
+}
+
+#[test]
+fn test_g2_mul_generator() {
+    check_mul_generator::<G2Element>();
+}
+
 #[test]
 fn test_g2_arithmetic() {
     // Test that different ways of computing [5]G gives the expected result
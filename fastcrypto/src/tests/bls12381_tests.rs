@@ -8,6 +8,7 @@ use crate::traits::Signer;
 use crate::{
     bls12381::{BLS_G1_LENGTH, BLS_G2_LENGTH, BLS_PRIVATE_KEY_LENGTH},
     encoding::Base64,
+    error::FastCryptoError,
     hash::{HashFunction, Sha256, Sha3_256},
     hmac::hkdf_generate_from_ikm,
     traits::{
@@ -799,6 +800,146 @@ proptest! {
     }
 
 }
+
+#[test]
+fn test_eip2333() {
+    // Test cases 0 and 1 from EIP-2333.
+    let test_cases = [
+        (
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+            "6083874454709270928345386274498605044986640685124978867557563392430687146096",
+            0,
+            "20397789859736650942317412262472558107875392172444076792671091975210932703118",
+        ),
+        (
+            "3141592653589793238462643383279502884197169399375105820974944592",
+            "29757020647961307431480504535336562678282505419141012933316116377660817309383",
+            3141592653,
+            "25457201688850691947727629385191704516744796114925897962676248250929345014287",
+        ),
+    ];
+    let to_bytes = |decimal: &str| {
+        let bytes = num_bigint::BigUint::parse_bytes(decimal.as_bytes(), 10).unwrap().to_bytes_be();
+        let mut padded = vec![0u8; BLS_PRIVATE_KEY_LENGTH - bytes.len()];
+        padded.extend_from_slice(&bytes);
+        padded
+    };
+    for (seed, master, index, child) in test_cases {
+        let seed = hex::decode(seed).unwrap();
+        let master_sk = BLS12381PrivateKey::derive_master_eip2333(&seed).unwrap();
+        assert_eq!(master_sk.as_bytes(), to_bytes(master));
+        let child_sk = master_sk.derive_child_eip2333(index);
+        assert_eq!(child_sk.as_bytes(), to_bytes(child));
+        assert_eq!(
+            BLS12381PrivateKey::derive_eip2333_path(&seed, &format!("m/{}", index)).unwrap(),
+            child_sk
+        );
+    }
+}
+
+#[test]
+fn test_eip2333_path() {
+    let seed = [7u8; 32];
+    let key = BLS12381PrivateKey::derive_eip2333_path(&seed, "m/12381/3600/0/0/0").unwrap();
+    let expected = [12381, 3600, 0, 0, 0]
+        .into_iter()
+        .fold(BLS12381PrivateKey::derive_master_eip2333(&seed).unwrap(), |key, index| {
+            key.derive_child_eip2333(index)
+        });
+    assert_eq!(key, expected);
+    assert_eq!(
+        BLS12381PrivateKey::derive_eip2333_path(&seed, "m").unwrap(),
+        BLS12381PrivateKey::derive_master_eip2333(&seed).unwrap()
+    );
+
+    // Keys derived from different paths are different and can be used for signing.
+    let other = BLS12381PrivateKey::derive_eip2333_path(&seed, "m/12381/3600/1/0/0").unwrap();
+    assert_ne!(key, other);
+    let kp = BLS12381KeyPair::from(other);
+    assert!(kp.public().verify(b"message", &kp.sign(b"message")).is_ok());
+
+    assert!(BLS12381PrivateKey::derive_eip2333_path(&seed, "12381/3600").is_err());
+    assert!(BLS12381PrivateKey::derive_eip2333_path(&seed, "m/12381/").is_err());
+    assert!(BLS12381PrivateKey::derive_eip2333_path(&seed, "m/-1").is_err());
+    assert!(BLS12381PrivateKey::derive_eip2333_path(&seed, "m/4294967296").is_err());
+    assert!(BLS12381PrivateKey::derive_master_eip2333(&[7u8; 31]).is_err());
+}
+
+#[test]
+fn test_batch_verify_with_transcript() {
+    let (msg1, msg2, pks1, pks2, sig1, sig2) = verify_batch_aggregate_signature_inputs();
+    let sigs = [&sig1, &sig2];
+    let pks: [&[BLS12381PublicKey]; 2] = [&pks1, &pks2];
+    let msgs: [&[u8]; 2] = [&msg1, &msg2];
+    let mut rng = StdRng::from_seed([0; 32]);
+
+    let transcript =
+        BLS12381AggregateSignature::batch_verify_with_transcript(&sigs, &pks, &msgs, &mut rng)
+            .unwrap();
+    assert!(transcript.valid);
+    assert!(BLS12381AggregateSignature::check_transcript(&transcript, &sigs, &pks, &msgs).is_ok());
+    let serialized = bincode::serialize(&transcript).unwrap();
+    assert_eq!(
+        bincode::deserialize::<crate::bls12381::VerificationTranscript>(&serialized).unwrap(),
+        transcript
+    );
+
+    // A transcript claiming another result is rejected.
+    let mut wrong = transcript.clone();
+    wrong.valid = false;
+    assert_eq!(
+        BLS12381AggregateSignature::check_transcript(&wrong, &sigs, &pks, &msgs),
+        Err(FastCryptoError::InvalidProof)
+    );
+
+    // A transcript of other inputs is rejected.
+    let other_msgs: [&[u8]; 2] = [&msg2, &msg1];
+    assert_eq!(
+        BLS12381AggregateSignature::check_transcript(&transcript, &sigs, &pks, &other_msgs),
+        Err(FastCryptoError::InvalidInput)
+    );
+
+    // An invalid batch gives a transcript which can be checked too.
+    let invalid_sigs = [&sig2, &sig1];
+    let transcript = BLS12381AggregateSignature::batch_verify_with_transcript(
+        &invalid_sigs,
+        &pks,
+        &msgs,
+        &mut rng,
+    )
+    .unwrap();
+    assert!(!transcript.valid);
+    assert!(
+        BLS12381AggregateSignature::check_transcript(&transcript, &invalid_sigs, &pks, &msgs)
+            .is_ok()
+    );
+
+    // A single aggregate signature is a batch of size one.
+    let transcript = BLS12381AggregateSignature::batch_verify_with_transcript(
+        &[&sig1],
+        &[pks1.as_slice()],
+        &[msg1.as_slice()],
+        &mut rng,
+    )
+    .unwrap();
+    assert!(transcript.valid);
+
+    // Invalid inputs.
+    assert!(
+        BLS12381AggregateSignature::batch_verify_with_transcript(&sigs, &pks[..1], &msgs, &mut rng)
+            .is_err()
+    );
+    assert!(BLS12381AggregateSignature::batch_verify_with_transcript(
+        &[&sig1],
+        &[&[]],
+        &[msg1.as_slice()],
+        &mut rng
+    )
+    .is_err());
+    assert!(
+        BLS12381AggregateSignature::batch_verify_with_transcript(&[], &[], &[], &mut rng).is_err()
+    );
+}
 }} // macro_rules! define_tests
 
 pub mod min_sig {
@@ -153,3 +153,84 @@ proptest::proptest! {
         assert!(range_proof.verify_bit_length(&commitment, upper_bound, TEST_DOMAIN).is_ok());
     }
 }
+
+#[test]
+fn test_aggregated_range_proof() {
+    let values = [0u64, 1, 255, 256, u32::MAX as u64, 7, 8, 9];
+    let blindings = (0..8u8).map(|i| [i; 32]).collect::<Vec<_>>();
+
+    let (commitments, range_proof) =
+        BulletproofsRangeProof::prove_bit_length_aggregated(&values, &blindings, 32, TEST_DOMAIN)
+            .unwrap();
+    assert!(range_proof
+        .verify_bit_length_aggregated(&commitments, 32, TEST_DOMAIN)
+        .is_ok());
+
+    // The commitments are ordinary Pedersen commitments to the values.
+    for ((value, blinding), commitment) in values.iter().zip(&blindings).zip(&commitments) {
+        let mut value_bytes = [0u8; 32];
+        value_bytes[..8].copy_from_slice(&value.to_le_bytes());
+        assert_eq!(&PedersenCommitment::new(value_bytes, *blinding), commitment);
+    }
+
+    // Reordered or missing commitments, another bit length or another domain.
+    let mut reordered = commitments.clone();
+    reordered.swap(0, 1);
+    assert!(range_proof
+        .verify_bit_length_aggregated(&reordered, 32, TEST_DOMAIN)
+        .is_err());
+    assert!(range_proof
+        .verify_bit_length_aggregated(&commitments[..4], 32, TEST_DOMAIN)
+        .is_err());
+    assert!(range_proof
+        .verify_bit_length_aggregated(&commitments, 16, TEST_DOMAIN)
+        .is_err());
+    assert!(range_proof
+        .verify_bit_length_aggregated(&commitments, 32, b"OTHER")
+        .is_err());
+
+    // Values out of range cannot be proven.
+    if let Ok((commitments, range_proof)) =
+        BulletproofsRangeProof::prove_bit_length_aggregated(&values, &blindings, 8, TEST_DOMAIN)
+    {
+        assert!(range_proof
+            .verify_bit_length_aggregated(&commitments, 8, TEST_DOMAIN)
+            .is_err());
+    }
+
+    // The number of values must be a power of two and match the number of blindings.
+    assert!(BulletproofsRangeProof::prove_bit_length_aggregated(
+        &values[..3],
+        &blindings[..3],
+        32,
+        TEST_DOMAIN
+    )
+    .is_err());
+    assert!(
+        BulletproofsRangeProof::prove_bit_length_aggregated(&[], &[], 32, TEST_DOMAIN).is_err()
+    );
+    assert!(BulletproofsRangeProof::prove_bit_length_aggregated(
+        &values[..4],
+        &blindings[..2],
+        32,
+        TEST_DOMAIN
+    )
+    .is_err());
+}
+
+#[test]
+fn test_range_proof_serde() {
+    let (commitments, range_proof) = BulletproofsRangeProof::prove_bit_length_aggregated(
+        &[1, 2],
+        &[[1; 32], [2; 32]],
+        64,
+        TEST_DOMAIN,
+    )
+    .unwrap();
+    let ser = bincode::serialize(&range_proof).unwrap();
+    let range_proof_dup: BulletproofsRangeProof = bincode::deserialize(&ser).unwrap();
+    assert_eq!(range_proof.as_bytes(), range_proof_dup.as_bytes());
+    assert!(range_proof_dup
+        .verify_bit_length_aggregated(&commitments, 64, TEST_DOMAIN)
+        .is_ok());
+}
@@ -0,0 +1,122 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::bls12381::{min_pk, min_sig};
+use crate::certificate_codec::*;
+use crate::traits::{AggregateAuthenticator, Authenticator, KeyPair, Signer, ToFromBytes};
+use rand::{rngs::StdRng, SeedableRng};
+
+const MESSAGE: &[u8] = b"block";
+
+fn test_certificate<K, A>()
+where
+    K: KeyPair,
+    A: AggregateAuthenticator<Sig = K::Sig, PubKey = K::PubKey, PrivKey = K::PrivKey>
+        + ToFromBytes
+        + PartialEq
+        + std::fmt::Debug,
+{
+    let mut rng = StdRng::from_seed([0; 32]);
+    let committee = (0..20).map(|_| K::generate(&mut rng)).collect::<Vec<_>>();
+    let public_keys = committee
+        .iter()
+        .map(|kp| kp.public().clone())
+        .collect::<Vec<_>>();
+
+    // Sparse and dense signer sets use different containers.
+    for signers in [vec![3, 17], (0..20).filter(|i| i % 7 != 0).collect()] {
+        let votes = signers
+            .iter()
+            .map(|i| (*i, committee[*i].sign(MESSAGE)))
+            .collect::<Vec<_>>();
+        let certificate = Certificate::<A>::from_signatures(20, &votes).unwrap();
+        assert!(certificate.verify(&public_keys, MESSAGE).is_ok());
+        assert!(certificate.verify(&public_keys, b"other block").is_err());
+        assert!(certificate.verify(&public_keys[1..], MESSAGE).is_err());
+
+        let bytes = certificate.to_bytes();
+        assert!(bytes.len() <= <A::Sig as Authenticator>::LENGTH + 4);
+        let parsed = Certificate::<A>::from_bytes(&bytes, 20).unwrap();
+        assert_eq!(parsed, certificate);
+        assert_eq!(parsed.signers.signers(), signers.as_slice());
+        assert!(Certificate::<A>::from_bytes(&bytes, 10).is_err());
+        assert!(Certificate::<A>::from_bytes(&bytes[..bytes.len() - 1], 20).is_err());
+    }
+
+    // A certificate with other signers does not verify.
+    let votes = [(1, committee[1].sign(MESSAGE))];
+    let mut certificate = Certificate::<A>::from_signatures(20, &votes).unwrap();
+    certificate.signers = SignerSet::new(20, &[2]).unwrap();
+    assert!(certificate.verify(&public_keys, MESSAGE).is_err());
+
+    // Duplicate and out of range signers.
+    let vote = committee[1].sign(MESSAGE);
+    assert!(
+        Certificate::<A>::from_signatures(20, &[(1, vote.clone()), (1, vote.clone())]).is_err()
+    );
+    assert!(Certificate::<A>::from_signatures(20, &[(20, vote)]).is_err());
+}
+
+#[test]
+fn test_certificate_bls12381() {
+    test_certificate::<min_sig::BLS12381KeyPair, min_sig::BLS12381AggregateSignature>();
+    test_certificate::<min_pk::BLS12381KeyPair, min_pk::BLS12381AggregateSignature>();
+}
+
+#[test]
+fn test_signer_set_encoding() {
+    // Few signers are encoded as an array of varint gaps.
+    let set = SignerSet::new(1000, &[900, 2, 130]).unwrap();
+    assert_eq!(set.signers(), &[2, 130, 900]);
+    assert_eq!(set.to_bytes(), vec![0, 3, 2, 127, 0x81, 0x06]);
+    assert_eq!(SignerSet::from_bytes(&set.to_bytes(), 1000).unwrap(), set);
+    assert!(set.contains(130) && !set.contains(131));
+
+    // Many signers are encoded as a bitmap.
+    let set = SignerSet::new(10, &[0, 1, 2, 3, 5, 9]).unwrap();
+    assert_eq!(set.to_bytes(), vec![1, 0b0010_1111, 0b0000_0010]);
+    assert_eq!(SignerSet::from_bytes(&set.to_bytes(), 10).unwrap(), set);
+
+    // The empty set.
+    let set = SignerSet::new(10, &[]).unwrap();
+    assert!(set.is_empty());
+    assert_eq!(set.to_bytes(), vec![0, 0]);
+    assert_eq!(SignerSet::from_bytes(&[0, 0], 10).unwrap(), set);
+}
+
+#[test]
+fn test_signer_set_strict_parsing() {
+    // Empty input and unknown tag.
+    assert!(SignerSet::from_bytes(&[], 10).is_err());
+    assert!(SignerSet::from_bytes(&[2, 0], 10).is_err());
+
+    // A bitmap for a set which is shorter as an array.
+    assert!(SignerSet::from_bytes(&[1, 0b0000_0001, 0], 10).is_err());
+    assert_eq!(
+        SignerSet::from_bytes(&[0, 1, 0], 10).unwrap().signers(),
+        &[0]
+    );
+
+    // An array for a set which is shorter as a bitmap.
+    assert!(SignerSet::from_bytes(&[0, 6, 0, 0, 0, 0, 1, 3], 10).is_err());
+
+    // Non-minimal varints.
+    assert!(SignerSet::from_bytes(&[0, 0x81, 0x00, 0], 1000).is_err());
+    assert!(SignerSet::from_bytes(&[0, 0x80, 0x00], 1000).is_err());
+
+    // Indices outside the committee, in an array and in the unused bits of a bitmap.
+    assert!(SignerSet::from_bytes(&[0, 1, 10], 10).is_err());
+    assert!(SignerSet::from_bytes(&[1, 0xff, 0b0000_0111], 10).is_err());
+
+    // More signers than members, truncated and trailing bytes.
+    assert!(SignerSet::from_bytes(&[0, 11], 10).is_err());
+    assert!(SignerSet::from_bytes(&[0, 2, 1], 10).is_err());
+    assert!(SignerSet::from_bytes(&[0, 1, 1, 1], 10).is_err());
+    assert!(SignerSet::from_bytes(&[1, 0xff, 0b0000_0011, 0], 10).is_err());
+
+    // A varint which overflows.
+    let mut bytes = vec![0, 1];
+    bytes.extend([0xff; 9]);
+    bytes.push(0x02);
+    assert!(SignerSet::from_bytes(&bytes, usize::MAX).is_err());
+}
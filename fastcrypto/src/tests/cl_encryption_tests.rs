@@ -0,0 +1,94 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cl_encryption::{ClCiphertext, ClParameters, ClPrivateKey};
+use num_bigint::BigUint;
+use rand::{rngs::StdRng, SeedableRng as _};
+
+// Small parameters to keep the tests fast.
+const DISCRIMINANT_BITS: u64 = 256;
+
+/// The largest 64-bit prime, 2^64 - 59.
+fn q() -> BigUint {
+    BigUint::from(u64::MAX - 58)
+}
+
+#[test]
+fn test_encrypt_decrypt() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let parameters = ClParameters::generate(&q(), DISCRIMINANT_BITS, &mut rng).unwrap();
+    let sk = ClPrivateKey::generate(&parameters, &mut rng);
+    let pk = sk.public_key();
+
+    for m in [
+        BigUint::from(0u8),
+        BigUint::from(1u8),
+        BigUint::from(2u8),
+        q() - 1u8,
+    ] {
+        let (c, _) = pk.encrypt(&m, &mut rng).unwrap();
+        assert_eq!(sk.decrypt(&c).unwrap(), m);
+    }
+    assert!(pk.encrypt(&q(), &mut rng).is_err());
+
+    // A ciphertext can only be decrypted with the right key.
+    let other = ClPrivateKey::generate(&parameters, &mut rng);
+    let (c, _) = pk.encrypt(&BigUint::from(42u8), &mut rng).unwrap();
+    assert_ne!(other.decrypt(&c), Ok(BigUint::from(42u8)));
+}
+
+#[test]
+fn test_homomorphic_operations() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let parameters = ClParameters::generate(&q(), DISCRIMINANT_BITS, &mut rng).unwrap();
+    let sk = ClPrivateKey::generate(&parameters, &mut rng);
+    let pk = sk.public_key();
+
+    let a = q() - 5u8;
+    let b = BigUint::from(123456789u64);
+    let k = BigUint::from(987654321u64);
+    let (ca, _) = pk.encrypt(&a, &mut rng).unwrap();
+    let (cb, _) = pk.encrypt(&b, &mut rng).unwrap();
+
+    assert_eq!(sk.decrypt(&pk.add(&ca, &cb)).unwrap(), (&a + &b) % q());
+    assert_eq!(sk.decrypt(&pk.mul(&ca, &k)).unwrap(), (&a * &k) % q());
+    assert_eq!(
+        sk.decrypt(&pk.add(&pk.mul(&ca, &k), &cb)).unwrap(),
+        (&a * &k + &b) % q()
+    );
+}
+
+#[test]
+fn test_serialization() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let parameters = ClParameters::generate(&q(), DISCRIMINANT_BITS, &mut rng).unwrap();
+    let sk = ClPrivateKey::generate(&parameters, &mut rng);
+    let pk = sk.public_key();
+
+    let (c, r) = pk.encrypt(&BigUint::from(7u8), &mut rng).unwrap();
+    assert_eq!(
+        pk.encrypt_with_randomness(&BigUint::from(7u8), &r).unwrap(),
+        c
+    );
+    let bytes = bincode::serialize(&c).unwrap();
+    let deserialized: ClCiphertext = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(sk.decrypt(&deserialized).unwrap(), BigUint::from(7u8));
+
+    // Ciphertexts for other parameters are rejected.
+    let other_parameters = ClParameters::generate(&q(), DISCRIMINANT_BITS, &mut rng).unwrap();
+    let other_pk = ClPrivateKey::generate(&other_parameters, &mut rng)
+        .public_key()
+        .clone();
+    let (c, _) = other_pk.encrypt(&BigUint::from(7u8), &mut rng).unwrap();
+    assert!(sk.decrypt(&c).is_err());
+}
+
+#[test]
+fn test_invalid_parameters() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    // The discriminant is too small.
+    assert!(ClParameters::generate(&q(), 130, &mut rng).is_err());
+    // q is not prime.
+    assert!(ClParameters::generate(&BigUint::from(1001u32), 64, &mut rng).is_err());
+    assert!(ClParameters::generate(&BigUint::from(2u32), 64, &mut rng).is_err());
+}
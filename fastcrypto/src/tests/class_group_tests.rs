@@ -0,0 +1,66 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::class_group::QuadraticForm;
+use num_bigint::{BigInt, BigUint};
+
+fn form(a: i64, b: i64, discriminant: i64) -> QuadraticForm {
+    QuadraticForm::from_a_b(
+        BigInt::from(a),
+        BigInt::from(b),
+        &BigInt::from(discriminant),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_reduction() {
+    // (4, 5, 3) of discriminant -23 is equivalent to (2, -1, 3).
+    let f = QuadraticForm::new(BigInt::from(4), BigInt::from(5), BigInt::from(3)).unwrap();
+    assert_eq!(
+        f.coefficients(),
+        (&BigInt::from(2), &BigInt::from(-1), &BigInt::from(3))
+    );
+    assert_eq!(f.discriminant(), BigInt::from(-23));
+
+    // (2, 1, 3) is the inverse of (2, -1, 3).
+    assert_eq!(f.inverse(), form(2, 1, -23));
+
+    // Invalid forms.
+    assert!(QuadraticForm::new(BigInt::from(-1), BigInt::from(1), BigInt::from(6)).is_err());
+    assert!(QuadraticForm::new(BigInt::from(1), BigInt::from(0), BigInt::from(1)).is_err());
+    assert!(QuadraticForm::from_a_b(BigInt::from(3), BigInt::from(2), &BigInt::from(-23)).is_err());
+    assert!(QuadraticForm::identity(&BigInt::from(-24)).is_err());
+    assert!(QuadraticForm::identity(&BigInt::from(5)).is_err());
+}
+
+#[test]
+fn test_group_law() {
+    // The class number of -199 is 9.
+    let discriminant = BigInt::from(-199);
+    let identity = QuadraticForm::identity(&discriminant).unwrap();
+    let f = form(2, 1, -199);
+    let g = form(4, -3, -199);
+    let h = form(5, 1, -199);
+
+    assert_eq!(f.compose(&identity), f);
+    assert_eq!(identity.compose(&f), f);
+    assert_eq!(f.compose(&f.inverse()), identity);
+    assert_eq!(f.compose(&g), g.compose(&f));
+    assert_eq!(f.compose(&g).compose(&h), f.compose(&g.compose(&h)));
+    for x in [&f, &g, &h] {
+        assert_eq!(x.pow(&BigUint::from(9u8)), identity);
+        assert_eq!(x.pow(&BigUint::from(10u8)), *x);
+        assert_eq!(x.pow(&BigUint::from(0u8)), identity);
+        assert_eq!(x.compose(x).discriminant(), discriminant);
+    }
+
+    // The powers of (2, 1, 25) generate the whole group.
+    let powers = (0u8..9)
+        .map(|i| f.pow(&BigUint::from(i)))
+        .collect::<Vec<_>>();
+    for (i, x) in powers.iter().enumerate() {
+        assert!(!powers[..i].contains(x));
+    }
+    assert_eq!(f.pow(&BigUint::from(2u8)), g);
+}
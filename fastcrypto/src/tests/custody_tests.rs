@@ -0,0 +1,179 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::bls12381::min_sig::BLS12381KeyPair;
+use crate::custody::*;
+use crate::error::FastCryptoError;
+use crate::limits::Limit;
+use crate::traits::KeyPair;
+use rand::{rngs::StdRng, SeedableRng};
+
+const SEED: &[u8] = b"seed";
+
+fn chunks(n: u8) -> Vec<Vec<u8>> {
+    (0..n).map(|i| vec![i; 32 + i as usize]).collect()
+}
+
+fn key_pair(seed: u8) -> BLS12381KeyPair {
+    BLS12381KeyPair::generate(&mut StdRng::from_seed([seed; 32]))
+}
+
+#[test]
+fn test_merkle_proofs() {
+    // Trees with lone nodes on some levels.
+    for n in [1, 2, 3, 5, 8, 13] {
+        let data = CommittedData::new(chunks(n)).unwrap();
+        let commitment = data.commitment();
+        assert_eq!(commitment.num_chunks, n as u32);
+        for index in 0..n as u32 {
+            let chunk = &data.chunks()[index as usize];
+            let proof = data.prove(index).unwrap();
+            assert!(commitment.verify_chunk(index, chunk, &proof).is_ok());
+
+            // Other chunks, indices and proofs.
+            assert_eq!(
+                commitment.verify_chunk(index, &[chunk.as_slice(), &[0]].concat(), &proof),
+                Err(FastCryptoError::InvalidProof)
+            );
+            if n > 1 {
+                assert!(commitment
+                    .verify_chunk((index + 1) % n as u32, chunk, &proof)
+                    .is_err());
+            }
+            let mut longer = proof.clone();
+            longer.0.push(commitment.root);
+            assert!(commitment.verify_chunk(index, chunk, &longer).is_err());
+            if !proof.0.is_empty() {
+                let mut shorter = proof.clone();
+                shorter.0.pop();
+                assert!(commitment.verify_chunk(index, chunk, &shorter).is_err());
+            }
+        }
+        assert!(data.prove(n as u32).is_err());
+        assert!(commitment
+            .verify_chunk(n as u32, &[], &MerkleProof(vec![]))
+            .is_err());
+    }
+
+    // The number of chunks is part of the commitment.
+    let mut commitment = CommittedData::new(chunks(5)).unwrap().commitment();
+    commitment.num_chunks = 6;
+    let proof = CommittedData::new(chunks(5)).unwrap().prove(4).unwrap();
+    assert!(commitment.verify_chunk(4, &chunks(5)[4], &proof).is_err());
+
+    assert!(CommittedData::new(vec![]).is_err());
+
+    // Proofs deeper than the limit are rejected when verified and when deserialized.
+    let data = CommittedData::new(chunks(2)).unwrap();
+    let mut deep = data.prove(0).unwrap();
+    deep.0 = vec![deep.0[0]; Limit::MerkleProofDepth.default_maximum() + 1];
+    assert_eq!(
+        data.commitment().verify_chunk(0, &data.chunks()[0], &deep),
+        Err(FastCryptoError::InputTooLong(
+            Limit::MerkleProofDepth.default_maximum()
+        ))
+    );
+    assert!(bincode::deserialize::<MerkleProof>(&bincode::serialize(&deep).unwrap()).is_err());
+    deep.0.pop();
+    let deserialized: MerkleProof =
+        bincode::deserialize(&bincode::serialize(&deep).unwrap()).unwrap();
+    assert_eq!(deserialized, deep);
+}
+
+#[test]
+fn test_attestations() {
+    let data = CommittedData::new(chunks(7)).unwrap();
+    let commitment = data.commitment();
+    let custodian = key_pair(1);
+    let response = data.respond(&custodian, 3).unwrap();
+    assert_eq!(response.chunk, data.chunks()[3]);
+    assert_eq!(response.attestation, data.attest(&custodian, 3).unwrap());
+    assert!(response.attestation.verify(custodian.public()).is_ok());
+    assert!(response.verify(custodian.public(), &commitment, 3).is_ok());
+
+    // Other custodians, indices, commitments and chunks.
+    assert!(response
+        .verify(key_pair(2).public(), &commitment, 3)
+        .is_err());
+    assert!(response.verify(custodian.public(), &commitment, 4).is_err());
+    let other = CommittedData::new(chunks(8)).unwrap().commitment();
+    assert!(response.verify(custodian.public(), &other, 3).is_err());
+    let mut modified = response.clone();
+    modified.chunk[0] ^= 1;
+    assert!(modified.verify(custodian.public(), &commitment, 3).is_err());
+
+    // An attestation for another chunk than the proven one.
+    let mut modified = response.clone();
+    modified.attestation = data.attest(&custodian, 4).unwrap();
+    modified.attestation.index = 3;
+    assert!(modified.verify(custodian.public(), &commitment, 3).is_err());
+
+    assert!(data.attest(&custodian, 7).is_err());
+    assert!(data.respond(&custodian, 7).is_err());
+}
+
+#[test]
+fn test_sampling() {
+    let data = CommittedData::new(chunks(20)).unwrap();
+    let commitment = data.commitment();
+    let indices = sample_indices(&commitment, SEED, 20).unwrap();
+    assert_eq!(indices.len(), 20);
+    let mut sorted = indices.clone();
+    sorted.sort_unstable();
+    assert_eq!(sorted, (0..20).collect::<Vec<_>>());
+
+    // Sampling is deterministic, consistent across sample sizes and depends on the seed and the
+    // commitment.
+    assert_eq!(sample_indices(&commitment, SEED, 5).unwrap(), indices[..5]);
+    assert_ne!(
+        sample_indices(&commitment, b"other", 5).unwrap(),
+        indices[..5]
+    );
+    let other = CommittedData::new(chunks(21)).unwrap().commitment();
+    assert_ne!(sample_indices(&other, SEED, 5).unwrap(), indices[..5]);
+
+    assert!(sample_indices(&commitment, SEED, 0).is_err());
+    assert!(sample_indices(&commitment, SEED, 21).is_err());
+}
+
+#[test]
+fn test_verify_samples() {
+    let data = CommittedData::new(chunks(16)).unwrap();
+    let commitment = data.commitment();
+    let custodian = key_pair(1);
+    let responses = sample_indices(&commitment, SEED, 4)
+        .unwrap()
+        .iter()
+        .map(|i| data.respond(&custodian, *i).unwrap())
+        .collect::<Vec<_>>();
+    assert!(verify_samples(custodian.public(), &commitment, SEED, &responses).is_ok());
+    assert!(verify_samples(custodian.public(), &commitment, SEED, &responses[..3]).is_ok());
+
+    // Another seed, custodian or order of the responses.
+    assert!(verify_samples(custodian.public(), &commitment, b"other", &responses).is_err());
+    assert!(verify_samples(key_pair(2).public(), &commitment, SEED, &responses).is_err());
+    let mut reordered = responses.clone();
+    reordered.swap(0, 1);
+    assert!(verify_samples(custodian.public(), &commitment, SEED, &reordered).is_err());
+
+    // An attestation from another custodian.
+    let mut modified = responses.clone();
+    modified[2].attestation = data
+        .attest(&key_pair(2), modified[2].attestation.index)
+        .unwrap();
+    assert_eq!(
+        verify_samples(custodian.public(), &commitment, SEED, &modified),
+        Err(FastCryptoError::InvalidSignature)
+    );
+
+    assert!(verify_samples(custodian.public(), &commitment, SEED, &[]).is_err());
+}
+
+#[test]
+fn test_serde() {
+    let data = CommittedData::new(chunks(3)).unwrap();
+    let response = data.respond(&key_pair(1), 2).unwrap();
+    let serialized = bincode::serialize(&response).unwrap();
+    let deserialized: CustodyResponse = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(deserialized, response);
+}
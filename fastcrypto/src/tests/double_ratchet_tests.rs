@@ -0,0 +1,198 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::double_ratchet::*;
+use crate::key_ratchet::DEFAULT_MAX_SKIP;
+use crate::noise::NoiseKeyPair;
+use rand::{rngs::StdRng, SeedableRng};
+
+fn session(
+    header_encryption: bool,
+    max_skip: u64,
+    rng: &mut StdRng,
+) -> (DoubleRatchet, DoubleRatchet) {
+    let secret = [3u8; 32];
+    let bob_key = NoiseKeyPair::generate(rng);
+    let alice =
+        DoubleRatchet::initiator(&secret, bob_key.public(), header_encryption, max_skip, rng)
+            .unwrap();
+    let bob = DoubleRatchet::responder(&secret, bob_key, header_encryption, max_skip).unwrap();
+    (alice, bob)
+}
+
+fn send(
+    sender: &mut DoubleRatchet,
+    receiver: &mut DoubleRatchet,
+    plaintext: &[u8],
+    rng: &mut StdRng,
+) {
+    let message = sender.encrypt(plaintext, b"ad", rng).unwrap();
+    assert_eq!(receiver.decrypt(&message, b"ad", rng).unwrap(), plaintext);
+}
+
+#[test]
+fn test_conversation() {
+    for header_encryption in [false, true] {
+        let mut rng = StdRng::from_seed([0; 32]);
+        let (mut alice, mut bob) = session(header_encryption, DEFAULT_MAX_SKIP, &mut rng);
+        assert!(bob.encrypt(b"too early", b"ad", &mut rng).is_err());
+        assert!(bob.remote_public_key().is_none());
+
+        let mut alice_keys = vec![alice.public_key().clone()];
+        for round in 0..5 {
+            for i in 0..round {
+                send(
+                    &mut alice,
+                    &mut bob,
+                    format!("a{round}{i}").as_bytes(),
+                    &mut rng,
+                );
+            }
+            send(&mut alice, &mut bob, b"ping", &mut rng);
+            assert_eq!(bob.remote_public_key(), Some(alice.public_key()));
+            send(&mut bob, &mut alice, b"pong", &mut rng);
+            assert_eq!(alice.remote_public_key(), Some(bob.public_key()));
+            send(&mut bob, &mut alice, b"pong again", &mut rng);
+
+            // Alice uses a new key pair after each round trip.
+            send(&mut alice, &mut bob, b"next", &mut rng);
+            assert!(!alice_keys.contains(alice.public_key()));
+            alice_keys.push(alice.public_key().clone());
+        }
+    }
+}
+
+#[test]
+fn test_out_of_order() {
+    for header_encryption in [false, true] {
+        let mut rng = StdRng::from_seed([0; 32]);
+        let (mut alice, mut bob) = session(header_encryption, DEFAULT_MAX_SKIP, &mut rng);
+
+        let first = (0..3)
+            .map(|i| alice.encrypt(&[i], b"ad", &mut rng).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(bob.decrypt(&first[1], b"ad", &mut rng).unwrap(), [1]);
+        send(&mut bob, &mut alice, b"reply", &mut rng);
+
+        // A message of a new chain of Alice arrives before the skipped messages of the old chain.
+        let second = alice.encrypt(b"second chain", b"ad", &mut rng).unwrap();
+        assert_eq!(
+            bob.decrypt(&second, b"ad", &mut rng).unwrap(),
+            b"second chain"
+        );
+        assert_eq!(bob.decrypt(&first[2], b"ad", &mut rng).unwrap(), [2]);
+        assert_eq!(bob.decrypt(&first[0], b"ad", &mut rng).unwrap(), [0]);
+
+        // Each message is decrypted once.
+        for message in first.iter().chain([&second]) {
+            assert!(bob.decrypt(message, b"ad", &mut rng).is_err());
+        }
+        send(&mut bob, &mut alice, b"still working", &mut rng);
+    }
+}
+
+#[test]
+fn test_invalid_messages() {
+    for header_encryption in [false, true] {
+        let mut rng = StdRng::from_seed([0; 32]);
+        let (mut alice, mut bob) = session(header_encryption, DEFAULT_MAX_SKIP, &mut rng);
+        let message = alice.encrypt(b"message", b"ad", &mut rng).unwrap();
+
+        assert!(bob.decrypt(&message, b"other ad", &mut rng).is_err());
+        let mut modified = message.clone();
+        modified.ciphertext[0] ^= 1;
+        assert!(bob.decrypt(&modified, b"ad", &mut rng).is_err());
+        let mut modified = message.clone();
+        modified.header[0] ^= 1;
+        assert!(bob.decrypt(&modified, b"ad", &mut rng).is_err());
+        let mut modified = message.clone();
+        modified.header.pop();
+        assert!(bob.decrypt(&modified, b"ad", &mut rng).is_err());
+
+        // The failures did not change the state.
+        assert!(bob.remote_public_key().is_none());
+        assert_eq!(bob.decrypt(&message, b"ad", &mut rng).unwrap(), b"message");
+
+        // A message from another session.
+        let (mut other, _) = session(header_encryption, DEFAULT_MAX_SKIP, &mut rng);
+        let message = other.encrypt(b"message", b"ad", &mut rng).unwrap();
+        assert!(bob.decrypt(&message, b"ad", &mut rng).is_err());
+        send(&mut bob, &mut alice, b"reply", &mut rng);
+    }
+}
+
+#[test]
+fn test_header_encryption() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let (mut alice, _) = session(false, DEFAULT_MAX_SKIP, &mut rng);
+    let message = alice.encrypt(b"message", b"ad", &mut rng).unwrap();
+    let header = Header::from_bytes(&message.header).unwrap();
+    assert_eq!(&header.dh, alice.public_key());
+    assert_eq!(header.index, 0);
+    assert_eq!(Header::from_bytes(&header.to_bytes()).unwrap(), header);
+
+    let (mut alice, _) = session(true, DEFAULT_MAX_SKIP, &mut rng);
+    let first = alice.encrypt(b"message", b"ad", &mut rng).unwrap();
+    let second = alice.encrypt(b"message", b"ad", &mut rng).unwrap();
+    assert!(Header::from_bytes(&first.header).is_err());
+    assert_ne!(first.header, second.header);
+    let public_key = alice.public_key().as_ref().to_vec();
+    assert!(!first
+        .header
+        .windows(public_key.len())
+        .any(|w| w == public_key.as_slice()));
+}
+
+#[test]
+fn test_max_skip() {
+    for header_encryption in [false, true] {
+        let mut rng = StdRng::from_seed([0; 32]);
+        let (mut alice, mut bob) = session(header_encryption, 3, &mut rng);
+        let messages = (0..5)
+            .map(|i| alice.encrypt(&[i], b"ad", &mut rng).unwrap())
+            .collect::<Vec<_>>();
+        assert!(bob.decrypt(&messages[4], b"ad", &mut rng).is_err());
+        assert_eq!(bob.decrypt(&messages[3], b"ad", &mut rng).unwrap(), [3]);
+        assert_eq!(bob.decrypt(&messages[4], b"ad", &mut rng).unwrap(), [4]);
+        assert_eq!(bob.decrypt(&messages[0], b"ad", &mut rng).unwrap(), [0]);
+    }
+}
+
+#[test]
+fn test_previous_chains_are_bounded() {
+    for header_encryption in [false, true] {
+        let mut rng = StdRng::from_seed([0; 32]);
+        let (mut alice, mut bob) = session(header_encryption, DEFAULT_MAX_SKIP, &mut rng);
+        // Each round trip starts a new receiving chain of Bob with a skipped message.
+        let mut skipped = vec![];
+        for _ in 0..MAX_PREVIOUS_CHAINS + 2 {
+            skipped.push(alice.encrypt(b"skipped", b"ad", &mut rng).unwrap());
+            send(&mut alice, &mut bob, b"ping", &mut rng);
+            send(&mut bob, &mut alice, b"pong", &mut rng);
+        }
+        // The skipped message of the oldest chain has been dropped.
+        assert!(bob.decrypt(&skipped[0], b"ad", &mut rng).is_err());
+        for message in &skipped[1..] {
+            assert_eq!(bob.decrypt(message, b"ad", &mut rng).unwrap(), b"skipped");
+        }
+    }
+}
+
+#[test]
+fn test_serialization() {
+    for header_encryption in [false, true] {
+        let mut rng = StdRng::from_seed([0; 32]);
+        let (mut alice, mut bob) = session(header_encryption, DEFAULT_MAX_SKIP, &mut rng);
+        let skipped = alice.encrypt(b"skipped", b"ad", &mut rng).unwrap();
+        send(&mut alice, &mut bob, b"ping", &mut rng);
+
+        let mut bob: DoubleRatchet = bcs::from_bytes(&bcs::to_bytes(&bob).unwrap()).unwrap();
+        let mut alice: DoubleRatchet = bcs::from_bytes(&bcs::to_bytes(&alice).unwrap()).unwrap();
+        send(&mut bob, &mut alice, b"pong", &mut rng);
+        send(&mut alice, &mut bob, b"ping", &mut rng);
+        assert_eq!(bob.decrypt(&skipped, b"ad", &mut rng).unwrap(), b"skipped");
+
+        // The state is not printed.
+        assert!(!format!("{:?}", bob).contains("root_key"));
+    }
+}
@@ -0,0 +1,186 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::bls12381::min_pk::DST_G2;
+use crate::bls12381::{min_pk, min_sig};
+use crate::drand::{DrandBeacon, DrandChain, DrandScheme};
+use crate::hash::{HashFunction, Sha256};
+use crate::traits::{KeyPair, Signer, ToFromBytes};
+use rand::{rngs::StdRng, SeedableRng as _};
+
+const SCHEMES: [DrandScheme; 4] = [
+    DrandScheme::PedersenBlsChained,
+    DrandScheme::PedersenBlsUnchained,
+    DrandScheme::BlsUnchainedOnG1,
+    DrandScheme::BlsUnchainedG1Rfc9380,
+];
+
+/// A drand network signing rounds with a single key.
+struct Network {
+    scheme: DrandScheme,
+    g1_key: min_pk::BLS12381KeyPair,
+    g2_key: min_sig::BLS12381KeyPair,
+}
+
+impl Network {
+    fn new(scheme: DrandScheme) -> Self {
+        let rng = &mut StdRng::from_seed([0; 32]);
+        Self {
+            scheme,
+            g1_key: min_pk::BLS12381KeyPair::generate(rng),
+            g2_key: min_sig::BLS12381KeyPair::generate(rng),
+        }
+    }
+
+    fn chain(&self) -> DrandChain {
+        let public_key = match self.scheme {
+            DrandScheme::PedersenBlsChained | DrandScheme::PedersenBlsUnchained => {
+                self.g1_key.public().as_bytes().to_vec()
+            }
+            _ => self.g2_key.public().as_bytes().to_vec(),
+        };
+        DrandChain::new(self.scheme, &public_key).unwrap()
+    }
+
+    fn beacon(&self, round: u64, previous_signature: Option<Vec<u8>>) -> DrandBeacon {
+        let mut hash = Sha256::default();
+        if let Some(previous_signature) = &previous_signature {
+            hash.update(previous_signature);
+        }
+        hash.update(round.to_be_bytes());
+        let message = hash.finalize().digest;
+        let signature = match self.scheme {
+            DrandScheme::PedersenBlsChained | DrandScheme::PedersenBlsUnchained => {
+                self.g1_key.sign(&message).as_bytes().to_vec()
+            }
+            DrandScheme::BlsUnchainedOnG1 => self
+                .g2_key
+                .copy()
+                .private()
+                .privkey
+                .sign(&message, DST_G2, &[])
+                .compress()
+                .to_vec(),
+            DrandScheme::BlsUnchainedG1Rfc9380 => self.g2_key.sign(&message).as_bytes().to_vec(),
+        };
+        DrandBeacon {
+            round,
+            signature,
+            previous_signature,
+        }
+    }
+
+    fn beacons(&self, first_round: u64, count: usize) -> Vec<DrandBeacon> {
+        let mut beacons: Vec<DrandBeacon> = Vec::new();
+        for round in first_round..first_round + count as u64 {
+            let previous_signature = match beacons.last() {
+                _ if !self.scheme.is_chained() => None,
+                Some(previous) => Some(previous.signature.clone()),
+                None => Some(vec![round as u8; 96]),
+            };
+            beacons.push(self.beacon(round, previous_signature));
+        }
+        beacons
+    }
+}
+
+#[test]
+fn test_scheme_ids() {
+    for scheme in SCHEMES {
+        assert_eq!(DrandScheme::from_id(scheme.id()).unwrap(), scheme);
+    }
+    assert!(DrandScheme::from_id("pedersen-bls").is_err());
+}
+
+#[test]
+fn test_verify() {
+    for scheme in SCHEMES {
+        let network = Network::new(scheme);
+        let chain = network.chain();
+        let beacons = network.beacons(1, 3);
+        for beacon in &beacons {
+            assert!(chain.verify(beacon).is_ok());
+            assert_eq!(
+                beacon.randomness(),
+                Sha256::digest(&beacon.signature).digest
+            );
+        }
+        assert!(chain.verify_chain(&beacons).is_ok());
+
+        // Wrong round or signature.
+        let mut beacon = beacons[1].clone();
+        beacon.round += 1;
+        assert!(chain.verify(&beacon).is_err());
+        let mut beacon = beacons[1].clone();
+        beacon.signature = beacons[2].signature.clone();
+        assert!(chain.verify(&beacon).is_err());
+        let mut beacon = beacons[1].clone();
+        beacon.signature.pop();
+        assert!(chain.verify(&beacon).is_err());
+
+        // Rounds must be consecutive.
+        assert!(chain
+            .verify_chain(&[beacons[0].clone(), beacons[2].clone()])
+            .is_err());
+    }
+}
+
+#[test]
+fn test_chained() {
+    let network = Network::new(DrandScheme::PedersenBlsChained);
+    let chain = network.chain();
+    let beacons = network.beacons(5, 2);
+
+    // The previous signature is part of the message.
+    let mut beacon = beacons[1].clone();
+    beacon.previous_signature = None;
+    assert!(chain.verify(&beacon).is_err());
+    beacon.previous_signature = Some(vec![0; 96]);
+    assert!(chain.verify(&beacon).is_err());
+
+    // The previous signature must match the previous beacon.
+    let unlinked = network.beacon(6, Some(vec![1; 96]));
+    assert!(chain.verify(&unlinked).is_ok());
+    assert!(chain.verify_chain(&[beacons[0].clone(), unlinked]).is_err());
+}
+
+#[test]
+fn test_wrong_scheme() {
+    let network = Network::new(DrandScheme::BlsUnchainedOnG1);
+    let beacon = network.beacon(1, None);
+    let chain = Network::new(DrandScheme::BlsUnchainedG1Rfc9380).chain();
+    assert!(chain.verify(&beacon).is_err());
+
+    // Public keys must be in the group used by the scheme.
+    let g1_key = network.g1_key.public().as_bytes().to_vec();
+    assert!(DrandChain::new(DrandScheme::BlsUnchainedOnG1, &g1_key).is_err());
+    assert!(DrandChain::new(DrandScheme::PedersenBlsUnchained, &g1_key).is_ok());
+}
+
+#[test]
+fn test_genesis_round() {
+    let network = Network::new(DrandScheme::PedersenBlsUnchained);
+    assert!(network.chain().verify(&network.beacon(0, None)).is_err());
+}
+
+#[test]
+fn test_json() {
+    let network = Network::new(DrandScheme::PedersenBlsChained);
+    let beacon = network.beacons(1, 1).pop().unwrap();
+    let json = format!(
+        r#"{{"round": 1, "randomness": "{}", "signature": "{}", "previous_signature": "{}"}}"#,
+        hex::encode(beacon.randomness()),
+        hex::encode(&beacon.signature),
+        hex::encode(beacon.previous_signature.as_ref().unwrap()),
+    );
+    let parsed: DrandBeacon = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, beacon);
+    assert!(network.chain().verify(&parsed).is_ok());
+
+    let unchained: DrandBeacon = serde_json::from_str(&format!(
+        r#"{{"round": 1, "signature": "{}"}}"#,
+        hex::encode(&beacon.signature)
+    ))
+    .unwrap();
+    assert_eq!(unchained.previous_signature, None);
+}
@@ -7,8 +7,8 @@ use crate::test_helpers::verify_serialization;
 use crate::traits::{InsecureDefault, Signer};
 use crate::{
     ed25519::{
-        Ed25519AggregateSignature, Ed25519KeyPair, Ed25519PrivateKey, Ed25519PublicKey,
-        Ed25519Signature, ED25519_PRIVATE_KEY_LENGTH,
+        Ed25519AggregateSignature, Ed25519BatchItemRef, Ed25519ExpandedKeyPair, Ed25519KeyPair,
+        Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature, ED25519_PRIVATE_KEY_LENGTH,
     },
     encoding::Base64,
     hash::{HashFunction, Sha256, Sha3_256},
@@ -30,6 +30,52 @@ pub fn keys() -> Vec<Ed25519KeyPair> {
     (0..4).map(|_| Ed25519KeyPair::generate(&mut rng)).collect()
 }
 
+#[test]
+fn generate_batch() {
+    let batch = |seed| {
+        Ed25519KeyPair::generate_batch(17, &mut StdRng::from_seed(seed))
+            .unwrap()
+            .iter()
+            .map(|kp| kp.public().clone())
+            .collect::<Vec<_>>()
+    };
+    let keys = batch([0; 32]);
+    assert_eq!(keys.len(), 17);
+    assert_eq!(keys, batch([0; 32]));
+    assert_ne!(keys, batch([1; 32]));
+    assert_eq!(
+        keys.iter().collect::<std::collections::HashSet<_>>().len(),
+        17
+    );
+
+    assert!(
+        Ed25519KeyPair::generate_batch(0, &mut StdRng::from_seed([0; 32]))
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn expanded_key_pair() {
+    let message: &[u8] = b"Hello, world!";
+    for kp in keys() {
+        let expanded = Ed25519ExpandedKeyPair::from(&kp);
+        assert_eq!(expanded.public(), kp.public());
+
+        // Signing is deterministic, so both key pairs give the same signatures.
+        let signature = expanded.sign(message);
+        assert_eq!(signature.as_ref(), kp.sign(message).as_ref());
+        assert!(kp.public().verify(message, &signature).is_ok());
+    }
+
+    // The scalar must be clamped.
+    let mut expanded = [1u8; 64];
+    assert!(Ed25519ExpandedKeyPair::from_expanded_secret(&expanded).is_err());
+    expanded[0] = 8;
+    expanded[31] = 64;
+    assert!(Ed25519ExpandedKeyPair::from_expanded_secret(&expanded).is_ok());
+}
+
 #[test]
 fn serialize_deserialize() {
     let kp = keys().pop().unwrap();
@@ -328,6 +374,56 @@ fn verify_batch_aggregate_signature() {
     .is_ok());
 }
 
+#[test]
+fn verify_batch_aggregate_signature_from_iterator() {
+    let (digest1, digest2, pubkeys1, pubkeys2, aggregated_signature1, aggregated_signature2) =
+        verify_batch_aggregate_signature_inputs();
+
+    let pubkeys = [pubkeys1, pubkeys2];
+    assert!(Ed25519AggregateSignature::batch_verify(
+        &[&aggregated_signature1, &aggregated_signature2],
+        pubkeys.iter().map(|pks| pks.iter()),
+        &[&digest1[..], &digest2[..]]
+    )
+    .is_ok());
+}
+
+#[test]
+fn verify_batch_from_bytes() {
+    let messages: Vec<Vec<u8>> = (0..4u8).map(|i| vec![i; 10]).collect();
+    let kps = keys();
+    let pks = kps
+        .iter()
+        .map(|kp| <[u8; 32]>::try_from(kp.public().as_ref()).unwrap())
+        .collect::<Vec<_>>();
+    let mut sigs = kps
+        .iter()
+        .zip(&messages)
+        .map(|(kp, msg)| <[u8; 64]>::try_from(kp.sign(msg).as_ref()).unwrap())
+        .collect::<Vec<_>>();
+
+    fn items<'a>(
+        pks: &'a [[u8; 32]],
+        sigs: &'a [[u8; 64]],
+        messages: &'a [Vec<u8>],
+    ) -> Vec<Ed25519BatchItemRef<'a>> {
+        pks.iter()
+            .zip(sigs)
+            .zip(messages)
+            .map(|((public_key, signature), message)| Ed25519BatchItemRef {
+                public_key,
+                signature,
+                message,
+            })
+            .collect()
+    }
+    assert!(Ed25519PublicKey::verify_batch_from_bytes(items(&pks, &sigs, &messages)).is_ok());
+    assert!(Ed25519PublicKey::verify_batch_from_bytes(vec![]).is_err());
+
+    sigs[2][0] ^= 1;
+    assert!(Ed25519PublicKey::verify_batch_from_bytes(items(&pks, &sigs, &messages)).is_err());
+}
+
 #[test]
 fn verify_batch_missing_parameters_length_mismatch() {
     let (digest1, digest2, pubkeys1, pubkeys2, aggregated_signature1, aggregated_signature2) =
@@ -0,0 +1,120 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::bls12381::{min_pk, min_sig};
+use crate::ed25519::{Ed25519KeyPair, Ed25519PublicKey};
+use crate::envelope::*;
+use crate::error::FastCryptoError;
+use crate::secp256k1::Secp256k1KeyPair;
+use crate::secp256r1::Secp256r1KeyPair;
+use crate::traits::KeyPair;
+use rand::{rngs::StdRng, SeedableRng};
+
+fn check_envelopes<KP>(scheme: SignatureScheme)
+where
+    KP: KeyPair,
+    KP::PubKey: EnvelopeScheme<Sig = KP::Sig>,
+    KP::Sig: PartialEq,
+{
+    let kp = KP::generate(&mut StdRng::from_seed([0; 32]));
+
+    let detached = DetachedSignature::sign(&kp, b"message");
+    assert!(detached.verify(b"message").is_ok());
+    assert!(detached.verify(b"other").is_err());
+    let bytes = detached.to_bytes();
+    assert_eq!(bytes.len(), DetachedSignature::<KP::PubKey>::LENGTH);
+    assert_eq!(scheme_of(&bytes).unwrap(), scheme);
+    assert_eq!(
+        DetachedSignature::<KP::PubKey>::from_bytes(&bytes).unwrap(),
+        detached
+    );
+
+    let attached = AttachedSignature::sign(&kp, b"message".to_vec());
+    assert!(attached.verify().is_ok());
+    assert_eq!(attached.public_key(), kp.public());
+    let attached_bytes = attached.to_bytes();
+    assert_eq!(&attached_bytes[..bytes.len()], bytes.as_slice());
+    assert_eq!(scheme_of(&attached_bytes).unwrap(), scheme);
+    let parsed = AttachedSignature::<KP::PubKey>::from_bytes(&attached_bytes).unwrap();
+    assert_eq!(parsed, attached);
+    let (parsed_detached, message) = parsed.into_parts();
+    assert_eq!(parsed_detached, detached);
+    assert_eq!(message, b"message");
+
+    // Empty messages.
+    let empty = AttachedSignature::sign(&kp, Vec::new());
+    assert!(
+        AttachedSignature::<KP::PubKey>::from_bytes(&empty.to_bytes())
+            .unwrap()
+            .verify()
+            .is_ok()
+    );
+
+    // Wrong lengths.
+    assert!(DetachedSignature::<KP::PubKey>::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    assert!(
+        DetachedSignature::<KP::PubKey>::from_bytes(&[bytes.as_slice(), &[0]].concat()).is_err()
+    );
+    assert!(AttachedSignature::<KP::PubKey>::from_bytes(&bytes).is_err());
+    for length in [0, 1, attached_bytes.len() - 1] {
+        assert!(AttachedSignature::<KP::PubKey>::from_bytes(&attached_bytes[..length]).is_err());
+    }
+    assert!(AttachedSignature::<KP::PubKey>::from_bytes(
+        &[attached_bytes.as_slice(), &[0]].concat()
+    )
+    .is_err());
+    assert!(DetachedSignature::<KP::PubKey>::from_bytes(&attached_bytes).is_err());
+
+    // Unknown versions and flags of other schemes.
+    let mut modified = bytes.clone();
+    modified[0] = ENVELOPE_VERSION + 1;
+    assert!(scheme_of(&modified).is_err());
+    assert!(DetachedSignature::<KP::PubKey>::from_bytes(&modified).is_err());
+    let mut modified = bytes.clone();
+    modified[1] = (scheme.flag() + 1) % 5;
+    assert!(DetachedSignature::<KP::PubKey>::from_bytes(&modified).is_err());
+    modified[1] = 0xff;
+    assert!(scheme_of(&modified).is_err());
+}
+
+#[test]
+fn test_envelopes() {
+    check_envelopes::<Ed25519KeyPair>(SignatureScheme::Ed25519);
+    check_envelopes::<Secp256k1KeyPair>(SignatureScheme::Secp256k1);
+    check_envelopes::<Secp256r1KeyPair>(SignatureScheme::Secp256r1);
+    check_envelopes::<min_sig::BLS12381KeyPair>(SignatureScheme::BLS12381MinSig);
+    check_envelopes::<min_pk::BLS12381KeyPair>(SignatureScheme::BLS12381MinPk);
+}
+
+#[test]
+fn test_flags() {
+    for scheme in [
+        SignatureScheme::Ed25519,
+        SignatureScheme::Secp256k1,
+        SignatureScheme::Secp256r1,
+        SignatureScheme::BLS12381MinSig,
+        SignatureScheme::BLS12381MinPk,
+    ] {
+        assert_eq!(SignatureScheme::from_flag(scheme.flag()).unwrap(), scheme);
+    }
+    assert!(SignatureScheme::from_flag(5).is_err());
+    assert_eq!(
+        scheme_of(&[ENVELOPE_VERSION]),
+        Err(FastCryptoError::InputTooShort(2))
+    );
+}
+
+#[test]
+fn test_message_length() {
+    let kp = Ed25519KeyPair::generate(&mut StdRng::from_seed([0; 32]));
+    let attached = AttachedSignature::sign(&kp, b"message".to_vec());
+    let mut bytes = attached.to_bytes();
+
+    // The message length must match the remaining bytes.
+    let length_start = DetachedSignature::<Ed25519PublicKey>::LENGTH;
+    bytes[length_start + 7] += 1;
+    assert!(AttachedSignature::<Ed25519PublicKey>::from_bytes(&bytes).is_err());
+    bytes[length_start + 7] -= 1;
+    bytes[length_start] = 1;
+    assert!(AttachedSignature::<Ed25519PublicKey>::from_bytes(&bytes).is_err());
+}
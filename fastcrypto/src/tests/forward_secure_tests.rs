@@ -0,0 +1,81 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::forward_secure::*;
+use crate::test_helpers::verify_serialization;
+use rand::{rngs::StdRng, SeedableRng};
+
+#[test]
+fn test_sign_and_verify_all_periods() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let mut sk = ForwardSecureSigningKey::generate(3, &mut rng).unwrap();
+    let pk = sk.public_key().clone();
+    assert_eq!(pk.number_of_periods(), 8);
+
+    let mut signatures = vec![];
+    loop {
+        let msg = sk.period().to_le_bytes();
+        let signature = sk.sign(&msg);
+        assert_eq!(signature.period(), sk.period());
+        assert!(pk.verify(&msg, &signature).is_ok());
+        signatures.push(signature);
+        if sk.evolve().is_err() {
+            break;
+        }
+        assert_eq!(sk.public_key(), &pk);
+    }
+    assert_eq!(signatures.len(), 8);
+    assert_eq!(sk.period(), 7);
+
+    // Old signatures remain valid.
+    for (period, signature) in signatures.iter().enumerate() {
+        assert!(pk.verify(&(period as u32).to_le_bytes(), signature).is_ok());
+        assert!(pk.verify(b"other message", signature).is_err());
+    }
+}
+
+#[test]
+fn test_deterministic_and_evolve_to() {
+    let mut sk1 = ForwardSecureSigningKey::from_seed(4, [7; 32]).unwrap();
+    let mut sk2 = ForwardSecureSigningKey::from_seed(4, [7; 32]).unwrap();
+    assert_eq!(sk1.public_key(), sk2.public_key());
+
+    sk1.evolve_to(11).unwrap();
+    for _ in 0..11 {
+        sk2.evolve().unwrap();
+    }
+    assert_eq!(sk1.period(), 11);
+    assert_eq!(sk1.sign(b"msg"), sk2.sign(b"msg"));
+
+    assert!(sk1.evolve_to(11).is_err());
+    assert!(sk1.evolve_to(5).is_err());
+    assert!(sk1.evolve_to(16).is_err());
+
+    let other = ForwardSecureSigningKey::from_seed(4, [8; 32]).unwrap();
+    assert!(other
+        .public_key()
+        .verify(b"msg", &sk1.sign(b"msg"))
+        .is_err());
+
+    assert!(ForwardSecureSigningKey::from_seed(0, [7; 32]).is_err());
+    assert!(ForwardSecureSigningKey::from_seed(MAX_DEPTH + 1, [7; 32]).is_err());
+}
+
+#[test]
+fn test_tampered_signature() {
+    let mut sk = ForwardSecureSigningKey::from_seed(3, [1; 32]).unwrap();
+    let pk = sk.public_key().clone();
+    sk.evolve_to(5).unwrap();
+    let signature = sk.sign(b"msg");
+
+    // A signature cannot be claimed for another period.
+    let bytes = bincode::serialize(&signature).unwrap();
+    let mut modified = bytes.clone();
+    modified[0] = 4;
+    let modified: ForwardSecureSignature = bincode::deserialize(&modified).unwrap();
+    assert_eq!(modified.period(), 4);
+    assert!(pk.verify(b"msg", &modified).is_err());
+
+    verify_serialization(&signature, Some(&bytes));
+    verify_serialization(&pk, None);
+}
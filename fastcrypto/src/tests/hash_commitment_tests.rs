@@ -0,0 +1,47 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::hash_commitment::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+#[test]
+fn test_commit_and_verify() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let (commitment, opening) = commit(b"domain", b"message", &mut rng);
+    assert!(commitment.verify(b"domain", b"message", &opening).is_ok());
+    assert_eq!(
+        commitment,
+        commit_with_opening(b"domain", b"message", &opening)
+    );
+
+    // Another message, domain or opening.
+    assert!(commitment.verify(b"domain", b"other", &opening).is_err());
+    assert!(commitment.verify(b"other", b"message", &opening).is_err());
+    let (_, other_opening) = commit(b"domain", b"message", &mut rng);
+    assert!(commitment
+        .verify(b"domain", b"message", &other_opening)
+        .is_err());
+
+    // Commitments to the same message are different.
+    let (other_commitment, _) = commit(b"domain", b"message", &mut rng);
+    assert_ne!(commitment, other_commitment);
+
+    let bytes = bincode::serialize(&(commitment, &opening)).unwrap();
+    let (c, o): (Commitment, Opening) = bincode::deserialize(&bytes).unwrap();
+    assert!(c.verify(b"domain", b"message", &o).is_ok());
+}
+
+#[test]
+fn test_domain_separation() {
+    let mut rng = StdRng::from_seed([0; 32]);
+
+    // Moving bytes between the domain and the message changes the commitment.
+    let (commitment, opening) = commit(b"domain", b"message", &mut rng);
+    assert!(commitment.verify(b"domainm", b"essage", &opening).is_err());
+    assert!(commitment.verify(b"domai", b"nmessage", &opening).is_err());
+    assert!(commitment.verify(b"", b"domainmessage", &opening).is_err());
+
+    let (commitment, opening) = commit(b"", b"", &mut rng);
+    assert!(commitment.verify(b"", b"", &opening).is_ok());
+    assert!(commitment.verify(b"", &[0], &opening).is_err());
+}
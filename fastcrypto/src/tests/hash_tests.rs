@@ -2,9 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::encoding::{Base64, Encoding};
+#[cfg(not(feature = "fips"))]
+use crate::hash::{Blake2b256, Blake3, EllipticCurveMultisetHash, Keccak256, MultisetHash};
 use crate::hash::{
-    Blake2b256, Blake3, Digest, EllipticCurveMultisetHash, HashFunction, Keccak256, MultisetHash,
-    Sha256, Sha3_256, Sha3_512, Sha512,
+    Digest, DigestDomain, HashFunction, Sha256, Sha3_256, Sha3_512, Sha512, TaggedDigest,
 };
 use std::io::Write;
 
@@ -116,6 +117,7 @@ fn test_sha3_512() {
     );
 }
 
+#[cfg(not(feature = "fips"))]
 #[test]
 fn test_keccak_256() {
     let data =
@@ -127,6 +129,7 @@ fn test_keccak_256() {
     );
 }
 
+#[cfg(not(feature = "fips"))]
 #[test]
 fn test_blake2b_256() {
     let data =
@@ -138,6 +141,7 @@ fn test_blake2b_256() {
     );
 }
 
+#[cfg(not(feature = "fips"))]
 #[test]
 fn test_blake3() {
     let data =
@@ -149,6 +153,7 @@ fn test_blake3() {
     );
 }
 
+#[cfg(not(feature = "fips"))]
 #[test]
 fn test_accumulator() {
     let mut accumulator = EllipticCurveMultisetHash::default();
@@ -216,6 +221,7 @@ fn test_digest_debug() {
     assert_eq!(format!("{:?}", digest), Base64::encode(digest.as_ref()));
 }
 
+#[cfg(not(feature = "fips"))]
 #[test]
 fn test_accumulator_debug() {
     assert_eq!(
@@ -223,3 +229,62 @@ fn test_accumulator_debug() {
         "Accumulator"
     );
 }
+
+struct Transactions;
+impl DigestDomain for Transactions {
+    const NAME: &'static str = "Transaction";
+}
+
+struct Checkpoints;
+impl DigestDomain for Checkpoints {
+    const NAME: &'static str = "Checkpoint";
+}
+
+struct TransactionsWithSuffix;
+impl DigestDomain for TransactionsWithSuffix {
+    const NAME: &'static str = "Transaction::";
+}
+
+#[test]
+fn test_tagged_digest() {
+    let digest = TaggedDigest::<Transactions, 32>::compute::<Sha256, _>(b"content");
+    let input = [11u64.to_be_bytes().as_slice(), b"Transaction", b"content"].concat();
+    assert_eq!(digest.inner(), &Sha256::digest(input));
+    assert_ne!(
+        digest.inner(),
+        TaggedDigest::<Checkpoints, 32>::compute::<Sha256, _>(b"content").inner()
+    );
+    // The name cannot be extended into the content.
+    assert_ne!(
+        TaggedDigest::<Transactions, 32>::compute::<Sha256, _>(b"::content").inner(),
+        TaggedDigest::<TransactionsWithSuffix, 32>::compute::<Sha256, _>(b"content").inner()
+    );
+    assert_eq!(
+        TaggedDigest::<Transactions, 32>::compute_iterator::<Sha256, _, _>(
+            [b"con".as_slice(), b"tent".as_slice()].into_iter()
+        ),
+        digest
+    );
+
+    // Display, Debug and parsing.
+    let hex = hex::encode(digest.inner().digest);
+    assert_eq!(digest.to_string(), hex);
+    assert_eq!(format!("{:?}", digest), format!("Transaction({})", hex));
+    assert_eq!(
+        hex.parse::<TaggedDigest<Transactions, 32>>().unwrap(),
+        digest
+    );
+    assert!(hex[2..].parse::<TaggedDigest<Transactions, 32>>().is_err());
+    assert!("xyz".parse::<TaggedDigest<Transactions, 32>>().is_err());
+
+    // Ordering follows the bytes of the digest.
+    let smaller = TaggedDigest::<Transactions, 32>::new(Digest::new([0; 32]));
+    let larger = TaggedDigest::<Transactions, 32>::new(Digest::new([0xff; 32]));
+    assert!(smaller < digest && digest < larger);
+
+    // Serialized as the untyped digest.
+    let serialized = bincode::serialize(&digest).unwrap();
+    assert_eq!(serialized, bincode::serialize(digest.inner()).unwrap());
+    let deserialized: TaggedDigest<Transactions, 32> = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(deserialized, digest);
+}
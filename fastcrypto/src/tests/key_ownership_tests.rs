@@ -0,0 +1,75 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::bls12381::{min_pk, min_sig};
+use crate::ed25519::{Ed25519KeyPair, Ed25519Signature};
+use crate::error::FastCryptoError;
+use crate::key_ownership::*;
+use crate::secp256k1::Secp256k1KeyPair;
+use crate::secp256r1::Secp256r1KeyPair;
+use crate::traits::KeyPair;
+use rand::{rngs::StdRng, SeedableRng};
+
+fn test_key_ownership<K: KeyPair>() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let kp = K::generate(&mut rng);
+    let other = K::generate(&mut rng);
+    let challenge = KeyOwnershipChallenge::new(b"handshake", &mut rng).unwrap();
+    let proof = KeyOwnershipProof::new(&kp, &challenge);
+    assert_eq!(proof.nonce(), challenge.nonce());
+    assert!(proof.verify(kp.public(), &challenge).is_ok());
+
+    // Another key.
+    assert_eq!(
+        proof.verify(other.public(), &challenge),
+        Err(FastCryptoError::InvalidProof)
+    );
+
+    // Another nonce.
+    let other_challenge = KeyOwnershipChallenge::new(b"handshake", &mut rng).unwrap();
+    assert!(proof.verify(kp.public(), &other_challenge).is_err());
+
+    // Another domain with the same nonce.
+    let other_domain =
+        KeyOwnershipChallenge::from_nonce(b"registration", *challenge.nonce()).unwrap();
+    assert!(proof.verify(kp.public(), &other_domain).is_err());
+
+    // The prover can answer a challenge received from the verifier.
+    let received =
+        KeyOwnershipChallenge::from_nonce(challenge.domain(), *challenge.nonce()).unwrap();
+    assert!(KeyOwnershipProof::new(&kp, &received)
+        .verify(kp.public(), &challenge)
+        .is_ok());
+}
+
+#[test]
+fn test_all_schemes() {
+    test_key_ownership::<Ed25519KeyPair>();
+    test_key_ownership::<Secp256k1KeyPair>();
+    test_key_ownership::<Secp256r1KeyPair>();
+    test_key_ownership::<min_sig::BLS12381KeyPair>();
+    test_key_ownership::<min_pk::BLS12381KeyPair>();
+}
+
+#[test]
+fn test_invalid_domain() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    assert!(KeyOwnershipChallenge::new(b"", &mut rng).is_err());
+    assert_eq!(
+        KeyOwnershipChallenge::new(&[0u8; MAX_DOMAIN_LENGTH + 1], &mut rng),
+        Err(FastCryptoError::InputTooLong(MAX_DOMAIN_LENGTH))
+    );
+    assert!(KeyOwnershipChallenge::new(&[0u8; MAX_DOMAIN_LENGTH], &mut rng).is_ok());
+}
+
+#[test]
+fn test_serialization() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let kp = Ed25519KeyPair::generate(&mut rng);
+    let challenge = KeyOwnershipChallenge::new(b"handshake", &mut rng).unwrap();
+    let proof = KeyOwnershipProof::new(&kp, &challenge);
+    let bytes = bincode::serialize(&proof).unwrap();
+    let deserialized: KeyOwnershipProof<Ed25519Signature> = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(proof, deserialized);
+    assert!(deserialized.verify(kp.public(), &challenge).is_ok());
+}
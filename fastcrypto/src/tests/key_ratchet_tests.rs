@@ -0,0 +1,127 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::FastCryptoError;
+use crate::key_ratchet::*;
+
+fn pair(max_skip: u64) -> (KeyRatchet, KeyRatchet) {
+    (
+        KeyRatchet::new(&[1; 32], b"test", max_skip).unwrap(),
+        KeyRatchet::new(&[1; 32], b"test", max_skip).unwrap(),
+    )
+}
+
+#[test]
+fn test_in_order() {
+    let (mut sender, mut receiver) = pair(DEFAULT_MAX_SKIP);
+    let mut keys = vec![];
+    for i in 0..10 {
+        let key = sender.next_key();
+        assert_eq!(key.index(), i);
+        let ciphertext = key.encrypt(b"aad", &i.to_le_bytes());
+        let received = receiver.key_for(i).unwrap();
+        assert_eq!(received, key);
+        assert_eq!(
+            received.decrypt(b"aad", &ciphertext).unwrap(),
+            i.to_le_bytes()
+        );
+        assert!(received.decrypt(b"other", &ciphertext).is_err());
+        keys.push(key);
+    }
+    assert_eq!(receiver.skipped(), 0);
+
+    // All keys are distinct.
+    for (i, k1) in keys.iter().enumerate() {
+        for k2 in &keys[i + 1..] {
+            assert_ne!(k1.as_bytes(), k2.as_bytes());
+        }
+    }
+    assert_ne!(keys[0].cipher().1, keys[1].cipher().1);
+}
+
+#[test]
+fn test_chains_are_bound_to_secret_and_info() {
+    let key = KeyRatchet::new(&[1; 32], b"test", 10).unwrap().next_key();
+    let other_info = KeyRatchet::new(&[1; 32], b"other", 10).unwrap().next_key();
+    let other_secret = KeyRatchet::new(&[2; 32], b"test", 10).unwrap().next_key();
+    assert_ne!(key, other_info);
+    assert_ne!(key, other_secret);
+}
+
+#[test]
+fn test_out_of_order_and_skipped() {
+    let (mut sender, mut receiver) = pair(DEFAULT_MAX_SKIP);
+    let keys = (0..6).map(|_| sender.next_key()).collect::<Vec<_>>();
+
+    assert_eq!(receiver.key_for(4).unwrap(), keys[4]);
+    assert_eq!(receiver.skipped(), 4);
+    assert_eq!(receiver.index(), 5);
+    assert_eq!(receiver.key_for(1).unwrap(), keys[1]);
+    assert_eq!(receiver.key_for(5).unwrap(), keys[5]);
+    assert_eq!(receiver.key_for(0).unwrap(), keys[0]);
+    assert_eq!(receiver.skipped(), 2);
+
+    // Keys are returned once.
+    assert_eq!(receiver.key_for(1), Err(FastCryptoError::InvalidInput));
+    assert_eq!(receiver.key_for(4), Err(FastCryptoError::InvalidInput));
+
+    let skipped = receiver.take_skipped();
+    assert_eq!(skipped, vec![keys[2].clone(), keys[3].clone()]);
+    assert_eq!(receiver.skipped(), 0);
+    assert!(receiver.key_for(2).is_err());
+}
+
+#[test]
+fn test_max_skip() {
+    let (mut sender, mut receiver) = pair(3);
+    let keys = (0..10).map(|_| sender.next_key()).collect::<Vec<_>>();
+
+    // Skipping more than three keys fails and leaves the state unchanged.
+    assert_eq!(receiver.key_for(4), Err(FastCryptoError::InputTooLong(3)));
+    assert_eq!(receiver.index(), 0);
+    assert_eq!(receiver.skipped(), 0);
+    assert_eq!(receiver.key_for(3).unwrap(), keys[3]);
+    assert_eq!(receiver.skipped(), 3);
+
+    // Only the three most recent skipped keys are kept.
+    assert_eq!(receiver.key_for(6).unwrap(), keys[6]);
+    assert_eq!(receiver.skipped(), 3);
+    assert!(receiver.key_for(0).is_err());
+    assert_eq!(receiver.key_for(1), Err(FastCryptoError::InvalidInput));
+    assert_eq!(receiver.key_for(2).unwrap(), keys[2]);
+    assert_eq!(receiver.key_for(5).unwrap(), keys[5]);
+
+    receiver.skip_to(9).unwrap();
+    assert_eq!(receiver.index(), 9);
+    assert_eq!(receiver.key_for(8).unwrap(), keys[8]);
+    assert_eq!(receiver.key_for(9).unwrap(), keys[9]);
+}
+
+#[test]
+fn test_serialization() {
+    let (mut sender, mut receiver) = pair(DEFAULT_MAX_SKIP);
+    let keys = (0..4).map(|_| sender.next_key()).collect::<Vec<_>>();
+    receiver.key_for(2).unwrap();
+
+    let bytes = bcs::to_bytes(&receiver).unwrap();
+    let mut restored: KeyRatchet = bcs::from_bytes(&bytes).unwrap();
+    assert_eq!(restored.index(), 3);
+    assert_eq!(restored.key_for(0).unwrap(), keys[0]);
+    assert_eq!(restored.key_for(3).unwrap(), keys[3]);
+    assert_eq!(restored.key_for(1).unwrap(), keys[1]);
+
+    // Secrets are not printed.
+    assert!(!format!("{:?}", keys[0]).contains(&format!("{:?}", keys[0].as_bytes())));
+}
+
+#[test]
+fn test_deterministic() {
+    let mut ratchet = KeyRatchet::from_chain_key([0; 32], 10);
+    let first = ratchet.next_key();
+    let second = ratchet.next_key();
+    assert_ne!(first.as_bytes(), &[0; 32]);
+    assert_ne!(first, second);
+    // The ratchet is deterministic.
+    let mut other = KeyRatchet::from_chain_key([0; 32], 10);
+    assert_eq!(other.key_for(1).unwrap(), second);
+}
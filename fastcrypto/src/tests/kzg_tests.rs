@@ -0,0 +1,244 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::FastCryptoError;
+use crate::groups::bls12381::{G1Element, G2Element, Scalar};
+use crate::groups::{FixedBaseMultiplication, GroupElement, Scalar as _};
+use crate::kzg::*;
+use crate::serde_helpers::ToFromByteArray;
+use rand::{rngs::StdRng, SeedableRng};
+
+const DEGREE: usize = 7;
+
+fn polynomial(offset: u64) -> Vec<Scalar> {
+    (0..=DEGREE as u64)
+        .map(|i| Scalar::from(i * i + offset))
+        .collect()
+}
+
+fn tau_powers(tau: u64, g1_length: usize, g2_length: usize) -> (Vec<G1Element>, Vec<G2Element>) {
+    let tau = Scalar::from(tau);
+    let mut power = Scalar::generator();
+    let mut powers_g1 = Vec::new();
+    let mut powers_g2 = Vec::new();
+    for i in 0..g1_length.max(g2_length) {
+        if i < g1_length {
+            powers_g1.push(G1Element::mul_generator(&power));
+        }
+        if i < g2_length {
+            powers_g2.push(G2Element::mul_generator(&power));
+        }
+        power = power * tau;
+    }
+    (powers_g1, powers_g2)
+}
+
+#[test]
+fn test_open_and_verify() {
+    let parameters = KzgParameters::generate(DEGREE, &mut StdRng::from_seed([0; 32])).unwrap();
+    assert_eq!(parameters.max_degree(), DEGREE);
+    let polynomial = polynomial(1);
+    let commitment = parameters.commit(&polynomial).unwrap();
+    let point = Scalar::from(5);
+    let (value, proof) = parameters.open(&polynomial, &point).unwrap();
+    let expected = polynomial
+        .iter()
+        .rev()
+        .fold(Scalar::zero(), |acc, c| acc * point + c);
+    assert_eq!(value, expected);
+    assert!(parameters
+        .verify(&commitment, &point, &value, &proof)
+        .is_ok());
+
+    // Wrong values, points, commitments and proofs.
+    let one = Scalar::generator();
+    assert_eq!(
+        parameters.verify(&commitment, &point, &(value + one), &proof),
+        Err(FastCryptoError::InvalidProof)
+    );
+    assert!(parameters
+        .verify(&commitment, &(point + one), &value, &proof)
+        .is_err());
+    let other = parameters.commit(&polynomial(2)).unwrap();
+    assert!(parameters.verify(&other, &point, &value, &proof).is_err());
+    let other_proof = OpeningProof(proof.0 + G1Element::generator());
+    assert!(parameters
+        .verify(&commitment, &point, &value, &other_proof)
+        .is_err());
+
+    // Shorter polynomials are padded with zeros.
+    let (value, proof) = parameters.open(&polynomial[..3], &point).unwrap();
+    let commitment = parameters.commit(&polynomial[..3]).unwrap();
+    assert!(parameters
+        .verify(&commitment, &point, &value, &proof)
+        .is_ok());
+
+    // Too high degrees.
+    let too_long = [polynomial.as_slice(), &[one]].concat();
+    assert_eq!(
+        parameters.commit(&too_long),
+        Err(FastCryptoError::InputTooLong(DEGREE + 1))
+    );
+    assert!(parameters.open(&too_long, &point).is_err());
+}
+
+#[test]
+fn test_batch_open_and_verify() {
+    let parameters = KzgParameters::generate(DEGREE, &mut StdRng::from_seed([0; 32])).unwrap();
+    let polynomials = (1..=3).map(polynomial).collect::<Vec<_>>();
+    let commitments = polynomials
+        .iter()
+        .map(|p| parameters.commit(p).unwrap())
+        .collect::<Vec<_>>();
+    let point = Scalar::from(9);
+    let (values, proof) = parameters.batch_open(&polynomials, &point).unwrap();
+    for (p, v) in polynomials.iter().zip(&values) {
+        assert_eq!(parameters.open(p, &point).unwrap().0, *v);
+    }
+    assert!(parameters
+        .batch_verify(&commitments, &point, &values, &proof)
+        .is_ok());
+
+    // Tampered values, reordered commitments and wrong lengths.
+    let mut tampered = values.clone();
+    tampered[1] = tampered[1] + Scalar::generator();
+    assert!(parameters
+        .batch_verify(&commitments, &point, &tampered, &proof)
+        .is_err());
+    let mut reordered = commitments.clone();
+    reordered.swap(0, 2);
+    assert!(parameters
+        .batch_verify(&reordered, &point, &values, &proof)
+        .is_err());
+    assert!(parameters
+        .batch_verify(&commitments, &point, &values[..2], &proof)
+        .is_err());
+    assert!(parameters.batch_verify(&[], &point, &[], &proof).is_err());
+    assert!(parameters.batch_open(&[], &point).is_err());
+
+    // A single polynomial.
+    let (values, proof) = parameters.batch_open(&polynomials[..1], &point).unwrap();
+    assert!(parameters
+        .batch_verify(&commitments[..1], &point, &values, &proof)
+        .is_ok());
+}
+
+#[test]
+fn test_from_powers() {
+    let (powers_g1, powers_g2) = tau_powers(1234, DEGREE + 1, 3);
+    let parameters = KzgParameters::from_powers(powers_g1.clone(), &powers_g2).unwrap();
+    assert_eq!(parameters.max_degree(), DEGREE);
+    let polynomial = polynomial(1);
+    let (value, proof) = parameters.open(&polynomial, &Scalar::from(3)).unwrap();
+    assert!(parameters
+        .verify(
+            &parameters.commit(&polynomial).unwrap(),
+            &Scalar::from(3),
+            &value,
+            &proof
+        )
+        .is_ok());
+
+    // Powers of different trapdoors.
+    let (_, other_g2) = tau_powers(1235, 0, 3);
+    assert!(KzgParameters::from_powers(powers_g1.clone(), &other_g2).is_err());
+    let mut modified = powers_g1.clone();
+    modified[4] = modified[4] + G1Element::generator();
+    assert!(KzgParameters::from_powers(modified, &powers_g2).is_err());
+    let mut modified = powers_g2.clone();
+    modified[2] = modified[2] + G2Element::generator();
+    assert!(KzgParameters::from_powers(powers_g1.clone(), &modified).is_err());
+
+    // Missing generators and too few powers.
+    assert!(KzgParameters::from_powers(powers_g1[1..].to_vec(), &powers_g2).is_err());
+    assert!(KzgParameters::from_powers(powers_g1.clone(), &powers_g2[1..]).is_err());
+    assert!(KzgParameters::from_powers(powers_g1[..1].to_vec(), &powers_g2).is_err());
+    assert!(KzgParameters::from_powers(powers_g1, &powers_g2[..1]).is_err());
+}
+
+#[test]
+fn test_from_ceremony() {
+    let (powers_g1, powers_g2) = tau_powers(42, 16, 4);
+    let json = format!(
+        r#"{{"G1Powers":[{}],"G2Powers":[{}]}}"#,
+        powers_g1
+            .iter()
+            .map(|p| format!("\"0x{}\"", hex::encode(p.to_byte_array())))
+            .collect::<Vec<_>>()
+            .join(","),
+        powers_g2
+            .iter()
+            .map(|p| format!("\"0x{}\"", hex::encode(p.to_byte_array())))
+            .collect::<Vec<_>>()
+            .join(",")
+    );
+    let ceremony: CeremonyPowers = serde_json::from_str(&json).unwrap();
+    let parameters = KzgParameters::from_ceremony(&ceremony, DEGREE).unwrap();
+    assert_eq!(
+        parameters,
+        KzgParameters::from_powers(powers_g1[..=DEGREE].to_vec(), &powers_g2).unwrap()
+    );
+    assert_eq!(
+        KzgParameters::from_ceremony(&ceremony, 15)
+            .unwrap()
+            .max_degree(),
+        15
+    );
+
+    // Not enough powers.
+    assert!(KzgParameters::from_ceremony(&ceremony, 16).is_err());
+
+    // Invalid hex and points.
+    let mut invalid = ceremony.clone();
+    invalid.g1_powers[3] = "0xzz".to_string();
+    assert!(KzgParameters::from_ceremony(&invalid, DEGREE).is_err());
+    let mut invalid = ceremony.clone();
+    invalid.g2_powers[1].pop();
+    assert!(KzgParameters::from_ceremony(&invalid, DEGREE).is_err());
+    let mut invalid = ceremony;
+    invalid.g1_powers[2] = format!("0x{}", hex::encode([0xffu8; 48]));
+    assert!(KzgParameters::from_ceremony(&invalid, DEGREE).is_err());
+}
+
+#[test]
+fn test_from_powers_of_tau_response() {
+    // A response with 2^2 powers, i.e., 7 powers in G1 and 4 powers in G2.
+    let (powers_g1, powers_g2) = tau_powers(7, 7, 4);
+    let mut bytes = vec![0u8; 64];
+    powers_g1
+        .iter()
+        .for_each(|p| bytes.extend_from_slice(&p.to_byte_array()));
+    powers_g2
+        .iter()
+        .for_each(|p| bytes.extend_from_slice(&p.to_byte_array()));
+
+    for max_degree in 1..7 {
+        let parameters = KzgParameters::from_powers_of_tau_response(&bytes, 2, max_degree).unwrap();
+        assert_eq!(
+            parameters,
+            KzgParameters::from_powers(powers_g1[..=max_degree].to_vec(), &powers_g2).unwrap()
+        );
+    }
+    assert!(KzgParameters::from_powers_of_tau_response(&bytes, 2, 7).is_err());
+    assert!(KzgParameters::from_powers_of_tau_response(&bytes, 3, 3).is_err());
+    assert!(KzgParameters::from_powers_of_tau_response(&bytes, 64, 3).is_err());
+
+    // Truncated and modified files.
+    let g2_end = 64 + 7 * 48 + 2 * 96;
+    assert!(KzgParameters::from_powers_of_tau_response(&bytes[..g2_end], 2, 3).is_ok());
+    assert_eq!(
+        KzgParameters::from_powers_of_tau_response(&bytes[..g2_end - 1], 2, 3),
+        Err(FastCryptoError::InputTooShort(g2_end))
+    );
+    let mut modified = bytes.clone();
+    modified[64 + 2 * 48..64 + 3 * 48].copy_from_slice(&G1Element::generator().to_byte_array());
+    assert!(KzgParameters::from_powers_of_tau_response(&modified, 2, 3).is_err());
+}
+
+#[test]
+fn test_serde() {
+    let parameters = KzgParameters::generate(3, &mut StdRng::from_seed([0; 32])).unwrap();
+    let serialized = bincode::serialize(&parameters).unwrap();
+    let deserialized: KzgParameters = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(deserialized, parameters);
+}
@@ -0,0 +1,51 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::FastCryptoError;
+use crate::limits::{current, Limit, Limits};
+
+const ALL_LIMITS: [Limit; 4] = [
+    Limit::PublicInputs,
+    Limit::AggregateParticipants,
+    Limit::ProofBytes,
+    Limit::MerkleProofDepth,
+];
+
+#[test]
+fn test_check() {
+    let limits = Limits::default();
+    for limit in ALL_LIMITS {
+        let maximum = limit.default_maximum();
+        assert_eq!(limits.maximum(limit), maximum);
+        assert!(limits.check(limit, 0).is_ok());
+        assert!(limits.check(limit, maximum).is_ok());
+        assert_eq!(
+            limits.check(limit, maximum + 1),
+            Err(FastCryptoError::InputTooLong(maximum))
+        );
+    }
+
+    // Changing one limit does not affect the others.
+    let limits = Limits::default().with_maximum(Limit::ProofBytes, 10);
+    assert_eq!(limits.maximum(Limit::ProofBytes), 10);
+    assert_eq!(
+        limits.check(Limit::ProofBytes, 11),
+        Err(FastCryptoError::InputTooLong(10))
+    );
+    assert!(limits
+        .check(Limit::PublicInputs, Limit::PublicInputs.default_maximum())
+        .is_ok());
+    assert_eq!(
+        limits
+            .with_maximum(Limit::ProofBytes, 0)
+            .check(Limit::ProofBytes, 1),
+        Err(FastCryptoError::InputTooLong(0))
+    );
+}
+
+#[test]
+fn test_default_limits() {
+    // Other tests rely on the default limits, so they are never replaced in unit tests.
+    assert_eq!(current(), Limits::default());
+    assert_eq!(Limit::MerkleProofDepth.to_string(), "Merkle proof depth");
+}
@@ -0,0 +1,68 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::linkable_ring_signatures::{PrivateKey, PublicKey, RingSignature};
+use crate::test_helpers::verify_serialization;
+use rand::{rngs::StdRng, SeedableRng as _};
+
+const MSG: &[u8] = b"test message";
+
+fn keys(n: usize, rng: &mut StdRng) -> (Vec<PrivateKey>, Vec<PublicKey>) {
+    let private_keys = (0..n)
+        .map(|_| PrivateKey::generate(rng))
+        .collect::<Vec<_>>();
+    let ring = private_keys.iter().map(PublicKey::from).collect();
+    (private_keys, ring)
+}
+
+#[test]
+fn test_sign_verify() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let (private_keys, ring) = keys(5, &mut rng);
+
+    for private_key in &private_keys {
+        let signature = RingSignature::sign(MSG, &ring, private_key, &mut rng).unwrap();
+        assert!(signature.verify(MSG, &ring).is_ok());
+        assert!(signature.verify(b"other message", &ring).is_err());
+        assert!(signature.verify(MSG, &ring[1..]).is_err());
+
+        let mut reordered = ring.clone();
+        reordered.swap(0, 1);
+        assert!(signature.verify(MSG, &reordered).is_err());
+    }
+
+    // Rings of size one.
+    let signature = RingSignature::sign(MSG, &ring[..1], &private_keys[0], &mut rng).unwrap();
+    assert!(signature.verify(MSG, &ring[..1]).is_ok());
+
+    // The signer must be in the ring.
+    let outsider = PrivateKey::generate(&mut rng);
+    assert!(RingSignature::sign(MSG, &ring, &outsider, &mut rng).is_err());
+}
+
+#[test]
+fn test_linkability() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let (private_keys, ring) = keys(4, &mut rng);
+    let (_, mut other_ring) = keys(3, &mut rng);
+    other_ring.push(ring[1]);
+
+    let s1 = RingSignature::sign(MSG, &ring, &private_keys[1], &mut rng).unwrap();
+    let s2 =
+        RingSignature::sign(b"other message", &other_ring, &private_keys[1], &mut rng).unwrap();
+    let s3 = RingSignature::sign(MSG, &ring, &private_keys[2], &mut rng).unwrap();
+
+    assert!(s2.verify(b"other message", &other_ring).is_ok());
+    assert!(s1.is_linked(&s2));
+    assert!(!s1.is_linked(&s3));
+    assert_eq!(s1.key_image(), s2.key_image());
+}
+
+#[test]
+fn test_serialization() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let (private_keys, ring) = keys(3, &mut rng);
+    let signature = RingSignature::sign(MSG, &ring, &private_keys[0], &mut rng).unwrap();
+    verify_serialization(&signature, None);
+    verify_serialization(&ring[0], None);
+}
@@ -0,0 +1,152 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::ed25519::{Ed25519KeyPair, Ed25519PrivateKey};
+use crate::error::FastCryptoError;
+use crate::minisign::*;
+use crate::traits::ToFromBytes;
+use rand::{rngs::StdRng, SeedableRng};
+
+// The test vectors below are for the Ed25519 key with seed [7; 32] and key id 0x0807060504030201,
+// and were created with an independent implementation of the format.
+const KEY_ID: MinisignKeyId = [1, 2, 3, 4, 5, 6, 7, 8];
+const MESSAGE: &[u8] = b"hello minisign\n";
+const TRUSTED_COMMENT: &str = "timestamp:1700000000\tfile:hello.txt";
+
+const PUBLIC_KEY: &str = "untrusted comment: minisign public key 0807060504030201
+RWQBAgMEBQYHCOpKbGPinFIKvvVQexMuxfmVR3auvr57kkIe6mkURtIs
+";
+
+// The public key with seed [8; 32] and the same key id.
+const OTHER_PUBLIC_KEY: &str = "RWQBAgMEBQYHCBOY9ixtGkV8UbpqS189vS9p/KkyFiGNyJl+QWvRfZPK";
+
+const SIGNATURE: &str = "untrusted comment: signature from minisign secret key
+RUQBAgMEBQYHCFfdV1BBNQz2YHeyIX3S7w4olmIuu2dQTKuILLOb2m0F33hFSuTFK7PwvrIRhqkGo5ZOL5GnEnCn3Fpc27PQEAc=
+trusted comment: timestamp:1700000000\tfile:hello.txt
+F/tjOlejNrN+dw1E3d1/rZzqpDK0b9UATFOMC6Fg3du76fgACQMWOOwFfImhAraJwT7CrBVaDvZOU6jGukPaDQ==
+";
+
+const LEGACY_SIGNATURE: &str = "untrusted comment: signature from minisign secret key
+RWQBAgMEBQYHCJ5juEXoC0ARsKWMVBJKnngwmiUhIFzpeFUhdjoD+XhAQBa2vnVePF5eX2Jko16ru3bdR4CQU7GyZXpffTvp0gQ=
+trusted comment: timestamp:1700000000\tfile:hello.txt
+p5bLd3zzn9trRrjPSuLBmcE74XHf+WPzeaAoERzWpSKoDy1k3sQBUYN7DeicnRDtF2Vm/i00vKMh78ni+/whAg==
+";
+
+fn key_pair() -> MinisignKeyPair {
+    let private = Ed25519PrivateKey::from_bytes(&[7; 32]).unwrap();
+    MinisignKeyPair::new(KEY_ID, Ed25519KeyPair::from(private))
+}
+
+#[test]
+fn test_sign() {
+    let signature = key_pair().sign(MESSAGE, TRUSTED_COMMENT).unwrap();
+    assert_eq!(signature.algorithm(), MinisignAlgorithm::Prehashed);
+    assert_eq!(signature.encode(), SIGNATURE);
+    assert_eq!(
+        key_pair().sign_reader(MESSAGE, TRUSTED_COMMENT).unwrap(),
+        signature
+    );
+    assert!(signature.verify(&key_pair().public(), MESSAGE).is_ok());
+
+    // Trusted comments must be on a single line.
+    assert!(key_pair().sign(MESSAGE, "a\nb").is_err());
+    assert!(key_pair().sign(MESSAGE, "a\rb").is_err());
+}
+
+#[test]
+fn test_verify() {
+    let public_key = MinisignPublicKey::decode(PUBLIC_KEY).unwrap();
+    for (encoded, algorithm) in [
+        (SIGNATURE, MinisignAlgorithm::Prehashed),
+        (LEGACY_SIGNATURE, MinisignAlgorithm::Legacy),
+    ] {
+        let signature = MinisignSignature::decode(encoded).unwrap();
+        assert_eq!(signature.algorithm(), algorithm);
+        assert_eq!(signature.key_id(), &KEY_ID);
+        assert_eq!(signature.trusted_comment(), TRUSTED_COMMENT);
+        assert_eq!(
+            signature.untrusted_comment(),
+            "signature from minisign secret key"
+        );
+        assert_eq!(signature.encode(), encoded);
+        assert!(signature.verify(&public_key, MESSAGE).is_ok());
+        assert!(signature.verify_reader(&public_key, MESSAGE).is_ok());
+
+        // Other messages and keys.
+        assert_eq!(
+            signature.verify(&public_key, b"hello minisign"),
+            Err(FastCryptoError::InvalidSignature)
+        );
+        let other_key = MinisignPublicKey::from_base64(OTHER_PUBLIC_KEY).unwrap();
+        assert!(signature.verify(&other_key, MESSAGE).is_err());
+        let other_id =
+            MinisignPublicKey::new([0; MINISIGN_KEY_ID_LENGTH], public_key.public_key().clone());
+        assert!(signature.verify(&other_id, MESSAGE).is_err());
+
+        // The trusted comment is signed, but the untrusted comment is not.
+        let modified = encoded.replace("hello.txt", "other.txt");
+        let modified = MinisignSignature::decode(&modified).unwrap();
+        assert!(modified.verify(&public_key, MESSAGE).is_err());
+        let mut modified = signature.clone();
+        modified.set_untrusted_comment("another comment").unwrap();
+        assert!(modified.verify(&public_key, MESSAGE).is_ok());
+        assert!(MinisignSignature::decode(&modified.encode())
+            .unwrap()
+            .verify(&public_key, MESSAGE)
+            .is_ok());
+        assert!(modified.set_untrusted_comment("a\nb").is_err());
+    }
+}
+
+#[test]
+fn test_invalid_signatures() {
+    let lines = SIGNATURE.lines().collect::<Vec<_>>();
+    assert!(MinisignSignature::decode(&lines.join("\r\n")).is_ok());
+
+    // Missing lines and prefixes.
+    assert!(MinisignSignature::decode(&lines[..3].join("\n")).is_err());
+    assert!(MinisignSignature::decode(&[SIGNATURE, "\n"].concat()).is_err());
+    assert!(MinisignSignature::decode("").is_err());
+    assert!(MinisignSignature::decode(&SIGNATURE.replace("untrusted comment", "comment")).is_err());
+    assert!(MinisignSignature::decode(&SIGNATURE.replace("trusted comment: t", "t")).is_err());
+
+    // Unknown algorithms and wrong lengths.
+    assert!(MinisignSignature::decode(&SIGNATURE.replace("RUQB", "RVhB")).is_err());
+    assert_eq!(
+        MinisignSignature::decode(&SIGNATURE.replace("EAc=", "")),
+        Err(FastCryptoError::InputLengthWrong(74))
+    );
+    assert!(MinisignSignature::decode(&SIGNATURE.replace("aDQ==", "*DQ==")).is_err());
+}
+
+#[test]
+fn test_public_keys() {
+    let public_key = MinisignPublicKey::decode(PUBLIC_KEY).unwrap();
+    assert_eq!(public_key, key_pair().public());
+    assert_eq!(public_key.key_id(), &KEY_ID);
+    assert_eq!(public_key.encode(), PUBLIC_KEY);
+
+    // The comment is optional.
+    let encoded = PUBLIC_KEY.lines().nth(1).unwrap();
+    assert_eq!(public_key.to_base64(), encoded);
+    assert_eq!(MinisignPublicKey::decode(encoded).unwrap(), public_key);
+    assert_eq!(MinisignPublicKey::from_base64(encoded).unwrap(), public_key);
+
+    // Wrong comments, algorithms and lengths.
+    assert!(MinisignPublicKey::decode(&PUBLIC_KEY.replace("untrusted", "trusted")).is_err());
+    assert!(MinisignPublicKey::decode(&[PUBLIC_KEY, encoded].concat()).is_err());
+    assert!(MinisignPublicKey::from_base64(&encoded.replace("RWQ", "RUQ")).is_err());
+    assert!(MinisignPublicKey::from_base64(&encoded[..encoded.len() - 4]).is_err());
+}
+
+#[test]
+fn test_generate() {
+    let key_pair = MinisignKeyPair::generate(&mut StdRng::from_seed([0; 32]));
+    let public_key = MinisignPublicKey::decode(&key_pair.public().encode()).unwrap();
+    let message = vec![5u8; 100_000];
+    let signature = key_pair.sign(&message, "").unwrap();
+    let signature = MinisignSignature::decode(&signature.encode()).unwrap();
+    assert_eq!(signature.trusted_comment(), "");
+    assert!(signature.verify(&public_key, &message).is_ok());
+    assert!(signature.verify(&public_key, &message[1..]).is_err());
+}
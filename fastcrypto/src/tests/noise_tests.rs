@@ -0,0 +1,265 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::noise::{
+    HandshakePattern, HandshakeState, NoiseKeyPair, NoisePrivateKey, NoisePublicKey,
+    TransportState, MAX_MESSAGE_LENGTH,
+};
+use rand::{rngs::StdRng, SeedableRng as _};
+
+fn handshake(
+    mut initiator: HandshakeState,
+    mut responder: HandshakeState,
+) -> (TransportState, TransportState) {
+    let mut turn = 0;
+    while !initiator.is_finished() {
+        let (writer, reader) = match turn % 2 {
+            0 => (&mut initiator, &mut responder),
+            _ => (&mut responder, &mut initiator),
+        };
+        let payload = format!("payload {}", turn);
+        let message = writer.write_message(payload.as_bytes()).unwrap();
+        assert_eq!(reader.read_message(&message).unwrap(), payload.as_bytes());
+        turn += 1;
+    }
+    assert!(responder.is_finished());
+    assert_eq!(initiator.handshake_hash(), responder.handshake_hash());
+    (
+        initiator.into_transport().unwrap(),
+        responder.into_transport().unwrap(),
+    )
+}
+
+fn check_transport(initiator: &mut TransportState, responder: &mut TransportState) {
+    assert_eq!(initiator.handshake_hash(), responder.handshake_hash());
+    for i in 0..3 {
+        let message = format!("message {}", i);
+        let ciphertext = initiator.write_message(message.as_bytes()).unwrap();
+        assert_eq!(
+            responder.read_message(&ciphertext).unwrap(),
+            message.as_bytes()
+        );
+        let ciphertext = responder.write_message(message.as_bytes()).unwrap();
+        assert_eq!(
+            initiator.read_message(&ciphertext).unwrap(),
+            message.as_bytes()
+        );
+    }
+}
+
+#[test]
+fn test_x25519_keys() {
+    // Test vectors from section 6.1 of RFC 7748.
+    let alice = NoiseKeyPair::from(
+        NoisePrivateKey::from_bytes(
+            &hex::decode("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a")
+                .unwrap(),
+        )
+        .unwrap(),
+    );
+    assert_eq!(
+        hex::encode(alice.public()),
+        "8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a"
+    );
+    let bob = NoiseKeyPair::from(
+        NoisePrivateKey::from_bytes(
+            &hex::decode("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb")
+                .unwrap(),
+        )
+        .unwrap(),
+    );
+    assert_eq!(
+        hex::encode(bob.public()),
+        "de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f"
+    );
+}
+
+#[test]
+fn test_xx() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let initiator_key = NoiseKeyPair::generate(&mut rng);
+    let responder_key = NoiseKeyPair::generate(&mut rng);
+    let initiator_public = *initiator_key.public();
+    let responder_public = *responder_key.public();
+
+    let initiator = HandshakeState::initiator(
+        HandshakePattern::XX,
+        b"prologue",
+        initiator_key,
+        None,
+        &mut rng,
+    )
+    .unwrap();
+    let responder =
+        HandshakeState::responder(HandshakePattern::XX, b"prologue", responder_key, &mut rng)
+            .unwrap();
+    let (mut initiator, mut responder) = handshake(initiator, responder);
+    assert_eq!(initiator.remote_static(), &responder_public);
+    assert_eq!(responder.remote_static(), &initiator_public);
+    check_transport(&mut initiator, &mut responder);
+}
+
+#[test]
+fn test_ik() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let initiator_key = NoiseKeyPair::generate(&mut rng);
+    let responder_key = NoiseKeyPair::generate(&mut rng);
+    let initiator_public = *initiator_key.public();
+    let responder_public = *responder_key.public();
+
+    // The static key of the responder is required.
+    assert!(HandshakeState::initiator(
+        HandshakePattern::IK,
+        b"",
+        NoiseKeyPair::generate(&mut rng),
+        None,
+        &mut rng
+    )
+    .is_err());
+
+    let initiator = HandshakeState::initiator(
+        HandshakePattern::IK,
+        b"",
+        initiator_key,
+        Some(responder_public),
+        &mut rng,
+    )
+    .unwrap();
+    let responder =
+        HandshakeState::responder(HandshakePattern::IK, b"", responder_key, &mut rng).unwrap();
+    let (mut initiator, mut responder) = handshake(initiator, responder);
+    assert_eq!(responder.remote_static(), &initiator_public);
+    check_transport(&mut initiator, &mut responder);
+}
+
+#[test]
+fn test_ik_wrong_responder_key() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let initiator_key = NoiseKeyPair::generate(&mut rng);
+    let responder_key = NoiseKeyPair::generate(&mut rng);
+    let other_key = NoiseKeyPair::generate(&mut rng);
+
+    let mut initiator = HandshakeState::initiator(
+        HandshakePattern::IK,
+        b"",
+        initiator_key,
+        Some(*other_key.public()),
+        &mut rng,
+    )
+    .unwrap();
+    let mut responder =
+        HandshakeState::responder(HandshakePattern::IK, b"", responder_key, &mut rng).unwrap();
+    let message = initiator.write_message(b"").unwrap();
+    assert!(responder.read_message(&message).is_err());
+}
+
+#[test]
+fn test_failures() {
+    let mut rng = StdRng::from_seed([0; 32]);
+
+    // Different prologues.
+    let mut initiator = HandshakeState::initiator(
+        HandshakePattern::XX,
+        b"a",
+        NoiseKeyPair::generate(&mut rng),
+        None,
+        &mut rng,
+    )
+    .unwrap();
+    let mut responder = HandshakeState::responder(
+        HandshakePattern::XX,
+        b"b",
+        NoiseKeyPair::generate(&mut rng),
+        &mut rng,
+    )
+    .unwrap();
+
+    // Messages must be sent in turn.
+    assert!(responder.write_message(b"").is_err());
+    assert!(initiator.read_message(&[0u8; 32]).is_err());
+    assert!(!initiator.is_finished());
+
+    let message = initiator.write_message(b"").unwrap();
+    responder.read_message(&message).unwrap();
+    let message = responder.write_message(b"").unwrap();
+    assert!(initiator.read_message(&message).is_err());
+
+    // Tampered transport messages are rejected.
+    let initiator = HandshakeState::initiator(
+        HandshakePattern::XX,
+        b"",
+        NoiseKeyPair::generate(&mut rng),
+        None,
+        &mut rng,
+    )
+    .unwrap();
+    let responder = HandshakeState::responder(
+        HandshakePattern::XX,
+        b"",
+        NoiseKeyPair::generate(&mut rng),
+        &mut rng,
+    )
+    .unwrap();
+    assert!(initiator.is_my_turn());
+    let (mut initiator, mut responder) = handshake(initiator, responder);
+    let mut ciphertext = initiator.write_message(b"Hello, world!").unwrap();
+    ciphertext[0] ^= 1;
+    assert!(responder.read_message(&ciphertext).is_err());
+    assert!(responder.read_message(&[0u8; 4]).is_err());
+
+    // A small order public key is rejected.
+    let mut responder = HandshakeState::responder(
+        HandshakePattern::XX,
+        b"",
+        NoiseKeyPair::generate(&mut rng),
+        &mut rng,
+    )
+    .unwrap();
+    assert!(responder.read_message(&[0u8; 32]).is_ok());
+    assert!(responder.write_message(b"").is_err());
+    assert_eq!(
+        NoisePublicKey::from_bytes(&[0u8; 31]).unwrap_err(),
+        crate::error::FastCryptoError::InputLengthWrong(32)
+    );
+}
+
+#[test]
+fn test_long_handshake_payload() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let mut initiator = HandshakeState::initiator(
+        HandshakePattern::XX,
+        b"",
+        NoiseKeyPair::generate(&mut rng),
+        None,
+        &mut rng,
+    )
+    .unwrap();
+    let mut responder = HandshakeState::responder(
+        HandshakePattern::XX,
+        b"",
+        NoiseKeyPair::generate(&mut rng),
+        &mut rng,
+    )
+    .unwrap();
+
+    // The first message is the ephemeral key followed by the plaintext payload.
+    assert!(initiator
+        .write_message(&vec![0; MAX_MESSAGE_LENGTH - 31])
+        .is_err());
+    let message = initiator
+        .write_message(&vec![0; MAX_MESSAGE_LENGTH - 32])
+        .unwrap();
+    assert_eq!(message.len(), MAX_MESSAGE_LENGTH);
+    responder.read_message(&message).unwrap();
+
+    // The second message has an ephemeral key, an encrypted static key and an encrypted payload.
+    // A rejected payload does not modify the state, so the handshake can continue.
+    let max_payload = MAX_MESSAGE_LENGTH - 32 - (32 + 16) - 16;
+    assert!(responder.write_message(&vec![0; max_payload + 1]).is_err());
+    let message = responder.write_message(&vec![0; max_payload]).unwrap();
+    assert_eq!(message.len(), MAX_MESSAGE_LENGTH);
+    initiator.read_message(&message).unwrap();
+    let message = initiator.write_message(b"").unwrap();
+    responder.read_message(&message).unwrap();
+    assert!(initiator.is_finished() && responder.is_finished());
+}
@@ -0,0 +1,35 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::ed25519::Ed25519KeyPair;
+use crate::one_time_signer::*;
+use crate::secp256k1::Secp256k1KeyPair;
+use crate::traits::{KeyPair, VerifyingKey};
+use rand::{rngs::StdRng, SeedableRng};
+
+#[test]
+fn test_one_time_signer() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let key_pair = Ed25519KeyPair::generate(&mut rng);
+    let pk = key_pair.public().clone();
+    let signer = OneTimeSigner::new(key_pair);
+    assert_eq!(signer.public(), &pk);
+    let signature = signer.sign(b"message");
+    assert!(pk.verify(b"message", &signature).is_ok());
+}
+
+#[test]
+fn test_bounded_use_signer() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let mut signer = BoundedUseSigner::<Secp256k1KeyPair>::generate(3, &mut rng).unwrap();
+    let pk = signer.public().clone();
+    for i in 0..3u8 {
+        assert_eq!(signer.remaining(), 3 - i as u64);
+        let signature = signer.sign(&[i]).unwrap();
+        assert!(pk.verify(&[i], &signature).is_ok());
+    }
+    assert_eq!(signer.remaining(), 0);
+    assert!(signer.sign(b"message").is_err());
+
+    assert!(BoundedUseSigner::<Ed25519KeyPair>::generate(0, &mut rng).is_err());
+}
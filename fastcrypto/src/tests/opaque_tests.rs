@@ -0,0 +1,99 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::opaque::{ClientLogin, ClientRegistration, RegistrationRecord, ServerSetup, KE3};
+use crate::test_helpers::verify_serialization;
+use rand::{rngs::StdRng, SeedableRng as _};
+
+const PASSWORD: &[u8] = b"password";
+const CREDENTIAL_IDENTIFIER: &[u8] = b"alice";
+
+fn register(server: &ServerSetup, rng: &mut StdRng) -> RegistrationRecord {
+    let (state, request) = ClientRegistration::start(PASSWORD, rng).unwrap();
+    let response = server
+        .registration_response(&request, CREDENTIAL_IDENTIFIER)
+        .unwrap();
+    verify_serialization(&request, None);
+    verify_serialization(&response, None);
+    state.finish(&response, rng).unwrap().0
+}
+
+#[test]
+fn test_registration_and_login() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let server = ServerSetup::new(&mut rng);
+
+    let (state, request) = ClientRegistration::start(PASSWORD, &mut rng).unwrap();
+    let response = server
+        .registration_response(&request, CREDENTIAL_IDENTIFIER)
+        .unwrap();
+    let (record, export_key) = state.finish(&response, &mut rng).unwrap();
+    verify_serialization(&record, None);
+
+    for _ in 0..2 {
+        let (client_state, ke1) = ClientLogin::start(PASSWORD, &mut rng).unwrap();
+        let (server_state, ke2) = server
+            .login_start(&record, CREDENTIAL_IDENTIFIER, &ke1, &mut rng)
+            .unwrap();
+        let (ke3, client_session_key, client_export_key) = client_state.finish(&ke2).unwrap();
+        let server_session_key = server_state.finish(&ke3).unwrap();
+
+        assert_eq!(client_session_key, server_session_key);
+        assert_eq!(client_export_key, export_key);
+
+        verify_serialization(&ke1, None);
+        verify_serialization(&ke2, None);
+        verify_serialization(&ke3, None);
+    }
+}
+
+#[test]
+fn test_wrong_password() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let server = ServerSetup::new(&mut rng);
+    let record = register(&server, &mut rng);
+
+    let (client_state, ke1) = ClientLogin::start(b"wrong password", &mut rng).unwrap();
+    let (_, ke2) = server
+        .login_start(&record, CREDENTIAL_IDENTIFIER, &ke1, &mut rng)
+        .unwrap();
+    assert!(client_state.finish(&ke2).is_err());
+}
+
+#[test]
+fn test_wrong_server() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let server = ServerSetup::new(&mut rng);
+    let record = register(&server, &mut rng);
+
+    // A server with other secrets cannot impersonate the real server, even with the record.
+    let other_server = ServerSetup::new(&mut rng);
+    let (client_state, ke1) = ClientLogin::start(PASSWORD, &mut rng).unwrap();
+    let (_, ke2) = other_server
+        .login_start(&record, CREDENTIAL_IDENTIFIER, &ke1, &mut rng)
+        .unwrap();
+    assert!(client_state.finish(&ke2).is_err());
+
+    // The OPRF key depends on the credential identifier.
+    let (client_state, ke1) = ClientLogin::start(PASSWORD, &mut rng).unwrap();
+    let (_, ke2) = server.login_start(&record, b"bob", &ke1, &mut rng).unwrap();
+    assert!(client_state.finish(&ke2).is_err());
+}
+
+#[test]
+fn test_wrong_client_mac() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let server = ServerSetup::new(&mut rng);
+    let record = register(&server, &mut rng);
+
+    let (client_state, ke1) = ClientLogin::start(PASSWORD, &mut rng).unwrap();
+    let (server_state, ke2) = server
+        .login_start(&record, CREDENTIAL_IDENTIFIER, &ke1, &mut rng)
+        .unwrap();
+    let (ke3, _, _) = client_state.finish(&ke2).unwrap();
+
+    let mut bytes = bincode::serialize(&ke3).unwrap();
+    bytes[0] ^= 1;
+    let tampered: KE3 = bincode::deserialize(&bytes).unwrap();
+    assert!(server_state.finish(&tampered).is_err());
+}
@@ -0,0 +1,253 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[cfg(feature = "secp256r1")]
+use crate::oprf::P256Sha256;
+use crate::oprf::{
+    blind, blind_with_scalar, finalize, finalize_verifiable, Mode, OprfPrivateKey, OprfPublicKey,
+    OprfSuite, Ristretto255Sha512,
+};
+use crate::test_helpers::verify_serialization;
+use rand::{rngs::StdRng, SeedableRng as _};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+const INPUT: &[u8] = b"test input";
+
+fn test_oprf<S: OprfSuite>() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let private_key = OprfPrivateKey::<S>::generate(&mut rng);
+
+    let (blind_scalar, blinded_element) = blind::<S, _>(Mode::Oprf, INPUT, &mut rng).unwrap();
+    let evaluated_element = private_key.blind_evaluate(&blinded_element);
+    let output = finalize(INPUT, &blind_scalar, &evaluated_element).unwrap();
+    assert_eq!(output, private_key.evaluate(Mode::Oprf, INPUT).unwrap());
+
+    // Outputs differ for different inputs, keys and modes.
+    assert_ne!(
+        output,
+        private_key.evaluate(Mode::Oprf, b"other input").unwrap()
+    );
+    assert_ne!(output, private_key.evaluate(Mode::Voprf, INPUT).unwrap());
+    let other_key = OprfPrivateKey::<S>::generate(&mut rng);
+    assert_ne!(output, other_key.evaluate(Mode::Oprf, INPUT).unwrap());
+
+    verify_serialization(&blinded_element, None);
+    verify_serialization(&evaluated_element, None);
+}
+
+#[test]
+fn test_oprf_ristretto255() {
+    test_oprf::<Ristretto255Sha512>();
+}
+
+#[cfg(feature = "secp256r1")]
+#[test]
+fn test_oprf_p256() {
+    test_oprf::<P256Sha256>();
+}
+
+fn test_voprf<S: OprfSuite>() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let private_key = OprfPrivateKey::<S>::generate(&mut rng);
+    let public_key = OprfPublicKey::from(&private_key);
+
+    let (blind_scalar, blinded_element) = blind::<S, _>(Mode::Voprf, INPUT, &mut rng).unwrap();
+    let (evaluated_element, proof) =
+        private_key.blind_evaluate_verifiable(&blinded_element, &mut rng);
+    let output = finalize_verifiable(
+        INPUT,
+        &blind_scalar,
+        &blinded_element,
+        &evaluated_element,
+        &proof,
+        &public_key,
+    )
+    .unwrap();
+    assert_eq!(output, private_key.evaluate(Mode::Voprf, INPUT).unwrap());
+
+    // The proof does not verify for another public key.
+    let other_public_key = OprfPublicKey::from(&OprfPrivateKey::<S>::generate(&mut rng));
+    assert!(finalize_verifiable(
+        INPUT,
+        &blind_scalar,
+        &blinded_element,
+        &evaluated_element,
+        &proof,
+        &other_public_key,
+    )
+    .is_err());
+
+    // Nor if the server evaluated using another key.
+    let other_key = OprfPrivateKey::<S>::generate(&mut rng);
+    let (wrong_element, wrong_proof) =
+        other_key.blind_evaluate_verifiable(&blinded_element, &mut rng);
+    assert!(finalize_verifiable(
+        INPUT,
+        &blind_scalar,
+        &blinded_element,
+        &wrong_element,
+        &wrong_proof,
+        &public_key,
+    )
+    .is_err());
+
+    verify_serialization(&proof, None);
+}
+
+#[test]
+fn test_voprf_ristretto255() {
+    test_voprf::<Ristretto255Sha512>();
+}
+
+#[cfg(feature = "secp256r1")]
+#[test]
+fn test_voprf_p256() {
+    test_voprf::<P256Sha256>();
+}
+
+#[test]
+fn test_derive_key() {
+    let seed = [7u8; 32];
+    let derive = OprfPrivateKey::<Ristretto255Sha512>::derive;
+    let k1 = derive(Mode::Oprf, &seed, b"info").unwrap();
+    let k2 = derive(Mode::Oprf, &seed, b"info").unwrap();
+    assert_eq!(k1, k2);
+    assert_ne!(k1, derive(Mode::Oprf, &seed, b"other").unwrap());
+    assert_ne!(k1, derive(Mode::Voprf, &seed, b"info").unwrap());
+}
+
+/// A test vector from appendix A of RFC 9497. All values are hex encoded and the proof and the
+/// public key are only given in the VOPRF mode.
+struct TestVector {
+    sk_sm: &'static str,
+    pk_sm: Option<&'static str>,
+    blind: &'static str,
+    blinded_element: &'static str,
+    evaluation_element: &'static str,
+    proof: Option<(&'static str, &'static str)>,
+    output: &'static str,
+}
+
+/// All test vectors in appendix A of RFC 9497 use the same seed, key info and input.
+const SEED: [u8; 32] = [0xa3; 32];
+const KEY_INFO: &str = "74657374206b6579";
+const VECTOR_INPUT: &str = "00";
+
+fn from_hex<T: DeserializeOwned>(hex: &str) -> T {
+    bincode::deserialize(&hex::decode(hex).unwrap()).unwrap()
+}
+
+fn assert_hex_eq<T: Serialize>(value: &T, hex: &str) {
+    assert_eq!(hex::encode(bincode::serialize(value).unwrap()), hex);
+}
+
+fn check_test_vector<S: OprfSuite>(mode: Mode, vector: &TestVector) {
+    let private_key =
+        OprfPrivateKey::<S>::derive(mode, &SEED, &hex::decode(KEY_INFO).unwrap()).unwrap();
+    assert_hex_eq(&private_key, vector.sk_sm);
+    if let Some(pk_sm) = vector.pk_sm {
+        assert_hex_eq(&OprfPublicKey::from(&private_key), pk_sm);
+    }
+
+    let input = hex::decode(VECTOR_INPUT).unwrap();
+    let (blind_scalar, blinded_element) =
+        blind_with_scalar::<S>(mode, &input, from_hex(vector.blind)).unwrap();
+    assert_hex_eq(&blinded_element, vector.blinded_element);
+
+    let evaluated_element = match vector.proof {
+        None => private_key.blind_evaluate(&blinded_element),
+        Some((proof_random_scalar, expected_proof)) => {
+            let (evaluated_element, proof) = private_key.blind_evaluate_verifiable_with_scalar(
+                &blinded_element,
+                &from_hex(proof_random_scalar),
+            );
+            assert_hex_eq(&proof, expected_proof);
+            finalize_verifiable(
+                &input,
+                &blind_scalar,
+                &blinded_element,
+                &evaluated_element,
+                &proof,
+                &OprfPublicKey::from(&private_key),
+            )
+            .unwrap();
+            evaluated_element
+        }
+    };
+    assert_hex_eq(&evaluated_element, vector.evaluation_element);
+
+    let output = finalize(&input, &blind_scalar, &evaluated_element).unwrap();
+    assert_eq!(hex::encode(output), vector.output);
+    assert_eq!(output, private_key.evaluate(mode, &input).unwrap());
+}
+
+#[test]
+fn test_vectors_ristretto255() {
+    // Test vector 1 for the OPRF mode of ristretto255-SHA512 from appendix A.1.1 of RFC 9497.
+    check_test_vector::<Ristretto255Sha512>(
+        Mode::Oprf,
+        &TestVector {
+            sk_sm: "5ebcea5ee37023ccb9fc2d2019f9d7737be85591ae8652ffa9ef0f4d37063b0e",
+            pk_sm: None,
+            blind: "64d37aed22a27f5191de1c1d69fadb899d8862b58eb4220029e036ec4c1f6706",
+            blinded_element: "609a0ae68c15a3cf6903766461307e5c8bb2f95e7e6550e1ffa2dc99e412803c",
+            evaluation_element: "7ec6578ae5120958eb2db1745758ff379e77cb64fe77b0b2d8cc917ea0869c7e",
+            proof: None,
+            output: "527759c3d9366f277d8c6020418d96bb393ba2afb20ff90df23fb7708264e2f3ab9135e3bd69955851de4b1f9fe8a0973396719b7912ba9ee8aa7d0b5e24bcf6",
+        },
+    );
+
+    // Test vector 1 for the VOPRF mode of ristretto255-SHA512 from appendix A.1.2 of RFC 9497.
+    check_test_vector::<Ristretto255Sha512>(
+        Mode::Voprf,
+        &TestVector {
+            sk_sm: "e6f73f344b79b379f1a0dd37e07ff62e38d9f71345ce62ae3a9bc60b04ccd909",
+            pk_sm: Some("c803e2cc6b05fc15064549b5920659ca4a77b2cca6f04f6b357009335476ad4e"),
+            blind: "64d37aed22a27f5191de1c1d69fadb899d8862b58eb4220029e036ec4c1f6706",
+            blinded_element: "863f330cc1a1259ed5a5998a23acfd37fb4351a793a5b3c090b642ddc439b945",
+            evaluation_element: "aa8fa048764d5623868679402ff6108d2521884fa138cd7f9c7669a9a014267e",
+            proof: Some((
+                "222a5e897cf59db8145db8d16e597e8facb80ae7d4e26d9881aa6f61d645fc0e",
+                "ddef93772692e535d1a53903db24367355cc2cc78de93b3be5a8ffcc6985dd066d4346421d17bf5117a2a1ff0fcb2a759f58a539dfbe857a40bce4cf49ec600d",
+            )),
+            output: "b58cfbe118e0cb94d79b5fd6a6dafb98764dff49c14e1770b566e42402da1a7da4d8527693914139caee5bd03903af43a491351d23b430948dd50cde10d32b3c",
+        },
+    );
+}
+
+#[cfg(feature = "secp256r1")]
+#[test]
+fn test_vectors_p256() {
+    // Test vector 1 for the OPRF mode of P256-SHA256 from appendix A.3.1 of RFC 9497.
+    check_test_vector::<P256Sha256>(
+        Mode::Oprf,
+        &TestVector {
+            sk_sm: "159749d750713afe245d2d39ccfaae8381c53ce92d098a9375ee70739c7ac0bf",
+            pk_sm: None,
+            blind: "3338fa65ec36e0290022b48eb562889d89dbfa691d1cde91517fa222ed7ad364",
+            blinded_element: "03723a1e5c09b8b9c18d1dcbca29e8007e95f14f4732d9346d490ffc195110368d",
+            evaluation_element:
+                "030de02ffec47a1fd53efcdd1c6faf5bdc270912b8749e783c7ca75bb412958832",
+            proof: None,
+            output: "a0b34de5fa4c5b6da07e72af73cc507cceeb48981b97b7285fc375345fe495dd",
+        },
+    );
+
+    // Test vector 1 for the VOPRF mode of P256-SHA256 from appendix A.3.2 of RFC 9497.
+    check_test_vector::<P256Sha256>(
+        Mode::Voprf,
+        &TestVector {
+            sk_sm: "ca5d94c8807817669a51b196c34c1b7f8442fde4334a7121ae4736364312fca6",
+            pk_sm: Some("03e17e70604bcabe198882c0a1f27a92441e774224ed9c702e51dd17038b102462"),
+            blind: "3338fa65ec36e0290022b48eb562889d89dbfa691d1cde91517fa222ed7ad364",
+            blinded_element: "02dd05901038bb31a6fae01828fd8d0e49e35a486b5c5d4b4994013648c01277da",
+            evaluation_element: "0209f33cab60cf8fe69239b0afbcfcd261af4c1c5632624f2e9ba29b90ae83e4a2",
+            proof: Some((
+                "f9db001266677f62c095021db018cd8cbb55941d4073698ce45c405d1348b7b1",
+                "e7c2b3c5c954c035949f1f74e6bce2ed539a3be267d1481e9ddb178533df4c2664f69d065c604a4fd953e100b856ad83804eb3845189babfa5a702090d6fc5fa",
+            )),
+            output: "0412e8f78b02c415ab3a288e228978376f99927767ff37c5718d420010a645a1",
+        },
+    );
+}
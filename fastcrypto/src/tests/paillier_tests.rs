@@ -0,0 +1,134 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::paillier::{
+    AffineProof, PaillierPrivateKey, PaillierPublicKey, RangeProof, RingPedersenParameters,
+};
+use num_bigint::BigUint;
+use rand::{rngs::StdRng, SeedableRng as _};
+
+// Small parameters to keep the tests fast.
+const MODULUS_BITS: u64 = 512;
+
+/// The largest 64-bit prime, 2^64 - 59.
+fn q() -> BigUint {
+    BigUint::from(u64::MAX - 58)
+}
+
+#[test]
+fn test_encrypt_decrypt() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let sk = PaillierPrivateKey::generate(MODULUS_BITS, &mut rng).unwrap();
+    let pk = sk.public_key();
+    assert_eq!(pk.modulus().bits(), MODULUS_BITS);
+
+    for m in [0u64, 1, 42, u64::MAX] {
+        let m = BigUint::from(m);
+        let (c, _) = pk.encrypt(&m, &mut rng).unwrap();
+        assert_eq!(sk.decrypt(&c).unwrap(), m);
+    }
+
+    // Messages must be smaller than the modulus.
+    assert!(pk.encrypt(pk.modulus(), &mut rng).is_err());
+    assert!(PaillierPrivateKey::generate(MODULUS_BITS + 1, &mut rng).is_err());
+}
+
+#[test]
+fn test_homomorphic_operations() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let sk = PaillierPrivateKey::generate(MODULUS_BITS, &mut rng).unwrap();
+    let pk = sk.public_key();
+    let n = pk.modulus();
+
+    let a = n - 1u8;
+    let b = BigUint::from(1234u32);
+    let k = BigUint::from(98765u32);
+    let (ca, _) = pk.encrypt(&a, &mut rng).unwrap();
+    let (cb, _) = pk.encrypt(&b, &mut rng).unwrap();
+
+    assert_eq!(sk.decrypt(&pk.add(&ca, &cb)).unwrap(), (&a + &b) % n);
+    assert_eq!(sk.decrypt(&pk.mul(&cb, &k)).unwrap(), &b * &k);
+    assert_eq!(sk.decrypt(&pk.mul(&ca, &k)).unwrap(), (&a * &k) % n);
+}
+
+#[test]
+fn test_serialization() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let sk = PaillierPrivateKey::generate(MODULUS_BITS, &mut rng).unwrap();
+    let pk = sk.public_key();
+    let bytes = bincode::serialize(pk).unwrap();
+    let deserialized: PaillierPublicKey = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(&deserialized, pk);
+
+    let (c, _) = deserialized.encrypt(&BigUint::from(7u8), &mut rng).unwrap();
+    assert_eq!(sk.decrypt(&c).unwrap(), BigUint::from(7u8));
+
+    // Even moduli are rejected.
+    let bytes = bincode::serialize(&BigUint::from(u128::MAX - 1)).unwrap();
+    assert!(bincode::deserialize::<PaillierPublicKey>(&bytes).is_err());
+}
+
+#[test]
+fn test_range_proof() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let sk = PaillierPrivateKey::generate(MODULUS_BITS, &mut rng).unwrap();
+    let pk = sk.public_key();
+    let setup = RingPedersenParameters::generate(MODULUS_BITS, &mut rng).unwrap();
+    let q = q();
+
+    let m = &q - 1u8;
+    let (c, r) = pk.encrypt(&m, &mut rng).unwrap();
+    let proof = RangeProof::prove(pk, &setup, &q, &c, &m, &r, &mut rng).unwrap();
+    assert!(proof.verify(pk, &setup, &q, &c).is_ok());
+
+    // The proof does not verify for another ciphertext.
+    let (other, _) = pk.encrypt(&m, &mut rng).unwrap();
+    assert!(proof.verify(pk, &setup, &q, &other).is_err());
+
+    // A proof with a wrong nonce does not verify.
+    let proof = RangeProof::prove(pk, &setup, &q, &c, &m, &(&r + 1u8), &mut rng).unwrap();
+    assert!(proof.verify(pk, &setup, &q, &c).is_err());
+
+    // Values out of range cannot be proven.
+    let (c, r) = pk.encrypt(&q, &mut rng).unwrap();
+    assert!(RangeProof::prove(pk, &setup, &q, &c, &q, &r, &mut rng).is_err());
+}
+
+#[test]
+fn test_affine_proof() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let alice = PaillierPrivateKey::generate(MODULUS_BITS, &mut rng).unwrap();
+    let pk = alice.public_key();
+    let setup = RingPedersenParameters::generate(MODULUS_BITS, &mut rng).unwrap();
+    let q = q();
+
+    // Alice encrypts a, and Bob computes c2 = a * b + beta homomorphically.
+    let a = BigUint::from(123456789u64);
+    let (c1, _) = pk.encrypt(&a, &mut rng).unwrap();
+    let b = BigUint::from(987654321u64);
+    let beta = q.pow(4) + 5u8;
+    let (c_beta, r) = pk.encrypt(&beta, &mut rng).unwrap();
+    let c2 = pk.add(&pk.mul(&c1, &b), &c_beta);
+    assert_eq!(alice.decrypt(&c2).unwrap(), &a * &b + &beta);
+
+    let proof =
+        AffineProof::prove(pk, &setup, &q, &c1, &c2, &b, &beta, &r, b"X", &mut rng).unwrap();
+    assert!(proof.verify(pk, &setup, &q, &c1, &c2, b"X").is_ok());
+
+    // The statement is bound to the proof.
+    assert!(proof.verify(pk, &setup, &q, &c1, &c2, b"Y").is_err());
+    assert!(proof.verify(pk, &setup, &q, &c1, &c2, b"").is_err());
+
+    // A proof for the wrong ciphertext does not verify.
+    let c3 = pk.add(&c2, &c_beta);
+    assert!(proof.verify(pk, &setup, &q, &c1, &c3, b"X").is_err());
+    let proof =
+        AffineProof::prove(pk, &setup, &q, &c1, &c3, &b, &beta, &r, b"X", &mut rng).unwrap();
+    assert!(proof.verify(pk, &setup, &q, &c1, &c3, b"X").is_err());
+
+    // Values out of range cannot be proven.
+    assert!(AffineProof::prove(pk, &setup, &q, &c1, &c2, &q, &beta, &r, b"", &mut rng).is_err());
+    assert!(
+        AffineProof::prove(pk, &setup, &q, &c1, &c2, &b, &q.pow(5), &r, b"", &mut rng).is_err()
+    );
+}
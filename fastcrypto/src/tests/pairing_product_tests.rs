@@ -0,0 +1,112 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::groups::bls12381::{G1Element, G2Element, GTElement, Scalar};
+use crate::groups::{GroupElement, Pairing, Scalar as ScalarTrait};
+use crate::pairing_product::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+/// The equation e(x * y * g_1, g_2) - e(x * g_1, y * g_2) = 0.
+fn equation(rng: &mut StdRng) -> PairingProductEquation<G1Element> {
+    let x = Scalar::rand(rng);
+    let y = Scalar::rand(rng);
+    PairingProductEquation::new(
+        vec![
+            (G1Element::generator() * (x * y), G2Element::generator()),
+            (-G1Element::generator() * x, G2Element::generator() * y),
+        ],
+        GTElement::zero(),
+    )
+}
+
+#[test]
+fn test_multi_pairing() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let a = (0..4)
+        .map(|_| G1Element::generator() * Scalar::rand(&mut rng))
+        .chain([G1Element::zero()])
+        .collect::<Vec<_>>();
+    let b = (0..5)
+        .map(|_| G2Element::generator() * Scalar::rand(&mut rng))
+        .collect::<Vec<_>>();
+    let expected = a
+        .iter()
+        .zip(&b)
+        .fold(GTElement::zero(), |acc, (a, b)| acc + a.pairing(b));
+    assert_eq!(G1Element::multi_pairing(&a, &b).unwrap(), expected);
+
+    assert_eq!(
+        G1Element::multi_pairing(&[], &[]).unwrap(),
+        GTElement::zero()
+    );
+    assert!(G1Element::multi_pairing(&a[1..], &b).is_err());
+}
+
+#[test]
+fn test_verify() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let mut equation = equation(&mut rng);
+    assert!(equation.verify().is_ok());
+
+    // A non-zero target.
+    equation.target = G1Element::generator().pairing(&G2Element::generator());
+    assert!(equation.verify().is_err());
+    equation
+        .pairs
+        .push((G1Element::generator(), G2Element::generator()));
+    assert!(equation.verify().is_ok());
+}
+
+#[test]
+fn test_verify_batch() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let mut equations = (0..5).map(|_| equation(&mut rng)).collect::<Vec<_>>();
+    assert!(verify_batch(&equations, &mut rng).is_ok());
+    assert!(verify_batch::<G1Element, _>(&[], &mut rng).is_ok());
+
+    // A single invalid equation invalidates the batch.
+    equations[3].pairs[0].0 += G1Element::generator();
+    assert!(equations[3].verify().is_err());
+    assert!(verify_batch(&equations, &mut rng).is_err());
+
+    // Two invalid equations do not cancel out.
+    equations[1].pairs[0].0 -= G1Element::generator();
+    assert!(verify_batch(&equations, &mut rng).is_err());
+}
+
+#[test]
+fn test_pairing_check() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let x = Scalar::rand(&mut rng);
+    let y = Scalar::rand(&mut rng);
+    let g1 = G1Element::generator();
+    let g2 = G2Element::generator();
+
+    // x * y * e(g_1, g_2) + e(-x * g_1, g_2) * y = 0, with both terms merged into a single pair.
+    let check = PairingCheck::new()
+        .pair_scaled(x * y, g1, g2)
+        .pair_negated(g1 * x, g2)
+        .pair_scaled(x, g1, -g2 * y)
+        .pair(g1 * x, g2);
+    let equation = check.clone().build();
+    assert_eq!(equation.pairs.len(), 2);
+    assert!(equation.verify().is_ok());
+    assert!(check.clone().verify().is_ok());
+    assert!(check.clone().pair(g1, g2).verify().is_err());
+
+    // A non-zero target.
+    let target = g1.pairing(&g2) * x;
+    assert!(check.clone().equals(target).verify().is_err());
+    assert!(check.pair_scaled(x, g1, g2).equals(target).verify().is_ok());
+
+    // Pairs with the identity and terms which cancel out are left out.
+    let equation = PairingCheck::<G1Element>::new()
+        .pair(G1Element::zero(), g2 * y)
+        .pair(g1 * x, G2Element::zero())
+        .pair(g1, g2)
+        .pair_negated(g1, g2)
+        .build();
+    assert!(equation.pairs.is_empty());
+    assert!(equation.verify().is_ok());
+    assert!(PairingCheck::<G1Element>::default().verify().is_ok());
+}
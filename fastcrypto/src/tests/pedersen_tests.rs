@@ -0,0 +1,70 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::FastCryptoError;
+use crate::groups::bls12381::G1Element;
+use crate::groups::pedersen::{PedersenCommitment, PedersenParameters};
+use crate::groups::ristretto255::RistrettoPoint;
+use crate::groups::{GroupElement, HashToGroupElement, Scalar};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn test_commitments<G: GroupElement + HashToGroupElement>() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let parameters = PedersenParameters::<G>::new(b"test");
+    assert_eq!(parameters.generator(), &G::generator());
+    assert_ne!(parameters.blinding_generator(), &G::generator());
+    assert_eq!(parameters, PedersenParameters::<G>::new(b"test"));
+    assert_ne!(parameters, PedersenParameters::<G>::new(b"other test"));
+
+    let v1 = G::ScalarType::from(3);
+    let v2 = G::ScalarType::from(5);
+    let (c1, r1) = PedersenCommitment::commit_random(&parameters, &v1, &mut rng);
+    let (c2, r2) = PedersenCommitment::commit_random(&parameters, &v2, &mut rng);
+    assert_eq!(c1, PedersenCommitment::commit(&parameters, &v1, &r1));
+    assert!(c1.open(&parameters, &v1, &r1).is_ok());
+    assert_eq!(
+        c1.open(&parameters, &v2, &r1),
+        Err(FastCryptoError::InvalidInput)
+    );
+    assert!(c1.open(&parameters, &v1, &r2).is_err());
+    assert!(c1
+        .open(&PedersenParameters::new(b"other test"), &v1, &r1)
+        .is_err());
+
+    // Hiding: Commitments to the same value with different blindings differ.
+    assert_ne!(c1, PedersenCommitment::commit(&parameters, &v1, &r2));
+
+    // Homomorphism.
+    assert!((c1 + c2).open(&parameters, &(v1 + v2), &(r1 + r2)).is_ok());
+    assert!((c2 - c1).open(&parameters, &(v2 - v1), &(r2 - r1)).is_ok());
+    assert!((-c1).open(&parameters, &(-v1), &(-r1)).is_ok());
+    assert_eq!(c1 - c1, PedersenCommitment::from(G::zero()),);
+    assert_eq!(
+        PedersenCommitment::commit(&parameters, &v1, &G::ScalarType::zero()).point(),
+        &(G::generator() * v1)
+    );
+}
+
+#[test]
+fn test_ristretto255() {
+    test_commitments::<RistrettoPoint>();
+}
+
+#[test]
+fn test_bls12381_g1() {
+    test_commitments::<G1Element>();
+}
+
+#[test]
+fn test_serde() {
+    let mut rng = StdRng::from_seed([1; 32]);
+    let parameters = PedersenParameters::<G1Element>::new(b"test");
+    let value = <G1Element as GroupElement>::ScalarType::rand(&mut rng);
+    let (commitment, blinding) = PedersenCommitment::commit_random(&parameters, &value, &mut rng);
+
+    let bytes = bcs::to_bytes(&commitment).unwrap();
+    let deserialized: PedersenCommitment<G1Element> = bcs::from_bytes(&bytes).unwrap();
+    assert_eq!(commitment, deserialized);
+    assert!(deserialized.open(&parameters, &value, &blinding).is_ok());
+}
@@ -0,0 +1,43 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::policy::{current, factoring_security_bits, Algorithm, Policy};
+
+#[test]
+fn test_factoring_security_bits() {
+    assert_eq!(factoring_security_bits(512), 0);
+    assert_eq!(factoring_security_bits(1023), 0);
+    assert_eq!(factoring_security_bits(1024), 80);
+    assert_eq!(factoring_security_bits(2048), 112);
+    assert_eq!(factoring_security_bits(3071), 112);
+    assert_eq!(factoring_security_bits(3072), 128);
+    assert_eq!(factoring_security_bits(7680), 192);
+    assert_eq!(factoring_security_bits(15360), 256);
+}
+
+#[test]
+fn test_check() {
+    let policy = Policy::default();
+    assert!(policy.check(Algorithm::RsaSignature, 0).is_ok());
+
+    let policy = Policy::default().with_min_security_bits(112);
+    assert_eq!(policy.min_security_bits(), 112);
+    assert!(policy
+        .check(Algorithm::RsaSignature, factoring_security_bits(1024))
+        .is_err());
+    assert!(policy
+        .check(Algorithm::RsaSignature, factoring_security_bits(2048))
+        .is_ok());
+
+    let policy = policy.forbid(Algorithm::RsaAccumulator);
+    assert!(policy.check(Algorithm::RsaAccumulator, 256).is_err());
+    assert!(policy.check(Algorithm::RsaSignature, 256).is_ok());
+    assert_eq!(policy.clone().forbid(Algorithm::RsaAccumulator), policy);
+}
+
+#[test]
+fn test_default_policy() {
+    // Other tests rely on the default policy, so it is never replaced in unit tests. See the
+    // module documentation for an example of installing a policy.
+    assert_eq!(current(), Policy::default());
+}
@@ -0,0 +1,138 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::groups::bls12381::{G1Element, Scalar};
+use crate::groups::{GroupElement, Scalar as _};
+use crate::polynomial::*;
+use crate::reed_solomon::ScalarReedSolomon;
+use rand::{rngs::StdRng, SeedableRng};
+
+fn scalars(values: &[u64]) -> Vec<Scalar> {
+    values.iter().map(|v| Scalar::from(*v)).collect()
+}
+
+#[test]
+fn test_powers() {
+    assert_eq!(powers(&Scalar::from(3), 4), scalars(&[1, 3, 9, 27]));
+    assert!(powers(&Scalar::from(3), 0).is_empty());
+    assert_eq!(pow(Scalar::from(3), 0), Scalar::from(1));
+    assert_eq!(pow(Scalar::from(3), 5), Scalar::from(243));
+}
+
+#[test]
+fn test_evaluate() {
+    // 1 + 2X + 3X^2.
+    let polynomial = scalars(&[1, 2, 3]);
+    assert_eq!(evaluate(&polynomial, &Scalar::from(2)), Scalar::from(17));
+    assert_eq!(evaluate::<Scalar>(&[], &Scalar::from(2)), Scalar::zero());
+
+    // In the exponent.
+    let commitment = polynomial
+        .iter()
+        .map(G1Element::mul_generator)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        evaluate(&commitment, &Scalar::from(2)),
+        G1Element::mul_generator(&Scalar::from(17))
+    );
+}
+
+#[test]
+fn test_division() {
+    // (X - 2)(X + 3) + 5 = X^2 + X - 1.
+    let polynomial = [-Scalar::from(1), Scalar::from(1), Scalar::from(1)];
+    let (quotient, remainder) = divide_by_linear(&polynomial, &Scalar::from(2));
+    assert_eq!(quotient, scalars(&[3, 1]));
+    assert_eq!(remainder, Scalar::from(5));
+
+    let (quotient, remainder) = divide_by_monic(&polynomial, &[-Scalar::from(2), Scalar::from(1)]);
+    assert_eq!(quotient, scalars(&[3, 1]));
+    assert_eq!(remainder, scalars(&[5]));
+
+    // The degree of the divisor exceeds the degree of the dividend.
+    let (quotient, remainder) = divide_by_monic(&polynomial[..1], &polynomial);
+    assert!(quotient.is_empty());
+    assert_eq!(remainder, polynomial[..1]);
+}
+
+#[test]
+fn test_vanishing_polynomial() {
+    let points = scalars(&[1, 2, 3]);
+    let z = vanishing_polynomial(&points);
+    assert_eq!(z.len(), 4);
+    assert_eq!(z[3], Scalar::from(1));
+    assert!(points.iter().all(|p| evaluate(&z, p) == Scalar::zero()));
+    assert_eq!(evaluate(&z, &Scalar::from(4)), Scalar::from(6));
+    assert_eq!(vanishing_polynomial::<Scalar>(&[]), scalars(&[1]));
+}
+
+#[test]
+fn test_interpolate() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let polynomial = (0..5).map(|_| Scalar::rand(&mut rng)).collect::<Vec<_>>();
+    let points = (0..5).map(|_| Scalar::rand(&mut rng)).collect::<Vec<_>>();
+    let values = points
+        .iter()
+        .map(|p| evaluate(&polynomial, p))
+        .collect::<Vec<_>>();
+    assert_eq!(interpolate(&points, &values).unwrap(), polynomial);
+
+    // The Lagrange coefficients at any point combine the values to the evaluation.
+    let x = Scalar::rand(&mut rng);
+    let coefficients = lagrange_coefficients(&x, &points).unwrap();
+    let combined = coefficients
+        .iter()
+        .zip(&values)
+        .fold(Scalar::zero(), |acc, (l, v)| acc + *l * v);
+    assert_eq!(combined, evaluate(&polynomial, &x));
+
+    // The points are not distinct or the lengths differ.
+    let duplicate = scalars(&[1, 2, 1]);
+    assert!(interpolate(&duplicate, &values[..3]).is_err());
+    assert!(lagrange_coefficients(&x, &duplicate).is_err());
+    assert!(interpolate(&points, &values[..4]).is_err());
+}
+
+#[test]
+fn test_batch_inverse() {
+    let mut values = scalars(&[1, 2, 3, 4]);
+    batch_inverse(&mut values).unwrap();
+    for (v, i) in values.iter().zip(1..) {
+        assert_eq!(*v * Scalar::from(i), Scalar::from(1));
+    }
+    assert!(batch_inverse(&mut scalars(&[1, 0, 3])).is_err());
+}
+
+#[test]
+fn test_fft() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    // A generator of the subgroup of order 8.
+    let root = ScalarReedSolomon::new(1, 8)
+        .unwrap()
+        .point(reverse_bits(1, 8))
+        .unwrap();
+    assert_ne!(pow(root, 4), Scalar::from(1));
+    assert_eq!(pow(root, 8), Scalar::from(1));
+
+    let polynomial = (0..8).map(|_| Scalar::rand(&mut rng)).collect::<Vec<_>>();
+    let mut values = polynomial.clone();
+    fft(&mut values, root);
+    let expected = powers(&root, 8)
+        .iter()
+        .map(|x| evaluate(&polynomial, x))
+        .collect::<Vec<_>>();
+    assert_eq!(values, expected);
+
+    inverse_fft(&mut values, root).unwrap();
+    assert_eq!(values, polynomial);
+}
+
+#[test]
+fn test_bit_reversal() {
+    assert_eq!(reverse_bits(0, 1), 0);
+    assert_eq!(reverse_bits(1, 8), 4);
+    assert_eq!(reverse_bits(6, 8), 3);
+    let mut values = (0..8).collect::<Vec<_>>();
+    bit_reverse_permutation(&mut values);
+    assert_eq!(values, vec![0, 4, 2, 6, 1, 5, 3, 7]);
+}
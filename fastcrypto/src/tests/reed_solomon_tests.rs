@@ -0,0 +1,199 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::FastCryptoError;
+use crate::groups::bls12381::Scalar;
+use crate::groups::{GroupElement, Scalar as _};
+use crate::kzg::KzgParameters;
+use crate::reed_solomon::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+/// All ways to keep `k` of `n` chunks.
+fn subsets(n: usize, k: usize) -> Vec<Vec<usize>> {
+    (0u32..1 << n)
+        .filter(|mask| mask.count_ones() as usize == k)
+        .map(|mask| (0..n).filter(|i| (mask >> i) & 1 == 1).collect())
+        .collect()
+}
+
+fn keep<T: Clone>(chunks: &[T], indices: &[usize]) -> Vec<Option<T>> {
+    (0..chunks.len())
+        .map(|i| indices.contains(&i).then(|| chunks[i].clone()))
+        .collect()
+}
+
+#[test]
+fn test_encode_bytes() {
+    let code = ReedSolomon::new(3, 5).unwrap();
+    let data = b"hello world";
+    let chunks = code.encode(data).unwrap();
+
+    // The parity chunks were computed with an independent implementation.
+    assert_eq!(code.chunk_length(data.len()), 4);
+    assert_eq!(
+        chunks,
+        vec![
+            b"hell".to_vec(),
+            b"o wo".to_vec(),
+            b"rld\0".to_vec(),
+            hex::decode("75297f03").unwrap(),
+            hex::decode("0c418401").unwrap(),
+        ]
+    );
+
+    // Codes without parity and with the largest number of chunks.
+    assert_eq!(
+        ReedSolomon::new(2, 2)
+            .unwrap()
+            .encode(data)
+            .unwrap()
+            .concat(),
+        b"hello world\0"
+    );
+    let code = ReedSolomon::new(100, MAX_BYTE_CHUNKS).unwrap();
+    let data = (0..=255).collect::<Vec<u8>>();
+    let chunks = code.encode(&data).unwrap();
+    let indices = (MAX_BYTE_CHUNKS - 100..MAX_BYTE_CHUNKS).collect::<Vec<_>>();
+    assert_eq!(code.decode(&keep(&chunks, &indices), 256).unwrap(), data);
+
+    assert!(ReedSolomon::new(0, 1).is_err());
+    assert!(ReedSolomon::new(3, 2).is_err());
+    assert_eq!(
+        ReedSolomon::new(1, MAX_BYTE_CHUNKS + 1),
+        Err(FastCryptoError::InputTooLong(MAX_BYTE_CHUNKS))
+    );
+    assert!(code.encode(&[]).is_err());
+}
+
+#[test]
+fn test_decode_bytes() {
+    let code = ReedSolomon::new(3, 6).unwrap();
+    let data = (0..50).collect::<Vec<u8>>();
+    let chunks = code.encode(&data).unwrap();
+    for indices in subsets(6, 3).into_iter().chain(subsets(6, 4)) {
+        assert_eq!(code.decode(&keep(&chunks, &indices), 50).unwrap(), data);
+    }
+
+    // Too few chunks, or chunks of the wrong length.
+    for indices in subsets(6, 2) {
+        assert!(code.decode(&keep(&chunks, &indices), 50).is_err());
+    }
+    let all = keep(&chunks, &[0, 1, 2, 3, 4, 5]);
+    assert!(code.decode(&all, 51).is_err());
+    assert!(code.decode(&all, 0).is_err());
+    assert_eq!(
+        code.decode(&all[..5], 50),
+        Err(FastCryptoError::InputLengthWrong(6))
+    );
+}
+
+#[test]
+fn test_committed_bytes() {
+    let code = ReedSolomon::new(4, 8).unwrap();
+    let data = vec![9u8; 1000];
+    let committed = code.commit(&data).unwrap();
+    let commitment = committed.commitment();
+    assert_eq!(commitment.num_chunks, 8);
+    assert_eq!(committed.chunks(), code.encode(&data).unwrap());
+
+    let mut chunks = vec![None; 8];
+    for index in 4..8 {
+        let chunk = &committed.chunks()[index];
+        let proof = committed.prove(index as u32).unwrap();
+        assert!(commitment.verify_chunk(index as u32, chunk, &proof).is_ok());
+        chunks[index] = Some(chunk.clone());
+    }
+    assert_eq!(code.decode(&chunks, 1000).unwrap(), data);
+}
+
+#[test]
+fn test_encode_scalars() {
+    let code = ScalarReedSolomon::new(4, 16).unwrap();
+    let data = (1..=4u64).map(Scalar::from).collect::<Vec<_>>();
+    let chunks = code.encode(&data).unwrap();
+    assert_eq!(chunks.len(), 16);
+    assert_eq!(chunks[..4], data[..]);
+
+    // The chunks are the evaluations of the polynomial of the data at distinct points.
+    let coefficients = code.interpolate(&data).unwrap();
+    assert_eq!(coefficients.len(), 4);
+    let mut points = Vec::new();
+    for (index, chunk) in chunks.iter().enumerate() {
+        let point = code.point(index).unwrap();
+        let value = coefficients
+            .iter()
+            .rev()
+            .fold(Scalar::zero(), |acc, c| acc * point + *c);
+        assert_eq!(&value, chunk);
+        assert!(!points.contains(&point));
+        points.push(point);
+    }
+    assert!(code.point(16).is_err());
+    assert_eq!(
+        code.encode(&data[..3]),
+        Err(FastCryptoError::InputLengthWrong(4))
+    );
+
+    // Codes with a single data chunk or without parity.
+    let code = ScalarReedSolomon::new(1, 4).unwrap();
+    assert_eq!(code.encode(&data[..1]).unwrap(), vec![data[0]; 4]);
+    let code = ScalarReedSolomon::new(4, 4).unwrap();
+    assert_eq!(code.encode(&data).unwrap(), data);
+
+    assert!(ScalarReedSolomon::new(3, 8).is_err());
+    assert!(ScalarReedSolomon::new(4, 12).is_err());
+    assert!(ScalarReedSolomon::new(8, 4).is_err());
+}
+
+#[test]
+fn test_decode_scalars() {
+    let rng = &mut StdRng::from_seed([0; 32]);
+    let code = ScalarReedSolomon::new(4, 8).unwrap();
+    let data = (0..4).map(|_| Scalar::rand(rng)).collect::<Vec<_>>();
+    let chunks = code.encode(&data).unwrap();
+    for indices in subsets(8, 4).into_iter().chain(subsets(8, 6)) {
+        assert_eq!(code.decode(&keep(&chunks, &indices)).unwrap(), data);
+    }
+    assert!(code.decode(&keep(&chunks, &[1, 3, 5])).is_err());
+    assert_eq!(
+        code.decode(&keep(&chunks[..4], &[0, 1, 2, 3])),
+        Err(FastCryptoError::InputLengthWrong(8))
+    );
+}
+
+#[test]
+fn test_committed_scalars() {
+    let rng = &mut StdRng::from_seed([0; 32]);
+    let parameters = KzgParameters::generate(7, rng).unwrap();
+    let code = ScalarReedSolomon::new(8, 32).unwrap();
+    let data = (0..8).map(|_| Scalar::rand(rng)).collect::<Vec<_>>();
+    let chunks = code.encode(&data).unwrap();
+    let commitment = code.commit(&parameters, &data).unwrap();
+    for index in [0, 5, 8, 31] {
+        let (chunk, proof) = code.open(&parameters, &data, index).unwrap();
+        assert_eq!(chunk, chunks[index]);
+        assert!(code
+            .verify_chunk(&parameters, &commitment, index, &chunk, &proof)
+            .is_ok());
+
+        // Other chunks and indices.
+        assert!(code
+            .verify_chunk(
+                &parameters,
+                &commitment,
+                index,
+                &chunks[(index + 1) % 32],
+                &proof
+            )
+            .is_err());
+        assert!(code
+            .verify_chunk(&parameters, &commitment, (index + 1) % 32, &chunk, &proof)
+            .is_err());
+    }
+    assert!(code.open(&parameters, &data, 32).is_err());
+
+    // The degree of the polynomial must not exceed that of the parameters.
+    let code = ScalarReedSolomon::new(16, 32).unwrap();
+    let data = vec![Scalar::from(1); 16];
+    assert!(code.commit(&parameters, &data).is_err());
+}
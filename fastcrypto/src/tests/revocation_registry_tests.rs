@@ -0,0 +1,99 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::ed25519::{Ed25519KeyPair, Ed25519PublicKey};
+use crate::revocation_registry::*;
+use crate::rsa_accumulator::AccumulatorParameters;
+use crate::traits::KeyPair;
+use rand::{rngs::StdRng, SeedableRng as _};
+
+fn keys(n: usize) -> Vec<Ed25519PublicKey> {
+    let mut rng = StdRng::from_seed([1; 32]);
+    (0..n)
+        .map(|_| Ed25519KeyPair::generate(&mut rng).public().clone())
+        .collect()
+}
+
+#[test]
+fn test_register_and_revoke() {
+    let parameters =
+        AccumulatorParameters::generate(1024, &mut StdRng::from_seed([0; 32])).unwrap();
+    let mut registry = RevocationRegistry::<Ed25519PublicKey>::new(&parameters);
+    let keys = keys(4);
+
+    let (proofs, _) = registry.register(&keys[..2]).unwrap();
+    let mut proof_0 = proofs[0].clone();
+    let mut proof_1 = proofs[1].clone();
+    assert_eq!(registry.state().epoch, 1);
+    assert!(proof_0
+        .verify(&parameters, &registry.state(), &keys[0])
+        .is_ok());
+    assert!(proof_0
+        .verify(&parameters, &registry.state(), &keys[1])
+        .is_err());
+    let stale_state = registry.state();
+
+    // Register more keys and update the existing proofs.
+    let (proofs, update) = registry.register(&keys[2..]).unwrap();
+    let mut proof_2 = proofs[0].clone();
+    assert!(proof_0
+        .verify(&parameters, &registry.state(), &keys[0])
+        .is_err());
+    proof_0.update(&parameters, &keys[0], &update).unwrap();
+    proof_1.update(&parameters, &keys[1], &update).unwrap();
+    assert!(proof_0
+        .verify(&parameters, &registry.state(), &keys[0])
+        .is_ok());
+    assert!(proof_1
+        .verify(&parameters, &registry.state(), &keys[1])
+        .is_ok());
+    assert!(proof_0.verify(&parameters, &stale_state, &keys[0]).is_err());
+
+    // Revoke a key. Its holder can no longer update its proof, but the other holders can.
+    let update = registry.revoke(&[keys[1].clone()]).unwrap();
+    assert!(registry.is_revoked(&keys[1]) && !registry.is_valid(&keys[1]));
+    assert!(proof_1.update(&parameters, &keys[1], &update).is_err());
+    assert!(proof_1
+        .verify(&parameters, &registry.state(), &keys[1])
+        .is_err());
+    assert!(registry.prove(&keys[1]).is_err());
+    proof_0.update(&parameters, &keys[0], &update).unwrap();
+    assert!(proof_0
+        .verify(&parameters, &registry.state(), &keys[0])
+        .is_ok());
+
+    // Updates must be applied in order.
+    let (_, update) = registry.register(&keys(5)[4..]).unwrap();
+    proof_0.update(&parameters, &keys[0], &update).unwrap();
+    assert!(proof_2.update(&parameters, &keys[2], &update).is_err());
+    assert_eq!(proof_2.epoch(), 2);
+
+    // A holder who missed updates can get a fresh proof.
+    let proof_2 = registry.prove(&keys[2]).unwrap();
+    assert!(proof_2
+        .verify(&parameters, &registry.state(), &keys[2])
+        .is_ok());
+    assert_eq!(proof_0, registry.prove(&keys[0]).unwrap());
+}
+
+#[test]
+fn test_invalid_changes() {
+    let parameters =
+        AccumulatorParameters::generate(1024, &mut StdRng::from_seed([0; 32])).unwrap();
+    let mut registry = RevocationRegistry::<Ed25519PublicKey>::new(&parameters);
+    let keys = keys(3);
+    registry.register(&keys[..2]).unwrap();
+
+    // Keys cannot be registered twice or revoked if they are not registered.
+    assert!(registry.register(&[keys[0].clone()]).is_err());
+    assert!(registry
+        .register(&[keys[2].clone(), keys[2].clone()])
+        .is_err());
+    assert!(registry.revoke(&[keys[2].clone()]).is_err());
+
+    // Revoked keys cannot be registered again.
+    registry.revoke(&[keys[0].clone()]).unwrap();
+    assert!(registry.revoke(&[keys[0].clone()]).is_err());
+    assert!(registry.register(&[keys[0].clone()]).is_err());
+    assert_eq!(registry.state().epoch, 2);
+}
@@ -3,7 +3,8 @@
 
 use crate::groups::ristretto255::RistrettoPoint;
 use crate::groups::ristretto255::RistrettoScalar;
-use crate::groups::GroupElement;
+use crate::groups::{FixedBaseMultiplication, GroupElement, Scalar};
+use rand::{rngs::StdRng, SeedableRng as _};
 
 #[test]
 fn test_arithmetic() {
@@ -167,3 +168,20 @@ fn test_multiscalar_mul() {
     // Invalid lengths
     assert!(RistrettoPoint::multiscalar_mul([1, 2], [g, g, g]).is_err());
 }
+
+#[test]
+fn test_mul_generator() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let scalars = [
+        RistrettoScalar::zero(),
+        RistrettoScalar::generator(),
+        -RistrettoScalar::generator(),
+        RistrettoScalar::rand(&mut rng),
+    ];
+    for s in scalars {
+        assert_eq!(
+            RistrettoPoint::mul_generator(&s),
+            RistrettoPoint::generator() * s
+        );
+    }
+}
@@ -0,0 +1,140 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::rsa_accumulator::{AccumulatorParameters, MembershipWitness, RsaAccumulator};
+use num_bigint::BigUint;
+use rand::{rngs::StdRng, SeedableRng as _};
+
+fn parameters() -> AccumulatorParameters {
+    AccumulatorParameters::generate(1024, &mut StdRng::from_seed([0; 32])).unwrap()
+}
+
+#[test]
+fn test_membership() {
+    let parameters = parameters();
+    let mut accumulator = RsaAccumulator::new(&parameters);
+    let empty = accumulator.value().clone();
+
+    let witnesses = accumulator.add(&[b"a", b"b", b"c"]).unwrap();
+    assert_ne!(accumulator.value(), &empty);
+    for (element, witness) in [b"a", b"b", b"c"].iter().zip(witnesses.iter()) {
+        assert!(accumulator.contains(*element));
+        assert!(witness
+            .verify(&parameters, accumulator.value(), *element)
+            .is_ok());
+        assert_eq!(witness, &accumulator.membership_witness(*element).unwrap());
+    }
+    assert!(witnesses[0]
+        .verify(&parameters, accumulator.value(), b"b")
+        .is_err());
+    assert!(witnesses[0].verify(&parameters, &empty, b"a").is_err());
+
+    // Elements cannot be added twice.
+    assert!(accumulator.add(&[b"a"]).is_err());
+    assert!(accumulator.add(&[b"d", b"d"]).is_err());
+    assert!(!accumulator.contains(b"d"));
+    assert!(accumulator.membership_witness(b"d").is_err());
+}
+
+#[test]
+fn test_witness_updates() {
+    let parameters = parameters();
+    let mut accumulator = RsaAccumulator::new(&parameters);
+    let mut witness = accumulator.add(&[b"a", b"b"]).unwrap().remove(0);
+
+    accumulator.add(&[b"c", b"d", b"e"]).unwrap();
+    assert!(witness
+        .verify(&parameters, accumulator.value(), b"a")
+        .is_err());
+    witness.update_on_add(&parameters, &[b"c", b"d", b"e"]);
+    assert!(witness
+        .verify(&parameters, accumulator.value(), b"a")
+        .is_ok());
+
+    accumulator.delete(&[b"b", b"d"]).unwrap();
+    assert!(!accumulator.contains(b"b"));
+    witness
+        .update_on_delete(&parameters, b"a", &[b"b", b"d"], accumulator.value())
+        .unwrap();
+    assert!(witness
+        .verify(&parameters, accumulator.value(), b"a")
+        .is_ok());
+    assert_eq!(witness, accumulator.membership_witness(b"a").unwrap());
+
+    // The witness of a deleted element cannot be updated.
+    let mut deleted = accumulator.membership_witness(b"c").unwrap();
+    accumulator.delete(&[b"c"]).unwrap();
+    assert!(deleted
+        .update_on_delete(&parameters, b"c", &[b"c"], accumulator.value())
+        .is_err());
+
+    // Elements which are not members cannot be deleted.
+    assert!(accumulator.delete(&[b"c"]).is_err());
+    assert!(accumulator.delete(&[b"e", b"e"]).is_err());
+
+    // Deleting all elements gives the empty accumulator.
+    accumulator.delete(&[b"a", b"e"]).unwrap();
+    assert_eq!(accumulator, RsaAccumulator::new(&parameters));
+}
+
+#[test]
+fn test_aggregation() {
+    let parameters = parameters();
+    let mut accumulator = RsaAccumulator::new(&parameters);
+    let elements: [&[u8]; 4] = [b"a", b"b", b"c", b"d"];
+    let witnesses = accumulator.add(&elements).unwrap();
+
+    let pairs = elements[..3]
+        .iter()
+        .copied()
+        .zip(witnesses)
+        .collect::<Vec<_>>();
+    let aggregate = MembershipWitness::aggregate(&parameters, &pairs).unwrap();
+    assert!(aggregate
+        .verify_batch(&parameters, accumulator.value(), &elements[..3])
+        .is_ok());
+    assert!(aggregate
+        .verify_batch(&parameters, accumulator.value(), &elements[..2])
+        .is_err());
+    assert!(aggregate
+        .verify_batch(&parameters, accumulator.value(), &elements)
+        .is_err());
+    assert!(MembershipWitness::aggregate(&parameters, &[]).is_err());
+}
+
+#[test]
+fn test_non_membership() {
+    let parameters = parameters();
+    let mut accumulator = RsaAccumulator::new(&parameters);
+    let witness = accumulator.non_membership_witness(b"x").unwrap();
+    assert!(witness
+        .verify(&parameters, accumulator.value(), b"x")
+        .is_ok());
+
+    accumulator.add(&[b"a", b"b"]).unwrap();
+    let witness = accumulator.non_membership_witness(b"x").unwrap();
+    assert!(witness
+        .verify(&parameters, accumulator.value(), b"x")
+        .is_ok());
+    assert!(witness
+        .verify(&parameters, accumulator.value(), b"y")
+        .is_err());
+    assert!(accumulator.non_membership_witness(b"a").is_err());
+
+    // The witness is invalidated when the element is added.
+    accumulator.add(&[b"x"]).unwrap();
+    assert!(witness
+        .verify(&parameters, accumulator.value(), b"x")
+        .is_err());
+}
+
+#[test]
+fn test_invalid_parameters() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    assert!(AccumulatorParameters::generate(512, &mut rng).is_err());
+    let n = (BigUint::from(1u8) << 1024) - 1u8;
+    assert!(AccumulatorParameters::new(n.clone(), BigUint::from(1u8)).is_err());
+    assert!(AccumulatorParameters::new(n.clone(), n.clone()).is_err());
+    assert!(AccumulatorParameters::new(n.clone() - 1u8, BigUint::from(7u8)).is_err());
+    assert!(AccumulatorParameters::new(n, BigUint::from(7u8)).is_ok());
+}
@@ -0,0 +1,234 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::bls12381::min_sig::{BLS12381AggregateSignature, BLS12381KeyPair, BLS12381Signature};
+use crate::certificate_codec::{Certificate, SignerSet};
+use crate::hash::{HashFunction, Sha512};
+use crate::sampled_certificate::*;
+use crate::traits::{KeyPair, Signer};
+use crate::vrf::ecvrf::{ECVRFKeyPair, ECVRFProof};
+use crate::vrf::VRFKeyPair;
+use rand::{rngs::StdRng, SeedableRng};
+
+const SEED: &[u8] = b"round 1";
+const MESSAGE: &[u8] = b"block";
+
+type Committee = SampledCommittee<BLS12381AggregateSignature, ECVRFProof>;
+type Vote = (usize, BLS12381Signature, ECVRFProof);
+
+struct Members {
+    keys: Vec<BLS12381KeyPair>,
+    vrf_keys: Vec<ECVRFKeyPair>,
+}
+
+fn setup(
+    stakes: Vec<u64>,
+    expected_size: u64,
+    threshold: u64,
+    rng: &mut StdRng,
+) -> (Committee, Members) {
+    let keys = stakes
+        .iter()
+        .map(|_| BLS12381KeyPair::generate(rng))
+        .collect::<Vec<_>>();
+    let vrf_keys = stakes
+        .iter()
+        .map(|_| ECVRFKeyPair::generate(rng))
+        .collect::<Vec<_>>();
+    let committee = Committee::new(
+        keys.iter().map(|kp| kp.public().clone()).collect(),
+        vrf_keys.iter().map(|kp| kp.pk.clone()).collect(),
+        stakes,
+        expected_size,
+        threshold,
+    )
+    .unwrap();
+    (committee, Members { keys, vrf_keys })
+}
+
+/// The votes of the members which have seats for the seed.
+fn selected_votes(committee: &Committee, members: &Members, seed: &[u8]) -> Vec<Vote> {
+    let input = sortition_input(seed);
+    (0..committee.size())
+        .filter_map(|i| {
+            let (output, proof) = members.vrf_keys[i].output(&input);
+            (committee.seats(i, &output).unwrap() > 0)
+                .then(|| (i, members.keys[i].sign(MESSAGE), proof))
+        })
+        .collect()
+}
+
+#[test]
+fn test_committee() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let (committee, members) = setup(vec![1, 2, 3], 2, 1, &mut rng);
+    assert_eq!(committee.size(), 3);
+    assert_eq!(committee.threshold(), 1);
+    assert!(committee.seats(3, &[0; 64]).is_err());
+
+    let public_keys = || {
+        members
+            .keys
+            .iter()
+            .map(|kp| kp.public().clone())
+            .collect::<Vec<_>>()
+    };
+    let vrf_public_keys = || {
+        members
+            .vrf_keys
+            .iter()
+            .map(|kp| kp.pk.clone())
+            .collect::<Vec<_>>()
+    };
+    assert!(Committee::new(public_keys(), vrf_public_keys(), vec![1, 2], 2, 1).is_err());
+    assert!(Committee::new(public_keys(), vrf_public_keys(), vec![0, 0, 0], 2, 1).is_err());
+    assert!(Committee::new(public_keys(), vrf_public_keys(), vec![u64::MAX, 1, 0], 2, 1).is_err());
+    assert!(Committee::new(public_keys(), vrf_public_keys(), vec![1, 2, 3], 0, 1).is_err());
+    assert!(Committee::new(public_keys(), vrf_public_keys(), vec![1, 2, 3], 7, 1).is_err());
+    assert!(Committee::new(public_keys(), vrf_public_keys(), vec![1, 2, 3], 2, 0).is_err());
+}
+
+#[test]
+fn test_seats() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    // Each unit of stake is a seat with probability 1/10, so the members have 1, 0, 4.5 and 4.5
+    // seats on average and at most their stake.
+    let (committee, _) = setup(vec![10, 0, 45, 45], 10, 1, &mut rng);
+    let mut sums = [0; 4];
+    for i in 0..1000u32 {
+        let output = Sha512::digest(i.to_be_bytes()).digest;
+        for (j, sum) in sums.iter_mut().enumerate() {
+            let seats = committee.seats(j, &output).unwrap();
+            assert!(seats <= [10, 0, 45, 45][j]);
+            *sum += seats;
+        }
+    }
+    assert!((800..1200).contains(&sums[0]));
+    assert_eq!(sums[1], 0);
+    assert!((4200..4800).contains(&sums[2]));
+    assert!((4200..4800).contains(&sums[3]));
+
+    // The seat probability is 1/4 for a member with a single unit of stake.
+    let (committee, _) = setup(vec![1; 8], 2, 1, &mut rng);
+    let seats = (0..1000u32)
+        .map(|i| {
+            let output = Sha512::digest(i.to_be_bytes()).digest;
+            committee.seats(0, &output).unwrap()
+        })
+        .sum::<u64>();
+    assert!((180..320).contains(&seats));
+
+    // If the expected size is the total stake, every unit of stake is a seat.
+    let (committee, _) = setup(vec![3, 2], 5, 1, &mut rng);
+    assert_eq!(committee.seats(0, &[0; 64]).unwrap(), 3);
+    assert_eq!(committee.seats(1, &[0; 64]).unwrap(), 2);
+}
+
+#[test]
+fn test_sampled_certificate() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let (committee, members) = setup(vec![1; 100], 40, 20, &mut rng);
+    let votes = selected_votes(&committee, &members, SEED);
+    assert!(votes.len() as u64 >= committee.threshold() && votes.len() < committee.size());
+
+    // The order of the votes does not matter.
+    let mut reversed = selected_votes(&committee, &members, SEED);
+    reversed.reverse();
+    let certificate = SampledCertificate::from_votes(&committee, votes).unwrap();
+    assert_eq!(
+        SampledCertificate::from_votes(&committee, reversed).unwrap(),
+        certificate
+    );
+    assert!(certificate.verify(&committee, SEED, MESSAGE).is_ok());
+    assert!(certificate
+        .verify(&committee, SEED, b"other block")
+        .is_err());
+    // The proofs are for another seed.
+    assert!(certificate.verify(&committee, b"round 2", MESSAGE).is_err());
+}
+
+#[test]
+fn test_invalid_certificates() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let (committee, members) = setup(vec![1; 100], 40, 20, &mut rng);
+    let selected = selected_votes(&committee, &members, SEED);
+    let selected_indices = selected.iter().map(|(i, _, _)| *i).collect::<Vec<_>>();
+
+    // Too few signers. Each signer has one seat.
+    let too_few = selected_votes(&committee, &members, SEED)
+        .into_iter()
+        .take(committee.threshold() as usize - 1)
+        .collect();
+    let certificate = SampledCertificate::from_votes(&committee, too_few).unwrap();
+    assert!(certificate.verify(&committee, SEED, MESSAGE).is_err());
+
+    // A member without seats signs with a valid VRF proof.
+    let input = sortition_input(SEED);
+    let unselected = (0..committee.size())
+        .find(|i| !selected_indices.contains(i))
+        .unwrap();
+    let mut votes = selected_votes(&committee, &members, SEED);
+    votes.push((
+        unselected,
+        members.keys[unselected].sign(MESSAGE),
+        members.vrf_keys[unselected].prove(&input),
+    ));
+    let certificate = SampledCertificate::from_votes(&committee, votes).unwrap();
+    assert!(certificate.verify(&committee, SEED, MESSAGE).is_err());
+
+    // A member with seats uses the VRF proof of another member with seats.
+    let mut votes = selected_votes(&committee, &members, SEED);
+    votes[0].2 = members.vrf_keys[votes[1].0].prove(&input);
+    let certificate = SampledCertificate::from_votes(&committee, votes).unwrap();
+    assert!(certificate.verify(&committee, SEED, MESSAGE).is_err());
+
+    // A proof is missing.
+    let mut certificate =
+        SampledCertificate::from_votes(&committee, selected_votes(&committee, &members, SEED))
+            .unwrap();
+    certificate.proofs.pop();
+    assert!(certificate.verify(&committee, SEED, MESSAGE).is_err());
+
+    // The signer set is for another committee size.
+    let certificate =
+        SampledCertificate::from_votes(&committee, selected_votes(&committee, &members, SEED))
+            .unwrap();
+    let signers = SignerSet::new(
+        committee.size() + 1,
+        certificate.certificate.signers.signers(),
+    )
+    .unwrap();
+    let certificate = SampledCertificate {
+        certificate: Certificate::new(certificate.certificate.signature, signers).unwrap(),
+        proofs: certificate.proofs,
+    };
+    assert!(certificate.verify(&committee, SEED, MESSAGE).is_err());
+}
+
+#[test]
+fn test_threshold_of_seats() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    // The first member has 30 seats on average and the others at most one.
+    let mut stakes = vec![60];
+    stakes.extend([1; 40]);
+    let (committee, members) = setup(stakes, 50, 10, &mut rng);
+    let input = sortition_input(SEED);
+    let (output, proof) = members.vrf_keys[0].output(&input);
+    let seats = committee.seats(0, &output).unwrap();
+    assert!(seats >= committee.threshold());
+    assert_eq!(committee.verify_selection(0, SEED, &proof).unwrap(), seats);
+
+    // A single member with enough seats forms a certificate.
+    let vote = (0, members.keys[0].sign(MESSAGE), proof);
+    let certificate = SampledCertificate::from_votes(&committee, vec![vote]).unwrap();
+    assert!(certificate.verify(&committee, SEED, MESSAGE).is_ok());
+
+    // Members with a single unit of stake need a threshold of signers.
+    let votes = selected_votes(&committee, &members, SEED)
+        .into_iter()
+        .filter(|(i, _, _)| *i != 0)
+        .take(committee.threshold() as usize - 1)
+        .collect();
+    let certificate = SampledCertificate::from_votes(&committee, votes).unwrap();
+    assert!(certificate.verify(&committee, SEED, MESSAGE).is_err());
+}
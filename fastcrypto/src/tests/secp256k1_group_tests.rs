@@ -0,0 +1,62 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::groups::secp256k1::{Secp256k1Point, Secp256k1Scalar};
+use crate::groups::{GroupElement, Scalar};
+use crate::secp256k1::Secp256k1KeyPair;
+use crate::serde_helpers::ToFromByteArray;
+use crate::traits::KeyPair;
+use rand::{rngs::StdRng, SeedableRng as _};
+
+#[test]
+fn test_arithmetic() {
+    let g = Secp256k1Point::generator();
+
+    let p1 = g * Secp256k1Scalar::from(5);
+    let p2 = g + g + g + g + g + g - g;
+    assert_eq!(p1, p2);
+
+    let mut p3 = Secp256k1Point::zero();
+    p3 += p2;
+    assert_eq!(p1, p3);
+
+    let p4 = g * (Secp256k1Scalar::from(7) - Secp256k1Scalar::from(2));
+    assert_eq!(p1, p4);
+
+    let s = (Secp256k1Scalar::from(10) / Secp256k1Scalar::from(2)).unwrap();
+    assert_eq!(s, Secp256k1Scalar::from(5));
+    assert!((Secp256k1Scalar::from(10) / Secp256k1Scalar::zero()).is_err());
+}
+
+#[test]
+fn test_serialization() {
+    // The generator in compressed SEC1 form.
+    let g = Secp256k1Point::generator();
+    assert_eq!(
+        hex::encode(g.to_byte_array()),
+        "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+    );
+
+    for p in [Secp256k1Point::zero(), g, g * Secp256k1Scalar::from(1234)] {
+        let bytes = bincode::serialize(&p).unwrap();
+        assert_eq!(p, bincode::deserialize::<Secp256k1Point>(&bytes).unwrap());
+    }
+
+    let s = Secp256k1Scalar::rand(&mut StdRng::from_seed([0; 32]));
+    let bytes = bincode::serialize(&s).unwrap();
+    assert_eq!(s, bincode::deserialize::<Secp256k1Scalar>(&bytes).unwrap());
+
+    // The group order is not a canonical scalar.
+    let order =
+        hex::decode("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141").unwrap();
+    assert!(Secp256k1Scalar::from_byte_array(&order.try_into().unwrap()).is_err());
+    assert!(Secp256k1Point::from_byte_array(&[2; 33]).is_err());
+}
+
+#[test]
+fn test_consistent_with_keys() {
+    let kp = Secp256k1KeyPair::generate(&mut StdRng::from_seed([0; 32]));
+    let sk = Secp256k1Scalar::from_byte_array(kp.secret.as_ref().try_into().unwrap()).unwrap();
+    let pk = Secp256k1Point::generator() * sk;
+    assert_eq!(pk.to_byte_array().as_slice(), kp.public().as_ref());
+}
@@ -0,0 +1,85 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::groups::secp256r1::{Secp256r1Point, Secp256r1Scalar};
+use crate::groups::{FixedBaseMultiplication, GroupElement, Scalar};
+use crate::secp256r1::Secp256r1KeyPair;
+use crate::serde_helpers::ToFromByteArray;
+use crate::traits::KeyPair;
+use rand::{rngs::StdRng, SeedableRng as _};
+
+#[test]
+fn test_arithmetic() {
+    let g = Secp256r1Point::generator();
+
+    let p1 = g * Secp256r1Scalar::from(5);
+    let p2 = g + g + g + g + g + g - g;
+    assert_eq!(p1, p2);
+
+    let mut p3 = Secp256r1Point::zero();
+    p3 += p2;
+    assert_eq!(p1, p3);
+
+    let p4 = g * (Secp256r1Scalar::from(7) - Secp256r1Scalar::from(2));
+    assert_eq!(p1, p4);
+    assert_eq!(p1, Secp256r1Point::mul_generator(&Secp256r1Scalar::from(5)));
+
+    let s = (Secp256r1Scalar::from(10) / Secp256r1Scalar::from(2)).unwrap();
+    assert_eq!(s, Secp256r1Scalar::from(5));
+    assert!((Secp256r1Scalar::from(10) / Secp256r1Scalar::zero()).is_err());
+}
+
+#[test]
+fn test_serialization() {
+    // The generator in compressed SEC1 form.
+    let g = Secp256r1Point::generator();
+    assert_eq!(
+        hex::encode(g.to_byte_array()),
+        "036b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296"
+    );
+
+    for p in [Secp256r1Point::zero(), g, g * Secp256r1Scalar::from(1234)] {
+        let bytes = bincode::serialize(&p).unwrap();
+        assert_eq!(p, bincode::deserialize::<Secp256r1Point>(&bytes).unwrap());
+    }
+
+    let s = Secp256r1Scalar::rand(&mut StdRng::from_seed([0; 32]));
+    let bytes = bincode::serialize(&s).unwrap();
+    assert_eq!(s, bincode::deserialize::<Secp256r1Scalar>(&bytes).unwrap());
+
+    // The group order is not a canonical scalar.
+    let order =
+        hex::decode("ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551").unwrap();
+    assert!(Secp256r1Scalar::from_byte_array(&order.try_into().unwrap()).is_err());
+    assert!(Secp256r1Point::from_byte_array(&[2; 33]).is_err());
+}
+
+#[test]
+fn test_hash_to_curve() {
+    // Test vector for an empty message for P256_XMD:SHA-256_SSWU_RO_ from appendix J.1.1 of
+    // RFC 9380.
+    let dst: &[u8] = b"QUUX-V01-CS02-with-P256_XMD:SHA-256_SSWU_RO_";
+    let p = Secp256r1Point::hash_to_curve(&[b""], &[dst]).unwrap();
+    assert_eq!(
+        hex::encode(p.to_byte_array()),
+        "032c15230b26dbc6fc9a37051158c95b79656e17a1a920b11394ca91c44247d3e4"
+    );
+
+    // The message and the DST may be split into several parts.
+    assert_eq!(
+        Secp256r1Point::hash_to_curve(&[b"a", b"bc"], &[&dst[..4], &dst[4..]]).unwrap(),
+        Secp256r1Point::hash_to_curve(&[b"abc"], &[dst]).unwrap()
+    );
+    assert_ne!(
+        Secp256r1Scalar::hash_to_field(&[b"abc"], &[dst]).unwrap(),
+        Secp256r1Scalar::hash_to_field(&[b"abd"], &[dst]).unwrap()
+    );
+}
+
+#[test]
+fn test_consistent_with_keys() {
+    let kp = Secp256r1KeyPair::generate(&mut StdRng::from_seed([0; 32]));
+    let sk = Secp256r1Scalar::from_byte_array(kp.secret.as_ref().try_into().unwrap()).unwrap();
+    let pk = Secp256r1Point::generator() * sk;
+    assert_eq!(pk.to_byte_array().as_slice(), kp.public().as_ref());
+}
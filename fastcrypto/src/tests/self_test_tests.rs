@@ -0,0 +1,12 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::self_test::{run, run_once};
+
+#[test]
+fn test_self_test() {
+    assert!(run().is_ok());
+    assert!(run_once().is_ok());
+    // The cached result is returned on later calls.
+    assert!(run_once().is_ok());
+}
@@ -3,7 +3,7 @@
 
 use crate::bls12381::min_sig::BLS12381KeyPair;
 use crate::hash::{HashFunction, Sha256};
-use crate::signature_service::SignatureService;
+use crate::signature_service::{BatchingConfig, BatchingSignatureService, SignatureService};
 use crate::traits::{KeyPair, VerifyingKey};
 use rand::rngs::StdRng;
 use rand::SeedableRng;
@@ -25,3 +25,80 @@ async fn signature_service() {
     // Verify the signature we received.
     assert!(pk.verify(digest.as_ref(), &signature).is_ok());
 }
+
+#[tokio::test]
+async fn batching_signature_service() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let kp = BLS12381KeyPair::generate(&mut rng);
+    let pk = kp.public().clone();
+
+    let service = BatchingSignatureService::new_with_config(
+        kp,
+        BatchingConfig {
+            queue_capacity: 100,
+            max_batch_size: 8,
+        },
+    );
+
+    let handles = (0..50u32)
+        .map(|i| {
+            let service = service.clone();
+            tokio::spawn(async move {
+                let digest = Sha256::digest(i.to_le_bytes());
+                (digest, service.request_signature(digest).await)
+            })
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        let (digest, signature) = handle.await.unwrap();
+        assert!(pk.verify(digest.as_ref(), &signature).is_ok());
+    }
+
+    let metrics = service.metrics();
+    assert_eq!(metrics.signed(), 50);
+    assert_eq!(metrics.pending(), 0);
+    assert!(metrics.batches() >= 50 / 8);
+    assert!(metrics.max_latency() >= metrics.average_latency());
+
+    let digest = Sha256::digest(b"Hello, world!");
+    let signature = service.try_request_signature(digest).await.unwrap();
+    assert!(pk.verify(digest.as_ref(), &signature).is_ok());
+    assert_eq!(metrics.rejected(), 0);
+}
+
+#[tokio::test]
+async fn cancelled_signature_request() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let kp = BLS12381KeyPair::generate(&mut rng);
+    let pk = kp.public().clone();
+
+    let service = BatchingSignatureService::new_with_config(
+        kp,
+        BatchingConfig {
+            queue_capacity: 1,
+            max_batch_size: 1,
+        },
+    );
+
+    // The batcher does not run before this task yields, so the first request fills the queue and
+    // the second waits for room.
+    let digest = Sha256::digest(b"Hello, world!");
+    let first = service.request_signature(digest);
+    let mut second = Box::pin(service.request_signature(Sha256::digest(b"Goodbye, world!")));
+    tokio::pin!(first);
+    tokio::select! {
+        biased;
+        _ = &mut first => panic!("The first request was signed before the batcher ran"),
+        _ = &mut second => panic!("The second request was queued in a full queue"),
+        _ = std::future::ready(()) => {}
+    }
+    assert_eq!(service.metrics().pending(), 1);
+
+    // Cancelling the blocked request does not leave it pending.
+    drop(second);
+    assert_eq!(service.metrics().pending(), 1);
+    let signature = first.await;
+    assert!(pk.verify(digest.as_ref(), &signature).is_ok());
+    assert_eq!(service.metrics().pending(), 0);
+    assert_eq!(service.metrics().signed(), 1);
+}
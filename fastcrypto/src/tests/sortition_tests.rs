@@ -0,0 +1,117 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::sortition::*;
+
+#[test]
+fn test_vectors() {
+    // Computed with an independent implementation of the specification in the module documentation.
+    let mut rng = SortitionRng::new(b"domain", b"seed");
+    assert_eq!(rng.next_u64(), 0xbba639baeb8f6e9a);
+    assert_eq!(rng.next_u64(), 0x2ca4cb7ae0cb5602);
+    assert_eq!(rng.next_u64(), 0xecf10bafb836326e);
+
+    let mut elements = (0..10).collect::<Vec<_>>();
+    SortitionRng::new(b"domain", b"seed").shuffle(&mut elements);
+    assert_eq!(elements, vec![2, 4, 9, 0, 3, 5, 7, 6, 1, 8]);
+
+    let committee = SortitionRng::new(b"domain", b"seed")
+        .sample_without_replacement(&[10, 5, 0, 30, 20, 1, 7], 4)
+        .unwrap();
+    assert_eq!(committee, vec![0, 3, 6, 1]);
+}
+
+#[test]
+fn test_domain_separation() {
+    assert_ne!(
+        SortitionRng::new(b"domain", b"seed").next_u64(),
+        SortitionRng::new(b"domai", b"nseed").next_u64()
+    );
+    assert_ne!(
+        SortitionRng::new(b"domain", b"seed").next_u64(),
+        SortitionRng::new(b"other", b"seed").next_u64()
+    );
+}
+
+#[test]
+fn test_uniform() {
+    let mut rng = SortitionRng::new(b"domain", b"seed");
+    assert!(rng.uniform(0).is_err());
+    assert_eq!(rng.uniform(1).unwrap(), 0);
+    assert!(rng.uniform(u64::MAX).unwrap() < u64::MAX);
+    let mut counts = [0; 3];
+    for _ in 0..3000 {
+        counts[rng.uniform(3).unwrap() as usize] += 1;
+    }
+    assert!(counts.iter().all(|c| (900..1100).contains(c)));
+}
+
+#[test]
+fn test_weighted_sampling() {
+    let mut rng = SortitionRng::new(b"domain", b"seed");
+    assert!(rng.weighted_index(&[]).is_err());
+    assert!(rng.weighted_index(&[0, 0]).is_err());
+    assert!(rng.weighted_index(&[u64::MAX, 1]).is_err());
+    assert_eq!(rng.weighted_index(&[0, 3, 0]).unwrap(), 1);
+
+    let mut counts = [0; 3];
+    for _ in 0..4000 {
+        counts[rng.weighted_index(&[1, 0, 3]).unwrap()] += 1;
+    }
+    assert_eq!(counts[1], 0);
+    assert!((900..1100).contains(&counts[0]));
+
+    // Zero weights are never chosen, and there must be enough non-zero weights.
+    let mut sample = rng.sample_without_replacement(&[1, 0, 2, 3, 0], 3).unwrap();
+    sample.sort();
+    assert_eq!(sample, vec![0, 2, 3]);
+    assert!(rng.sample_without_replacement(&[1, 0, 2], 3).is_err());
+    assert!(rng
+        .sample_without_replacement(&[1, 0, 2], 0)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_shuffle() {
+    let mut rng = SortitionRng::new(b"domain", b"seed");
+    let mut empty: [u8; 0] = [];
+    rng.shuffle(&mut empty);
+    let mut elements = (0..100).collect::<Vec<_>>();
+    rng.shuffle(&mut elements);
+    assert_ne!(elements, (0..100).collect::<Vec<_>>());
+    elements.sort();
+    assert_eq!(elements, (0..100).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_leader_schedule() {
+    let stakes = [10, 0, 30, 60];
+    let schedule = leader_schedule(b"randomness", &stakes, 10000).unwrap();
+    assert_eq!(
+        schedule,
+        leader_schedule(b"randomness", &stakes, 10000).unwrap()
+    );
+    assert_ne!(schedule, leader_schedule(b"other", &stakes, 10000).unwrap());
+
+    // The schedule agrees with weighted sampling.
+    let mut rng = SortitionRng::new(LEADER_SCHEDULE_DOMAIN, b"randomness");
+    assert!(schedule
+        .iter()
+        .take(100)
+        .all(|leader| *leader == rng.weighted_index(&stakes).unwrap()));
+
+    // With ε = 0.02, each count is within the bound except with probability 2 exp(-8).
+    for (i, stake) in stakes.iter().enumerate() {
+        let count = schedule.iter().filter(|leader| **leader == i).count();
+        let expected = *stake as usize * 100;
+        assert!(count + 200 >= expected && count <= expected + 200);
+    }
+
+    assert!(leader_schedule(b"randomness", &stakes, 0)
+        .unwrap()
+        .is_empty());
+    assert!(leader_schedule(b"randomness", &[0, 0], 1).is_err());
+    assert!(leader_schedule(b"randomness", &[], 1).is_err());
+    assert!(leader_schedule(b"randomness", &[u64::MAX, 1], 1).is_err());
+}
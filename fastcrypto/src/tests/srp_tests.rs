@@ -0,0 +1,96 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::hash::{Sha256, Sha512};
+use crate::srp::{SrpClient, SrpGroup, SrpServer, SrpVerifier};
+use crate::test_helpers::verify_serialization;
+use rand::{rngs::StdRng, SeedableRng as _};
+
+const IDENTITY: &[u8] = b"alice";
+const PASSWORD: &[u8] = b"password";
+
+#[test]
+fn test_login() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    for group in [SrpGroup::rfc5054_2048(), SrpGroup::rfc5054_3072()] {
+        let verifier = SrpVerifier::new::<Sha256, 32, _>(&group, IDENTITY, PASSWORD, &mut rng);
+        verify_serialization(&verifier, None);
+
+        let (client, a) = SrpClient::new(&group, &mut rng);
+        let (server, b) = SrpServer::new::<Sha256, 32, _>(&group, &verifier, &mut rng);
+        let (client, m1) = client
+            .process_challenge::<Sha256, 32>(IDENTITY, PASSWORD, verifier.salt(), &b)
+            .unwrap();
+        let (m2, server_key) = server
+            .verify_client::<Sha256, 32>(IDENTITY, &a, &m1)
+            .unwrap();
+        let client_key = client.verify_server(&m2).unwrap();
+        assert_eq!(client_key, server_key);
+    }
+
+    // Other hash functions can be used.
+    let group = SrpGroup::rfc5054_2048();
+    let verifier = SrpVerifier::new::<Sha512, 64, _>(&group, IDENTITY, PASSWORD, &mut rng);
+    let (client, a) = SrpClient::new(&group, &mut rng);
+    let (server, b) = SrpServer::new::<Sha512, 64, _>(&group, &verifier, &mut rng);
+    let (client, m1) = client
+        .process_challenge::<Sha512, 64>(IDENTITY, PASSWORD, verifier.salt(), &b)
+        .unwrap();
+    let (m2, server_key) = server
+        .verify_client::<Sha512, 64>(IDENTITY, &a, &m1)
+        .unwrap();
+    assert_eq!(client.verify_server(&m2).unwrap(), server_key);
+}
+
+#[test]
+fn test_wrong_password() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let group = SrpGroup::rfc5054_2048();
+    let verifier = SrpVerifier::new::<Sha256, 32, _>(&group, IDENTITY, PASSWORD, &mut rng);
+
+    let (client, a) = SrpClient::new(&group, &mut rng);
+    let (server, b) = SrpServer::new::<Sha256, 32, _>(&group, &verifier, &mut rng);
+    let (_, m1) = client
+        .process_challenge::<Sha256, 32>(IDENTITY, b"wrong password", verifier.salt(), &b)
+        .unwrap();
+    assert!(server
+        .verify_client::<Sha256, 32>(IDENTITY, &a, &m1)
+        .is_err());
+}
+
+#[test]
+fn test_invalid_public_values() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let group = SrpGroup::rfc5054_2048();
+    let verifier = SrpVerifier::new::<Sha256, 32, _>(&group, IDENTITY, PASSWORD, &mut rng);
+
+    // A public value which is zero modulo N must be rejected.
+    let (client, _) = SrpClient::new(&group, &mut rng);
+    assert!(client
+        .process_challenge::<Sha256, 32>(IDENTITY, PASSWORD, verifier.salt(), &[0u8; 256])
+        .is_err());
+
+    let (server, _) = SrpServer::new::<Sha256, 32, _>(&group, &verifier, &mut rng);
+    let n = hex::decode("AC6BDB41324A9A9BF166DE5E1389582FAF72B6651987EE07FC3192943DB56050A37329CBB4A099ED8193E0757767A13DD52312AB4B03310DCD7F48A9DA04FD50E8083969EDB767B0CF6095179A163AB3661A05FBD5FAAAE82918A9962F0B93B855F97993EC975EEAA80D740ADBF4FF747359D041D5C33EA71D281E446B14773BCA97B43A23FB801676BD207A436C6481F1D2B9078717461A5B9D32E688F87748544523B524B0D57D5EA77A2775D2ECFA032CFBDBF52FB3786160279004E57AE6AF874E7303CE53299CCC041C7BC308D82A5698F3A8D0C38271AE35F8E9DBFBB694B5C803D89F7AE435DE236D525F54759B65E372FCD68EF20FA7111F9E4AFF73").unwrap();
+    assert!(server
+        .verify_client::<Sha256, 32>(IDENTITY, &n, &[0u8; 32])
+        .is_err());
+
+    assert!(SrpGroup::new(&n, &[2]).is_ok());
+    assert!(SrpGroup::new(&n, &[1]).is_err());
+    assert!(SrpGroup::new(&[23], &[5]).is_err());
+}
+
+#[test]
+fn test_wrong_server_proof() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let group = SrpGroup::rfc5054_2048();
+    let verifier = SrpVerifier::new::<Sha256, 32, _>(&group, IDENTITY, PASSWORD, &mut rng);
+
+    let (client, _) = SrpClient::new(&group, &mut rng);
+    let (_, b) = SrpServer::new::<Sha256, 32, _>(&group, &verifier, &mut rng);
+    let (client, _) = client
+        .process_challenge::<Sha256, 32>(IDENTITY, PASSWORD, verifier.salt(), &b)
+        .unwrap();
+    assert!(client.verify_server(&[0u8; 32]).is_err());
+}
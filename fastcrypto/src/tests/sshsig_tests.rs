@@ -0,0 +1,194 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::sshsig::*;
+use base64ct::{Base64, Encoding};
+
+// The test vectors below were created with OpenSSH 9.2 using `ssh-keygen -Y sign`.
+const MESSAGE: &[u8] = b"hello sshsig\n";
+
+const ED25519_KEY: &str =
+    "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIIOj0BxtZ2SYqTs+itr/WqbSBnmMLujgVVEkFUMND8Vi test";
+const OTHER_ED25519_KEY: &str =
+    "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAINLoyiW1oLhIl87Yvnk7vkS7cPros9vS12UcX+1r3y28 other";
+const ECDSA_KEY: &str = "ecdsa-sha2-nistp256 AAAAE2VjZHNhLXNoYTItbmlzdHAyNTYAAAAIbmlzdHAyNTYAAABBBOm3EbIjvW2L0p892DIvTSZq+teJJmuc9jLMVIobF8KrA0zbf673wTqDnvPhzUNq8vv/50fnFNQ9DWNq4xxQBvs= test";
+
+// Namespace "file" with SHA-512.
+const ED25519_SIGNATURE: &str = "-----BEGIN SSH SIGNATURE-----
+U1NIU0lHAAAAAQAAADMAAAALc3NoLWVkMjU1MTkAAAAgg6PQHG1nZJipOz6K2v9aptIGeY
+wu6OBVUSQVQw0PxWIAAAAEZmlsZQAAAAAAAAAGc2hhNTEyAAAAUwAAAAtzc2gtZWQyNTUx
+OQAAAEAXCCptSYrNZWZTh2sl+rVCcB0DevbUiI9jy/7I0rAKJInAQmf3OSwk75MzO7Ls5k
+4fBxCNn3Fgl6VMa9x0csoP
+-----END SSH SIGNATURE-----
+";
+
+// Namespace "git" with SHA-256.
+const ED25519_SHA256_SIGNATURE: &str = "-----BEGIN SSH SIGNATURE-----
+U1NIU0lHAAAAAQAAADMAAAALc3NoLWVkMjU1MTkAAAAgg6PQHG1nZJipOz6K2v9aptIGeY
+wu6OBVUSQVQw0PxWIAAAADZ2l0AAAAAAAAAAZzaGEyNTYAAABTAAAAC3NzaC1lZDI1NTE5
+AAAAQCY9o0Pg4BMioBvHAB3hB9ZAtsftgszp+5XBLSHV4W6FWs8z4j62kYD53iLUiGcbag
+NiYq1Zr8piNtXChbAbiQY=
+-----END SSH SIGNATURE-----
+";
+
+// Namespace "file" with SHA-512. The s value of this signature is high.
+const ECDSA_SIGNATURE: &str = "-----BEGIN SSH SIGNATURE-----
+U1NIU0lHAAAAAQAAAGgAAAATZWNkc2Etc2hhMi1uaXN0cDI1NgAAAAhuaXN0cDI1NgAAAE
+EE6bcRsiO9bYvSnz3YMi9NJmr614kma5z2MsxUihsXwqsDTNt/rvfBOoOe8+HNQ2ry+//n
+R+cU1D0NY2rjHFAG+wAAAARmaWxlAAAAAAAAAAZzaGE1MTIAAABlAAAAE2VjZHNhLXNoYT
+ItbmlzdHAyNTYAAABKAAAAIQCLMIAqG0lRNWyKDoiUj0VmOCRho3lDAzP8XLI03kTIwgAA
+ACEAv3AjkzIAgnHdW5AWgglcMG9g3jk2ZmjeqSXzweoc4fI=
+-----END SSH SIGNATURE-----
+";
+
+// Namespace "git" with SHA-256. The s value of this signature is low and has no leading zero.
+const ECDSA_SHA256_SIGNATURE: &str = "-----BEGIN SSH SIGNATURE-----
+U1NIU0lHAAAAAQAAAGgAAAATZWNkc2Etc2hhMi1uaXN0cDI1NgAAAAhuaXN0cDI1NgAAAE
+EE6bcRsiO9bYvSnz3YMi9NJmr614kma5z2MsxUihsXwqsDTNt/rvfBOoOe8+HNQ2ry+//n
+R+cU1D0NY2rjHFAG+wAAAANnaXQAAAAAAAAABnNoYTI1NgAAAGQAAAATZWNkc2Etc2hhMi
+1uaXN0cDI1NgAAAEkAAAAhAPicekuwY8bDLgBqj+CnsZBdGDPzD5BUydZ0mbba9YvTAAAA
+IDXKQkLH58TwqAy3NoZO4hW44J7RocLsoJ44pIPNhZ4v
+-----END SSH SIGNATURE-----
+";
+
+fn decode_armored(armored: &str) -> Vec<u8> {
+    let encoded = armored
+        .lines()
+        .filter(|line| !line.starts_with("-----"))
+        .collect::<String>();
+    Base64::decode_vec(&encoded).unwrap()
+}
+
+fn check_signature(
+    public_key: &SshPublicKey,
+    armored: &str,
+    namespace: &str,
+    hash_algorithm: SshHashAlgorithm,
+) {
+    let signature = SshSignature::from_armored(armored).unwrap();
+    assert_eq!(signature.public_key(), public_key);
+    assert_eq!(signature.namespace(), namespace);
+    assert_eq!(signature.hash_algorithm(), hash_algorithm);
+    assert!(signature.verify(public_key, namespace, MESSAGE).is_ok());
+    assert!(signature
+        .verify_reader(public_key, namespace, MESSAGE)
+        .is_ok());
+    assert_eq!(
+        SshSignature::from_bytes(&decode_armored(armored)).unwrap(),
+        signature
+    );
+
+    // Other messages, namespaces and keys.
+    assert!(signature
+        .verify(public_key, namespace, b"hello sshsig")
+        .is_err());
+    assert!(signature.verify(public_key, "other", MESSAGE).is_err());
+    let other_key = SshPublicKey::from_openssh(OTHER_ED25519_KEY).unwrap();
+    assert!(signature.verify(&other_key, namespace, MESSAGE).is_err());
+
+    // A modified signature.
+    let mut bytes = decode_armored(armored);
+    let last = bytes.len() - 1;
+    bytes[last] ^= 1;
+    assert!(SshSignature::from_bytes(&bytes)
+        .and_then(|s| s.verify(public_key, namespace, MESSAGE))
+        .is_err());
+}
+
+#[test]
+fn test_ed25519() {
+    let public_key = SshPublicKey::from_openssh(ED25519_KEY).unwrap();
+    assert!(matches!(public_key, SshPublicKey::Ed25519(_)));
+    check_signature(
+        &public_key,
+        ED25519_SIGNATURE,
+        "file",
+        SshHashAlgorithm::Sha512,
+    );
+    check_signature(
+        &public_key,
+        ED25519_SHA256_SIGNATURE,
+        "git",
+        SshHashAlgorithm::Sha256,
+    );
+}
+
+#[test]
+fn test_ecdsa_p256() {
+    let public_key = SshPublicKey::from_openssh(ECDSA_KEY).unwrap();
+    assert!(matches!(public_key, SshPublicKey::EcdsaP256(_)));
+    check_signature(
+        &public_key,
+        ECDSA_SIGNATURE,
+        "file",
+        SshHashAlgorithm::Sha512,
+    );
+    check_signature(
+        &public_key,
+        ECDSA_SHA256_SIGNATURE,
+        "git",
+        SshHashAlgorithm::Sha256,
+    );
+
+    // A signature by another type of key.
+    let signature = SshSignature::from_armored(ED25519_SIGNATURE).unwrap();
+    assert!(signature.verify(&public_key, "file", MESSAGE).is_err());
+}
+
+#[test]
+fn test_invalid_encodings() {
+    let bytes = decode_armored(ED25519_SIGNATURE);
+    assert!(SshSignature::from_bytes(&bytes).is_ok());
+
+    // Truncated and extended signatures.
+    assert!(SshSignature::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    assert!(SshSignature::from_bytes(&[bytes.as_slice(), &[0]].concat()).is_err());
+    assert!(SshSignature::from_bytes(&[]).is_err());
+
+    // Wrong magic and version.
+    let mut modified = bytes.clone();
+    modified[0] = b'X';
+    assert!(SshSignature::from_bytes(&modified).is_err());
+    let mut modified = bytes.clone();
+    modified[9] = 2;
+    assert!(SshSignature::from_bytes(&modified).is_err());
+
+    // Unsupported hash functions.
+    let position = bytes.windows(6).position(|w| w == b"sha512").unwrap();
+    let mut modified = bytes.clone();
+    modified[position..position + 6].copy_from_slice(b"sha384");
+    assert!(SshSignature::from_bytes(&modified).is_err());
+
+    // Broken armor.
+    assert!(SshSignature::from_armored(&ED25519_SIGNATURE[1..]).is_err());
+    assert!(SshSignature::from_armored(&ED25519_SIGNATURE.replace('U', "*")).is_err());
+    assert!(SshSignature::from_armored(ED25519_SIGNATURE.trim_end_matches('\n')).is_ok());
+}
+
+#[test]
+fn test_public_keys() {
+    let public_key = SshPublicKey::from_openssh(ED25519_KEY).unwrap();
+
+    // The comment is optional, but the key type must match the encoded key.
+    let without_comment = ED25519_KEY.rsplit_once(' ').unwrap().0;
+    assert_eq!(
+        SshPublicKey::from_openssh(without_comment).unwrap(),
+        public_key
+    );
+    assert!(SshPublicKey::from_openssh(&ED25519_KEY.replace("ssh-ed25519", "ssh-rsa")).is_err());
+    assert!(SshPublicKey::from_openssh("ssh-ed25519").is_err());
+    assert!(SshPublicKey::from_openssh("").is_err());
+
+    let encoded = ED25519_KEY.split(' ').nth(1).unwrap();
+    let bytes = Base64::decode_vec(encoded).unwrap();
+    assert_eq!(SshPublicKey::from_bytes(&bytes).unwrap(), public_key);
+    assert!(SshPublicKey::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    assert!(SshPublicKey::from_bytes(&[bytes.as_slice(), &[0]].concat()).is_err());
+
+    // Only uncompressed points on P-256.
+    let encoded = ECDSA_KEY.split(' ').nth(1).unwrap();
+    let mut bytes = Base64::decode_vec(encoded).unwrap();
+    let last = bytes.len() - 65;
+    bytes[last] = 0x02;
+    assert!(SshPublicKey::from_bytes(&bytes).is_err());
+}
@@ -0,0 +1,58 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::time_lock::{TimeLockPuzzle, TimeLockTrapdoor, MIN_MODULUS_BITS};
+use rand::{rngs::StdRng, SeedableRng as _};
+
+fn trapdoor(rng: &mut StdRng) -> TimeLockTrapdoor {
+    TimeLockTrapdoor::generate(MIN_MODULUS_BITS, rng).unwrap()
+}
+
+#[test]
+fn test_solve() {
+    let rng = &mut StdRng::from_seed([0; 32]);
+    let trapdoor = trapdoor(rng);
+    for (message, iterations) in [(&b"hello"[..], 0), (b"", 1), (b"world", 1000)] {
+        let puzzle = trapdoor.create_puzzle(message, iterations, rng);
+        assert_eq!(puzzle.iterations(), iterations);
+        assert_eq!(puzzle.modulus(), &trapdoor.modulus());
+        assert_eq!(puzzle.solve().unwrap(), message);
+        assert_eq!(trapdoor.open(&puzzle).unwrap(), message);
+    }
+
+    // Puzzles with the same message use different bases.
+    assert_ne!(
+        trapdoor.create_puzzle(b"hello", 10, rng),
+        trapdoor.create_puzzle(b"hello", 10, rng)
+    );
+}
+
+#[test]
+fn test_invalid_puzzles() {
+    let rng = &mut StdRng::from_seed([0; 32]);
+    let trapdoor = trapdoor(rng);
+    let puzzle = trapdoor.create_puzzle(b"hello", 100, rng);
+
+    // Changing the number of iterations changes the solution.
+    let mut json = serde_json::to_value(&puzzle).unwrap();
+    json["iterations"] = 99.into();
+    let modified: TimeLockPuzzle = serde_json::from_value(json).unwrap();
+    assert!(modified.solve().is_err());
+    assert!(trapdoor.open(&modified).is_err());
+
+    // A base larger than the modulus is rejected.
+    let mut json = serde_json::to_value(&puzzle).unwrap();
+    json["base"] = json["modulus"].clone();
+    let modified: TimeLockPuzzle = serde_json::from_value(json).unwrap();
+    assert!(modified.solve().is_err());
+
+    // Only the creator can open a puzzle.
+    assert!(trapdoor(rng).open(&puzzle).is_err());
+}
+
+#[test]
+fn test_invalid_modulus_size() {
+    let rng = &mut StdRng::from_seed([0; 32]);
+    assert!(TimeLockTrapdoor::generate(MIN_MODULUS_BITS - 2, rng).is_err());
+    assert!(TimeLockTrapdoor::generate(MIN_MODULUS_BITS + 1, rng).is_err());
+}
@@ -0,0 +1,100 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::test_helpers::verify_serialization;
+use crate::vdf::*;
+use num_bigint::BigInt;
+use rand::{rngs::StdRng, SeedableRng};
+use std::time::Duration;
+
+#[test]
+fn test_evaluate_and_verify() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let parameters = VdfParameters::generate(256, 100, &mut rng).unwrap();
+    assert_eq!(parameters.discriminant().bits(), 256);
+    assert_eq!(parameters.discriminant() % 4, BigInt::from(-3));
+
+    let input = parameters.default_input();
+    let (output, proof) = parameters.evaluate(&input).unwrap();
+    assert!(parameters.verify(&input, &output, &proof).is_ok());
+    assert_eq!(
+        parameters.evaluate(&input).unwrap(),
+        (output.clone(), proof.clone())
+    );
+
+    // Wrong output or wrong number of iterations.
+    assert!(parameters.verify(&input, &input, &proof).is_err());
+    let other = VdfParameters::new(parameters.discriminant().clone(), 101).unwrap();
+    assert!(other.verify(&input, &output, &proof).is_err());
+
+    // Forms from another class group are rejected.
+    let other = VdfParameters::generate(256, 100, &mut rng).unwrap();
+    assert!(other.evaluate(&input).is_err());
+    assert!(other.verify(&input, &output, &proof).is_err());
+
+    verify_serialization(&parameters, None);
+    verify_serialization(&proof, None);
+}
+
+#[test]
+fn test_invalid_parameters() {
+    assert!(VdfParameters::new(BigInt::from(-23), 0).is_err());
+    assert!(VdfParameters::new(BigInt::from(-21), 10).is_err());
+    assert!(VdfParameters::new(BigInt::from(23), 10).is_err());
+    assert!(VdfParameters::new(BigInt::from(-23), 10).is_ok());
+    assert!(generate_discriminant(2, &mut StdRng::from_seed([0; 32])).is_err());
+}
+
+#[test]
+fn test_calibration() {
+    let discriminant = generate_discriminant(256, &mut StdRng::from_seed([0; 32])).unwrap();
+    let calibration = VdfCalibration::measure(&discriminant, Duration::from_millis(20)).unwrap();
+    assert!(calibration.squarings_per_second() > 0.0);
+
+    let one_second = calibration.recommended_iterations(Duration::from_secs(1));
+    let two_seconds = calibration.recommended_iterations(Duration::from_secs(2));
+    assert!(one_second >= 1);
+    assert!(two_seconds >= 2 * one_second - 1 && two_seconds <= 2 * one_second + 1);
+    assert_eq!(calibration.recommended_iterations(Duration::ZERO), 1);
+
+    let parameters = calibration
+        .parameters(&discriminant, Duration::from_secs(1))
+        .unwrap();
+    assert_eq!(parameters.iterations(), one_second);
+    assert!(VdfCalibration::measure(&BigInt::from(-1), Duration::ZERO).is_err());
+}
+
+#[test]
+fn test_discriminant_from_seed() {
+    let seed = b"chain randomness";
+    let (discriminant, certificate) = discriminant_from_seed(seed, 256).unwrap();
+    assert_eq!(discriminant.bits(), 256);
+    assert_eq!(&discriminant % 4, BigInt::from(-3));
+    assert!(verify_discriminant(seed, 256, &discriminant, &certificate).is_ok());
+    assert_eq!(
+        discriminant_from_seed(seed, 256).unwrap(),
+        (discriminant.clone(), certificate.clone())
+    );
+
+    let (parameters, _) = VdfParameters::from_seed(seed, 256, 10).unwrap();
+    assert_eq!(parameters.discriminant(), &discriminant);
+
+    // Another seed, size or discriminant is rejected.
+    assert!(verify_discriminant(b"other randomness", 256, &discriminant, &certificate).is_err());
+    assert!(verify_discriminant(seed, 255, &discriminant, &certificate).is_err());
+    assert!(verify_discriminant(seed, 256, &(&discriminant - 4), &certificate).is_err());
+
+    // Skipping to a later candidate is rejected. The counter of the base prime is serialized first.
+    let mut bytes = bincode::serialize(&certificate).unwrap();
+    bytes[0] = bytes[0].wrapping_add(1);
+    let modified: DiscriminantCertificate = bincode::deserialize(&bytes).unwrap();
+    assert!(verify_discriminant(seed, 256, &discriminant, &modified).is_err());
+
+    verify_serialization(&certificate, None);
+
+    // Small discriminants only need a prime proven by trial division.
+    let (small, certificate) = discriminant_from_seed(seed, 20).unwrap();
+    assert_eq!(small.bits(), 20);
+    assert!(verify_discriminant(seed, 20, &small, &certificate).is_ok());
+    assert!(discriminant_from_seed(seed, 2).is_err());
+}
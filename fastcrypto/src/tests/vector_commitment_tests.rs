@@ -0,0 +1,120 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::groups::bls12381::Scalar;
+use crate::groups::Scalar as _;
+use crate::test_helpers::verify_serialization;
+use crate::vector_commitment::VectorCommitmentKey;
+use rand::{rngs::StdRng, SeedableRng as _};
+
+const LENGTH: usize = 8;
+
+fn setup() -> (VectorCommitmentKey, Vec<Scalar>) {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let key = VectorCommitmentKey::generate(LENGTH, &mut rng).unwrap();
+    let values = (0..LENGTH).map(|_| Scalar::rand(&mut rng)).collect();
+    (key, values)
+}
+
+#[test]
+fn test_open_single_position() {
+    let (key, values) = setup();
+    let commitment = key.commit(&values).unwrap();
+    for i in 0..LENGTH {
+        let proof = key.open(&values, &[i]).unwrap();
+        assert!(key.verify(&commitment, &[i], &[values[i]], &proof).is_ok());
+        assert!(key
+            .verify(&commitment, &[i], &[values[(i + 1) % LENGTH]], &proof)
+            .is_err());
+        assert!(key
+            .verify(&commitment, &[(i + 1) % LENGTH], &[values[i]], &proof)
+            .is_err());
+    }
+}
+
+#[test]
+fn test_open_multiple_positions() {
+    let (key, values) = setup();
+    let commitment = key.commit(&values).unwrap();
+
+    let positions = [5, 0, 3];
+    let opened = positions.iter().map(|i| values[*i]).collect::<Vec<_>>();
+    let proof = key.open(&values, &positions).unwrap();
+    assert!(key.verify(&commitment, &positions, &opened, &proof).is_ok());
+    assert!(key
+        .verify(&commitment, &positions[..2], &opened[..2], &proof)
+        .is_err());
+
+    // Opening all positions.
+    let positions = (0..LENGTH).collect::<Vec<_>>();
+    let proof = key.open(&values, &positions).unwrap();
+    assert!(key.verify(&commitment, &positions, &values, &proof).is_ok());
+
+    // A proof for another commitment does not verify.
+    let other = key.commit(&values[1..]).unwrap();
+    let proof = key.open(&values, &[2]).unwrap();
+    assert!(key.verify(&other, &[2], &[values[2]], &proof).is_err());
+}
+
+#[test]
+fn test_short_vectors() {
+    let (key, values) = setup();
+    let commitment = key.commit(&values[..3]).unwrap();
+    let proof = key.open(&values[..3], &[1, 6]).unwrap();
+    assert!(key
+        .verify(&commitment, &[1, 6], &[values[1], Scalar::zero()], &proof)
+        .is_ok());
+    assert_eq!(
+        key.commit(&[]).unwrap(),
+        key.commit(&[Scalar::zero()]).unwrap()
+    );
+}
+
+#[test]
+fn test_update() {
+    let (key, mut values) = setup();
+    let commitment = key.commit(&values).unwrap();
+    let new_value = Scalar::from(42);
+    let updated = key.update(&commitment, 4, &values[4], &new_value).unwrap();
+    values[4] = new_value;
+    assert_eq!(updated, key.commit(&values).unwrap());
+
+    let proof = key.open(&values, &[4]).unwrap();
+    assert!(key.verify(&updated, &[4], &[new_value], &proof).is_ok());
+    assert!(key
+        .update(&commitment, LENGTH, &values[4], &new_value)
+        .is_err());
+}
+
+#[test]
+fn test_invalid_inputs() {
+    let (key, values) = setup();
+    let commitment = key.commit(&values).unwrap();
+    let proof = key.open(&values, &[0]).unwrap();
+
+    assert!(VectorCommitmentKey::generate(0, &mut StdRng::from_seed([0; 32])).is_err());
+    assert!(key
+        .commit(&[values.clone(), values.clone()].concat())
+        .is_err());
+    assert!(key.open(&values, &[]).is_err());
+    assert!(key.open(&values, &[1, 1]).is_err());
+    assert!(key.open(&values, &[LENGTH]).is_err());
+    assert!(key.verify(&commitment, &[0], &[], &proof).is_err());
+    assert!(key
+        .verify(&commitment, &[0, 0], &[values[0], values[0]], &proof)
+        .is_err());
+}
+
+#[test]
+fn test_serialization() {
+    let (key, values) = setup();
+    let commitment = key.commit(&values).unwrap();
+    let proof = key.open(&values, &[1, 2]).unwrap();
+    verify_serialization(&commitment, None);
+    verify_serialization(&proof, None);
+    let bytes = bincode::serialize(&key).unwrap();
+    assert_eq!(
+        bincode::deserialize::<VectorCommitmentKey>(&bytes).unwrap(),
+        key
+    );
+}
@@ -0,0 +1,83 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::groups::ristretto255::{RistrettoPoint, RistrettoScalar};
+use crate::groups::{GroupElement, Scalar};
+use crate::test_helpers::verify_serialization;
+use crate::verifiable_encryption::{PrivateKey, PublicKey, VerifiableEncryption};
+use rand::thread_rng;
+
+const CONTEXT: &[u8] = b"test";
+
+#[test]
+fn test_encrypt_verify_decrypt() {
+    let sk = PrivateKey::generate(&mut thread_rng());
+    let pk = PublicKey::from(&sk);
+
+    let x = RistrettoScalar::rand(&mut thread_rng());
+    let x_g = RistrettoPoint::generator() * x;
+
+    let encryption = VerifiableEncryption::encrypt(&pk, &x, CONTEXT, &mut thread_rng());
+    assert!(encryption.verify(&pk, &x_g, CONTEXT).is_ok());
+    assert_eq!(encryption.decrypt(&sk, &x_g).unwrap(), x);
+
+    // Wrong context.
+    assert!(encryption.verify(&pk, &x_g, b"other context").is_err());
+
+    // Wrong public point.
+    let other_x_g = x_g + RistrettoPoint::generator();
+    assert!(encryption.verify(&pk, &other_x_g, CONTEXT).is_err());
+    assert!(encryption.decrypt(&sk, &other_x_g).is_err());
+
+    // Wrong public key.
+    let other_pk = PublicKey::from(&PrivateKey::generate(&mut thread_rng()));
+    assert!(encryption.verify(&other_pk, &x_g, CONTEXT).is_err());
+}
+
+#[test]
+fn test_small_values() {
+    let sk = PrivateKey::generate(&mut thread_rng());
+    let pk = PublicKey::from(&sk);
+
+    for x in [
+        RistrettoScalar::zero(),
+        RistrettoScalar::generator(),
+        -RistrettoScalar::generator(),
+    ] {
+        let x_g = RistrettoPoint::generator() * x;
+        let encryption = VerifiableEncryption::encrypt(&pk, &x, CONTEXT, &mut thread_rng());
+        assert!(encryption.verify(&pk, &x_g, CONTEXT).is_ok());
+        assert_eq!(encryption.decrypt(&sk, &x_g).unwrap(), x);
+    }
+}
+
+#[test]
+fn test_invalid_encryption() {
+    let sk = PrivateKey::generate(&mut thread_rng());
+    let pk = PublicKey::from(&sk);
+
+    // An encryption of x does not verify against the public point of another scalar.
+    let x = RistrettoScalar::from(1234);
+    let y = RistrettoScalar::from(1235);
+    let encryption = VerifiableEncryption::encrypt(&pk, &x, CONTEXT, &mut thread_rng());
+    assert!(encryption
+        .verify(&pk, &(RistrettoPoint::generator() * y), CONTEXT)
+        .is_err());
+}
+
+#[test]
+fn test_serialization() {
+    let sk = PrivateKey::generate(&mut thread_rng());
+    let pk = PublicKey::from(&sk);
+    let x = RistrettoScalar::rand(&mut thread_rng());
+    let x_g = RistrettoPoint::generator() * x;
+
+    verify_serialization(&sk, None);
+    verify_serialization(&pk, None);
+
+    let encryption = VerifiableEncryption::encrypt(&pk, &x, CONTEXT, &mut thread_rng());
+    let bytes = bincode::serialize(&encryption).unwrap();
+    let deserialized: VerifiableEncryption = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(deserialized, encryption);
+    assert!(deserialized.verify(&pk, &x_g, CONTEXT).is_ok());
+}
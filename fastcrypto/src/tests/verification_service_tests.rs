@@ -0,0 +1,124 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::bls12381::min_sig::{BLS12381AggregateSignature, BLS12381KeyPair};
+use crate::error::FastCryptoError;
+use crate::traits::{AggregateAuthenticator, KeyPair, Signer};
+use crate::verification_service::VerificationService;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[tokio::test]
+async fn test_verify_signatures() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let key_pairs = (0..4)
+        .map(|_| BLS12381KeyPair::generate(&mut rng))
+        .collect::<Vec<_>>();
+    let public_keys = key_pairs
+        .iter()
+        .map(|kp| kp.public().clone())
+        .collect::<Vec<_>>();
+    let signatures = key_pairs
+        .iter()
+        .map(|kp| kp.sign(b"message"))
+        .collect::<Vec<_>>();
+    let service = VerificationService::new(2);
+
+    assert!(service
+        .verify_signature(
+            public_keys[0].clone(),
+            b"message".to_vec(),
+            signatures[0].clone()
+        )
+        .await
+        .is_ok());
+    assert!(service
+        .verify_signature(
+            public_keys[1].clone(),
+            b"message".to_vec(),
+            signatures[0].clone()
+        )
+        .await
+        .is_err());
+
+    let aggregate = BLS12381AggregateSignature::aggregate(&signatures).unwrap();
+    assert!(service
+        .verify_aggregate(aggregate.clone(), public_keys.clone(), b"message".to_vec())
+        .await
+        .is_ok());
+    assert!(service
+        .verify_aggregate(
+            aggregate.clone(),
+            public_keys[..3].to_vec(),
+            b"message".to_vec()
+        )
+        .await
+        .is_err());
+
+    let other = BLS12381AggregateSignature::aggregate(
+        &key_pairs[..2]
+            .iter()
+            .map(|kp| kp.sign(b"other message"))
+            .collect::<Vec<_>>(),
+    )
+    .unwrap();
+    assert!(service
+        .batch_verify(
+            vec![aggregate.clone(), other.clone()],
+            vec![public_keys.clone(), public_keys[..2].to_vec()],
+            vec![b"message".to_vec(), b"other message".to_vec()],
+        )
+        .await
+        .is_ok());
+    assert!(service
+        .batch_verify(
+            vec![aggregate, other],
+            vec![public_keys.clone(), public_keys[..2].to_vec()],
+            vec![b"message".to_vec(), b"message".to_vec()],
+        )
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_verify() {
+    let service = VerificationService::new(0);
+    assert_eq!(service.max_concurrent_verifications(), 1);
+    assert_eq!(service.verify(|| Ok(7)).await, Ok(7));
+    assert_eq!(
+        service
+            .verify::<(), _>(|| Err(FastCryptoError::InvalidSignature))
+            .await,
+        Err(FastCryptoError::InvalidSignature)
+    );
+
+    // A verification which panics.
+    assert!(matches!(
+        service.verify::<(), _>(|| panic!("Invalid input")).await,
+        Err(FastCryptoError::GeneralError(_))
+    ));
+    assert_eq!(service.available_permits(), 1);
+}
+
+#[tokio::test]
+async fn test_concurrency() {
+    let service = VerificationService::new(1);
+    let (sender, receiver) = std::sync::mpsc::channel::<()>();
+    let running = service.clone();
+    let handle = tokio::spawn(async move {
+        running
+            .verify(move || {
+                receiver.recv().unwrap();
+                Ok(1)
+            })
+            .await
+    });
+
+    // The running verification holds the only permit until it finishes.
+    while service.available_permits() > 0 {
+        tokio::task::yield_now().await;
+    }
+    sender.send(()).unwrap();
+    assert_eq!(handle.await.unwrap(), Ok(1));
+    assert_eq!(service.available_permits(), 1);
+}
@@ -0,0 +1,120 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::ed25519::{Ed25519PublicKey, Ed25519Signature};
+use crate::hash::{HashFunction, Sha512};
+use crate::secp256k1::Secp256k1KeyPair;
+use crate::secp256r1::Secp256r1KeyPair;
+use crate::traits::{KeyPair, Signer, ToFromBytes};
+use crate::verify_stream::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+fn verify_chunked<K: VerifyStream>(
+    key: &K,
+    message: &[u8],
+    chunk_size: usize,
+    signature: &K::Sig,
+) -> bool {
+    let mut stream = key.verify_stream();
+    message
+        .chunks(chunk_size)
+        .for_each(|chunk| stream.update(chunk));
+    stream.finalize(signature).is_ok()
+}
+
+fn check_stream<K: VerifyStream>(key: &K, message: &[u8], signature: &K::Sig) {
+    for chunk_size in [1, 7, 64, message.len()] {
+        assert!(verify_chunked(key, message, chunk_size, signature));
+    }
+    assert!(key
+        .verify_stream()
+        .verify_reader(message, signature)
+        .is_ok());
+
+    // Missing, extra or modified chunks.
+    assert!(!verify_chunked(key, &message[1..], 7, signature));
+    assert!(!verify_chunked(
+        key,
+        &[message, &[0]].concat(),
+        7,
+        signature
+    ));
+    let mut other = message.to_vec();
+    other[message.len() / 2] ^= 1;
+    assert!(!verify_chunked(key, &other, 7, signature));
+    assert!(key.verify_stream().finalize(signature).is_err());
+}
+
+fn message() -> Vec<u8> {
+    (0..100_000u32).map(|i| (i % 251) as u8).collect()
+}
+
+#[test]
+fn test_secp256k1_stream() {
+    let kp = Secp256k1KeyPair::generate(&mut StdRng::from_seed([0; 32]));
+    let message = message();
+    check_stream(kp.public(), &message, &kp.sign(&message));
+}
+
+#[test]
+fn test_secp256r1_stream() {
+    let kp = Secp256r1KeyPair::generate(&mut StdRng::from_seed([0; 32]));
+    let message = message();
+    check_stream(kp.public(), &message, &kp.sign(&message));
+}
+
+#[cfg(feature = "rsa")]
+#[test]
+fn test_rsa_stream() {
+    use crate::rsa::{RSAPublicKey, RSASignature};
+    use base64ct::{Base64UrlUnpadded, Encoding};
+
+    // Test vector from RFC 7515 section A.2.1.
+    let n = Base64UrlUnpadded::decode_vec("ofgWCuLjybRlzo0tZWJjNiuSfb4p4fAkd_wWJcyQoTbji9k0l8W26mPddxHmfHQp-Vaw-4qPCJrcS2mJPMEzP1Pt0Bm4d4QlL-yRT-SFd2lZS-pCgNMsD1W_YpRPEwOWvG6b32690r2jZ47soMZo9wGzjb_7OMg0LOL-bSf63kpaSHSXndS5z5rexMdbBYUsLA9e-KXBdQOS-UTo7WTBEMa2R2CapHg665xsmtdVMTBQY4uDZlxvb3qCo5ZwKh9kG4LT6_I5IhlJH7aGhyxXFvUK-DWNmoudF8NAco9_h9iaGNj8q2ethFkMLs91kzk2PAcDTW9gb54h4FRWyuXpoQ").unwrap();
+    let pk = RSAPublicKey::from_raw_components(&n, &[1, 0, 1]).unwrap();
+    let message = b"eyJhbGciOiJSUzI1NiJ9.eyJpc3MiOiJqb2UiLA0KICJleHAiOjEzMDA4MTkzODAsDQogImh0dHA6Ly9leGFtcGxlLmNvbS9pc19yb290Ijp0cnVlfQ";
+    let signature = Base64UrlUnpadded::decode_vec("cC4hiUPoj9Eetdgtv3hF80EGrhuB__dzERat0XF9g2VtQgr9PJbu3XOiZj5RZmh7AAuHIm4Bh-0Qc_lF5YKt_O8W2Fp5jujGbds9uJdbF9CUAr7t1dnZcAcQjbKBYNX4BAynRFdiuB--f_nZLgrnbyTyWzO75vRK5h6xBArLIARNPvkSjtQBMHlb1L07Qe7K0GarZRmB_eSN9383LcOLn6_dO--xi12jzDwusC-eOkHWEsqtFZESc6BfI7noOPqvhJ1phCnvWh6IeYI2w9QOYEUipUTI8np6LbgGY9Fs98rqVt5AXLIhWkWywlVmtVrBp0igcN_IoypGlUPQGe77Rw").unwrap();
+    let signature = RSASignature::from_bytes(&signature).unwrap();
+    check_stream(&pk, message, &signature);
+}
+
+#[test]
+fn test_ed25519ph_stream() {
+    // Test vector "abc" for Ed25519ph from RFC 8032 section 7.3.
+    let pk = Ed25519PublicKey::from_bytes(
+        &hex::decode("ec172b93ad5e563bf4932c70e1245034c35467ef2efd4d64ebf819683467e2bf").unwrap(),
+    )
+    .unwrap();
+    let signature = Ed25519Signature::from_bytes(
+        &hex::decode(
+            "98a70222f0b8121aa9d30f813d683f809e462b469c7ff87639499bb94e6dae41\
+             31f85042463c2a355a2003d062adf5aaa10b8c61e636062aaad11c2a26083406",
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    check_stream(&pk, b"abc", &signature);
+
+    let prehash = Sha512::digest(b"abc").digest;
+    assert!(pk.verify_prehashed(&prehash, &[], &signature).is_ok());
+
+    // The signature is bound to the context and is not a plain Ed25519 signature.
+    assert!(pk
+        .verify_prehashed(&prehash, b"context", &signature)
+        .is_err());
+    let stream = Ed25519phStream::with_context(&pk, b"context").unwrap();
+    assert!(stream.verify_reader(b"abc".as_slice(), &signature).is_err());
+    assert!(crate::traits::VerifyingKey::verify(&pk, b"abc", &signature).is_err());
+
+    // Too long contexts.
+    assert!(Ed25519phStream::with_context(&pk, &[0; 256]).is_err());
+    assert!(pk
+        .verify_prehashed(&prehash, &[0; 256], &signature)
+        .is_err());
+
+    // A non-canonical s.
+    let mut bytes = signature.as_ref().to_vec();
+    bytes[63] |= 0xf0;
+    let modified = Ed25519Signature::from_bytes(&bytes).unwrap();
+    assert!(pk.verify_prehashed(&prehash, &[], &modified).is_err());
+}
@@ -0,0 +1,184 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Time-lock puzzles by [Rivest, Shamir and Wagner](https://people.csail.mit.edu/rivest/pubs/RSW96.pdf),
+//! which encrypt a message such that it can only be decrypted after `T` sequential squarings
+//! modulo an RSA modulus `N`.
+//!
+//! The creator of a puzzle knows the factorization of `N` and can compute the key
+//! `x^(2^T) mod N` for a random `x` using only two modular exponentiations, by first reducing
+//! `2^T` modulo `φ(N)`. Anyone else has to compute the key by squaring `x` `T` times. The message
+//! is encrypted with AES-256-GCM using a key and nonce derived from `x^(2^T) mod N` with HKDF,
+//! so a wrongly solved puzzle is detected on decryption.
+//!
+//! Unlike a [VDF](crate::vdf), the solver does not get a proof that the solution is correct, but
+//! the decryption authenticates the solution. This is sufficient for commit-reveal schemes where
+//! each party publishes a puzzle with its contribution, so the contributions can be recovered even
+//! if a party refuses to reveal. Puzzles are only defined for RSA groups, since class groups have
+//! no trapdoor allowing the creator to compute the solution fast.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::time_lock::*;
+//! # use rand::thread_rng;
+//! let trapdoor = TimeLockTrapdoor::generate(1024, &mut thread_rng()).unwrap();
+//! let puzzle = trapdoor.create_puzzle(b"my bid", 1000, &mut thread_rng());
+//! assert_eq!(puzzle.solve().unwrap(), b"my bid");
+//! assert_eq!(trapdoor.open(&puzzle).unwrap(), b"my bid");
+//! ```
+
+use crate::aes::{Aes256Gcm, AesKey, AuthenticatedCipher, InitializationVector};
+use crate::error::FastCryptoError;
+use crate::hmac::{hkdf_sha3_256, HkdfIkm};
+use crate::number_theory::{generate_prime, random_unit};
+use crate::traits::{AllowedRng, ToFromBytes};
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+use typenum::U12;
+
+/// The smallest supported bit length of the modulus.
+pub const MIN_MODULUS_BITS: u64 = 1024;
+
+const KEY_DERIVATION_DST: &[u8] = b"fastcrypto-time-lock-v1";
+
+const AES_KEY_LENGTH: usize = 32;
+const AES_NONCE_LENGTH: usize = 12;
+
+/// The factorization of an RSA modulus, which allows its holder to create puzzles and to open
+/// them without solving them.
+pub struct TimeLockTrapdoor {
+    p: BigUint,
+    q: BigUint,
+}
+
+/// A message encrypted such that it can be decrypted after `iterations` sequential squarings of
+/// `base` modulo `modulus`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeLockPuzzle {
+    modulus: BigUint,
+    base: BigUint,
+    iterations: u64,
+    ciphertext: Vec<u8>,
+}
+
+impl TimeLockTrapdoor {
+    /// Generate a new modulus of `bits` bits.
+    pub fn generate<R: AllowedRng>(bits: u64, rng: &mut R) -> Result<Self, FastCryptoError> {
+        if bits < MIN_MODULUS_BITS || bits % 2 != 0 {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let p = generate_prime(bits / 2, rng);
+        let q = loop {
+            let q = generate_prime(bits / 2, rng);
+            if q != p {
+                break q;
+            }
+        };
+        Ok(Self { p, q })
+    }
+
+    /// The modulus of the puzzles created with this trapdoor.
+    pub fn modulus(&self) -> BigUint {
+        &self.p * &self.q
+    }
+
+    /// Encrypt a message such that it can be decrypted after `iterations` sequential squarings.
+    /// The same trapdoor may be used for many puzzles since each puzzle uses a fresh base.
+    pub fn create_puzzle<R: AllowedRng>(
+        &self,
+        message: &[u8],
+        iterations: u64,
+        rng: &mut R,
+    ) -> TimeLockPuzzle {
+        let modulus = self.modulus();
+        let base = random_unit(&modulus, rng);
+        let solution = self.fast_solution(&modulus, &base, iterations);
+        TimeLockPuzzle {
+            ciphertext: encrypt(&modulus, &solution, message),
+            modulus,
+            base,
+            iterations,
+        }
+    }
+
+    /// Decrypt a puzzle created with this trapdoor without solving it.
+    pub fn open(&self, puzzle: &TimeLockPuzzle) -> Result<Vec<u8>, FastCryptoError> {
+        let modulus = self.modulus();
+        if puzzle.modulus != modulus {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let solution = self.fast_solution(&modulus, &puzzle.base, puzzle.iterations);
+        decrypt(&modulus, &solution, &puzzle.ciphertext)
+    }
+
+    /// Compute `base^(2^iterations) mod N` as `base^(2^iterations mod φ(N)) mod N`.
+    fn fast_solution(&self, modulus: &BigUint, base: &BigUint, iterations: u64) -> BigUint {
+        let one = BigUint::from(1u8);
+        let phi = (&self.p - &one) * (&self.q - &one);
+        let exponent = BigUint::from(2u8).modpow(&BigUint::from(iterations), &phi);
+        base.modpow(&exponent, modulus)
+    }
+}
+
+impl TimeLockPuzzle {
+    /// The number of sequential squarings needed to solve this puzzle.
+    pub fn iterations(&self) -> u64 {
+        self.iterations
+    }
+
+    /// The modulus of this puzzle.
+    pub fn modulus(&self) -> &BigUint {
+        &self.modulus
+    }
+
+    /// Solve the puzzle by sequential squaring and decrypt the message. This takes time linear in
+    /// the number of iterations, so the caller should check [TimeLockPuzzle::iterations] before
+    /// solving a puzzle from an untrusted source.
+    pub fn solve(&self) -> Result<Vec<u8>, FastCryptoError> {
+        if self.modulus.bits() < MIN_MODULUS_BITS || self.base >= self.modulus {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let mut solution = self.base.clone();
+        for _ in 0..self.iterations {
+            solution = &solution * &solution % &self.modulus;
+        }
+        decrypt(&self.modulus, &solution, &self.ciphertext)
+    }
+}
+
+/// Derive the AES key and nonce from the solution, encoded as a big-endian integer of the same
+/// length as the modulus.
+fn derive_cipher(
+    modulus: &BigUint,
+    solution: &BigUint,
+) -> (Aes256Gcm<U12>, InitializationVector<U12>) {
+    let length = ((modulus.bits() + 7) / 8) as usize;
+    let mut ikm = vec![0u8; length];
+    let bytes = solution.to_bytes_be();
+    ikm[length - bytes.len()..].copy_from_slice(&bytes);
+    let output = hkdf_sha3_256(
+        &HkdfIkm::from_bytes(&ikm).expect("Any length is allowed"),
+        KEY_DERIVATION_DST,
+        &[],
+        AES_KEY_LENGTH + AES_NONCE_LENGTH,
+    )
+    .expect("Output length is valid");
+    let key = AesKey::from_bytes(&output[..AES_KEY_LENGTH]).expect("Key length is valid");
+    let nonce =
+        InitializationVector::from_bytes(&output[AES_KEY_LENGTH..]).expect("Nonce length is valid");
+    (Aes256Gcm::new(key), nonce)
+}
+
+fn encrypt(modulus: &BigUint, solution: &BigUint, message: &[u8]) -> Vec<u8> {
+    let (cipher, nonce) = derive_cipher(modulus, solution);
+    cipher.encrypt_authenticated(&nonce, &[], message)
+}
+
+fn decrypt(
+    modulus: &BigUint,
+    solution: &BigUint,
+    ciphertext: &[u8],
+) -> Result<Vec<u8>, FastCryptoError> {
+    let (cipher, nonce) = derive_cipher(modulus, solution);
+    cipher.decrypt_authenticated(&nonce, &[], ciphertext)
+}
@@ -9,13 +9,14 @@ use crate::{
 #[cfg(any(test, feature = "experimental"))]
 use eyre::eyre;
 use rand::rngs::{StdRng, ThreadRng};
-use rand::{CryptoRng, RngCore};
+use rand::{CryptoRng, RngCore, SeedableRng};
 use serde::{de::DeserializeOwned, Serialize};
 use std::{
     borrow::Borrow,
     fmt::{Debug, Display},
     str::FromStr,
 };
+use zeroize::Zeroize;
 
 /// Trait impl'd by concrete types that represent digital cryptographic material
 /// (keys).
@@ -89,6 +90,9 @@ pub trait VerifyingKey:
 
     /// Use Self to verify that the provided signature for a given message bytestring is authentic.
     /// Returns Error if it is inauthentic, or otherwise returns ().
+    ///
+    /// The Ed25519, Secp256k1 and Secp256r1 implementations do not allocate on the heap (see
+    /// `tests/verify_allocations.rs`).
     fn verify(&self, msg: &[u8], signature: &Self::Sig) -> Result<(), FastCryptoError>;
 
     // Expected to be overridden by implementations
@@ -192,6 +196,20 @@ pub trait Signer<Sig> {
     fn sign(&self, msg: &[u8]) -> Sig;
 }
 
+/// Trait impl'd by signers which can sign many messages at once. Schemes where work can be shared
+/// between signatures by the same key should override [BatchSigner::sign_batch].
+///
+/// None of the schemes in this crate override it yet: they all keep everything derived from the
+/// private key in the key pair, so signing a batch is not faster than signing the messages one by
+/// one. The trait lets callers such as `signature_service::SignatureService` hand over batches.
+pub trait BatchSigner<Sig>: Signer<Sig> {
+    /// Create signatures over the given messages, in the same order. The default implementation
+    /// signs the messages one by one.
+    fn sign_batch(&self, msgs: &[&[u8]]) -> Vec<Sig> {
+        msgs.iter().map(|msg| self.sign(msg)).collect()
+    }
+}
+
 /// Trait impl'd by a public / private key pair in asymmetric cryptography.
 ///
 pub trait KeyPair:
@@ -211,6 +229,52 @@ pub trait KeyPair:
 
     /// Generate a new keypair using the given RNG.
     fn generate<R: AllowedRng>(rng: &mut R) -> Self;
+
+    /// Generate `n` keypairs using multiple threads. Each keypair is generated from a seed drawn
+    /// from the given RNG in order, so the output is deterministic if the RNG is seeded. Every
+    /// keypair is validated by signing and verifying a test message.
+    fn generate_batch<R: AllowedRng>(n: usize, rng: &mut R) -> Result<Vec<Self>, FastCryptoError>
+    where
+        Self: Send,
+    {
+        const VALIDATION_MESSAGE: &[u8] = b"fastcrypto keypair validation";
+
+        let mut seeds = (0..n)
+            .map(|_| {
+                let mut seed = <StdRng as SeedableRng>::Seed::default();
+                rng.fill_bytes(&mut seed);
+                seed
+            })
+            .collect::<Vec<_>>();
+        let threads = std::thread::available_parallelism().map_or(1, |t| t.get());
+        let chunk_size = ((n + threads - 1) / threads).max(1);
+
+        let result = std::thread::scope(|scope| {
+            let handles = seeds
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|seed| {
+                                let keypair = Self::generate(&mut StdRng::from_seed(*seed));
+                                keypair
+                                    .public()
+                                    .verify(VALIDATION_MESSAGE, &keypair.sign(VALIDATION_MESSAGE))
+                                    .map(|_| keypair)
+                            })
+                            .collect::<Result<Vec<_>, _>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("Key generation should not panic"))
+                .collect::<Result<Vec<_>, _>>()
+        });
+        seeds.zeroize();
+        Ok(result?.into_iter().flatten().collect())
+    }
 }
 
 /// Trait impl'd by public / private keypairs that can generate recoverable signatures
@@ -365,14 +429,21 @@ pub trait AggregateAuthenticator:
     /// let messages = [message1, message2];
     /// let pks1 = [kp1.public().clone()];
     /// let pks2 = [kp2.public().clone()];
-    /// let public_keys = vec!(pks1.iter(), pks2.iter());
+    /// let public_keys = [pks1.iter(), pks2.iter()];
     /// assert!(Ed25519AggregateSignature::batch_verify(&aggregated_signatures, public_keys, &messages).is_ok());
     /// ```
-    fn batch_verify<'a>(
+    ///
+    /// The public keys may be given by any iterator over iterators, so callers do not have to
+    /// collect them into vectors.
+    fn batch_verify<'a, P, I>(
         sigs: &[&Self],
-        pks: Vec<impl ExactSizeIterator<Item = &'a Self::PubKey>>,
+        pks: P,
         messages: &[&[u8]],
-    ) -> Result<(), FastCryptoError>;
+    ) -> Result<(), FastCryptoError>
+    where
+        P: IntoIterator<Item = I>,
+        P::IntoIter: ExactSizeIterator,
+        I: ExactSizeIterator<Item = &'a Self::PubKey>;
 }
 
 /// Trait impl'd by cryptographic material that can be generated randomly such as keys and nonces.
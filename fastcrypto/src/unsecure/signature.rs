@@ -369,15 +369,21 @@ impl AggregateAuthenticator for UnsecureAggregateSignature {
         Err(FastCryptoError::GeneralOpaqueError)
     }
 
-    fn batch_verify<'a>(
+    fn batch_verify<'a, P, I>(
         sigs: &[&Self],
-        pks: Vec<impl ExactSizeIterator<Item = &'a Self::PubKey>>,
+        pks: P,
         messages: &[&[u8]],
-    ) -> Result<(), FastCryptoError> {
+    ) -> Result<(), FastCryptoError>
+    where
+        P: IntoIterator<Item = I>,
+        P::IntoIter: ExactSizeIterator,
+        I: ExactSizeIterator<Item = &'a Self::PubKey>,
+    {
+        let pks = pks.into_iter();
         if sigs.len() != pks.len() {
             return Err(FastCryptoError::InvalidInput);
         }
-        let mut pks_iter = pks.into_iter();
+        let mut pks_iter = pks;
 
         for (msg, sig) in messages.iter().zip(sigs.iter()) {
             let public_keys: Vec<UnsecurePublicKey> = pks_iter
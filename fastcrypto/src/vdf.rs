@@ -0,0 +1,471 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A verifiable delay function (VDF) based on repeated squaring in class groups of imaginary
+//! quadratic orders with proofs of correct evaluation by
+//! [Wesolowski](https://eprint.iacr.org/2018/623.pdf).
+//!
+//! The output for an input `x` is `y = x^(2^T)` where `T` is the number of iterations, which can
+//! only be computed by `T` sequential squarings since the order of the class group is unknown.
+//! The proof is `π = x^floor(2^T / l)` for a 128-bit prime `l` derived from the input and output,
+//! and is verified by checking that `π^l * x^(2^T mod l) = y`.
+//!
+//! The delay depends on how fast the evaluator can square, so the number of iterations for a
+//! target delay should be chosen after measuring the squaring speed with [VdfCalibration]. Note
+//! that an adversary with faster hardware will be able to evaluate the VDF faster, and that
+//! computing the proof takes about as long as computing the output.
+//!
+//! The discriminant can be derived from a public seed, e.g. randomness from a blockchain, using
+//! [discriminant_from_seed]. This also returns a [DiscriminantCertificate] which anyone can check
+//! with [verify_discriminant] to be convinced that the discriminant was derived correctly from the
+//! seed without trusting whoever derived it.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::vdf::*;
+//! # use rand::thread_rng;
+//! # use std::time::Duration;
+//! let discriminant = generate_discriminant(256, &mut thread_rng()).unwrap();
+//! let calibration = VdfCalibration::measure(&discriminant, Duration::from_millis(10)).unwrap();
+//! let parameters = calibration.parameters(&discriminant, Duration::from_millis(10)).unwrap();
+//!
+//! let input = parameters.default_input();
+//! let (output, proof) = parameters.evaluate(&input).unwrap();
+//! assert!(parameters.verify(&input, &output, &proof).is_ok());
+//! ```
+
+use crate::class_group::QuadraticForm;
+use crate::error::FastCryptoError;
+use crate::hash::{HashFunction, Sha256, Sha512};
+use crate::number_theory::{
+    generate_prime, is_prime_by_trial_division, is_probable_prime, pocklington_criterion,
+};
+use crate::traits::AllowedRng;
+use num_bigint::{BigInt, BigUint};
+use rand::{rngs::StdRng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// The size of the prime challenge in bits.
+const CHALLENGE_BITS: usize = 128;
+const CHALLENGE_DST: &[u8] = b"fastcrypto-vdf-challenge";
+const DISCRIMINANT_DST: &[u8] = b"fastcrypto-vdf-discriminant";
+
+/// Primes of at most this many bits are proven prime by trial division.
+const BASE_PRIME_BITS: u64 = 32;
+
+/// The maximal number of candidates tried for each prime when deriving a discriminant from a seed.
+/// The expected number of candidates is about `ln(2^bits)`, so this is only reached with negligible
+/// probability, but it bounds the work of the verifier.
+const MAX_CANDIDATES: u64 = 1 << 16;
+
+/// The parameters of a VDF: the discriminant of the class group and the number of iterations.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VdfParameters {
+    discriminant: BigInt,
+    iterations: u64,
+}
+
+/// A proof that an output of a VDF is correct.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VdfProof(QuadraticForm);
+
+/// A certificate that a discriminant `-p` was derived correctly from a seed.
+///
+/// The prime `p` is the last of a chain of primes `p_0, ..., p_k` where `p_0` is small enough to be
+/// proven prime by trial division, and each `p_{i+1} = 2 * h_i * p_i + 1` is proven prime by
+/// Pocklington's criterion with a witness `a_i`. Each `p_i` is the first acceptable candidate in a
+/// sequence derived from the seed, and the certificate gives the position of `p_i` in the sequence
+/// and the witness. The verifier checks that all earlier candidates are rejected, so the
+/// discriminant is uniquely determined by the seed and cannot be ground by the prover.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiscriminantCertificate {
+    base_counter: u64,
+    steps: Vec<PocklingtonStep>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct PocklingtonStep {
+    counter: u64,
+    witness: u32,
+}
+
+/// The measured speed of sequential squarings in a class group on this host.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct VdfCalibration {
+    squarings_per_second: f64,
+}
+
+impl VdfParameters {
+    /// Create parameters from a discriminant, which must be negative and congruent to 1 modulo 4,
+    /// and a positive number of iterations.
+    pub fn new(discriminant: BigInt, iterations: u64) -> Result<Self, FastCryptoError> {
+        QuadraticForm::identity(&discriminant)?;
+        if iterations == 0 {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(Self {
+            discriminant,
+            iterations,
+        })
+    }
+
+    /// Generate parameters with a random discriminant of `discriminant_bits` bits. See
+    /// [generate_discriminant].
+    pub fn generate<R: AllowedRng>(
+        discriminant_bits: u64,
+        iterations: u64,
+        rng: &mut R,
+    ) -> Result<Self, FastCryptoError> {
+        Self::new(generate_discriminant(discriminant_bits, rng)?, iterations)
+    }
+
+    /// Derive parameters with a discriminant of `discriminant_bits` bits from a seed. See
+    /// [discriminant_from_seed].
+    pub fn from_seed(
+        seed: &[u8],
+        discriminant_bits: u64,
+        iterations: u64,
+    ) -> Result<(Self, DiscriminantCertificate), FastCryptoError> {
+        let (discriminant, certificate) = discriminant_from_seed(seed, discriminant_bits)?;
+        Ok((Self::new(discriminant, iterations)?, certificate))
+    }
+
+    /// The discriminant of the class group.
+    pub fn discriminant(&self) -> &BigInt {
+        &self.discriminant
+    }
+
+    /// The number of sequential squarings.
+    pub fn iterations(&self) -> u64 {
+        self.iterations
+    }
+
+    /// A fixed non-trivial element of the class group which may be used as input.
+    pub fn default_input(&self) -> QuadraticForm {
+        small_form(&self.discriminant)
+    }
+
+    /// Evaluate the VDF on an input, returning the output and a proof of correctness.
+    pub fn evaluate(
+        &self,
+        input: &QuadraticForm,
+    ) -> Result<(QuadraticForm, VdfProof), FastCryptoError> {
+        self.check_form(input)?;
+        let mut output = input.clone();
+        for _ in 0..self.iterations {
+            output = output.compose(&output);
+        }
+
+        // Compute x^floor(2^T / l) by long division of 2^T by l, one quotient bit at a time.
+        let l = self.challenge(input, &output);
+        let mut proof = QuadraticForm::identity(&self.discriminant)?;
+        let mut remainder = BigUint::from(1u8);
+        for _ in 0..self.iterations {
+            proof = proof.compose(&proof);
+            remainder <<= 1;
+            if remainder >= l {
+                remainder -= &l;
+                proof = proof.compose(input);
+            }
+        }
+        Ok((output, VdfProof(proof)))
+    }
+
+    /// Verify that `output` is the output of this VDF on `input`.
+    pub fn verify(
+        &self,
+        input: &QuadraticForm,
+        output: &QuadraticForm,
+        proof: &VdfProof,
+    ) -> Result<(), FastCryptoError> {
+        self.check_form(input)?;
+        self.check_form(output)?;
+        self.check_form(&proof.0)?;
+
+        let l = self.challenge(input, output);
+        let r = BigUint::from(2u8).modpow(&BigUint::from(self.iterations), &l);
+        if &proof.0.pow(&l).compose(&input.pow(&r)) != output {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        Ok(())
+    }
+
+    fn check_form(&self, form: &QuadraticForm) -> Result<(), FastCryptoError> {
+        if form.discriminant() != self.discriminant {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(())
+    }
+
+    /// Derive the prime challenge `l` from the parameters, the input and the output.
+    fn challenge(&self, input: &QuadraticForm, output: &QuadraticForm) -> BigUint {
+        let mut prefix = CHALLENGE_DST.to_vec();
+        append_integer(&mut prefix, &self.discriminant);
+        prefix.extend_from_slice(&self.iterations.to_le_bytes());
+        for form in [input, output] {
+            let (a, b, c) = form.coefficients();
+            for x in [a, b, c] {
+                append_integer(&mut prefix, x);
+            }
+        }
+
+        (0u64..)
+            .find_map(|counter| {
+                let parts: [&[u8]; 2] = [&prefix, &counter.to_le_bytes()];
+                let digest = Sha256::digest_iterator(parts.into_iter()).digest;
+                let mut candidate = BigUint::from_bytes_be(&digest[..CHALLENGE_BITS / 8]);
+                candidate.set_bit(CHALLENGE_BITS as u64 - 1, true);
+                candidate.set_bit(0, true);
+                // The primality test is randomized, so seed it from the digest to make the
+                // challenge deterministic.
+                is_probable_prime(&candidate, &mut StdRng::from_seed(digest)).then_some(candidate)
+            })
+            .expect("There are infinitely many primes")
+    }
+}
+
+impl VdfCalibration {
+    /// Measure the number of sequential squarings per second in the class group of the given
+    /// discriminant by squaring repeatedly for the given duration.
+    pub fn measure(discriminant: &BigInt, duration: Duration) -> Result<Self, FastCryptoError> {
+        QuadraticForm::identity(discriminant)?;
+        let mut form = small_form(discriminant);
+        let mut squarings = 0u64;
+        let start = Instant::now();
+        while squarings == 0 || start.elapsed() < duration {
+            form = form.compose(&form);
+            squarings += 1;
+        }
+        Ok(Self {
+            squarings_per_second: squarings as f64 / start.elapsed().as_secs_f64(),
+        })
+    }
+
+    /// The measured number of sequential squarings per second.
+    pub fn squarings_per_second(&self) -> f64 {
+        self.squarings_per_second
+    }
+
+    /// The number of iterations it takes this host to evaluate the VDF for the given delay.
+    pub fn recommended_iterations(&self, target_delay: Duration) -> u64 {
+        ((target_delay.as_secs_f64() * self.squarings_per_second).ceil() as u64).max(1)
+    }
+
+    /// Parameters with the given discriminant and the recommended number of iterations for the
+    /// given delay. The discriminant should be the one used for the measurement.
+    pub fn parameters(
+        &self,
+        discriminant: &BigInt,
+        target_delay: Duration,
+    ) -> Result<VdfParameters, FastCryptoError> {
+        VdfParameters::new(
+            discriminant.clone(),
+            self.recommended_iterations(target_delay),
+        )
+    }
+}
+
+/// Generate a random fundamental discriminant `-p` of `bits` bits where `p = 3 mod 4` is prime. For
+/// 128-bit security the discriminant should have at least 1024 bits (Hamdy and Möller estimate
+/// that a 1600-bit discriminant is as hard as a 3072-bit RSA modulus).
+pub fn generate_discriminant<R: AllowedRng>(
+    bits: u64,
+    rng: &mut R,
+) -> Result<BigInt, FastCryptoError> {
+    if bits < 3 {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    let p = loop {
+        let candidate = generate_prime(bits, rng);
+        if candidate.bit(1) {
+            break candidate;
+        }
+    };
+    Ok(-BigInt::from(p))
+}
+
+/// Derive a fundamental discriminant `-p` of `bits` bits from a seed, where `p = 3 mod 4` is prime,
+/// together with a certificate that it was derived correctly. The same seed always gives the same
+/// discriminant.
+pub fn discriminant_from_seed(
+    seed: &[u8],
+    bits: u64,
+) -> Result<(BigInt, DiscriminantCertificate), FastCryptoError> {
+    let sizes = prime_sizes(bits)?;
+    let top = sizes.len() - 1;
+
+    let (base_counter, mut prime) = (0..MAX_CANDIDATES)
+        .map(|counter| (counter, base_candidate(seed, sizes[0], counter)))
+        .find(|(_, candidate)| is_acceptable_base(candidate, top == 0))
+        .ok_or(FastCryptoError::GeneralOpaqueError)?;
+
+    let mut steps = Vec::with_capacity(top);
+    for (level, &size) in sizes.iter().enumerate().skip(1) {
+        let (counter, (h, candidate, _)) = (0..MAX_CANDIDATES)
+            .map(|counter| (counter, step_candidate(seed, size, level, counter, &prime)))
+            .find(|(_, (_, candidate, rng_seed))| {
+                is_acceptable_step(candidate, rng_seed, level == top)
+            })
+            .ok_or(FastCryptoError::GeneralOpaqueError)?;
+        // Any base is a witness with probability 1 - 1/p_i if the candidate is prime.
+        let witness = (2u32..1000)
+            .find(|a| pocklington_criterion(&candidate, &prime, &h, *a))
+            .ok_or(FastCryptoError::GeneralOpaqueError)?;
+        steps.push(PocklingtonStep { counter, witness });
+        prime = candidate;
+    }
+
+    Ok((
+        -BigInt::from(prime),
+        DiscriminantCertificate {
+            base_counter,
+            steps,
+        },
+    ))
+}
+
+/// Verify that a discriminant of `bits` bits was derived from the seed by [discriminant_from_seed].
+/// This proves that `-discriminant` is prime without relying on probabilistic primality tests.
+pub fn verify_discriminant(
+    seed: &[u8],
+    bits: u64,
+    discriminant: &BigInt,
+    certificate: &DiscriminantCertificate,
+) -> Result<(), FastCryptoError> {
+    let sizes = prime_sizes(bits)?;
+    let top = sizes.len() - 1;
+    if certificate.steps.len() != top
+        || certificate.base_counter >= MAX_CANDIDATES
+        || certificate
+            .steps
+            .iter()
+            .any(|step| step.counter >= MAX_CANDIDATES)
+    {
+        return Err(FastCryptoError::InvalidProof);
+    }
+
+    // A rejection of a candidate by the Miller-Rabin test is always correct, so checking that all
+    // earlier candidates are rejected does not require trusting a probabilistic test.
+    if (0..certificate.base_counter)
+        .any(|counter| is_acceptable_base(&base_candidate(seed, sizes[0], counter), top == 0))
+    {
+        return Err(FastCryptoError::InvalidProof);
+    }
+    let mut prime = base_candidate(seed, sizes[0], certificate.base_counter);
+    if !is_acceptable_base(&prime, top == 0) {
+        return Err(FastCryptoError::InvalidProof);
+    }
+
+    for ((level, &size), step) in sizes.iter().enumerate().skip(1).zip(&certificate.steps) {
+        if (0..step.counter).any(|counter| {
+            let (_, candidate, rng_seed) = step_candidate(seed, size, level, counter, &prime);
+            is_acceptable_step(&candidate, &rng_seed, level == top)
+        }) {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        let (h, candidate, _) = step_candidate(seed, size, level, step.counter, &prime);
+        if (level == top && !is_three_mod_four(&candidate))
+            || !pocklington_criterion(&candidate, &prime, &h, step.witness)
+        {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        prime = candidate;
+    }
+
+    if discriminant != &-BigInt::from(prime) {
+        return Err(FastCryptoError::InvalidProof);
+    }
+    Ok(())
+}
+
+/// The sizes in bits of the chain of primes ending with a prime of `bits` bits. Each prime must be
+/// more than half the size of the next for Pocklington's criterion to apply.
+fn prime_sizes(bits: u64) -> Result<Vec<u64>, FastCryptoError> {
+    if bits < 3 {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    let mut sizes = vec![bits];
+    let mut size = bits;
+    while size > BASE_PRIME_BITS {
+        size = size / 2 + 2;
+        sizes.push(size);
+    }
+    sizes.reverse();
+    Ok(sizes)
+}
+
+/// Derive 64 bytes of randomness for a candidate from the seed, and a seed for the primality test.
+fn candidate_randomness(seed: &[u8], bits: u64, level: usize, counter: u64) -> (BigUint, [u8; 32]) {
+    let parts: [&[u8]; 6] = [
+        DISCRIMINANT_DST,
+        &(seed.len() as u64).to_le_bytes(),
+        seed,
+        &bits.to_le_bytes(),
+        &(level as u64).to_le_bytes(),
+        &counter.to_le_bytes(),
+    ];
+    let digest = Sha512::digest_iterator(parts.into_iter()).digest;
+    let rng_seed = digest[..32].try_into().expect("Digest is 64 bytes");
+    (BigUint::from_bytes_be(&digest), rng_seed)
+}
+
+/// A random odd candidate of exactly `bits <= BASE_PRIME_BITS` bits.
+fn base_candidate(seed: &[u8], bits: u64, counter: u64) -> BigUint {
+    let (x, _) = candidate_randomness(seed, bits, 0, counter);
+    let lower = BigUint::from(1u8) << (bits - 1);
+    let mut candidate = &lower + x % &lower;
+    candidate.set_bit(0, true);
+    candidate
+}
+
+/// A candidate `2 * h * q + 1` of exactly `bits` bits with a random `h`. Returns `h`, the candidate
+/// and a seed for the primality test.
+fn step_candidate(
+    seed: &[u8],
+    bits: u64,
+    level: usize,
+    counter: u64,
+    q: &BigUint,
+) -> (BigUint, BigUint, [u8; 32]) {
+    let (x, rng_seed) = candidate_randomness(seed, bits, level, counter);
+    let one = BigUint::from(1u8);
+    let two_q: BigUint = q << 1;
+    let lower = ((&one << (bits - 1)) + &two_q - &one) / &two_q;
+    let upper = ((&one << bits) - 2u8) / &two_q;
+    let h = &lower + x % (&upper - &lower + &one);
+    let candidate = &two_q * &h + &one;
+    (h, candidate, rng_seed)
+}
+
+fn is_acceptable_base(candidate: &BigUint, is_top: bool) -> bool {
+    (!is_top || is_three_mod_four(candidate))
+        && u64::try_from(candidate).map_or(false, is_prime_by_trial_division)
+}
+
+fn is_acceptable_step(candidate: &BigUint, rng_seed: &[u8; 32], is_top: bool) -> bool {
+    (!is_top || is_three_mod_four(candidate))
+        && is_probable_prime(candidate, &mut StdRng::from_seed(*rng_seed))
+}
+
+fn is_three_mod_four(n: &BigUint) -> bool {
+    n.bit(0) && n.bit(1)
+}
+
+/// The reduced form `(a, b, *)` with the smallest `a > 1` of the given discriminant.
+fn small_form(discriminant: &BigInt) -> QuadraticForm {
+    (2u64..)
+        .find_map(|a| {
+            (1..=a).step_by(2).find_map(|b| {
+                QuadraticForm::from_a_b(BigInt::from(a), BigInt::from(b), discriminant).ok()
+            })
+        })
+        .expect("Every discriminant has a form with a > 1")
+}
+
+/// Append a length-prefixed encoding of an integer.
+fn append_integer(buffer: &mut Vec<u8>, x: &BigInt) {
+    let bytes = x.to_signed_bytes_be();
+    buffer.extend_from_slice(&(bytes.len() as u64).to_le_bytes());
+    buffer.extend_from_slice(&bytes);
+}
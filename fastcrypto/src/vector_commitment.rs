@@ -0,0 +1,201 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A vector commitment scheme over BLS12-381 based on KZG polynomial commitments.
+//!
+//! A vector `(v_0, ..., v_{n-1})` is committed to as the KZG commitment `C = [p(τ)]_1` of the
+//! unique polynomial `p` of degree less than `n` with `p(i) = v_i`, which is computed directly from
+//! the Lagrange basis `[L_i(τ)]_1` in the public parameters. This gives:
+//! * Constant-size openings of any subset `S` of the positions, `π = [q(τ)]_1` where
+//!   `q = (p - I_S) / Z_S`, `I_S` interpolates the opened values and `Z_S` vanishes on `S`. The
+//!   verifier checks `e(C - [I_S(τ)]_1, [1]_2) = e(π, [Z_S(τ)]_2)`.
+//! * Commitment updates in constant time, `C' = C + (v_i' - v_i) * [L_i(τ)]_1`.
+//!
+//! The public parameters require a trusted setup, since anyone knowing `τ` can open a commitment
+//! to arbitrary values. [VectorCommitmentKey::generate] is only suitable for testing and
+//! experiments.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::vector_commitment::*;
+//! # use fastcrypto::groups::bls12381::Scalar;
+//! # use rand::thread_rng;
+//! let key = VectorCommitmentKey::generate(8, &mut thread_rng()).unwrap();
+//! let values = (0..8u64).map(Scalar::from).collect::<Vec<_>>();
+//! let commitment = key.commit(&values).unwrap();
+//!
+//! let proof = key.open(&values, &[1, 6]).unwrap();
+//! assert!(key.verify(&commitment, &[1, 6], &[values[1], values[6]], &proof).is_ok());
+//!
+//! let updated = key.update(&commitment, 1, &values[1], &Scalar::from(42)).unwrap();
+//! let mut new_values = values.clone();
+//! new_values[1] = Scalar::from(42);
+//! assert_eq!(updated, key.commit(&new_values).unwrap());
+//! ```
+
+use crate::error::FastCryptoError;
+use crate::groups::bls12381::{G1Element, G2Element, Scalar};
+use crate::groups::{FixedBaseMultiplication, GroupElement, Pairing, Scalar as _};
+use crate::polynomial::{
+    divide_by_monic, interpolate, lagrange_coefficients, powers, vanishing_polynomial,
+};
+use crate::traits::AllowedRng;
+use serde::{Deserialize, Serialize};
+
+/// The public parameters for committing to vectors of length at most `n`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VectorCommitmentKey {
+    /// `[τ^j]_1` for `0 <= j < n`.
+    powers_g1: Vec<G1Element>,
+    /// `[L_i(τ)]_1` for `0 <= i < n` where `L_i` is the Lagrange polynomial for the point `i` over
+    /// the domain `{0, ..., n-1}`.
+    lagrange_g1: Vec<G1Element>,
+    /// `[τ^j]_2` for `0 <= j <= n`.
+    powers_g2: Vec<G2Element>,
+}
+
+/// A commitment to a vector.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Commitment(G1Element);
+
+/// A proof that a commitment opens to some values at a set of positions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpeningProof(G1Element);
+
+impl VectorCommitmentKey {
+    /// Generate parameters for vectors of length at most `n` from a random trapdoor which is
+    /// discarded afterwards.
+    pub fn generate<R: AllowedRng>(n: usize, rng: &mut R) -> Result<Self, FastCryptoError> {
+        if n == 0 {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let tau = Scalar::rand(rng);
+        let powers_of_tau = powers(&tau, n + 1);
+        let lagrange_g1 = lagrange_coefficients(&tau, &domain(n))?
+            .iter()
+            .map(G1Element::mul_generator)
+            .collect();
+
+        Ok(Self {
+            powers_g1: powers_of_tau[..n]
+                .iter()
+                .map(G1Element::mul_generator)
+                .collect(),
+            lagrange_g1,
+            powers_g2: powers_of_tau.iter().map(G2Element::mul_generator).collect(),
+        })
+    }
+
+    /// The maximal length of vectors which can be committed to with these parameters.
+    pub fn max_length(&self) -> usize {
+        self.lagrange_g1.len()
+    }
+
+    /// Commit to a vector. Vectors shorter than [Self::max_length] are padded with zeros.
+    pub fn commit(&self, values: &[Scalar]) -> Result<Commitment, FastCryptoError> {
+        if values.len() > self.max_length() {
+            return Err(FastCryptoError::InputTooLong(self.max_length()));
+        }
+        Ok(Commitment(
+            values
+                .iter()
+                .zip(self.lagrange_g1.iter())
+                .fold(G1Element::zero(), |acc, (v, l)| acc + *l * v),
+        ))
+    }
+
+    /// Compute a proof that a commitment to `values` opens to `values[i]` at each of the given
+    /// distinct `positions`.
+    pub fn open(
+        &self,
+        values: &[Scalar],
+        positions: &[usize],
+    ) -> Result<OpeningProof, FastCryptoError> {
+        if values.len() > self.max_length() {
+            return Err(FastCryptoError::InputTooLong(self.max_length()));
+        }
+        let points = self.points(positions)?;
+        let mut padded = values.to_vec();
+        padded.resize(self.max_length(), Scalar::zero());
+        let opened = positions.iter().map(|i| padded[*i]).collect::<Vec<_>>();
+
+        let p = interpolate(&domain(self.max_length()), &padded)?;
+        let i_s = interpolate(&points, &opened)?;
+        let (q, remainder) = divide_by_monic(&subtract(&p, &i_s), &vanishing_polynomial(&points));
+        debug_assert!(remainder.iter().all(|c| c == &Scalar::zero()));
+        Ok(OpeningProof(msm(&self.powers_g1, &q)))
+    }
+
+    /// Verify that a commitment opens to `values[k]` at `positions[k]` for all `k`.
+    pub fn verify(
+        &self,
+        commitment: &Commitment,
+        positions: &[usize],
+        values: &[Scalar],
+        proof: &OpeningProof,
+    ) -> Result<(), FastCryptoError> {
+        if positions.len() != values.len() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let points = self.points(positions)?;
+        let i_s = interpolate(&points, values)?;
+        let z_s = vanishing_polynomial(&points);
+
+        let lhs = (commitment.0 - msm(&self.powers_g1, &i_s)).pairing(&G2Element::generator());
+        let rhs = proof.0.pairing(&msm(&self.powers_g2, &z_s));
+        if lhs != rhs {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        Ok(())
+    }
+
+    /// Update a commitment after the value at `position` was changed from `old` to `new`.
+    pub fn update(
+        &self,
+        commitment: &Commitment,
+        position: usize,
+        old: &Scalar,
+        new: &Scalar,
+    ) -> Result<Commitment, FastCryptoError> {
+        let l = self
+            .lagrange_g1
+            .get(position)
+            .ok_or(FastCryptoError::InvalidInput)?;
+        Ok(Commitment(commitment.0 + *l * (*new - old)))
+    }
+
+    /// The domain points of the given positions, which must be distinct, non-empty and in range.
+    fn points(&self, positions: &[usize]) -> Result<Vec<Scalar>, FastCryptoError> {
+        let mut sorted = positions.to_vec();
+        sorted.sort_unstable();
+        sorted.dedup();
+        if positions.is_empty()
+            || sorted.len() != positions.len()
+            || sorted.last().expect("Not empty") >= &self.max_length()
+        {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(positions.iter().map(|i| Scalar::from(*i as u64)).collect())
+    }
+}
+
+/// The domain `{0, ..., n-1}` as scalars.
+fn domain(n: usize) -> Vec<Scalar> {
+    (0..n as u64).map(Scalar::from).collect()
+}
+
+/// Compute `sum_j coefficients[j] * bases[j]`.
+fn msm<G: GroupElement<ScalarType = Scalar>>(bases: &[G], coefficients: &[Scalar]) -> G {
+    debug_assert!(coefficients.len() <= bases.len());
+    bases
+        .iter()
+        .zip(coefficients.iter())
+        .fold(G::zero(), |acc, (b, c)| acc + *b * c)
+}
+
+fn subtract(a: &[Scalar], b: &[Scalar]) -> Vec<Scalar> {
+    let mut result = a.to_vec();
+    result.resize(a.len().max(b.len()), Scalar::zero());
+    result.iter_mut().zip(b.iter()).for_each(|(r, b)| *r -= *b);
+    result
+}
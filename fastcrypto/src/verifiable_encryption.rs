@@ -0,0 +1,374 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verifiable encryption of discrete logarithms over the Ristretto255 group.
+//!
+//! A secret scalar `x` is encrypted to a public key `P = sk * G` such that anyone holding the
+//! public point `X = x * G` can verify, without learning `x`, that the ciphertext decrypts to
+//! the discrete log of `X`. This is the building block needed for key-escrow and social-recovery
+//! features where a trustee must be convinced that it holds a valid backup of a key.
+//!
+//! The construction follows the "bitwise" variant of Camenisch-Shoup style verifiable encryption:
+//! - `x` is decomposed into bits `b_0, ..., b_255` and each bit is encrypted using ElGamal in the
+//!   exponent, `(R_i, C_i) = (r_i * G, b_i * G + r_i * P)`.
+//! - Each bit encryption carries a (Fiat-Shamir) OR-proof that it encrypts either 0 or 1.
+//! - A DLEQ proof shows that `sum 2^i C_i - X` and `sum 2^i R_i` have the same discrete log with
+//!   respect to `P` and `G`, which binds the encrypted bits to `X`.
+//!
+//! Decryption is cheap since every plaintext is either the identity or the generator.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::verifiable_encryption::*;
+//! # use fastcrypto::groups::{GroupElement, Scalar};
+//! # use fastcrypto::groups::ristretto255::{RistrettoPoint, RistrettoScalar};
+//! use rand::thread_rng;
+//! let sk = PrivateKey::generate(&mut thread_rng());
+//! let pk = PublicKey::from(&sk);
+//!
+//! let x = RistrettoScalar::rand(&mut thread_rng());
+//! let x_g = RistrettoPoint::generator() * x;
+//!
+//! let encryption = VerifiableEncryption::encrypt(&pk, &x, b"context", &mut thread_rng());
+//! assert!(encryption.verify(&pk, &x_g, b"context").is_ok());
+//! assert_eq!(encryption.decrypt(&sk, &x_g).unwrap(), x);
+//! ```
+
+use crate::error::FastCryptoError;
+use crate::groups::ristretto255::{RistrettoPoint, RistrettoScalar};
+use crate::groups::{FixedBaseMultiplication, GroupElement, Scalar};
+use crate::hash::{HashFunction, Sha512};
+use crate::serde_helpers::ToFromByteArray;
+use crate::traits::AllowedRng;
+use serde::{Deserialize, Serialize};
+
+/// Domain separation tag used for all Fiat-Shamir challenges in this module.
+const DST: &[u8] = b"FASTCRYPTO_VERIFIABLE_ENCRYPTION_DLOG_V1";
+
+/// Number of bits encrypted. Ristretto255 scalars are less than 2^253, but we encrypt all the
+/// bits of the canonical 32 byte representation.
+const NUMBER_OF_BITS: usize = 256;
+
+/// A private decryption key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrivateKey(RistrettoScalar);
+
+/// A public encryption key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublicKey(RistrettoPoint);
+
+impl PrivateKey {
+    /// Generate a new random private key.
+    pub fn generate<R: AllowedRng>(rng: &mut R) -> Self {
+        Self(RistrettoScalar::rand(rng))
+    }
+}
+
+impl From<&PrivateKey> for PublicKey {
+    fn from(sk: &PrivateKey) -> Self {
+        Self(RistrettoPoint::mul_generator(&sk.0))
+    }
+}
+
+/// Proof that an ElGamal ciphertext encrypts either 0 or 1 (in the exponent). The challenges of
+/// the two branches must sum to the Fiat-Shamir challenge.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct BitProof {
+    c0: RistrettoScalar,
+    c1: RistrettoScalar,
+    s0: RistrettoScalar,
+    s1: RistrettoScalar,
+}
+
+/// ElGamal encryption of a single bit together with a proof that it is a bit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct BitEncryption {
+    ephemeral_key: RistrettoPoint,
+    ciphertext: RistrettoPoint,
+    proof: BitProof,
+}
+
+/// NIZK proof that `(G, P, R, D)` is a DDH tuple, i.e. that `R = r * G` and `D = r * P` for the
+/// same `r`. The proof is `(r' * G, r' * P, r' + c * r)` where `c` is a Fiat-Shamir challenge.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct DleqProof {
+    commitment_g: RistrettoPoint,
+    commitment_pk: RistrettoPoint,
+    z: RistrettoScalar,
+}
+
+/// A verifiable encryption of the discrete log of a public point.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifiableEncryption {
+    bits: Vec<BitEncryption>,
+    proof: DleqProof,
+}
+
+impl VerifiableEncryption {
+    /// Encrypt `x` to the given public key. The `context` is bound to all proofs and must be given
+    /// again when verifying.
+    pub fn encrypt<R: AllowedRng>(
+        pk: &PublicKey,
+        x: &RistrettoScalar,
+        context: &[u8],
+        rng: &mut R,
+    ) -> Self {
+        let x_g = RistrettoPoint::mul_generator(x);
+        let bytes = x.to_byte_array();
+
+        let mut bits = Vec::with_capacity(NUMBER_OF_BITS);
+        let mut combined_randomness = RistrettoScalar::zero();
+        let mut power_of_two = RistrettoScalar::generator();
+        for i in 0..NUMBER_OF_BITS {
+            // The scalar is encoded in little-endian.
+            let bit = (bytes[i / 8] >> (i % 8)) & 1 == 1;
+            let r = RistrettoScalar::rand(rng);
+            bits.push(BitEncryption::encrypt(pk, bit, &r, context, i, rng));
+            combined_randomness = combined_randomness + power_of_two * r;
+            power_of_two = power_of_two + power_of_two;
+        }
+
+        let (combined_ephemeral_key, combined_ciphertext) = combine(&bits);
+        let proof = DleqProof::create(
+            &combined_randomness,
+            pk,
+            &combined_ephemeral_key,
+            &(combined_ciphertext - x_g),
+            &x_g,
+            context,
+            rng,
+        );
+
+        Self { bits, proof }
+    }
+
+    /// Verify that this is a valid encryption of the discrete log of `x_g` to the public key `pk`.
+    pub fn verify(
+        &self,
+        pk: &PublicKey,
+        x_g: &RistrettoPoint,
+        context: &[u8],
+    ) -> Result<(), FastCryptoError> {
+        if self.bits.len() != NUMBER_OF_BITS {
+            return Err(FastCryptoError::InputLengthWrong(NUMBER_OF_BITS));
+        }
+        self.bits
+            .iter()
+            .enumerate()
+            .try_for_each(|(i, bit)| bit.verify(pk, context, i))?;
+
+        let (combined_ephemeral_key, combined_ciphertext) = combine(&self.bits);
+        self.proof.verify(
+            pk,
+            &combined_ephemeral_key,
+            &(combined_ciphertext - x_g),
+            x_g,
+            context,
+        )
+    }
+
+    /// Decrypt and return the discrete log of `x_g`. This assumes that [VerifiableEncryption::verify]
+    /// has been called, but the result is checked against `x_g` anyway.
+    pub fn decrypt(
+        &self,
+        sk: &PrivateKey,
+        x_g: &RistrettoPoint,
+    ) -> Result<RistrettoScalar, FastCryptoError> {
+        let mut x = RistrettoScalar::zero();
+        let mut power_of_two = RistrettoScalar::generator();
+        for bit in &self.bits {
+            let plaintext = bit.ciphertext - bit.ephemeral_key * sk.0;
+            if plaintext == RistrettoPoint::generator() {
+                x = x + power_of_two;
+            } else if plaintext != RistrettoPoint::zero() {
+                return Err(FastCryptoError::GeneralOpaqueError);
+            }
+            power_of_two = power_of_two + power_of_two;
+        }
+        if RistrettoPoint::mul_generator(&x) != *x_g {
+            return Err(FastCryptoError::GeneralOpaqueError);
+        }
+        Ok(x)
+    }
+}
+
+/// Compute `(sum 2^i R_i, sum 2^i C_i)`.
+fn combine(bits: &[BitEncryption]) -> (RistrettoPoint, RistrettoPoint) {
+    // Horner's method starting from the most significant bit.
+    bits.iter().rev().fold(
+        (RistrettoPoint::zero(), RistrettoPoint::zero()),
+        |(r, c), bit| (r + r + bit.ephemeral_key, c + c + bit.ciphertext),
+    )
+}
+
+/// Hash the given points into a challenge.
+fn fiat_shamir_challenge(
+    context: &[u8],
+    index: u64,
+    points: &[&RistrettoPoint],
+) -> RistrettoScalar {
+    let mut hash = Sha512::default();
+    hash.update(DST);
+    hash.update((context.len() as u64).to_le_bytes());
+    hash.update(context);
+    hash.update(index.to_le_bytes());
+    points.iter().for_each(|p| hash.update(p.compress()));
+    RistrettoScalar::from_bytes_mod_order_wide(&hash.finalize().digest)
+}
+
+impl BitEncryption {
+    fn encrypt<R: AllowedRng>(
+        pk: &PublicKey,
+        bit: bool,
+        r: &RistrettoScalar,
+        context: &[u8],
+        index: usize,
+        rng: &mut R,
+    ) -> Self {
+        let g = RistrettoPoint::generator();
+        let ephemeral_key = g * r;
+        let ciphertext = match bit {
+            true => g + pk.0 * r,
+            false => pk.0 * r,
+        };
+
+        // The statements are "ciphertext - b * G = r * P" for b = 0, 1.
+        let d0 = ciphertext;
+        let d1 = ciphertext - g;
+
+        // Simulate the branch we cannot prove and prove the real one.
+        let k = RistrettoScalar::rand(rng);
+        let c_simulated = RistrettoScalar::rand(rng);
+        let s_simulated = RistrettoScalar::rand(rng);
+        let (a0, b0, a1, b1) = if bit {
+            (
+                g * s_simulated - ephemeral_key * c_simulated,
+                pk.0 * s_simulated - d0 * c_simulated,
+                g * k,
+                pk.0 * k,
+            )
+        } else {
+            (
+                g * k,
+                pk.0 * k,
+                g * s_simulated - ephemeral_key * c_simulated,
+                pk.0 * s_simulated - d1 * c_simulated,
+            )
+        };
+        let c = fiat_shamir_challenge(
+            context,
+            index as u64,
+            &[&pk.0, &ephemeral_key, &ciphertext, &a0, &b0, &a1, &b1],
+        );
+        let c_real = c - c_simulated;
+        let s_real = k + c_real * r;
+        let proof = if bit {
+            BitProof {
+                c0: c_simulated,
+                c1: c_real,
+                s0: s_simulated,
+                s1: s_real,
+            }
+        } else {
+            BitProof {
+                c0: c_real,
+                c1: c_simulated,
+                s0: s_real,
+                s1: s_simulated,
+            }
+        };
+
+        Self {
+            ephemeral_key,
+            ciphertext,
+            proof,
+        }
+    }
+
+    fn verify(&self, pk: &PublicKey, context: &[u8], index: usize) -> Result<(), FastCryptoError> {
+        let g = RistrettoPoint::generator();
+        let d0 = self.ciphertext;
+        let d1 = self.ciphertext - g;
+        let BitProof { c0, c1, s0, s1 } = &self.proof;
+
+        let a0 = g * s0 - self.ephemeral_key * c0;
+        let b0 = pk.0 * s0 - d0 * c0;
+        let a1 = g * s1 - self.ephemeral_key * c1;
+        let b1 = pk.0 * s1 - d1 * c1;
+
+        let c = fiat_shamir_challenge(
+            context,
+            index as u64,
+            &[
+                &pk.0,
+                &self.ephemeral_key,
+                &self.ciphertext,
+                &a0,
+                &b0,
+                &a1,
+                &b1,
+            ],
+        );
+        if *c0 + c1 != c {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        Ok(())
+    }
+}
+
+/// Index used for the challenge of the DLEQ proof. Bit proofs use indices 0..NUMBER_OF_BITS.
+const DLEQ_INDEX: u64 = u64::MAX;
+
+impl DleqProof {
+    fn create<R: AllowedRng>(
+        r: &RistrettoScalar,
+        pk: &PublicKey,
+        r_g: &RistrettoPoint,
+        r_pk: &RistrettoPoint,
+        x_g: &RistrettoPoint,
+        context: &[u8],
+        rng: &mut R,
+    ) -> Self {
+        let k = RistrettoScalar::rand(rng);
+        let commitment_g = RistrettoPoint::mul_generator(&k);
+        let commitment_pk = pk.0 * k;
+        let c = fiat_shamir_challenge(
+            context,
+            DLEQ_INDEX,
+            &[&pk.0, r_g, r_pk, x_g, &commitment_g, &commitment_pk],
+        );
+        Self {
+            commitment_g,
+            commitment_pk,
+            z: k + c * r,
+        }
+    }
+
+    fn verify(
+        &self,
+        pk: &PublicKey,
+        r_g: &RistrettoPoint,
+        r_pk: &RistrettoPoint,
+        x_g: &RistrettoPoint,
+        context: &[u8],
+    ) -> Result<(), FastCryptoError> {
+        let c = fiat_shamir_challenge(
+            context,
+            DLEQ_INDEX,
+            &[
+                &pk.0,
+                r_g,
+                r_pk,
+                x_g,
+                &self.commitment_g,
+                &self.commitment_pk,
+            ],
+        );
+        if RistrettoPoint::mul_generator(&self.z) != self.commitment_g + r_g * c
+            || pk.0 * self.z != self.commitment_pk + r_pk * c
+        {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A service running expensive verifications, e.g. of aggregated BLS signatures or of zk proofs in
+//! `fastcrypto-zkp`, on the blocking thread pool of tokio, so that async services do not starve
+//! their executors while verifying.
+//!
+//! The inputs are moved into the verification, which is why the methods of [VerificationService]
+//! take owned values. The number of verifications running at the same time is bounded, and further
+//! verifications wait for one of them to finish.
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::ed25519::Ed25519KeyPair;
+//! # use fastcrypto::traits::{KeyPair, Signer};
+//! # use fastcrypto::verification_service::VerificationService;
+//! # use rand::thread_rng;
+//! # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+//! let kp = Ed25519KeyPair::generate(&mut thread_rng());
+//! let signature = kp.sign(b"Hello, world!");
+//!
+//! let service = VerificationService::new(4);
+//! assert!(service
+//!     .verify_signature(kp.public().clone(), b"Hello, world!".to_vec(), signature)
+//!     .await
+//!     .is_ok());
+//! # });
+//! ```
+
+use crate::error::{FastCryptoError, FastCryptoResult};
+use crate::traits::{AggregateAuthenticator, VerifyingKey};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+/// Runs verifications on the blocking thread pool of tokio, at most a given number at a time.
+#[derive(Clone, Debug)]
+pub struct VerificationService {
+    permits: Arc<Semaphore>,
+    max_concurrent_verifications: usize,
+}
+
+impl VerificationService {
+    /// A service running at most `max_concurrent_verifications` verifications at the same time,
+    /// which is at least one.
+    pub fn new(max_concurrent_verifications: usize) -> Self {
+        let max_concurrent_verifications = max_concurrent_verifications.max(1);
+        Self {
+            permits: Arc::new(Semaphore::new(max_concurrent_verifications)),
+            max_concurrent_verifications,
+        }
+    }
+
+    pub fn max_concurrent_verifications(&self) -> usize {
+        self.max_concurrent_verifications
+    }
+
+    /// The number of verifications which may start without waiting.
+    pub fn available_permits(&self) -> usize {
+        self.permits.available_permits()
+    }
+
+    /// Run a verification on the blocking thread pool and return its result. Fails with
+    /// [FastCryptoError::GeneralError] if the verification panics.
+    ///
+    /// This must be called from within a tokio runtime.
+    pub async fn verify<T, F>(&self, verification: F) -> FastCryptoResult<T>
+    where
+        F: FnOnce() -> FastCryptoResult<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let _permit = self
+            .permits
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("The semaphore is never closed");
+        tokio::task::spawn_blocking(verification)
+            .await
+            .map_err(|_| FastCryptoError::GeneralError("Verification task failed".to_string()))?
+    }
+
+    /// Verify a signature over a message.
+    pub async fn verify_signature<K: VerifyingKey>(
+        &self,
+        public_key: K,
+        message: Vec<u8>,
+        signature: K::Sig,
+    ) -> FastCryptoResult<()> {
+        self.verify(move || public_key.verify(&message, &signature))
+            .await
+    }
+
+    /// Verify an aggregate of signatures over the same message, see
+    /// [AggregateAuthenticator::verify].
+    pub async fn verify_aggregate<A: AggregateAuthenticator>(
+        &self,
+        aggregate: A,
+        public_keys: Vec<A::PubKey>,
+        message: Vec<u8>,
+    ) -> FastCryptoResult<()> {
+        self.verify(move || aggregate.verify(&public_keys, &message))
+            .await
+    }
+
+    /// Verify a batch of aggregates, where the `i`'th aggregate is over `messages[i]` by
+    /// `public_keys[i]`, see [AggregateAuthenticator::batch_verify].
+    pub async fn batch_verify<A: AggregateAuthenticator>(
+        &self,
+        aggregates: Vec<A>,
+        public_keys: Vec<Vec<A::PubKey>>,
+        messages: Vec<Vec<u8>>,
+    ) -> FastCryptoResult<()> {
+        self.verify(move || {
+            let aggregates = aggregates.iter().collect::<Vec<_>>();
+            let messages = messages.iter().map(Vec::as_slice).collect::<Vec<_>>();
+            A::batch_verify(
+                &aggregates,
+                public_keys.iter().map(|pks| pks.iter()),
+                &messages,
+            )
+        })
+        .await
+    }
+}
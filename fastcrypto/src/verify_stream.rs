@@ -0,0 +1,251 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verification of signatures over messages which are too large to be kept in memory, e.g.
+//! gigabyte-scale artifacts read from disk or the network.
+//!
+//! Only schemes which sign a digest of the message can be verified incrementally: A
+//! [StreamVerifier] hashes the chunks of the message as they are given, and the signature is
+//! verified against the digest when the stream is finalized. The streams are created with
+//! [VerifyStream::verify_stream] from a public key, and are implemented for
+//! * Ed25519ph with SHA-512 (not plain Ed25519, whose signatures hash the message twice),
+//! * ECDSA over secp256k1 and secp256r1 with SHA-256, and
+//! * RSASSA-PKCS1-v1_5 with SHA-256 (requires the `rsa` feature).
+//!
+//! # Example
+//! ```rust
+//! # use fastcrypto::secp256r1::*;
+//! # use fastcrypto::traits::{KeyPair, Signer};
+//! # use fastcrypto::verify_stream::*;
+//! # use rand::thread_rng;
+//! let kp = Secp256r1KeyPair::generate(&mut thread_rng());
+//! let message = vec![7u8; 1 << 20];
+//! let signature = kp.sign(&message);
+//!
+//! let mut stream = kp.public().verify_stream();
+//! for chunk in message.chunks(4096) {
+//!     stream.update(chunk);
+//! }
+//! assert!(stream.finalize(&signature).is_ok());
+//!
+//! let stream = kp.public().verify_stream();
+//! assert!(stream.verify_reader(message.as_slice(), &signature).is_ok());
+//! ```
+
+use crate::ed25519::{Ed25519PublicKey, Ed25519Signature, ED25519PH_MAX_CONTEXT_LENGTH};
+use crate::error::{FastCryptoError, FastCryptoResult};
+#[cfg(feature = "rsa")]
+use crate::hash::Sha256;
+use crate::hash::{HashFunction, Sha512};
+#[cfg(feature = "rsa")]
+use crate::rsa::{RSAPublicKey, RSASignature};
+#[cfg(feature = "secp256k1")]
+use crate::secp256k1::{Secp256k1PublicKey, Secp256k1Signature};
+#[cfg(feature = "secp256r1")]
+use crate::secp256r1::{Secp256r1PublicKey, Secp256r1Signature};
+use std::io::Read;
+
+/// The size of the chunks read by [StreamVerifier::verify_reader].
+pub const READ_CHUNK_SIZE: usize = 1 << 16;
+
+/// A public key which can verify signatures over a message given in chunks.
+pub trait VerifyStream {
+    type Sig;
+    type Stream: StreamVerifier<Sig = Self::Sig>;
+
+    /// Start the verification of a signature by this key over a message given in chunks.
+    fn verify_stream(&self) -> Self::Stream;
+}
+
+/// The incremental verification of a signature over a message given in chunks.
+pub trait StreamVerifier: Sized {
+    type Sig;
+
+    /// Hash the next chunk of the message.
+    fn update(&mut self, chunk: &[u8]);
+
+    /// Verify the signature over the concatenation of all chunks given so far.
+    fn finalize(self, signature: &Self::Sig) -> FastCryptoResult<()>;
+
+    /// Read the rest of the message from the given reader and verify the signature. Fails if
+    /// reading fails.
+    fn verify_reader<R: Read>(
+        mut self,
+        mut reader: R,
+        signature: &Self::Sig,
+    ) -> FastCryptoResult<()> {
+        let mut buffer = vec![0u8; READ_CHUNK_SIZE];
+        loop {
+            match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(n) => self.update(&buffer[..n]),
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(FastCryptoError::GeneralError(e.to_string())),
+            }
+        }
+        self.finalize(signature)
+    }
+}
+
+/// A public key which verifies signatures over a 32 byte digest of the message.
+pub trait VerifyPrehash {
+    type Sig;
+
+    /// Verify a signature over the given digest.
+    fn verify_prehash(&self, digest: &[u8; 32], signature: &Self::Sig) -> FastCryptoResult<()>;
+}
+
+/// A [StreamVerifier] for schemes which sign the digest of the message by the hash function `H`.
+pub struct PrehashStream<K, H> {
+    key: K,
+    hasher: H,
+}
+
+impl<K: VerifyPrehash, H: HashFunction<32>> PrehashStream<K, H> {
+    pub fn new(key: K) -> Self {
+        Self {
+            key,
+            hasher: H::new(),
+        }
+    }
+}
+
+impl<K: VerifyPrehash, H: HashFunction<32>> StreamVerifier for PrehashStream<K, H> {
+    type Sig = K::Sig;
+
+    fn update(&mut self, chunk: &[u8]) {
+        self.hasher.update(chunk);
+    }
+
+    fn finalize(self, signature: &K::Sig) -> FastCryptoResult<()> {
+        self.key
+            .verify_prehash(&self.hasher.finalize().digest, signature)
+    }
+}
+
+#[cfg(feature = "secp256k1")]
+impl VerifyPrehash for Secp256k1PublicKey {
+    type Sig = Secp256k1Signature;
+
+    fn verify_prehash(
+        &self,
+        digest: &[u8; 32],
+        signature: &Secp256k1Signature,
+    ) -> FastCryptoResult<()> {
+        Secp256k1PublicKey::verify_prehash(self, digest, signature)
+    }
+}
+
+#[cfg(feature = "secp256k1")]
+impl VerifyStream for Secp256k1PublicKey {
+    type Sig = Secp256k1Signature;
+    type Stream = PrehashStream<Self, crate::secp256k1::DefaultHash>;
+
+    fn verify_stream(&self) -> Self::Stream {
+        PrehashStream::new(self.clone())
+    }
+}
+
+#[cfg(feature = "secp256r1")]
+impl VerifyPrehash for Secp256r1PublicKey {
+    type Sig = Secp256r1Signature;
+
+    fn verify_prehash(
+        &self,
+        digest: &[u8; 32],
+        signature: &Secp256r1Signature,
+    ) -> FastCryptoResult<()> {
+        Secp256r1PublicKey::verify_prehash(self, digest, signature)
+    }
+}
+
+#[cfg(feature = "secp256r1")]
+impl VerifyStream for Secp256r1PublicKey {
+    type Sig = Secp256r1Signature;
+    type Stream = PrehashStream<Self, crate::secp256r1::DefaultHash>;
+
+    fn verify_stream(&self) -> Self::Stream {
+        PrehashStream::new(self.clone())
+    }
+}
+
+#[cfg(feature = "rsa")]
+impl VerifyPrehash for RSAPublicKey {
+    type Sig = RSASignature;
+
+    fn verify_prehash(&self, digest: &[u8; 32], signature: &RSASignature) -> FastCryptoResult<()> {
+        RSAPublicKey::verify_prehash(self, digest, signature)
+    }
+}
+
+#[cfg(feature = "rsa")]
+impl VerifyStream for RSAPublicKey {
+    type Sig = RSASignature;
+    type Stream = PrehashStream<Self, Sha256>;
+
+    fn verify_stream(&self) -> Self::Stream {
+        PrehashStream::new(self.clone())
+    }
+}
+
+/// A [StreamVerifier] for Ed25519ph signatures. See [Ed25519PublicKey::verify_prehashed].
+pub struct Ed25519phStream {
+    key: Ed25519PublicKey,
+    context: Vec<u8>,
+    hasher: Sha512,
+}
+
+impl Ed25519phStream {
+    /// Start the verification of an Ed25519ph signature with the given context, which must be at
+    /// most [ED25519PH_MAX_CONTEXT_LENGTH] bytes.
+    pub fn with_context(key: &Ed25519PublicKey, context: &[u8]) -> FastCryptoResult<Self> {
+        if context.len() > ED25519PH_MAX_CONTEXT_LENGTH {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(Self {
+            key: key.clone(),
+            context: context.to_vec(),
+            hasher: Sha512::new(),
+        })
+    }
+}
+
+impl StreamVerifier for Ed25519phStream {
+    type Sig = Ed25519Signature;
+
+    fn update(&mut self, chunk: &[u8]) {
+        self.hasher.update(chunk);
+    }
+
+    fn finalize(self, signature: &Ed25519Signature) -> FastCryptoResult<()> {
+        self.key
+            .verify_prehashed(&self.hasher.finalize().digest, &self.context, signature)
+    }
+}
+
+impl VerifyStream for Ed25519PublicKey {
+    type Sig = Ed25519Signature;
+    type Stream = Ed25519phStream;
+
+    /// Start the verification of an Ed25519ph signature with an empty context.
+    fn verify_stream(&self) -> Ed25519phStream {
+        Ed25519phStream::with_context(self, &[]).expect("The empty context is valid")
+    }
+}
+
+/// Hash the message read from the given reader. Fails if reading fails.
+pub(crate) fn hash_reader<H: HashFunction<N>, const N: usize, R: Read>(
+    mut reader: R,
+) -> FastCryptoResult<[u8; N]> {
+    let mut hash = H::new();
+    let mut buffer = vec![0u8; READ_CHUNK_SIZE];
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => hash.update(&buffer[..n]),
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(FastCryptoError::GeneralError(e.to_string())),
+        }
+    }
+    Ok(hash.finalize().digest)
+}
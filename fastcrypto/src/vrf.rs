@@ -65,7 +65,7 @@ pub trait VRFProof<const OUTPUT_SIZE: usize> {
 pub mod ecvrf {
     use crate::error::FastCryptoError;
     use crate::groups::ristretto255::{RistrettoPoint, RistrettoScalar};
-    use crate::groups::{GroupElement, Scalar};
+    use crate::groups::{FixedBaseMultiplication, GroupElement, Scalar};
     use crate::hash::{HashFunction, ReverseWrapper, Sha512};
     use crate::serde_helpers::ToFromByteArray;
     use crate::traits::AllowedRng;
@@ -87,7 +87,7 @@ pub mod ecvrf {
     /// Domain separation tag used in ecvrf_encode_to_curve (see also draft-irtf-cfrg-hash-to-curve-16)
     const DST: &[u8; 49] = b"ECVRF_ristretto255_XMD:SHA-512_R255MAP_RO_sui_vrf";
 
-    #[derive(Serialize, Deserialize, PartialEq, Eq, Debug)]
+    #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
     pub struct ECVRFPublicKey(RistrettoPoint);
 
     impl VRFPublicKey for ECVRFPublicKey {
@@ -202,7 +202,7 @@ pub mod ecvrf {
                 &self.pk.0,
                 &h,
                 &gamma,
-                &(RistrettoPoint::generator() * k),
+                &(RistrettoPoint::mul_generator(&k)),
                 &(h * k),
             ]);
             let s = k + RistrettoScalar::from(&c) * self.sk.0;
@@ -213,7 +213,7 @@ pub mod ecvrf {
 
     impl From<ECVRFPrivateKey> for ECVRFKeyPair {
         fn from(sk: ECVRFPrivateKey) -> Self {
-            let p = RistrettoPoint::generator() * sk.0;
+            let p = RistrettoPoint::mul_generator(&sk.0);
             ECVRFKeyPair {
                 pk: ECVRFPublicKey(p),
                 sk,
@@ -0,0 +1,110 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Checks that verifying signatures does not allocate on the heap, which matters for embedders
+//! with custom allocators or strict latency requirements. This is an integration test because it
+//! replaces the global allocator with one counting the allocations of each thread.
+//!
+//! The blst verification functions allocate a pairing context internally, so for BLS we only
+//! check that the number of allocations does not depend on the number of public keys.
+
+use fastcrypto::bls12381::min_sig::{BLS12381AggregateSignature, BLS12381KeyPair};
+use fastcrypto::ed25519::Ed25519KeyPair;
+use fastcrypto::secp256k1::Secp256k1KeyPair;
+use fastcrypto::secp256r1::Secp256r1KeyPair;
+use fastcrypto::traits::{AggregateAuthenticator, KeyPair, Signer, VerifyingKey};
+use rand::{rngs::StdRng, SeedableRng};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Run `f` and return its result and the number of allocations made by this thread while running
+/// it.
+fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(|count| count.get());
+    let result = f();
+    let after = ALLOCATIONS.with(|count| count.get());
+    (result, after - before)
+}
+
+fn assert_verify_does_not_allocate<K: KeyPair>() {
+    let key_pair = K::generate(&mut StdRng::from_seed([0; 32]));
+    let message = b"Hello, world!";
+    let signature = key_pair.sign(message);
+
+    // The first verification may initialize lazy statics such as precomputed tables.
+    assert!(key_pair.public().verify(message, &signature).is_ok());
+
+    let (result, allocations) = count_allocations(|| key_pair.public().verify(message, &signature));
+    assert!(result.is_ok());
+    assert_eq!(allocations, 0);
+
+    let (result, allocations) =
+        count_allocations(|| key_pair.public().verify(b"Other message", &signature));
+    assert!(result.is_err());
+    assert_eq!(allocations, 0);
+}
+
+#[test]
+fn test_ed25519_verify_does_not_allocate() {
+    assert_verify_does_not_allocate::<Ed25519KeyPair>();
+}
+
+#[test]
+fn test_secp256k1_verify_does_not_allocate() {
+    assert_verify_does_not_allocate::<Secp256k1KeyPair>();
+}
+
+#[test]
+fn test_secp256r1_verify_does_not_allocate() {
+    assert_verify_does_not_allocate::<Secp256r1KeyPair>();
+}
+
+#[test]
+fn test_bls12381_aggregate_verify_allocations_do_not_grow() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let message = b"Hello, world!";
+    let key_pairs = (0..16)
+        .map(|_| BLS12381KeyPair::generate(&mut rng))
+        .collect::<Vec<_>>();
+    let public_keys = key_pairs
+        .iter()
+        .map(|kp| kp.public().clone())
+        .collect::<Vec<_>>();
+
+    let allocations = [1, 16]
+        .into_iter()
+        .map(|n| {
+            let signatures = key_pairs[..n]
+                .iter()
+                .map(|kp| kp.sign(message))
+                .collect::<Vec<_>>();
+            let aggregate = BLS12381AggregateSignature::aggregate(&signatures).unwrap();
+            assert!(aggregate.verify(&public_keys[..n], message).is_ok());
+            let (result, allocations) =
+                count_allocations(|| aggregate.verify(&public_keys[..n], message));
+            assert!(result.is_ok());
+            allocations
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(allocations[0], allocations[1]);
+}
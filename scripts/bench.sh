@@ -0,0 +1,32 @@
+#!/bin/bash
+# Copyright (c) 2022, Mysten Labs, Inc.
+# SPDX-License-Identifier: Apache-2.0
+#
+# Run the benchmarks of fastcrypto and fastcrypto-zkp.
+#
+# Usage: scripts/bench.sh [--json <output file>]
+#
+# With --json, the results are written to the given file in the JSON message format of
+# cargo-criterion (one message per line), which requires `cargo install cargo-criterion`. The
+# output can be compared to an earlier run using scripts/compare_benchmarks.py.
+set -e
+set -o pipefail
+
+DIR="$( cd "$( dirname "${BASH_SOURCE[0]}" )" && pwd )"
+TOPLEVEL="${DIR}/.."
+
+if [ "$#" -eq 0 ]; then
+  (cd "$TOPLEVEL/fastcrypto" && cargo bench --features experimental)
+  (cd "$TOPLEVEL/fastcrypto-zkp" && cargo bench)
+  exit 0
+fi
+
+if [ "$1" != "--json" ] || [ "$#" -ne 2 ]; then
+  echo "Usage: $0 [--json <output file>]"
+  exit 1
+fi
+
+OUTPUT="$(realpath "$2")"
+(cd "$TOPLEVEL/fastcrypto" && cargo criterion --message-format=json --features experimental) | grep '"reason":"benchmark-complete"' > "$OUTPUT"
+(cd "$TOPLEVEL/fastcrypto-zkp" && cargo criterion --message-format=json) | grep '"reason":"benchmark-complete"' >> "$OUTPUT"
+echo "Wrote $(wc -l < "$OUTPUT") benchmark results to $OUTPUT"
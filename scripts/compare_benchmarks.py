@@ -0,0 +1,75 @@
+#!/usr/bin/env python3
+# Copyright (c) 2022, Mysten Labs, Inc.
+# SPDX-License-Identifier: Apache-2.0
+#
+# Compare two benchmark runs in the JSON message format of cargo-criterion, e.g. as created by
+# scripts/bench.sh --json, and fail if any benchmark is slower than the baseline by more than the
+# given threshold.
+#
+# Usage: scripts/compare_benchmarks.py [--threshold <percent>] <baseline> <current>
+
+import argparse
+import json
+import sys
+
+UNITS = {"ps": 1e-3, "ns": 1.0, "us": 1e3, "µs": 1e3, "ms": 1e6, "s": 1e9}
+
+
+def load(path):
+    """Return a map from benchmark id to the typical time in nanoseconds."""
+    results = {}
+    with open(path) as f:
+        for line in f:
+            line = line.strip()
+            if not line:
+                continue
+            message = json.loads(line)
+            if message.get("reason") != "benchmark-complete":
+                continue
+            typical = message["typical"]
+            results[message["id"]] = typical["estimate"] * UNITS[typical["unit"]]
+    return results
+
+
+def format_time(ns):
+    for unit, scale in [("s", 1e9), ("ms", 1e6), ("us", 1e3)]:
+        if ns >= scale:
+            return "%.2f %s" % (ns / scale, unit)
+    return "%.2f ns" % ns
+
+
+def main():
+    parser = argparse.ArgumentParser(description="Compare two cargo-criterion benchmark runs.")
+    parser.add_argument("--threshold", type=float, default=10.0,
+                        help="maximal allowed slowdown in percent (default: 10)")
+    parser.add_argument("baseline")
+    parser.add_argument("current")
+    args = parser.parse_args()
+
+    baseline = load(args.baseline)
+    current = load(args.current)
+
+    regressions = []
+    for id in sorted(current):
+        if id not in baseline:
+            print("%-70s %12s (new)" % (id, format_time(current[id])))
+            continue
+        change = (current[id] - baseline[id]) / baseline[id] * 100
+        marker = ""
+        if change > args.threshold:
+            marker = " REGRESSION"
+            regressions.append(id)
+        print("%-70s %12s %+7.1f%%%s" % (id, format_time(current[id]), change, marker))
+    for id in sorted(set(baseline) - set(current)):
+        print("%-70s %12s (removed)" % (id, "-"))
+
+    if regressions:
+        print("\n%d benchmark(s) regressed by more than %.1f%%:" % (len(regressions), args.threshold))
+        for id in regressions:
+            print("  " + id)
+        sys.exit(1)
+    print("\nNo benchmark regressed by more than %.1f%%." % args.threshold)
+
+
+if __name__ == "__main__":
+    main()